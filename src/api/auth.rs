@@ -0,0 +1,376 @@
+//! Authenticated actions against the Hacker News website.
+//!
+//! The public Firebase API is read-only, so voting and favoriting go through the
+//! regular `news.ycombinator.com` HTML endpoints: log in with a form post to get
+//! the `user` session cookie, then scrape the per-item `auth` token (HN's CSRF
+//! token) from the item page before following the vote/fave link.
+
+use anyhow::{Context, Result};
+use reqwest::Client;
+use reqwest::header::{COOKIE, SET_COOKIE};
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+const HN_WEB_BASE_URL: &str = "https://news.ycombinator.com/";
+
+/// Environment variables used to log in when no saved session exists.
+pub const USERNAME_ENV: &str = "HN_USERNAME";
+pub const PASSWORD_ENV: &str = "HN_PASSWORD";
+
+/// A logged-in HN session, persisted so the password is only needed once.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub username: String,
+    /// Value of the `user` cookie
+    pub cookie: String,
+}
+
+impl Session {
+    fn file_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("tui-hn-app").join("session.json"))
+    }
+
+    /// Load the saved session, if any. A session file readable by others, as
+    /// earlier versions wrote it, is made private.
+    pub fn load() -> Option<Self> {
+        let path = Self::file_path()?;
+        let content = fs::read_to_string(&path).ok()?;
+        if let Err(e) = make_private(&path) {
+            tracing::warn!("Failed to restrict {}: {:#}", path.display(), e);
+        }
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::file_path().context("Could not find config directory")?;
+        self.save_to(&path)
+    }
+
+    /// Write the session readable by the user only, since the cookie logs into
+    /// their account.
+    fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).context("Failed to create config directory")?;
+        }
+        let content = serde_json::to_string_pretty(self).context("Failed to serialize session")?;
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(path).context("Failed to write session file")?;
+        // The mode only applies to new files
+        make_private(path)?;
+        std::io::Write::write_all(&mut file, content.as_bytes())
+            .context("Failed to write session file")
+    }
+
+    /// Remove the saved session (e.g. after HN rejected the cookie).
+    pub fn delete() -> Result<()> {
+        match Self::file_path() {
+            Some(path) if path.exists() => {
+                fs::remove_file(path).context("Failed to remove session file")
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Item-level actions that need a logged-in session.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ItemAction {
+    Upvote,
+    Favorite,
+}
+
+impl ItemAction {
+    fn path(&self) -> &'static str {
+        match self {
+            Self::Upvote => "vote",
+            Self::Favorite => "fave",
+        }
+    }
+
+    fn verb(&self) -> &'static str {
+        match self {
+            Self::Upvote => "upvote",
+            Self::Favorite => "favorite",
+        }
+    }
+
+    pub fn past_tense(&self) -> &'static str {
+        match self {
+            Self::Upvote => "Upvoted",
+            Self::Favorite => "Favorited",
+        }
+    }
+}
+
+/// Client for the HN website endpoints that require a session.
+#[derive(Clone)]
+pub struct AuthClient {
+    client: Client,
+    base_url: String,
+    session: Arc<Mutex<Option<Session>>>,
+}
+
+impl Default for AuthClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AuthClient {
+    /// Create a client against news.ycombinator.com using the saved session, if any.
+    pub fn new() -> Self {
+        let mut client = Self::with_base_url(HN_WEB_BASE_URL.to_string());
+        client.session = Arc::new(Mutex::new(Session::load()));
+        client
+    }
+
+    /// Helper to create a client with a custom base URL and no session (for testing).
+    pub fn with_base_url(base_url: String) -> Self {
+        // Redirects are not followed: the login response carries the session cookie
+        // on the 302 itself.
+        let client = Client::builder()
+            .user_agent(super::USER_AGENT)
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap_or_default();
+        Self {
+            client,
+            base_url,
+            session: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn session(&self) -> Option<Session> {
+        self.session.lock().ok().and_then(|s| s.clone())
+    }
+
+    fn set_session(&self, session: Option<Session>) {
+        if let Ok(mut guard) = self.session.lock() {
+            *guard = session;
+        }
+    }
+
+    /// Log in with username and password, returning the new session.
+    pub async fn login(&self, username: &str, password: &str) -> Result<Session> {
+        let response = self
+            .client
+            .post(format!("{}login", self.base_url))
+            .form(&[("acct", username), ("pw", password), ("goto", "news")])
+            .send()
+            .await
+            .context("Failed to send login request")?;
+
+        let cookie = response
+            .headers()
+            .get_all(SET_COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .find_map(parse_user_cookie)
+            .context("Login failed: check username and password")?;
+
+        let session = Session {
+            username: username.to_string(),
+            cookie,
+        };
+        self.set_session(Some(session.clone()));
+        Ok(session)
+    }
+
+    /// Return the current session, logging in with `HN_USERNAME`/`HN_PASSWORD`
+    /// (and saving the session) when none exists yet.
+    pub async fn ensure_session(&self) -> Result<Session> {
+        if let Some(session) = self.session() {
+            return Ok(session);
+        }
+
+        match (std::env::var(USERNAME_ENV), std::env::var(PASSWORD_ENV)) {
+            (Ok(username), Ok(password)) => {
+                let session = self.login(&username, &password).await?;
+                if let Err(e) = session.save() {
+                    tracing::warn!(%e, "Failed to save HN session");
+                }
+                Ok(session)
+            }
+            _ => Err(anyhow::anyhow!(
+                "Not logged in: set {} and {} to enable voting",
+                USERNAME_ENV,
+                PASSWORD_ENV
+            )),
+        }
+    }
+
+    /// Upvote or favorite a story or comment.
+    pub async fn perform(&self, action: ItemAction, id: u32) -> Result<()> {
+        let session = self.ensure_session().await?;
+        let cookie = format!("user={}", session.cookie);
+
+        let page = self
+            .client
+            .get(format!("{}item", self.base_url))
+            .query(&[("id", id.to_string())])
+            .header(COOKIE, &cookie)
+            .send()
+            .await
+            .context("Failed to fetch item page")?
+            .text()
+            .await
+            .context("Failed to read item page")?;
+
+        let auth = match (action, extract_auth_token(&page, action, id)) {
+            (_, Some(auth)) => auth,
+            (ItemAction::Upvote, None) if has_unvote_link(&page, id) => {
+                return Err(anyhow::anyhow!("Already upvoted"));
+            }
+            (_, None) if !is_logged_in_page(&page) => {
+                // HN no longer accepts the cookie; forget it so the next attempt logs in again
+                self.set_session(None);
+                let _ = Session::delete();
+                return Err(anyhow::anyhow!("HN session expired, please retry"));
+            }
+            (_, None) => {
+                return Err(anyhow::anyhow!("Cannot {} this item", action.verb()));
+            }
+        };
+
+        let mut query = vec![("id", id.to_string()), ("auth", auth)];
+        if action == ItemAction::Upvote {
+            query.push(("how", "up".to_string()));
+        }
+
+        let response = self
+            .client
+            .get(format!("{}{}", self.base_url, action.path()))
+            .query(&query)
+            .header(COOKIE, &cookie)
+            .send()
+            .await
+            .context("Failed to send request")?;
+
+        match response.status().is_success() || response.status().is_redirection() {
+            true => Ok(()),
+            false => Err(anyhow::anyhow!("HN returned {}", response.status())),
+        }
+    }
+}
+
+/// Extract the `user` cookie value from a `Set-Cookie` header.
+fn parse_user_cookie(header: &str) -> Option<String> {
+    let pair = header.split(';').next()?.trim();
+    match pair.split_once('=') {
+        Some(("user", value)) if !value.is_empty() => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// Find the `auth` token on the vote/fave link for `id` in an item page.
+pub fn extract_auth_token(html: &str, action: ItemAction, id: u32) -> Option<String> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("a[href]").ok()?;
+    let prefix = format!("{}?id={}&", action.path(), id);
+
+    document
+        .select(&selector)
+        .filter_map(|a| a.value().attr("href"))
+        .filter(|href| href.starts_with(&prefix))
+        // Skip "unvote"/"un-favorite" links
+        .filter(|href| !href.contains("how=un") && !href.contains("un=t"))
+        .find_map(|href| {
+            href.split(['?', '&'])
+                .find_map(|param| match param.split_once('=') {
+                    Some(("auth", value)) => Some(value.to_string()),
+                    _ => None,
+                })
+        })
+}
+
+fn has_unvote_link(html: &str, id: u32) -> bool {
+    html.contains(&format!("vote?id={}&amp;how=un", id))
+        || html.contains(&format!("vote?id={}&how=un", id))
+}
+
+/// Logged-in pages link to `logout`; anonymous pages link to `login`.
+fn is_logged_in_page(html: &str) -> bool {
+    html.contains("href=\"logout") || html.contains("id=\"logout\"")
+}
+
+/// Restrict the file at `path` to its owner (mode 0600); nothing to do elsewhere
+/// than on Unix.
+fn make_private(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+            .context("Failed to restrict session file permissions")?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn session_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("hn-session-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.json");
+        // An existing world-readable file is tightened too
+        fs::write(&path, "{}").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        let session = Session {
+            username: "pg".to_string(),
+            cookie: "pg&secret".to_string(),
+        };
+        session.save_to(&path).unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert!(fs::read_to_string(&path).unwrap().contains("pg&secret"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    const ITEM_PAGE: &str = r#"<html><body>
+        <a id="me" href="user?id=pg">pg</a> | <a id="logout" href="logout?auth=x&amp;goto=news">logout</a>
+        <a id="up_42" href="vote?id=42&amp;how=up&amp;auth=abc123&amp;goto=item%3Fid%3D42">up</a>
+        <a href="fave?id=42&amp;auth=fav456">favorite</a>
+    </body></html>"#;
+
+    #[test]
+    fn parses_user_cookie() {
+        assert_eq!(
+            parse_user_cookie("user=pg&hash; Path=/; Secure").as_deref(),
+            Some("pg&hash")
+        );
+        assert_eq!(parse_user_cookie("other=1; Path=/"), None);
+        assert_eq!(parse_user_cookie("user=; Path=/"), None);
+    }
+
+    #[test]
+    fn extracts_vote_and_fave_tokens() {
+        assert_eq!(
+            extract_auth_token(ITEM_PAGE, ItemAction::Upvote, 42).as_deref(),
+            Some("abc123")
+        );
+        assert_eq!(
+            extract_auth_token(ITEM_PAGE, ItemAction::Favorite, 42).as_deref(),
+            Some("fav456")
+        );
+        assert_eq!(extract_auth_token(ITEM_PAGE, ItemAction::Upvote, 7), None);
+    }
+
+    #[test]
+    fn detects_logged_in_pages() {
+        assert!(is_logged_in_page(ITEM_PAGE));
+        assert!(!is_logged_in_page(r#"<a href="login?goto=news">login</a>"#));
+    }
+}
@@ -0,0 +1,241 @@
+//! Circuit breaker for the HN API: after repeated failures requests fail fast for a
+//! cooldown, then a single probe decides whether the API is back.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Longest cooldown after repeated failed probes.
+const MAX_COOLDOWN: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Closed {
+        failures: u32,
+    },
+    Open {
+        until: Instant,
+        cooldown: Duration,
+    },
+    /// The cooldown elapsed and one probe request is in flight. A probe that has
+    /// not reported by `deadline` (its caller was dropped mid-request) is given up
+    /// and the next caller probes instead.
+    HalfOpen {
+        cooldown: Duration,
+        deadline: Instant,
+    },
+}
+
+/// What the status bar shows about the API.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ApiHealth {
+    Healthy,
+    /// Requests fail fast; the next probe is due in this long
+    Degraded {
+        retry_in: Duration,
+    },
+    /// A probe request is checking whether the API recovered
+    Probing,
+}
+
+/// Returned instead of sending a request while the circuit is open.
+#[derive(Debug)]
+pub struct CircuitOpen;
+
+impl std::fmt::Display for CircuitOpen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "HN API degraded: requests are paused after repeated failures"
+        )
+    }
+}
+
+impl std::error::Error for CircuitOpen {}
+
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    state: Mutex<State>,
+    /// Consecutive failures that open the circuit; 0 disables the breaker
+    threshold: u32,
+    cooldown: Duration,
+}
+
+impl CircuitBreaker {
+    pub fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            state: Mutex::new(State::Closed { failures: 0 }),
+            threshold,
+            cooldown,
+        }
+    }
+
+    /// Whether a request may go out now. Once the cooldown has passed, the first
+    /// caller becomes the probe and everyone else keeps failing fast until it reports,
+    /// or for another cooldown if it never does.
+    pub fn allow(&self) -> bool {
+        self.allow_at(Instant::now())
+    }
+
+    fn allow_at(&self, now: Instant) -> bool {
+        let Ok(mut state) = self.state.lock() else {
+            return true;
+        };
+        match *state {
+            State::Closed { .. } => true,
+            State::Open { until, cooldown } if now >= until => {
+                *state = State::HalfOpen {
+                    cooldown,
+                    deadline: now + cooldown,
+                };
+                true
+            }
+            State::HalfOpen { cooldown, deadline } if now >= deadline => {
+                *state = State::HalfOpen {
+                    cooldown,
+                    deadline: now + cooldown,
+                };
+                true
+            }
+            State::Open { .. } | State::HalfOpen { .. } => false,
+        }
+    }
+
+    pub fn record_success(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            *state = State::Closed { failures: 0 };
+        }
+    }
+
+    /// Count a failed request. Returns true when this failure opened the circuit.
+    pub fn record_failure(&self) -> bool {
+        self.record_failure_at(Instant::now())
+    }
+
+    fn record_failure_at(&self, now: Instant) -> bool {
+        let Ok(mut state) = self.state.lock() else {
+            return false;
+        };
+        match *state {
+            _ if self.threshold == 0 => false,
+            State::Closed { failures } if failures + 1 >= self.threshold => {
+                *state = State::Open {
+                    until: now + self.cooldown,
+                    cooldown: self.cooldown,
+                };
+                true
+            }
+            State::Closed { failures } => {
+                *state = State::Closed {
+                    failures: failures + 1,
+                };
+                false
+            }
+            // The probe failed: back off further before the next one
+            State::HalfOpen { cooldown, .. } => {
+                let cooldown = (cooldown * 2).min(MAX_COOLDOWN);
+                *state = State::Open {
+                    until: now + cooldown,
+                    cooldown,
+                };
+                true
+            }
+            State::Open { .. } => false,
+        }
+    }
+
+    /// Time until the next probe while the circuit is open.
+    pub fn retry_in(&self) -> Option<Duration> {
+        match self.state.lock().map(|state| *state) {
+            Ok(State::Open { until, .. }) => Some(until.saturating_duration_since(Instant::now())),
+            _ => None,
+        }
+    }
+
+    pub fn health(&self) -> ApiHealth {
+        match self.state.lock().map(|state| *state) {
+            Ok(State::Open { until, .. }) => ApiHealth::Degraded {
+                retry_in: until.saturating_duration_since(Instant::now()),
+            },
+            Ok(State::HalfOpen { .. }) => ApiHealth::Probing,
+            _ => ApiHealth::Healthy,
+        }
+    }
+}
+
+/// `delay` with "equal jitter": half of it fixed, the other half random, so clients
+/// that failed together don't retry in lockstep.
+pub fn jittered(delay: Duration) -> Duration {
+    use std::hash::{BuildHasher, Hasher};
+
+    // RandomState is seeded randomly per instance, which is plenty for jitter
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(Instant::now().elapsed().as_nanos());
+    let fraction = (hasher.finish() % 1000) as f64 / 1000.0;
+    delay / 2 + delay.mul_f64(fraction / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opens_after_threshold_and_probes_after_cooldown() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(10));
+        let now = Instant::now();
+
+        assert!(!breaker.record_failure_at(now));
+        breaker.record_success();
+        assert!(!breaker.record_failure_at(now));
+        assert!(!breaker.record_failure_at(now));
+        assert!(breaker.record_failure_at(now));
+        assert!(!breaker.allow_at(now + Duration::from_secs(5)));
+        assert!(matches!(breaker.health(), ApiHealth::Degraded { .. }));
+
+        // One probe after the cooldown, the rest keep failing fast
+        let later = now + Duration::from_secs(10);
+        assert!(breaker.allow_at(later));
+        assert!(!breaker.allow_at(later));
+        assert_eq!(breaker.health(), ApiHealth::Probing);
+
+        // A failed probe doubles the cooldown
+        assert!(breaker.record_failure_at(later));
+        assert!(!breaker.allow_at(later + Duration::from_secs(19)));
+        assert!(breaker.allow_at(later + Duration::from_secs(20)));
+        breaker.record_success();
+        assert_eq!(breaker.health(), ApiHealth::Healthy);
+        assert!(breaker.allow_at(later));
+    }
+
+    #[test]
+    fn lost_probe_is_replaced_after_a_cooldown() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(10));
+        let now = Instant::now();
+        assert!(breaker.record_failure_at(now));
+
+        // The probe's caller goes away without reporting
+        let probe = now + Duration::from_secs(10);
+        assert!(breaker.allow_at(probe));
+        assert!(!breaker.allow_at(probe + Duration::from_secs(9)));
+        assert!(breaker.allow_at(probe + Duration::from_secs(10)));
+        assert!(!breaker.allow_at(probe + Duration::from_secs(11)));
+        assert_eq!(breaker.health(), ApiHealth::Probing);
+    }
+
+    #[test]
+    fn zero_threshold_never_opens() {
+        let breaker = CircuitBreaker::new(0, Duration::from_secs(10));
+        for _ in 0..10 {
+            assert!(!breaker.record_failure());
+        }
+        assert!(breaker.allow());
+    }
+
+    #[test]
+    fn jitter_stays_within_half_to_full_delay() {
+        let delay = Duration::from_millis(800);
+        for _ in 0..50 {
+            let d = jittered(delay);
+            assert!(d >= delay / 2 && d <= delay, "{:?}", d);
+        }
+    }
+}
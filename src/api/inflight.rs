@@ -0,0 +1,180 @@
+//! Deduplication of in-flight requests: callers asking for the same URL, decoded as
+//! the same type, while a request for it runs share its response instead of
+//! fetching it again.
+//!
+//! An entry stays in the map until its last waiter is done, so a caller joining
+//! after the response arrived but before every earlier caller picked it up still
+//! shares it. Waiters that are dropped (cancelled tasks) count as done, so no entry
+//! outlives the callers that could drive it.
+
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use futures::future::{BoxFuture, FutureExt, Shared};
+use std::sync::Arc;
+
+/// Response body shared by every waiter of a request.
+type SharedBody = Shared<BoxFuture<'static, Result<Arc<String>, SharedError>>>;
+
+/// An error shared between waiters. It keeps the original error's chain, so
+/// `AppError` can still classify timeouts and connection failures.
+#[derive(Debug, Clone)]
+pub struct SharedError(Arc<anyhow::Error>);
+
+impl SharedError {
+    /// The original error, including a root cause that `source` cannot reach.
+    pub fn inner(&self) -> &anyhow::Error {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for SharedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&*self.0, f)
+    }
+}
+
+impl std::error::Error for SharedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+struct Inflight {
+    body: SharedBody,
+    waiters: usize,
+}
+
+/// What a request is for: the type its response is decoded into and its URL.
+type Key = (&'static str, String);
+
+#[derive(Default)]
+pub struct InflightRequests {
+    requests: DashMap<Key, Inflight>,
+}
+
+impl InflightRequests {
+    /// Requests currently in flight.
+    pub fn count(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// The response for `url` decoded as `kind`, from the request already running for
+    /// it or else from `start`. Also says whether a running request was joined.
+    pub async fn join(
+        &self,
+        kind: &'static str,
+        url: &str,
+        start: impl FnOnce() -> BoxFuture<'static, anyhow::Result<Arc<String>>>,
+    ) -> (anyhow::Result<Arc<String>>, bool) {
+        let key = (kind, url.to_string());
+        let (body, joined) = match self.requests.entry(key.clone()) {
+            Entry::Occupied(mut entry) => {
+                entry.get_mut().waiters += 1;
+                (entry.get().body.clone(), true)
+            }
+            Entry::Vacant(entry) => {
+                let body = start()
+                    .map(|result| result.map_err(|e| SharedError(Arc::new(e))))
+                    .boxed()
+                    .shared();
+                entry.insert(Inflight {
+                    body: body.clone(),
+                    waiters: 1,
+                });
+                (body, false)
+            }
+        };
+        let _waiter = Waiter {
+            requests: &self.requests,
+            key,
+        };
+        (body.await.map_err(anyhow::Error::new), joined)
+    }
+}
+
+/// Removes the entry when the last waiter finishes or is dropped.
+struct Waiter<'a> {
+    requests: &'a DashMap<Key, Inflight>,
+    key: Key,
+}
+
+impl Drop for Waiter<'_> {
+    fn drop(&mut self) {
+        self.requests.remove_if_mut(&self.key, |_, inflight| {
+            inflight.waiters -= 1;
+            inflight.waiters == 0
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn late_joiners_share_the_response_until_every_waiter_is_done() {
+        let requests = InflightRequests::default();
+        let started = AtomicUsize::new(0);
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        let start = || {
+            started.fetch_add(1, Ordering::SeqCst);
+            async move {
+                let _ = rx.await;
+                Ok(Arc::new("body".to_string()))
+            }
+            .boxed()
+        };
+
+        let first = requests.join("Story", "item/1", start);
+        let second = requests.join("Story", "item/1", || unreachable!());
+        // Another type asking for the same URL gets its own request
+        let other = requests.join("Comment", "item/1", || {
+            async { Ok(Arc::new("other".to_string())) }.boxed()
+        });
+        let respond = async {
+            tokio::task::yield_now().await;
+            let _ = tx.send(());
+        };
+        let ((a, joined_a), (b, joined_b), (c, joined_c), ()) =
+            tokio::join!(first, second, other, respond);
+        assert_eq!(*a.unwrap(), "body");
+        assert_eq!(*b.unwrap(), "body");
+        assert_eq!(*c.unwrap(), "other");
+        assert_eq!((joined_a, joined_b, joined_c), (false, true, false));
+        assert_eq!(started.load(Ordering::SeqCst), 1);
+        assert_eq!(requests.count(), 0);
+    }
+
+    #[tokio::test]
+    async fn cancelled_waiters_leave_no_entry_and_errors_keep_their_cause() {
+        let requests = InflightRequests::default();
+        let pending = requests.join("Story", "item/2", || {
+            futures::future::pending::<anyhow::Result<Arc<String>>>().boxed()
+        });
+        assert!(
+            tokio::time::timeout(std::time::Duration::from_millis(10), pending)
+                .await
+                .is_err()
+        );
+        assert_eq!(requests.count(), 0);
+
+        let (result, _) = requests
+            .join("Story", "item/3", || {
+                async {
+                    Err(std::io::Error::from(std::io::ErrorKind::TimedOut))
+                        .context("failed to send GET request")
+                }
+                .boxed()
+            })
+            .await;
+        let error = result.unwrap_err();
+        assert_eq!(error.to_string(), "failed to send GET request");
+        assert!(
+            error
+                .chain()
+                .any(|cause| cause.downcast_ref::<std::io::Error>().is_some())
+        );
+    }
+}
@@ -1,20 +1,29 @@
-use crate::internal::cache::Cache;
-use crate::internal::models::{Article, Comment, Story};
-use crate::utils::html_parser::parse_article_html;
+pub mod auth;
+pub mod circuit_breaker;
+pub mod inflight;
+pub mod rate_limit;
+
+use crate::internal::cache::{Cache, CacheStats};
+use crate::internal::models::{Article, Comment, Item, Poll, PollOpt, SiteMeta, Story};
+use crate::utils::html_parser::extract_article;
 use anyhow::{Context, Result};
-use dashmap::DashMap;
-use futures::future::{BoxFuture, FutureExt, Shared};
+use circuit_breaker::{ApiHealth, CircuitBreaker, CircuitOpen};
+use futures::future::FutureExt;
+use inflight::InflightRequests;
+use rate_limit::RateLimiter;
 use reqwest::Client;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 use strum_macros::Display;
-use tokio::sync::Semaphore;
 use tokio_util::sync::CancellationToken;
 
 /// Types of Hacker News story lists we can fetch.
-#[derive(Debug, Clone, Copy, PartialEq, Display, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Display, Serialize, Deserialize, clap::ValueEnum,
+)]
 pub enum StoryListType {
     Best,
     Top,
@@ -22,6 +31,9 @@ pub enum StoryListType {
     Ask,
     Show,
     Job,
+    /// Ad-hoc list of item ids imported by the user; it has no API endpoint
+    #[value(skip)]
+    Custom,
 }
 
 impl StoryListType {
@@ -33,15 +45,101 @@ impl StoryListType {
             Self::Ask => "askstories",
             Self::Show => "showstories",
             Self::Job => "jobstories",
+            Self::Custom => "",
         }
     }
 }
 
 const HN_API_BASE_URL: &str = "https://hacker-news.firebaseio.com/v0/";
+/// Algolia's HN search API, used to find the submission of a URL
+const ALGOLIA_API_BASE_URL: &str = "https://hn.algolia.com/api/v1/";
+
+/// User-Agent sent unless `network.user_agent` overrides it.
+pub const USER_AGENT: &str = concat!("tui-hn-app/", env!("CARGO_PKG_VERSION"));
+
+/// Limit on comments per thread, to prevent freezing on huge threads
+const MAX_COMMENTS: usize = 500;
+/// Top-level comments in the first streamed batch; later batches double up to `MAX_CHUNK_ROOTS`
+const FIRST_CHUNK_ROOTS: usize = 4;
+const MAX_CHUNK_ROOTS: usize = 32;
+
+/// HTTP client with the configured timeouts, User-Agent and proxy, and gzip/brotli
+/// decompression. An invalid proxy is logged and skipped rather than failing startup.
+fn build_client(config: &crate::config::NetworkConfig) -> Client {
+    let mut builder = Client::builder()
+        .user_agent(config.user_agent.as_deref().unwrap_or(USER_AGENT))
+        .timeout(Duration::from_secs(config.request_timeout_secs))
+        .connect_timeout(Duration::from_secs(config.connect_timeout_secs))
+        .gzip(true)
+        .brotli(true);
+    if let Some(proxy) = &config.proxy {
+        match reqwest::Proxy::all(proxy) {
+            Ok(proxy) => builder = builder.proxy(proxy),
+            Err(e) => tracing::warn!("Ignoring invalid proxy {:?}: {}", proxy, e),
+        }
+    }
+    builder.build().unwrap_or_else(|e| {
+        tracing::warn!("Failed to build HTTP client, using defaults: {}", e);
+        Client::new()
+    })
+}
+
+fn circuit_breaker_for(config: &crate::config::NetworkConfig) -> CircuitBreaker {
+    CircuitBreaker::new(
+        config.circuit_breaker_threshold,
+        Duration::from_secs(config.circuit_breaker_cooldown_secs),
+    )
+}
+
+/// Statuses that mean the API itself is struggling, as opposed to a bad request.
+fn is_api_failure(status: reqwest::StatusCode) -> bool {
+    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Body of `updates.json`: items and profiles that changed recently.
+#[derive(Debug, Default, Deserialize)]
+struct Updates {
+    #[serde(default)]
+    items: Vec<u32>,
+}
 
-/// Type alias for in-flight request tracking map
-type InflightRequestMap =
-    Arc<DashMap<String, Shared<BoxFuture<'static, Result<Arc<String>, String>>>>>;
+/// Body of an Algolia search: the matching items, best first.
+#[derive(Debug, Default, Deserialize)]
+struct SearchResults {
+    #[serde(default)]
+    hits: Vec<SearchHit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchHit {
+    #[serde(rename = "objectID")]
+    object_id: String,
+    url: Option<String>,
+    points: Option<u32>,
+}
+
+/// `url` without its scheme, `www.`, trailing slash and case, to compare links
+/// written differently.
+fn link_key(url: &str) -> String {
+    let url = url.trim();
+    let url = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+    url.trim_start_matches("www.")
+        .trim_end_matches('/')
+        .to_lowercase()
+}
+
+/// Id of the most upvoted hit linking to `url`; the search also returns stories
+/// whose links only contain it.
+fn best_submission(hits: &[SearchHit], url: &str) -> Option<u32> {
+    let wanted = link_key(url);
+    hits.iter()
+        .filter(|hit| hit.url.as_deref().is_some_and(|u| link_key(u) == wanted))
+        .max_by_key(|hit| hit.points.unwrap_or(0))
+        .and_then(|hit| hit.object_id.parse().ok())
+}
 
 #[cfg(test)]
 pub fn hn_item_url(id: u32) -> String {
@@ -53,6 +151,125 @@ pub fn get_story_list_url(list_type: StoryListType) -> String {
     format!("{}{}.json", HN_API_BASE_URL, list_type.as_api_str())
 }
 
+/// Cache and request statistics of an `ApiService`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ApiCacheStats {
+    pub stories: CacheStats,
+    pub comments: CacheStats,
+    pub articles: CacheStats,
+    /// Requests currently in flight
+    pub inflight: usize,
+    /// Requests served by joining an identical in-flight request
+    pub dedup_joins: u64,
+}
+
+/// Lookups a cache needs before its hit rate says anything.
+const MIN_LOOKUPS_FOR_HINTS: u64 = 50;
+
+impl ApiCacheStats {
+    /// Config changes suggested by the observed hit rates, evictions and stale
+    /// serves, one line each, e.g. "Article cache hit rate 12% — consider
+    /// increasing `article_cache_ttl_secs` (now 900)".
+    pub fn tuning_hints(&self, network: &crate::config::NetworkConfig) -> Vec<String> {
+        let caches = [
+            (
+                "Story",
+                &self.stories,
+                "story_cache_ttl_secs",
+                network.story_cache_ttl_secs,
+            ),
+            (
+                "Comment",
+                &self.comments,
+                "comment_cache_ttl_secs",
+                network.comment_cache_ttl_secs,
+            ),
+            (
+                "Article",
+                &self.articles,
+                "article_cache_ttl_secs",
+                network.article_cache_ttl_secs,
+            ),
+        ];
+        let mut hints = Vec::new();
+        for (name, stats, ttl_key, ttl) in caches {
+            let lookups = stats.hits + stats.misses;
+            if let Some(ratio) = stats
+                .hit_ratio()
+                .filter(|_| lookups >= MIN_LOOKUPS_FOR_HINTS)
+                && ratio < 0.25
+            {
+                hints.push(format!(
+                    "{} cache hit rate {:.0}% — consider increasing `{}` (now {})",
+                    name,
+                    ratio * 100.0,
+                    ttl_key,
+                    ttl
+                ));
+            }
+            // Evicting a tenth of the cache means entries are dropped before they expire
+            if stats.evictions > 0 && stats.evictions * 10 >= stats.entries as u64 {
+                hints.push(format!(
+                    "{} cache evicted {} entries — consider raising `cache_max_entries` (now {})",
+                    name, stats.evictions, network.cache_max_entries
+                ));
+            }
+            if stats.stale_serves > 0 && !network.disk_cache {
+                hints.push(format!(
+                    "{} cache served {} stale entries — `disk_cache: true` keeps them across restarts",
+                    name, stats.stale_serves
+                ));
+            }
+        }
+        hints
+    }
+}
+
+fn opt_len(text: &Option<String>) -> usize {
+    text.as_ref().map_or(0, String::len)
+}
+
+fn story_size(story: &Story) -> usize {
+    std::mem::size_of::<Story>()
+        + opt_len(&story.title)
+        + opt_len(&story.url)
+        + opt_len(&story.by)
+        + story.kids.as_ref().map_or(0, |kids| kids.len() * 4)
+}
+
+fn comment_size(comment: &Comment) -> usize {
+    std::mem::size_of::<Comment>()
+        + opt_len(&comment.text)
+        + opt_len(&comment.by)
+        + comment.kids.as_ref().map_or(0, |kids| kids.len() * 4)
+}
+
+fn article_size(article: &Article) -> usize {
+    use crate::internal::models::ArticleElement;
+    let elements: usize = article
+        .elements
+        .iter()
+        .map(|element| {
+            std::mem::size_of::<ArticleElement>()
+                + match element {
+                    ArticleElement::Paragraph(text)
+                    | ArticleElement::Heading(_, text)
+                    | ArticleElement::Image(text)
+                    | ArticleElement::Quote(text) => text.len(),
+                    ArticleElement::CodeBlock { lang, code } => opt_len(lang) + code.len(),
+                    ArticleElement::List(items) => items.iter().map(String::len).sum(),
+                    ArticleElement::Table(rows) => rows.iter().flatten().map(String::len).sum(),
+                }
+        })
+        .sum();
+    std::mem::size_of::<Article>()
+        + article.title.len()
+        + opt_len(&article.byline)
+        + opt_len(&article.published)
+        + opt_len(&article.site_name)
+        + elements
+}
+
 /// HTTP API service for fetching Hacker News data.
 ///
 /// This service uses async `reqwest::Client` and returns `anyhow::Result` with
@@ -68,11 +285,22 @@ pub struct ApiService {
     enable_performance_metrics: bool,
     // Exposed for integration tests
     pub base_url: Option<String>,
-    // Rate limiting semaphore
-    rate_limiter: Arc<Semaphore>,
-    // In-flight request deduplication
-    // Maps URL -> Shared Future that returns Result<Arc<String> (body), String (error)>
-    inflight_requests: InflightRequestMap,
+    // Token buckets per host, refilling at `rate_limit_per_second`
+    rate_limiter: Arc<RateLimiter>,
+    // In-flight request deduplication, keyed by response type and URL
+    inflight_requests: Arc<InflightRequests>,
+    // Requests that joined an identical in-flight request instead of hitting the network
+    dedup_joins: Arc<AtomicU64>,
+    // Pauses API requests after repeated failures
+    breaker: Arc<CircuitBreaker>,
+    // Fail every network request immediately (`--offline`)
+    offline: bool,
+    // Where the caches are saved by the disk cache and the offline pack
+    cache_dir: Option<std::path::PathBuf>,
+    // Load the caches on start and save them on exit (`network.disk_cache`)
+    disk_cache: bool,
+    // Durations of finished requests, for the performance HUD
+    request_timings: Arc<std::sync::Mutex<crate::internal::metrics::Histogram>>,
 }
 
 impl ApiService {
@@ -81,27 +309,117 @@ impl ApiService {
         network_config: crate::config::NetworkConfig,
         enable_performance_metrics: bool,
     ) -> Self {
-        // Create semaphore for rate limiting
-        // permits = rate_limit_per_second (allows bursts up to that rate)
-        let permits = network_config.rate_limit_per_second.ceil() as usize;
-        let rate_limiter = Arc::new(Semaphore::new(permits));
+        let rate_limiter = Arc::new(RateLimiter::new(network_config.rate_limit_per_second));
 
-        Self {
-            client: Client::new(),
-            story_cache: Cache::with_metrics(Duration::from_secs(300), enable_performance_metrics), // 5 minutes
+        let ttl = Duration::from_secs;
+        let max_entries = network_config.cache_max_entries;
+
+        let service = Self {
+            client: build_client(&network_config),
+            story_cache: Cache::with_metrics(
+                ttl(network_config.story_cache_ttl_secs),
+                enable_performance_metrics,
+            )
+            .with_max_entries(max_entries),
             comment_cache: Cache::with_metrics(
-                Duration::from_secs(300),
+                ttl(network_config.comment_cache_ttl_secs),
                 enable_performance_metrics,
-            ), // 5 minutes
+            )
+            .with_max_entries(max_entries),
             article_cache: Cache::with_metrics(
-                Duration::from_secs(900),
+                ttl(network_config.article_cache_ttl_secs),
                 enable_performance_metrics,
-            ), // 15 minutes
+            )
+            .with_max_entries(max_entries),
+            cache_dir: network_config.cache_dir(),
+            disk_cache: network_config.disk_cache,
+            breaker: Arc::new(circuit_breaker_for(&network_config)),
             network_config,
             enable_performance_metrics,
             base_url: None,
             rate_limiter,
-            inflight_requests: Arc::new(DashMap::new()),
+            inflight_requests: Arc::default(),
+            dedup_joins: Arc::default(),
+            offline: false,
+            request_timings: Arc::default(),
+        };
+        if service.disk_cache {
+            service.load_caches();
+        }
+        service
+    }
+
+    /// Fill the caches from the cache directory. Failures only cost a cold start.
+    fn load_caches(&self) {
+        let Some(dir) = &self.cache_dir else {
+            return;
+        };
+        let results = [
+            (
+                "stories",
+                self.story_cache.load_from(&dir.join("stories.json")),
+            ),
+            (
+                "comments",
+                self.comment_cache.load_from(&dir.join("comments.json")),
+            ),
+            (
+                "articles",
+                self.article_cache.load_from(&dir.join("articles.json")),
+            ),
+        ];
+        for (name, result) in results {
+            match result {
+                Ok(count) => tracing::info!(cache = name, count, "Loaded disk cache"),
+                Err(e) => tracing::warn!(cache = name, "Failed to load disk cache: {:#}", e),
+            }
+        }
+    }
+
+    /// Write the caches to the disk cache directory, if enabled.
+    pub fn persist_caches(&self) -> Result<()> {
+        match self.disk_cache {
+            true => self.save_caches(),
+            false => Ok(()),
+        }
+    }
+
+    /// Write the caches to the cache directory even with the disk cache disabled, so
+    /// an offline pack survives until `--offline` reads it back.
+    pub fn save_caches(&self) -> Result<()> {
+        let dir = self
+            .cache_dir
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Could not find a cache directory"))?;
+        self.story_cache.save_to(&dir.join("stories.json"))?;
+        self.comment_cache.save_to(&dir.join("comments.json"))?;
+        self.article_cache.save_to(&dir.join("articles.json"))?;
+        Ok(())
+    }
+
+    /// Durations of the requests made so far (with their retries), recorded when
+    /// performance metrics are on.
+    pub fn request_timings(&self) -> crate::internal::metrics::Histogram {
+        self.request_timings
+            .lock()
+            .map(|timings| timings.clone())
+            .unwrap_or_default()
+    }
+
+    fn record_request_time(&self, elapsed: Duration) {
+        if let Ok(mut timings) = self.request_timings.lock() {
+            timings.record(elapsed);
+        }
+    }
+
+    /// Snapshot of the caches and request deduplication, for the cache statistics overlay.
+    pub fn cache_stats(&self) -> ApiCacheStats {
+        ApiCacheStats {
+            stories: self.story_cache.stats(story_size),
+            comments: self.comment_cache.stats(comment_size),
+            articles: self.article_cache.stats(article_size),
+            inflight: self.inflight_requests.count(),
+            dedup_joins: self.dedup_joins.load(Ordering::Relaxed),
         }
     }
 
@@ -109,11 +427,11 @@ impl ApiService {
     #[allow(dead_code)]
     pub fn with_base_url(base_url: String) -> Self {
         let network_config = crate::config::NetworkConfig::default();
-        let permits = network_config.rate_limit_per_second.ceil() as usize;
-        let rate_limiter = Arc::new(Semaphore::new(permits));
+        let rate_limiter = Arc::new(RateLimiter::new(network_config.rate_limit_per_second));
 
         Self {
-            client: Client::new(),
+            client: build_client(&network_config),
+            breaker: Arc::new(circuit_breaker_for(&network_config)),
             story_cache: Cache::with_metrics(Duration::from_secs(300), false),
             comment_cache: Cache::with_metrics(Duration::from_secs(300), false),
             article_cache: Cache::with_metrics(Duration::from_secs(900), false),
@@ -121,19 +439,102 @@ impl ApiService {
             enable_performance_metrics: false,
             base_url: Some(base_url),
             rate_limiter,
-            inflight_requests: Arc::new(DashMap::new()),
+            inflight_requests: Arc::default(),
+            dedup_joins: Arc::default(),
+            offline: false,
+            cache_dir: None,
+            disk_cache: false,
+            request_timings: Arc::default(),
         }
     }
 
+    /// The service with new retry, concurrency, rate and timeout settings, sharing
+    /// this one's caches. Used when the config changes while the app runs.
+    pub fn with_network(&self, network_config: crate::config::NetworkConfig) -> Self {
+        Self {
+            client: build_client(&network_config),
+            rate_limiter: Arc::new(RateLimiter::new(network_config.rate_limit_per_second)),
+            network_config,
+            ..self.clone()
+        }
+    }
+
+    /// Disable network access; only cached (including stale) content is served.
+    /// Without the disk cache the caches are loaded here, for the offline pack.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        if offline && !self.disk_cache {
+            self.load_caches();
+        }
+        self
+    }
+
+    /// Save and load the caches in `dir` instead of the configured cache directory.
+    #[allow(dead_code)]
+    pub fn with_cache_dir(mut self, dir: std::path::PathBuf) -> Self {
+        self.cache_dir = Some(dir);
+        self
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Requests waiting for the rate limit, for the status bar.
+    pub fn throttled_requests(&self) -> usize {
+        self.rate_limiter.waiting()
+    }
+
+    /// Whether the API is reachable, for the status bar banner.
+    pub fn api_health(&self) -> ApiHealth {
+        self.breaker.health()
+    }
+
+    fn record_api_failure(&self) {
+        if self.breaker.record_failure() {
+            tracing::warn!("HN API degraded: pausing requests after repeated failures");
+            self.schedule_probe();
+        }
+    }
+
+    /// Once the cooldown has passed, send one cheap request to find out whether the
+    /// API recovered. A failed probe reopens the circuit, which schedules the next one.
+    fn schedule_probe(&self) {
+        let Some(wait) = self.breaker.retry_in() else {
+            return;
+        };
+        let service = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(wait + Duration::from_millis(10)).await;
+            let url = format!("{}maxitem.json", service.get_base_url());
+            match service.fetch_raw(url).await {
+                Ok(_) => tracing::info!("HN API recovered"),
+                Err(e) => tracing::debug!("HN API probe failed: {:#}", e),
+            }
+        });
+    }
+
     fn get_base_url(&self) -> &str {
         self.base_url.as_deref().unwrap_or(HN_API_BASE_URL)
     }
 
+    /// Tests serve both APIs from the same mock server.
+    fn get_search_url(&self) -> &str {
+        self.base_url.as_deref().unwrap_or(ALGOLIA_API_BASE_URL)
+    }
+
     /// Generic helper to GET a URL and deserialize the JSON body into `T`.
-    /// Retries on network errors and timeouts with exponential backoff.
+    /// Retries on network errors, timeouts, 5xx and 429 with exponential backoff.
     /// Fetch raw text from URL with retries.
-    #[tracing::instrument(skip(self), fields(url = %url))]
+    #[tracing::instrument(name = "fetch.raw", skip(self), fields(url = %url))]
     async fn fetch_raw(&self, url: String) -> Result<Arc<String>> {
+        if self.offline {
+            return Err(anyhow::anyhow!("Offline mode: network access is disabled"));
+        }
+        if !self.breaker.allow() {
+            return Err(CircuitOpen.into());
+        }
+
         let start = std::time::Instant::now();
         let mut attempt = 0;
         let mut delay = self.network_config.initial_retry_delay_ms;
@@ -141,23 +542,36 @@ impl ApiService {
         loop {
             attempt += 1;
 
-            // Acquire rate limiting permit
-            let _permit = self
-                .rate_limiter
-                .acquire()
-                .await
-                .expect("Semaphore should never be closed");
+            // Wait for a token from the host's bucket
+            self.rate_limiter.acquire(&rate_limit::host_of(&url)).await;
 
             let resp_result = self.client.get(&url).send().await;
 
             match resp_result {
                 Ok(resp) => {
+                    let status = resp.status();
+                    match is_api_failure(status) {
+                        true => self.record_api_failure(),
+                        false => self.breaker.record_success(),
+                    }
+                    // 5xx and 429 are retried too, until the failures open the circuit
+                    if is_api_failure(status)
+                        && attempt <= self.network_config.max_retries
+                        && self.breaker.allow()
+                    {
+                        self.back_off(&url, attempt, &mut delay, status).await;
+                        continue;
+                    }
+                    let resp = resp
+                        .error_for_status()
+                        .with_context(|| format!("GET {} failed", url))?;
                     let text = resp
                         .text()
                         .await
                         .with_context(|| format!("failed to get response text from {}", url))?;
 
                     if self.enable_performance_metrics {
+                        self.record_request_time(start.elapsed());
                         tracing::debug!(elapsed = ?start.elapsed(), url = %url, attempt = attempt, "GET successful");
                     }
                     return Ok(Arc::new(text));
@@ -169,91 +583,80 @@ impl ApiService {
                         (is_timeout && self.network_config.retry_on_timeout) || is_connect;
 
                     if !should_retry || attempt > self.network_config.max_retries {
+                        self.record_api_failure();
                         if self.enable_performance_metrics {
+                            self.record_request_time(start.elapsed());
                             tracing::debug!(elapsed = ?start.elapsed(), url = %url, attempt = attempt, error = %e, "GET failed (final)");
                         }
                         return Err(anyhow::Error::new(e))
                             .with_context(|| format!("failed to send GET request to {}", url));
                     }
 
-                    tracing::warn!(
-                        "Request to {} failed (attempt {}/{}): {}. Retrying in {}ms...",
-                        url,
-                        attempt,
-                        self.network_config.max_retries + 1,
-                        e,
-                        delay
-                    );
-
-                    tokio::time::sleep(Duration::from_millis(delay)).await;
-                    delay = (delay * 2).min(self.network_config.max_retry_delay_ms);
+                    self.back_off(&url, attempt, &mut delay, e).await;
                 }
             }
         }
     }
 
+    /// Sleep before retrying a failed request, doubling `delay` for the next time.
+    async fn back_off(
+        &self,
+        url: &str,
+        attempt: u32,
+        delay: &mut u64,
+        error: impl std::fmt::Display,
+    ) {
+        let wait = circuit_breaker::jittered(Duration::from_millis(*delay));
+        tracing::warn!(
+            "Request to {} failed (attempt {}/{}): {}. Retrying in {}ms...",
+            url,
+            attempt,
+            self.network_config.max_retries + 1,
+            error,
+            wait.as_millis()
+        );
+
+        tokio::time::sleep(wait).await;
+        *delay = (*delay * 2).min(self.network_config.max_retry_delay_ms);
+    }
+
     /// Generic helper to GET a URL and deserialize the JSON body into `T`.
     /// Uses request deduplication to prevent duplicate in-flight requests.
-    #[tracing::instrument(skip(self), fields(url = %url))]
+    #[tracing::instrument(name = "fetch.json", skip(self), fields(url = %url))]
     async fn get_json<T>(&self, url: &str) -> Result<T>
     where
         T: DeserializeOwned,
     {
-        // Deduplication logic
-        let future = {
-            if let Some(future) = self.inflight_requests.get(url) {
-                if self.enable_performance_metrics {
-                    tracing::debug!(url = %url, "Deduplicated request joined");
-                }
-                future.clone()
-            } else {
-                let url_owned = url.to_string();
-                let self_clone = self.clone();
-
-                let future = async move {
-                    self_clone
-                        .fetch_raw(url_owned)
-                        .await
-                        .map_err(|e| e.to_string())
-                }
-                .boxed()
-                .shared();
-
-                self.inflight_requests
-                    .insert(url.to_string(), future.clone());
-                future
-            }
-        };
-
-        // Wait for the request to complete
-        let result = future.await;
-
-        // Clean up the map entry if we were the ones who inserted it (or just always try to remove)
-        // Note: In a shared future scenario, multiple waiters might try to remove, which is fine.
-        // Ideally we only remove when the *last* waiter is done, but Shared doesn't expose refcount easily.
-        // A simple approach is to remove it immediately after *creation* finishes, but that might be too early if we want to dedupe retries?
-        // Actually, `future` here is the Shared future. When it completes, the value is ready.
-        // We should remove it from the map so subsequent requests (later in time) fetch fresh data.
-        // We can just try to remove it. It's a DashMap, so it's safe.
-        self.inflight_requests.remove(url);
-
-        match result {
-            Ok(body) => {
-                let parsed = serde_json::from_str::<T>(&body)
-                    .with_context(|| format!("failed to parse JSON response from {}", url))?;
-                Ok(parsed)
+        let api = self.clone();
+        let url_owned = url.to_string();
+        let (body, joined) = self
+            .inflight_requests
+            .join(std::any::type_name::<T>(), url, move || {
+                async move { api.fetch_raw(url_owned).await }.boxed()
+            })
+            .await;
+        if joined {
+            self.dedup_joins.fetch_add(1, Ordering::Relaxed);
+            if self.enable_performance_metrics {
+                tracing::debug!(url = %url, "Deduplicated request joined");
             }
-            Err(e_str) => Err(anyhow::anyhow!(e_str)),
         }
+
+        serde_json::from_str::<T>(&body?)
+            .with_context(|| format!("failed to parse JSON response from {}", url))
     }
 
     /// Fetch a list of story IDs for the given list type (e.g., top, new).
-    #[tracing::instrument(skip(self, token), fields(list_type = ?list_type))]
+    #[tracing::instrument(name = "fetch.story_ids", skip(self, token), fields(list_type = ?list_type))]
     pub async fn fetch_story_ids(
         &self,
         list_type: StoryListType,
         token: Option<CancellationToken>,
     ) -> Result<Vec<u32>> {
+        if list_type == StoryListType::Custom {
+            return Err(anyhow::anyhow!("Custom lists are not fetched from the API"));
+        }
+
         let start = std::time::Instant::now();
         let url = format!("{}{}.json", self.get_base_url(), list_type.as_api_str());
 
@@ -282,7 +685,7 @@ impl ApiService {
     }
 
     /// Fetch a single story item by id.
-    #[tracing::instrument(skip(self), fields(id = %id))]
+    #[tracing::instrument(name = "fetch.story", skip(self), fields(id = %id))]
     pub async fn fetch_story_content(&self, id: u32) -> Result<Story> {
         // Check cache first
         if let Some(story) = self.story_cache.get(&id) {
@@ -301,7 +704,7 @@ impl ApiService {
             Ok(s) => s,
             Err(e) => {
                 // Try stale cache
-                if let Some(stale_story) = self.story_cache.get_stale(&id) {
+                if let Some(stale_story) = self.story_cache.serve_stale(&id) {
                     tracing::warn!("Network failed for story {}, serving stale content", id);
                     return Ok(stale_story);
                 }
@@ -309,8 +712,11 @@ impl ApiService {
             }
         };
 
-        // Cache the result
-        self.story_cache.set(id, story.clone());
+        // Cache the result; new stories go stale sooner than archived ones
+        let ttl = self
+            .network_config
+            .story_cache_ttl(story.time, jiff::Timestamp::now().as_second());
+        self.story_cache.set_with_ttl(id, story.clone(), ttl);
         if self.enable_performance_metrics {
             tracing::debug!(elapsed = ?start.elapsed(), "Fetched and cached story content");
         }
@@ -319,7 +725,7 @@ impl ApiService {
 
     /// Fetch multiple stories concurrently with a limit on concurrent requests.
     /// Returns a Vec of Results, preserving order of input IDs.
-    #[tracing::instrument(skip(self, ids, token), fields(count = ids.len(), limit = limit))]
+    #[tracing::instrument(name = "fetch.stories", skip(self, ids, token), fields(count = ids.len(), limit = limit))]
     pub async fn fetch_stories_concurrent(
         &self,
         ids: &[u32],
@@ -377,149 +783,358 @@ impl ApiService {
         results
     }
 
-    /// Fetch a single comment item by id.
-    #[tracing::instrument(skip(self), fields(id = %id))]
-    pub async fn fetch_comment_content(&self, id: u32) -> Result<Comment> {
-        // Check cache first
+    /// Fetch the options of a poll, in the order of `parts`. Options that fail to
+    /// load are skipped; the call fails only if none could be fetched.
+    #[tracing::instrument(name = "fetch.poll", skip(self, parts, token), fields(poll = poll_id))]
+    pub async fn fetch_poll(
+        &self,
+        poll_id: u32,
+        parts: &[u32],
+        token: Option<CancellationToken>,
+    ) -> Result<Poll> {
+        use futures::stream::{self, StreamExt};
+
+        if token.as_ref().is_some_and(|t| t.is_cancelled()) {
+            return Err(anyhow::anyhow!("Request cancelled"));
+        }
+        let results: Vec<Result<PollOpt>> = stream::iter(parts.iter().copied())
+            .map(|id| {
+                let url = format!("{}item/{}.json", self.get_base_url(), id);
+                async move { self.get_json::<PollOpt>(&url).await }
+            })
+            .buffered(10)
+            .collect()
+            .await;
+
+        let failed = results.iter().filter(|r| r.is_err()).count();
+        let options: Vec<PollOpt> = results.into_iter().flatten().collect();
+        match (options.is_empty(), failed) {
+            (true, 1..) => Err(anyhow::anyhow!(
+                "failed to fetch the options of poll {}",
+                poll_id
+            )),
+            _ => {
+                if failed > 0 {
+                    tracing::warn!(failed, "Some poll options failed to load");
+                }
+                Ok(Poll {
+                    id: poll_id,
+                    options,
+                })
+            }
+        }
+    }
+
+    /// Like `fetch_stories_concurrent`, but hands stories to `on_batch` as they arrive:
+    /// up to `batch` at a time, in the order of `ids`, with the number of fetches
+    /// still outstanding. Stories that fail to load are skipped. `on_batch` is called
+    /// at least once, and the last call reports 0 remaining; a cancelled token stops
+    /// the stream without it.
+    pub async fn stream_stories(
+        &self,
+        ids: &[u32],
+        limit: usize,
+        batch: usize,
+        token: Option<CancellationToken>,
+        mut on_batch: impl FnMut(Vec<Story>, usize),
+    ) {
+        use futures::stream::{self, StreamExt};
+
+        if ids.is_empty() {
+            on_batch(Vec::new(), 0);
+            return;
+        }
+        let mut remaining = ids.len();
+        let mut batches = stream::iter(ids.iter().copied())
+            .map(|id| self.fetch_story_content(id))
+            .buffered(limit.max(1))
+            .ready_chunks(batch.max(1));
+        while let Some(results) = batches.next().await {
+            if token.as_ref().is_some_and(|token| token.is_cancelled()) {
+                tracing::debug!("Story stream cancelled with {} fetches left", remaining);
+                return;
+            }
+            remaining -= results.len();
+            on_batch(
+                results.into_iter().filter_map(|r| r.ok()).collect(),
+                remaining,
+            );
+        }
+    }
+
+    /// Re-fetch the given stories from the network, bypassing fresh cache entries.
+    /// Used by background refresh so only the requested (visible) rows hit the API.
+    #[tracing::instrument(name = "fetch.refresh", skip(self, ids, token), fields(count = ids.len()))]
+    pub async fn refresh_stories(
+        &self,
+        ids: &[u32],
+        limit: usize,
+        token: Option<CancellationToken>,
+    ) -> Vec<Result<Story>> {
+        for id in ids {
+            self.story_cache.invalidate(id);
+        }
+        self.fetch_stories_concurrent(ids, limit, token).await
+    }
+
+    /// Ids of recently changed items, from `updates.json`. Their cached stories and
+    /// comments are dropped so the next read gets the new version.
+    pub async fn fetch_updates(&self) -> Result<Vec<u32>> {
+        let url = format!("{}updates.json", self.get_base_url());
+        let updates: Updates = self
+            .get_json(&url)
+            .await
+            .context("Failed to fetch updated items")?;
+        for id in &updates.items {
+            self.story_cache.invalidate(id);
+            self.comment_cache.invalidate(id);
+        }
+        Ok(updates.items)
+    }
+
+    /// Fetch any item by id, whatever its type. Stories, jobs, polls and comments
+    /// are served from and stored in their caches like the typed fetches.
+    #[tracing::instrument(name = "fetch.item", skip(self), fields(id = %id))]
+    pub async fn fetch_item(&self, id: u32) -> Result<Item> {
         if let Some(comment) = self.comment_cache.get(&id) {
-            tracing::trace!("Cache hit for comment {}", id);
-            return Ok(comment);
+            return Ok(Item::Comment(comment));
+        }
+        if let Some(story) = self.story_cache.get(&id) {
+            return Ok(Item::from_story(story));
         }
 
         if self.enable_performance_metrics {
-            tracing::trace!("Cache miss for comment {}", id);
+            tracing::trace!("Cache miss for item {}", id);
         }
 
         let start = std::time::Instant::now();
-        // Fetch from API
         let url = format!("{}item/{}.json", self.get_base_url(), id);
-        let comment: Comment = match self.get_json(&url).await {
-            Ok(c) => c,
+        let item: Item = match self.get_json(&url).await {
+            Ok(item) => item,
             Err(e) => {
-                // Try stale cache
-                if let Some(stale_comment) = self.comment_cache.get_stale(&id) {
-                    tracing::warn!("Network failed for comment {}, serving stale content", id);
-                    return Ok(stale_comment);
+                if let Some(comment) = self.comment_cache.serve_stale(&id) {
+                    tracing::warn!("Network failed for item {}, serving stale comment", id);
+                    return Ok(Item::Comment(comment));
                 }
-                return Err(e)
-                    .with_context(|| format!("fetch_comment_content failed for id {}", id));
+                if let Some(story) = self.story_cache.serve_stale(&id) {
+                    tracing::warn!("Network failed for item {}, serving stale story", id);
+                    return Ok(Item::from_story(story));
+                }
+                return Err(e).with_context(|| format!("fetch_item failed for id {}", id));
             }
         };
 
-        // Cache the result
-        self.comment_cache.set(id, comment.clone());
+        match &item {
+            Item::Comment(comment) => self.comment_cache.set(id, comment.clone()),
+            Item::Story(story) | Item::Job(story) | Item::Poll(story) => {
+                let ttl = self
+                    .network_config
+                    .story_cache_ttl(story.time, jiff::Timestamp::now().as_second());
+                self.story_cache.set_with_ttl(id, story.clone(), ttl);
+            }
+            Item::PollOpt(_) => {}
+        }
         if self.enable_performance_metrics {
-            tracing::debug!(elapsed = ?start.elapsed(), "Fetched and cached comment content");
+            tracing::debug!(elapsed = ?start.elapsed(), "Fetched and cached item");
         }
-        Ok(comment)
+        Ok(item)
     }
 
     /// Fetch a tree of comments starting from the given root IDs.
     /// Returns a flattened list of CommentRows in DFS order.
-    #[tracing::instrument(skip(self, root_ids, token), fields(root_count = root_ids.len()))]
+    ///
+    /// Comments are fetched one after another in depth-first order up to
+    /// `max_depth`, then flattened by `comment_tree::flatten_comment_tree`.
+    #[tracing::instrument(name = "fetch.comment_tree", skip(self, root_ids, token), fields(root_count = root_ids.len()))]
     pub async fn fetch_comment_tree(
         &self,
         root_ids: Vec<u32>,
         max_depth: usize,
         token: Option<CancellationToken>,
     ) -> Result<Vec<crate::internal::models::CommentRow>> {
-        let start = std::time::Instant::now();
-        let mut rows = Vec::new();
-        // Limit total comments to prevent freezing on huge threads
-        // Increased from 100 to 500 since we now have depth limit
-        let mut total_fetched = 0;
-        const MAX_COMMENTS: usize = 500;
-
-        for id in root_ids {
-            if total_fetched >= MAX_COMMENTS {
+        self.fetch_comment_tree_limited(root_ids, max_depth, MAX_COMMENTS, token)
+            .await
+    }
+
+    /// Fetch the comment tree a few top-level threads at a time, handing each batch
+    /// of rows to `on_chunk` together with the number of top-level comments still to
+    /// fetch. Batches start small so the first threads show up quickly, and grow to
+    /// keep later requests parallel. Stops at the same comment limit as
+    /// `fetch_comment_tree`; rows already delivered stay valid if a later batch fails.
+    #[tracing::instrument(name = "fetch.comment_stream", skip(self, root_ids, token, on_chunk), fields(root_count = root_ids.len()))]
+    pub async fn stream_comment_tree(
+        &self,
+        root_ids: Vec<u32>,
+        max_depth: usize,
+        token: Option<CancellationToken>,
+        mut on_chunk: impl FnMut(Vec<crate::internal::models::CommentRow>, usize),
+    ) -> Result<()> {
+        let mut budget = MAX_COMMENTS;
+        let mut start = 0;
+        let mut size = FIRST_CHUNK_ROOTS;
+        // Runs at least once, so a story without comments still reports completion
+        loop {
+            let end = (start + size).min(root_ids.len());
+            let rows = match budget {
+                0 => Vec::new(),
+                _ => {
+                    self.fetch_comment_tree_limited(
+                        root_ids[start..end].to_vec(),
+                        max_depth,
+                        budget,
+                        token.clone(),
+                    )
+                    .await?
+                }
+            };
+            budget = budget.saturating_sub(rows.len());
+            // Once the limit is hit the remaining threads are skipped
+            let remaining = match budget {
+                0 => 0,
+                _ => root_ids.len() - end,
+            };
+            on_chunk(rows, remaining);
+            if remaining == 0 {
                 break;
             }
-            if let Some(token) = &token
-                && token.is_cancelled()
-            {
-                return Err(anyhow::anyhow!("Request cancelled"));
-            }
-            self.fetch_comment_recursive(
-                id,
-                0,
-                max_depth,
-                None,
-                &mut rows,
-                &mut total_fetched,
-                token.clone(),
-            )
-            .await?;
-        }
-        if self.enable_performance_metrics {
-            tracing::debug!(elapsed = ?start.elapsed(), fetched = total_fetched, "Fetched comment tree");
+            start = end;
+            size = (size * 2).min(MAX_CHUNK_ROOTS);
         }
-        Ok(rows)
+        Ok(())
     }
 
-    #[allow(clippy::too_many_arguments)]
-    fn fetch_comment_recursive<'a>(
-        &'a self,
-        id: u32,
-        depth: usize,
+    async fn fetch_comment_tree_limited(
+        &self,
+        root_ids: Vec<u32>,
         max_depth: usize,
-        parent_id: Option<u32>,
-        rows: &'a mut Vec<crate::internal::models::CommentRow>,
-        total_fetched: &'a mut usize,
+        limit: usize,
         token: Option<CancellationToken>,
-    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>> {
-        Box::pin(async move {
-            if *total_fetched >= 500 {
-                return Ok(());
+    ) -> Result<Vec<crate::internal::models::CommentRow>> {
+        use std::collections::HashMap;
+
+        let start = std::time::Instant::now();
+
+        let mut fetched: HashMap<u32, Comment> = HashMap::new();
+        // (id, depth) still to fetch; kids are pushed in reverse so they pop in order
+        let mut stack: Vec<(u32, usize)> = root_ids.iter().rev().map(|&id| (id, 0)).collect();
+
+        while let Some((id, depth)) = stack.pop() {
+            if fetched.len() >= limit {
+                break;
             }
             if let Some(token) = &token
                 && token.is_cancelled()
             {
-                return Ok(());
+                return Err(anyhow::anyhow!("Request cancelled"));
             }
 
-            // Fetch the comment (uses cache internally)
-            match self.fetch_comment_content(id).await {
-                Ok(comment) => {
-                    *total_fetched += 1;
-                    let kids = comment.kids.clone();
-
-                    // Check if we should recurse
-                    let should_recurse = depth < max_depth;
-                    let has_kids = kids.as_ref().map(|k| !k.is_empty()).unwrap_or(false);
-                    let loaded_kids = should_recurse && has_kids;
-
-                    rows.push(crate::internal::models::CommentRow {
-                        comment,
-                        depth,
-                        expanded: loaded_kids || !has_kids,
-                        parent_id,
-                        loaded_kids, // Set based on whether we are about to recurse
-                    });
-
-                    if should_recurse && let Some(kids) = kids {
-                        for kid_id in kids {
-                            self.fetch_comment_recursive(
-                                kid_id,
-                                depth + 1,
-                                max_depth,
-                                Some(id),
-                                rows,
-                                total_fetched,
-                                token.clone(),
-                            )
-                            .await?;
-                        }
+            match self.fetch_item(id).await {
+                Ok(Item::Comment(comment)) => {
+                    if depth < max_depth {
+                        let kids = comment.kids.iter().flatten().rev();
+                        stack.extend(kids.map(|&kid| (kid, depth + 1)));
                     }
+                    fetched.insert(id, comment);
                 }
-                Err(_) => {
-                    // If a comment fails to load, just skip it and its children
-                }
+                // A kid that is not a comment is skipped
+                Ok(other) => tracing::debug!(id = other.id(), "Skipping non-comment kid"),
+                // If a comment fails to load, just skip it and its children
+                Err(_) => {}
             }
-            Ok(())
-        })
+        }
+
+        let rows =
+            crate::internal::comment_tree::flatten_comment_tree(&root_ids, &fetched, max_depth);
+        if self.enable_performance_metrics {
+            tracing::debug!(elapsed = ?start.elapsed(), fetched = fetched.len(), "Fetched comment tree");
+        }
+        Ok(rows)
+    }
+
+    /// Whether a comment of `story` in the comment cache satisfies `matches`. Only
+    /// comments already loaded are searched; nothing is fetched.
+    pub fn cached_comments_match(&self, story: &Story, matches: impl Fn(&Comment) -> bool) -> bool {
+        let mut pending = story.kids.clone().unwrap_or_default();
+        while let Some(id) = pending.pop() {
+            let Some(comment) = self.comment_cache.get_stale(&id) else {
+                continue;
+            };
+            if matches(&comment) {
+                return true;
+            }
+            pending.extend(comment.kids.iter().flatten());
+        }
+        false
+    }
+
+    /// Follow the redirects of `url` with a HEAD request and return where it ends up.
+    /// Error statuses are not failures here: the final URL is known either way.
+    #[tracing::instrument(skip(self), fields(url = %url))]
+    pub async fn resolve_url(&self, url: &str) -> Result<String> {
+        if self.offline {
+            return Err(anyhow::anyhow!("Offline mode: links cannot be resolved"));
+        }
+        let response = self
+            .client
+            .head(url)
+            .send()
+            .await
+            .context("Failed to resolve link")?;
+        Ok(response.url().to_string())
+    }
+
+    /// Id of the HN story submitting `url`, found with the Algolia search API, or
+    /// None if it was never submitted. The most upvoted submission wins.
+    #[tracing::instrument(name = "fetch.submission", skip(self), fields(url = %url))]
+    pub async fn find_submission(&self, url: &str) -> Result<Option<u32>> {
+        if self.offline {
+            return Err(anyhow::anyhow!("Offline mode: HN search is unavailable"));
+        }
+        let endpoint = format!("{}search", self.get_search_url());
+        self.rate_limiter
+            .acquire(&rate_limit::host_of(&endpoint))
+            .await;
+        let results: SearchResults = self
+            .client
+            .get(&endpoint)
+            .query(&[
+                ("query", url),
+                ("restrictSearchableAttributes", "url"),
+                ("tags", "story"),
+            ])
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .context("Failed to search HN submissions")?
+            .json()
+            .await
+            .context("Failed to parse HN search results")?;
+        Ok(best_submission(&results.hits, url))
+    }
+
+    /// Name and brand color of `domain`, from the `<meta>` tags of its home page.
+    #[tracing::instrument(name = "fetch.site_meta", skip(self))]
+    pub async fn fetch_site_meta(&self, domain: &str) -> Result<SiteMeta> {
+        if self.offline {
+            return Err(anyhow::anyhow!(
+                "Offline mode: site metadata cannot be fetched"
+            ));
+        }
+        self.rate_limiter.acquire(domain).await;
+        let html = self
+            .client
+            .get(format!("https://{}/", domain))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .with_context(|| format!("Failed to fetch the home page of {}", domain))?
+            .text()
+            .await
+            .context("Failed to get response text")?;
+        Ok(crate::utils::html_parser::extract_site_meta(&html))
     }
 
-    #[tracing::instrument(skip(self, token), fields(url = %url))]
+    #[tracing::instrument(name = "fetch.article", skip(self, token), fields(url = %url))]
     pub async fn fetch_article_content(
         &self,
         url: &str,
@@ -541,11 +1156,21 @@ impl ApiService {
             return Err(anyhow::anyhow!("Request cancelled"));
         }
 
+        if self.offline {
+            return self
+                .article_cache
+                .serve_stale(&url.to_string())
+                .context("Offline mode: article is not cached");
+        }
+
         let start = std::time::Instant::now();
         // Fetch from web
         // We can use tokio::select! here too if we want to cancel mid-request
         let response = match tokio::select! {
-            res = self.client.get(url).timeout(Duration::from_secs(10)).send() => res,
+            res = async {
+                self.rate_limiter.acquire(&rate_limit::host_of(url)).await;
+                self.client.get(url).send().await
+            } => res.and_then(|r| r.error_for_status()),
             _ = async {
                 if let Some(token) = token {
                     token.cancelled().await;
@@ -557,7 +1182,7 @@ impl ApiService {
             Ok(r) => r,
             Err(e) => {
                 // Try stale cache
-                if let Some(stale_article) = self.article_cache.get_stale(&url.to_string()) {
+                if let Some(stale_article) = self.article_cache.serve_stale(&url.to_string()) {
                     tracing::warn!("Network failed for article {}, serving stale content", url);
                     return Ok(stale_article);
                 }
@@ -569,12 +1194,8 @@ impl ApiService {
             .text()
             .await
             .context("Failed to get response text")?;
-        let elements = parse_article_html(&html);
-
-        // Simple title extraction heuristic (could be improved)
-        let title = "Article".to_string();
-
-        let article = Article { title, elements };
+        let mut article = extract_article(&html);
+        article.resolve_links(url);
 
         // Cache the result
         self.article_cache.set(url.to_string(), article.clone());
@@ -605,6 +1226,22 @@ mod tests {
         assert_eq!(StoryListType::Job.as_api_str(), "jobstories");
     }
 
+    #[test]
+    fn picks_the_most_upvoted_submission_of_the_link() {
+        let hit = |id: &str, url: &str, points| SearchHit {
+            object_id: id.to_string(),
+            url: Some(url.to_string()),
+            points: Some(points),
+        };
+        let hits = [
+            hit("1", "https://example.com/post/2", 500),
+            hit("2", "http://www.example.com/post/", 12),
+            hit("3", "https://example.com/post", 40),
+        ];
+        assert_eq!(best_submission(&hits, "https://Example.com/post"), Some(3));
+        assert_eq!(best_submission(&hits, "example.com/other"), None);
+    }
+
     #[test]
     fn test_hn_item_url() {
         assert_eq!(
@@ -625,6 +1262,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tuning_hints_follow_observed_metrics() {
+        let network = crate::config::NetworkConfig::default();
+        let mut stats = ApiCacheStats::default();
+        // Too few lookups to judge
+        stats.articles.misses = 10;
+        assert!(stats.tuning_hints(&network).is_empty());
+
+        stats.articles = CacheStats {
+            hits: 12,
+            misses: 88,
+            ..Default::default()
+        };
+        stats.comments = CacheStats {
+            entries: 1000,
+            hits: 900,
+            misses: 100,
+            evictions: 400,
+            stale_serves: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            stats.tuning_hints(&network),
+            vec![
+                "Comment cache evicted 400 entries — consider raising `cache_max_entries` (now 5000)"
+                    .to_string(),
+                "Comment cache served 2 stale entries — `disk_cache: true` keeps them across restarts"
+                    .to_string(),
+                format!(
+                    "Article cache hit rate 12% — consider increasing `article_cache_ttl_secs` (now {})",
+                    network.article_cache_ttl_secs
+                ),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_fetch_story_ids_success() {
         let mut server = mockito::Server::new_async().await;
@@ -706,7 +1379,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_fetch_comment_content_success() {
+    async fn test_fetch_item_comment() {
         let mut server = mockito::Server::new_async().await;
         let comment_json = r#"{
             "by": "commenter",
@@ -726,18 +1399,65 @@ mod tests {
             .create();
 
         let service = ApiService::with_base_url(format!("{}/", server.url()));
-        let result = service.fetch_comment_content(67890).await;
+        let result = service.fetch_item(67890).await;
 
         mock.assert();
-        assert!(result.is_ok());
-        let comment = result.unwrap();
+        let Ok(Item::Comment(comment)) = result else {
+            panic!("expected a comment, got {:?}", result);
+        };
         assert_eq!(comment.id, 67890);
         assert_eq!(comment.by, Some("commenter".to_string()));
         assert_eq!(comment.text, Some("This is a comment".to_string()));
     }
 
     #[tokio::test]
-    async fn test_fetch_comment_content_http_error() {
+    async fn test_fetch_item_by_type_and_comment_tree_skips_other_kids() {
+        let mut server = mockito::Server::new_async().await;
+        let items = [
+            (
+                1,
+                r#"{"id": 1, "type": "comment", "by": "a", "text": "Hi", "kids": [2, 3]}"#,
+            ),
+            (
+                2,
+                r#"{"id": 2, "type": "story", "title": "Linked", "kids": [4]}"#,
+            ),
+            (
+                3,
+                r#"{"id": 3, "type": "comment", "by": "b", "text": "Reply"}"#,
+            ),
+            (5, r#"{"id": 5, "type": "job", "title": "Hiring"}"#),
+            (
+                6,
+                r#"{"id": 6, "type": "pollopt", "poll": 7, "text": "Yes", "score": 3}"#,
+            ),
+        ];
+        for (id, body) in items {
+            server
+                .mock("GET", format!("/item/{}.json", id).as_str())
+                .with_status(200)
+                .with_body(body)
+                .create();
+        }
+
+        let service = ApiService::with_base_url(format!("{}/", server.url()));
+        assert!(matches!(service.fetch_item(1).await.unwrap(), Item::Comment(c) if c.id == 1));
+        let Item::Job(job) = service.fetch_item(5).await.unwrap() else {
+            panic!("expected a job");
+        };
+        assert!(job.is_job());
+        assert!(
+            matches!(service.fetch_item(6).await.unwrap(), Item::PollOpt(o) if o.poll == Some(7))
+        );
+
+        // The story among the kids is left out, along with its own kids
+        let rows = service.fetch_comment_tree(vec![1], 5, None).await.unwrap();
+        let ids: Vec<u32> = rows.iter().map(|row| row.comment.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_item_http_error() {
         let mut server = mockito::Server::new_async().await;
         let mock = server
             .mock("GET", "/item/99999.json")
@@ -745,11 +1465,91 @@ mod tests {
             .create();
 
         let service = ApiService::with_base_url(format!("{}/", server.url()));
-        let result = service.fetch_comment_content(99999).await;
+        let result = service.fetch_item(99999).await;
 
         mock.assert();
-        // reqwest will succeed on 404 but JSON parsing should fail
-        assert!(result.is_err());
+        let error = format!("{:#}", result.unwrap_err());
+        assert!(error.contains("404"), "unexpected error: {}", error);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_opens_after_repeated_failures() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/item/1.json")
+            .with_status(503)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let mut service = ApiService::with_base_url(format!("{}/", server.url()));
+        service.breaker = Arc::new(CircuitBreaker::new(2, Duration::from_secs(60)));
+        for _ in 0..2 {
+            assert!(service.fetch_item(1).await.is_err());
+        }
+        assert!(matches!(service.api_health(), ApiHealth::Degraded { .. }));
+
+        // Further requests fail fast without reaching the server
+        let error = service.fetch_item(1).await.unwrap_err();
+        let app_error = crate::internal::error::AppError::new(
+            crate::internal::error::Operation::LoadComments(1),
+            &error,
+        );
+        assert!(
+            app_error.kind == crate::internal::error::ErrorKind::Network
+                && app_error.message.contains("degraded"),
+            "unexpected error: {:#}",
+            error
+        );
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_are_retried() {
+        let mut server = mockito::Server::new_async().await;
+        let unavailable = server
+            .mock("GET", "/maxitem.json")
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+        let ok = server
+            .mock("GET", "/maxitem.json")
+            .with_body("42")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let service = ApiService::with_base_url(format!("{}/", server.url())).with_network(
+            crate::config::NetworkConfig {
+                initial_retry_delay_ms: 10,
+                ..Default::default()
+            },
+        );
+        let body = service
+            .fetch_raw(format!("{}/maxitem.json", server.url()))
+            .await;
+
+        assert_eq!(body.unwrap().as_str(), "42");
+        unavailable.assert_async().await;
+        ok.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_requests_send_user_agent() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/topstories.json")
+            .match_header("user-agent", USER_AGENT)
+            .with_body("[1]")
+            .create_async()
+            .await;
+
+        let service = ApiService::with_base_url(format!("{}/", server.url()));
+        let ids = service.fetch_story_ids(StoryListType::Top, None).await;
+
+        mock.assert_async().await;
+        assert_eq!(ids.unwrap(), vec![1]);
     }
 
     #[test]
@@ -0,0 +1,197 @@
+//! Token-bucket rate limiting of outgoing requests. Each host has its own bucket,
+//! so the HN API and the sites articles come from are limited separately: a bucket
+//! holds up to `rate_limit_per_second` tokens (at least one) and refills at that
+//! rate, and every request takes a token or waits for the next one.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Buckets kept before idle (full) ones are dropped, since every article host gets one.
+const MAX_BUCKETS: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn full(capacity: f64, now: Instant) -> Self {
+        Self {
+            tokens: capacity,
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, rate: f64, capacity: f64, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(capacity);
+        self.updated = now;
+    }
+
+    /// Take a token, or say how long until the next one.
+    fn take(&mut self, rate: f64, capacity: f64, now: Instant) -> Result<(), Duration> {
+        self.refill(rate, capacity, now);
+        match self.tokens >= 1.0 {
+            true => {
+                self.tokens -= 1.0;
+                Ok(())
+            }
+            false => Err(Duration::from_secs_f64((1.0 - self.tokens) / rate)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// Tokens added per second
+    rate: f64,
+    capacity: f64,
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+    /// Requests waiting for a token
+    waiting: AtomicUsize,
+}
+
+impl RateLimiter {
+    pub fn new(rate_per_second: f64) -> Self {
+        let rate = match rate_per_second.is_finite() && rate_per_second > 0.0 {
+            true => rate_per_second,
+            false => 1.0,
+        };
+        Self {
+            rate,
+            capacity: rate.max(1.0),
+            buckets: Mutex::new(HashMap::new()),
+            waiting: AtomicUsize::new(0),
+        }
+    }
+
+    /// Take a token for `host` now, or say how long until one is available.
+    fn try_acquire(&self, host: &str, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() >= MAX_BUCKETS && !buckets.contains_key(host) {
+            let (rate, capacity) = (self.rate, self.capacity);
+            buckets.retain(|_, bucket| {
+                bucket.refill(rate, capacity, now);
+                bucket.tokens < capacity
+            });
+        }
+        buckets
+            .entry(host.to_string())
+            .or_insert_with(|| TokenBucket::full(self.capacity, now))
+            .take(self.rate, self.capacity, now)
+    }
+
+    /// Wait until a request to `host` may be sent.
+    pub async fn acquire(&self, host: &str) {
+        let mut waiting = None;
+        while let Err(wait) = self.try_acquire(host, Instant::now()) {
+            if waiting.is_none() {
+                waiting = Some(Waiting::new(&self.waiting));
+            }
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Requests currently held back by the limit, for the status bar.
+    pub fn waiting(&self) -> usize {
+        self.waiting.load(Ordering::Relaxed)
+    }
+}
+
+/// Counts a request as waiting for as long as it lives, so a request dropped
+/// while it waits (a cancelled fetch) is not left counted.
+struct Waiting<'a>(&'a AtomicUsize);
+
+impl<'a> Waiting<'a> {
+    fn new(count: &'a AtomicUsize) -> Self {
+        count.fetch_add(1, Ordering::Relaxed);
+        Self(count)
+    }
+}
+
+impl Drop for Waiting<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Bucket name for a URL: its host, or the whole URL when it has none.
+pub fn host_of(url: &str) -> String {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_refill_over_time_per_host() {
+        let limiter = RateLimiter::new(2.0);
+        let now = Instant::now();
+        // A full bucket allows a burst of `rate` requests
+        assert!(
+            limiter
+                .try_acquire("hacker-news.firebaseio.com", now)
+                .is_ok()
+        );
+        assert!(
+            limiter
+                .try_acquire("hacker-news.firebaseio.com", now)
+                .is_ok()
+        );
+        let wait = limiter
+            .try_acquire("hacker-news.firebaseio.com", now)
+            .unwrap_err();
+        assert_eq!(wait, Duration::from_millis(500));
+        // Other hosts have their own bucket
+        assert!(limiter.try_acquire("example.com", now).is_ok());
+
+        let later = now + Duration::from_millis(500);
+        assert!(
+            limiter
+                .try_acquire("hacker-news.firebaseio.com", later)
+                .is_ok()
+        );
+        assert!(
+            limiter
+                .try_acquire("hacker-news.firebaseio.com", later)
+                .is_err()
+        );
+
+        // Slow rates still allow one request at a time
+        let slow = RateLimiter::new(0.5);
+        assert!(slow.try_acquire("example.com", now).is_ok());
+        assert_eq!(
+            slow.try_acquire("example.com", now).unwrap_err(),
+            Duration::from_secs(2)
+        );
+    }
+
+    #[tokio::test]
+    async fn dropped_waiters_are_not_counted() {
+        let limiter = RateLimiter::new(1.0);
+        limiter.acquire("example.com").await;
+        let wait = tokio::time::timeout(Duration::from_millis(50), limiter.acquire("example.com"));
+        assert!(wait.await.is_err());
+        assert_eq!(limiter.waiting(), 0);
+    }
+
+    #[test]
+    fn idle_buckets_are_dropped() {
+        let limiter = RateLimiter::new(1.0);
+        let now = Instant::now();
+        for i in 0..MAX_BUCKETS {
+            let _ = limiter.try_acquire(&format!("site{}.example", i), now);
+        }
+        let later = now + Duration::from_secs(5);
+        let _ = limiter.try_acquire("another.example", later);
+        assert_eq!(limiter.buckets.lock().unwrap().len(), 1);
+        assert_eq!(host_of("https://example.com/a?b"), "example.com");
+    }
+}
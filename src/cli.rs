@@ -0,0 +1,508 @@
+//! Command-line arguments and the headless commands that run without the TUI.
+
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::api::{ApiService, StoryListType};
+use crate::config::{AppConfig, ListColumn, ListExportFormat};
+use crate::internal::export;
+use crate::internal::models::{CommentRow, Story};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "tui-hn-app",
+    version,
+    about = "Browse Hacker News in the terminal"
+)]
+pub struct Cli {
+    /// Story list to open at startup
+    #[arg(long, value_enum)]
+    pub list: Option<StoryListType>,
+    /// Start without network access (bookmarks, history and cached content only)
+    #[arg(long)]
+    pub offline: bool,
+    /// Config file to use instead of ./config.ron
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+    /// Theme to use instead of the configured `theme_name`
+    #[arg(long, value_name = "NAME")]
+    pub theme: Option<String>,
+    /// Open a custom list of the HN item ids/URLs in this file (one per line)
+    #[arg(long, value_name = "PATH", conflicts_with = "list")]
+    pub ids: Option<PathBuf>,
+    /// Check the config, theme files and keybindings, print the problems found and
+    /// exit (nonzero if there are any)
+    #[arg(long)]
+    pub validate: bool,
+    /// Record the session's spans as a Chrome trace, to PATH or to a
+    /// timestamped file in the log directory
+    #[arg(long, value_name = "PATH", require_equals = true)]
+    pub profile: Option<Option<PathBuf>>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Write bookmarks as JSON
+    ExportBookmarks {
+        /// Write to a file instead of stdout
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+    /// Add bookmarks from a Pocket or browser HTML export, a bookmarks JSON export,
+    /// or a list of HN item ids/URLs
+    ImportBookmarks {
+        #[arg(value_name = "PATH")]
+        path: PathBuf,
+    },
+    /// Print a story and its comment thread
+    Dump {
+        /// Story (or any item) id
+        #[arg(long, value_name = "ID")]
+        story: u32,
+        /// Print JSON instead of Markdown
+        #[arg(long)]
+        json: bool,
+        /// Maximum comment depth to fetch
+        #[arg(long, default_value_t = 3)]
+        depth: usize,
+    },
+    /// Write a story list to CSV/TSV
+    ExportList(ExportListArgs),
+    /// Print a story list, optionally with comments
+    Fetch(FetchArgs),
+    /// Download stories, articles and comments into the cache for reading offline
+    OfflinePack(OfflinePackArgs),
+}
+
+#[derive(Debug, Clone, PartialEq, Args)]
+pub struct OfflinePackArgs {
+    /// Comma separated story lists [default: offline_pack.lists]
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub list: Vec<StoryListType>,
+    /// Stories from the top of each list [default: offline_pack.stories_per_list]
+    #[arg(long)]
+    pub limit: Option<usize>,
+    /// Maximum comment depth [default: offline_pack.comment_depth]
+    #[arg(long)]
+    pub depth: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Args)]
+pub struct FetchArgs {
+    /// Story list to fetch
+    #[arg(value_enum, default_value = "top")]
+    pub list: StoryListType,
+    /// Number of stories
+    #[arg(long, default_value_t = 30)]
+    pub limit: usize,
+    /// Print JSON instead of one line per story
+    #[arg(long)]
+    pub json: bool,
+    /// Include each story's comment thread
+    #[arg(long)]
+    pub comments: bool,
+    /// Maximum comment depth to fetch with --comments
+    #[arg(long, default_value_t = 3)]
+    pub depth: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Args)]
+pub struct ExportListArgs {
+    /// Story list to export [default: top]
+    #[arg(long, value_enum)]
+    pub list: Option<StoryListType>,
+    /// Number of stories
+    #[arg(long, default_value_t = 30)]
+    pub limit: usize,
+    /// Output format [default: export.list_format]
+    #[arg(long, value_enum)]
+    pub format: Option<ListExportFormat>,
+    /// Comma separated columns [default: export.list_columns]
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub columns: Vec<ListColumn>,
+    /// Write to a file instead of stdout
+    #[arg(long, short, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+}
+
+impl Cli {
+    /// Load the config selected by `--config` and apply `--theme`.
+    pub fn load_config(&self) -> Result<AppConfig> {
+        let mut config = match &self.config {
+            Some(path) => AppConfig::load_from(path)?,
+            None => AppConfig::load(),
+        };
+        if let Some(theme) = &self.theme {
+            config.theme_name = theme.clone();
+        }
+        Ok(config)
+    }
+
+    /// Item ids from the `--ids` file, if given.
+    pub fn custom_list(&self) -> Result<Option<Vec<u32>>> {
+        let Some(path) = &self.ids else {
+            return Ok(None);
+        };
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        match crate::utils::url::parse_item_ids(&content) {
+            ids if ids.is_empty() => anyhow::bail!("No HN item ids found in {}", path.display()),
+            ids => Ok(Some(ids)),
+        }
+    }
+}
+
+fn write_output(output: Option<&Path>, content: &str) -> Result<()> {
+    match output {
+        Some(path) => std::fs::write(path, content)
+            .with_context(|| format!("Failed to write {}", path.display())),
+        None => std::io::stdout()
+            .write_all(content.as_bytes())
+            .context("Failed to write to stdout"),
+    }
+}
+
+/// `--validate`: print what was checked and the problems found. Returns whether
+/// everything was valid.
+pub fn validate(path: Option<&Path>) -> bool {
+    let report = crate::config::validate::validate(path);
+    for problem in &report.problems {
+        eprintln!("error: {}", problem);
+    }
+    println!(
+        "Checked {}: {}",
+        report.checked.join(", "),
+        match report.problems.len() {
+            0 => "no problems".to_string(),
+            1 => "1 problem".to_string(),
+            n => format!("{} problems", n),
+        }
+    );
+    report.problems.is_empty()
+}
+
+/// Run a headless subcommand to completion.
+pub async fn run(command: &Command, config: &AppConfig, offline: bool) -> Result<()> {
+    let api = ApiService::new(
+        config.network.clone(),
+        config.logging.enable_performance_metrics,
+    )
+    .with_offline(offline);
+
+    let result = run_command(&api, command, config).await;
+    api.persist_caches()?;
+    result
+}
+
+async fn run_command(api: &ApiService, command: &Command, config: &AppConfig) -> Result<()> {
+    match command {
+        Command::ExportBookmarks { output } => {
+            let bookmarks = crate::internal::bookmarks::Bookmarks::load_or_create()?;
+            let content = serde_json::to_string_pretty(&bookmarks)
+                .context("Failed to serialize bookmarks")?;
+            write_output(output.as_deref(), &(content + "\n"))
+        }
+        Command::ImportBookmarks { path } => {
+            use crate::internal::bookmark_import;
+            let text = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let parsed = bookmark_import::parse(&text);
+            let mut bookmarks = crate::internal::bookmarks::Bookmarks::load_or_create()?;
+            let existing = bookmarks.stories.iter().map(|b| b.id).collect();
+            let found = parsed.entries.len();
+            let imported = bookmark_import::resolve(
+                api,
+                parsed.entries,
+                &existing,
+                config.network.concurrent_requests,
+            )
+            .await;
+            let skipped = parsed.skipped + found - imported.len();
+            let added = bookmarks.merge(imported);
+            bookmarks.save()?;
+            write_output(
+                None,
+                &format!("Imported {} bookmarks, skipped {}\n", added, skipped),
+            )
+        }
+        Command::Dump { story, json, depth } => {
+            let content = dump_story(api, *story, *depth, *json).await?;
+            write_output(None, &content)
+        }
+        Command::ExportList(args) => {
+            let content = export_list(api, args, config).await?;
+            write_output(args.output.as_deref(), &content)
+        }
+        Command::Fetch(args) => {
+            let content = fetch(api, args, config).await?;
+            write_output(None, &content)
+        }
+        Command::OfflinePack(args) => {
+            use crate::internal::offline_pack::{self, OfflinePack};
+            let defaults = &config.offline_pack;
+            let pack_config = crate::config::OfflinePackConfig {
+                lists: match args.list.is_empty() {
+                    true => defaults.lists.clone(),
+                    false => args.list.clone(),
+                },
+                stories_per_list: args.limit.unwrap_or(defaults.stories_per_list),
+                comment_depth: args.depth.unwrap_or(defaults.comment_depth),
+            };
+            let mut pack = OfflinePack::load_or_create()?;
+            let summary = offline_pack::prepare(
+                api,
+                &pack_config,
+                config.network.concurrent_requests,
+                &mut pack,
+                |done, total| eprint!("\rDownloading {}/{} stories", done, total),
+            )
+            .await;
+            eprintln!();
+            let mut out = summary.line() + "\n";
+            for failure in &summary.failures {
+                out.push_str(&format!("  failed: {}\n", failure));
+            }
+            write_output(None, &out)
+        }
+    }
+}
+
+/// Fetch the first `limit` stories of a list, in HN rank order.
+async fn fetch_ranked(
+    api: &ApiService,
+    list: StoryListType,
+    limit: usize,
+    config: &AppConfig,
+) -> Result<Vec<Story>> {
+    let ids = api.fetch_story_ids(list, None).await?;
+    let ids: Vec<u32> = ids.into_iter().take(limit).collect();
+
+    // Concurrent fetches complete out of order; restore the list's ranking
+    let mut stories: Vec<_> = api
+        .fetch_stories_concurrent(&ids, config.network.concurrent_requests, None)
+        .await
+        .into_iter()
+        .filter_map(Result::ok)
+        .collect();
+    stories.sort_by_key(|story| ids.iter().position(|id| *id == story.id));
+    Ok(stories)
+}
+
+/// JSON shape of a fetched comment, shared by `dump` and `fetch`.
+fn comment_json(row: &CommentRow) -> serde_json::Value {
+    serde_json::json!({
+        "id": row.comment.id,
+        "parent_id": row.parent_id,
+        "depth": row.depth,
+        "by": row.comment.by,
+        "time": row.comment.time,
+        "text": row.comment.text,
+        "deleted": row.comment.deleted,
+        "dead": row.comment.dead,
+    })
+}
+
+async fn fetch_comments(api: &ApiService, story: &Story, depth: usize) -> Result<Vec<CommentRow>> {
+    match &story.kids {
+        Some(kids) if !kids.is_empty() => api.fetch_comment_tree(kids.clone(), depth, None).await,
+        _ => Ok(Vec::new()),
+    }
+}
+
+async fn fetch(api: &ApiService, args: &FetchArgs, config: &AppConfig) -> Result<String> {
+    let stories = fetch_ranked(api, args.list, args.limit, config).await?;
+
+    let mut comments = Vec::with_capacity(stories.len());
+    for story in stories.iter().filter(|_| args.comments) {
+        comments.push(fetch_comments(api, story, args.depth).await?);
+    }
+
+    match args.json {
+        true => {
+            let items: Vec<_> = stories
+                .iter()
+                .enumerate()
+                .map(|(i, story)| match comments.get(i) {
+                    Some(rows) => serde_json::json!({
+                        "story": story,
+                        "comments": rows.iter().map(comment_json).collect::<Vec<_>>(),
+                    }),
+                    None => serde_json::json!({ "story": story }),
+                })
+                .collect();
+            let content =
+                serde_json::to_string_pretty(&items).context("Failed to serialize stories")?;
+            Ok(content + "\n")
+        }
+        false => {
+            let mut out = String::new();
+            for (i, story) in stories.iter().enumerate() {
+                out.push_str(&format!(
+                    "{:>3}. {} ({} points, {} comments) {}\n",
+                    i + 1,
+                    story.title.as_deref().unwrap_or("No Title"),
+                    story.score.unwrap_or(0),
+                    story.descendants.unwrap_or(0),
+                    story.url.as_deref().unwrap_or(""),
+                ));
+                for row in comments.get(i).into_iter().flatten() {
+                    let text = row
+                        .comment
+                        .text
+                        .as_deref()
+                        .map(crate::utils::html::extract_text_from_html)
+                        .unwrap_or_default();
+                    out.push_str(&format!(
+                        "{}     {}: {}\n",
+                        "  ".repeat(row.depth),
+                        row.comment.by.as_deref().unwrap_or("[deleted]"),
+                        text.replace('\n', " "),
+                    ));
+                }
+            }
+            Ok(out)
+        }
+    }
+}
+
+async fn dump_story(api: &ApiService, id: u32, depth: usize, json: bool) -> Result<String> {
+    let story = api.fetch_story_content(id).await?;
+    let comments = fetch_comments(api, &story, depth).await?;
+
+    match json {
+        true => {
+            let comments: Vec<_> = comments.iter().map(comment_json).collect();
+            let value = serde_json::json!({ "story": story, "comments": comments });
+            let content =
+                serde_json::to_string_pretty(&value).context("Failed to serialize story")?;
+            Ok(content + "\n")
+        }
+        false => Ok(export::render_markdown(&export::StoryExport {
+            story: &story,
+            article: None,
+            comments: &comments,
+        })),
+    }
+}
+
+/// Fetch the requested list in HN rank order and render it as CSV/TSV.
+async fn export_list(
+    api: &ApiService,
+    args: &ExportListArgs,
+    config: &AppConfig,
+) -> Result<String> {
+    let list = args.list.unwrap_or(StoryListType::Top);
+    let stories = fetch_ranked(api, list, args.limit, config).await?;
+
+    let columns = match args.columns.is_empty() {
+        true => &config.export.list_columns,
+        false => &args.columns,
+    };
+    let format = args.format.unwrap_or(config.export.list_format);
+    Ok(export::render_story_list(&stories, columns, format))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_startup_flags() {
+        let cli = Cli::try_parse_from([
+            "tui-hn-app",
+            "--list",
+            "ask",
+            "--offline",
+            "--theme",
+            "Gruvbox Dark",
+        ])
+        .unwrap();
+        assert_eq!(cli.list, Some(StoryListType::Ask));
+        assert!(cli.offline);
+        assert_eq!(cli.theme.as_deref(), Some("Gruvbox Dark"));
+        assert!(cli.command.is_none());
+        assert!(Cli::try_parse_from(["tui-hn-app", "--list", "bogus"]).is_err());
+        assert!(Cli::try_parse_from(["tui-hn-app", "--list", "custom"]).is_err());
+    }
+
+    #[test]
+    fn parses_headless_commands() {
+        let cli = Cli::try_parse_from(["tui-hn-app", "dump", "--story", "42", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Dump {
+                story: 42,
+                json: true,
+                depth: 3
+            })
+        ));
+
+        let cli = Cli::try_parse_from(["tui-hn-app", "import-bookmarks", "pocket.html"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::ImportBookmarks { path }) if path == Path::new("pocket.html")
+        ));
+
+        let cli = Cli::try_parse_from([
+            "tui-hn-app",
+            "export-list",
+            "--list",
+            "best",
+            "--format",
+            "tsv",
+            "--columns",
+            "rank,title",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Command::ExportList(args)) => {
+                assert_eq!(args.list, Some(StoryListType::Best));
+                assert_eq!(args.format, Some(ListExportFormat::Tsv));
+                assert_eq!(args.columns, vec![ListColumn::Rank, ListColumn::Title]);
+                assert_eq!(args.limit, 30);
+            }
+            other => panic!("unexpected command {:?}", other),
+        }
+        assert!(Cli::try_parse_from(["tui-hn-app", "export-list", "--columns", "votes"]).is_err());
+
+        let cli =
+            Cli::try_parse_from(["tui-hn-app", "fetch", "show", "--limit", "5", "--json"]).unwrap();
+        match cli.command {
+            Some(Command::Fetch(args)) => {
+                assert_eq!(args.list, StoryListType::Show);
+                assert_eq!(args.limit, 5);
+                assert!(args.json && !args.comments);
+            }
+            other => panic!("unexpected command {:?}", other),
+        }
+        let cli = Cli::try_parse_from([
+            "tui-hn-app",
+            "offline-pack",
+            "--list",
+            "top,ask",
+            "--limit",
+            "10",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Command::OfflinePack(args)) => {
+                assert_eq!(args.list, vec![StoryListType::Top, StoryListType::Ask]);
+                assert_eq!((args.limit, args.depth), (Some(10), None));
+            }
+            other => panic!("unexpected command {:?}", other),
+        }
+
+        let cli = Cli::try_parse_from(["tui-hn-app", "fetch"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Fetch(FetchArgs {
+                list: StoryListType::Top,
+                ..
+            }))
+        ));
+    }
+}
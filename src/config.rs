@@ -1,11 +1,15 @@
 use regex::RegexBuilder;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::internal::ui::app::Action;
 use std::collections::HashMap;
 
+pub mod migration;
+pub mod settings;
+pub mod validate;
+
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct KeyBindingConfig {
     #[serde(default)]
@@ -20,6 +24,8 @@ pub struct KeyBindingConfig {
     pub bookmarks: HashMap<String, Action>,
     #[serde(default)]
     pub history: HashMap<String, Action>,
+    #[serde(default)]
+    pub reading_lists: HashMap<String, Action>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -28,6 +34,123 @@ pub struct UIConfig {
     pub padding: PaddingConfig,
     pub status_bar_format: String,
     pub list_view: ListViewConfig,
+    /// Low-bandwidth rendering for SSH sessions (Auto detects `SSH_CONNECTION`)
+    pub low_bandwidth: LowBandwidthMode,
+    /// Keep the story list visible beside the comments/article view
+    pub split_layout: bool,
+    /// Width of the list pane in the split layout, in percent
+    #[serde(default = "default_split_ratio")]
+    pub split_ratio: u16,
+    /// How long a chord prefix such as `y` waits for its second key
+    #[serde(default = "default_chord_timeout_ms")]
+    pub chord_timeout_ms: u64,
+    /// Show where a link redirects to, and ask for confirmation, before opening it
+    pub confirm_links: bool,
+    /// Make URLs clickable with OSC 8 hyperlinks in terminals known to support them
+    pub hyperlinks: bool,
+    /// Rows of the message line above the status bar (0 = no message line). With a
+    /// message line, notifications, match counts and chord hints go there and the
+    /// status bar keeps its keybinding hints.
+    pub message_line_height: u16,
+    /// Content of the message line, with the same tokens as `status_bar_format`
+    #[serde(default = "default_message_line_format")]
+    pub message_line_format: String,
+    /// Row under the top bar listing the story categories with their story count
+    /// and when they were last loaded
+    pub category_bar: bool,
+    /// Sort applied when switching to a category, e.g. `{New: (by: Time)}`
+    pub category_sorts: HashMap<crate::api::StoryListType, CategorySort>,
+    /// What the comment pane shows in place of the scores HN does not publish
+    pub comment_hints: CommentHintsConfig,
+    /// How timestamps are shown: `Relative` ("3h ago"), `Absolute` (local date and
+    /// time) or `Iso8601`; `Ctrl+t` cycles through them while the app runs
+    pub time_format: crate::utils::datetime::TimeFormat,
+    /// How long toasts stay up by severity, and how many are stacked at once
+    pub notifications: NotificationConfig,
+}
+
+/// Toast notifications; the notification center (`N`) keeps the last 50.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct NotificationConfig {
+    /// How long info toasts stay up, in milliseconds
+    pub info_ms: u64,
+    pub warning_ms: u64,
+    pub error_ms: u64,
+    /// Toasts shown at once; the oldest goes when another comes
+    pub max_toasts: usize,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            info_ms: 3_000,
+            warning_ms: 5_000,
+            error_ms: 10_000,
+            max_toasts: 3,
+        }
+    }
+}
+
+/// Signals of a comment's standing, since HN does not publish comment scores.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(default)]
+pub struct CommentHintsConfig {
+    /// Mark dead (flagged or killed) comments and grey them out
+    pub mark_dead: bool,
+    /// Show each comment's position among its siblings, which is how HN ranks them
+    pub sibling_rank: bool,
+    /// Order siblings by their number of replies, most first, as a proxy for prominence
+    pub sort_by_replies: bool,
+    /// Tag comments by the story's submitter with "OP" and your own with "you"
+    pub author_badges: bool,
+    /// Give each author a color derived from their name, so exchanges between the
+    /// same people stand out; uses `list_view.domain_palette`
+    pub author_colors: bool,
+}
+
+impl Default for CommentHintsConfig {
+    fn default() -> Self {
+        Self {
+            mark_dead: true,
+            sibling_rank: false,
+            sort_by_replies: false,
+            author_badges: true,
+            author_colors: false,
+        }
+    }
+}
+
+/// Default sort of a story category.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub struct CategorySort {
+    pub by: crate::internal::ui::sort::SortBy,
+    #[serde(default = "default_category_sort_order")]
+    pub order: crate::internal::ui::sort::SortOrder,
+}
+
+fn default_category_sort_order() -> crate::internal::ui::sort::SortOrder {
+    crate::internal::ui::sort::SortOrder::Descending
+}
+
+fn default_message_line_format() -> String {
+    "{chord} {matches} {message}".to_string()
+}
+
+fn default_split_ratio() -> u16 {
+    40
+}
+
+fn default_chord_timeout_ms() -> u64 {
+    1000
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LowBandwidthMode {
+    #[default]
+    Auto,
+    On,
+    Off,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -45,6 +168,44 @@ pub struct ListViewConfig {
     pub show_comments: bool,
     pub show_age: bool,
     pub show_author: bool,
+    /// Show score/comment/rank changes since the previous launch
+    pub show_deltas: bool,
+    /// Render the domain as a colored badge instead of plain "(domain)" text
+    pub domain_chips: bool,
+    /// Colors used for domain chips
+    pub domain_palette: DomainPalette,
+    /// Show the publisher's name instead of the domain when it is known
+    pub publisher_names: bool,
+    /// Put the publisher's initial, in its brand color, in front of the domain chip
+    pub domain_icons: bool,
+    /// Show clickable Score/Comments/Time sort columns in the list border
+    pub sort_header: bool,
+    /// Show the reading time of stories whose article has been loaded
+    pub show_reading_time: bool,
+    /// Lay rows out by this template instead of the `show_*` fields, e.g.
+    /// `"{rank} {score} {title} [{domain}] — {age} · {comments}c by {author}"`
+    pub row_template: Option<String>,
+    /// Sparkline of how a story's score moved as it refreshed
+    pub trends: TrendsMode,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TrendsMode {
+    Off,
+    /// Track scores while the app runs
+    #[default]
+    Session,
+    /// Keep them across launches in `story_trends.json`
+    Persist,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DomainPalette {
+    /// Theme-aware palette (soft colors on dark themes, deeper ones on light themes)
+    #[default]
+    Default,
+    /// Okabe-Ito palette, safe for common color vision deficiencies
+    ColorBlind,
 }
 
 impl Default for UIConfig {
@@ -56,6 +217,19 @@ impl Default for UIConfig {
                 "{spinner} {mode} | {category} | {count}/{total} | {sort} {order} | {shortcuts} {loading_text}"
                     .to_string(),
             list_view: ListViewConfig::default(),
+            low_bandwidth: LowBandwidthMode::default(),
+            split_layout: false,
+            split_ratio: default_split_ratio(),
+            chord_timeout_ms: default_chord_timeout_ms(),
+            confirm_links: false,
+            hyperlinks: true,
+            message_line_height: 0,
+            message_line_format: default_message_line_format(),
+            category_bar: false,
+            category_sorts: HashMap::new(),
+            comment_hints: CommentHintsConfig::default(),
+            time_format: Default::default(),
+            notifications: NotificationConfig::default(),
         }
     }
 }
@@ -77,6 +251,15 @@ impl Default for ListViewConfig {
             show_comments: true,
             show_age: true,
             show_author: true,
+            show_deltas: true,
+            domain_chips: true,
+            domain_palette: DomainPalette::default(),
+            publisher_names: true,
+            domain_icons: true,
+            sort_header: false,
+            show_reading_time: false,
+            row_template: None,
+            trends: TrendsMode::default(),
         }
     }
 }
@@ -85,13 +268,22 @@ impl Default for ListViewConfig {
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
 #[serde(default)]
 pub struct AccessibilityConfig {
-    /// Enable high contrast mode (uses high contrast theme colors)
+    /// Derive a high-contrast variant of whichever theme is active, pushing its
+    /// colors past the WCAG contrast thresholds
     pub high_contrast_mode: bool,
+    /// Move the active theme's hues onto a palette that stays distinguishable with
+    /// deuteranopia and protanopia (red-green color blindness)
+    pub color_blind_safe: bool,
+    /// Draw in the terminal's default colors, marking emphasis with bold, underline
+    /// and reverse video only; also turned on by the `NO_COLOR` environment variable
+    pub no_color: bool,
+    /// Mark the selected row with a margin marker and full-row inverse video
+    pub prominent_selection: bool,
     /// Show more verbose status descriptions for screen readers
     pub verbose_status: bool,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(default)]
 pub struct NetworkConfig {
     /// Maximum number of retry attempts (0 = no retries)
@@ -105,9 +297,59 @@ pub struct NetworkConfig {
     /// Maximum number of concurrent requests
     #[serde(default = "default_concurrent_requests")]
     pub concurrent_requests: usize,
-    /// Rate limit in requests per second
+    /// Rate limit in requests per second, per host
     #[serde(default = "default_rate_limit_per_second")]
     pub rate_limit_per_second: f64,
+    /// Interval in seconds between background refreshes of visible stories (0 = disabled)
+    pub refresh_interval_secs: u64,
+    /// Number of rows above/below the viewport that are refreshed along with visible rows
+    #[serde(default = "default_refresh_margin")]
+    pub refresh_margin: usize,
+    /// Interval in seconds between polls of the HN updates feed (0 = disabled).
+    /// Changed items are dropped from the cache and visible rows update in place.
+    pub live_updates_interval_secs: u64,
+    /// How long fetched stories stay fresh in the cache, in seconds
+    #[serde(default = "default_story_cache_ttl_secs")]
+    pub story_cache_ttl_secs: u64,
+    /// Cache freshness of stories posted within the last hour, whose score and
+    /// comment count change quickly
+    #[serde(default = "default_new_story_cache_ttl_secs")]
+    pub new_story_cache_ttl_secs: u64,
+    /// Cache freshness of stories older than a day, which rarely change
+    #[serde(default = "default_old_story_cache_ttl_secs")]
+    pub old_story_cache_ttl_secs: u64,
+    /// How long fetched comments stay fresh in the cache, in seconds
+    #[serde(default = "default_comment_cache_ttl_secs")]
+    pub comment_cache_ttl_secs: u64,
+    /// How long extracted articles stay fresh in the cache, in seconds
+    #[serde(default = "default_article_cache_ttl_secs")]
+    pub article_cache_ttl_secs: u64,
+    /// Maximum entries per cache (stories, comments, articles); oldest are evicted first
+    #[serde(default = "default_cache_max_entries")]
+    pub cache_max_entries: usize,
+    /// Save the caches on exit and load them on start
+    pub disk_cache: bool,
+    /// Where the disk cache is stored (default: ~/.cache/tui-hn-app)
+    pub disk_cache_directory: Option<String>,
+    /// Look up the name and brand color of story sites on their home pages
+    pub fetch_domain_metadata: bool,
+    /// Timeout for a whole request (connect, send and read the body), in seconds
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+    /// Timeout for establishing a connection, in seconds
+    #[serde(default = "default_connect_timeout_secs")]
+    pub connect_timeout_secs: u64,
+    /// User-Agent header (default: "tui-hn-app/<version>")
+    pub user_agent: Option<String>,
+    /// Proxy for all requests, e.g. "http://proxy:8080".
+    /// Without it, HTTP_PROXY/HTTPS_PROXY/NO_PROXY from the environment apply.
+    pub proxy: Option<String>,
+    /// Consecutive failed API requests that pause requests to the API (0 = never)
+    #[serde(default = "default_circuit_breaker_threshold")]
+    pub circuit_breaker_threshold: u32,
+    /// How long requests stay paused before a probe checks whether the API recovered
+    #[serde(default = "default_circuit_breaker_cooldown_secs")]
+    pub circuit_breaker_cooldown_secs: u64,
 }
 
 impl Default for NetworkConfig {
@@ -119,6 +361,24 @@ impl Default for NetworkConfig {
             retry_on_timeout: true,
             concurrent_requests: default_concurrent_requests(),
             rate_limit_per_second: default_rate_limit_per_second(),
+            refresh_interval_secs: 0,
+            refresh_margin: default_refresh_margin(),
+            live_updates_interval_secs: 0,
+            story_cache_ttl_secs: default_story_cache_ttl_secs(),
+            new_story_cache_ttl_secs: default_new_story_cache_ttl_secs(),
+            old_story_cache_ttl_secs: default_old_story_cache_ttl_secs(),
+            comment_cache_ttl_secs: default_comment_cache_ttl_secs(),
+            article_cache_ttl_secs: default_article_cache_ttl_secs(),
+            cache_max_entries: default_cache_max_entries(),
+            disk_cache: false,
+            disk_cache_directory: None,
+            fetch_domain_metadata: false,
+            request_timeout_secs: default_request_timeout_secs(),
+            connect_timeout_secs: default_connect_timeout_secs(),
+            user_agent: None,
+            proxy: None,
+            circuit_breaker_threshold: default_circuit_breaker_threshold(),
+            circuit_breaker_cooldown_secs: default_circuit_breaker_cooldown_secs(),
         }
     }
 }
@@ -151,9 +411,29 @@ pub struct LogConfig {
     pub level: LogLevel,
     pub module_levels: HashMap<String, LogLevel>,
     pub enable_performance_metrics: bool,
+    /// Defaults to `default_log_directory()`
     pub log_directory: Option<String>,
 }
 
+impl LogConfig {
+    /// Directory the log files are written to.
+    pub fn directory(&self) -> PathBuf {
+        self.log_directory
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(default_log_directory)
+    }
+}
+
+/// `$XDG_STATE_HOME/tui-hn-app/logs` (`~/.local/state/tui-hn-app/logs`), or the local
+/// data directory on platforms without a state directory.
+pub fn default_log_directory() -> PathBuf {
+    dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .map(|dir| dir.join("tui-hn-app").join("logs"))
+        .unwrap_or_else(|| PathBuf::from("logs"))
+}
+
 impl Default for LogConfig {
     fn default() -> Self {
         Self {
@@ -165,14 +445,418 @@ impl Default for LogConfig {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    #[default]
+    Markdown,
+    Html,
+}
+
+/// Delimited format for story list exports
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ListExportFormat {
+    #[default]
+    Csv,
+    Tsv,
+}
+
+/// Columns available in story list exports
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ListColumn {
+    Rank,
+    Id,
+    Title,
+    Url,
+    Domain,
+    Score,
+    Comments,
+    Author,
+    Time,
+}
+
+impl ListColumn {
+    pub const ALL: [ListColumn; 9] = [
+        ListColumn::Rank,
+        ListColumn::Id,
+        ListColumn::Title,
+        ListColumn::Url,
+        ListColumn::Domain,
+        ListColumn::Score,
+        ListColumn::Comments,
+        ListColumn::Author,
+        ListColumn::Time,
+    ];
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct ExportConfig {
+    /// Directory where exported stories are written
+    pub directory: String,
+    pub format: ExportFormat,
+    /// File name without extension; `{id}`, `{slug}` and `{date}` are expanded
+    pub filename_template: String,
+    /// Format used when exporting the story list
+    pub list_format: ListExportFormat,
+    /// Columns written when exporting the story list, in order
+    pub list_columns: Vec<ListColumn>,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            directory: default_export_directory(),
+            format: ExportFormat::Markdown,
+            filename_template: "{date}-{id}-{slug}".to_string(),
+            list_format: ListExportFormat::Csv,
+            list_columns: ListColumn::ALL.to_vec(),
+        }
+    }
+}
+
+fn default_export_directory() -> String {
+    match dirs::config_dir() {
+        Some(config_dir) => config_dir
+            .join("tui-hn-app")
+            .join("exports")
+            .to_string_lossy()
+            .to_string(),
+        None => "./exports".to_string(),
+    }
+}
+
+/// Bookmarks stories that match every condition set on the rule.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct AutoBookmarkRule {
+    /// Shown in notifications and the log when the rule fires
+    pub name: String,
+    /// Matches this domain and its subdomains (e.g. "arxiv.org")
+    pub domain: Option<String>,
+    /// At least one of these words must appear in the title (case-insensitive)
+    pub title_contains: Vec<String>,
+    pub author: Option<String>,
+    pub min_score: Option<u32>,
+    pub min_comments: Option<u32>,
+    /// Tag recorded on the bookmarks the rule creates
+    pub tag: Option<String>,
+}
+
+impl AutoBookmarkRule {
+    /// A rule without conditions would bookmark everything, so it is ignored.
+    pub fn has_conditions(&self) -> bool {
+        self.domain.is_some()
+            || !self.title_contains.is_empty()
+            || self.author.is_some()
+            || self.min_score.is_some()
+            || self.min_comments.is_some()
+    }
+}
+
+/// Local full-text index of opened articles and comment threads, searched with
+/// "Search my reading". Off by default.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ReadingIndexConfig {
+    pub enabled: bool,
+    /// Directory holding the index
+    pub directory: String,
+}
+
+impl Default for ReadingIndexConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: match dirs::config_dir() {
+                Some(config_dir) => config_dir
+                    .join("tui-hn-app")
+                    .join("reading_index")
+                    .to_string_lossy()
+                    .to_string(),
+                None => "./reading_index".to_string(),
+            },
+        }
+    }
+}
+
+/// Choosing a random unread bookmark to open.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct RandomBookmarkConfig {
+    /// Make bookmarks more likely to be picked the longer they have waited
+    pub prefer_older: bool,
+}
+
+impl Default for RandomBookmarkConfig {
+    fn default() -> Self {
+        Self { prefer_older: true }
+    }
+}
+
+/// What "prepare offline pack" downloads.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct OfflinePackConfig {
+    pub lists: Vec<crate::api::StoryListType>,
+    /// Stories taken from the top of each list
+    pub stories_per_list: usize,
+    /// Levels of replies fetched for each story
+    pub comment_depth: usize,
+}
+
+impl Default for OfflinePackConfig {
+    fn default() -> Self {
+        Self {
+            lists: vec![crate::api::StoryListType::Top],
+            stories_per_list: 30,
+            comment_depth: 3,
+        }
+    }
+}
+
+/// How stories queued to read later (`R`) are downloaded and kept.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ReadLaterConfig {
+    /// Levels of replies downloaded with each story
+    pub comment_depth: usize,
+    /// Days a downloaded story stays in the queue (0 = until removed)
+    pub keep_days: u64,
+}
+
+impl Default for ReadLaterConfig {
+    fn default() -> Self {
+        Self {
+            comment_depth: 3,
+            keep_days: 7,
+        }
+    }
+}
+
+/// Service "send to phone" pushes stories to.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub enum PushService {
+    /// An ntfy topic, on ntfy.sh or a self-hosted server
+    Ntfy,
+    Pushover,
+    /// Any URL taking a JSON POST
+    Webhook,
+}
+
+impl std::fmt::Display for PushService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            PushService::Ntfy => "ntfy",
+            PushService::Pushover => "Pushover",
+            PushService::Webhook => "webhook",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Where "send to phone" pushes the focused story. Nothing is sent until `service`
+/// is set.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct PushConfig {
+    pub service: Option<PushService>,
+    /// The ntfy server (https://ntfy.sh when unset), the webhook URL, or another
+    /// Pushover API endpoint
+    pub url: Option<String>,
+    /// ntfy topic
+    pub topic: Option<String>,
+    /// Pushover application token and user key
+    pub token: Option<String>,
+    pub user: Option<String>,
+    /// JSON body posted, with `{title}`, `{url}`, `{hn_url}`, `{id}`, `{by}`,
+    /// `{score}`, `{topic}`, `{token}` and `{user}` expanded; each service has a
+    /// default
+    pub template: Option<String>,
+    /// Extra HTTP headers, e.g. `"Authorization": "Bearer tk_..."` for ntfy
+    pub headers: std::collections::BTreeMap<String, String>,
+}
+
+/// Stories kept in the History view.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct HistoryConfig {
+    /// Oldest entries beyond this many are dropped
+    pub max_entries: usize,
+    /// Stories remembered as read; beyond this many the oldest are forgotten
+    pub max_read_ids: usize,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 50,
+            max_read_ids: 10_000,
+        }
+    }
+}
+
+/// Background refresh of bookmarked and watched stories when the app starts.
+/// Off by default.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct StartupPrefetchConfig {
+    pub enabled: bool,
+    /// Stories and articles fetched at the same time
+    pub concurrency: usize,
+    /// Also refresh the cached articles of bookmarked stories, so they can be read offline
+    pub articles: bool,
+}
+
+impl Default for StartupPrefetchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            concurrency: 4,
+            articles: true,
+        }
+    }
+}
+
+/// Background fetching of what is likely to be opened next: the next stories when
+/// the selection nears the end of the list, and the article and first comments of
+/// a story highlighted for a moment.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct PrefetchConfig {
+    pub enabled: bool,
+    /// Stories fetched ahead of the end of the loaded list
+    pub stories: usize,
+    /// How long a story stays highlighted before its article and comments are fetched
+    pub highlight_delay_ms: u64,
+}
+
+impl Default for PrefetchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            stories: 20,
+            highlight_delay_ms: 500,
+        }
+    }
+}
+
+/// Story list filters, edited in the filter panel (`F`). A story is shown when it
+/// passes every filter that is set.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct StoryFilterConfig {
+    /// Hide stories below this score (0 = off)
+    pub min_score: u32,
+    /// Hide stories older than this many hours (0 = off)
+    pub max_age_hours: u32,
+    /// Only show stories from these domains and their subdomains (empty = all)
+    pub include_domains: Vec<String>,
+    pub exclude_domains: Vec<String>,
+    /// Only show stories by these authors (empty = all)
+    pub allow_authors: Vec<String>,
+    pub block_authors: Vec<String>,
+}
+
+impl StoryFilterConfig {
+    pub fn is_active(&self) -> bool {
+        *self != Self::default()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct AutoBookmarkConfig {
+    /// Report which rules would fire without bookmarking anything
+    pub dry_run: bool,
+    pub rules: Vec<AutoBookmarkRule>,
+}
+
+/// User scripts (Rhai) loaded from a directory at startup.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ScriptingConfig {
+    pub enabled: bool,
+    /// Directory of `*.rhai` files; defaults to `~/.config/tui-hn-app/scripts`
+    pub directory: Option<String>,
+    /// Budget of operations per hook call, so a runaway loop can't freeze the UI
+    pub max_operations: u64,
+}
+
+impl Default for ScriptingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            directory: None,
+            max_operations: default_script_max_operations(),
+        }
+    }
+}
+
+fn default_script_max_operations() -> u64 {
+    100_000
+}
+
+impl ScriptingConfig {
+    pub fn directory_path(&self) -> Option<PathBuf> {
+        match &self.directory {
+            Some(dir) => Some(PathBuf::from(dir)),
+            None => dirs::config_dir().map(|dir| dir.join("tui-hn-app").join("scripts")),
+        }
+    }
+}
+
+/// What an external plugin command contributes.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq)]
+pub enum PluginKind {
+    /// Receives the selected story as JSON on stdin
+    #[default]
+    ItemAction,
+    /// Prints a JSON array of stories (or item ids) shown as a custom list
+    ListSource,
+}
+
+/// An external executable run from a key binding.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct PluginConfig {
+    /// Shown in notifications
+    pub name: String,
+    pub command: String,
+    pub args: Vec<String>,
+    pub kind: PluginKind,
+    /// Key that runs the plugin in every view, e.g. "Ctrl+g"
+    pub key: Option<String>,
+    /// The command is killed after this long
+    pub timeout_secs: u64,
+}
+
+impl Default for PluginConfig {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            command: String::new(),
+            args: Vec::new(),
+            kind: PluginKind::default(),
+            key: None,
+            timeout_secs: default_plugin_timeout_secs(),
+        }
+    }
+}
+
+fn default_plugin_timeout_secs() -> u64 {
+    30
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(default)]
 pub struct AppConfig {
+    /// Schema version of the file; older files are migrated on load
+    pub config_version: u32,
     /// Preferred theme name to apply (e.g., "Flexoki Light" / "Flexoki Dark")
     #[serde(default = "default_theme_name")]
     pub theme_name: String,
-    /// Path to a specific theme file or directory to load (e.g., "./themes" or "./themes/flexoki.json")
-    /// Defaults to "./themes" so ThemeRegistry can watch that directory.
+    /// Extra theme file or directory to load from (e.g., "~/dotfiles/hn-themes" or
+    /// "./themes/flexoki.json"), on top of `theme_directory`. Empty by default.
     #[serde(default = "default_theme_file")]
     pub theme_file: String,
     /// Directory where custom themes are stored and exported
@@ -204,6 +888,55 @@ pub struct AppConfig {
     /// Accessibility configuration
     #[serde(default)]
     pub accessibility: AccessibilityConfig,
+    /// Story export settings
+    #[serde(default)]
+    pub export: ExportConfig,
+    /// Rules that bookmark stories automatically as they load
+    #[serde(default)]
+    pub auto_bookmark: AutoBookmarkConfig,
+    /// External commands providing list sources or story actions
+    #[serde(default)]
+    pub plugins: Vec<PluginConfig>,
+    /// User scripts hooking into story loading and the status bar
+    #[serde(default)]
+    pub scripting: ScriptingConfig,
+    /// Story list filters set in the filter panel
+    #[serde(default)]
+    pub filters: StoryFilterConfig,
+    /// Full-text index of the articles and comments read in the app
+    #[serde(default)]
+    pub reading_index: ReadingIndexConfig,
+    /// Refresh bookmarks and watched threads in the background on launch
+    #[serde(default)]
+    pub startup_prefetch: StartupPrefetchConfig,
+    /// Fetch the next stories and the highlighted story's article ahead of time
+    #[serde(default)]
+    pub prefetch: PrefetchConfig,
+    /// Size of the History view
+    #[serde(default)]
+    pub history: HistoryConfig,
+    /// How the random bookmark is picked
+    #[serde(default)]
+    pub random_bookmark: RandomBookmarkConfig,
+    /// Stories, articles and comments downloaded ahead for offline reading
+    #[serde(default)]
+    pub offline_pack: OfflinePackConfig,
+    /// Background downloads of stories queued to read later
+    #[serde(default)]
+    pub read_later: ReadLaterConfig,
+    /// Push service the focused story is sent to, to read it on another device
+    #[serde(default)]
+    pub push: PushConfig,
+    /// Save the list, story, view, scroll positions, sort and search on quit, and
+    /// offer to restore them on the next launch
+    #[serde(default)]
+    pub restore_session: bool,
+    /// What the migration done on load changed, to tell the user
+    #[serde(skip)]
+    pub migration_summary: Option<String>,
+    /// File the config was loaded from, which `save` writes back to
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
 }
 
 fn default_theme_name() -> String {
@@ -211,7 +944,7 @@ fn default_theme_name() -> String {
 }
 
 fn default_theme_file() -> String {
-    "./themes".to_string()
+    String::new()
 }
 
 fn default_theme_directory() -> String {
@@ -244,9 +977,77 @@ fn default_rate_limit_per_second() -> f64 {
     3.0
 }
 
+fn default_refresh_margin() -> usize {
+    3
+}
+
+fn default_story_cache_ttl_secs() -> u64 {
+    300
+}
+
+fn default_new_story_cache_ttl_secs() -> u64 {
+    60
+}
+
+fn default_old_story_cache_ttl_secs() -> u64 {
+    1800
+}
+
+fn default_comment_cache_ttl_secs() -> u64 {
+    300
+}
+
+fn default_article_cache_ttl_secs() -> u64 {
+    900
+}
+
+fn default_cache_max_entries() -> usize {
+    5000
+}
+
+fn default_request_timeout_secs() -> u64 {
+    15
+}
+
+fn default_connect_timeout_secs() -> u64 {
+    10
+}
+
+fn default_circuit_breaker_threshold() -> u32 {
+    5
+}
+
+fn default_circuit_breaker_cooldown_secs() -> u64 {
+    30
+}
+
+impl NetworkConfig {
+    /// Where the caches are written, whether or not the disk cache is enabled: the
+    /// offline pack always saves there.
+    pub fn cache_dir(&self) -> Option<std::path::PathBuf> {
+        match &self.disk_cache_directory {
+            Some(dir) => Some(std::path::PathBuf::from(dir)),
+            None => dirs::cache_dir().map(|dir| dir.join("tui-hn-app")),
+        }
+    }
+
+    /// Cache freshness for a story posted at `time` (Unix seconds): short while it is
+    /// under an hour old, long once it is over a day old. Unknown ages get the default.
+    pub fn story_cache_ttl(&self, time: Option<i64>, now: i64) -> std::time::Duration {
+        const HOUR: i64 = 60 * 60;
+        let secs = match time.map(|time| now - time) {
+            Some(age) if age < HOUR => self.new_story_cache_ttl_secs,
+            Some(age) if age > 24 * HOUR => self.old_story_cache_ttl_secs,
+            _ => self.story_cache_ttl_secs,
+        };
+        std::time::Duration::from_secs(secs)
+    }
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
+            config_version: migration::CURRENT_VERSION,
             theme_name: default_theme_name(),
             theme_file: default_theme_file(),
             theme_directory: default_theme_directory(),
@@ -257,31 +1058,46 @@ impl Default for AppConfig {
             network: NetworkConfig::default(),
             logging: LogConfig::default(),
             accessibility: AccessibilityConfig::default(),
+            export: ExportConfig::default(),
+            auto_bookmark: AutoBookmarkConfig::default(),
+            plugins: Vec::new(),
+            scripting: ScriptingConfig::default(),
+            filters: StoryFilterConfig::default(),
+            reading_index: ReadingIndexConfig::default(),
+            startup_prefetch: StartupPrefetchConfig::default(),
+            prefetch: PrefetchConfig::default(),
+            history: HistoryConfig::default(),
+            random_bookmark: RandomBookmarkConfig::default(),
+            offline_pack: OfflinePackConfig::default(),
+            read_later: ReadLaterConfig::default(),
+            push: PushConfig::default(),
+            restore_session: false,
+            migration_summary: None,
+            source: None,
         }
     }
 }
 
 #[allow(dead_code)]
 impl AppConfig {
-    pub fn load() -> Self {
-        // Look for config.ron in current directory or next to executable
-        let mut candidates = Vec::new();
-
-        // 1. Current working directory
-        candidates.push(PathBuf::from("config.ron"));
-
-        // 2. Next to executable
+    /// Where `load` looks for config.ron: the current directory, then next to the
+    /// executable.
+    pub fn candidate_paths() -> Vec<PathBuf> {
+        let mut candidates = vec![PathBuf::from("config.ron")];
         if let Ok(exe) = std::env::current_exe()
             && let Some(dir) = exe.parent()
         {
             candidates.push(dir.join("config.ron"));
         }
+        candidates
+    }
 
-        for path in candidates {
+    pub fn load() -> Self {
+        for path in Self::candidate_paths() {
             if path.exists()
                 && let Ok(content) = fs::read_to_string(&path)
             {
-                match ron::from_str::<AppConfig>(&content) {
+                match Self::parse_migrating(&path, &content) {
                     Ok(config) => {
                         tracing::info!("Loaded config from {}", path.display());
                         return config;
@@ -297,8 +1113,59 @@ impl AppConfig {
         Self::default()
     }
 
+    /// Load the config from an explicit path (`--config`), failing instead of
+    /// falling back to defaults.
+    pub fn load_from(path: &Path) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read config {}: {}", path.display(), e))?;
+        Self::parse_migrating(path, &content)
+            .map_err(|e| anyhow::anyhow!("Failed to parse config {}: {}", path.display(), e))
+    }
+
+    /// Parse the file at `path`, migrating it to the current schema first. The file
+    /// itself is left alone; see `write_migration`.
+    fn parse_migrating(path: &Path, content: &str) -> Result<Self, ron::error::SpannedError> {
+        let mut config = match migration::migrate(content) {
+            Some(migrated) => {
+                let mut config = ron::from_str::<AppConfig>(&migrated.text)?;
+                config.migration_summary = Some(migrated.summary());
+                config
+            }
+            None => ron::from_str::<AppConfig>(content)?,
+        };
+        config.source = Some(path.to_path_buf());
+        Ok(config)
+    }
+
+    /// Rewrite the file the config was loaded from in the current schema if loading
+    /// migrated it, keeping the old file as a backup. Only the app's startup does
+    /// this, so merely loading a config never touches the file.
+    pub fn write_migration(&self) {
+        let Some(path) = self
+            .source
+            .as_ref()
+            .filter(|_| self.migration_summary.is_some())
+        else {
+            return;
+        };
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                migration::migrate_file(path, &content);
+            }
+            Err(e) => tracing::warn!("Failed to reread config {}: {}", path.display(), e),
+        }
+    }
+
+    /// Write the config back to the file it was loaded from, or to config.ron in
+    /// the current directory.
     pub fn save(&self) {
-        self.save_to(PathBuf::from("config.ron"));
+        self.save_to(self.save_path());
+    }
+
+    pub fn save_path(&self) -> PathBuf {
+        self.source
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("config.ron"))
     }
 
     pub fn save_to(&self, path: PathBuf) {
@@ -361,6 +1228,42 @@ impl AppConfig {
             self.auto_switch_dark_to_light.to_string(),
         );
         replace_str(&mut new_content, "ghost_term_name", &self.ghost_term_name);
+        new_content = settings::write_settings(&new_content, self);
+
+        // The filters section is rewritten whole (it holds no nested tuples), or
+        // appended when it is missing and a filter is set
+        match ron::ser::to_string(&self.filters) {
+            Ok(filters) => {
+                let re = RegexBuilder::new(r"(\s*filters\s*:\s*)\([^()]*\)")
+                    .build()
+                    .unwrap();
+                match (re.is_match(&new_content), new_content.rfind(')')) {
+                    (true, _) => {
+                        new_content = re
+                            .replace(&new_content, |caps: &regex::Captures| {
+                                format!("{}{}", &caps[1], filters)
+                            })
+                            .to_string();
+                    }
+                    (false, Some(end)) if self.filters.is_active() => {
+                        let head = new_content[..end].trim_end();
+                        let separator = match head.ends_with(',') || head.ends_with('(') {
+                            true => "",
+                            false => ",",
+                        };
+                        new_content = format!(
+                            "{}{}\n    filters: {},\n{}",
+                            head,
+                            separator,
+                            filters,
+                            &new_content[end..]
+                        );
+                    }
+                    (false, _) => {}
+                }
+            }
+            Err(e) => tracing::error!("Failed to serialize filters: {}", e),
+        }
 
         match fs::write(&path, new_content) {
             Ok(_) => {
@@ -378,6 +1281,73 @@ mod tests {
     use super::*;
     use std::fs;
 
+    #[test]
+    fn test_category_sorts_parse() {
+        use crate::internal::ui::sort::{SortBy, SortOrder};
+        let config: AppConfig = ron::from_str(
+            "(ui: (category_sorts: {New: (by: Time), Ask: (by: Comments, order: Ascending)}))",
+        )
+        .unwrap();
+        let sorts = &config.ui.category_sorts;
+        assert_eq!(
+            sorts.get(&crate::api::StoryListType::New),
+            Some(&CategorySort {
+                by: SortBy::Time,
+                order: SortOrder::Descending
+            })
+        );
+        assert_eq!(
+            sorts.get(&crate::api::StoryListType::Ask).map(|s| s.order),
+            Some(SortOrder::Ascending)
+        );
+    }
+
+    #[test]
+    fn test_story_cache_ttl_follows_story_age() {
+        let network = NetworkConfig::default();
+        let now = 1_700_000_000;
+        let ttl = |age: i64| network.story_cache_ttl(Some(now - age), now).as_secs();
+        assert_eq!(ttl(10 * 60), 60);
+        assert_eq!(ttl(5 * 60 * 60), 300);
+        assert_eq!(ttl(3 * 24 * 60 * 60), 1800);
+        assert_eq!(network.story_cache_ttl(None, now).as_secs(), 300);
+    }
+
+    #[test]
+    fn test_save_writes_filters_section() {
+        let config_path = std::env::temp_dir().join("config_test_filters.ron");
+        fs::write(
+            &config_path,
+            "(\n    // Theme settings\n    theme_name: \"A\"\n)",
+        )
+        .unwrap();
+
+        let mut config = AppConfig {
+            theme_name: "A".to_string(),
+            filters: StoryFilterConfig {
+                min_score: 50,
+                exclude_domains: vec!["example.com".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        config.save_to(config_path.clone());
+        let content = fs::read_to_string(&config_path).unwrap();
+        assert!(content.contains("// Theme settings"));
+        let restored: AppConfig = ron::from_str(&content).unwrap();
+        assert_eq!(restored.filters, config.filters);
+
+        // Saving again replaces the section instead of adding another
+        config.filters.min_score = 0;
+        config.save_to(config_path.clone());
+        let content = fs::read_to_string(&config_path).unwrap();
+        assert_eq!(content.matches("filters:").count(), 1);
+        let restored: AppConfig = ron::from_str(&content).unwrap();
+        assert_eq!(restored.filters, config.filters);
+
+        let _ = fs::remove_file(config_path);
+    }
+
     #[test]
     fn test_save_preserves_comments() {
         use std::io::Write;
@@ -0,0 +1,448 @@
+//! Versioned migrations of `config.ron`. Each migration rewrites the text of the
+//! file, so comments and layout survive, and says what it changed. Files without a
+//! `config_version` key are version 1.
+
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Version written by this build of the app.
+pub const CURRENT_VERSION: u32 = 4;
+
+/// Rewrites a config of version `n` into version `n + 1`, returning a line for the
+/// summary for each change it made.
+type Migration = fn(&mut String) -> Vec<String>;
+
+/// Migration `i` turns version `i + 1` into `i + 2`.
+const MIGRATIONS: [Migration; (CURRENT_VERSION - 1) as usize] = [
+    keybindings_into_contexts,
+    theme_file_to_directory,
+    relative_paths_to_user_dirs,
+];
+
+/// A config file brought up to date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Migrated {
+    pub text: String,
+    pub from: u32,
+    /// What each migration changed, in order
+    pub changes: Vec<String>,
+}
+
+impl Migrated {
+    /// One-line summary for the status bar.
+    pub fn summary(&self) -> String {
+        match self.changes.is_empty() {
+            true => format!(
+                "Config updated from version {} to {}",
+                self.from, CURRENT_VERSION
+            ),
+            false => format!(
+                "Config updated from version {} to {}: {}",
+                self.from,
+                CURRENT_VERSION,
+                self.changes.join("; ")
+            ),
+        }
+    }
+}
+
+/// `config_version` of the file text, 1 if it has none.
+pub fn version_of(text: &str) -> u32 {
+    Regex::new(r"(?m)^\s*config_version\s*:\s*(\d+)")
+        .expect("valid regex")
+        .captures(text)
+        .and_then(|caps| caps[1].parse().ok())
+        .unwrap_or(1)
+}
+
+/// Run the migrations the text needs. `None` if it is already current; files from a
+/// newer version of the app are left alone too.
+pub fn migrate(text: &str) -> Option<Migrated> {
+    let from = version_of(text);
+    if from >= CURRENT_VERSION {
+        return None;
+    }
+    let mut text = text.to_string();
+    let mut changes = Vec::new();
+    for migration in &MIGRATIONS[(from.max(1) - 1) as usize..] {
+        changes.extend(migration(&mut text));
+    }
+    set_version(&mut text, CURRENT_VERSION);
+    Some(Migrated {
+        text,
+        from,
+        changes,
+    })
+}
+
+/// Migrate the file at `path` in place, copying the old file to
+/// `<name>.v<version>.bak` first. Returns the migration even if the file could not
+/// be rewritten, so the app still runs with the migrated settings.
+pub fn migrate_file(path: &Path, text: &str) -> Option<Migrated> {
+    let migrated = migrate(text)?;
+    let backup = backup_path(path, migrated.from);
+    let written = fs::write(&backup, text).and_then(|_| fs::write(path, &migrated.text));
+    match written {
+        Ok(()) => tracing::info!(
+            "Migrated {} from version {} to {}, old file kept as {}",
+            path.display(),
+            migrated.from,
+            CURRENT_VERSION,
+            backup.display()
+        ),
+        Err(e) => tracing::warn!("Failed to write migrated config {}: {}", path.display(), e),
+    }
+    for change in &migrated.changes {
+        tracing::info!("Config migration: {}", change);
+    }
+    Some(migrated)
+}
+
+pub fn backup_path(path: &Path, version: u32) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "config.ron".to_string());
+    path.with_file_name(format!("{}.v{}.bak", name, version))
+}
+
+fn set_version(text: &mut String, version: u32) {
+    let re = Regex::new(r"(?m)^(\s*config_version\s*:\s*)\d+").expect("valid regex");
+    if re.is_match(text) {
+        *text = re.replace(text, format!("${{1}}{}", version)).to_string();
+        return;
+    }
+    // Otherwise add it as the first field of the top-level struct
+    let open = code_positions(text).find(|&i| text.as_bytes()[i] == b'(');
+    if let Some(open) = open {
+        text.insert_str(open + 1, &format!("\n    config_version: {},", version));
+    }
+}
+
+/// Byte offsets of the characters outside comments and strings.
+pub(super) fn code_positions(text: &str) -> impl Iterator<Item = usize> + '_ {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        while i < bytes.len() {
+            let at = i;
+            match (bytes[i], bytes.get(i + 1)) {
+                (b'/', Some(b'/')) => {
+                    i = text[i..].find('\n').map_or(bytes.len(), |n| i + n);
+                }
+                (b'/', Some(b'*')) => {
+                    i = text[i + 2..].find("*/").map_or(bytes.len(), |n| i + n + 4);
+                }
+                (b'"', _) => {
+                    i += 1;
+                    while i < bytes.len() && bytes[i] != b'"' {
+                        i += if bytes[i] == b'\\' { 2 } else { 1 };
+                    }
+                    i += 1;
+                }
+                _ => {
+                    i += 1;
+                    return Some(at);
+                }
+            }
+        }
+        None
+    })
+}
+
+/// Offset of the bracket closing the one at `open`.
+pub(super) fn matching_close(text: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for i in code_positions(text).skip_while(|&i| i < open) {
+        match text.as_bytes()[i] {
+            b'(' | b'{' | b'[' => depth += 1,
+            b')' | b'}' | b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Version 1 → 2: keybindings were one flat map; they now live in contexts, and
+/// the old bindings apply everywhere, like the `global` context.
+fn keybindings_into_contexts(text: &mut String) -> Vec<String> {
+    let re = Regex::new(r"(?m)^\s*keybindings\s*:\s*(?:Some\s*\(\s*)?\{").expect("valid regex");
+    let Some(found) = re.find(text) else {
+        return Vec::new();
+    };
+    let open = found.end() - 1;
+    if !code_positions(text).any(|i| i == open) {
+        return Vec::new();
+    }
+    let Some(close) = matching_close(text, open) else {
+        return Vec::new();
+    };
+    let map = text[open..=close].to_string();
+    let wrapped = match found.as_str().contains("Some") {
+        true => format!("(global: {})", map),
+        false => format!("Some((global: {}))", map),
+    };
+    text.replace_range(open..=close, &wrapped);
+    vec!["moved keybindings into the `global` context".to_string()]
+}
+
+/// Version 2 → 3: `theme_file` used to name the directory custom themes live in.
+/// It now only adds a theme file or directory to load from, and `theme_directory`
+/// says where themes are kept and exported; a custom directory moves there.
+fn theme_file_to_directory(text: &mut String) -> Vec<String> {
+    let has_directory = Regex::new(r"(?m)^\s*theme_directory\s*:")
+        .expect("valid regex")
+        .is_match(text);
+    let re = Regex::new(r#"(?m)^(\s*)theme_file(\s*:\s*)"([^"]*)""#).expect("valid regex");
+    let Some(caps) = re.captures(text) else {
+        return Vec::new();
+    };
+    let dir = caps[3].trim_end_matches('/');
+    let is_default = matches!(dir, "" | "./themes" | "themes");
+    if has_directory || is_default || dir.to_ascii_lowercase().ends_with(".json") {
+        return Vec::new();
+    }
+    let line = caps[0].to_string();
+    let moved = format!("{}theme_directory{}\"{}\"", &caps[1], &caps[2], &caps[3]);
+    let change = format!(
+        "`theme_file` directory \"{}\" is now `theme_directory`",
+        &caps[3]
+    );
+    *text = text.replacen(&line, &moved, 1);
+    vec![change]
+}
+
+/// Version 3 → 4: themes and logs defaulted to `./themes` and `logs`, relative to
+/// wherever the app was started. Those values are dropped for the per-user
+/// directories; `migrate_legacy_files` moves what was in them.
+fn relative_paths_to_user_dirs(text: &mut String) -> Vec<String> {
+    let mut changes = Vec::new();
+    let theme_file =
+        Regex::new(r#"(?m)^(\s*theme_file\s*:\s*)"(?:\./)?themes/?""#).expect("valid regex");
+    if theme_file.is_match(text) {
+        *text = theme_file.replace(text, r#"${1}"""#).to_string();
+        changes.push(
+            "`theme_file` \"./themes\" cleared; themes live in `theme_directory`".to_string(),
+        );
+    }
+    let log_directory = Regex::new(r#"(\blog_directory\s*:\s*)Some\(\s*"(?:\./)?logs/?"\s*\)"#)
+        .expect("valid regex");
+    if log_directory.is_match(text) {
+        *text = log_directory.replace(text, "${1}None").to_string();
+        changes
+            .push("`log_directory` \"logs\" cleared; logs go to the state directory".to_string());
+    }
+    changes
+}
+
+/// Files earlier versions kept under the working directory, moved to the per-user
+/// directories: logs in `./logs` to the log directory, and themes in `./themes` to
+/// `theme_directory`. Themes are copied, since `./themes` may be a checkout of the
+/// app, and copies of the built-in themes are skipped. Only defaults are followed:
+/// a config that sets `log_directory` or `theme_file` keeps using them. Returns a
+/// line for each kind of file moved.
+pub fn migrate_legacy_files(config: &super::AppConfig) -> Vec<String> {
+    let log_dir = config
+        .logging
+        .log_directory
+        .is_none()
+        .then(super::default_log_directory);
+    let theme_dir = config
+        .theme_file
+        .trim()
+        .is_empty()
+        .then(|| PathBuf::from(&config.theme_directory));
+    move_legacy_files(Path::new("."), log_dir.as_deref(), theme_dir.as_deref())
+}
+
+fn move_legacy_files(base: &Path, log_dir: Option<&Path>, theme_dir: Option<&Path>) -> Vec<String> {
+    let files = |dir: &Path, keep: fn(&str) -> bool| -> Vec<PathBuf> {
+        fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| {
+                        path.is_file()
+                            && path.file_name().and_then(|n| n.to_str()).is_some_and(keep)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let mut changes = Vec::new();
+
+    let legacy_logs = base.join("logs");
+    if let Some(log_dir) = log_dir.filter(|dir| !same_dir(dir, &legacy_logs)) {
+        let moved = files(&legacy_logs, |name| name.starts_with("tui-hn-app.log"))
+            .iter()
+            .filter(|path| {
+                let target = log_dir.join(path.file_name().unwrap_or_default());
+                !target.exists()
+                    && fs::create_dir_all(log_dir).is_ok()
+                    && (fs::rename(path, &target).is_ok()
+                        || fs::copy(path, &target)
+                            .and_then(|_| fs::remove_file(path))
+                            .is_ok())
+            })
+            .count();
+        if moved > 0 {
+            // Only goes if nothing else was in it
+            let _ = fs::remove_dir(&legacy_logs);
+            changes.push(format!(
+                "moved {} log files to {}",
+                moved,
+                log_dir.display()
+            ));
+        }
+    }
+
+    let legacy_themes = base.join("themes");
+    if let Some(theme_dir) = theme_dir.filter(|dir| !same_dir(dir, &legacy_themes)) {
+        let copied = files(&legacy_themes, |name| name.ends_with(".json"))
+            .iter()
+            .filter(|path| {
+                let name = path.file_name().unwrap_or_default();
+                let target = theme_dir.join(name);
+                let content = fs::read_to_string(path).unwrap_or_default();
+                let builtin = crate::utils::theme_loader::builtin_theme(
+                    &Path::new(crate::utils::theme_loader::BUILTIN_DIR).join(name),
+                );
+                !target.exists()
+                    && builtin != Some(content.as_str())
+                    && fs::create_dir_all(theme_dir).is_ok()
+                    && fs::write(&target, content).is_ok()
+            })
+            .count();
+        if copied > 0 {
+            changes.push(format!(
+                "copied {} themes to {}",
+                copied,
+                theme_dir.display()
+            ));
+        }
+    }
+    changes
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+
+    #[test]
+    fn migrates_old_configs_keeping_comments() {
+        let old = r#"(
+    // My setup
+    theme_name: "Flexoki Dark",
+    theme_file: "/home/me/hn-themes/",
+    keybindings: Some({
+        "x": "Quit", // was q
+        "}": "NavigateDown",
+    }),
+)"#;
+        let migrated = migrate(old).unwrap();
+        assert_eq!(migrated.from, 1);
+        assert_eq!(migrated.changes.len(), 2);
+        assert!(migrated.text.contains("// My setup"));
+        assert!(migrated.text.contains("// was q"));
+        assert_eq!(version_of(&migrated.text), CURRENT_VERSION);
+
+        let config: AppConfig = ron::from_str(&migrated.text).unwrap();
+        assert_eq!(config.config_version, CURRENT_VERSION);
+        assert_eq!(config.theme_directory, "/home/me/hn-themes/");
+        assert_eq!(config.keybindings.unwrap().global.len(), 2);
+
+        // Current files are left alone
+        assert_eq!(migrate(&migrated.text), None);
+    }
+
+    #[test]
+    fn leaves_settings_that_already_match_the_new_schema() {
+        let old = r#"(
+    config_version: 2,
+    theme_file: "./mine.json",
+    keybindings: Some((global: {"x": "Quit"})),
+)"#;
+        let migrated = migrate(old).unwrap();
+        assert_eq!(migrated.from, 2);
+        assert!(migrated.changes.is_empty());
+        assert_eq!(
+            migrated.text,
+            old.replace("config_version: 2", "config_version: 4")
+        );
+    }
+
+    #[test]
+    fn backs_up_the_old_file() {
+        let dir = std::env::temp_dir().join(format!("hn-config-migration-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.ron");
+        let old = "(\n    theme_name: \"Flexoki Dark\",\n)";
+        fs::write(&path, old).unwrap();
+
+        // Loading migrates in memory only; the startup writes the file
+        let config = crate::config::AppConfig::load_from(&path).unwrap();
+        assert!(config.migration_summary.is_some());
+        assert_eq!(fs::read_to_string(&path).unwrap(), old);
+        assert!(!dir.join("config.ron.v1.bak").exists());
+
+        let migrated = migrate_file(&path, old).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join("config.ron.v1.bak")).unwrap(),
+            old
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), migrated.text);
+        assert!(migrated.text.starts_with("(\n    config_version: 4,"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn moves_relative_paths_to_user_dirs() {
+        let old = r#"(
+    config_version: 3,
+    theme_file: "./themes", // bundled
+    logging: (log_directory: Some("logs")),
+)"#;
+        let migrated = migrate(old).unwrap();
+        assert_eq!(migrated.changes.len(), 2);
+        assert!(migrated.text.contains(r#"theme_file: "", // bundled"#));
+        let config: AppConfig = ron::from_str(&migrated.text).unwrap();
+        assert_eq!(config.logging.log_directory, None);
+
+        let base = std::env::temp_dir().join(format!("hn-legacy-files-{}", std::process::id()));
+        let (logs, themes) = (base.join("state/logs"), base.join("config/themes"));
+        fs::create_dir_all(base.join("logs")).unwrap();
+        fs::create_dir_all(base.join("themes")).unwrap();
+        fs::write(base.join("logs/tui-hn-app.log.2024-01-01"), "old log").unwrap();
+        fs::write(base.join("themes/mine.json"), "{}").unwrap();
+        // Copies of built-in themes stay behind
+        fs::copy("themes/nord.json", base.join("themes/nord.json")).unwrap();
+
+        let changes = move_legacy_files(&base, Some(&logs), Some(&themes));
+        assert_eq!(changes.len(), 2);
+        assert_eq!(
+            fs::read_to_string(logs.join("tui-hn-app.log.2024-01-01")).unwrap(),
+            "old log"
+        );
+        assert!(!base.join("logs").exists());
+        assert!(themes.join("mine.json").exists() && base.join("themes/mine.json").exists());
+        assert!(!themes.join("nord.json").exists());
+        // Nothing is left to move the next time
+        assert!(move_legacy_files(&base, Some(&logs), Some(&themes)).is_empty());
+        fs::remove_dir_all(&base).unwrap();
+    }
+}
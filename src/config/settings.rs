@@ -0,0 +1,320 @@
+//! Config values the settings panel (`,`) edits while the app runs, and how they
+//! are written back into the text of `config.ron` so its comments and layout
+//! survive the save.
+
+use super::AppConfig;
+use super::migration::{code_positions, matching_close};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Setting {
+    PaddingHorizontal,
+    PaddingVertical,
+    ShowScore,
+    ShowComments,
+    ShowDomain,
+    ShowAge,
+    ShowAuthor,
+    StatusBarFormat,
+    MaxRetries,
+    ConcurrentRequests,
+    RateLimit,
+    RequestTimeout,
+}
+
+impl Setting {
+    pub const ALL: [Setting; 12] = [
+        Setting::PaddingHorizontal,
+        Setting::PaddingVertical,
+        Setting::ShowScore,
+        Setting::ShowComments,
+        Setting::ShowDomain,
+        Setting::ShowAge,
+        Setting::ShowAuthor,
+        Setting::StatusBarFormat,
+        Setting::MaxRetries,
+        Setting::ConcurrentRequests,
+        Setting::RateLimit,
+        Setting::RequestTimeout,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Setting::PaddingHorizontal => "Horizontal padding",
+            Setting::PaddingVertical => "Vertical padding",
+            Setting::ShowScore => "List: score",
+            Setting::ShowComments => "List: comments",
+            Setting::ShowDomain => "List: domain",
+            Setting::ShowAge => "List: age",
+            Setting::ShowAuthor => "List: author",
+            Setting::StatusBarFormat => "Status bar format",
+            Setting::MaxRetries => "Retries",
+            Setting::ConcurrentRequests => "Concurrent requests",
+            Setting::RateLimit => "Requests per second",
+            Setting::RequestTimeout => "Request timeout (s)",
+        }
+    }
+
+    /// Where the value lives in `config.ron`.
+    pub fn path(self) -> &'static [&'static str] {
+        match self {
+            Setting::PaddingHorizontal => &["ui", "padding", "horizontal"],
+            Setting::PaddingVertical => &["ui", "padding", "vertical"],
+            Setting::ShowScore => &["ui", "list_view", "show_score"],
+            Setting::ShowComments => &["ui", "list_view", "show_comments"],
+            Setting::ShowDomain => &["ui", "list_view", "show_domain"],
+            Setting::ShowAge => &["ui", "list_view", "show_age"],
+            Setting::ShowAuthor => &["ui", "list_view", "show_author"],
+            Setting::StatusBarFormat => &["ui", "status_bar_format"],
+            Setting::MaxRetries => &["network", "max_retries"],
+            Setting::ConcurrentRequests => &["network", "concurrent_requests"],
+            Setting::RateLimit => &["network", "rate_limit_per_second"],
+            Setting::RequestTimeout => &["network", "request_timeout_secs"],
+        }
+    }
+
+    /// On/off settings, flipped rather than typed.
+    pub fn is_toggle(self) -> bool {
+        self.flag(&AppConfig::default()).is_some()
+    }
+
+    fn flag(self, config: &AppConfig) -> Option<bool> {
+        let list = &config.ui.list_view;
+        match self {
+            Setting::ShowScore => Some(list.show_score),
+            Setting::ShowComments => Some(list.show_comments),
+            Setting::ShowDomain => Some(list.show_domain),
+            Setting::ShowAge => Some(list.show_age),
+            Setting::ShowAuthor => Some(list.show_author),
+            _ => None,
+        }
+    }
+
+    /// Current value as shown and typed in the panel.
+    pub fn value(self, config: &AppConfig) -> String {
+        let (ui, network) = (&config.ui, &config.network);
+        match self {
+            Setting::PaddingHorizontal => ui.padding.horizontal.to_string(),
+            Setting::PaddingVertical => ui.padding.vertical.to_string(),
+            Setting::StatusBarFormat => ui.status_bar_format.clone(),
+            Setting::MaxRetries => network.max_retries.to_string(),
+            Setting::ConcurrentRequests => network.concurrent_requests.to_string(),
+            Setting::RateLimit => network.rate_limit_per_second.to_string(),
+            Setting::RequestTimeout => network.request_timeout_secs.to_string(),
+            flag => flag.flag(config).unwrap_or_default().to_string(),
+        }
+    }
+
+    /// The value as written in `config.ron`.
+    pub fn ron_value(self, config: &AppConfig) -> String {
+        match self {
+            Setting::StatusBarFormat => {
+                ron::to_string(&config.ui.status_bar_format).unwrap_or_default()
+            }
+            Setting::RateLimit => format!("{:?}", config.network.rate_limit_per_second),
+            other => other.value(config),
+        }
+    }
+
+    /// Set the value from typed text.
+    pub fn set(self, config: &mut AppConfig, text: &str) -> Result<(), String> {
+        fn number<T: std::str::FromStr>(setting: Setting, text: &str) -> Result<T, String> {
+            text.trim()
+                .parse()
+                .map_err(|_| format!("{} must be a whole number", setting.label()))
+        }
+        let (ui, network) = (&mut config.ui, &mut config.network);
+        match self {
+            Setting::PaddingHorizontal => ui.padding.horizontal = number(self, text)?,
+            Setting::PaddingVertical => ui.padding.vertical = number(self, text)?,
+            Setting::StatusBarFormat => {
+                if !text.is_empty() {
+                    crate::internal::ui::status_template::StatusTemplate::parse(text)?;
+                }
+                ui.status_bar_format = text.to_string()
+            }
+            Setting::MaxRetries => network.max_retries = number(self, text)?,
+            Setting::ConcurrentRequests => match number(self, text)? {
+                0 => return Err(format!("{} must be at least 1", self.label())),
+                n => network.concurrent_requests = n,
+            },
+            Setting::RateLimit => match text.trim().parse::<f64>() {
+                Ok(rate) if rate > 0.0 && rate.is_finite() => network.rate_limit_per_second = rate,
+                _ => return Err(format!("{} must be a positive number", self.label())),
+            },
+            Setting::RequestTimeout => match number(self, text)? {
+                0 => return Err(format!("{} must be at least 1", self.label())),
+                n => network.request_timeout_secs = n,
+            },
+            flag => {
+                let value = match text.trim() {
+                    "true" | "on" => true,
+                    "false" | "off" => false,
+                    _ => return Err(format!("{} is on or off", flag.label())),
+                };
+                let list = &mut ui.list_view;
+                match flag {
+                    Setting::ShowScore => list.show_score = value,
+                    Setting::ShowComments => list.show_comments = value,
+                    Setting::ShowDomain => list.show_domain = value,
+                    Setting::ShowAge => list.show_age = value,
+                    _ => list.show_author = value,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Flip an on/off setting; other settings are left as they are.
+    pub fn toggle(self, config: &mut AppConfig) {
+        if let Some(on) = self.flag(config) {
+            let _ = self.set(config, &(!on).to_string());
+        }
+    }
+}
+
+/// Write every setting into the config file `text`: values already in the file are
+/// replaced in place, missing ones are added only when they differ from the default.
+pub fn write_settings(text: &str, config: &AppConfig) -> String {
+    let defaults = AppConfig::default();
+    Setting::ALL.iter().fold(text.to_string(), |text, setting| {
+        let value = setting.ron_value(config);
+        let add_missing = value != setting.ron_value(&defaults);
+        set_value(&text, setting.path(), &value, add_missing)
+    })
+}
+
+/// `text` with the field at `path` (e.g. `["ui", "padding", "horizontal"]`) set to
+/// the RON `value`. A field the file lacks, or whose section it lacks, is added to
+/// the innermost section present when `add_missing` is true.
+pub fn set_value(text: &str, path: &[&str], value: &str, add_missing: bool) -> String {
+    let open = code_positions(text).find(|&i| text.as_bytes()[i] == b'(');
+    let mut text = text.to_string();
+    if let Some(open) = open {
+        set_in(&mut text, open, 1, path, value, add_missing);
+    }
+    text
+}
+
+/// Set `path` inside the struct whose `(` is at `open`, `depth` levels deep.
+fn set_in(text: &mut String, open: usize, depth: usize, path: &[&str], value: &str, add: bool) {
+    let (Some((key, rest)), Some(close)) = (path.split_first(), matching_close(text, open)) else {
+        return;
+    };
+    match (field_value(text, open, close, key), rest.is_empty()) {
+        (Some(start), true) => {
+            let end = value_end(text, start);
+            text.replace_range(start..end, value);
+        }
+        (Some(start), false) if text.as_bytes()[start] == b'(' => {
+            set_in(text, start, depth + 1, rest, value, add)
+        }
+        (Some(_), false) => {}
+        (None, _) if add => {
+            let nested = rest.iter().rev().fold(value.to_string(), |inner, key| {
+                format!("({}: {})", key, inner)
+            });
+            let field = format!("\n{}{}: {},", "    ".repeat(depth), key, nested);
+            text.insert_str(open + 1, &field);
+        }
+        (None, _) => {}
+    }
+}
+
+/// Start of the value of the field `key` directly inside the struct spanning
+/// `open..close`.
+fn field_value(text: &str, open: usize, close: usize, key: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let mut depth = 0usize;
+    for i in code_positions(text).skip_while(|&i| i <= open) {
+        if i >= close {
+            break;
+        }
+        match bytes[i] {
+            b'(' | b'{' | b'[' => depth += 1,
+            b')' | b'}' | b']' => depth = depth.saturating_sub(1),
+            _ if depth == 0
+                && text[i..].starts_with(key)
+                && !is_ident(bytes[i - 1])
+                && !bytes.get(i + key.len()).copied().is_some_and(is_ident) =>
+            {
+                let after = &text[i + key.len()..];
+                let colon = after.len() - after.trim_start().len();
+                if after[colon..].starts_with(':') {
+                    let value = &after[colon + 1..];
+                    return Some(
+                        i + key.len() + colon + 1 + value.len() - value.trim_start().len(),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// End of the value starting at `start`: the `,` or closing bracket after it, less
+/// the whitespace before that.
+fn value_end(text: &str, start: usize) -> usize {
+    let mut depth = 0usize;
+    let end = code_positions(text)
+        .skip_while(|&i| i < start)
+        .find(|&i| match text.as_bytes()[i] {
+            b'(' | b'{' | b'[' => {
+                depth += 1;
+                false
+            }
+            b')' | b'}' | b']' if depth > 0 => {
+                depth -= 1;
+                false
+            }
+            b',' | b')' | b'}' | b']' => depth == 0,
+            _ => false,
+        })
+        .unwrap_or(text.len());
+    start + text[start..end].trim_end().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_settings_keeping_comments() {
+        let text = r#"(
+    // Layout
+    ui: (
+        padding: (horizontal: 1, vertical: 0), // roomy
+        status_bar_format: "{mode} | {help}",
+    ),
+    theme_name: "Flexoki Dark",
+)"#;
+        let mut config: AppConfig = ron::from_str(text).unwrap();
+        Setting::PaddingHorizontal.set(&mut config, "3").unwrap();
+        Setting::StatusBarFormat
+            .set(&mut config, "{mode} \"x\"")
+            .unwrap();
+        Setting::ShowAge.toggle(&mut config);
+        Setting::RateLimit.set(&mut config, "5").unwrap();
+        assert!(Setting::ConcurrentRequests.set(&mut config, "0").is_err());
+        assert!(Setting::MaxRetries.set(&mut config, "lots").is_err());
+        assert!(Setting::StatusBarFormat.set(&mut config, "{nope}").is_err());
+
+        let saved = write_settings(text, &config);
+        assert!(saved.contains("padding: (horizontal: 3, vertical: 0), // roomy"));
+        assert!(saved.contains(r#"status_bar_format: "{mode} \"x\"","#));
+        assert!(saved.contains("// Layout"));
+        // Missing fields and sections are added when they differ from the default;
+        // unchanged ones (max_retries) are not
+        assert!(saved.contains("list_view: (show_age: false),"));
+        assert!(saved.contains("network: (rate_limit_per_second: 5.0),"));
+        assert!(!saved.contains("max_retries"));
+
+        let reloaded: AppConfig = ron::from_str(&saved).unwrap();
+        assert_eq!(reloaded.ui.padding.horizontal, 3);
+        assert_eq!(reloaded.ui.status_bar_format, "{mode} \"x\"");
+        assert!(!reloaded.ui.list_view.show_age);
+        assert_eq!(reloaded.network.rate_limit_per_second, 5.0);
+        assert_eq!(reloaded.theme_name, "Flexoki Dark");
+    }
+}
@@ -0,0 +1,309 @@
+//! `--validate`: check the config file, every theme file, the keybindings, the row
+//! template and the status bar formats without starting the TUI, for dotfile CI.
+//! Nothing is written; a config that needs migrating is checked as migrated.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::{AppConfig, KeyBindingConfig, migration};
+use crate::internal::ui::app::Action;
+use crate::internal::ui::keybindings::{key_label, parse_chord_str, parse_key_str};
+use crate::internal::ui::row_template::RowTemplate;
+use crate::internal::ui::status_template::StatusTemplate;
+use crate::utils::theme_loader::{ThemeFile, theme_files, variant_problems};
+
+/// Something wrong, and where: a file position or a path inside the config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    pub location: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+/// What was checked and what was found.
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    /// One line per file or section checked
+    pub checked: Vec<String>,
+    pub problems: Vec<Problem>,
+}
+
+/// Validate the config at `config_path` (or the one the app would load) with the
+/// themes and keybindings it refers to.
+pub fn validate(config_path: Option<&Path>) -> Report {
+    let mut report = Report::default();
+    let path = config_path.map(Path::to_path_buf).or_else(|| {
+        AppConfig::candidate_paths()
+            .into_iter()
+            .find(|p| p.exists())
+    });
+    let config = match &path {
+        Some(path) => match check_config(path) {
+            Ok(config) => {
+                report.checked.push(format!("config {}", path.display()));
+                config
+            }
+            Err(problem) => {
+                report.problems.push(problem);
+                return report;
+            }
+        },
+        None => {
+            report
+                .checked
+                .push("no config file, checking the defaults".to_string());
+            AppConfig::default()
+        }
+    };
+
+    if let Some(keybindings) = &config.keybindings {
+        let count = contexts(keybindings)
+            .map(|(_, map)| map.len())
+            .sum::<usize>();
+        report.checked.push(format!("{} keybindings", count));
+        report.problems.extend(keybinding_problems(keybindings));
+    }
+
+    report.problems.extend(template_problems(&config));
+
+    let files = theme_files(&config.theme_directory, &config.theme_file);
+    report.checked.push(format!("{} theme files", files.len()));
+    for file in files {
+        match std::fs::read_to_string(&file) {
+            Ok(content) => report.problems.extend(theme_problems(&file, &content)),
+            Err(e) => report.problems.push(Problem {
+                location: file.display().to_string(),
+                message: format!("cannot be read: {}", e),
+            }),
+        }
+    }
+    report
+}
+
+/// Row template and status bar formats that do not parse.
+fn template_problems(config: &AppConfig) -> Vec<Problem> {
+    let ui = &config.ui;
+    let row = ui.list_view.row_template.as_deref().map(|template| {
+        (
+            "ui.list_view.row_template",
+            RowTemplate::parse(template).err(),
+        )
+    });
+    let status = (!ui.status_bar_format.is_empty()).then(|| {
+        (
+            "ui.status_bar_format",
+            StatusTemplate::parse(&ui.status_bar_format).err(),
+        )
+    });
+    let message = Some((
+        "ui.message_line_format",
+        StatusTemplate::parse(&ui.message_line_format).err(),
+    ));
+    [row, status, message]
+        .into_iter()
+        .flatten()
+        .filter_map(|(location, error)| {
+            error.map(|message| Problem {
+                location: location.to_string(),
+                message,
+            })
+        })
+        .collect()
+}
+
+/// Parse the config, migrated in memory if it is from an older version.
+fn check_config(path: &PathBuf) -> Result<AppConfig, Problem> {
+    let content = std::fs::read_to_string(path).map_err(|e| Problem {
+        location: path.display().to_string(),
+        message: format!("cannot be read: {}", e),
+    })?;
+    let (text, note) = match migration::migrate(&content) {
+        Some(migrated) => (
+            migrated.text,
+            format!(" (after migrating from version {})", migrated.from),
+        ),
+        None => (content, String::new()),
+    };
+    ron::from_str(&text).map_err(|e| Problem {
+        location: format!(
+            "{}:{}:{}",
+            path.display(),
+            e.span.start.line,
+            e.span.start.col
+        ),
+        message: format!("{}{}", e.code, note),
+    })
+}
+
+fn contexts(
+    config: &KeyBindingConfig,
+) -> impl Iterator<Item = (&'static str, &std::collections::HashMap<String, Action>)> {
+    [
+        ("global", &config.global),
+        ("list", &config.list),
+        ("story_detail", &config.story_detail),
+        ("article", &config.article),
+        ("bookmarks", &config.bookmarks),
+        ("history", &config.history),
+        ("reading_lists", &config.reading_lists),
+    ]
+    .into_iter()
+}
+
+/// Key strings that are not keys, and keys bound to different actions in one
+/// context by spellings that read the same (`"ctrl+d"` and `"Ctrl+d"`).
+pub fn keybinding_problems(config: &KeyBindingConfig) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    for (context, bindings) in contexts(config) {
+        let mut by_key: BTreeMap<String, Vec<(&String, &Action)>> = BTreeMap::new();
+        let mut keys: Vec<&String> = bindings.keys().collect();
+        keys.sort();
+        for key_str in keys {
+            let label = match (parse_chord_str(key_str), parse_key_str(key_str)) {
+                (Some((first, second)), _) => {
+                    format!("{} {}", key_label(&first), key_label(&second))
+                }
+                (None, Some(key)) => key_label(&key),
+                (None, None) => {
+                    problems.push(Problem {
+                        location: format!("keybindings.{}.\"{}\"", context, key_str),
+                        message: "not a valid key or chord".to_string(),
+                    });
+                    continue;
+                }
+            };
+            by_key
+                .entry(label)
+                .or_default()
+                .push((key_str, &bindings[key_str]));
+        }
+        for (label, bound) in by_key {
+            let first_action = bound[0].1;
+            if bound.iter().all(|(_, action)| *action == first_action) {
+                continue;
+            }
+            let spellings: Vec<String> = bound
+                .iter()
+                .map(|(key, action)| format!("\"{}\" → {:?}", key, action))
+                .collect();
+            problems.push(Problem {
+                location: format!("keybindings.{}", context),
+                message: format!("{} is bound twice: {}", label, spellings.join(", ")),
+            });
+        }
+    }
+    problems
+}
+
+/// Malformed JSON (with its line and column), variants missing for a mode, and
+/// missing or invalid colors, located by their path in the file.
+pub fn theme_problems(path: &Path, content: &str) -> Vec<Problem> {
+    let file: ThemeFile = match serde_json::from_str(content) {
+        Ok(file) => file,
+        Err(e) => {
+            return vec![Problem {
+                location: format!("{}:{}:{}", path.display(), e.line(), e.column()),
+                message: format!("malformed theme: {}", e),
+            }];
+        }
+    };
+    let mut problems = Vec::new();
+    for mode in ["dark", "light"] {
+        if !file.themes.iter().any(|variant| variant.mode == mode) {
+            problems.push(Problem {
+                location: path.display().to_string(),
+                message: format!("no {} variant; the first variant is used instead", mode),
+            });
+        }
+    }
+    for (i, variant) in file.themes.iter().enumerate() {
+        problems.extend(
+            variant_problems(variant)
+                .into_iter()
+                .map(|message| Problem {
+                    location: format!("{}: themes[{}].colors", path.display(), i),
+                    message,
+                }),
+        );
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_conflicting_and_invalid_keys() {
+        let config: KeyBindingConfig = ron::from_str(
+            r#"(list: {"ctrl+d": "Quit", "Ctrl+d": "NavigateDown", "Ctrl+Q+": "Quit", "g g": "Back"},
+                global: {"q": "Quit", "x": "Quit"})"#,
+        )
+        .unwrap();
+        let problems = keybinding_problems(&config);
+        let lines: Vec<String> = problems.iter().map(|p| p.to_string()).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "keybindings.list.\"Ctrl+Q+\": not a valid key or chord",
+                "keybindings.list: Ctrl+d is bound twice: \"Ctrl+d\" → NavigateDown, \"ctrl+d\" → Quit",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_formats_that_do_not_parse() {
+        let mut config = AppConfig::default();
+        assert!(template_problems(&config).is_empty());
+        config.ui.status_bar_format = "{mode} {nope}".to_string();
+        config.ui.message_line_format = "{message".to_string();
+        let locations: Vec<_> = template_problems(&config)
+            .into_iter()
+            .map(|problem| problem.location)
+            .collect();
+        assert_eq!(
+            locations,
+            ["ui.status_bar_format", "ui.message_line_format"]
+        );
+    }
+
+    #[test]
+    fn reports_theme_problems_with_paths() {
+        let path = Path::new("themes/mine.json");
+        let malformed = theme_problems(path, "{\"name\": \"Mine\",\n \"themes\": [}");
+        assert_eq!(malformed.len(), 1);
+        assert!(malformed[0].location.starts_with("themes/mine.json:2:"));
+
+        let json = r##"{"name": "Mine", "themes": [{"name": "Mine Dark", "mode": "dark",
+            "colors": {"background": "#000000", "foreground": "#fffff", "border": "#333333",
+                "selection.background": "#111111", "base.blue": "#0000ff", "base.yellow": "yellow"}}]}"##;
+        let lines: Vec<String> = theme_problems(path, json)
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "themes/mine.json: no light variant; the first variant is used instead",
+                "themes/mine.json: themes[0].colors: missing property \"muted.foreground\"",
+                "themes/mine.json: themes[0].colors: \"foreground\": \"#fffff\" is not a color",
+            ]
+        );
+    }
+
+    #[test]
+    fn bundled_themes_are_valid() {
+        for file in std::fs::read_dir("themes").unwrap().flatten() {
+            let path = file.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                let content = std::fs::read_to_string(&path).unwrap();
+                assert_eq!(theme_problems(&path, &content), vec![]);
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! Config-driven rules that bookmark stories as they load or refresh.
+
+use crate::config::AutoBookmarkRule;
+use crate::internal::models::Story;
+
+/// A rule that matched a story.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleHit {
+    pub rule: String,
+    pub story_id: u32,
+    pub tag: Option<String>,
+}
+
+pub fn matches(rule: &AutoBookmarkRule, story: &Story) -> bool {
+    let domain_ok = match (&rule.domain, &story.url) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(wanted), Some(url)) => crate::utils::url::extract_domain(url).is_some_and(|d| {
+            let wanted = wanted.trim_start_matches("www.").to_lowercase();
+            let d = d.to_lowercase();
+            d == wanted || d.ends_with(&format!(".{}", wanted))
+        }),
+    };
+    let title = story.title.as_deref().unwrap_or("").to_lowercase();
+    let title_ok = rule.title_contains.is_empty()
+        || rule
+            .title_contains
+            .iter()
+            .any(|word| title.contains(&word.to_lowercase()));
+    let author_ok = match (&rule.author, &story.by) {
+        (None, _) => true,
+        (Some(wanted), Some(by)) => wanted.eq_ignore_ascii_case(by),
+        (Some(_), None) => false,
+    };
+    let score_ok = rule
+        .min_score
+        .is_none_or(|min| story.score.unwrap_or(0) >= min);
+    let comments_ok = rule
+        .min_comments
+        .is_none_or(|min| story.descendants.unwrap_or(0) >= min);
+
+    rule.has_conditions() && domain_ok && title_ok && author_ok && score_ok && comments_ok
+}
+
+/// First matching rule per story, in rule order. `skip` filters out stories
+/// that are already bookmarked or were handled earlier.
+pub fn evaluate<'a>(
+    rules: &[AutoBookmarkRule],
+    stories: impl IntoIterator<Item = &'a Story>,
+    skip: impl Fn(u32) -> bool,
+) -> Vec<RuleHit> {
+    stories
+        .into_iter()
+        .filter(|story| !skip(story.id))
+        .filter_map(|story| {
+            rules
+                .iter()
+                .find(|rule| matches(rule, story))
+                .map(|rule| RuleHit {
+                    rule: rule.name.clone(),
+                    story_id: story.id,
+                    tag: rule.tag.clone(),
+                })
+        })
+        .collect()
+}
+
+/// One-line summary such as "papers: 2, rust: 1".
+pub fn summarize(hits: &[RuleHit]) -> String {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for hit in hits {
+        match counts.iter_mut().find(|(name, _)| *name == hit.rule) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((&hit.rule, 1)),
+        }
+    }
+    counts
+        .iter()
+        .map(|(name, n)| format!("{}: {}", name, n))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn story(id: u32, url: &str, title: &str, score: u32) -> Story {
+        Story {
+            id,
+            title: Some(title.to_string()),
+            url: Some(url.to_string()),
+            score: Some(score),
+            ..Default::default()
+        }
+    }
+
+    fn papers_rule() -> AutoBookmarkRule {
+        AutoBookmarkRule {
+            name: "papers".to_string(),
+            domain: Some("arxiv.org".to_string()),
+            min_score: Some(200),
+            tag: Some("papers".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rule_requires_every_condition() {
+        let rule = papers_rule();
+        assert!(matches(
+            &rule,
+            &story(1, "https://arxiv.org/abs/1", "A", 250)
+        ));
+        assert!(matches(
+            &rule,
+            &story(2, "https://export.arxiv.org/x", "B", 200)
+        ));
+        assert!(!matches(
+            &rule,
+            &story(3, "https://arxiv.org/abs/1", "C", 150)
+        ));
+        assert!(!matches(
+            &rule,
+            &story(4, "https://notarxiv.org/", "D", 500)
+        ));
+
+        let words = AutoBookmarkRule {
+            name: "rust".to_string(),
+            title_contains: vec!["Rust".to_string(), "cargo".to_string()],
+            ..Default::default()
+        };
+        assert!(matches(&words, &story(5, "https://x.dev", "Why rust?", 1)));
+        assert!(!matches(&words, &story(6, "https://x.dev", "Go 2", 1)));
+
+        // Rules without conditions never fire
+        assert!(!matches(
+            &AutoBookmarkRule::default(),
+            &story(7, "https://x.dev", "E", 1)
+        ));
+    }
+
+    #[test]
+    fn evaluate_uses_first_matching_rule_and_skips() {
+        let rules = vec![
+            papers_rule(),
+            AutoBookmarkRule {
+                name: "popular".to_string(),
+                min_score: Some(100),
+                ..Default::default()
+            },
+        ];
+        let stories = [
+            story(1, "https://arxiv.org/abs/1", "A", 300),
+            story(2, "https://example.com", "B", 120),
+            story(3, "https://example.com", "C", 10),
+            story(4, "https://example.com", "D", 999),
+        ];
+        let hits = evaluate(&rules, &stories, |id| id == 4);
+        let fired: Vec<(u32, &str)> = hits.iter().map(|h| (h.story_id, h.rule.as_str())).collect();
+        assert_eq!(fired, vec![(1, "papers"), (2, "popular")]);
+        assert_eq!(hits[0].tag.as_deref(), Some("papers"));
+        assert_eq!(summarize(&hits), "papers: 1, popular: 1");
+    }
+}
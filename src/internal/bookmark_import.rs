@@ -0,0 +1,233 @@
+//! Import bookmarks from a Pocket export, a Netscape bookmarks file (what browsers
+//! export), this app's own bookmarks JSON, or a plain list of HN item ids/URLs.
+//! Only links to HN items can become bookmarks; titles and URLs of the stories
+//! are looked up with the API.
+
+use std::collections::HashSet;
+
+use jiff::{Timestamp, Zoned, tz::TimeZone};
+use scraper::{ElementRef, Html, Selector};
+
+use super::bookmarks::{BookmarkedStory, Bookmarks, normalize_tag};
+use crate::api::ApiService;
+use crate::utils::url::{parse_item_id, parse_item_ids};
+
+/// An HN item found in an import file.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ImportEntry {
+    pub id: u32,
+    /// Title in the file, used if the API has none
+    pub title: Option<String>,
+    pub tags: Vec<String>,
+    /// When it was saved, as a Unix timestamp
+    pub added_at: Option<i64>,
+}
+
+/// Items found in a file, and how many links were not HN items.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Parsed {
+    pub entries: Vec<ImportEntry>,
+    pub skipped: usize,
+}
+
+/// Parse any of the supported formats, telling them apart by their content.
+pub fn parse(text: &str) -> Parsed {
+    if let Ok(bookmarks) = serde_json::from_str::<Bookmarks>(text) {
+        return Parsed {
+            entries: bookmarks
+                .stories
+                .into_iter()
+                .map(|b| ImportEntry {
+                    id: b.id,
+                    title: Some(b.title),
+                    tags: b.tags,
+                    added_at: Some(b.bookmarked_at.timestamp().as_second()),
+                })
+                .collect(),
+            skipped: 0,
+        };
+    }
+    match text.to_ascii_lowercase().contains("<a ") {
+        true => parse_html(text),
+        false => Parsed {
+            entries: parse_item_ids(text)
+                .into_iter()
+                .map(|id| ImportEntry {
+                    id,
+                    ..Default::default()
+                })
+                .collect(),
+            skipped: 0,
+        },
+    }
+}
+
+/// Links of a Pocket or Netscape HTML export. Pocket keeps tags in a `tags`
+/// attribute; Netscape files in `tags` and in the folders (`<h3>` headings) around
+/// the link, which become nested tags like `tech/rust`.
+fn parse_html(text: &str) -> Parsed {
+    let document = Html::parse_document(text);
+    let links = Selector::parse("a[href]").expect("valid selector");
+    let mut parsed = Parsed::default();
+    let mut seen = HashSet::new();
+    for link in document.select(&links) {
+        let attr = |name| link.value().attr(name);
+        let Some(id) = attr("href").and_then(parse_item_id) else {
+            parsed.skipped += 1;
+            continue;
+        };
+        if !seen.insert(id) {
+            continue;
+        }
+        let mut tags: Vec<String> = attr("tags")
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(normalize_tag)
+            .collect();
+        if let Some(folder) = folder_tag(link) {
+            tags.push(folder);
+        }
+        let title = link.text().collect::<String>().trim().to_string();
+        parsed.entries.push(ImportEntry {
+            id,
+            title: (!title.is_empty()).then_some(title),
+            tags,
+            added_at: attr("time_added")
+                .or(attr("add_date"))
+                .and_then(|t| t.trim().parse().ok()),
+        });
+    }
+    parsed
+}
+
+/// Folder path of a link in a Netscape bookmarks file. Each folder is a `<dl>`
+/// list that follows its `<h3>` name.
+fn folder_tag(link: ElementRef) -> Option<String> {
+    let mut folders: Vec<String> = link
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .filter(|el| el.value().name() == "dl")
+        .filter_map(|list| {
+            list.prev_siblings()
+                .filter_map(ElementRef::wrap)
+                .next()
+                .filter(|el| el.value().name() == "h3")
+                .map(|heading| heading.text().collect::<String>())
+        })
+        .collect();
+    folders.reverse();
+    normalize_tag(&folders.join("/"))
+}
+
+/// Look up the entries not bookmarked yet, `limit` at a time, and turn them into
+/// bookmarks. Items the API cannot return keep the title from the file; those
+/// without one are left out.
+pub async fn resolve(
+    api: &ApiService,
+    entries: Vec<ImportEntry>,
+    existing: &HashSet<u32>,
+    limit: usize,
+) -> Vec<BookmarkedStory> {
+    let entries: Vec<ImportEntry> = entries
+        .into_iter()
+        .filter(|entry| !existing.contains(&entry.id))
+        .collect();
+    let ids: Vec<u32> = entries.iter().map(|entry| entry.id).collect();
+    let stories = api.fetch_stories_concurrent(&ids, limit.max(1), None).await;
+    let now = Zoned::now();
+    entries
+        .into_iter()
+        .filter_map(|entry| {
+            let story = stories
+                .iter()
+                .filter_map(|result| result.as_ref().ok())
+                .find(|story| story.id == entry.id);
+            let title = story.and_then(|s| s.title.clone()).or(entry.title)?;
+            let bookmarked_at = entry
+                .added_at
+                .and_then(|t| Timestamp::from_second(t).ok())
+                .map(|t| t.to_zoned(TimeZone::system()))
+                .unwrap_or_else(|| now.clone());
+            Some(BookmarkedStory {
+                id: entry.id,
+                title,
+                url: story.and_then(|s| s.url.clone()),
+                bookmarked_at,
+                tags: entry.tags,
+                note: None,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pocket_exports() {
+        let html = r#"<!DOCTYPE html><html><body>
+            <h1>Unread</h1><ul>
+            <li><a href="https://news.ycombinator.com/item?id=42" time_added="1700000000" tags="Rust,to read">Show HN: A thing</a></li>
+            <li><a href="https://example.com/article" time_added="1700000001" tags="">Elsewhere</a></li>
+            </ul><h1>Read Archive</h1><ul>
+            <li><a href="https://news.ycombinator.com/item?id=42">Again</a></li>
+            </ul></body></html>"#;
+        let parsed = parse(html);
+        assert_eq!(parsed.skipped, 1);
+        assert_eq!(
+            parsed.entries,
+            vec![ImportEntry {
+                id: 42,
+                title: Some("Show HN: A thing".to_string()),
+                tags: vec!["rust".to_string(), "to-read".to_string()],
+                added_at: Some(1_700_000_000),
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_netscape_folders_as_tags() {
+        let html = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+            <TITLE>Bookmarks</TITLE><H1>Bookmarks</H1>
+            <DL><p>
+                <DT><A HREF="https://news.ycombinator.com/item?id=1" ADD_DATE="1600000000">Top level</A>
+                <DT><H3>Tech</H3>
+                <DL><p>
+                    <DT><H3>Rust Lang</H3>
+                    <DL><p>
+                        <DT><A HREF="https://news.ycombinator.com/item?id=2" TAGS="async">Nested</A>
+                    </DL><p>
+                </DL><p>
+            </DL><p>"#;
+        let parsed = parse(html);
+        let tags: Vec<(u32, Vec<String>)> = parsed
+            .entries
+            .iter()
+            .map(|e| (e.id, e.tags.clone()))
+            .collect();
+        assert_eq!(
+            tags,
+            vec![
+                (1, vec![]),
+                (2, vec!["async".to_string(), "tech/rust-lang".to_string()])
+            ]
+        );
+        assert_eq!(parsed.entries[0].added_at, Some(1_600_000_000));
+    }
+
+    #[test]
+    fn parses_id_lists_and_own_exports() {
+        let parsed = parse("8863\nhttps://news.ycombinator.com/item?id=121003\n");
+        let ids: Vec<u32> = parsed.entries.iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![8863, 121003]);
+
+        let json = r#"{"stories":[{"id":7,"title":"Mine","url":null,
+            "bookmarked_at":"2024-01-01T00:00:00+00:00[UTC]","tags":["x"]}]}"#;
+        let entry = &parse(json).entries[0];
+        assert_eq!(
+            (entry.id, entry.title.as_deref(), entry.tags.clone()),
+            (7, Some("Mine"), vec!["x".to_string()])
+        );
+    }
+}
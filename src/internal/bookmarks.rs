@@ -7,16 +7,68 @@ use tracing::info;
 
 use super::models::Story;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Version of the bookmarks.json layout. Files without a version predate tags
+/// being normalized and are migrated when loaded.
+const SCHEMA_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BookmarkedStory {
     pub id: u32,
     pub title: String,
     pub url: Option<String>,
     pub bookmarked_at: Zoned,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Free-form note, possibly several lines
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl BookmarkedStory {
+    /// Whether the bookmark has `filter` as a tag, or a tag in the `filter/`
+    /// folder: "lang" matches "lang" and "lang/rust".
+    pub fn has_tag(&self, filter: &str) -> bool {
+        self.tags.iter().any(|tag| {
+            tag == filter
+                || tag
+                    .strip_prefix(filter)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+}
+
+/// Lowercase a typed tag and drop a leading `#`, surrounding slashes and inner
+/// spaces; `None` when nothing is left.
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().trim_start_matches('#').trim_matches('/');
+    let tag = tag
+        .split('/')
+        .map(|part| part.split_whitespace().collect::<Vec<_>>().join("-"))
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+        .to_lowercase();
+    match tag.is_empty() {
+        true => None,
+        false => Some(tag),
+    }
+}
+
+/// Parse comma-separated tags as typed in the tag prompt.
+pub fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text.split(',').filter_map(normalize_tag) {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Bookmarks {
+    #[serde(default)]
+    pub version: u32,
     pub stories: Vec<BookmarkedStory>,
     #[serde(skip)]
     file_path: Option<PathBuf>,
@@ -25,6 +77,7 @@ pub struct Bookmarks {
 impl Bookmarks {
     pub fn new() -> Self {
         Self {
+            version: SCHEMA_VERSION,
             stories: Vec::new(),
             file_path: None,
         }
@@ -64,18 +117,36 @@ impl Bookmarks {
                     serde_json::from_str(&content).context("Failed to parse bookmarks file")?;
                 bookmarks.file_path = Some(file_path.clone());
                 info!(bookmarks_file = %file_path.display(), "Loaded bookmarks from file");
+                if bookmarks.migrate() {
+                    info!(version = SCHEMA_VERSION, "Migrated bookmarks file");
+                    bookmarks.save()?;
+                }
                 Ok(bookmarks)
             }
             false => {
                 info!(bookmarks_file = %file_path.display(), "No bookmarks file found, initializing empty bookmarks with file path set");
                 Ok(Self {
-                    stories: Vec::new(),
                     file_path: Some(file_path),
+                    ..Self::new()
                 })
             }
         }
     }
 
+    /// Bring bookmarks from an older file up to `SCHEMA_VERSION`. Tags written
+    /// by hand or by scripts before tags were normalized are cleaned up.
+    /// Returns whether anything changed.
+    fn migrate(&mut self) -> bool {
+        if self.version >= SCHEMA_VERSION {
+            return false;
+        }
+        for bookmark in &mut self.stories {
+            bookmark.tags = parse_tags(&bookmark.tags.join(","));
+        }
+        self.version = SCHEMA_VERSION;
+        true
+    }
+
     pub fn save(&self) -> Result<()> {
         match &self.file_path {
             Some(path) => {
@@ -98,16 +169,114 @@ impl Bookmarks {
                 title: story.title.clone().unwrap_or_default(),
                 url: story.url.clone(),
                 bookmarked_at: Zoned::now(),
+                tags: Vec::new(),
+                note: None,
             };
             // Add to beginning of list (newest first)
             self.stories.insert(0, bookmarked);
         }
     }
 
+    /// Bookmark a story with a tag, or add the tag if it is already bookmarked.
+    pub fn add_tagged(&mut self, story: &Story, tag: Option<&str>) {
+        self.add(story);
+        if let (Some(tag), Some(bookmark)) = (
+            tag.and_then(normalize_tag),
+            self.stories.iter_mut().find(|s| s.id == story.id),
+        ) && !bookmark.tags.contains(&tag)
+        {
+            bookmark.tags.push(tag);
+        }
+    }
+
+    /// Add imported bookmarks that are not bookmarked yet, keeping the list newest
+    /// first. Returns how many were added.
+    pub fn merge(&mut self, imported: Vec<BookmarkedStory>) -> usize {
+        let before = self.stories.len();
+        for bookmark in imported {
+            if !self.contains(bookmark.id) {
+                self.stories.push(bookmark);
+            }
+        }
+        self.stories.sort_by(|a, b| {
+            b.bookmarked_at
+                .timestamp()
+                .cmp(&a.bookmarked_at.timestamp())
+        });
+        self.stories.len() - before
+    }
+
+    /// Replace the tags of a bookmarked story.
+    pub fn set_tags(&mut self, id: u32, tags: Vec<String>) {
+        if let Some(bookmark) = self.stories.iter_mut().find(|s| s.id == id) {
+            bookmark.tags = tags;
+        }
+    }
+
+    /// Attach a note to a bookmarked story; `None` removes it.
+    pub fn set_note(&mut self, id: u32, note: Option<String>) {
+        if let Some(bookmark) = self.stories.iter_mut().find(|s| s.id == id) {
+            bookmark.note = note;
+        }
+    }
+
+    pub fn note_of(&self, id: u32) -> Option<&str> {
+        self.stories
+            .iter()
+            .find(|s| s.id == id)
+            .and_then(|s| s.note.as_deref())
+    }
+
+    pub fn tags_of(&self, id: u32) -> &[String] {
+        self.stories
+            .iter()
+            .find(|s| s.id == id)
+            .map_or(&[], |s| s.tags.as_slice())
+    }
+
+    /// Every tag in use, plus the folders above nested tags, sorted.
+    pub fn all_tags(&self) -> Vec<String> {
+        let mut tags = std::collections::BTreeSet::new();
+        for tag in self.stories.iter().flat_map(|s| &s.tags) {
+            let mut end = 0;
+            for part in tag.split('/') {
+                end += part.len();
+                tags.insert(tag[..end].to_string());
+                end += 1;
+            }
+        }
+        tags.into_iter().collect()
+    }
+
     pub fn remove(&mut self, id: u32) {
         self.stories.retain(|s| s.id != id);
     }
 
+    /// Pick a bookmark not read yet for a `roll` in `[0, 1)`. With `prefer_older`
+    /// each bookmark's chance grows with the days since it was saved, so the
+    /// backlog gets read too.
+    pub fn pick_unread(
+        &self,
+        is_read: impl Fn(u32) -> bool,
+        prefer_older: bool,
+        now: &Zoned,
+        roll: f64,
+    ) -> Option<&BookmarkedStory> {
+        let unread: Vec<&BookmarkedStory> =
+            self.stories.iter().filter(|s| !is_read(s.id)).collect();
+        let weights: Vec<f64> = unread
+            .iter()
+            .map(|s| match prefer_older {
+                true => {
+                    let age = now.timestamp().as_second() - s.bookmarked_at.timestamp().as_second();
+                    1.0 + (age.max(0) / 86_400) as f64
+                }
+                false => 1.0,
+            })
+            .collect();
+        crate::utils::sample::weighted_index(&weights, roll).map(|idx| unread[idx])
+    }
+
     pub fn contains(&self, id: u32) -> bool {
         self.stories.iter().any(|s| s.id == id)
     }
@@ -136,6 +305,9 @@ mod tests {
             time: Some(1234567890),
             descendants: Some(10),
             kids: None,
+            parts: None,
+            item_type: None,
+            text: None,
         };
 
         bookmarks.add(&story);
@@ -160,6 +332,9 @@ mod tests {
             time: None,
             descendants: None,
             kids: None,
+            parts: None,
+            item_type: None,
+            text: None,
         };
 
         bookmarks.toggle(&story);
@@ -168,4 +343,114 @@ mod tests {
         bookmarks.toggle(&story);
         assert!(!bookmarks.contains(2));
     }
+
+    #[test]
+    fn test_merge_skips_existing_and_sorts_newest_first() {
+        let mut bookmarks = Bookmarks::new();
+        bookmarks.add(&Story {
+            id: 1,
+            ..Default::default()
+        });
+        let at = |id: u32, time: &str| BookmarkedStory {
+            id,
+            title: format!("Imported {}", id),
+            url: None,
+            bookmarked_at: time.parse().unwrap(),
+            tags: Vec::new(),
+            note: None,
+        };
+        let added = bookmarks.merge(vec![
+            at(1, "2020-01-01T00:00:00+00:00[UTC]"),
+            at(2, "2020-01-01T00:00:00+00:00[UTC]"),
+            at(3, "2021-01-01T00:00:00+00:00[UTC]"),
+        ]);
+        assert_eq!(added, 2);
+        let ids: Vec<u32> = bookmarks.stories.iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec![1, 3, 2]);
+        assert_eq!(bookmarks.stories[0].title, "");
+    }
+
+    #[test]
+    fn test_pick_unread_prefers_older_bookmarks() {
+        let mut bookmarks = Bookmarks::new();
+        let at = |id: u32, time: &str| BookmarkedStory {
+            id,
+            title: String::new(),
+            url: None,
+            bookmarked_at: time.parse().unwrap(),
+            tags: Vec::new(),
+            note: None,
+        };
+        bookmarks.merge(vec![
+            at(1, "2024-03-01T00:00:00+00:00[UTC]"),
+            at(2, "2024-03-05T00:00:00+00:00[UTC]"),
+            at(3, "2024-03-14T00:00:00+00:00[UTC]"),
+        ]);
+        let now: Zoned = "2024-03-14T12:00:00+00:00[UTC]".parse().unwrap();
+        let is_read = |id| id == 1;
+        let pick = |older, roll| {
+            bookmarks
+                .pick_unread(is_read, older, &now, roll)
+                .map(|b| b.id)
+        };
+        // Story 3 is a day old at most (weight 1), story 2 nine days (weight 10)
+        assert_eq!(pick(true, 0.05), Some(3));
+        assert_eq!(pick(true, 0.5), Some(2));
+        assert_eq!(pick(false, 0.4), Some(3));
+        assert_eq!(pick(false, 0.5), Some(2));
+        assert_eq!(
+            bookmarks
+                .pick_unread(|_| true, true, &now, 0.5)
+                .map(|b| b.id),
+            None
+        );
+    }
+
+    #[test]
+    fn test_tags_and_folders() {
+        assert_eq!(
+            parse_tags(" #Rust, lang / Go Lang ,, rust"),
+            vec!["rust", "lang/go-lang"]
+        );
+        assert_eq!(normalize_tag(" # "), None);
+
+        let mut bookmarks = Bookmarks::new();
+        let story = |id| Story {
+            id,
+            ..Default::default()
+        };
+        bookmarks.add_tagged(&story(1), Some("Lang/Rust"));
+        bookmarks.add(&story(2));
+        bookmarks.set_tags(2, parse_tags("lang/go, to-read"));
+        assert_eq!(bookmarks.tags_of(1), ["lang/rust"]);
+        assert_eq!(
+            bookmarks.all_tags(),
+            vec!["lang", "lang/go", "lang/rust", "to-read"]
+        );
+
+        let bookmark = &bookmarks.stories[1];
+        assert!(bookmark.has_tag("lang") && bookmark.has_tag("lang/rust"));
+        assert!(!bookmark.has_tag("lan") && !bookmark.has_tag("lang/go"));
+    }
+
+    #[test]
+    fn test_migrates_files_without_a_version() {
+        let json = r#"{"stories":[{"id":1,"title":"Old","url":null,
+            "bookmarked_at":"2024-01-01T00:00:00+00:00[UTC]","tags":["Rust"," rust","AI Safety"]},
+            {"id":2,"title":"Untagged","url":null,"bookmarked_at":"2024-01-01T00:00:00+00:00[UTC]"}]}"#;
+        let mut bookmarks: Bookmarks = serde_json::from_str(json).unwrap();
+        assert_eq!(bookmarks.version, 0);
+        assert!(bookmarks.migrate());
+        assert_eq!(bookmarks.version, SCHEMA_VERSION);
+        assert_eq!(bookmarks.stories[0].tags, vec!["rust", "ai-safety"]);
+        assert!(bookmarks.stories[1].tags.is_empty());
+        assert_eq!(bookmarks.note_of(1), None);
+        assert!(!bookmarks.migrate());
+
+        // Notes are written only when set
+        bookmarks.set_note(1, Some("Read the\nfollow-up".to_string()));
+        let json = serde_json::to_string(&bookmarks).unwrap();
+        assert!(json.contains(r#""note":"Read the\nfollow-up""#));
+        assert_eq!(json.matches("\"note\"").count(), 1);
+    }
 }
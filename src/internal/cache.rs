@@ -1,12 +1,92 @@
-use std::collections::HashMap;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::collections::{BTreeMap, HashMap};
 use std::hash::Hash;
-use std::sync::{Arc, RwLock};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::{Duration, Instant};
 
 /// A cache entry with expiration time
 struct CacheEntry<V> {
     value: V,
     expires_at: Instant,
+    /// Tie-breaker for entries expiring at the same instant; with `expires_at`,
+    /// the entry's key in `Entries::by_expiry`
+    seq: u64,
+}
+
+/// The entries, plus an index of their keys ordered by expiry so eviction takes
+/// the soonest to expire without sorting.
+struct Entries<K, V> {
+    map: HashMap<K, CacheEntry<V>>,
+    by_expiry: BTreeMap<(Instant, u64), K>,
+    next_seq: u64,
+}
+
+impl<K, V> Default for Entries<K, V> {
+    fn default() -> Self {
+        Self {
+            map: HashMap::new(),
+            by_expiry: BTreeMap::new(),
+            next_seq: 0,
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> Entries<K, V> {
+    fn insert(&mut self, key: K, value: V, expires_at: Instant) {
+        self.remove(&key);
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.by_expiry.insert((expires_at, seq), key.clone());
+        self.map.insert(
+            key,
+            CacheEntry {
+                value,
+                expires_at,
+                seq,
+            },
+        );
+    }
+
+    fn remove(&mut self, key: &K) -> Option<CacheEntry<V>> {
+        let entry = self.map.remove(key)?;
+        self.by_expiry.remove(&(entry.expires_at, entry.seq));
+        Some(entry)
+    }
+
+    fn clear(&mut self) {
+        self.map.clear();
+        self.by_expiry.clear();
+    }
+
+    /// Drop the entries expiring soonest until at most `max` remain. Returns how many went.
+    fn evict_to(&mut self, max: usize) -> usize {
+        let mut evicted = 0;
+        while self.map.len() > max {
+            let Some((_, key)) = self.by_expiry.pop_first() else {
+                break;
+            };
+            self.map.remove(&key);
+            evicted += 1;
+        }
+        evicted
+    }
+
+    /// Drop the entries that expired at or before `now`. Returns how many went.
+    fn remove_expired(&mut self, now: Instant) -> usize {
+        let mut removed = 0;
+        while let Some(entry) = self.by_expiry.first_entry() {
+            if entry.key().0 > now {
+                break;
+            }
+            let key = entry.remove();
+            self.map.remove(&key);
+            removed += 1;
+        }
+        removed
+    }
 }
 
 /// Generic in-memory cache with TTL support
@@ -15,9 +95,60 @@ struct CacheEntry<V> {
 /// `Cache::new` creates a cache with metrics disabled to preserve the existing
 /// constructor signature. To enable metrics, use `Cache::with_metrics`.
 pub struct Cache<K, V> {
-    entries: Arc<RwLock<HashMap<K, CacheEntry<V>>>>,
+    entries: Arc<RwLock<Entries<K, V>>>,
     ttl: Duration,
     enable_metrics: bool,
+    /// When set, `set` evicts the entries closest to expiry beyond this many
+    max_entries: Option<usize>,
+    /// Lookup and eviction counters, shared between clones
+    counters: Arc<Counters>,
+    /// Held while saving, so clones persisting at once don't interleave writes
+    persist_lock: Arc<Mutex<()>>,
+}
+
+#[derive(Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    stale_serves: AtomicU64,
+}
+
+/// Point-in-time view of a cache, for the cache statistics overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CacheStats {
+    pub entries: usize,
+    /// Entries past their TTL, kept as stale fallback
+    pub expired: usize,
+    pub max_entries: Option<usize>,
+    pub ttl: Duration,
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries dropped to stay under `max_entries`
+    pub evictions: u64,
+    /// Expired entries served because a fresh copy could not be fetched
+    pub stale_serves: u64,
+    /// Rough size of the cached values in bytes
+    pub approx_bytes: usize,
+}
+
+impl CacheStats {
+    /// Share of lookups served from the cache, `None` before the first lookup.
+    pub fn hit_ratio(&self) -> Option<f64> {
+        match self.hits + self.misses {
+            0 => None,
+            total => Some(self.hits as f64 / total as f64),
+        }
+    }
+}
+
+/// On-disk form of an entry; `Instant` can't outlive the process, so expiry is
+/// stored as a Unix timestamp.
+#[derive(Serialize, Deserialize)]
+struct DiskEntry<K, V> {
+    key: K,
+    value: V,
+    expires_at: i64,
 }
 
 impl<K, V> Cache<K, V>
@@ -34,9 +165,62 @@ where
     /// Create a new cache with the specified TTL and explicit metrics flag.
     pub fn with_metrics(ttl: Duration, enable_metrics: bool) -> Self {
         Self {
-            entries: Arc::new(RwLock::new(HashMap::new())),
+            entries: Arc::default(),
             ttl,
             enable_metrics,
+            max_entries: None,
+            counters: Arc::default(),
+            persist_lock: Arc::default(),
+        }
+    }
+
+    /// Bound the number of entries; the ones expiring soonest (oldest) go first.
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries
+            .read()
+            .map(|entries| entries.map.len())
+            .unwrap_or(0)
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Snapshot of the counters and contents. `weigh` estimates the size of one value.
+    pub fn stats(&self, weigh: impl Fn(&V) -> usize) -> CacheStats {
+        let now = Instant::now();
+        let (entries, expired, approx_bytes) = self
+            .entries
+            .read()
+            .map(|entries| {
+                entries
+                    .map
+                    .values()
+                    .fold((0, 0, 0), |(count, expired, bytes), entry| {
+                        (
+                            count + 1,
+                            expired + usize::from(now >= entry.expires_at),
+                            bytes + std::mem::size_of::<K>() + weigh(&entry.value),
+                        )
+                    })
+            })
+            .unwrap_or_default();
+        CacheStats {
+            entries,
+            expired,
+            max_entries: self.max_entries,
+            ttl: self.ttl,
+            hits: self.counters.hits.load(Ordering::Relaxed),
+            misses: self.counters.misses.load(Ordering::Relaxed),
+            evictions: self.counters.evictions.load(Ordering::Relaxed),
+            stale_serves: self.counters.stale_serves.load(Ordering::Relaxed),
+            approx_bytes,
         }
     }
 
@@ -46,15 +230,17 @@ where
         let start = Instant::now();
         let entries = self.entries.read().ok()?;
 
-        if let Some(entry) = entries.get(key)
+        if let Some(entry) = entries.map.get(key)
             && Instant::now() < entry.expires_at
         {
+            self.counters.hits.fetch_add(1, Ordering::Relaxed);
             if self.enable_metrics {
                 tracing::debug!(elapsed = ?start.elapsed(), hit = true, "cache.get");
             }
             return Some(entry.value.clone());
         }
 
+        self.counters.misses.fetch_add(1, Ordering::Relaxed);
         if self.enable_metrics {
             tracing::debug!(elapsed = ?start.elapsed(), hit = false, "cache.get");
         }
@@ -65,20 +251,38 @@ where
     /// Useful for offline mode / fallback.
     pub fn get_stale(&self, key: &K) -> Option<V> {
         let entries = self.entries.read().ok()?;
-        entries.get(key).map(|entry| entry.value.clone())
+        entries.map.get(key).map(|entry| entry.value.clone())
+    }
+
+    /// `get_stale` for a fallback that is shown to the user, such as after a failed
+    /// fetch; serving an expired entry is counted in the stats.
+    pub fn serve_stale(&self, key: &K) -> Option<V> {
+        let entries = self.entries.read().ok()?;
+        let entry = entries.map.get(key)?;
+        if Instant::now() >= entry.expires_at {
+            self.counters.stale_serves.fetch_add(1, Ordering::Relaxed);
+        }
+        Some(entry.value.clone())
     }
 
     /// Set a value in the cache. Emits a tracing debug log with elapsed time when enabled.
     pub fn set(&self, key: K, value: V) {
+        self.set_with_ttl(key, value, self.ttl);
+    }
+
+    /// Set a value that stays fresh for `ttl` instead of the cache-wide TTL.
+    pub fn set_with_ttl(&self, key: K, value: V, ttl: Duration) {
         let start = Instant::now();
         if let Ok(mut entries) = self.entries.write() {
-            entries.insert(
-                key,
-                CacheEntry {
-                    value,
-                    expires_at: Instant::now() + self.ttl,
-                },
-            );
+            if let Some(max) = self.max_entries
+                && !entries.map.contains_key(&key)
+            {
+                let evicted = entries.evict_to(max.saturating_sub(1));
+                self.counters
+                    .evictions
+                    .fetch_add(evicted as u64, Ordering::Relaxed);
+            }
+            entries.insert(key, value, Instant::now() + ttl);
             if self.enable_metrics {
                 tracing::debug!(elapsed = ?start.elapsed(), "cache.set");
             }
@@ -120,12 +324,9 @@ where
     pub fn cleanup_expired(&self) {
         let start = Instant::now();
         if let Ok(mut entries) = self.entries.write() {
-            let before = entries.len();
-            let now = Instant::now();
-            entries.retain(|_, entry| now < entry.expires_at);
-            let after = entries.len();
+            let removed = entries.remove_expired(Instant::now());
             if self.enable_metrics {
-                tracing::debug!(elapsed = ?start.elapsed(), removed = before.saturating_sub(after), remaining = after, "cache.cleanup_expired");
+                tracing::debug!(elapsed = ?start.elapsed(), removed, remaining = entries.map.len(), "cache.cleanup_expired");
             }
         } else if self.enable_metrics {
             tracing::debug!(elapsed = ?start.elapsed(), "cache.cleanup_expired failed (lock poisoned)");
@@ -133,12 +334,97 @@ where
     }
 }
 
+impl<K, V> Cache<K, V>
+where
+    K: Eq + Hash + Clone + Serialize + DeserializeOwned,
+    V: Clone + Serialize + DeserializeOwned,
+{
+    /// Write all entries (expired ones too, they still serve as stale fallback) to `path`.
+    /// The file is written beside it and renamed into place, so a crash or a
+    /// concurrent load never sees half of it.
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        let _persisting = self
+            .persist_lock
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Cache persist lock poisoned"))?;
+        let now = Instant::now();
+        let now_unix = jiff::Timestamp::now().as_second();
+        let entries = self
+            .entries
+            .read()
+            .map_err(|_| anyhow::anyhow!("Cache lock poisoned"))?;
+        let disk: Vec<DiskEntry<&K, &V>> = entries
+            .map
+            .iter()
+            .map(|(key, entry)| DiskEntry {
+                key,
+                value: &entry.value,
+                expires_at: match entry.expires_at.checked_duration_since(now) {
+                    Some(left) => now_unix + left.as_secs() as i64,
+                    None => now_unix - now.duration_since(entry.expires_at).as_secs() as i64,
+                },
+            })
+            .collect();
+        let content = serde_json::to_string(&disk).context("Failed to serialize cache")?;
+        drop(entries);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let mut partial = path.as_os_str().to_owned();
+        partial.push(format!(".{}.tmp", std::process::id()));
+        let partial = std::path::PathBuf::from(partial);
+        std::fs::write(&partial, content)
+            .with_context(|| format!("Failed to write {}", partial.display()))?;
+        if let Err(e) = std::fs::rename(&partial, path) {
+            let _ = std::fs::remove_file(&partial);
+            return Err(e).with_context(|| format!("Failed to replace {}", path.display()));
+        }
+        Ok(())
+    }
+
+    /// Load entries saved by `save_to`, keeping their original expiry. Returns the
+    /// number of entries loaded; a missing file loads nothing.
+    pub fn load_from(&self, path: &Path) -> Result<usize> {
+        if !path.exists() {
+            return Ok(0);
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let disk: Vec<DiskEntry<K, V>> = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+        let now = Instant::now();
+        let now_unix = jiff::Timestamp::now().as_second();
+        let mut entries = self
+            .entries
+            .write()
+            .map_err(|_| anyhow::anyhow!("Cache lock poisoned"))?;
+        let count = disk.len();
+        for entry in disk {
+            let expires_at = match entry.expires_at - now_unix {
+                left if left >= 0 => now + Duration::from_secs(left as u64),
+                // Already expired: keep it as stale data
+                _ => now,
+            };
+            entries.insert(entry.key, entry.value, expires_at);
+        }
+        if let Some(max) = self.max_entries {
+            entries.evict_to(max);
+        }
+        Ok(count)
+    }
+}
+
 impl<K, V> Clone for Cache<K, V> {
     fn clone(&self) -> Self {
         Self {
             entries: Arc::clone(&self.entries),
             ttl: self.ttl,
             enable_metrics: self.enable_metrics,
+            max_entries: self.max_entries,
+            counters: Arc::clone(&self.counters),
+            persist_lock: Arc::clone(&self.persist_lock),
         }
     }
 }
@@ -172,6 +458,18 @@ mod tests {
         assert_eq!(cache.get(&1), None);
     }
 
+    #[test]
+    fn test_per_entry_ttl() {
+        let cache = Cache::new(Duration::from_secs(60));
+        cache.set_with_ttl(1, "fast", Duration::from_millis(50));
+        cache.set(2, "slow");
+        thread::sleep(Duration::from_millis(80));
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.get(&2), Some("slow"));
+        // Expired entries still serve as the stale fallback
+        assert_eq!(cache.get_stale(&1), Some("fast"));
+    }
+
     #[test]
     fn test_cache_invalidate() {
         let cache = Cache::new(Duration::from_secs(60));
@@ -195,6 +493,127 @@ mod tests {
         assert_eq!(cache.get(&2), None);
     }
 
+    #[test]
+    fn test_max_entries_evicts_oldest() {
+        let cache = Cache::new(Duration::from_secs(60)).with_max_entries(2);
+        cache.set(1, "a".to_string());
+        thread::sleep(Duration::from_millis(2));
+        cache.set(2, "b".to_string());
+        thread::sleep(Duration::from_millis(2));
+        cache.set(3, "c".to_string());
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.get(&3), Some("c".to_string()));
+        // Overwriting an existing key doesn't evict anything
+        cache.set(2, "b2".to_string());
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_eviction_follows_expiry_after_updates() {
+        let cache = Cache::new(Duration::from_secs(60)).with_max_entries(3);
+        cache.set_with_ttl(1, "a", Duration::from_secs(10));
+        cache.set_with_ttl(2, "b", Duration::from_secs(20));
+        cache.set_with_ttl(3, "c", Duration::from_secs(30));
+        // Refreshing 1 moves it to the back of the queue
+        cache.set_with_ttl(1, "a2", Duration::from_secs(40));
+        cache.invalidate(&3);
+        cache.set_with_ttl(4, "d", Duration::from_secs(50));
+        cache.set_with_ttl(5, "e", Duration::from_secs(60));
+
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.get(&1), Some("a2"));
+        assert_eq!(cache.get(&4), Some("d"));
+        assert_eq!(cache.get(&5), Some("e"));
+    }
+
+    #[test]
+    fn test_save_replaces_the_file_whole() {
+        let dir = std::env::temp_dir().join(format!("tui-hn-cache-save-{}", std::process::id()));
+        let path = dir.join("stories.json");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&path, "[{\"key\": 1, \"value\": \"tr").unwrap();
+
+        let cache = Cache::new(Duration::from_secs(60));
+        cache.set(1, "a".to_string());
+        let writers: Vec<_> = (0..4)
+            .map(|_| {
+                let (cache, path) = (cache.clone(), path.clone());
+                thread::spawn(move || cache.save_to(&path).unwrap())
+            })
+            .collect();
+        writers
+            .into_iter()
+            .for_each(|writer| writer.join().unwrap());
+
+        let files: Vec<_> = std::fs::read_dir(&dir).unwrap().flatten().collect();
+        let loaded: Cache<u32, String> = Cache::new(Duration::from_secs(60));
+        let count = loaded.load_from(&path).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(files.len(), 1);
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_disk_round_trip_keeps_stale_entries() {
+        let path = std::env::temp_dir().join(format!("tui-hn-cache-{}.json", std::process::id()));
+        let short = Cache::new(Duration::from_millis(50));
+        short.set(1, "old".to_string());
+        thread::sleep(Duration::from_millis(60));
+        // Same entries, longer TTL for new ones
+        let cache = Cache {
+            ttl: Duration::from_secs(60),
+            ..short
+        };
+        cache.set(2, "new".to_string());
+        cache.save_to(&path).unwrap();
+
+        let loaded: Cache<u32, String> = Cache::new(Duration::from_secs(60));
+        assert_eq!(loaded.load_from(&path).unwrap(), 2);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(loaded.get(&1), None);
+        assert_eq!(loaded.get_stale(&1), Some("old".to_string()));
+        assert_eq!(loaded.get(&2), Some("new".to_string()));
+        assert_eq!(loaded.load_from(&path).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_stats_count_lookups_and_evictions() {
+        let cache = Cache::new(Duration::from_secs(60)).with_max_entries(2);
+        assert_eq!(cache.stats(String::len).hit_ratio(), None);
+
+        cache.set(1, "a".to_string());
+        cache.set(2, "bb".to_string());
+        cache.set(3, "ccc".to_string());
+        let clone = cache.clone();
+        assert_eq!(clone.get(&3), Some("ccc".to_string()));
+        assert_eq!(cache.get(&1), None);
+
+        let stats = cache.stats(String::len);
+        assert_eq!((stats.entries, stats.expired), (2, 0));
+        assert_eq!((stats.hits, stats.misses, stats.evictions), (1, 1, 1));
+        assert_eq!(stats.hit_ratio(), Some(0.5));
+        assert_eq!(stats.approx_bytes, 2 * std::mem::size_of::<i32>() + 5);
+    }
+
+    #[test]
+    fn test_stale_serves_count_expired_fallbacks_only() {
+        let cache = Cache::new(Duration::from_secs(60));
+        cache.set_with_ttl(1, "old", Duration::from_millis(10));
+        cache.set(2, "fresh");
+        thread::sleep(Duration::from_millis(20));
+
+        assert_eq!(cache.serve_stale(&1), Some("old"));
+        assert_eq!(cache.serve_stale(&2), Some("fresh"));
+        assert_eq!(cache.serve_stale(&3), None);
+        // Peeking at stale entries is not serving them
+        assert_eq!(cache.get_stale(&1), Some("old"));
+        assert_eq!(cache.stats(|_| 0).stale_serves, 1);
+    }
+
     #[test]
     fn test_cleanup_expired() {
         let cache = Cache::new(Duration::from_millis(100));
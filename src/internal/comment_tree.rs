@@ -0,0 +1,351 @@
+//! Pure helpers for turning fetched comments into the flattened rows shown in
+//! the StoryDetail view.
+
+use std::collections::{BTreeSet, HashMap};
+
+use super::models::{Comment, CommentRow};
+
+/// Flatten a comment tree into rows in depth-first (pre-order) order.
+///
+/// `comments` holds every comment that was fetched; kids missing from the map
+/// (failed or skipped fetches) are omitted together with their subtrees. Comments
+/// at `max_depth` are emitted collapsed with `loaded_kids == false` so their
+/// replies can be fetched lazily.
+pub fn flatten_comment_tree(
+    root_ids: &[u32],
+    comments: &HashMap<u32, Comment>,
+    max_depth: usize,
+) -> Vec<CommentRow> {
+    let mut rows = Vec::new();
+    // Explicit stack of (id, depth, parent) to avoid recursion on deep threads.
+    // Children are pushed in reverse so they pop in their original order.
+    let mut stack: Vec<(u32, usize, Option<u32>)> =
+        root_ids.iter().rev().map(|&id| (id, 0, None)).collect();
+
+    while let Some((id, depth, parent_id)) = stack.pop() {
+        let Some(comment) = comments.get(&id) else {
+            continue;
+        };
+
+        let kids = comment.kids.as_deref().unwrap_or_default();
+        let has_kids = !kids.is_empty();
+        let loaded_kids = has_kids && depth < max_depth;
+
+        rows.push(CommentRow {
+            comment: comment.clone(),
+            depth,
+            expanded: loaded_kids || !has_kids,
+            parent_id,
+            loaded_kids,
+        });
+
+        if loaded_kids {
+            stack.extend(kids.iter().rev().map(|&kid| (kid, depth + 1, Some(id))));
+        }
+    }
+
+    rows
+}
+
+/// IDs of comments whose loaded replies are currently folded away.
+///
+/// Rows whose replies were never fetched (`loaded_kids == false`) are not counted:
+/// they are collapsed because of lazy loading, not because the user folded them.
+pub fn collapsed_ids(rows: &[CommentRow]) -> BTreeSet<u32> {
+    rows.iter()
+        .filter(|row| row.loaded_kids && !row.expanded)
+        .map(|row| row.comment.id)
+        .collect()
+}
+
+/// Restore a saved fold state: rows listed in `collapsed` are folded, every other
+/// loaded row is expanded.
+pub fn apply_folds(rows: &mut [CommentRow], collapsed: &BTreeSet<u32>) {
+    for row in rows.iter_mut().filter(|row| row.loaded_kids) {
+        row.expanded = !collapsed.contains(&row.comment.id);
+    }
+}
+
+/// Fold (`false`) or unfold (`true`) every row with loaded replies.
+pub fn set_all_expanded(rows: &mut [CommentRow], expanded: bool) {
+    for row in rows.iter_mut().filter(|row| row.loaded_kids) {
+        row.expanded = expanded;
+    }
+}
+
+/// Show only the first `depth` levels of the thread (`depth == 1` shows top-level
+/// comments only); shallower rows are unfolded.
+pub fn collapse_to_depth(rows: &mut [CommentRow], depth: usize) {
+    for row in rows.iter_mut().filter(|row| row.loaded_kids) {
+        row.expanded = row.depth + 1 < depth;
+    }
+}
+
+/// Shortest back-and-forth (A, B, A) shown as a conversation.
+pub const MIN_EXCHANGE: usize = 3;
+
+/// A comment that is part of a back-and-forth between two authors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Turn {
+    /// Id of the comment that opened the exchange
+    pub conversation: u32,
+    /// 0 for the opener's comments, 1 for the other author's
+    pub side: usize,
+    /// Position in the exchange, starting at 0
+    pub index: usize,
+    /// Replies in the longest chain of the exchange
+    pub length: usize,
+    /// Opener and the author replying to them
+    pub authors: [String; 2],
+}
+
+/// Find chains of direct replies where two authors take turns (A, B, A, ...), at
+/// least `MIN_EXCHANGE` long, keyed by comment id. Deleted comments end a chain.
+pub fn find_conversations(rows: &[CommentRow]) -> HashMap<u32, Turn> {
+    // (opener id, position, author, previous author) for every row
+    let mut chains: HashMap<u32, (u32, usize, &str, Option<&str>)> = HashMap::new();
+    for row in rows {
+        let Some(author) = row.comment.by.as_deref() else {
+            continue;
+        };
+        let parent = row.parent_id.and_then(|id| chains.get(&id)).copied();
+        let chain = match parent {
+            Some((opener, index, parent_author, previous))
+                if author != parent_author && (index == 0 || previous == Some(author)) =>
+            {
+                (opener, index + 1, author, Some(parent_author))
+            }
+            _ => (row.comment.id, 0, author, None),
+        };
+        chains.insert(row.comment.id, chain);
+    }
+
+    let mut exchanges: HashMap<u32, (usize, [&str; 2])> = HashMap::new();
+    for &(opener, index, author, previous) in chains.values() {
+        if index == 0 {
+            continue;
+        }
+        let entry = exchanges.entry(opener).or_insert((0, ["", ""]));
+        entry.0 = entry.0.max(index + 1);
+        if index == 1 {
+            entry.1 = [previous.unwrap_or_default(), author];
+        }
+    }
+
+    chains
+        .into_iter()
+        .filter_map(|(id, (opener, index, _, _))| {
+            let (length, authors) = exchanges.get(&opener)?;
+            (*length >= MIN_EXCHANGE).then(|| {
+                (
+                    id,
+                    Turn {
+                        conversation: opener,
+                        side: index % 2,
+                        index,
+                        length: *length,
+                        authors: authors.map(String::from),
+                    },
+                )
+            })
+        })
+        .collect()
+}
+
+/// 1-based position of every comment among its siblings, in the order HN ranks
+/// them, keyed by comment id.
+pub fn sibling_ranks(rows: &[CommentRow]) -> HashMap<u32, usize> {
+    let mut counts: HashMap<Option<u32>, usize> = HashMap::new();
+    rows.iter()
+        .map(|row| {
+            let count = counts.entry(row.parent_id).or_default();
+            *count += 1;
+            (row.comment.id, *count)
+        })
+        .collect()
+}
+
+/// Indices of `rows` with every set of siblings reordered by their number of direct
+/// replies, most first, each keeping its subtree below it. Ties keep HN's order.
+pub fn order_by_replies(rows: &[CommentRow]) -> Vec<usize> {
+    let mut order = Vec::with_capacity(rows.len());
+    push_by_replies(rows, 0..rows.len(), &mut order);
+    order
+}
+
+/// Append the subtrees starting at the depth of `range`'s first row, sorted.
+fn push_by_replies(rows: &[CommentRow], range: std::ops::Range<usize>, order: &mut Vec<usize>) {
+    let Some(depth) = rows.get(range.start).map(|row| row.depth) else {
+        return;
+    };
+    let mut subtrees: Vec<std::ops::Range<usize>> = Vec::new();
+    for i in range.clone() {
+        match rows[i].depth <= depth {
+            true => subtrees.push(i..i + 1),
+            false => {
+                if let Some(last) = subtrees.last_mut() {
+                    last.end = i + 1;
+                }
+            }
+        }
+    }
+    let replies = |i: usize| rows[i].comment.kids.as_ref().map_or(0, Vec::len);
+    subtrees.sort_by_key(|subtree| std::cmp::Reverse(replies(subtree.start)));
+    for subtree in subtrees {
+        order.push(subtree.start);
+        push_by_replies(rows, subtree.start + 1..subtree.end, order);
+    }
+}
+
+/// Id of the top-level comment whose thread contains `rows[index]`.
+pub fn thread_root(rows: &[CommentRow], index: usize) -> Option<u32> {
+    rows.get(..=index)?
+        .iter()
+        .rev()
+        .find(|row| row.depth == 0)
+        .map(|row| row.comment.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(id: u32, kids: &[u32]) -> Comment {
+        Comment {
+            id,
+            by: None,
+            text: None,
+            time: None,
+            kids: match kids.is_empty() {
+                true => None,
+                false => Some(kids.to_vec()),
+            },
+            deleted: false,
+            dead: false,
+        }
+    }
+
+    fn map(items: Vec<Comment>) -> HashMap<u32, Comment> {
+        items.into_iter().map(|c| (c.id, c)).collect()
+    }
+
+    #[test]
+    fn flattens_in_dfs_order() {
+        let comments = map(vec![
+            comment(1, &[2, 3]),
+            comment(2, &[4]),
+            comment(3, &[]),
+            comment(4, &[]),
+            comment(5, &[]),
+        ]);
+        let rows = flatten_comment_tree(&[1, 5], &comments, 3);
+        let order: Vec<(u32, usize)> = rows.iter().map(|r| (r.comment.id, r.depth)).collect();
+        assert_eq!(order, vec![(1, 0), (2, 1), (4, 2), (3, 1), (5, 0)]);
+        assert_eq!(rows[2].parent_id, Some(2));
+    }
+
+    #[test]
+    fn stops_at_max_depth_and_marks_collapsed() {
+        let comments = map(vec![comment(1, &[2]), comment(2, &[3]), comment(3, &[])]);
+        let rows = flatten_comment_tree(&[1], &comments, 1);
+        assert_eq!(rows.len(), 2);
+        assert!(!rows[1].loaded_kids);
+        assert!(!rows[1].expanded);
+    }
+
+    #[test]
+    fn fold_state_round_trips() {
+        let comments = map(vec![
+            comment(1, &[2]),
+            comment(2, &[3]),
+            comment(3, &[]),
+            comment(4, &[5]),
+            comment(5, &[]),
+        ]);
+        let mut rows = flatten_comment_tree(&[1, 4], &comments, 3);
+
+        collapse_to_depth(&mut rows, 2);
+        assert_eq!(collapsed_ids(&rows), BTreeSet::from([2]));
+
+        set_all_expanded(&mut rows, false);
+        let saved = collapsed_ids(&rows);
+        assert_eq!(saved, BTreeSet::from([1, 2, 4]));
+
+        let mut reopened = flatten_comment_tree(&[1, 4], &comments, 3);
+        apply_folds(&mut reopened, &saved);
+        assert_eq!(collapsed_ids(&reopened), saved);
+    }
+
+    #[test]
+    fn lazily_unloaded_rows_are_not_user_folds() {
+        let comments = map(vec![comment(1, &[2]), comment(2, &[3]), comment(3, &[])]);
+        let mut rows = flatten_comment_tree(&[1], &comments, 1);
+        set_all_expanded(&mut rows, true);
+        assert!(!rows[1].expanded);
+        assert!(collapsed_ids(&rows).is_empty());
+    }
+
+    #[test]
+    fn finds_alternating_exchanges() {
+        let by = |id, kids: &[u32], author: &str| Comment {
+            by: Some(author.to_string()),
+            ..comment(id, kids)
+        };
+        let comments = map(vec![
+            by(1, &[2], "alice"),
+            by(2, &[3, 6], "bob"),
+            by(3, &[4], "alice"),
+            by(4, &[5], "bob"),
+            by(5, &[], "carol"),
+            by(6, &[], "dave"),
+            by(7, &[8], "erin"),
+            by(8, &[], "frank"),
+        ]);
+        let rows = flatten_comment_tree(&[1, 7], &comments, 5);
+        let turns = find_conversations(&rows);
+
+        let mut ids: Vec<u32> = turns.keys().copied().collect();
+        ids.sort();
+        // carol and dave break the chain; erin and frank only trade one reply
+        assert_eq!(ids, vec![1, 2, 3, 4]);
+        assert_eq!(turns[&4].side, 1);
+        assert_eq!(turns[&4].length, 4);
+        assert_eq!(turns[&1].authors, ["alice".to_string(), "bob".to_string()]);
+
+        assert_eq!(thread_root(&rows, 4), Some(1));
+        assert_eq!(thread_root(&rows, 7), Some(7));
+        assert_eq!(thread_root(&rows, 99), None);
+    }
+
+    #[test]
+    fn ranks_siblings_and_orders_by_replies() {
+        let comments = map(vec![
+            comment(1, &[4]),
+            comment(2, &[5, 6]),
+            comment(3, &[]),
+            comment(4, &[]),
+            comment(5, &[]),
+            comment(6, &[7]),
+            comment(7, &[]),
+        ]);
+        let rows = flatten_comment_tree(&[1, 2, 3], &comments, 3);
+        let ranks = sibling_ranks(&rows);
+        assert_eq!((ranks[&1], ranks[&2], ranks[&3]), (1, 2, 3));
+        assert_eq!((ranks[&4], ranks[&5], ranks[&6], ranks[&7]), (1, 1, 2, 1));
+
+        let ids: Vec<u32> = order_by_replies(&rows)
+            .into_iter()
+            .map(|i| rows[i].comment.id)
+            .collect();
+        // 2 has the most replies and 6 outranks 5, each carrying its subtree along
+        assert_eq!(ids, vec![2, 6, 7, 5, 1, 4, 3]);
+    }
+
+    #[test]
+    fn skips_missing_subtrees() {
+        let comments = map(vec![comment(1, &[2, 3]), comment(3, &[])]);
+        let rows = flatten_comment_tree(&[1], &comments, 3);
+        let ids: Vec<u32> = rows.iter().map(|r| r.comment.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+    }
+}
@@ -0,0 +1,240 @@
+//! What is known about the sites stories link to: the publisher's name and brand
+//! color, shown on the domain chips of the story list. Names come from a built-in
+//! table of well-known publishers, from the articles read, and with
+//! `network.fetch_domain_metadata` from the sites' home pages. What is learned is
+//! saved to `domains.json`, so it is shown offline too.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use super::models::SiteMeta;
+
+/// How long a looked-up home page is trusted before it is looked at again.
+const RECHECK_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// Publishers that show up on HN often, by registered domain.
+const KNOWN_PUBLISHERS: &[(&str, &str)] = &[
+    ("acm.org", "ACM"),
+    ("apnews.com", "AP News"),
+    ("arstechnica.com", "Ars Technica"),
+    ("arxiv.org", "arXiv"),
+    ("bbc.co.uk", "BBC"),
+    ("bbc.com", "BBC"),
+    ("bloomberg.com", "Bloomberg"),
+    ("cnn.com", "CNN"),
+    ("economist.com", "The Economist"),
+    ("ft.com", "Financial Times"),
+    ("github.com", "GitHub"),
+    ("gitlab.com", "GitLab"),
+    ("lwn.net", "LWN.net"),
+    ("medium.com", "Medium"),
+    ("nature.com", "Nature"),
+    ("newyorker.com", "The New Yorker"),
+    ("npr.org", "NPR"),
+    ("nytimes.com", "The New York Times"),
+    ("quantamagazine.org", "Quanta Magazine"),
+    ("reddit.com", "Reddit"),
+    ("reuters.com", "Reuters"),
+    ("science.org", "Science"),
+    ("spectrum.ieee.org", "IEEE Spectrum"),
+    ("stackoverflow.com", "Stack Overflow"),
+    ("substack.com", "Substack"),
+    ("techcrunch.com", "TechCrunch"),
+    ("theatlantic.com", "The Atlantic"),
+    ("theguardian.com", "The Guardian"),
+    ("theverge.com", "The Verge"),
+    ("washingtonpost.com", "The Washington Post"),
+    ("wikipedia.org", "Wikipedia"),
+    ("wired.com", "Wired"),
+    ("wsj.com", "The Wall Street Journal"),
+    ("youtube.com", "YouTube"),
+];
+
+/// `domain` without `www.`, lowercased, as the metadata is keyed.
+pub fn normalize(domain: &str) -> String {
+    domain.trim_start_matches("www.").to_lowercase()
+}
+
+/// `domain` and the domains it is a subdomain of, longest first.
+fn suffixes(domain: &str) -> impl Iterator<Item = &str> {
+    std::iter::successors(Some(domain), |d| d.split_once('.').map(|(_, rest)| rest))
+        .filter(|d| d.contains('.'))
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct DomainInfo {
+    pub publisher: Option<String>,
+    /// Brand color (`theme-color` of the home page)
+    pub color: Option<(u8, u8, u8)>,
+    /// When the home page was last looked up (Unix seconds); 0 if never
+    #[serde(default)]
+    pub checked_at: i64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DomainMeta {
+    domains: HashMap<String, DomainInfo>,
+    #[serde(skip)]
+    file_path: Option<PathBuf>,
+    /// Bumped whenever something is learned, so list rows showing it are redrawn
+    #[serde(skip)]
+    version: u64,
+}
+
+impl DomainMeta {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load_or_create() -> Result<Self> {
+        let config_dir = dirs::config_dir()
+            .context("Could not find config directory")?
+            .join("tui-hn-app");
+        fs::create_dir_all(&config_dir).with_context(|| {
+            format!("Failed to create config directory {}", config_dir.display())
+        })?;
+
+        let file_path = config_dir.join("domains.json");
+        let mut meta: DomainMeta = match file_path.exists() {
+            true => {
+                let content =
+                    fs::read_to_string(&file_path).context("Failed to read domain metadata")?;
+                serde_json::from_str(&content).context("Failed to parse domain metadata")?
+            }
+            false => Self::new(),
+        };
+        meta.file_path = Some(file_path);
+        Ok(meta)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.file_path else {
+            return Ok(());
+        };
+        let content =
+            serde_json::to_string_pretty(self).context("Failed to serialize domain metadata")?;
+        fs::write(path, content).context("Failed to write domain metadata")
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Publisher name of `domain`: what the site said, or the built-in name of it or
+    /// a domain it belongs to.
+    pub fn publisher(&self, domain: &str) -> Option<&str> {
+        let domain = normalize(domain);
+        if let Some(name) = self
+            .domains
+            .get(&domain)
+            .and_then(|info| info.publisher.as_deref())
+        {
+            return Some(name);
+        }
+        suffixes(&domain).find_map(|d| {
+            KNOWN_PUBLISHERS
+                .iter()
+                .find(|(known, _)| *known == d)
+                .map(|(_, name)| *name)
+        })
+    }
+
+    pub fn color(&self, domain: &str) -> Option<(u8, u8, u8)> {
+        self.domains.get(&normalize(domain))?.color
+    }
+
+    /// Letter standing in for the site's favicon: the publisher's initial, or the
+    /// domain's.
+    pub fn glyph(&self, domain: &str) -> char {
+        let domain = normalize(domain);
+        let name = self.publisher(&domain).unwrap_or(&domain);
+        let name = name.strip_prefix("The ").unwrap_or(name);
+        name.chars()
+            .find(|c| c.is_alphanumeric())
+            .map_or('?', |c| c.to_uppercase().next().unwrap_or(c))
+    }
+
+    /// Whether the home page of `domain` has not been looked up lately.
+    pub fn needs_lookup(&self, domain: &str, now: i64) -> bool {
+        self.domains
+            .get(&normalize(domain))
+            .is_none_or(|info| now - info.checked_at >= RECHECK_SECS)
+    }
+
+    /// Note that `domain` is being looked up, so it is not looked up again meanwhile.
+    pub fn mark_checked(&mut self, domain: &str, now: i64) {
+        self.domains
+            .entry(normalize(domain))
+            .or_default()
+            .checked_at = now;
+    }
+
+    /// Keep what the home page of `domain` says about the site.
+    pub fn record_site(&mut self, domain: &str, site: SiteMeta) {
+        let info = self.domains.entry(normalize(domain)).or_default();
+        if site.name.is_some() {
+            info.publisher = site.name;
+        }
+        if site.theme_color.is_some() {
+            info.color = site.theme_color;
+        }
+        self.version += 1;
+    }
+
+    /// Keep the site name an article of `domain` gave, unless the site is named already.
+    pub fn learn_publisher(&mut self, domain: &str, name: &str) {
+        let info = self.domains.entry(normalize(domain)).or_default();
+        if info.publisher.is_none() && !name.trim().is_empty() {
+            info.publisher = Some(name.trim().to_string());
+            self.version += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn publishers_from_sites_articles_and_the_table() {
+        let mut meta = DomainMeta::new();
+        assert_eq!(
+            meta.publisher("www.NYTimes.com"),
+            Some("The New York Times")
+        );
+        assert_eq!(meta.publisher("en.wikipedia.org"), Some("Wikipedia"));
+        assert_eq!(meta.publisher("example.com"), None);
+        assert_eq!(meta.glyph("nytimes.com"), 'N');
+        assert_eq!(meta.glyph("example.com"), 'E');
+
+        meta.learn_publisher("example.com", "Example Blog");
+        meta.learn_publisher("example.com", "Another Name");
+        assert_eq!(meta.publisher("example.com"), Some("Example Blog"));
+        assert_eq!(meta.version(), 1);
+
+        // The home page wins over article names and the table
+        meta.record_site(
+            "www.example.com",
+            SiteMeta {
+                name: Some("Example".to_string()),
+                theme_color: Some((1, 2, 3)),
+            },
+        );
+        assert_eq!(meta.publisher("example.com"), Some("Example"));
+        assert_eq!(meta.color("example.com"), Some((1, 2, 3)));
+        meta.record_site("github.com", SiteMeta::default());
+        assert_eq!(meta.publisher("github.com"), Some("GitHub"));
+    }
+
+    #[test]
+    fn lookups_are_repeated_after_a_while() {
+        let mut meta = DomainMeta::new();
+        assert!(meta.needs_lookup("example.com", 1_000));
+        meta.mark_checked("example.com", 1_000);
+        assert!(!meta.needs_lookup("www.example.com", 1_000 + RECHECK_SECS - 1));
+        assert!(meta.needs_lookup("example.com", 1_000 + RECHECK_SECS));
+    }
+}
@@ -0,0 +1,151 @@
+//! Errors from background operations, classified so the UI can explain them and
+//! offer to retry the operation that failed.
+
+use std::fmt;
+
+use crate::api::StoryListType;
+use crate::api::circuit_breaker::CircuitOpen;
+use crate::api::inflight::SharedError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Connection, timeout or HTTP status failures, and the circuit breaker failing fast
+    Network,
+    /// A response that could not be decoded
+    Parse,
+    /// The request was cancelled because the user moved on
+    Cancelled,
+    Other,
+}
+
+/// What the app was doing when the error happened.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operation {
+    LoadStories(StoryListType),
+    /// Comments of the story with this id
+    LoadComments(u32),
+    /// Article of the story with this id
+    LoadArticle(u32),
+    /// Options of the poll with this id
+    LoadPoll(u32),
+    /// The plugin at this index of `config.plugins`
+    RunPlugin(usize, String),
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operation::LoadStories(list_type) => write!(f, "Loading {} stories", list_type),
+            Operation::LoadComments(_) => write!(f, "Loading comments"),
+            Operation::LoadArticle(_) => write!(f, "Loading the article"),
+            Operation::LoadPoll(_) => write!(f, "Loading poll options"),
+            Operation::RunPlugin(_, name) => write!(f, "Running {}", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppError {
+    pub kind: ErrorKind,
+    pub operation: Operation,
+    pub message: String,
+    /// Whether running the operation again might succeed
+    pub retryable: bool,
+}
+
+impl AppError {
+    pub fn new(operation: Operation, error: &anyhow::Error) -> Self {
+        let kind = classify(error);
+        Self {
+            kind,
+            operation,
+            message: format!("{:#}", error),
+            retryable: matches!(kind, ErrorKind::Network | ErrorKind::Other),
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.kind == ErrorKind::Cancelled
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} failed: {}", self.operation, self.message)
+    }
+}
+
+/// Look through the error chain for the underlying cause.
+fn classify(error: &anyhow::Error) -> ErrorKind {
+    if error.to_string() == "Request cancelled" {
+        return ErrorKind::Cancelled;
+    }
+    for cause in error.chain() {
+        if let Some(shared) = cause.downcast_ref::<SharedError>() {
+            return classify(shared.inner());
+        }
+        if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+            return match e.is_decode() {
+                true => ErrorKind::Parse,
+                false => ErrorKind::Network,
+            };
+        }
+        if cause.is::<serde_json::Error>() {
+            return ErrorKind::Parse;
+        }
+        if cause.is::<CircuitOpen>() {
+            return ErrorKind::Network;
+        }
+        if let Some(e) = cause.downcast_ref::<std::io::Error>() {
+            use std::io::ErrorKind as Io;
+            if let Io::ConnectionRefused
+            | Io::ConnectionReset
+            | Io::ConnectionAborted
+            | Io::NotConnected
+            | Io::TimedOut = e.kind()
+            {
+                return ErrorKind::Network;
+            }
+        }
+    }
+    // HTTP status checks report plain messages
+    let message = error.to_string().to_lowercase();
+    match ["timed out", "status", "connect"]
+        .iter()
+        .any(|needle| message.contains(needle))
+    {
+        true => ErrorKind::Network,
+        false => ErrorKind::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_error_chains() {
+        let op = || Operation::LoadStories(StoryListType::Top);
+
+        let cancelled = AppError::new(op(), &anyhow::anyhow!("Request cancelled"));
+        assert!(cancelled.is_cancelled() && !cancelled.retryable);
+
+        let parse_error = serde_json::from_str::<u32>("{").unwrap_err();
+        let parse = AppError::new(op(), &anyhow::Error::new(parse_error).context("Bad story"));
+        assert_eq!((parse.kind, parse.retryable), (ErrorKind::Parse, false));
+
+        let io = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset");
+        let network = AppError::new(op(), &anyhow::Error::new(io));
+        assert_eq!(
+            (network.kind, network.retryable),
+            (ErrorKind::Network, true)
+        );
+        assert_eq!(
+            network.to_string(),
+            format!("Loading {} stories failed: reset", StoryListType::Top)
+        );
+
+        let open = AppError::new(op(), &anyhow::Error::new(CircuitOpen).context("Bad story"));
+        assert_eq!((open.kind, open.retryable), (ErrorKind::Network, true));
+    }
+}
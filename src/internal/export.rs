@@ -0,0 +1,544 @@
+//! Export a story with its article and comment thread to Markdown or HTML, and
+//! the story list to CSV/TSV.
+
+use anyhow::{Context, Result};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::models::{Article, ArticleElement, CommentRow, Story};
+use crate::config::{ExportConfig, ExportFormat, ListColumn, ListExportFormat};
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+        }
+    }
+}
+
+impl ListExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Tsv => "tsv",
+        }
+    }
+}
+
+impl ListColumn {
+    /// Header and CLI name of the column.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Rank => "rank",
+            Self::Id => "id",
+            Self::Title => "title",
+            Self::Url => "url",
+            Self::Domain => "domain",
+            Self::Score => "score",
+            Self::Comments => "comments",
+            Self::Author => "author",
+            Self::Time => "time",
+        }
+    }
+
+    fn value(&self, rank: usize, story: &Story) -> String {
+        match self {
+            Self::Rank => rank.to_string(),
+            Self::Id => story.id.to_string(),
+            Self::Title => story.title.clone().unwrap_or_default(),
+            Self::Url => story.url.clone().unwrap_or_default(),
+            Self::Domain => story
+                .url
+                .as_deref()
+                .and_then(crate::utils::url::extract_domain)
+                .unwrap_or_default(),
+            Self::Score => story.score.unwrap_or(0).to_string(),
+            Self::Comments => story.descendants.unwrap_or(0).to_string(),
+            Self::Author => story.by.clone().unwrap_or_default(),
+            Self::Time => story
+                .time
+                .and_then(|t| jiff::Timestamp::from_second(t).ok())
+                .map(|t| t.strftime("%Y-%m-%dT%H:%M:%SZ").to_string())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+fn delimited_field(value: &str, format: ListExportFormat) -> String {
+    match format {
+        ListExportFormat::Csv if value.contains([',', '"', '\n', '\r']) => {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        }
+        ListExportFormat::Csv => value.to_string(),
+        // TSV has no quoting, so separators inside values become spaces
+        ListExportFormat::Tsv => value.replace(['\t', '\n', '\r'], " "),
+    }
+}
+
+/// Render stories as CSV/TSV with a header row. Ranks follow the iteration order,
+/// so pass the list exactly as it is displayed.
+pub fn render_story_list<'a>(
+    stories: impl IntoIterator<Item = &'a Story>,
+    columns: &[ListColumn],
+    format: ListExportFormat,
+) -> String {
+    let separator = match format {
+        ListExportFormat::Csv => ",",
+        ListExportFormat::Tsv => "\t",
+    };
+    let header: Vec<_> = columns.iter().map(|c| c.name()).collect();
+    let mut out = header.join(separator);
+    out.push('\n');
+    for (i, story) in stories.into_iter().enumerate() {
+        let row: Vec<_> = columns
+            .iter()
+            .map(|c| delimited_field(&c.value(i + 1, story), format))
+            .collect();
+        out.push_str(&row.join(separator));
+        out.push('\n');
+    }
+    out
+}
+
+/// Write the story list to `config.directory` as `<date>-<list>.csv` (or `.tsv`).
+pub fn write_story_list<'a>(
+    stories: impl IntoIterator<Item = &'a Story>,
+    list_name: &str,
+    config: &ExportConfig,
+) -> Result<PathBuf> {
+    let dir = Path::new(&config.directory);
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create export directory {}", dir.display()))?;
+
+    let date = jiff::Zoned::now().strftime("%Y-%m-%d").to_string();
+    let path = dir.join(format!(
+        "{}-{}.{}",
+        date,
+        slugify(list_name),
+        config.list_format.extension()
+    ));
+    let content = render_story_list(stories, &config.list_columns, config.list_format);
+    fs::write(&path, content)
+        .with_context(|| format!("Failed to write export file {}", path.display()))?;
+    Ok(path)
+}
+
+/// Everything written to an export file.
+pub struct StoryExport<'a> {
+    pub story: &'a Story,
+    pub article: Option<&'a Article>,
+    pub comments: &'a [CommentRow],
+}
+
+/// Write the export to `config.directory`, returning the path of the new file.
+pub fn write_story(export: &StoryExport, config: &ExportConfig) -> Result<PathBuf> {
+    let dir = Path::new(&config.directory);
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create export directory {}", dir.display()))?;
+
+    let path = dir.join(file_name(
+        &config.filename_template,
+        export.story,
+        config.format.extension(),
+    ));
+    let content = match config.format {
+        ExportFormat::Markdown => render_markdown(export),
+        ExportFormat::Html => render_html(export),
+    };
+    fs::write(&path, content)
+        .with_context(|| format!("Failed to write export file {}", path.display()))?;
+    Ok(path)
+}
+
+/// Expand `{id}`, `{slug}` and `{date}` in the filename template and append the
+/// extension. Path separators are replaced so the file always lands in the export
+/// directory.
+pub fn file_name(template: &str, story: &Story, extension: &str) -> String {
+    let date = jiff::Zoned::now().strftime("%Y-%m-%d").to_string();
+    let slug = slugify(story.title.as_deref().unwrap_or("story"));
+    let name = template
+        .replace("{id}", &story.id.to_string())
+        .replace("{slug}", &slug)
+        .replace("{date}", &date)
+        .replace(['/', '\\'], "-");
+    format!("{}.{}", name, extension)
+}
+
+/// Lowercase ASCII slug limited to 60 characters.
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.chars() {
+        match c.is_ascii_alphanumeric() {
+            true => slug.push(c.to_ascii_lowercase()),
+            false if !slug.is_empty() && !slug.ends_with('-') => slug.push('-'),
+            false => {}
+        }
+    }
+    let slug: String = slug.chars().take(60).collect();
+    slug.trim_end_matches('-').to_string()
+}
+
+fn format_time(time: Option<i64>) -> String {
+    time.and_then(|t| jiff::Timestamp::from_second(t).ok())
+        .map(|t| t.strftime("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn comment_text(row: &CommentRow) -> String {
+    match (&row.comment.text, row.comment.deleted) {
+        (Some(text), false) => crate::utils::html::extract_text_from_html(text),
+        _ => "[deleted]".to_string(),
+    }
+}
+
+pub fn render_markdown(export: &StoryExport) -> String {
+    let story = export.story;
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}\n", story.title.as_deref().unwrap_or("Untitled"));
+    if let Some(url) = &story.url {
+        let _ = writeln!(out, "- URL: <{}>", url);
+    }
+    let _ = writeln!(
+        out,
+        "- Discussion: <{}>",
+        crate::utils::url::hn_item_url(story.id)
+    );
+    let _ = writeln!(
+        out,
+        "- {} points by {} · {} · {} comments\n",
+        story.score.unwrap_or(0),
+        story.by.as_deref().unwrap_or("unknown"),
+        format_time(story.time),
+        story.descendants.unwrap_or(0)
+    );
+
+    // The body of Ask HN and other text posts
+    if let Some(text) = &story.text {
+        let _ = writeln!(
+            out,
+            "{}\n",
+            crate::utils::html::extract_text_from_html(text).trim_end()
+        );
+    }
+
+    if let Some(article) = export.article {
+        let _ = writeln!(out, "## Article\n");
+        for element in &article.elements {
+            match element {
+                ArticleElement::Paragraph(text) => {
+                    let _ = writeln!(out, "{}\n", text);
+                }
+                ArticleElement::Heading(level, text) => {
+                    let level = (*level).clamp(1, 4) + 2;
+                    let _ = writeln!(out, "{} {}\n", "#".repeat(level), text);
+                }
+                ArticleElement::CodeBlock { lang, code } => {
+                    let _ = writeln!(
+                        out,
+                        "```{}\n{}\n```\n",
+                        lang.as_deref().unwrap_or(""),
+                        code.trim_end()
+                    );
+                }
+                ArticleElement::List(items) => {
+                    for item in items {
+                        let _ = writeln!(out, "- {}", item);
+                    }
+                    out.push('\n');
+                }
+                ArticleElement::Table(rows) => {
+                    for (i, row) in rows.iter().enumerate() {
+                        let _ = writeln!(out, "| {} |", row.join(" | "));
+                        if i == 0 {
+                            let _ = writeln!(out, "|{}", " --- |".repeat(row.len()));
+                        }
+                    }
+                    out.push('\n');
+                }
+                ArticleElement::Image(alt) => {
+                    let _ = writeln!(out, "*[Image: {}]*\n", alt);
+                }
+                ArticleElement::Quote(text) => {
+                    for line in text.lines() {
+                        let _ = writeln!(out, "> {}", line);
+                    }
+                    out.push('\n');
+                }
+            }
+        }
+    }
+
+    if !export.comments.is_empty() {
+        let _ = writeln!(out, "## Comments\n");
+        for row in export.comments {
+            let indent = "  ".repeat(row.depth);
+            let _ = writeln!(
+                out,
+                "{}- **{}** · {}",
+                indent,
+                row.comment.by.as_deref().unwrap_or("unknown"),
+                format_time(row.comment.time)
+            );
+            for line in comment_text(row).lines() {
+                let _ = writeln!(out, "{}  {}", indent, line);
+            }
+        }
+    }
+
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn render_html(export: &StoryExport) -> String {
+    let story = export.story;
+    let title = escape_html(story.title.as_deref().unwrap_or("Untitled"));
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <style>body{{max-width:46em;margin:auto;font-family:sans-serif;line-height:1.5}}\
+         .comment{{border-left:2px solid #ddd;padding-left:.75em;margin:.75em 0}}\
+         .meta{{color:#777;font-size:.9em}}</style>\n</head>\n<body>",
+        title
+    );
+    let _ = writeln!(out, "<h1>{}</h1>", title);
+    let _ = write!(out, "<p class=\"meta\">");
+    if let Some(url) = &story.url {
+        let url = escape_html(url);
+        let _ = write!(out, "<a href=\"{}\">{}</a><br>", url, url);
+    }
+    let _ = writeln!(
+        out,
+        "{} points by {} · {} · <a href=\"{}\">{} comments</a></p>",
+        story.score.unwrap_or(0),
+        escape_html(story.by.as_deref().unwrap_or("unknown")),
+        format_time(story.time),
+        crate::utils::url::hn_item_url(story.id),
+        story.descendants.unwrap_or(0)
+    );
+
+    // HN serves the body of text posts as sanitized HTML, like comment bodies
+    if let Some(text) = &story.text {
+        let _ = writeln!(out, "<div>{}</div>", text);
+    }
+
+    if let Some(article) = export.article {
+        let _ = writeln!(out, "<h2>Article</h2>");
+        for element in &article.elements {
+            match element {
+                ArticleElement::Paragraph(text) => {
+                    let _ = writeln!(out, "<p>{}</p>", escape_html(text));
+                }
+                ArticleElement::Heading(level, text) => {
+                    let level = (*level).clamp(1, 4) + 2;
+                    let _ = writeln!(out, "<h{0}>{1}</h{0}>", level, escape_html(text));
+                }
+                ArticleElement::CodeBlock { code, .. } => {
+                    let _ = writeln!(out, "<pre><code>{}</code></pre>", escape_html(code));
+                }
+                ArticleElement::List(items) => {
+                    let _ = writeln!(out, "<ul>");
+                    for item in items {
+                        let _ = writeln!(out, "<li>{}</li>", escape_html(item));
+                    }
+                    let _ = writeln!(out, "</ul>");
+                }
+                ArticleElement::Table(rows) => {
+                    let _ = writeln!(out, "<table>");
+                    for row in rows {
+                        let cells: String = row
+                            .iter()
+                            .map(|c| format!("<td>{}</td>", escape_html(c)))
+                            .collect();
+                        let _ = writeln!(out, "<tr>{}</tr>", cells);
+                    }
+                    let _ = writeln!(out, "</table>");
+                }
+                ArticleElement::Image(alt) => {
+                    let _ = writeln!(out, "<p><em>[Image: {}]</em></p>", escape_html(alt));
+                }
+                ArticleElement::Quote(text) => {
+                    let _ = writeln!(out, "<blockquote>{}</blockquote>", escape_html(text));
+                }
+            }
+        }
+    }
+
+    if !export.comments.is_empty() {
+        let _ = writeln!(out, "<h2>Comments</h2>");
+        // Replies are nested inside their parent's div; close siblings and deeper
+        // threads before opening the next comment.
+        let mut open = 0;
+        for row in export.comments {
+            while open > row.depth {
+                let _ = writeln!(out, "</div>");
+                open -= 1;
+            }
+            let _ = writeln!(
+                out,
+                "<div class=\"comment\"><p class=\"meta\"><b>{}</b> · {}</p>",
+                escape_html(row.comment.by.as_deref().unwrap_or("unknown")),
+                format_time(row.comment.time)
+            );
+            // HN already serves sanitized HTML for comment bodies
+            let body = match (&row.comment.text, row.comment.deleted) {
+                (Some(text), false) => text.clone(),
+                _ => "[deleted]".to_string(),
+            };
+            let _ = writeln!(out, "<div>{}</div>", body);
+            open = row.depth + 1;
+        }
+        for _ in 0..open {
+            let _ = writeln!(out, "</div>");
+        }
+    }
+
+    let _ = writeln!(out, "</body>\n</html>");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::models::Comment;
+
+    fn row(id: u32, depth: usize, text: &str) -> CommentRow {
+        CommentRow {
+            comment: Comment {
+                id,
+                by: Some(format!("user{}", id)),
+                text: Some(text.to_string()),
+                time: Some(0),
+                kids: None,
+                deleted: false,
+                dead: false,
+            },
+            depth,
+            expanded: true,
+            parent_id: None,
+            loaded_kids: true,
+        }
+    }
+
+    fn story() -> Story {
+        Story {
+            id: 42,
+            title: Some("Show HN: A <Rust> TUI!".to_string()),
+            url: Some("https://example.com/a".to_string()),
+            score: Some(10),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn csv_quotes_fields_and_ranks_in_order() {
+        let second = Story {
+            id: 7,
+            title: Some("Plain".to_string()),
+            ..Default::default()
+        };
+        let stories = [story(), second];
+        let csv = render_story_list(
+            &stories,
+            &[
+                ListColumn::Rank,
+                ListColumn::Id,
+                ListColumn::Title,
+                ListColumn::Domain,
+            ],
+            ListExportFormat::Csv,
+        );
+        assert_eq!(
+            csv,
+            "rank,id,title,domain\n1,42,Show HN: A <Rust> TUI!,example.com\n2,7,Plain,\n"
+        );
+
+        let quoted = Story {
+            title: Some("Say \"hi\", world".to_string()),
+            ..Default::default()
+        };
+        let csv = render_story_list([&quoted], &[ListColumn::Title], ListExportFormat::Csv);
+        assert_eq!(csv, "title\n\"Say \"\"hi\"\", world\"\n");
+        let tsv = render_story_list(
+            [&quoted],
+            &[ListColumn::Title, ListColumn::Score],
+            ListExportFormat::Tsv,
+        );
+        assert_eq!(tsv, "title\tscore\nSay \"hi\", world\t0\n");
+    }
+
+    #[test]
+    fn slugify_strips_punctuation() {
+        assert_eq!(slugify("Show HN: A <Rust> TUI!"), "show-hn-a-rust-tui");
+        assert_eq!(slugify("???"), "");
+    }
+
+    #[test]
+    fn file_name_expands_template() {
+        let name = file_name("{id}-{slug}", &story(), "md");
+        assert_eq!(name, "42-show-hn-a-rust-tui.md");
+        assert_eq!(file_name("a/{id}", &story(), "html"), "a-42.html");
+    }
+
+    #[test]
+    fn markdown_nests_comments_by_depth() {
+        let comments = vec![row(1, 0, "top"), row(2, 1, "reply")];
+        let md = render_markdown(&StoryExport {
+            story: &story(),
+            article: None,
+            comments: &comments,
+        });
+        assert!(md.starts_with("# Show HN: A <Rust> TUI!"));
+        assert!(md.contains("- **user1** · 1970-01-01 00:00 UTC\n  top"));
+        assert!(md.contains("  - **user2** · 1970-01-01 00:00 UTC\n    reply"));
+    }
+
+    #[test]
+    fn text_posts_include_their_body() {
+        let ask = Story {
+            text: Some("<p>What do you use?<p>Asking for a friend.".to_string()),
+            ..story()
+        };
+        let export = StoryExport {
+            story: &ask,
+            article: None,
+            comments: &[],
+        };
+        let md = render_markdown(&export);
+        assert!(md.contains("What do you use?"), "{}", md);
+        assert!(md.contains("Asking for a friend."), "{}", md);
+        let html = render_html(&export);
+        assert!(html.contains("<div><p>What do you use?<p>Asking for a friend.</div>"));
+    }
+
+    #[test]
+    fn html_escapes_story_fields_and_balances_divs() {
+        let comments = vec![
+            row(1, 0, "<p>top</p>"),
+            row(2, 1, "reply"),
+            row(3, 2, "deep"),
+            row(4, 0, "second"),
+        ];
+        let article = Article {
+            title: "A".to_string(),
+            elements: vec![ArticleElement::Paragraph("x < y".to_string())],
+            ..Default::default()
+        };
+        let html = render_html(&StoryExport {
+            story: &story(),
+            article: Some(&article),
+            comments: &comments,
+        });
+        assert!(html.contains("<h1>Show HN: A &lt;Rust&gt; TUI!</h1>"));
+        assert!(html.contains("<p>x &lt; y</p>"));
+        assert_eq!(html.matches("<div").count(), html.matches("</div>").count());
+    }
+}
@@ -0,0 +1,109 @@
+//! Handing the terminal to other programs: `$PAGER` to read a story, `$EDITOR` for
+//! its text, and the shell itself while the app is suspended (Ctrl+Z).
+
+use anyhow::{Context, Result, bail};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+
+/// Something to run with the TUI out of the way. The run loop leaves the alternate
+/// screen for it and redraws everything once it is done.
+#[derive(Debug, Clone, PartialEq)]
+pub enum External {
+    /// Stop the process until the shell resumes it (`fg`)
+    Suspend,
+    /// Show the text in `$PAGER`
+    Pager(String),
+    /// Open the file in `$EDITOR`, removing it afterwards
+    Editor(PathBuf),
+}
+
+impl External {
+    /// Run to completion; blocks until the program exits or the app is resumed.
+    pub fn run(&self) -> Result<()> {
+        match self {
+            External::Suspend => suspend(),
+            External::Pager(text) => page(text),
+            External::Editor(path) => {
+                let result = edit(path);
+                let _ = std::fs::remove_file(path);
+                result
+            }
+        }
+    }
+}
+
+/// Program and arguments from a `$PAGER`-style value such as `less -R`. Arguments
+/// are split on whitespace; there is no quoting.
+pub fn command_from(value: &str) -> Option<Command> {
+    let mut words = value.split_whitespace();
+    let mut command = Command::new(words.next()?);
+    command.args(words);
+    Some(command)
+}
+
+/// The command named by the first of `vars` that is set, else `fallback`.
+fn command_from_env(vars: &[&str], fallback: &str) -> Command {
+    vars.iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find_map(|value| command_from(&value))
+        .unwrap_or_else(|| Command::new(fallback))
+}
+
+/// Page `text` through `$PAGER`, or `less` when it is unset.
+pub fn page(text: &str) -> Result<()> {
+    let mut child = command_from_env(&["PAGER"], "less")
+        .stdin(Stdio::piped())
+        .spawn()
+        .context("Failed to start the pager")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // The pager may be quit before it has read everything
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    exited(child.wait()?, "pager")
+}
+
+/// Open `path` in `$VISUAL`, `$EDITOR` or `vi`.
+pub fn edit(path: &Path) -> Result<()> {
+    let status = command_from_env(&["VISUAL", "EDITOR"], "vi")
+        .arg(path)
+        .status()
+        .context("Failed to start the editor")?;
+    exited(status, "editor")
+}
+
+fn exited(status: ExitStatus, program: &str) -> Result<()> {
+    match status.success() {
+        true => Ok(()),
+        false => bail!("The {} exited with {}", program, status),
+    }
+}
+
+/// Stop the process as the shell's Ctrl+Z would; returns once it is continued.
+#[cfg(unix)]
+pub fn suspend() -> Result<()> {
+    // SIGSTOP rather than SIGTSTP, which the app catches to get here.
+    // SAFETY: raise has no preconditions
+    match unsafe { libc::raise(libc::SIGSTOP) } {
+        0 => Ok(()),
+        _ => Err(std::io::Error::last_os_error().into()),
+    }
+}
+
+#[cfg(not(unix))]
+pub fn suspend() -> Result<()> {
+    bail!("Suspending is not supported on this platform")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_commands_from_environment_values() {
+        let command = command_from("  less -R  -S ").unwrap();
+        assert_eq!(command.get_program(), "less");
+        assert_eq!(command.get_args().collect::<Vec<_>>(), ["-R", "-S"]);
+        assert!(command_from("   ").is_none());
+    }
+}
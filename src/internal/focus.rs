@@ -0,0 +1,126 @@
+//! Focus filters that keep job postings and already-seen stories out of a list,
+//! remembered per list type in `focus_filters.json`.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::api::StoryListType;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ListFocus {
+    pub hide_jobs: bool,
+    /// Stories opened before, and reposts of links opened before
+    pub hide_seen: bool,
+}
+
+impl ListFocus {
+    /// Short description for the list title, such as "no jobs, unseen"; `None` when off.
+    pub fn label(&self) -> Option<String> {
+        let parts: Vec<&str> = [(self.hide_jobs, "no jobs"), (self.hide_seen, "unseen")]
+            .into_iter()
+            .filter_map(|(on, part)| on.then_some(part))
+            .collect();
+        match parts.is_empty() {
+            true => None,
+            false => Some(parts.join(", ")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FocusFilters {
+    /// Filters per list, keyed by list name ("Top", "Best", ...)
+    #[serde(default)]
+    pub lists: BTreeMap<String, ListFocus>,
+    #[serde(skip)]
+    file_path: Option<PathBuf>,
+}
+
+impl FocusFilters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load_or_create() -> Result<Self> {
+        let config_dir = dirs::config_dir()
+            .context("Could not find config directory")?
+            .join("tui-hn-app");
+        fs::create_dir_all(&config_dir).with_context(|| {
+            format!("Failed to create config directory {}", config_dir.display())
+        })?;
+
+        let file_path = config_dir.join("focus_filters.json");
+        let mut filters: FocusFilters = match file_path.exists() {
+            true => {
+                let content =
+                    fs::read_to_string(&file_path).context("Failed to read focus filters")?;
+                serde_json::from_str(&content).context("Failed to parse focus filters")?
+            }
+            false => Self::new(),
+        };
+        filters.file_path = Some(file_path);
+        Ok(filters)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = &self.file_path {
+            let content =
+                serde_json::to_string_pretty(self).context("Failed to serialize focus filters")?;
+            fs::write(path, content).context("Failed to write focus filters")?;
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, list_type: StoryListType) -> ListFocus {
+        self.lists
+            .get(&list_type.to_string())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Change the filters of one list and return the result. Lists with every
+    /// filter off are dropped from the file.
+    pub fn update(
+        &mut self,
+        list_type: StoryListType,
+        change: impl FnOnce(&mut ListFocus),
+    ) -> ListFocus {
+        let mut focus = self.get(list_type);
+        change(&mut focus);
+        match focus == ListFocus::default() {
+            true => self.lists.remove(&list_type.to_string()),
+            false => self.lists.insert(list_type.to_string(), focus),
+        };
+        focus
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_are_kept_per_list() {
+        let mut filters = FocusFilters::new();
+        let top = filters.update(StoryListType::Top, |f| f.hide_jobs = true);
+        assert_eq!(top.label().as_deref(), Some("no jobs"));
+        assert_eq!(filters.get(StoryListType::Best), ListFocus::default());
+
+        filters.update(StoryListType::Top, |f| f.hide_seen = true);
+        assert_eq!(
+            filters.get(StoryListType::Top).label().as_deref(),
+            Some("no jobs, unseen")
+        );
+
+        let json = serde_json::to_string(&filters).unwrap();
+        let restored: FocusFilters = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.lists, filters.lists);
+
+        filters.update(StoryListType::Top, |f| *f = ListFocus::default());
+        assert!(filters.lists.is_empty());
+    }
+}
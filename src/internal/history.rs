@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use jiff::Zoned;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::PathBuf;
 
@@ -17,25 +18,80 @@ pub struct ViewedStory {
     pub viewed_at: Zoned,
 }
 
+/// Group of the History view an entry falls in, by the day it was viewed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    Today,
+    Yesterday,
+    ThisWeek,
+    Older,
+}
+
+impl Period {
+    pub fn of(viewed_at: &Zoned, now: &Zoned) -> Self {
+        let viewed = viewed_at.with_time_zone(now.time_zone().clone()).date();
+        let days = now.date().since(viewed).map_or(0, |span| span.get_days());
+        match days {
+            ..=0 => Period::Today,
+            1 => Period::Yesterday,
+            2..=6 => Period::ThisWeek,
+            _ => Period::Older,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Period::Today => "Today",
+            Period::Yesterday => "Yesterday",
+            Period::ThisWeek => "This Week",
+            Period::Older => "Older",
+        }
+    }
+}
+
+/// Whether every word of `query` appears in `text` with its letters in order,
+/// ignoring case ("rst cmp" matches "Rust compiler").
+fn fuzzy_match(query: &str, text: &str) -> bool {
+    let text = text.to_lowercase();
+    query.to_lowercase().split_whitespace().all(|word| {
+        let mut chars = text.chars();
+        word.chars().all(|wanted| chars.any(|c| c == wanted))
+    })
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct History {
     pub stories: Vec<ViewedStory>,
+    /// IDs of every story that has been opened. Unlike `stories` this set is not
+    /// truncated to `max_size`, so read state survives history eviction; it has
+    /// its own, larger limit of `max_read_ids`.
+    #[serde(default)]
+    pub read_ids: BTreeSet<u32>,
+    /// Collapsed comment IDs per story, restored when the story is reopened.
+    /// Entries are dropped together with the story when it falls out of history.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub folds: BTreeMap<u32, BTreeSet<u32>>,
     #[serde(skip)]
     file_path: Option<PathBuf>,
     #[serde(skip)]
     max_size: usize,
+    #[serde(skip)]
+    max_read_ids: usize,
 }
 
 impl History {
-    pub fn new(max_size: usize) -> Self {
+    pub fn new(max_size: usize, max_read_ids: usize) -> Self {
         Self {
             stories: Vec::new(),
+            read_ids: BTreeSet::new(),
+            folds: BTreeMap::new(),
             file_path: None,
             max_size,
+            max_read_ids,
         }
     }
 
-    pub fn load_or_create(max_size: usize) -> Result<Self> {
+    pub fn load_or_create(max_size: usize, max_read_ids: usize) -> Result<Self> {
         let config_dir = dirs::config_dir()
             .ok_or_else(|| anyhow::anyhow!("Could not find config directory"))?
             .join("tui-hn-app");
@@ -52,14 +108,23 @@ impl History {
                     fs::read_to_string(&file_path).context("Failed to read history file")?;
                 let mut history: History =
                     serde_json::from_str(&content).context("Failed to parse history file")?;
+                // Older history files predate the read set; seed it from viewed stories
+                let viewed: Vec<u32> = history.stories.iter().map(|s| s.id).collect();
+                history.read_ids.extend(viewed);
                 history.file_path = Some(file_path);
+                // The limits may have been lowered since the file was written
                 history.max_size = max_size;
+                history.max_read_ids = max_read_ids;
+                history.prune();
                 Ok(history)
             }
             false => Ok(Self {
                 stories: Vec::new(),
+                read_ids: BTreeSet::new(),
+                folds: BTreeMap::new(),
                 file_path: Some(file_path),
                 max_size,
+                max_read_ids,
             }),
         }
     }
@@ -88,15 +153,108 @@ impl History {
         };
 
         self.stories.insert(0, viewed);
+        self.read_ids.insert(story.id);
 
-        // Enforce max size
+        self.prune();
+    }
+
+    /// Drop the oldest entries beyond `max_size`, with their fold state, and the
+    /// oldest read marks beyond `max_read_ids`. HN assigns ids in posting order,
+    /// so the lowest ids go first; stories still in the history stay read.
+    fn prune(&mut self) {
         if self.stories.len() > self.max_size {
             self.stories.truncate(self.max_size);
+            let kept: BTreeSet<u32> = self.stories.iter().map(|s| s.id).collect();
+            self.folds.retain(|id, _| kept.contains(id));
+        }
+
+        let excess = self.read_ids.len().saturating_sub(self.max_read_ids);
+        if excess > 0 {
+            let viewed: BTreeSet<u32> = self.stories.iter().map(|s| s.id).collect();
+            let dropped: Vec<u32> = self
+                .read_ids
+                .iter()
+                .copied()
+                .filter(|id| !viewed.contains(id))
+                .take(excess)
+                .collect();
+            for id in dropped {
+                self.read_ids.remove(&id);
+            }
         }
     }
 
+    /// Forget a story: its entry, read state and fold state.
+    pub fn remove(&mut self, id: u32) {
+        self.stories.retain(|s| s.id != id);
+        self.read_ids.remove(&id);
+        self.folds.remove(&id);
+    }
+
+    /// Indices of the entries matching `query` fuzzily on title, site or author;
+    /// all of them for an empty query.
+    pub fn search(&self, query: &str) -> Vec<usize> {
+        self.stories
+            .iter()
+            .enumerate()
+            .filter(|(_, viewed)| {
+                let domain = viewed
+                    .url
+                    .as_deref()
+                    .and_then(crate::utils::url::extract_domain);
+                let text = format!(
+                    "{} {} {}",
+                    viewed.title,
+                    domain.unwrap_or_default(),
+                    viewed.by.as_deref().unwrap_or_default()
+                );
+                fuzzy_match(query, &text)
+            })
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
     pub fn clear(&mut self) {
         self.stories.clear();
+        self.read_ids.clear();
+        self.folds.clear();
+    }
+
+    /// Collapsed comment IDs saved for a story.
+    pub fn folds_for(&self, story_id: u32) -> Option<&BTreeSet<u32>> {
+        self.folds.get(&story_id)
+    }
+
+    /// Replace the saved fold state for a story; an empty set removes the entry.
+    pub fn set_folds(&mut self, story_id: u32, collapsed: BTreeSet<u32>) {
+        match collapsed.is_empty() {
+            true => self.folds.remove(&story_id),
+            false => self.folds.insert(story_id, collapsed),
+        };
+    }
+
+    pub fn is_read(&self, id: u32) -> bool {
+        self.read_ids.contains(&id)
+    }
+
+    /// Whether the story was opened before, or the same link was under another id
+    /// (a repost of something already read).
+    pub fn has_seen(&self, story: &Story) -> bool {
+        use crate::utils::url::page_key;
+        self.is_read(story.id)
+            || story.url.as_deref().is_some_and(|url| {
+                let key = page_key(url);
+                self.stories
+                    .iter()
+                    .filter_map(|viewed| viewed.url.as_deref())
+                    .any(|viewed| page_key(viewed) == key)
+            })
+    }
+
+    /// Mark the given story IDs as read without adding them to the viewed list.
+    pub fn mark_all_read(&mut self, ids: impl IntoIterator<Item = u32>) {
+        self.read_ids.extend(ids);
+        self.prune();
     }
 }
 
@@ -106,7 +264,7 @@ mod tests {
 
     #[test]
     fn test_add_history() {
-        let mut history = History::new(5);
+        let mut history = History::new(5, 100);
         let story = Story {
             id: 1,
             title: Some("Test Story".to_string()),
@@ -116,6 +274,9 @@ mod tests {
             time: Some(1234567890),
             descendants: Some(10),
             kids: None,
+            parts: None,
+            item_type: None,
+            text: None,
         };
 
         history.add(&story);
@@ -138,6 +299,9 @@ mod tests {
                 time: None,
                 descendants: None,
                 kids: None,
+                parts: None,
+                item_type: None,
+                text: None,
             };
             history.add(&s);
         }
@@ -152,7 +316,7 @@ mod tests {
 
     #[test]
     fn test_clear_history() {
-        let mut history = History::new(5);
+        let mut history = History::new(5, 100);
         let story = Story {
             id: 1,
             title: Some("Test".to_string()),
@@ -163,5 +327,95 @@ mod tests {
 
         history.clear();
         assert!(history.stories.is_empty());
+        assert!(!history.is_read(1));
+    }
+
+    #[test]
+    fn test_read_state_outlives_eviction() {
+        let mut history = History::new(1, 100);
+        for id in 1..=3 {
+            history.add(&Story {
+                id,
+                ..Default::default()
+            });
+        }
+        assert_eq!(history.stories.len(), 1);
+        assert!(history.is_read(1));
+        assert!(history.is_read(3));
+        assert!(!history.is_read(4));
+
+        history.mark_all_read([4, 5]);
+        assert!(history.is_read(4));
+        assert!(history.is_read(5));
+        assert_eq!(history.stories.len(), 1);
+    }
+
+    #[test]
+    fn test_read_ids_drop_the_oldest_beyond_the_limit() {
+        let mut history = History::new(1, 3);
+        history.add(&Story {
+            id: 2,
+            ..Default::default()
+        });
+        history.mark_all_read([1, 5, 7, 9]);
+
+        // Story 2 is still in the history, so ids 1 and 5 are forgotten instead
+        assert_eq!(history.read_ids, BTreeSet::from([2, 7, 9]));
+        assert!(!history.is_read(1));
+        assert!(history.is_read(2));
+    }
+
+    #[test]
+    fn test_search_remove_and_periods() {
+        let mut history = History::new(10, 100);
+        for (id, title, url) in [
+            (1, "Rust compiler internals", "https://blog.rust-lang.org/a"),
+            (2, "Go generics", "https://go.dev/blog"),
+        ] {
+            history.add(&Story {
+                id,
+                title: Some(title.to_string()),
+                url: Some(url.to_string()),
+                ..Default::default()
+            });
+        }
+        assert_eq!(history.search(""), vec![0, 1]);
+        assert_eq!(history.search("rst CMP"), vec![1]);
+        assert_eq!(history.search("go.dev"), vec![0]);
+        assert!(history.search("python").is_empty());
+
+        history.remove(1);
+        assert_eq!(history.stories.len(), 1);
+        assert!(!history.is_read(1));
+
+        let now: Zoned = "2024-03-14T09:00:00+00:00[UTC]".parse().unwrap();
+        let at = |text: &str| text.parse::<Zoned>().unwrap();
+        let period = |text| Period::of(&at(text), &now);
+        assert_eq!(period("2024-03-14T00:30:00+00:00[UTC]"), Period::Today);
+        assert_eq!(period("2024-03-13T23:59:00+00:00[UTC]"), Period::Yesterday);
+        assert_eq!(period("2024-03-08T12:00:00+00:00[UTC]"), Period::ThisWeek);
+        assert_eq!(period("2024-03-01T12:00:00+00:00[UTC]"), Period::Older);
+    }
+
+    #[test]
+    fn test_folds_follow_history_eviction() {
+        let mut history = History::new(1, 100);
+        let story = |id| Story {
+            id,
+            ..Default::default()
+        };
+
+        history.add(&story(1));
+        history.set_folds(1, BTreeSet::from([10, 11]));
+        assert_eq!(history.folds_for(1), Some(&BTreeSet::from([10, 11])));
+
+        // Story 1 is evicted, so its fold state goes with it
+        history.add(&story(2));
+        assert_eq!(history.folds_for(1), None);
+
+        history.add(&story(2));
+        history.set_folds(2, BTreeSet::from([5]));
+        history.set_folds(2, BTreeSet::new());
+        assert!(history.folds.is_empty());
     }
 }
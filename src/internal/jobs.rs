@@ -0,0 +1,135 @@
+//! Company, YC batch and role parsed from job posting titles such as
+//! "Acme (YC S21) Is Hiring a Senior Engineer".
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JobTitle {
+    pub company: Option<String>,
+    /// YC batch such as "YC S21"
+    pub batch: Option<String>,
+    pub role: Option<String>,
+}
+
+impl JobTitle {
+    /// Titles that follow neither the "(YC …)" nor the "… is hiring" pattern are
+    /// kept whole as the role.
+    pub fn parse(title: &str) -> Self {
+        let title = title.trim();
+        // ASCII lowercasing keeps byte offsets valid for slicing `title`
+        let lower = title.to_ascii_lowercase();
+        if let Some(start) = lower.find("(yc ")
+            && let Some(len) = lower[start..].find(')')
+        {
+            return Self {
+                company: non_empty(&title[..start]),
+                batch: non_empty(&title[start + 1..start + len]),
+                role: role_from(&title[start + len + 1..]),
+            };
+        }
+        match lower.find(" is hiring").or_else(|| lower.find(" hiring")) {
+            Some(at) => Self {
+                company: non_empty(&title[..at]),
+                batch: None,
+                role: role_from(&title[at..]),
+            },
+            None => Self {
+                company: None,
+                batch: None,
+                role: non_empty(title),
+            },
+        }
+    }
+
+    /// "Company · Role" with whichever parts are known.
+    pub fn label(&self) -> String {
+        [&self.company, &self.role]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+}
+
+fn non_empty(text: &str) -> Option<String> {
+    let text = text.trim();
+    match text.is_empty() {
+        true => None,
+        false => Some(text.to_string()),
+    }
+}
+
+/// The role after the company: "Is Hiring a Senior Engineer" -> "Senior Engineer".
+fn role_from(rest: &str) -> Option<String> {
+    let separators = |c: char| c.is_whitespace() || "–—-:|".contains(c);
+    let mut rest = rest.trim_start_matches(separators);
+    for prefix in ["is hiring", "hiring"] {
+        if let Some(stripped) = strip_prefix_ignore_case(rest, prefix) {
+            rest = stripped.trim_start_matches(separators);
+            break;
+        }
+    }
+    for article in ["a ", "an "] {
+        if let Some(stripped) = strip_prefix_ignore_case(rest, article) {
+            rest = stripped;
+            break;
+        }
+    }
+    non_empty(rest)
+}
+
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    text.get(..prefix.len())
+        .filter(|head| head.eq_ignore_ascii_case(prefix))
+        .map(|_| &text[prefix.len()..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(title: &str) -> (Option<String>, Option<String>, Option<String>) {
+        let job = JobTitle::parse(title);
+        (job.company, job.batch, job.role)
+    }
+
+    fn some(text: &str) -> Option<String> {
+        Some(text.to_string())
+    }
+
+    #[test]
+    fn parses_common_title_shapes() {
+        assert_eq!(
+            parts("Stripe (YC S09) Is Hiring a Backend Engineer"),
+            (some("Stripe"), some("YC S09"), some("Backend Engineer"))
+        );
+        assert_eq!(
+            parts("Fly.io (YC W20) – Platform Engineers (Remote)"),
+            (
+                some("Fly.io"),
+                some("YC W20"),
+                some("Platform Engineers (Remote)")
+            )
+        );
+        assert_eq!(
+            parts("Acme (YC S21) is hiring"),
+            (some("Acme"), some("YC S21"), None)
+        );
+        assert_eq!(
+            parts("Acme is hiring an SRE in Berlin"),
+            (some("Acme"), None, some("SRE in Berlin"))
+        );
+        assert_eq!(
+            parts("Work on compilers at Acme"),
+            (None, None, some("Work on compilers at Acme"))
+        );
+    }
+
+    #[test]
+    fn labels_join_known_parts() {
+        assert_eq!(
+            JobTitle::parse("Stripe (YC S09) Is Hiring a Backend Engineer").label(),
+            "Stripe · Backend Engineer"
+        );
+        assert_eq!(JobTitle::parse("Acme (YC S21) is hiring").label(), "Acme");
+    }
+}
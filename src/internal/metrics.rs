@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Metric name for the time between receiving a key event and finishing the
+/// frame that reflects it.
+pub const INPUT_LATENCY: &str = "input_latency";
+
+/// Metric name for drawing a whole frame.
+pub const FRAME_RENDER: &str = "frame_render";
+
+/// Metric name for how late the event loop wakes for a timer (animation frame,
+/// spinner, notification expiry): time spent handling actions and drawing that
+/// holds it up.
+pub const EVENT_LOOP_LAG: &str = "event_loop_lag";
+
+/// Metric name for drawing one view, e.g. `render.list`.
+pub fn view_render(view: &str) -> &'static str {
+    match view {
+        "list" => "render.list",
+        "bookmarks" => "render.bookmarks",
+        "history" => "render.history",
+        "reading_lists" => "render.reading_lists",
+        "article" => "render.article",
+        _ => "render.detail",
+    }
+}
+
+/// Number of samples kept per histogram. Older samples are overwritten so the
+/// percentiles track recent behaviour rather than the whole session.
+const MAX_SAMPLES: usize = 512;
+
+/// Rolling histogram of duration samples.
+#[derive(Debug, Clone, Default)]
+pub struct Histogram {
+    samples: Vec<Duration>,
+    next: usize,
+    total: u64,
+}
+
+impl Histogram {
+    pub fn record(&mut self, value: Duration) {
+        match self.samples.len() < MAX_SAMPLES {
+            true => self.samples.push(value),
+            false => self.samples[self.next] = value,
+        }
+        self.next = (self.next + 1) % MAX_SAMPLES;
+        self.total += 1;
+    }
+
+    /// Number of samples recorded since startup (including evicted ones)
+    pub fn count(&self) -> u64 {
+        self.total
+    }
+
+    /// Nearest-rank percentile over the retained samples. `p` is in `0.0..=100.0`.
+    pub fn percentile(&self, p: f64) -> Option<Duration> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let mut sorted = self.samples.clone();
+        sorted.sort_unstable();
+        let rank = ((p.clamp(0.0, 100.0) / 100.0) * sorted.len() as f64).ceil() as usize;
+        sorted.get(rank.saturating_sub(1)).copied()
+    }
+}
+
+/// Registry of named histograms collected while the app is running.
+#[derive(Debug, Clone, Default)]
+pub struct MetricsRegistry {
+    histograms: HashMap<&'static str, Histogram>,
+}
+
+impl MetricsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, name: &'static str, value: Duration) {
+        self.histograms.entry(name).or_default().record(value);
+    }
+
+    pub fn histogram(&self, name: &str) -> Option<&Histogram> {
+        self.histograms.get(name)
+    }
+
+    /// Names of the histograms recorded so far, sorted.
+    pub fn names(&self) -> Vec<&'static str> {
+        let mut names: Vec<&'static str> = self.histograms.keys().copied().collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// One-line p50/p95 summary of a histogram, e.g. for the metrics overlay.
+    pub fn summary(&self, name: &str) -> Option<String> {
+        let hist = self.histogram(name)?;
+        let fmt = |d: Option<Duration>| match d {
+            Some(d) => format!("{:.1}ms", d.as_secs_f64() * 1000.0),
+            None => "-".to_string(),
+        };
+        Some(format!(
+            "p50 {} · p95 {} ({} samples)",
+            fmt(hist.percentile(50.0)),
+            fmt(hist.percentile(95.0)),
+            hist.count()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let mut hist = Histogram::default();
+        for ms in 1..=100 {
+            hist.record(Duration::from_millis(ms));
+        }
+        assert_eq!(hist.percentile(50.0), Some(Duration::from_millis(50)));
+        assert_eq!(hist.percentile(95.0), Some(Duration::from_millis(95)));
+        assert_eq!(hist.percentile(100.0), Some(Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn histogram_keeps_only_recent_samples() {
+        let mut hist = Histogram::default();
+        for _ in 0..MAX_SAMPLES {
+            hist.record(Duration::from_millis(100));
+        }
+        for _ in 0..MAX_SAMPLES {
+            hist.record(Duration::from_millis(1));
+        }
+        assert_eq!(hist.count(), 2 * MAX_SAMPLES as u64);
+        assert_eq!(hist.percentile(95.0), Some(Duration::from_millis(1)));
+    }
+
+    #[test]
+    fn names_views_and_lists_recorded_metrics() {
+        let mut registry = MetricsRegistry::new();
+        registry.record(view_render("article"), Duration::from_millis(2));
+        registry.record(view_render("list"), Duration::from_millis(1));
+        registry.record(view_render("comments"), Duration::from_millis(1));
+        assert_eq!(
+            registry.names(),
+            vec!["render.article", "render.detail", "render.list"]
+        );
+    }
+
+    #[test]
+    fn summary_is_none_until_recorded() {
+        let mut registry = MetricsRegistry::new();
+        assert!(registry.summary(INPUT_LATENCY).is_none());
+        registry.record(INPUT_LATENCY, Duration::from_millis(4));
+        assert_eq!(
+            registry.summary(INPUT_LATENCY).as_deref(),
+            Some("p50 4.0ms · p95 4.0ms (1 samples)")
+        );
+    }
+}
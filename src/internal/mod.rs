@@ -1,7 +1,32 @@
+pub mod auto_bookmark;
+pub mod bookmark_import;
 pub mod bookmarks;
 pub mod cache;
+pub mod comment_tree;
+pub mod domain_meta;
+pub mod error;
+pub mod export;
+pub mod external;
+pub mod focus;
 pub mod history;
+pub mod jobs;
+pub mod metrics;
 pub mod models;
+pub mod mute;
 pub mod notification;
+pub mod offline_pack;
+pub mod plugins;
+pub mod profiling;
+pub mod push;
+pub mod read_later;
+pub mod reading_index;
+pub mod reading_lists;
+pub mod scripting;
 pub mod search;
+pub mod session;
+pub mod snapshot;
+pub mod story_filter;
+pub mod thread_snapshot;
+pub mod trends;
 pub mod ui;
+pub mod whats_new;
@@ -1,6 +1,6 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct Story {
     pub id: u32,
     pub title: Option<String>,
@@ -10,6 +10,49 @@ pub struct Story {
     pub time: Option<i64>,
     pub descendants: Option<u32>,
     pub kids: Option<Vec<u32>>,
+    /// Option ids when the item is a poll
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parts: Option<Vec<u32>>,
+    /// HN item type ("story", "job", "poll", ...)
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub item_type: Option<String>,
+    /// HTML body of Ask HN posts and job postings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+impl Story {
+    pub fn is_poll(&self) -> bool {
+        self.parts.as_ref().is_some_and(|parts| !parts.is_empty())
+    }
+
+    /// Job postings from YC companies, mixed into the Top and Best lists.
+    pub fn is_job(&self) -> bool {
+        self.item_type.as_deref() == Some("job")
+    }
+}
+
+/// One option of a poll (HN item type `pollopt`); its score is the vote count.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct PollOpt {
+    pub id: u32,
+    /// Id of the poll this option belongs to
+    pub poll: Option<u32>,
+    pub text: Option<String>,
+    pub score: Option<u32>,
+}
+
+/// A poll story's options, in the order HN lists them.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Poll {
+    pub id: u32,
+    pub options: Vec<PollOpt>,
+}
+
+impl Poll {
+    pub fn total_votes(&self) -> u32 {
+        self.options.iter().filter_map(|opt| opt.score).sum()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -21,7 +64,7 @@ pub enum FetchState {
     Failed,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[allow(dead_code)]
 pub struct Comment {
     pub id: u32,
@@ -33,6 +76,67 @@ pub struct Comment {
     pub kids: Option<Vec<u32>>,
     #[serde(default)]
     pub deleted: bool,
+    /// Flagged or killed; the API still sends its text
+    #[serde(default)]
+    pub dead: bool,
+}
+
+/// Any HN item, told apart by its `type` field, for ids whose kind is not known
+/// in advance. Jobs and polls are stories that keep their `item_type`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+    Story(Story),
+    Comment(Comment),
+    Job(Story),
+    Poll(Story),
+    PollOpt(PollOpt),
+}
+
+impl Item {
+    pub fn id(&self) -> u32 {
+        match self {
+            Item::Story(story) | Item::Job(story) | Item::Poll(story) => story.id,
+            Item::Comment(comment) => comment.id,
+            Item::PollOpt(opt) => opt.id,
+        }
+    }
+
+    /// The variant for a story, by its `item_type`.
+    pub fn from_story(story: Story) -> Self {
+        match story.item_type.as_deref() {
+            Some("job") => Item::Job(story),
+            Some("poll") => Item::Poll(story),
+            _ => Item::Story(story),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Item {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let item_type = value
+            .get("type")
+            .and_then(|t| t.as_str())
+            .map(str::to_string);
+        match item_type.as_deref() {
+            Some("story" | "job" | "poll") => Story::deserialize(value)
+                .map(Item::from_story)
+                .map_err(D::Error::custom),
+            Some("comment") => Comment::deserialize(value)
+                .map(Item::Comment)
+                .map_err(D::Error::custom),
+            Some("pollopt") => PollOpt::deserialize(value)
+                .map(Item::PollOpt)
+                .map_err(D::Error::custom),
+            Some(other) => Err(D::Error::custom(format!("unknown item type `{}`", other))),
+            None => Err(D::Error::missing_field("type")),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -44,7 +148,7 @@ pub struct CommentRow {
     pub loaded_kids: bool,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Hash)]
 #[allow(dead_code)]
 pub enum ArticleElement {
     Paragraph(String),
@@ -56,9 +160,89 @@ pub enum ArticleElement {
     Quote(String),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// A link found in an article or a comment.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default, Hash)]
+pub struct Link {
+    /// Link text, or the URL when the link has none
+    pub text: String,
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default, Hash)]
 #[allow(dead_code)]
 pub struct Article {
     pub title: String,
     pub elements: Vec<ArticleElement>,
+    /// Author line extracted from the page (meta author, rel=author, .byline)
+    pub byline: Option<String>,
+    /// Publication date as found in the page (ISO 8601 when available)
+    pub published: Option<String>,
+    /// Publisher name (og:site_name)
+    pub site_name: Option<String>,
+    /// Words of text, counted when the page is parsed; 0 in articles cached before
+    /// it was counted
+    #[serde(default)]
+    pub word_count: usize,
+    /// Links in the text, in page order
+    #[serde(default)]
+    pub links: Vec<Link>,
+}
+
+/// Reading speed assumed for reading time estimates.
+const WORDS_PER_MINUTE: usize = 230;
+
+impl Article {
+    /// Words in the text of `elements`; code blocks and images are left out.
+    pub fn count_words(elements: &[ArticleElement]) -> usize {
+        let words = |text: &str| text.split_whitespace().count();
+        elements
+            .iter()
+            .map(|element| match element {
+                ArticleElement::Paragraph(text)
+                | ArticleElement::Heading(_, text)
+                | ArticleElement::Quote(text) => words(text),
+                ArticleElement::List(items) => items.iter().map(|item| words(item)).sum(),
+                ArticleElement::Table(rows) => rows.iter().flatten().map(|cell| words(cell)).sum(),
+                ArticleElement::CodeBlock { .. } | ArticleElement::Image(_) => 0,
+            })
+            .sum()
+    }
+
+    pub fn words(&self) -> usize {
+        match self.word_count {
+            0 => Self::count_words(&self.elements),
+            count => count,
+        }
+    }
+
+    /// Estimated minutes to read the article, rounded up; 0 if it has no text.
+    pub fn reading_minutes(&self) -> usize {
+        self.words().div_ceil(WORDS_PER_MINUTE)
+    }
+
+    /// Make the links absolute against the URL of the page they were found on,
+    /// dropping those that do not lead to a web page.
+    pub fn resolve_links(&mut self, page_url: &str) {
+        let mut seen = std::collections::HashSet::new();
+        self.links = std::mem::take(&mut self.links)
+            .into_iter()
+            .filter_map(|link| {
+                let url = crate::utils::url::join(page_url, &link.url)?;
+                let text = match link.text == link.url {
+                    true => url.clone(),
+                    false => link.text,
+                };
+                seen.insert(url.clone()).then_some(Link { text, url })
+            })
+            .collect();
+    }
+}
+
+/// What a site says about itself on its pages: its name and brand color.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct SiteMeta {
+    /// Publisher name (og:site_name, application-name)
+    pub name: Option<String>,
+    /// `theme-color` of the page
+    pub theme_color: Option<(u8, u8, u8)>,
 }
@@ -0,0 +1,129 @@
+//! Domains and users muted with `x` on a story, kept in `mute_list.json`.
+//! Stories from muted domains or by muted users are hidden from the lists, and
+//! comments by muted users are greyed out, until muted content is revealed.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+
+use super::models::Story;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MuteList {
+    /// Muted domains; each also mutes its subdomains
+    #[serde(default)]
+    pub domains: BTreeSet<String>,
+    #[serde(default)]
+    pub users: BTreeSet<String>,
+    #[serde(skip)]
+    file_path: Option<PathBuf>,
+}
+
+/// Domain of a story as stored in the mute list: lowercase, without "www.".
+pub fn story_domain(story: &Story) -> Option<String> {
+    story
+        .url
+        .as_deref()
+        .and_then(crate::utils::url::extract_domain)
+        .map(|domain| domain.trim_start_matches("www.").to_lowercase())
+}
+
+impl MuteList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load_or_create() -> Result<Self> {
+        let config_dir = dirs::config_dir()
+            .context("Could not find config directory")?
+            .join("tui-hn-app");
+        fs::create_dir_all(&config_dir).with_context(|| {
+            format!("Failed to create config directory {}", config_dir.display())
+        })?;
+
+        let file_path = config_dir.join("mute_list.json");
+        let mut list: MuteList = match file_path.exists() {
+            true => {
+                let content = fs::read_to_string(&file_path).context("Failed to read mute list")?;
+                serde_json::from_str(&content).context("Failed to parse mute list")?
+            }
+            false => Self::new(),
+        };
+        list.file_path = Some(file_path);
+        Ok(list)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = &self.file_path {
+            let content =
+                serde_json::to_string_pretty(self).context("Failed to serialize mute list")?;
+            fs::write(path, content).context("Failed to write mute list")?;
+        }
+        Ok(())
+    }
+
+    /// Mute the domain, or unmute it if it was muted. Returns whether it is muted now.
+    pub fn toggle_domain(&mut self, domain: &str) -> bool {
+        toggle(
+            &mut self.domains,
+            domain.trim_start_matches("www.").to_lowercase(),
+        )
+    }
+
+    /// Mute the user, or unmute them if they were muted. Returns whether they are muted now.
+    pub fn toggle_user(&mut self, user: &str) -> bool {
+        toggle(&mut self.users, user.to_string())
+    }
+
+    pub fn is_domain_muted(&self, domain: &str) -> bool {
+        self.domains
+            .iter()
+            .any(|muted| domain == muted || domain.ends_with(&format!(".{}", muted)))
+    }
+
+    pub fn is_user_muted(&self, user: &str) -> bool {
+        self.users.contains(user)
+    }
+
+    /// Whether the story comes from a muted domain or was posted by a muted user.
+    pub fn mutes_story(&self, story: &Story) -> bool {
+        if self.domains.is_empty() && self.users.is_empty() {
+            return false;
+        }
+        story_domain(story).is_some_and(|domain| self.is_domain_muted(&domain))
+            || story.by.as_deref().is_some_and(|by| self.is_user_muted(by))
+    }
+}
+
+fn toggle(set: &mut BTreeSet<String>, value: String) -> bool {
+    match set.remove(&value) {
+        true => false,
+        false => set.insert(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutes_domains_with_subdomains_and_users() {
+        let story = |url: &str, by: &str| Story {
+            url: Some(url.to_string()),
+            by: Some(by.to_string()),
+            ..Default::default()
+        };
+        let mut list = MuteList::new();
+        assert!(list.toggle_domain("www.Medium.com"));
+        assert!(list.mutes_story(&story("https://blog.medium.com/x", "alice")));
+        assert!(list.mutes_story(&story("https://medium.com/x", "alice")));
+        assert!(!list.mutes_story(&story("https://notmedium.com/x", "alice")));
+
+        assert!(list.toggle_user("bob"));
+        assert!(list.mutes_story(&story("https://example.com", "bob")));
+        assert!(!list.toggle_user("bob"));
+        assert!(!list.mutes_story(&story("https://example.com", "bob")));
+    }
+}
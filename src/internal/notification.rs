@@ -1,5 +1,13 @@
+use std::collections::{HashMap, VecDeque};
 use std::time::{Duration, Instant};
 
+use crate::config::NotificationConfig;
+
+/// How long an error from a background task stays quiet after it was shown.
+pub const BACKGROUND_ERROR_INTERVAL: Duration = Duration::from_secs(60);
+/// Notifications the notification center keeps.
+pub const HISTORY_LIMIT: usize = 50;
+
 /// Type of notification to display
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NotificationType {
@@ -10,11 +18,21 @@ pub enum NotificationType {
 }
 
 impl NotificationType {
-    fn timeout(&self) -> Duration {
+    /// How long a toast of this severity stays up with `config`.
+    fn timeout(&self, config: &NotificationConfig) -> Duration {
+        let millis = match self {
+            NotificationType::Info => config.info_ms,
+            NotificationType::Warning => config.warning_ms,
+            NotificationType::Error => config.error_ms,
+        };
+        Duration::from_millis(millis)
+    }
+
+    pub fn label(&self) -> &'static str {
         match self {
-            NotificationType::Info => Duration::from_secs(3),
-            NotificationType::Warning => Duration::from_secs(5),
-            NotificationType::Error => Duration::from_secs(10),
+            NotificationType::Info => "Info",
+            NotificationType::Warning => "Warning",
+            NotificationType::Error => "Error",
         }
     }
 }
@@ -25,6 +43,12 @@ pub struct Notification {
     pub message: String,
     pub notification_type: NotificationType,
     pub timestamp: Instant,
+    /// When it was raised (Unix seconds), as the notification center shows it
+    pub time: i64,
+    /// How long it stays up; set by the severity when it is shown
+    pub timeout: Duration,
+    /// Times the same notification fired while it was shown
+    pub count: usize,
 }
 
 impl Notification {
@@ -49,12 +73,41 @@ impl Notification {
             message: message.into(),
             notification_type,
             timestamp: Instant::now(),
+            time: jiff::Timestamp::now().as_second(),
+            timeout: notification_type.timeout(&NotificationConfig::default()),
+            count: 1,
+        }
+    }
+
+    /// Count `other` as a repeat of this notification if it says the same thing and
+    /// this one is still shown. The timer is not restarted, so a burst of repeats
+    /// goes away as soon as the first would have.
+    pub fn absorb(&mut self, other: &Notification) -> bool {
+        let repeat = self.notification_type == other.notification_type
+            && self.message == other.message
+            && !self.should_dismiss();
+        if repeat {
+            self.count += 1;
+        }
+        repeat
+    }
+
+    /// Message as shown, with the repeat count, e.g. "All comments already loaded ×3".
+    pub fn text(&self) -> String {
+        match self.count {
+            1 => self.message.clone(),
+            count => format!("{} ×{}", self.message, count),
         }
     }
 
     /// Check if this notification should be auto-dismissed
     pub fn should_dismiss(&self) -> bool {
-        self.timestamp.elapsed() > self.notification_type.timeout()
+        Instant::now() >= self.dismiss_at()
+    }
+
+    /// When this notification goes away on its own.
+    pub fn dismiss_at(&self) -> Instant {
+        self.timestamp + self.timeout
     }
 
     #[allow(dead_code)]
@@ -70,8 +123,160 @@ impl Notification {
     /// Get the remaining time before auto-dismiss
     #[allow(dead_code)]
     pub fn remaining_time(&self) -> Duration {
-        self.notification_type
-            .timeout()
-            .saturating_sub(self.timestamp.elapsed())
+        self.timeout.saturating_sub(self.timestamp.elapsed())
+    }
+}
+
+/// Notifications shown as toasts, stacked newest last, and the last
+/// [`HISTORY_LIMIT`] of them kept for the notification center.
+#[derive(Debug, Clone, Default)]
+pub struct NotificationCenter {
+    /// Shown now, oldest first
+    pub toasts: Vec<Notification>,
+    /// Newest first
+    pub history: VecDeque<Notification>,
+    config: NotificationConfig,
+}
+
+impl NotificationCenter {
+    pub fn new(config: NotificationConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    pub fn set_config(&mut self, config: NotificationConfig) {
+        self.config = config;
+    }
+
+    /// Show `notification` with the timeout of its severity, or count it on a toast
+    /// that says the same thing. The oldest toast makes way when too many are up.
+    pub fn push(&mut self, mut notification: Notification) {
+        notification.timeout = notification.notification_type.timeout(&self.config);
+        if let Some(count) = self
+            .toasts
+            .iter_mut()
+            .find_map(|toast| toast.absorb(&notification).then_some(toast.count))
+        {
+            if let Some(logged) = self.history.iter_mut().find(|logged| {
+                logged.message == notification.message
+                    && logged.notification_type == notification.notification_type
+            }) {
+                logged.count = count;
+            }
+            return;
+        }
+        self.history.push_front(notification.clone());
+        self.history.truncate(HISTORY_LIMIT);
+        self.toasts.push(notification);
+        let excess = self
+            .toasts
+            .len()
+            .saturating_sub(self.config.max_toasts.max(1));
+        self.toasts.drain(..excess);
+    }
+
+    /// The newest toast, for the status bar and the message line.
+    pub fn latest(&self) -> Option<&Notification> {
+        self.toasts.last()
+    }
+
+    /// Drop the toasts whose time is up; true if any went.
+    pub fn expire(&mut self) -> bool {
+        let before = self.toasts.len();
+        self.toasts.retain(|toast| !toast.should_dismiss());
+        self.toasts.len() != before
+    }
+
+    /// When the next toast goes away on its own.
+    pub fn next_dismiss(&self) -> Option<Instant> {
+        self.toasts.iter().map(Notification::dismiss_at).min()
+    }
+
+    pub fn dismiss_all(&mut self) {
+        self.toasts.clear();
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+}
+
+/// Remembers when errors from background tasks (refresh pollers, scripts run on
+/// refreshed stories) were last shown, so the same failure on every poll is shown
+/// once per interval.
+#[derive(Debug, Default)]
+pub struct ErrorLimiter {
+    last_shown: HashMap<String, Instant>,
+}
+
+impl ErrorLimiter {
+    /// Whether `message` may be shown at `now`; records it if so.
+    pub fn allow(&mut self, message: &str, now: Instant) -> bool {
+        let quiet = self
+            .last_shown
+            .get(message)
+            .is_some_and(|shown| now.duration_since(*shown) < BACKGROUND_ERROR_INTERVAL);
+        if !quiet {
+            self.last_shown
+                .retain(|_, shown| now.duration_since(*shown) < BACKGROUND_ERROR_INTERVAL);
+            self.last_shown.insert(message.to_string(), now);
+        }
+        !quiet
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_repeats_and_limits_background_errors() {
+        let mut shown = Notification::info("All comments already loaded");
+        assert!(shown.absorb(&Notification::info("All comments already loaded")));
+        assert!(shown.absorb(&Notification::info("All comments already loaded")));
+        assert!(!shown.absorb(&Notification::error("All comments already loaded")));
+        assert!(!shown.absorb(&Notification::info("Copied")));
+        assert_eq!(shown.text(), "All comments already loaded ×3");
+
+        let mut limiter = ErrorLimiter::default();
+        let start = Instant::now();
+        assert!(limiter.allow("Failed to save", start));
+        assert!(!limiter.allow("Failed to save", start + Duration::from_secs(5)));
+        assert!(limiter.allow("Other failure", start + Duration::from_secs(5)));
+        assert!(limiter.allow("Failed to save", start + BACKGROUND_ERROR_INTERVAL));
+    }
+
+    #[test]
+    fn stacks_toasts_and_keeps_a_history() {
+        let mut center = NotificationCenter::new(NotificationConfig {
+            max_toasts: 2,
+            error_ms: 60_000,
+            ..Default::default()
+        });
+        center.push(Notification::info("Copied"));
+        center.push(Notification::error("Refresh failed"));
+        center.push(Notification::error("Refresh failed"));
+        assert_eq!(center.toasts.len(), 2);
+        assert_eq!(center.latest().unwrap().text(), "Refresh failed ×2");
+        assert_eq!(center.history.len(), 2);
+        assert_eq!(center.history[0].count, 2);
+        assert_eq!(center.toasts[1].timeout, std::time::Duration::from_secs(60));
+
+        // A third toast pushes the oldest off the stack, but not out of the history
+        center.push(Notification::warning("Slow network"));
+        let shown: Vec<_> = center.toasts.iter().map(|t| t.message.as_str()).collect();
+        assert_eq!(shown, vec!["Refresh failed", "Slow network"]);
+        assert_eq!(center.history.len(), 3);
+
+        for i in 0..HISTORY_LIMIT {
+            center.push(Notification::info(format!("Message {}", i)));
+        }
+        assert_eq!(center.history.len(), HISTORY_LIMIT);
+        assert_eq!(
+            center.history[0].message,
+            format!("Message {}", HISTORY_LIMIT - 1)
+        );
     }
 }
@@ -0,0 +1,229 @@
+//! Offline pack: the top stories of some lists downloaded ahead of time, with their
+//! articles and comment threads, into the persistent cache. The story ids of each
+//! list are kept in `offline_pack.json` so `--offline` can show the lists.
+
+use anyhow::{Context, Result};
+use jiff::Zoned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::api::{ApiService, StoryListType};
+use crate::config::OfflinePackConfig;
+
+/// Story ids of the lists in the last pack.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct OfflinePack {
+    pub created_at: Option<Zoned>,
+    pub lists: BTreeMap<String, Vec<u32>>,
+    #[serde(skip)]
+    file_path: Option<PathBuf>,
+}
+
+impl OfflinePack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load_or_create() -> Result<Self> {
+        let config_dir = dirs::config_dir()
+            .ok_or_else(|| anyhow::anyhow!("Could not find config directory"))?
+            .join("tui-hn-app");
+        fs::create_dir_all(&config_dir).context("Failed to create config directory")?;
+        let file_path = config_dir.join("offline_pack.json");
+
+        let mut pack: OfflinePack = match file_path.exists() {
+            true => {
+                let content =
+                    fs::read_to_string(&file_path).context("Failed to read offline pack file")?;
+                serde_json::from_str(&content).context("Failed to parse offline pack file")?
+            }
+            false => Self::new(),
+        };
+        pack.file_path = Some(file_path);
+        Ok(pack)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = &self.file_path {
+            let content =
+                serde_json::to_string_pretty(self).context("Failed to serialize offline pack")?;
+            fs::write(path, content).context("Failed to write offline pack file")?;
+        }
+        Ok(())
+    }
+
+    /// Packed story ids of a list, in rank order.
+    pub fn ids(&self, list: StoryListType) -> Option<&Vec<u32>> {
+        self.lists.get(&list.to_string())
+    }
+}
+
+/// What a pack run downloaded.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PackSummary {
+    pub stories: usize,
+    pub articles: usize,
+    /// Comments fetched across all threads
+    pub comments: usize,
+    /// Rough size added to the cache, in bytes
+    pub bytes: usize,
+    /// What could not be downloaded, one line each
+    pub failures: Vec<String>,
+}
+
+impl PackSummary {
+    pub fn line(&self) -> String {
+        let mut line = format!(
+            "Offline pack: {} stories, {} articles, {} comments (~{:.1} MB)",
+            self.stories,
+            self.articles,
+            self.comments,
+            self.bytes as f64 / 1_000_000.0
+        );
+        if !self.failures.is_empty() {
+            line.push_str(&format!(", {} failed", self.failures.len()));
+        }
+        line
+    }
+}
+
+fn cached_bytes(api: &ApiService) -> usize {
+    let stats = api.cache_stats();
+    stats.stories.approx_bytes + stats.comments.approx_bytes + stats.articles.approx_bytes
+}
+
+/// Entries dropped from the caches to stay under `cache_max_entries`.
+fn evictions(api: &ApiService) -> u64 {
+    let stats = api.cache_stats();
+    stats.stories.evictions + stats.comments.evictions + stats.articles.evictions
+}
+
+/// Download the first `stories_per_list` stories of each list in `config`, with
+/// their articles and comments, and save the cache and the pack. `progress` is
+/// called with (stories done, stories in the pack) as they finish.
+pub async fn prepare(
+    api: &ApiService,
+    config: &OfflinePackConfig,
+    concurrency: usize,
+    pack: &mut OfflinePack,
+    progress: impl Fn(usize, usize),
+) -> PackSummary {
+    use futures::stream::{self, StreamExt};
+
+    let before = cached_bytes(api);
+    let evicted_before = evictions(api);
+    let mut summary = PackSummary::default();
+    let mut lists = BTreeMap::new();
+    let mut ids: Vec<u32> = Vec::new();
+    for list in &config.lists {
+        match api.fetch_story_ids(*list, None).await {
+            Ok(list_ids) => {
+                let list_ids: Vec<u32> =
+                    list_ids.into_iter().take(config.stories_per_list).collect();
+                for id in &list_ids {
+                    if !ids.contains(id) {
+                        ids.push(*id);
+                    }
+                }
+                lists.insert(list.to_string(), list_ids);
+            }
+            Err(e) => summary.failures.push(format!("{} list: {:#}", list, e)),
+        }
+    }
+
+    let total = ids.len();
+    progress(0, total);
+    let stories = api
+        .fetch_stories_concurrent(&ids, concurrency.max(1), None)
+        .await;
+    let mut done = 0;
+    let mut items = stream::iter(stories)
+        .map(|story| async move {
+            let story = story?;
+            let article = match &story.url {
+                Some(url) => Some(
+                    api.fetch_article_content(url, None)
+                        .await
+                        .map(|_| ())
+                        .with_context(|| format!("article of {}", story.id)),
+                ),
+                None => None,
+            };
+            let comments = match &story.kids {
+                Some(kids) if config.comment_depth > 0 => api
+                    .fetch_comment_tree(kids.clone(), config.comment_depth, None)
+                    .await
+                    .map(|rows| rows.len())
+                    .with_context(|| format!("comments of {}", story.id)),
+                _ => Ok(0),
+            };
+            Ok::<_, anyhow::Error>((article, comments))
+        })
+        .buffer_unordered(concurrency.max(1));
+    while let Some(item) = items.next().await {
+        done += 1;
+        progress(done, total);
+        match item {
+            Ok((article, comments)) => {
+                summary.stories += 1;
+                match article {
+                    Some(Ok(())) => summary.articles += 1,
+                    Some(Err(e)) => summary.failures.push(format!("{:#}", e)),
+                    None => {}
+                }
+                match comments {
+                    Ok(count) => summary.comments += count,
+                    Err(e) => summary.failures.push(format!("{:#}", e)),
+                }
+            }
+            Err(e) => summary.failures.push(format!("{:#}", e)),
+        }
+    }
+
+    // Packed items pushed out of a full cache are not there when going offline
+    let evicted = evictions(api) - evicted_before;
+    if evicted > 0 {
+        summary.failures.push(format!(
+            "{} cached items evicted: raise network.cache_max_entries",
+            evicted
+        ));
+    }
+    // Saved even with the disk cache disabled, for `--offline` to load
+    if let Err(e) = api.save_caches() {
+        summary.failures.push(format!("saving the cache: {:#}", e));
+    }
+    summary.bytes = cached_bytes(api).saturating_sub(before);
+    pack.created_at = Some(Zoned::now());
+    pack.lists = lists;
+    if let Err(e) = pack.save() {
+        summary.failures.push(format!("saving the pack: {:#}", e));
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_line_mentions_failures() {
+        let summary = PackSummary {
+            stories: 30,
+            articles: 24,
+            comments: 1200,
+            bytes: 2_500_000,
+            failures: vec!["article of 1: timeout".to_string()],
+        };
+        assert_eq!(
+            summary.line(),
+            "Offline pack: 30 stories, 24 articles, 1200 comments (~2.5 MB), 1 failed"
+        );
+
+        let mut pack = OfflinePack::new();
+        pack.lists.insert("Top".to_string(), vec![3, 1]);
+        assert_eq!(pack.ids(StoryListType::Top), Some(&vec![3, 1]));
+        assert_eq!(pack.ids(StoryListType::New), None);
+    }
+}
@@ -0,0 +1,130 @@
+//! External commands configured as plugins: list sources print stories, item actions
+//! receive the selected story on stdin.
+
+use anyhow::{Context, Result, bail};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::config::PluginConfig;
+use crate::internal::models::Story;
+
+/// What a plugin printed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PluginOutput {
+    /// A JSON array, shown as a custom list
+    Stories(Vec<Story>),
+    /// Any other output, shown as a notification (empty when nothing was printed)
+    Message(String),
+}
+
+/// Entry of a printed story array: a full story or just an HN item id.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum StoryEntry {
+    Id(u32),
+    Story(Box<Story>),
+}
+
+/// Interpret plugin stdout. A JSON array of stories or ids becomes a story list;
+/// anything else, including malformed JSON, is treated as a text message.
+pub fn parse_output(stdout: &str) -> PluginOutput {
+    let trimmed = stdout.trim();
+    match serde_json::from_str::<Vec<StoryEntry>>(trimmed) {
+        Ok(entries) => PluginOutput::Stories(
+            entries
+                .into_iter()
+                .map(|entry| match entry {
+                    StoryEntry::Id(id) => Story {
+                        id,
+                        ..Default::default()
+                    },
+                    StoryEntry::Story(story) => *story,
+                })
+                .collect(),
+        ),
+        _ => PluginOutput::Message(trimmed.to_string()),
+    }
+}
+
+/// Run `plugin`, writing `input` to its stdin, and parse what it prints.
+/// A non-zero exit is an error carrying the first line of stderr.
+pub async fn run(plugin: &PluginConfig, input: Option<String>) -> Result<PluginOutput> {
+    let mut child = Command::new(&plugin.command)
+        .args(&plugin.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to start {}", plugin.command))?;
+
+    // Dropping stdin closes it, so the plugin sees EOF
+    if let Some(mut stdin) = child.stdin.take() {
+        let input = input.unwrap_or_default();
+        // A plugin that ignores its input may exit before reading it
+        let _ = stdin.write_all(input.as_bytes()).await;
+    }
+
+    let timeout = Duration::from_secs(plugin.timeout_secs.max(1));
+    let output = tokio::time::timeout(timeout, child.wait_with_output())
+        .await
+        .with_context(|| format!("{} timed out after {:?}", plugin.name, timeout))?
+        .with_context(|| format!("Failed to run {}", plugin.command))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().next().unwrap_or("").trim().to_string();
+        match reason.is_empty() {
+            true => bail!("{} failed ({})", plugin.name, output.status),
+            false => bail!("{} failed: {}", plugin.name, reason),
+        }
+    }
+    Ok(parse_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arrays_become_story_lists() {
+        let output = parse_output(r#"[123, {"id": 7, "title": "Seven", "score": 3}]"#);
+        let PluginOutput::Stories(stories) = output else {
+            panic!("expected stories, got {:?}", output);
+        };
+        assert_eq!(stories[0].id, 123);
+        assert_eq!(stories[1].title.as_deref(), Some("Seven"));
+
+        assert_eq!(
+            parse_output("  Summary: fine\n"),
+            PluginOutput::Message("Summary: fine".to_string())
+        );
+        assert_eq!(
+            parse_output("[oops"),
+            PluginOutput::Message("[oops".to_string())
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn runs_commands_with_stdin_and_reports_failures() {
+        let plugin = |script: &str| PluginConfig {
+            name: "test".to_string(),
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            ..Default::default()
+        };
+
+        let echoed = run(&plugin("cat"), Some(r#"[{"id": 1}]"#.to_string()))
+            .await
+            .unwrap();
+        assert!(matches!(echoed, PluginOutput::Stories(s) if s[0].id == 1));
+
+        let err = run(&plugin("echo broken >&2; exit 3"), None)
+            .await
+            .unwrap_err();
+        assert_eq!(format!("{:#}", err), "test failed: broken");
+    }
+}
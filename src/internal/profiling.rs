@@ -0,0 +1,116 @@
+//! `--profile`: record the session's spans as a Chrome trace, to look into
+//! slowness in Perfetto (ui.perfetto.dev), `chrome://tracing` or speedscope, which
+//! draw it as a flame chart.
+//!
+//! Spans are named by subsystem: `fetch.*` around network requests, `parse.*`
+//! around HTML parsing, `render.*` around drawing and layout, and `action` around
+//! each action handled, with the action's name.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tracing_chrome::{ChromeLayer, ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::internal::ui::app::Action;
+
+/// What the trace records, whatever the log level.
+pub const FILTER: &str = "tui_hn_app=debug";
+
+/// `requested`, or a timestamped file in the log directory.
+pub fn trace_path(requested: Option<&Path>, log_dir: &Path) -> PathBuf {
+    match requested {
+        Some(path) => path.to_path_buf(),
+        None => log_dir.join(format!(
+            "trace-{}.json",
+            jiff::Zoned::now().strftime("%Y%m%d-%H%M%S")
+        )),
+    }
+}
+
+/// Layer writing the trace to `path`. The file is complete once the guard is
+/// dropped.
+pub fn layer<S>(path: &Path) -> Result<(ChromeLayer<S>, FlushGuard)>
+where
+    S: tracing::Subscriber + for<'span> LookupSpan<'span> + Send + Sync,
+{
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("Failed to create trace file {}", path.display()))?;
+    Ok(ChromeLayerBuilder::new()
+        .writer(file)
+        .include_args(true)
+        .build())
+}
+
+/// Span around the handling of `action`, named after its variant.
+pub fn action_span(action: &Action) -> tracing::Span {
+    let span = tracing::debug_span!("action", name = tracing::field::Empty);
+    if !span.is_disabled() {
+        span.record("name", variant_name(action));
+    }
+    span
+}
+
+/// `"LoadStories"` for `LoadStories(Top)`.
+fn variant_name(action: &Action) -> String {
+    let debug = format!("{:?}", action);
+    debug
+        .split(['(', ' ', '{'])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::StoryListType;
+
+    #[test]
+    fn names_actions_and_trace_files() {
+        assert_eq!(
+            variant_name(&Action::LoadStories(StoryListType::Top)),
+            "LoadStories"
+        );
+        assert_eq!(variant_name(&Action::Quit), "Quit");
+
+        let requested = Path::new("/tmp/slow.json");
+        assert_eq!(
+            trace_path(Some(requested), Path::new("logs")),
+            requested.to_path_buf()
+        );
+        let default = trace_path(None, Path::new("logs"));
+        assert!(default.starts_with("logs"));
+        let name = default.file_name().unwrap().to_string_lossy().to_string();
+        assert!(name.starts_with("trace-") && name.ends_with(".json"));
+    }
+
+    #[test]
+    fn writes_spans_to_the_trace() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let path =
+            std::env::temp_dir().join(format!("hn-profile-{}/trace.json", std::process::id()));
+        let (layer, guard) = layer(&path).unwrap();
+        let subscriber = tracing_subscriber::registry().with(layer);
+        tracing::subscriber::with_default(subscriber, || {
+            let _action = action_span(&Action::Quit).entered();
+            tracing::debug_span!("render.frame").in_scope(|| {});
+        });
+        drop(guard);
+
+        let trace: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let names: Vec<&str> = trace
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|event| event["name"].as_str())
+            .collect();
+        assert!(names.contains(&"action") && names.contains(&"render.frame"));
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,198 @@
+//! "Send to phone": post the focused story's title and link to ntfy, Pushover or a
+//! webhook, to carry on reading on another device.
+
+use anyhow::{Context, Result, anyhow, bail};
+use regex::Regex;
+use std::time::Duration;
+
+use crate::config::{PushConfig, PushService};
+use crate::internal::models::Story;
+
+const NTFY_URL: &str = "https://ntfy.sh";
+const PUSHOVER_URL: &str = "https://api.pushover.net/1/messages.json";
+
+/// Body posted when the config has no `template`.
+pub fn default_template(service: PushService) -> &'static str {
+    match service {
+        PushService::Ntfy => {
+            r#"{"topic": "{topic}", "title": "{title}", "message": "{url}", "click": "{url}"}"#
+        }
+        PushService::Pushover => {
+            r#"{"token": "{token}", "user": "{user}", "title": "{title}", "message": "{url}", "url": "{url}"}"#
+        }
+        PushService::Webhook => {
+            r#"{"id": {id}, "title": "{title}", "url": "{url}", "hn_url": "{hn_url}", "by": "{by}", "score": {score}}"#
+        }
+    }
+}
+
+/// A push ready to be posted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushRequest {
+    pub url: String,
+    /// JSON body
+    pub body: String,
+}
+
+/// Build the push of `story`. Stories without a link (Ask HN) send their HN
+/// discussion as `{url}`.
+pub fn request(config: &PushConfig, story: &Story) -> Result<PushRequest> {
+    let service = config
+        .service
+        .ok_or_else(|| anyhow!("push.service is not set"))?;
+    let required = |value: &Option<String>, name: &str| {
+        value
+            .clone()
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| anyhow!("push.{} is required for {}", name, service))
+    };
+    let url = match service {
+        PushService::Ntfy => {
+            required(&config.topic, "topic")?;
+            config.url.clone().unwrap_or_else(|| NTFY_URL.to_string())
+        }
+        PushService::Pushover => {
+            required(&config.token, "token")?;
+            required(&config.user, "user")?;
+            config
+                .url
+                .clone()
+                .unwrap_or_else(|| PUSHOVER_URL.to_string())
+        }
+        PushService::Webhook => required(&config.url, "url")?,
+    };
+
+    let hn_url = crate::utils::url::hn_item_url(story.id);
+    let value = |name: &str| -> Option<String> {
+        let value = match name {
+            "title" => story.title.clone().unwrap_or_default(),
+            "url" => story.url.clone().unwrap_or_else(|| hn_url.clone()),
+            "hn_url" => hn_url.clone(),
+            "id" => story.id.to_string(),
+            "by" => story.by.clone().unwrap_or_default(),
+            "score" => story.score.unwrap_or(0).to_string(),
+            "topic" => config.topic.clone().unwrap_or_default(),
+            "token" => config.token.clone().unwrap_or_default(),
+            "user" => config.user.clone().unwrap_or_default(),
+            _ => return None,
+        };
+        Some(json_escape(&value))
+    };
+    let template = config
+        .template
+        .as_deref()
+        .unwrap_or(default_template(service));
+    // One pass, so a title containing "{url}" is not expanded again
+    let body = Regex::new(r"\{(\w+)\}")
+        .expect("valid regex")
+        .replace_all(template, |caps: &regex::Captures| {
+            value(&caps[1]).unwrap_or_else(|| caps[0].to_string())
+        })
+        .to_string();
+    serde_json::from_str::<serde_json::Value>(&body)
+        .context("push.template is not valid JSON once expanded")?;
+    Ok(PushRequest { url, body })
+}
+
+/// `text` as the inside of a JSON string.
+fn json_escape(text: &str) -> String {
+    let quoted = serde_json::to_string(text).unwrap_or_default();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+/// Post the push of `story`. An error status is an error carrying the start of
+/// the response.
+pub async fn send(config: &PushConfig, story: &Story, timeout: Duration) -> Result<()> {
+    let request = request(config, story)?;
+    let client = reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .context("Failed to create HTTP client")?;
+    let mut builder = client
+        .post(&request.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(request.body);
+    for (name, value) in &config.headers {
+        builder = builder.header(name, value);
+    }
+    let response = builder
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach {}", request.url))?;
+    let status = response.status();
+    if !status.is_success() {
+        let text = response.text().await.unwrap_or_default();
+        let detail: String = text
+            .lines()
+            .next()
+            .unwrap_or("")
+            .chars()
+            .take(120)
+            .collect();
+        bail!("{} answered {}: {}", request.url, status, detail);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_each_service_payload() {
+        let story = Story {
+            id: 42,
+            title: Some("Say \"hi\" to {url}".to_string()),
+            url: Some("https://example.com/a".to_string()),
+            by: Some("pg".to_string()),
+            score: Some(100),
+            ..Default::default()
+        };
+        let ntfy = PushConfig {
+            service: Some(PushService::Ntfy),
+            topic: Some("my-reads".to_string()),
+            ..Default::default()
+        };
+        let push = request(&ntfy, &story).unwrap();
+        assert_eq!(push.url, "https://ntfy.sh");
+        let body: serde_json::Value = serde_json::from_str(&push.body).unwrap();
+        assert_eq!(body["topic"], "my-reads");
+        assert_eq!(body["title"], "Say \"hi\" to {url}");
+        assert_eq!(body["click"], "https://example.com/a");
+
+        // Ask HN stories send their discussion; templates may use any field
+        let webhook = PushConfig {
+            service: Some(PushService::Webhook),
+            url: Some("https://hooks.example.com/in".to_string()),
+            template: Some(r#"{"text": "{title} ({score}) {url} {unknown}"}"#.to_string()),
+            ..Default::default()
+        };
+        let ask = Story {
+            url: None,
+            title: Some("Ask HN".to_string()),
+            ..story.clone()
+        };
+        let body: serde_json::Value =
+            serde_json::from_str(&request(&webhook, &ask).unwrap().body).unwrap();
+        assert_eq!(
+            body["text"],
+            "Ask HN (100) https://news.ycombinator.com/item?id=42 {unknown}"
+        );
+
+        let pushover = PushConfig {
+            service: Some(PushService::Pushover),
+            token: Some("app".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            request(&pushover, &story).unwrap_err().to_string(),
+            "push.user is required for Pushover"
+        );
+        let broken = PushConfig {
+            template: Some("{\"text\": {title}}".to_string()),
+            ..webhook
+        };
+        assert!(request(&broken, &story).is_err());
+        assert!(request(&PushConfig::default(), &story).is_err());
+    }
+}
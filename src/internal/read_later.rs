@@ -0,0 +1,284 @@
+//! Read-later queue: stories queued with `R` have their article and comments
+//! downloaded in the background, one at a time, into the persistent cache so they
+//! can be read offline. The queue is kept in `read_later.json`; downloaded stories
+//! are pruned from it after `read_later.keep_days`.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use super::models::Story;
+use crate::api::ApiService;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ItemState {
+    Queued,
+    Downloading,
+    Done,
+    /// The download failed with this error
+    Failed(String),
+}
+
+/// What the download of a story fetched.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Downloaded {
+    /// False for stories without a link, such as Ask HN
+    pub article: bool,
+    pub comments: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueuedStory {
+    pub id: u32,
+    pub title: String,
+    pub url: Option<String>,
+    /// When the story was queued (Unix seconds)
+    pub added_at: i64,
+    pub state: ItemState,
+    /// When the download finished or failed (Unix seconds)
+    #[serde(default)]
+    pub finished_at: Option<i64>,
+    #[serde(default)]
+    pub downloaded: Downloaded,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReadLater {
+    pub items: Vec<QueuedStory>,
+    #[serde(skip)]
+    file_path: Option<PathBuf>,
+}
+
+impl ReadLater {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load_or_create() -> Result<Self> {
+        let config_dir = dirs::config_dir()
+            .context("Could not find config directory")?
+            .join("tui-hn-app");
+        fs::create_dir_all(&config_dir).with_context(|| {
+            format!("Failed to create config directory {}", config_dir.display())
+        })?;
+
+        let file_path = config_dir.join("read_later.json");
+        let mut queue: ReadLater = match file_path.exists() {
+            true => {
+                let content =
+                    fs::read_to_string(&file_path).context("Failed to read read-later queue")?;
+                serde_json::from_str(&content).context("Failed to parse read-later queue")?
+            }
+            false => Self::new(),
+        };
+        // Downloads cut short by quitting start over
+        for item in &mut queue.items {
+            if item.state == ItemState::Downloading {
+                item.state = ItemState::Queued;
+            }
+        }
+        queue.file_path = Some(file_path);
+        Ok(queue)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = &self.file_path {
+            let content = serde_json::to_string_pretty(self)
+                .context("Failed to serialize read-later queue")?;
+            fs::write(path, content).context("Failed to write read-later queue")?;
+        }
+        Ok(())
+    }
+
+    fn get_mut(&mut self, id: u32) -> Option<&mut QueuedStory> {
+        self.items.iter_mut().find(|item| item.id == id)
+    }
+
+    /// Queue `story` for download; false if it is in the queue already.
+    pub fn enqueue(&mut self, story: &Story, now: i64) -> bool {
+        if self.items.iter().any(|item| item.id == story.id) {
+            return false;
+        }
+        self.items.push(QueuedStory {
+            id: story.id,
+            title: story.title.clone().unwrap_or_default(),
+            url: story.url.clone(),
+            added_at: now,
+            state: ItemState::Queued,
+            finished_at: None,
+            downloaded: Downloaded::default(),
+        });
+        true
+    }
+
+    /// The story to download next, unless one is being downloaded.
+    pub fn next_to_download(&self) -> Option<u32> {
+        if self
+            .items
+            .iter()
+            .any(|item| item.state == ItemState::Downloading)
+        {
+            return None;
+        }
+        self.items
+            .iter()
+            .find(|item| item.state == ItemState::Queued)
+            .map(|item| item.id)
+    }
+
+    pub fn start(&mut self, id: u32) {
+        if let Some(item) = self.get_mut(id) {
+            item.state = ItemState::Downloading;
+        }
+    }
+
+    /// Record how the download of `id` went.
+    pub fn finish(&mut self, id: u32, result: Result<Downloaded, String>, now: i64) {
+        let Some(item) = self.get_mut(id) else {
+            return;
+        };
+        item.finished_at = Some(now);
+        match result {
+            Ok(downloaded) => {
+                item.state = ItemState::Done;
+                item.downloaded = downloaded;
+            }
+            Err(e) => item.state = ItemState::Failed(e),
+        }
+    }
+
+    /// Queue a failed download again; false if `id` had not failed.
+    pub fn retry(&mut self, id: u32) -> bool {
+        match self.get_mut(id) {
+            Some(item) if matches!(item.state, ItemState::Failed(_)) => {
+                item.state = ItemState::Queued;
+                item.finished_at = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Queue every failed download again, returning how many there were.
+    pub fn retry_failed(&mut self) -> usize {
+        let ids: Vec<u32> = self.items.iter().map(|item| item.id).collect();
+        ids.into_iter().filter(|&id| self.retry(id)).count()
+    }
+
+    pub fn remove(&mut self, id: u32) -> bool {
+        let before = self.items.len();
+        self.items.retain(|item| item.id != id);
+        self.items.len() != before
+    }
+
+    /// Drop stories downloaded more than `keep_days` days before `now` (never with
+    /// 0), returning how many went.
+    pub fn prune(&mut self, now: i64, keep_days: u64) -> usize {
+        if keep_days == 0 {
+            return 0;
+        }
+        let keep_secs = (keep_days * 24 * 60 * 60) as i64;
+        let before = self.items.len();
+        self.items.retain(|item| {
+            item.state != ItemState::Done
+                || item
+                    .finished_at
+                    .is_none_or(|finished| now - finished < keep_secs)
+        });
+        before - self.items.len()
+    }
+
+    /// `(downloaded, in the queue)`
+    pub fn progress(&self) -> (usize, usize) {
+        let done = self
+            .items
+            .iter()
+            .filter(|item| item.state == ItemState::Done)
+            .count();
+        (done, self.items.len())
+    }
+}
+
+/// Download story `id` with its article and `comment_depth` levels of comments into
+/// the cache, and save the cache.
+pub async fn download(api: &ApiService, id: u32, comment_depth: usize) -> Result<Downloaded> {
+    let story = api.fetch_story_content(id).await.context("story")?;
+    let article = match &story.url {
+        Some(url) => {
+            api.fetch_article_content(url, None)
+                .await
+                .context("article")?;
+            true
+        }
+        None => false,
+    };
+    let comments = match &story.kids {
+        Some(kids) if comment_depth > 0 => api
+            .fetch_comment_tree(kids.clone(), comment_depth, None)
+            .await
+            .context("comments")?
+            .len(),
+        _ => 0,
+    };
+    api.persist_caches().context("saving the cache")?;
+    Ok(Downloaded { article, comments })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn story(id: u32) -> Story {
+        Story {
+            id,
+            title: Some(format!("Story {}", id)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn downloads_one_at_a_time_and_retries_failures() {
+        let mut queue = ReadLater::new();
+        assert!(queue.enqueue(&story(1), 100));
+        assert!(queue.enqueue(&story(2), 100));
+        assert!(!queue.enqueue(&story(1), 200));
+
+        assert_eq!(queue.next_to_download(), Some(1));
+        queue.start(1);
+        assert_eq!(queue.next_to_download(), None);
+        queue.finish(1, Err("timeout".to_string()), 150);
+        assert_eq!(queue.next_to_download(), Some(2));
+        queue.start(2);
+        let downloaded = Downloaded {
+            article: true,
+            comments: 12,
+        };
+        queue.finish(2, Ok(downloaded), 160);
+        assert_eq!(queue.progress(), (1, 2));
+
+        assert!(!queue.retry(2));
+        assert_eq!(queue.retry_failed(), 1);
+        assert_eq!(queue.items[0].state, ItemState::Queued);
+        assert_eq!(queue.next_to_download(), Some(1));
+    }
+
+    #[test]
+    fn prunes_only_old_downloads() {
+        let day = 24 * 60 * 60;
+        let mut queue = ReadLater::new();
+        for id in 1..=3 {
+            queue.enqueue(&story(id), 0);
+        }
+        queue.finish(1, Ok(Downloaded::default()), 0);
+        queue.finish(2, Ok(Downloaded::default()), 6 * day);
+        queue.finish(3, Err("offline".to_string()), 0);
+
+        assert_eq!(queue.prune(7 * day, 0), 0);
+        assert_eq!(queue.prune(7 * day, 7), 1);
+        let ids: Vec<u32> = queue.items.iter().map(|item| item.id).collect();
+        assert_eq!(ids, vec![2, 3]);
+        assert!(queue.remove(3));
+        assert!(!queue.remove(3));
+    }
+}
@@ -0,0 +1,271 @@
+//! Full-text index of the articles and comment threads opened in the app, kept on
+//! disk with tantivy when `reading_index.enabled` is set. "Search my reading"
+//! queries it and shows snippets around the matches.
+
+use anyhow::{Context, Result};
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Mutex;
+use tantivy::collector::TopDocs;
+use tantivy::directory::MmapDirectory;
+use tantivy::query::QueryParser;
+use tantivy::schema::{Field, STORED, STRING, Schema, TEXT, Value};
+use tantivy::snippet::SnippetGenerator;
+use tantivy::{Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument, Term, doc};
+
+use super::models::{Article, ArticleElement, CommentRow};
+
+/// Memory budget of the index writer; tantivy's minimum is 15 MB.
+const WRITER_MEMORY: usize = 15_000_000;
+/// Length of the snippets shown with search results.
+const SNIPPET_CHARS: usize = 160;
+
+/// What part of a story a document holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemKind {
+    Article,
+    Comments,
+}
+
+impl ItemKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ItemKind::Article => "article",
+            ItemKind::Comments => "comments",
+        }
+    }
+
+    fn parse(text: &str) -> Self {
+        match text {
+            "article" => ItemKind::Article,
+            _ => ItemKind::Comments,
+        }
+    }
+}
+
+/// A search result.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hit {
+    pub story_id: u32,
+    pub kind: ItemKind,
+    pub title: String,
+    /// Text around the best matches
+    pub snippet: String,
+    /// Byte ranges of the matched words in `snippet`
+    pub highlighted: Vec<Range<usize>>,
+}
+
+struct Fields {
+    /// "<story id>:<kind>", so reopening an item replaces its document
+    key: Field,
+    story_id: Field,
+    kind: Field,
+    title: Field,
+    body: Field,
+}
+
+pub struct ReadingIndex {
+    index: Index,
+    reader: IndexReader,
+    writer: Mutex<IndexWriter>,
+    fields: Fields,
+}
+
+impl ReadingIndex {
+    fn schema() -> (Schema, Fields) {
+        let mut builder = Schema::builder();
+        let fields = Fields {
+            key: builder.add_text_field("key", STRING),
+            story_id: builder.add_u64_field("story_id", STORED),
+            kind: builder.add_text_field("kind", STRING | STORED),
+            title: builder.add_text_field("title", TEXT | STORED),
+            body: builder.add_text_field("body", TEXT | STORED),
+        };
+        (builder.build(), fields)
+    }
+
+    /// Open the index in `dir`, creating it if needed. Fails if another instance
+    /// of the app holds the index.
+    pub fn open(dir: &Path) -> Result<Self> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create index directory {}", dir.display()))?;
+        let (schema, fields) = Self::schema();
+        let directory = MmapDirectory::open(dir).context("Failed to open index directory")?;
+        let index = Index::open_or_create(directory, schema).context("Failed to open index")?;
+        Self::with_index(index, fields)
+    }
+
+    /// An index kept in memory only.
+    #[cfg(test)]
+    pub fn in_memory() -> Result<Self> {
+        let (schema, fields) = Self::schema();
+        Self::with_index(Index::create_in_ram(schema), fields)
+    }
+
+    fn with_index(index: Index, fields: Fields) -> Result<Self> {
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()
+            .context("Failed to open index reader")?;
+        let writer = index
+            .writer_with_num_threads(1, WRITER_MEMORY)
+            .context("Failed to open index writer")?;
+        Ok(Self {
+            index,
+            reader,
+            writer: Mutex::new(writer),
+            fields,
+        })
+    }
+
+    /// Index the text of a story's article or comments, replacing what was indexed
+    /// for it before.
+    pub fn add(&self, story_id: u32, kind: ItemKind, title: &str, body: &str) -> Result<()> {
+        let key = format!("{}:{}", story_id, kind.as_str());
+        let f = &self.fields;
+        let mut writer = self
+            .writer
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Index writer lock poisoned"))?;
+        writer.delete_term(Term::from_field_text(f.key, &key));
+        writer.add_document(doc!(
+            f.key => key,
+            f.story_id => story_id as u64,
+            f.kind => kind.as_str(),
+            f.title => title,
+            f.body => body,
+        ))?;
+        writer.commit().context("Failed to commit to the index")?;
+        self.reader.reload()?;
+        Ok(())
+    }
+
+    /// Best matches of `query` in titles and text, with snippets. The query syntax is
+    /// tantivy's: words, "phrases", `+required`, `-excluded`, `title:word`.
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<Hit>> {
+        if query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        let f = &self.fields;
+        let searcher = self.reader.searcher();
+        let parser = QueryParser::for_index(&self.index, vec![f.title, f.body]);
+        // Unbalanced quotes and the like are ignored rather than reported while typing
+        let (query, _errors) = parser.parse_query_lenient(query);
+        let mut snippets = SnippetGenerator::create(&searcher, &*query, f.body)?;
+        snippets.set_max_num_chars(SNIPPET_CHARS);
+
+        searcher
+            .search(&query, &TopDocs::with_limit(limit))?
+            .into_iter()
+            .map(|(_, address)| {
+                let doc: TantivyDocument = searcher.doc(address)?;
+                let text = |field| {
+                    doc.get_first(field)
+                        .and_then(|value| value.as_str())
+                        .unwrap_or_default()
+                        .to_string()
+                };
+                let snippet = snippets.snippet_from_doc(&doc);
+                Ok(Hit {
+                    story_id: doc
+                        .get_first(f.story_id)
+                        .and_then(|value| value.as_u64())
+                        .unwrap_or_default() as u32,
+                    kind: ItemKind::parse(&text(f.kind)),
+                    title: text(f.title),
+                    snippet: snippet.fragment().to_string(),
+                    highlighted: snippet.highlighted().to_vec(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Plain text of an article for indexing; images are left out.
+pub fn article_text(article: &Article) -> String {
+    let mut parts = Vec::new();
+    for element in &article.elements {
+        match element {
+            ArticleElement::Paragraph(text)
+            | ArticleElement::Heading(_, text)
+            | ArticleElement::Quote(text) => parts.push(text.clone()),
+            ArticleElement::CodeBlock { code, .. } => parts.push(code.clone()),
+            ArticleElement::List(items) => parts.extend(items.iter().cloned()),
+            ArticleElement::Table(rows) => parts.extend(rows.iter().map(|row| row.join(" "))),
+            ArticleElement::Image(_) => {}
+        }
+    }
+    parts.join("\n")
+}
+
+/// Plain text of a comment thread for indexing, one "author: text" per comment.
+pub fn thread_text(rows: &[CommentRow]) -> String {
+    rows.iter()
+        .filter(|row| !row.comment.deleted)
+        .filter_map(|row| {
+            let text = crate::utils::html::extract_text_from_html(row.comment.text.as_deref()?);
+            Some(format!(
+                "{}: {}",
+                row.comment.by.as_deref().unwrap_or("unknown"),
+                text.split_whitespace().collect::<Vec<_>>().join(" ")
+            ))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_items_with_snippets_and_replaces_them() {
+        let index = ReadingIndex::in_memory().unwrap();
+        index
+            .add(
+                1,
+                ItemKind::Article,
+                "Ownership in Rust",
+                "The borrow checker enforces ownership rules at compile time.",
+            )
+            .unwrap();
+        index
+            .add(
+                1,
+                ItemKind::Comments,
+                "Ownership in Rust",
+                "alice: garbage collection is simpler",
+            )
+            .unwrap();
+        index
+            .add(
+                2,
+                ItemKind::Comments,
+                "Go 2",
+                "bob: the borrow checker is overrated",
+            )
+            .unwrap();
+
+        let hits = index.search("borrow checker", 10).unwrap();
+        let mut found: Vec<(u32, ItemKind)> = hits.iter().map(|h| (h.story_id, h.kind)).collect();
+        found.sort_by_key(|(id, _)| *id);
+        assert_eq!(found, vec![(1, ItemKind::Article), (2, ItemKind::Comments)]);
+        let hit = hits.iter().find(|h| h.story_id == 2).unwrap();
+        assert_eq!(hit.title, "Go 2");
+        let words: Vec<&str> = hit
+            .highlighted
+            .iter()
+            .map(|r| &hit.snippet[r.clone()])
+            .collect();
+        assert_eq!(words, vec!["borrow", "checker"]);
+
+        // Titles are searched too, and re-adding an item replaces it
+        assert_eq!(index.search("title:ownership", 10).unwrap().len(), 2);
+        index
+            .add(2, ItemKind::Comments, "Go 2", "bob: generics at last")
+            .unwrap();
+        assert_eq!(index.search("overrated", 10).unwrap(), Vec::new());
+        assert!(index.search("  ", 10).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,223 @@
+//! Named reading queues that stories can be added to, reordered and ticked off.
+
+use anyhow::{Context, Result};
+use jiff::Zoned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use super::models::Story;
+
+/// Name of the list created when none exist yet.
+pub const DEFAULT_LIST_NAME: &str = "Reading";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadingListItem {
+    pub id: u32,
+    pub title: String,
+    pub url: Option<String>,
+    pub by: Option<String>,
+    pub score: Option<u32>,
+    pub descendants: Option<u32>,
+    pub added_at: Zoned,
+    #[serde(default)]
+    pub done: bool,
+}
+
+impl ReadingListItem {
+    /// Story used to render the item with the normal list row layout.
+    pub fn to_story(&self) -> Story {
+        Story {
+            id: self.id,
+            title: Some(self.title.clone()),
+            url: self.url.clone(),
+            by: self.by.clone(),
+            score: self.score,
+            time: Some(self.added_at.timestamp().as_second()),
+            descendants: self.descendants,
+            kids: None,
+            parts: None,
+            item_type: None,
+            text: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadingList {
+    pub name: String,
+    pub items: Vec<ReadingListItem>,
+}
+
+impl ReadingList {
+    pub fn contains(&self, id: u32) -> bool {
+        self.items.iter().any(|item| item.id == id)
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.items.iter().filter(|item| !item.done).count()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ReadingLists {
+    pub lists: Vec<ReadingList>,
+    #[serde(skip)]
+    file_path: Option<PathBuf>,
+}
+
+impl ReadingLists {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load_or_create() -> Result<Self> {
+        let config_dir = dirs::config_dir()
+            .context("Could not find config directory")?
+            .join("tui-hn-app");
+        fs::create_dir_all(&config_dir).with_context(|| {
+            format!("Failed to create config directory {}", config_dir.display())
+        })?;
+
+        let file_path = config_dir.join("reading_lists.json");
+        let mut lists: ReadingLists = match file_path.exists() {
+            true => {
+                let content =
+                    fs::read_to_string(&file_path).context("Failed to read reading lists file")?;
+                serde_json::from_str(&content).context("Failed to parse reading lists file")?
+            }
+            false => Self::new(),
+        };
+        lists.file_path = Some(file_path);
+        Ok(lists)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = &self.file_path {
+            let content =
+                serde_json::to_string_pretty(self).context("Failed to serialize reading lists")?;
+            fs::write(path, content).context("Failed to write reading lists file")?;
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, list: usize) -> Option<&ReadingList> {
+        self.lists.get(list)
+    }
+
+    /// Index of the list called `name`, creating it if needed.
+    pub fn find_or_create(&mut self, name: &str) -> usize {
+        let name = name.trim();
+        match self
+            .lists
+            .iter()
+            .position(|l| l.name.eq_ignore_ascii_case(name))
+        {
+            Some(idx) => idx,
+            None => {
+                self.lists.push(ReadingList {
+                    name: name.to_string(),
+                    items: Vec::new(),
+                });
+                self.lists.len() - 1
+            }
+        }
+    }
+
+    /// Append the story to a list. Returns false if it was already queued there.
+    pub fn add(&mut self, list: usize, story: &Story) -> bool {
+        match self.lists.get_mut(list) {
+            Some(l) if !l.contains(story.id) => {
+                l.items.push(ReadingListItem {
+                    id: story.id,
+                    title: story.title.clone().unwrap_or_default(),
+                    url: story.url.clone(),
+                    by: story.by.clone(),
+                    score: story.score,
+                    descendants: story.descendants,
+                    added_at: Zoned::now(),
+                    done: false,
+                });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn remove(&mut self, list: usize, index: usize) -> Option<ReadingListItem> {
+        let items = &mut self.lists.get_mut(list)?.items;
+        (index < items.len()).then(|| items.remove(index))
+    }
+
+    /// Move an item one position up (`up == true`) or down; returns its new index.
+    pub fn move_item(&mut self, list: usize, index: usize, up: bool) -> Option<usize> {
+        let items = &mut self.lists.get_mut(list)?.items;
+        let target = match up {
+            true => index.checked_sub(1)?,
+            false => index + 1,
+        };
+        match target < items.len() && index < items.len() {
+            true => {
+                items.swap(index, target);
+                Some(target)
+            }
+            false => None,
+        }
+    }
+
+    /// Flip the done flag; returns the new state.
+    pub fn toggle_done(&mut self, list: usize, index: usize) -> Option<bool> {
+        let item = self.lists.get_mut(list)?.items.get_mut(index)?;
+        item.done = !item.done;
+        Some(item.done)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn story(id: u32) -> Story {
+        Story {
+            id,
+            title: Some(format!("Story {}", id)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn add_skips_duplicates_per_list() {
+        let mut lists = ReadingLists::new();
+        let reading = lists.find_or_create(DEFAULT_LIST_NAME);
+        let papers = lists.find_or_create("Papers");
+        assert_eq!(lists.find_or_create("reading"), reading);
+
+        assert!(lists.add(reading, &story(1)));
+        assert!(!lists.add(reading, &story(1)));
+        assert!(lists.add(papers, &story(1)));
+        assert_eq!(lists.lists[reading].items.len(), 1);
+        assert!(!lists.add(7, &story(2)));
+    }
+
+    #[test]
+    fn reorder_remove_and_mark_done() {
+        let mut lists = ReadingLists::new();
+        let list = lists.find_or_create(DEFAULT_LIST_NAME);
+        for id in 1..=3 {
+            lists.add(list, &story(id));
+        }
+
+        assert_eq!(lists.move_item(list, 2, true), Some(1));
+        assert_eq!(lists.move_item(list, 0, true), None);
+        assert_eq!(lists.move_item(list, 2, false), None);
+        let ids: Vec<u32> = lists.lists[list].items.iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec![1, 3, 2]);
+
+        assert_eq!(lists.toggle_done(list, 0), Some(true));
+        assert_eq!(lists.lists[list].remaining(), 2);
+
+        assert_eq!(lists.remove(list, 1).map(|i| i.id), Some(3));
+        assert!(lists.remove(list, 5).is_none());
+        assert_eq!(lists.lists[list].items.len(), 2);
+    }
+}
@@ -0,0 +1,381 @@
+//! User scripts written in Rhai, loaded from the scripts directory at startup.
+//!
+//! Scripts define optional hooks (`on_start()`, `on_story(story)`, `on_open(story)`)
+//! and call back into the app through a few functions: `notify(msg)`,
+//! `dispatch(action)`, `set_status(name, value)`, `hide(id)`, `tag(id, tag)` and
+//! `current_story()`. Rhai has no file or network access; each hook call also runs
+//! under an operation budget, and a script that fails is disabled on its own.
+
+use rhai::{AST, CallFnOptions, Dynamic, Engine, Map, Scope};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use crate::config::ScriptingConfig;
+use crate::internal::models::Story;
+use crate::internal::ui::app::Action;
+
+/// What scripts asked for during hook calls, applied by the app afterwards.
+#[derive(Debug, Default)]
+pub struct ScriptEffects {
+    pub notifications: Vec<String>,
+    pub actions: Vec<Action>,
+    /// `(story id, tag)` pairs to bookmark
+    pub tags: Vec<(u32, String)>,
+    /// Load and runtime errors, one per failing script
+    pub errors: Vec<String>,
+}
+
+/// State the registered functions write to.
+#[derive(Default)]
+struct Shared {
+    effects: ScriptEffects,
+    hidden: HashSet<u32>,
+    status: BTreeMap<String, String>,
+    current_story: Option<Map>,
+}
+
+struct Script {
+    name: String,
+    ast: AST,
+    /// Globals of the script, kept between hook calls
+    scope: Scope<'static>,
+    failed: bool,
+}
+
+pub struct ScriptHost {
+    engine: Engine,
+    scripts: Vec<Script>,
+    shared: Arc<Mutex<Shared>>,
+}
+
+impl Default for ScriptHost {
+    fn default() -> Self {
+        Self::new(&ScriptingConfig::default())
+    }
+}
+
+impl ScriptHost {
+    /// A host without scripts, with the sandbox limits from `config`.
+    pub fn new(config: &ScriptingConfig) -> Self {
+        let shared = Arc::new(Mutex::new(Shared::default()));
+        let mut engine = Engine::new();
+        engine.set_max_operations(config.max_operations.max(1));
+        engine.set_max_call_levels(32);
+        engine.set_max_expr_depths(64, 32);
+        engine.set_max_string_size(64 * 1024);
+        engine.set_max_array_size(10_000);
+        engine.set_max_map_size(10_000);
+        engine.disable_symbol("eval");
+        // Rhai prints to stdout and stderr by default, which would draw over the UI
+        engine.on_print(|text| tracing::info!(target: "script", "{}", text));
+        engine.on_debug(|text, source, pos| {
+            tracing::debug!(target: "script", source = source.unwrap_or_default(), %pos, "{}", text)
+        });
+        register_api(&mut engine, &shared);
+        Self {
+            engine,
+            scripts: Vec::new(),
+            shared,
+        }
+    }
+
+    /// Compile and run every `*.rhai` file in the configured directory, in name order.
+    /// Scripts that fail to load are reported through `take_effects`.
+    pub fn load(config: &ScriptingConfig) -> Self {
+        let mut host = Self::new(config);
+        if let (true, Some(dir)) = (config.enabled, config.directory_path()) {
+            host.load_dir(&dir);
+        }
+        host
+    }
+
+    fn load_dir(&mut self, dir: &Path) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        let mut paths: Vec<_> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "rhai"))
+            .collect();
+        paths.sort();
+        for path in paths {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            match std::fs::read_to_string(&path) {
+                Ok(source) => self.add_script(&name, &source),
+                Err(e) => self.report(&name, e),
+            }
+        }
+    }
+
+    /// Compile `source` and run its top level once.
+    pub fn add_script(&mut self, name: &str, source: &str) {
+        let ast = match self.engine.compile(source) {
+            Ok(ast) => ast,
+            Err(e) => return self.report(name, e),
+        };
+        let mut scope = Scope::new();
+        match self.engine.run_ast_with_scope(&mut scope, &ast) {
+            Ok(()) => {
+                tracing::info!(script = name, "Loaded script");
+                self.scripts.push(Script {
+                    name: name.to_string(),
+                    ast,
+                    scope,
+                    failed: false,
+                });
+            }
+            Err(e) => self.report(name, e),
+        }
+    }
+
+    fn report(&self, name: &str, error: impl std::fmt::Display) {
+        tracing::warn!(script = name, %error, "Script failed");
+        if let Ok(mut shared) = self.shared.lock() {
+            shared
+                .effects
+                .errors
+                .push(format!("Script {} failed: {}", name, error));
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scripts.is_empty()
+    }
+
+    /// Call `hook` in every script that defines it. A script whose hook errors is
+    /// disabled for the rest of the session; the others keep running.
+    fn call_hook(&mut self, hook: &str, current: Option<&Story>, args: impl Fn() -> Vec<Dynamic>) {
+        if self.scripts.is_empty() {
+            return;
+        }
+        if let Ok(mut shared) = self.shared.lock() {
+            shared.current_story = current.map(story_map);
+        }
+        let mut failures = Vec::new();
+        for script in self.scripts.iter_mut().filter(|s| !s.failed) {
+            if !script.ast.iter_functions().any(|f| f.name == hook) {
+                continue;
+            }
+            let result = self.engine.call_fn_with_options::<Dynamic>(
+                CallFnOptions::new().eval_ast(false).rewind_scope(true),
+                &mut script.scope,
+                &script.ast,
+                hook,
+                args(),
+            );
+            if let Err(e) = result {
+                script.failed = true;
+                failures.push((script.name.clone(), e));
+            }
+        }
+        for (name, e) in failures {
+            self.report(&name, e);
+        }
+    }
+
+    pub fn on_start(&mut self, current: Option<&Story>) {
+        self.call_hook("on_start", current, Vec::new);
+    }
+
+    /// Newly loaded stories, one call each.
+    pub fn on_story(&mut self, story: &Story) {
+        self.call_hook("on_story", Some(story), || {
+            vec![Dynamic::from_map(story_map(story))]
+        });
+    }
+
+    /// A story was opened.
+    pub fn on_open(&mut self, story: &Story) {
+        self.call_hook("on_open", Some(story), || {
+            vec![Dynamic::from_map(story_map(story))]
+        });
+    }
+
+    /// Requests made by scripts since the last call.
+    pub fn take_effects(&mut self) -> ScriptEffects {
+        self.shared
+            .lock()
+            .map(|mut shared| std::mem::take(&mut shared.effects))
+            .unwrap_or_default()
+    }
+
+    /// Whether a script hid this story with `hide(id)`.
+    pub fn is_hidden(&self, id: u32) -> bool {
+        !self.is_empty()
+            && self
+                .shared
+                .lock()
+                .is_ok_and(|shared| shared.hidden.contains(&id))
+    }
+
+    /// Values set with `set_status(name, value)`, by name.
+    pub fn status_tokens(&self) -> BTreeMap<String, String> {
+        self.shared
+            .lock()
+            .map(|shared| shared.status.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// The story as a Rhai object map; missing fields are `()`.
+fn story_map(story: &Story) -> Map {
+    let text = |value: &Option<String>| value.clone().map_or(Dynamic::UNIT, Dynamic::from);
+    let number = |value: Option<u32>| value.map_or(Dynamic::UNIT, |n| Dynamic::from(n as i64));
+    let domain = story
+        .url
+        .as_deref()
+        .and_then(crate::utils::url::extract_domain);
+    let mut map = Map::new();
+    map.insert("id".into(), Dynamic::from(story.id as i64));
+    map.insert("title".into(), text(&story.title));
+    map.insert("url".into(), text(&story.url));
+    map.insert("domain".into(), text(&domain));
+    map.insert("by".into(), text(&story.by));
+    map.insert("score".into(), number(story.score));
+    map.insert("comments".into(), number(story.descendants));
+    map.insert(
+        "time".into(),
+        story.time.map_or(Dynamic::UNIT, Dynamic::from),
+    );
+    map
+}
+
+fn register_api(engine: &mut Engine, shared: &Arc<Mutex<Shared>>) {
+    let with = |shared: &Arc<Mutex<Shared>>| {
+        let shared = shared.clone();
+        move |f: &mut dyn FnMut(&mut Shared)| {
+            if let Ok(mut shared) = shared.lock() {
+                f(&mut shared);
+            }
+        }
+    };
+
+    let update = with(shared);
+    engine.register_fn("notify", move |message: Dynamic| {
+        update(&mut |s| s.effects.notifications.push(message.to_string()));
+    });
+
+    let update = with(shared);
+    engine.register_fn("dispatch", move |name: &str| {
+        // Same names as in the keybinding config
+        let action = serde_json::from_value::<Action>(serde_json::Value::String(name.into()));
+        update(&mut |s| match &action {
+            Ok(action) => s.effects.actions.push(action.clone()),
+            Err(_) => s
+                .effects
+                .errors
+                .push(format!("Script dispatched unknown action {:?}", name)),
+        });
+    });
+
+    let update = with(shared);
+    engine.register_fn("set_status", move |name: &str, value: Dynamic| {
+        update(&mut |s| match value.is_unit() {
+            true => {
+                s.status.remove(name);
+            }
+            false => {
+                s.status.insert(name.to_string(), value.to_string());
+            }
+        });
+    });
+
+    let update = with(shared);
+    engine.register_fn("hide", move |id: i64| {
+        update(&mut |s| {
+            s.hidden.insert(id as u32);
+        });
+    });
+
+    let update = with(shared);
+    engine.register_fn("tag", move |id: i64, tag: &str| {
+        update(&mut |s| s.effects.tags.push((id as u32, tag.to_string())));
+    });
+
+    let read = shared.clone();
+    engine.register_fn("current_story", move || -> Dynamic {
+        read.lock()
+            .ok()
+            .and_then(|s| s.current_story.clone())
+            .map_or(Dynamic::UNIT, Dynamic::from_map)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn story(id: u32, title: &str, score: u32) -> Story {
+        Story {
+            id,
+            title: Some(title.to_string()),
+            url: Some("https://rust-lang.org/news".to_string()),
+            score: Some(score),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn hooks_tag_hide_and_report() {
+        let mut host = ScriptHost::new(&ScriptingConfig::default());
+        host.add_script(
+            "rules.rhai",
+            r#"
+            fn on_story(story) {
+                if story.domain == "rust-lang.org" { tag(story.id, "rust"); }
+                if story.score < 10 { hide(story.id); }
+            }
+            fn on_open(story) {
+                notify("opened " + current_story().title);
+                set_status("last", story.id);
+                dispatch("ToggleHelp");
+            }
+            "#,
+        );
+
+        host.on_story(&story(1, "Rust 2.0", 5));
+        host.on_open(&story(2, "Other", 100));
+        assert!(host.is_hidden(1));
+        assert!(!host.is_hidden(2));
+        assert_eq!(
+            host.status_tokens().get("last").map(String::as_str),
+            Some("2")
+        );
+
+        let effects = host.take_effects();
+        assert_eq!(effects.tags, vec![(1, "rust".to_string())]);
+        assert_eq!(effects.notifications, vec!["opened Other".to_string()]);
+        assert!(matches!(effects.actions[..], [Action::ToggleHelp]));
+        assert!(effects.errors.is_empty());
+    }
+
+    #[test]
+    fn failing_scripts_are_isolated_and_bounded() {
+        let config = ScriptingConfig {
+            max_operations: 1_000,
+            ..Default::default()
+        };
+        let mut host = ScriptHost::new(&config);
+        host.add_script("broken.rhai", "let x = ;");
+        host.add_script("spin.rhai", "fn on_story(story) { loop { } }");
+        host.add_script("ok.rhai", "fn on_story(story) { tag(story.id, \"ok\"); }");
+        host.add_script("sneaky.rhai", "fn on_start() { eval(\"1\") }");
+
+        host.on_story(&story(1, "a", 1));
+        host.on_story(&story(2, "b", 1));
+        let effects = host.take_effects();
+        // The runaway loop is stopped once and disabled; the other script keeps going
+        assert_eq!(effects.tags.len(), 2);
+        let failed: Vec<_> = effects
+            .errors
+            .iter()
+            .filter_map(|e| e.split_whitespace().nth(1))
+            .collect();
+        assert_eq!(failed, vec!["broken.rhai", "sneaky.rhai", "spin.rhai"]);
+    }
+}
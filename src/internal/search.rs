@@ -1,9 +1,12 @@
 use anyhow::{Context, Result};
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::ops::Range;
 use std::path::PathBuf;
 
+use super::models::Story;
+
 /// Search mode - what to search in
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SearchMode {
@@ -59,7 +62,7 @@ pub struct SearchQuery {
     pub query: String,
     pub mode: SearchMode,
     pub search_type: SearchType,
-    #[allow(dead_code)]
+    /// The query as a regex; literal queries are escaped and case-insensitive
     compiled_regex: Option<Regex>,
     pub regex_error: Option<String>,
 }
@@ -71,7 +74,13 @@ impl SearchQuery {
                 Ok(re) => (Some(re), None),
                 Err(e) => (None, Some(format!("Regex error: {}", e))),
             },
-            SearchType::Literal => (None, None),
+            SearchType::Literal => (
+                RegexBuilder::new(&regex::escape(&query))
+                    .case_insensitive(true)
+                    .build()
+                    .ok(),
+                None,
+            ),
         };
 
         Self {
@@ -96,6 +105,18 @@ impl SearchQuery {
             },
         }
     }
+
+    /// Byte ranges of the matches in `text`, for highlighting them.
+    pub fn find_ranges(&self, text: &str) -> Vec<Range<usize>> {
+        match (&self.compiled_regex, self.is_empty()) {
+            (Some(re), false) => re
+                .find_iter(text)
+                .map(|m| m.range())
+                .filter(|range| !range.is_empty())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
 }
 
 impl Default for SearchQuery {
@@ -110,6 +131,38 @@ impl Default for SearchQuery {
     }
 }
 
+/// Return `(original_index, story)` pairs for the stories that match `query`
+/// and are not excluded by `is_hidden`, preserving the input order.
+/// `in_comments` tells whether a story's comments match, for the `Comments` and
+/// `Title+Comments` modes.
+pub fn filter_stories<'a>(
+    stories: &'a [Story],
+    query: &SearchQuery,
+    is_hidden: impl Fn(&Story) -> bool,
+    in_comments: impl Fn(&Story) -> bool,
+) -> Vec<(usize, &'a Story)> {
+    stories
+        .iter()
+        .enumerate()
+        .filter(|(_, story)| !is_hidden(story))
+        .filter(|(_, story)| match query.is_empty() {
+            true => true,
+            false => {
+                let title_match = story
+                    .title
+                    .as_ref()
+                    .map(|t| query.matches(t))
+                    .unwrap_or(false);
+                match query.mode {
+                    SearchMode::Title => title_match,
+                    SearchMode::TitleAndComments => title_match || in_comments(story),
+                    SearchMode::Comments => in_comments(story),
+                }
+            }
+        })
+        .collect()
+}
+
 /// Search history - tracks recent searches
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SearchHistory {
@@ -237,6 +290,40 @@ mod tests {
         assert!(!query.matches("anything"));
     }
 
+    #[test]
+    fn test_find_ranges_and_comment_modes() {
+        let literal = SearchQuery::new("rust".to_string(), SearchMode::Title, SearchType::Literal);
+        assert_eq!(
+            literal.find_ranges("Rust, trust and (rust)"),
+            vec![0..4, 7..11, 17..21]
+        );
+        let regex = SearchQuery::new("a*".to_string(), SearchMode::Title, SearchType::Regex);
+        assert_eq!(regex.find_ranges("baab"), vec![1..3]);
+
+        let stories = vec![
+            Story {
+                id: 1,
+                title: Some("Rust 2.0".to_string()),
+                ..Default::default()
+            },
+            Story {
+                id: 2,
+                title: Some("Other".to_string()),
+                ..Default::default()
+            },
+        ];
+        let ids = |mode| -> Vec<u32> {
+            let query = SearchQuery::new("rust".to_string(), mode, SearchType::Literal);
+            filter_stories(&stories, &query, |_| false, |s| s.id == 2)
+                .iter()
+                .map(|(_, s)| s.id)
+                .collect()
+        };
+        assert_eq!(ids(SearchMode::Title), vec![1]);
+        assert_eq!(ids(SearchMode::Comments), vec![2]);
+        assert_eq!(ids(SearchMode::TitleAndComments), vec![1, 2]);
+    }
+
     #[test]
     fn test_search_history_add() {
         let mut history = SearchHistory::new(5);
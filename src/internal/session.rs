@@ -0,0 +1,86 @@
+//! Where the last session left off, saved to `session.json` on quit and offered
+//! for restoring on the next launch when `restore_session` is on.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::api::StoryListType;
+use crate::internal::models::Story;
+use crate::internal::search::{SearchMode, SearchType};
+use crate::internal::ui::app::ViewMode;
+use crate::internal::ui::sort::{SortBy, SortOrder};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub list_type: StoryListType,
+    /// Ids of the custom list, when that was the list shown
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_list: Vec<u32>,
+    pub view_mode: ViewMode,
+    /// Story open in the Comments or Article view
+    pub story: Option<Story>,
+    /// Story focused in the list
+    pub selected_id: Option<u32>,
+    pub comments_offset: usize,
+    pub article_offset: usize,
+    pub sort_by: SortBy,
+    pub sort_order: SortOrder,
+    /// Active search; empty when there was none
+    pub search: String,
+    pub search_mode: SearchMode,
+    pub search_type: SearchType,
+}
+
+impl Session {
+    fn path() -> Result<PathBuf> {
+        let config_dir = dirs::config_dir()
+            .context("Could not find config directory")?
+            .join("tui-hn-app");
+        fs::create_dir_all(&config_dir).with_context(|| {
+            format!("Failed to create config directory {}", config_dir.display())
+        })?;
+        Ok(config_dir.join("session.json"))
+    }
+
+    /// The saved session, if there is one.
+    pub fn load() -> Result<Option<Self>> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path).context("Failed to read session file")?;
+        serde_json::from_str(&content)
+            .map(Some)
+            .context("Failed to parse session file")
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let content = serde_json::to_string_pretty(self).context("Failed to serialize session")?;
+        fs::write(Self::path()?, content).context("Failed to write session file")
+    }
+
+    /// One line for the restore prompt, e.g. `Top stories · Article: "Title"`.
+    pub fn summary(&self) -> String {
+        let list = format!("{} stories", self.list_type);
+        let view = match (self.view_mode, &self.story) {
+            (ViewMode::StoryDetail | ViewMode::Article, Some(story)) => format!(
+                "{}: \"{}\"",
+                match self.view_mode {
+                    ViewMode::Article => "Article",
+                    _ => "Comments",
+                },
+                story.title.as_deref().unwrap_or("(untitled)")
+            ),
+            (ViewMode::Bookmarks, _) => "Bookmarks".to_string(),
+            (ViewMode::History, _) => "History".to_string(),
+            (ViewMode::ReadingLists, _) => "Reading lists".to_string(),
+            _ => "List".to_string(),
+        };
+        match self.search.is_empty() {
+            true => format!("{} · {}", list, view),
+            false => format!("{} · {} · search \"{}\"", list, view, self.search),
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Story scores and ranks saved at exit, so the next launch can show what changed.
+
+use anyhow::{Context, Result};
+use jiff::Zoned;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+
+use super::models::Story;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotEntry {
+    pub score: u32,
+    pub descendants: u32,
+    /// Zero-based position in the HN list
+    pub rank: usize,
+}
+
+/// Change of a story since the previous snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StoryDelta {
+    pub score: i64,
+    pub comments: i64,
+    /// Positions gained in the list (negative when the story dropped)
+    pub rank: i64,
+}
+
+impl StoryDelta {
+    /// Compact label such as "▲3 +42 pts +12 comments"; empty when nothing changed.
+    pub fn label(&self) -> String {
+        let mut parts = Vec::new();
+        match self.rank {
+            0 => {}
+            r if r > 0 => parts.push(format!("▲{}", r)),
+            r => parts.push(format!("▼{}", -r)),
+        }
+        if self.score != 0 {
+            parts.push(format!("{:+} pts", self.score));
+        }
+        if self.comments != 0 {
+            parts.push(format!("{:+} comments", self.comments));
+        }
+        parts.join(" ")
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorySnapshot {
+    pub taken_at: Option<Zoned>,
+    /// Entries per list, keyed by list name ("Top", "New", ...)
+    pub lists: HashMap<String, HashMap<u32, SnapshotEntry>>,
+    #[serde(skip)]
+    file_path: Option<PathBuf>,
+    /// Lists recorded during this session
+    #[serde(skip)]
+    updated: HashSet<String>,
+}
+
+impl StorySnapshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load_or_create() -> Result<Self> {
+        let config_dir = dirs::config_dir()
+            .context("Could not find config directory")?
+            .join("tui-hn-app");
+        fs::create_dir_all(&config_dir).with_context(|| {
+            format!("Failed to create config directory {}", config_dir.display())
+        })?;
+
+        let file_path = config_dir.join("story_snapshot.json");
+        let mut snapshot: StorySnapshot = match file_path.exists() {
+            true => {
+                let content =
+                    fs::read_to_string(&file_path).context("Failed to read story snapshot")?;
+                serde_json::from_str(&content).context("Failed to parse story snapshot")?
+            }
+            false => Self::new(),
+        };
+        snapshot.file_path = Some(file_path);
+        Ok(snapshot)
+    }
+
+    /// Write the snapshot if any list was recorded this session.
+    pub fn save(&mut self) -> Result<()> {
+        if let (Some(path), false) = (&self.file_path, self.updated.is_empty()) {
+            self.taken_at = Some(Zoned::now());
+            let content =
+                serde_json::to_string_pretty(self).context("Failed to serialize story snapshot")?;
+            fs::write(path, content).context("Failed to write story snapshot")?;
+        }
+        Ok(())
+    }
+
+    /// Store the current state of loaded stories. The first call for a list in a
+    /// session drops its previous entries so stories that left the list don't pile up.
+    pub fn record<'a>(
+        &mut self,
+        list: &str,
+        stories: impl IntoIterator<Item = &'a Story>,
+        rank_of: impl Fn(u32) -> Option<usize>,
+    ) {
+        let entries = self.lists.entry(list.to_string()).or_default();
+        if self.updated.insert(list.to_string()) {
+            entries.clear();
+        }
+        for story in stories {
+            if let Some(rank) = rank_of(story.id) {
+                entries.insert(
+                    story.id,
+                    SnapshotEntry {
+                        score: story.score.unwrap_or(0),
+                        descendants: story.descendants.unwrap_or(0),
+                        rank,
+                    },
+                );
+            }
+        }
+    }
+
+    pub fn has_list(&self, list: &str) -> bool {
+        self.lists
+            .get(list)
+            .is_some_and(|entries| !entries.is_empty())
+    }
+
+    /// Change of `story` (currently at `rank`) since this snapshot was taken.
+    pub fn delta(&self, list: &str, story: &Story, rank: Option<usize>) -> Option<StoryDelta> {
+        let before = self.lists.get(list)?.get(&story.id)?;
+        Some(StoryDelta {
+            score: story.score.unwrap_or(0) as i64 - before.score as i64,
+            comments: story.descendants.unwrap_or(0) as i64 - before.descendants as i64,
+            rank: rank.map_or(0, |r| before.rank as i64 - r as i64),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn story(id: u32, score: u32, descendants: u32) -> Story {
+        Story {
+            id,
+            score: Some(score),
+            descendants: Some(descendants),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn delta_against_previous_launch() {
+        let mut snapshot = StorySnapshot::new();
+        let before = [story(1, 100, 10), story(2, 50, 0)];
+        snapshot.record("Top", &before, |id| Some(id as usize));
+
+        let delta = snapshot.delta("Top", &story(2, 92, 12), Some(0)).unwrap();
+        assert_eq!(
+            delta,
+            StoryDelta {
+                score: 42,
+                comments: 12,
+                rank: 2
+            }
+        );
+        assert_eq!(delta.label(), "▲2 +42 pts +12 comments");
+        assert_eq!(
+            snapshot
+                .delta("Top", &story(1, 100, 10), Some(1))
+                .map(|d| d.label()),
+            Some(String::new())
+        );
+        assert!(snapshot.delta("Top", &story(3, 1, 1), Some(0)).is_none());
+        assert!(snapshot.delta("New", &story(1, 1, 1), Some(0)).is_none());
+
+        // Recording the list again in the same session keeps earlier entries
+        snapshot.record("Top", &[story(3, 1, 0)], |_| Some(5));
+        assert!(snapshot.delta("Top", &story(1, 100, 10), None).is_some());
+    }
+}
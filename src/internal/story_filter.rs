@@ -0,0 +1,140 @@
+//! Story list filters on score, age, domain and author, set in the filter panel
+//! and saved in the `filters` section of the config.
+
+use crate::config::StoryFilterConfig;
+use crate::internal::models::Story;
+
+/// Whether `story` passes every filter that is set. `now` is a Unix timestamp.
+pub fn matches(filters: &StoryFilterConfig, story: &Story, now: i64) -> bool {
+    let score_ok = filters.min_score == 0 || story.score.unwrap_or(0) >= filters.min_score;
+    let age_ok = filters.max_age_hours == 0
+        || story
+            .time
+            .is_none_or(|time| now - time <= filters.max_age_hours as i64 * 3600);
+
+    let domain = story
+        .url
+        .as_deref()
+        .and_then(crate::utils::url::extract_domain)
+        .map(|d| d.to_lowercase());
+    let domain_in = |list: &[String]| {
+        domain
+            .as_deref()
+            .is_some_and(|d| list.iter().any(|wanted| domain_matches(d, wanted)))
+    };
+    let domains_ok = (filters.include_domains.is_empty() || domain_in(&filters.include_domains))
+        && !domain_in(&filters.exclude_domains);
+
+    let author_in = |list: &[String]| {
+        story
+            .by
+            .as_deref()
+            .is_some_and(|by| list.iter().any(|name| name.trim().eq_ignore_ascii_case(by)))
+    };
+    let authors_ok = (filters.allow_authors.is_empty() || author_in(&filters.allow_authors))
+        && !author_in(&filters.block_authors);
+
+    score_ok && age_ok && domains_ok && authors_ok
+}
+
+/// `wanted` matches the domain itself and its subdomains.
+fn domain_matches(domain: &str, wanted: &str) -> bool {
+    let wanted = wanted.trim().trim_start_matches("www.").to_lowercase();
+    let domain = domain.trim_start_matches("www.");
+    domain == wanted || domain.ends_with(&format!(".{}", wanted))
+}
+
+/// Short summary of the active filters for the status bar, such as
+/// "≥50 pts · ≤24h · -2 domains"; `None` when no filter is set.
+pub fn chip(filters: &StoryFilterConfig) -> Option<String> {
+    let count = |n: usize, noun: &str| match n {
+        1 => format!("1 {}", noun),
+        n => format!("{} {}s", n, noun),
+    };
+    let mut parts = Vec::new();
+    if filters.min_score > 0 {
+        parts.push(format!("≥{} pts", filters.min_score));
+    }
+    if filters.max_age_hours > 0 {
+        parts.push(format!("≤{}h", filters.max_age_hours));
+    }
+    for (list, prefix, noun) in [
+        (&filters.include_domains, "only ", "domain"),
+        (&filters.exclude_domains, "-", "domain"),
+        (&filters.allow_authors, "only ", "author"),
+        (&filters.block_authors, "-", "author"),
+    ] {
+        if !list.is_empty() {
+            parts.push(format!("{}{}", prefix, count(list.len(), noun)));
+        }
+    }
+    match parts.is_empty() {
+        true => None,
+        false => Some(parts.join(" · ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn story(score: u32, hours_old: i64, url: &str, by: &str) -> Story {
+        Story {
+            score: Some(score),
+            time: Some(1_000_000 - hours_old * 3600),
+            url: Some(url.to_string()),
+            by: Some(by.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn every_set_filter_must_pass() {
+        let now = 1_000_000;
+        let rust = story(120, 2, "https://blog.rust-lang.org/post", "alice");
+        let old = story(300, 48, "https://www.example.com/a", "bob");
+        let mut filters = StoryFilterConfig::default();
+        assert!(matches(&filters, &rust, now) && matches(&filters, &old, now));
+
+        filters.min_score = 100;
+        filters.max_age_hours = 24;
+        assert!(matches(&filters, &rust, now));
+        assert!(!matches(&filters, &old, now));
+
+        filters = StoryFilterConfig {
+            include_domains: vec!["rust-lang.org".to_string()],
+            ..Default::default()
+        };
+        assert!(matches(&filters, &rust, now));
+        assert!(!matches(&filters, &old, now));
+
+        filters = StoryFilterConfig {
+            exclude_domains: vec!["www.example.com".to_string()],
+            block_authors: vec!["Alice".to_string()],
+            ..Default::default()
+        };
+        assert!(!matches(&filters, &rust, now) && !matches(&filters, &old, now));
+
+        filters = StoryFilterConfig {
+            allow_authors: vec!["bob".to_string()],
+            ..Default::default()
+        };
+        assert!(!matches(&filters, &rust, now) && matches(&filters, &old, now));
+    }
+
+    #[test]
+    fn chip_summarizes_active_filters() {
+        assert_eq!(chip(&StoryFilterConfig::default()), None);
+        let filters = StoryFilterConfig {
+            min_score: 50,
+            max_age_hours: 24,
+            exclude_domains: vec!["a.com".to_string(), "b.com".to_string()],
+            allow_authors: vec!["pg".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            chip(&filters).as_deref(),
+            Some("≥50 pts · ≤24h · -2 domains · only 1 author")
+        );
+    }
+}
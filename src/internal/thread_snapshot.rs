@@ -0,0 +1,282 @@
+//! Saved copies of comment threads, compared later to show which comments were
+//! added, edited or deleted in the meantime. Snapshots live in
+//! `thread_snapshots.json` and keep a hash of each comment's text, plus a short
+//! excerpt so deleted comments can still be shown.
+
+use anyhow::{Context, Result};
+use jiff::Zoned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+use super::models::CommentRow;
+
+/// Threads kept at most; the oldest snapshot is dropped first.
+const MAX_THREADS: usize = 100;
+/// Characters of text kept per comment for showing deleted ones.
+const EXCERPT_CHARS: usize = 120;
+
+/// FNV-1a of the comment text. Unlike `DefaultHasher` it is stable across runs and
+/// Rust versions, so saved hashes stay comparable.
+pub fn text_hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotComment {
+    pub id: u32,
+    /// Parent comment; `None` for top-level comments
+    pub parent: Option<u32>,
+    pub by: Option<String>,
+    pub hash: u64,
+    pub excerpt: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadSnapshot {
+    pub taken_at: Zoned,
+    pub comments: Vec<SnapshotComment>,
+}
+
+impl ThreadSnapshot {
+    /// Snapshot of the loaded comments; deleted ones are left out.
+    pub fn take(rows: &[CommentRow]) -> Self {
+        let comments = rows
+            .iter()
+            .filter(|row| !row.comment.deleted)
+            .filter_map(|row| {
+                let text = row.comment.text.as_deref()?;
+                let plain = crate::utils::html::extract_text_from_html(text);
+                Some(SnapshotComment {
+                    id: row.comment.id,
+                    parent: row.parent_id,
+                    by: row.comment.by.clone(),
+                    hash: text_hash(text),
+                    excerpt: excerpt(&plain),
+                })
+            })
+            .collect();
+        Self {
+            taken_at: Zoned::now(),
+            comments,
+        }
+    }
+
+    /// Compare the thread as loaded now with the snapshot. `root_ids` are the
+    /// story's top-level comment ids.
+    ///
+    /// Comments that are merely not loaded (folded away lazily, or past the comment
+    /// limit) are not reported as deleted: a snapshotted comment counts as deleted
+    /// when HN marks it deleted, or when its parent is loaded and no longer lists it.
+    pub fn diff(&self, rows: &[CommentRow], root_ids: &[u32]) -> ThreadDiff {
+        let before: HashMap<u32, &SnapshotComment> =
+            self.comments.iter().map(|c| (c.id, c)).collect();
+        let now: HashMap<u32, &CommentRow> = rows.iter().map(|r| (r.comment.id, r)).collect();
+
+        let changes = rows
+            .iter()
+            .filter(|row| !row.comment.deleted)
+            .filter_map(|row| {
+                let text = row.comment.text.as_deref()?;
+                match before.get(&row.comment.id) {
+                    None => Some((row.comment.id, Change::Added)),
+                    Some(old) if old.hash != text_hash(text) => {
+                        Some((row.comment.id, Change::Edited))
+                    }
+                    Some(_) => None,
+                }
+            })
+            .collect();
+
+        let deleted = self
+            .comments
+            .iter()
+            .filter(|old| match now.get(&old.id) {
+                Some(row) => row.comment.deleted || row.comment.text.is_none(),
+                None => {
+                    let siblings = match old.parent {
+                        None => Some(root_ids),
+                        Some(parent) => {
+                            now.get(&parent).and_then(|row| row.comment.kids.as_deref())
+                        }
+                    };
+                    siblings.is_some_and(|kids| !kids.contains(&old.id))
+                }
+            })
+            .cloned()
+            .collect();
+
+        ThreadDiff {
+            taken_at: self.taken_at.clone(),
+            changes,
+            deleted,
+        }
+    }
+}
+
+fn excerpt(text: &str) -> String {
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match flat.char_indices().nth(EXCERPT_CHARS) {
+        Some((end, _)) => format!("{}…", &flat[..end]),
+        None => flat,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Change {
+    Added,
+    /// Text changed since the snapshot
+    Edited,
+}
+
+/// Differences between a snapshot and the thread as loaded now.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreadDiff {
+    pub taken_at: Zoned,
+    pub changes: HashMap<u32, Change>,
+    /// Snapshotted comments that are gone, in thread order
+    pub deleted: Vec<SnapshotComment>,
+}
+
+impl ThreadDiff {
+    pub fn count(&self, change: Change) -> usize {
+        self.changes.values().filter(|c| **c == change).count()
+    }
+
+    /// Summary such as "+3 ~1 -2".
+    pub fn summary(&self) -> String {
+        format!(
+            "+{} ~{} -{}",
+            self.count(Change::Added),
+            self.count(Change::Edited),
+            self.deleted.len()
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ThreadSnapshots {
+    /// Snapshots by story id
+    #[serde(default)]
+    pub threads: BTreeMap<u32, ThreadSnapshot>,
+    #[serde(skip)]
+    file_path: Option<PathBuf>,
+}
+
+impl ThreadSnapshots {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load_or_create() -> Result<Self> {
+        let config_dir = dirs::config_dir()
+            .context("Could not find config directory")?
+            .join("tui-hn-app");
+        fs::create_dir_all(&config_dir).with_context(|| {
+            format!("Failed to create config directory {}", config_dir.display())
+        })?;
+
+        let file_path = config_dir.join("thread_snapshots.json");
+        let mut snapshots: ThreadSnapshots = match file_path.exists() {
+            true => {
+                let content =
+                    fs::read_to_string(&file_path).context("Failed to read thread snapshots")?;
+                serde_json::from_str(&content).context("Failed to parse thread snapshots")?
+            }
+            false => Self::new(),
+        };
+        snapshots.file_path = Some(file_path);
+        Ok(snapshots)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = &self.file_path {
+            let content = serde_json::to_string(self).context("Failed to serialize snapshots")?;
+            fs::write(path, content).context("Failed to write thread snapshots")?;
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, story_id: u32) -> Option<&ThreadSnapshot> {
+        self.threads.get(&story_id)
+    }
+
+    /// Replace the snapshot of a story, dropping the oldest thread beyond the limit.
+    pub fn insert(&mut self, story_id: u32, snapshot: ThreadSnapshot) {
+        self.threads.insert(story_id, snapshot);
+        while self.threads.len() > MAX_THREADS {
+            let oldest = self
+                .threads
+                .iter()
+                .min_by_key(|(_, snapshot)| snapshot.taken_at.timestamp())
+                .map(|(id, _)| *id);
+            match oldest {
+                Some(id) => self.threads.remove(&id),
+                None => break,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::models::Comment;
+
+    fn row(id: u32, parent: Option<u32>, text: &str, kids: &[u32]) -> CommentRow {
+        CommentRow {
+            comment: Comment {
+                id,
+                by: Some(format!("user{}", id)),
+                text: Some(text.to_string()),
+                time: None,
+                kids: Some(kids.to_vec()),
+                deleted: false,
+                dead: false,
+            },
+            depth: usize::from(parent.is_some()),
+            expanded: true,
+            parent_id: parent,
+            loaded_kids: true,
+        }
+    }
+
+    #[test]
+    fn hashes_are_stable() {
+        assert_eq!(text_hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(text_hash("a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn diff_reports_added_edited_and_deleted() {
+        let before = ThreadSnapshot::take(&[
+            row(1, None, "first", &[2, 3]),
+            row(2, Some(1), "reply", &[]),
+            row(3, Some(1), "gone soon", &[]),
+            row(4, None, "removed", &[]),
+        ]);
+
+        let mut edited = row(2, Some(1), "reply, edited", &[]);
+        edited.comment.kids = None;
+        let now = [
+            row(1, None, "first", &[2, 5]),
+            edited,
+            row(5, Some(1), "new", &[]),
+        ];
+        let diff = before.diff(&now, &[1]);
+
+        assert_eq!(diff.changes.get(&2), Some(&Change::Edited));
+        assert_eq!(diff.changes.get(&5), Some(&Change::Added));
+        assert_eq!(diff.changes.get(&1), None);
+        let deleted: Vec<u32> = diff.deleted.iter().map(|c| c.id).collect();
+        assert_eq!(deleted, vec![3, 4]);
+        assert_eq!(diff.summary(), "+1 ~1 -2");
+
+        // A thread that is only partly loaded reports nothing as deleted
+        let partial = before.diff(&[], &[1, 4]);
+        assert!(partial.deleted.is_empty());
+    }
+}
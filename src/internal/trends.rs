@@ -0,0 +1,174 @@
+//! Score and comment counts of stories sampled each time they load or refresh, so
+//! the list can show a sparkline of how a story is doing. Kept for the session, or
+//! in `story_trends.json` across sessions with `list_view.trends: Persist`.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use super::models::Story;
+
+/// Samples kept per story; older ones make way.
+pub const MAX_POINTS: usize = 12;
+/// Stories not sampled for this long are dropped when the trends are loaded.
+const KEEP_SECS: i64 = 2 * 24 * 60 * 60;
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TrendPoint {
+    /// When the story was sampled (Unix seconds)
+    pub time: i64,
+    pub score: u32,
+    pub comments: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StoryTrends {
+    pub series: HashMap<u32, Vec<TrendPoint>>,
+    #[serde(skip)]
+    file_path: Option<PathBuf>,
+}
+
+impl StoryTrends {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load_or_create(now: i64) -> Result<Self> {
+        let config_dir = dirs::config_dir()
+            .context("Could not find config directory")?
+            .join("tui-hn-app");
+        fs::create_dir_all(&config_dir).with_context(|| {
+            format!("Failed to create config directory {}", config_dir.display())
+        })?;
+
+        let file_path = config_dir.join("story_trends.json");
+        let mut trends: StoryTrends = match file_path.exists() {
+            true => {
+                let content =
+                    fs::read_to_string(&file_path).context("Failed to read story trends")?;
+                serde_json::from_str(&content).context("Failed to parse story trends")?
+            }
+            false => Self::new(),
+        };
+        trends.series.retain(|_, points| {
+            points
+                .last()
+                .is_some_and(|last| now - last.time < KEEP_SECS)
+        });
+        trends.file_path = Some(file_path);
+        Ok(trends)
+    }
+
+    /// Write the trends when they were loaded from a file.
+    pub fn save(&self) -> Result<()> {
+        if let Some(path) = &self.file_path {
+            let content =
+                serde_json::to_string(self).context("Failed to serialize story trends")?;
+            fs::write(path, content).context("Failed to write story trends")?;
+        }
+        Ok(())
+    }
+
+    /// Sample `stories` at `now`; a story whose counts did not change since its
+    /// last sample is skipped.
+    pub fn record<'a>(&mut self, stories: impl IntoIterator<Item = &'a Story>, now: i64) {
+        for story in stories {
+            let point = TrendPoint {
+                time: now,
+                score: story.score.unwrap_or(0),
+                comments: story.descendants.unwrap_or(0),
+            };
+            let points = self.series.entry(story.id).or_default();
+            if points
+                .last()
+                .is_some_and(|last| (last.score, last.comments) == (point.score, point.comments))
+            {
+                continue;
+            }
+            points.push(point);
+            let excess = points.len().saturating_sub(MAX_POINTS);
+            points.drain(..excess);
+        }
+    }
+
+    pub fn points(&self, id: u32) -> &[TrendPoint] {
+        self.series.get(&id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Sparkline of the story's score with the points gained since the first
+    /// sample, e.g. "▁▂▅█ ↑42"; None until the score changed.
+    pub fn label(&self, id: u32) -> Option<String> {
+        let points = self.points(id);
+        let (first, last) = (points.first()?, points.last()?);
+        let gained = i64::from(last.score) - i64::from(first.score);
+        if points.len() < 2 || gained == 0 {
+            return None;
+        }
+        let scores: Vec<u32> = points.iter().map(|point| point.score).collect();
+        let arrow = match gained > 0 {
+            true => format!("↑{}", gained),
+            false => format!("↓{}", -gained),
+        };
+        Some(format!("{} {}", sparkline(&scores), arrow))
+    }
+}
+
+/// `values` as bars from ▁ (the lowest) to █ (the highest).
+pub fn sparkline(values: &[u32]) -> String {
+    let (min, max) = match (values.iter().min(), values.iter().max()) {
+        (Some(&min), Some(&max)) => (min, max),
+        _ => return String::new(),
+    };
+    let span = u64::from(max - min).max(1);
+    values
+        .iter()
+        .map(|&value| BARS[(u64::from(value - min) * (BARS.len() as u64 - 1) / span) as usize])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn story(id: u32, score: u32, descendants: u32) -> Story {
+        Story {
+            id,
+            score: Some(score),
+            descendants: Some(descendants),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn samples_changes_and_draws_the_score() {
+        let mut trends = StoryTrends::new();
+        trends.record(&[story(1, 10, 0), story(2, 5, 0)], 0);
+        trends.record(&[story(1, 10, 0)], 60);
+        assert_eq!(trends.points(1).len(), 1);
+        assert_eq!(trends.label(1), None);
+
+        trends.record(&[story(1, 24, 3), story(2, 5, 4)], 120);
+        trends.record(&[story(1, 52, 9)], 180);
+        assert_eq!(trends.label(1).as_deref(), Some("▁▃█ ↑42"));
+        // Comments alone do not make a trend
+        assert_eq!(trends.points(2).len(), 2);
+        assert_eq!(trends.label(2), None);
+
+        for minute in 0..20 {
+            trends.record(&[story(3, minute, 0)], minute as i64 * 60);
+        }
+        assert_eq!(trends.points(3).len(), MAX_POINTS);
+        assert_eq!(trends.points(3)[0].score, 20 - MAX_POINTS as u32);
+    }
+
+    #[test]
+    fn sparkline_spans_the_range() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[3, 3]), "▁▁");
+        assert_eq!(sparkline(&[0, 7, 14]), "▁▄█");
+        assert_eq!(sparkline(&[9, 1]), "█▁");
+    }
+}
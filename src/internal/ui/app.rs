@@ -6,10 +6,15 @@ use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::event::{
+    Event, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
+use futures::StreamExt;
 
 use crate::api::{ApiService, StoryListType};
 use crate::config::AppConfig;
+use crate::internal::error::{AppError, Operation};
 use crate::internal::models::{Article, CommentRow, Story};
 use crate::internal::ui::sort::{SortBy, SortOrder};
 use crate::utils::theme_loader::{TuiTheme, load_theme};
@@ -17,14 +22,45 @@ use crate::utils::theme_loader::{TuiTheme, load_theme};
 use ratatui::Frame;
 use ratatui::widgets::ListState;
 
+/// Stories fetched per page of the list
+const STORY_PAGE_SIZE: usize = 20;
+/// Stories shown per update while a page streams in
+const STORY_BATCH_SIZE: usize = 5;
+/// How long a row stays highlighted after a refresh changed its score or comments
+pub const UPDATE_FLASH: std::time::Duration = std::time::Duration::from_millis(1500);
+/// Time between spinner frames while something is loading
+const SPINNER_STEP: std::time::Duration = std::time::Duration::from_millis(100);
+/// Redraw an idle screen this often so relative times ("5 minutes ago") stay current
+const IDLE_REDRAW: std::time::Duration = std::time::Duration::from_secs(60);
+/// Home pages of story sites looked up at the same time
+const DOMAIN_LOOKUPS: usize = 4;
+
+/// Fetch a page of stories and send them over as they arrive: `StoriesBatchLoaded`
+/// for partial batches, `StoriesLoaded` for the last one.
+async fn stream_page(
+    api: &ApiService,
+    tx: &UnboundedSender<Action>,
+    ids: &[u32],
+    token: Option<tokio_util::sync::CancellationToken>,
+) {
+    api.stream_stories(ids, 10, STORY_BATCH_SIZE, token, |stories, remaining| {
+        let _ = tx.send(match remaining {
+            0 => Action::StoriesLoaded(stories),
+            _ => Action::StoriesBatchLoaded(stories, remaining),
+        });
+    })
+    .await;
+}
+
 /// Application view modes.
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub enum ViewMode {
     List,
     StoryDetail,
     Article,
     Bookmarks,
     History,
+    ReadingLists,
 }
 
 /// Input modes for the UI.
@@ -49,13 +85,17 @@ pub enum Action {
     StoryIdsLoaded(Vec<u32>),
     StoryLoadingProgress(usize),
     StoriesLoaded(Vec<Story>),
+    /// Part of a page of stories, with the number of fetches still running
+    StoriesBatchLoaded(Vec<Story>, usize),
     LoadMoreStories,
     LoadAllStories,
     SelectStory(Story, StoryListType),
-    CommentsLoaded(Vec<CommentRow>),
+    /// A batch of comment threads for a story, from the comment fetch numbered as in
+    /// `App::comment_fetch`, and how many top-level comments are still to come (0
+    /// once the tree is complete)
+    CommentsChunkLoaded(u32, u64, Vec<CommentRow>, usize),
     LoadMoreComments,
     AppendComments(usize, Vec<CommentRow>),
-    #[allow(dead_code)]
     ToggleCommentCollapse(usize),
     ToggleArticleView,
     ToggleHelp,
@@ -65,10 +105,14 @@ pub enum Action {
     SortByScore,
     SortByComments,
     SortByTime,
+    /// Re-rank the list with HN's front-page formula
+    SortByRank,
     ToggleSortOrder,
     SwitchTheme,
-    ClearNotification,
-    Error(String),
+    Error(AppError),
+    /// Run the operation behind the error panel again
+    RetryFailed,
+    DismissError,
     ToggleBookmark,
     ViewBookmarks,
     #[allow(dead_code)]
@@ -81,6 +125,132 @@ pub enum Action {
     ToggleThemeEditor,
     #[allow(dead_code)]
     ExportTheme(String),
+    RefreshVisibleStories,
+    StoriesRefreshed(Vec<Story>),
+    /// Bookmarks read by an import, and how many entries were skipped
+    BookmarksImported(Vec<crate::internal::bookmarks::BookmarkedStory>, usize),
+    /// Bookmarked and watched stories fetched by the startup prefetch
+    StoriesPrefetched(Vec<Story>),
+    /// What the home page of a domain says about the site
+    SiteMetaFetched(String, crate::internal::models::SiteMeta),
+    /// Poll the HN updates feed
+    CheckUpdates,
+    /// The config file changed on disk
+    ReloadConfig,
+    /// Ids of items HN reported as changed
+    UpdatesReceived(Vec<u32>),
+    ToggleHideRead,
+    MarkAllRead,
+    OpenHnPage,
+    CopyLink,
+    Upvote,
+    Favorite,
+    ItemActionDone(Result<String, String>),
+    FocusNextPane,
+    CollapseAllComments,
+    ExpandAllComments,
+    CollapseToDepth(usize),
+    ExportStory,
+    ExportStoryList,
+    ImportIdList,
+    LoadCustomList(Vec<u32>),
+    AddToReadingList,
+    ViewReadingLists,
+    MoveItemUp,
+    MoveItemDown,
+    RemoveFromReadingList,
+    ToggleItemDone,
+    NextReadingList,
+    PrevReadingList,
+    ToggleCacheStats,
+    /// Toggle the conversation view for the thread at the top of the comments pane
+    ToggleConversationView,
+    /// Copy the HN discussion link of the focused story
+    CopyCommentsLink,
+    PollLoaded(crate::internal::models::Poll),
+    /// Final URL of a link after redirects, or why it could not be resolved
+    LinkResolved(String, Result<String, String>),
+    /// Open the focused story straight in the Article view
+    OpenInReader,
+    /// Focus filters of the current list
+    ToggleHideJobs,
+    ToggleHideSeen,
+    /// Save the loaded comments of the open story for a later diff
+    SnapshotThread,
+    /// Mark comments added, edited or deleted since the snapshot
+    ToggleThreadDiff,
+    /// Edit the score, age, domain and author filters of the story list
+    OpenFilterPanel,
+    /// Ask whether to mute the focused story's domain or author
+    MuteStory,
+    /// Reveal or hide muted stories and comments
+    ToggleShowMuted,
+    /// Full-text search of the articles and comments read so far
+    SearchReading,
+    /// Edit the tags of the focused bookmark
+    EditBookmarkTags,
+    /// Show only bookmarks with the next (or previous) tag in the Bookmarks view
+    NextBookmarkTag,
+    PrevBookmarkTag,
+    /// Write a note on the focused bookmark
+    EditBookmarkNote,
+    /// Remove the focused entry from the History view
+    DeleteHistoryEntry,
+    /// Open a random bookmark that has not been read yet
+    OpenRandomBookmark,
+    /// Download the lists in `offline_pack` with their articles and comments
+    PrepareOfflinePack,
+    /// List the links of the open article or comment thread to pick one to open
+    OpenLinkPicker,
+    /// Open the next (or previous) story of the list without going back to it
+    NextStory,
+    PrevStory,
+    /// Scroll the comments or article by half a screen, a screen, or to either end;
+    /// amounts are in wrapped lines
+    ScrollHalfPageDown,
+    ScrollHalfPageUp,
+    ScrollPageDown,
+    ScrollPageUp,
+    ScrollToTop,
+    ScrollToBottom,
+    /// Push the focused story to the service set up in `push`
+    SendToPhone,
+    /// Settings panel editing common config values
+    OpenSettings,
+    /// Live performance HUD, when performance metrics are on
+    TogglePerfHud,
+    /// Last step of restoring a session, queued after the list and story loads:
+    /// sort, search and scroll positions
+    SessionRestored(Box<crate::internal::session::Session>),
+    /// Stop the app and return to the shell (Ctrl+Z), until it is resumed
+    Suspend,
+    /// Read the focused story, with its article and comments, in `$PAGER`
+    OpenInPager,
+    /// The same text in `$EDITOR`
+    OpenInEditor,
+    /// Every action by name with a fuzzy search, to run one without its key
+    OpenCommandPalette,
+    /// Show timestamps relative, as local time or as ISO 8601, in turn
+    CycleTimeFormat,
+    /// The last notifications, with when they came and how severe they were
+    ToggleNotificationCenter,
+    /// Jump to a story by its item id, HN link or article URL
+    OpenItemPrompt,
+    /// Queue the focused story's article and comments for background download
+    QueueReadLater,
+    /// The read-later queue with the state of each download
+    ViewReadLater,
+    /// How the download of a story in the read-later queue went
+    ReadLaterDone(u32, Result<crate::internal::read_later::Downloaded, String>),
+    /// `(stories done, total)` of the offline pack being prepared
+    OfflinePackProgress(usize, usize),
+    OfflinePackDone(
+        crate::internal::offline_pack::PackSummary,
+        crate::internal::offline_pack::OfflinePack,
+    ),
+    /// Run the plugin at this index of `config.plugins`
+    RunPlugin(usize),
+    PluginFinished(String, crate::internal::plugins::PluginOutput),
 }
 
 // Manual Serialize/Deserialize implementation for Action
@@ -131,9 +301,6 @@ impl serde::Serialize for Action {
                 serializer.serialize_unit_variant("Action", 17, "ToggleSortOrder")
             }
             Action::SwitchTheme => serializer.serialize_unit_variant("Action", 18, "SwitchTheme"),
-            Action::ClearNotification => {
-                serializer.serialize_unit_variant("Action", 19, "ClearNotification")
-            }
             Action::ToggleBookmark => {
                 serializer.serialize_unit_variant("Action", 20, "ToggleBookmark")
             }
@@ -148,6 +315,150 @@ impl serde::Serialize for Action {
             }
             Action::ViewHistory => serializer.serialize_unit_variant("Action", 24, "ViewHistory"),
             Action::ClearHistory => serializer.serialize_unit_variant("Action", 25, "ClearHistory"),
+            Action::RefreshVisibleStories => {
+                serializer.serialize_unit_variant("Action", 26, "RefreshVisibleStories")
+            }
+            Action::ToggleHideRead => {
+                serializer.serialize_unit_variant("Action", 27, "ToggleHideRead")
+            }
+            Action::MarkAllRead => serializer.serialize_unit_variant("Action", 28, "MarkAllRead"),
+            Action::OpenHnPage => serializer.serialize_unit_variant("Action", 29, "OpenHnPage"),
+            Action::CopyLink => serializer.serialize_unit_variant("Action", 30, "CopyLink"),
+            Action::Upvote => serializer.serialize_unit_variant("Action", 31, "Upvote"),
+            Action::Favorite => serializer.serialize_unit_variant("Action", 32, "Favorite"),
+            Action::FocusNextPane => {
+                serializer.serialize_unit_variant("Action", 33, "FocusNextPane")
+            }
+            Action::CollapseAllComments => {
+                serializer.serialize_unit_variant("Action", 34, "CollapseAllComments")
+            }
+            Action::ExpandAllComments => {
+                serializer.serialize_unit_variant("Action", 35, "ExpandAllComments")
+            }
+            Action::ExportStory => serializer.serialize_unit_variant("Action", 36, "ExportStory"),
+            Action::ExportStoryList => {
+                serializer.serialize_unit_variant("Action", 37, "ExportStoryList")
+            }
+            Action::ImportIdList => serializer.serialize_unit_variant("Action", 38, "ImportIdList"),
+            Action::AddToReadingList => {
+                serializer.serialize_unit_variant("Action", 39, "AddToReadingList")
+            }
+            Action::ViewReadingLists => {
+                serializer.serialize_unit_variant("Action", 40, "ViewReadingLists")
+            }
+            Action::MoveItemUp => serializer.serialize_unit_variant("Action", 41, "MoveItemUp"),
+            Action::MoveItemDown => serializer.serialize_unit_variant("Action", 42, "MoveItemDown"),
+            Action::RemoveFromReadingList => {
+                serializer.serialize_unit_variant("Action", 43, "RemoveFromReadingList")
+            }
+            Action::ToggleItemDone => {
+                serializer.serialize_unit_variant("Action", 44, "ToggleItemDone")
+            }
+            Action::NextReadingList => {
+                serializer.serialize_unit_variant("Action", 45, "NextReadingList")
+            }
+            Action::PrevReadingList => {
+                serializer.serialize_unit_variant("Action", 46, "PrevReadingList")
+            }
+            Action::ToggleCacheStats => {
+                serializer.serialize_unit_variant("Action", 47, "ToggleCacheStats")
+            }
+            Action::ToggleConversationView => {
+                serializer.serialize_unit_variant("Action", 48, "ToggleConversationView")
+            }
+            Action::CopyCommentsLink => {
+                serializer.serialize_unit_variant("Action", 49, "CopyCommentsLink")
+            }
+            Action::OpenInReader => serializer.serialize_unit_variant("Action", 50, "OpenInReader"),
+            Action::ToggleHideJobs => {
+                serializer.serialize_unit_variant("Action", 51, "ToggleHideJobs")
+            }
+            Action::ToggleHideSeen => {
+                serializer.serialize_unit_variant("Action", 52, "ToggleHideSeen")
+            }
+            Action::SnapshotThread => {
+                serializer.serialize_unit_variant("Action", 53, "SnapshotThread")
+            }
+            Action::ToggleThreadDiff => {
+                serializer.serialize_unit_variant("Action", 54, "ToggleThreadDiff")
+            }
+            Action::OpenFilterPanel => {
+                serializer.serialize_unit_variant("Action", 55, "OpenFilterPanel")
+            }
+            Action::MuteStory => serializer.serialize_unit_variant("Action", 56, "MuteStory"),
+            Action::ToggleShowMuted => {
+                serializer.serialize_unit_variant("Action", 57, "ToggleShowMuted")
+            }
+            Action::SearchReading => {
+                serializer.serialize_unit_variant("Action", 58, "SearchReading")
+            }
+            Action::EditBookmarkTags => {
+                serializer.serialize_unit_variant("Action", 59, "EditBookmarkTags")
+            }
+            Action::NextBookmarkTag => {
+                serializer.serialize_unit_variant("Action", 60, "NextBookmarkTag")
+            }
+            Action::PrevBookmarkTag => {
+                serializer.serialize_unit_variant("Action", 61, "PrevBookmarkTag")
+            }
+            Action::EditBookmarkNote => {
+                serializer.serialize_unit_variant("Action", 62, "EditBookmarkNote")
+            }
+            Action::DeleteHistoryEntry => {
+                serializer.serialize_unit_variant("Action", 63, "DeleteHistoryEntry")
+            }
+            Action::OpenRandomBookmark => {
+                serializer.serialize_unit_variant("Action", 64, "OpenRandomBookmark")
+            }
+            Action::PrepareOfflinePack => {
+                serializer.serialize_unit_variant("Action", 65, "PrepareOfflinePack")
+            }
+            Action::OpenLinkPicker => {
+                serializer.serialize_unit_variant("Action", 66, "OpenLinkPicker")
+            }
+            Action::NextStory => serializer.serialize_unit_variant("Action", 67, "NextStory"),
+            Action::PrevStory => serializer.serialize_unit_variant("Action", 68, "PrevStory"),
+            Action::ScrollHalfPageDown => {
+                serializer.serialize_unit_variant("Action", 69, "ScrollHalfPageDown")
+            }
+            Action::ScrollHalfPageUp => {
+                serializer.serialize_unit_variant("Action", 70, "ScrollHalfPageUp")
+            }
+            Action::ScrollPageDown => {
+                serializer.serialize_unit_variant("Action", 71, "ScrollPageDown")
+            }
+            Action::ScrollPageUp => serializer.serialize_unit_variant("Action", 72, "ScrollPageUp"),
+            Action::ScrollToTop => serializer.serialize_unit_variant("Action", 73, "ScrollToTop"),
+            Action::ScrollToBottom => {
+                serializer.serialize_unit_variant("Action", 74, "ScrollToBottom")
+            }
+            Action::SendToPhone => serializer.serialize_unit_variant("Action", 75, "SendToPhone"),
+            Action::OpenSettings => serializer.serialize_unit_variant("Action", 76, "OpenSettings"),
+            Action::TogglePerfHud => {
+                serializer.serialize_unit_variant("Action", 77, "TogglePerfHud")
+            }
+            Action::Suspend => serializer.serialize_unit_variant("Action", 78, "Suspend"),
+            Action::OpenInPager => serializer.serialize_unit_variant("Action", 79, "OpenInPager"),
+            Action::OpenInEditor => serializer.serialize_unit_variant("Action", 80, "OpenInEditor"),
+            Action::OpenCommandPalette => {
+                serializer.serialize_unit_variant("Action", 81, "OpenCommandPalette")
+            }
+            Action::CycleTimeFormat => {
+                serializer.serialize_unit_variant("Action", 82, "CycleTimeFormat")
+            }
+            Action::QueueReadLater => {
+                serializer.serialize_unit_variant("Action", 83, "QueueReadLater")
+            }
+            Action::ViewReadLater => {
+                serializer.serialize_unit_variant("Action", 84, "ViewReadLater")
+            }
+            Action::ToggleNotificationCenter => {
+                serializer.serialize_unit_variant("Action", 85, "ToggleNotificationCenter")
+            }
+            Action::SortByRank => serializer.serialize_unit_variant("Action", 86, "SortByRank"),
+            Action::OpenItemPrompt => {
+                serializer.serialize_unit_variant("Action", 87, "OpenItemPrompt")
+            }
             // Non-serializable variants
             _ => Err(serde::ser::Error::custom(
                 "Cannot serialize runtime-only Action variant",
@@ -196,13 +507,74 @@ impl<'de> serde::Deserialize<'de> for Action {
                     "SortByTime" => Ok(Action::SortByTime),
                     "ToggleSortOrder" => Ok(Action::ToggleSortOrder),
                     "SwitchTheme" => Ok(Action::SwitchTheme),
-                    "ClearNotification" => Ok(Action::ClearNotification),
                     "ToggleBookmark" => Ok(Action::ToggleBookmark),
                     "ViewBookmarks" => Ok(Action::ViewBookmarks),
                     "ExportBookmarks" => Ok(Action::ExportBookmarks),
                     "ImportBookmarks" => Ok(Action::ImportBookmarks),
                     "ViewHistory" => Ok(Action::ViewHistory),
                     "ClearHistory" => Ok(Action::ClearHistory),
+                    "RefreshVisibleStories" => Ok(Action::RefreshVisibleStories),
+                    "ToggleHideRead" => Ok(Action::ToggleHideRead),
+                    "MarkAllRead" => Ok(Action::MarkAllRead),
+                    "OpenHnPage" => Ok(Action::OpenHnPage),
+                    "CopyLink" => Ok(Action::CopyLink),
+                    "Upvote" => Ok(Action::Upvote),
+                    "Favorite" => Ok(Action::Favorite),
+                    "FocusNextPane" => Ok(Action::FocusNextPane),
+                    "CollapseAllComments" => Ok(Action::CollapseAllComments),
+                    "ExpandAllComments" => Ok(Action::ExpandAllComments),
+                    "ExportStory" => Ok(Action::ExportStory),
+                    "ExportStoryList" => Ok(Action::ExportStoryList),
+                    "ImportIdList" => Ok(Action::ImportIdList),
+                    "AddToReadingList" => Ok(Action::AddToReadingList),
+                    "ViewReadingLists" => Ok(Action::ViewReadingLists),
+                    "MoveItemUp" => Ok(Action::MoveItemUp),
+                    "MoveItemDown" => Ok(Action::MoveItemDown),
+                    "RemoveFromReadingList" => Ok(Action::RemoveFromReadingList),
+                    "ToggleItemDone" => Ok(Action::ToggleItemDone),
+                    "NextReadingList" => Ok(Action::NextReadingList),
+                    "PrevReadingList" => Ok(Action::PrevReadingList),
+                    "ToggleCacheStats" => Ok(Action::ToggleCacheStats),
+                    "ToggleConversationView" => Ok(Action::ToggleConversationView),
+                    "CopyCommentsLink" => Ok(Action::CopyCommentsLink),
+                    "OpenInReader" => Ok(Action::OpenInReader),
+                    "ToggleHideJobs" => Ok(Action::ToggleHideJobs),
+                    "ToggleHideSeen" => Ok(Action::ToggleHideSeen),
+                    "SnapshotThread" => Ok(Action::SnapshotThread),
+                    "ToggleThreadDiff" => Ok(Action::ToggleThreadDiff),
+                    "OpenFilterPanel" => Ok(Action::OpenFilterPanel),
+                    "MuteStory" => Ok(Action::MuteStory),
+                    "ToggleShowMuted" => Ok(Action::ToggleShowMuted),
+                    "SearchReading" => Ok(Action::SearchReading),
+                    "EditBookmarkTags" => Ok(Action::EditBookmarkTags),
+                    "NextBookmarkTag" => Ok(Action::NextBookmarkTag),
+                    "PrevBookmarkTag" => Ok(Action::PrevBookmarkTag),
+                    "EditBookmarkNote" => Ok(Action::EditBookmarkNote),
+                    "DeleteHistoryEntry" => Ok(Action::DeleteHistoryEntry),
+                    "OpenRandomBookmark" => Ok(Action::OpenRandomBookmark),
+                    "PrepareOfflinePack" => Ok(Action::PrepareOfflinePack),
+                    "OpenLinkPicker" => Ok(Action::OpenLinkPicker),
+                    "NextStory" => Ok(Action::NextStory),
+                    "PrevStory" => Ok(Action::PrevStory),
+                    "ScrollHalfPageDown" => Ok(Action::ScrollHalfPageDown),
+                    "ScrollHalfPageUp" => Ok(Action::ScrollHalfPageUp),
+                    "ScrollPageDown" => Ok(Action::ScrollPageDown),
+                    "ScrollPageUp" => Ok(Action::ScrollPageUp),
+                    "ScrollToTop" => Ok(Action::ScrollToTop),
+                    "ScrollToBottom" => Ok(Action::ScrollToBottom),
+                    "SendToPhone" => Ok(Action::SendToPhone),
+                    "OpenSettings" => Ok(Action::OpenSettings),
+                    "TogglePerfHud" => Ok(Action::TogglePerfHud),
+                    "Suspend" => Ok(Action::Suspend),
+                    "OpenInPager" => Ok(Action::OpenInPager),
+                    "OpenInEditor" => Ok(Action::OpenInEditor),
+                    "OpenCommandPalette" => Ok(Action::OpenCommandPalette),
+                    "CycleTimeFormat" => Ok(Action::CycleTimeFormat),
+                    "QueueReadLater" => Ok(Action::QueueReadLater),
+                    "ViewReadLater" => Ok(Action::ViewReadLater),
+                    "ToggleNotificationCenter" => Ok(Action::ToggleNotificationCenter),
+                    "SortByRank" => Ok(Action::SortByRank),
+                    "OpenItemPrompt" => Ok(Action::OpenItemPrompt),
                     _ => Err(de::Error::unknown_variant(
                         value,
                         &[
@@ -225,13 +597,74 @@ impl<'de> serde::Deserialize<'de> for Action {
                             "SortByTime",
                             "ToggleSortOrder",
                             "SwitchTheme",
-                            "ClearNotification",
                             "ToggleBookmark",
                             "ViewBookmarks",
                             "ExportBookmarks",
                             "ImportBookmarks",
                             "ViewHistory",
                             "ClearHistory",
+                            "RefreshVisibleStories",
+                            "ToggleHideRead",
+                            "MarkAllRead",
+                            "OpenHnPage",
+                            "CopyLink",
+                            "Upvote",
+                            "Favorite",
+                            "FocusNextPane",
+                            "CollapseAllComments",
+                            "ExpandAllComments",
+                            "ExportStory",
+                            "ExportStoryList",
+                            "ImportIdList",
+                            "AddToReadingList",
+                            "ViewReadingLists",
+                            "MoveItemUp",
+                            "MoveItemDown",
+                            "RemoveFromReadingList",
+                            "ToggleItemDone",
+                            "NextReadingList",
+                            "PrevReadingList",
+                            "ToggleCacheStats",
+                            "ToggleConversationView",
+                            "CopyCommentsLink",
+                            "OpenInReader",
+                            "ToggleHideJobs",
+                            "ToggleHideSeen",
+                            "SnapshotThread",
+                            "ToggleThreadDiff",
+                            "OpenFilterPanel",
+                            "MuteStory",
+                            "ToggleShowMuted",
+                            "SearchReading",
+                            "EditBookmarkTags",
+                            "NextBookmarkTag",
+                            "PrevBookmarkTag",
+                            "EditBookmarkNote",
+                            "DeleteHistoryEntry",
+                            "OpenRandomBookmark",
+                            "PrepareOfflinePack",
+                            "OpenLinkPicker",
+                            "NextStory",
+                            "PrevStory",
+                            "ScrollHalfPageDown",
+                            "ScrollHalfPageUp",
+                            "ScrollPageDown",
+                            "ScrollPageUp",
+                            "ScrollToTop",
+                            "ScrollToBottom",
+                            "SendToPhone",
+                            "OpenSettings",
+                            "TogglePerfHud",
+                            "Suspend",
+                            "OpenInPager",
+                            "OpenInEditor",
+                            "OpenCommandPalette",
+                            "CycleTimeFormat",
+                            "QueueReadLater",
+                            "ViewReadLater",
+                            "ToggleNotificationCenter",
+                            "SortByRank",
+                            "OpenItemPrompt",
                         ],
                     )),
                 }
@@ -273,6 +706,14 @@ impl<'de> serde::Deserialize<'de> for Action {
     }
 }
 
+/// When a key press was received, and how many queued actions it still waits for
+/// before the screen can reflect it.
+#[derive(Debug, Clone, Copy)]
+pub struct PendingInput {
+    received: std::time::Instant,
+    actions: usize,
+}
+
 /// Main application state.
 pub struct App {
     pub running: bool,
@@ -282,33 +723,91 @@ pub struct App {
     pub story_ids: Vec<u32>,
     pub loaded_count: usize,
     pub story_list_state: ListState,
+    /// Number of story rows that fit in the list viewport (updated on render)
+    pub list_viewport_rows: usize,
+    /// Formatted story list rows reused across frames
+    pub list_row_cache: crate::internal::ui::list_rows::RowCache,
+    /// `ui.list_view.row_template`, parsed when the config is loaded
+    pub row_template: Option<crate::internal::ui::row_template::RowTemplate>,
+    /// `ui.status_bar_format`, parsed when the config is loaded; None for the
+    /// built-in status bar
+    pub status_template: Option<crate::internal::ui::status_template::StatusTemplate>,
+    /// `ui.message_line_format`, parsed when the config is loaded
+    pub message_template: crate::internal::ui::status_template::StatusTemplate,
     pub current_list_type: StoryListType,
     pub sort_by: SortBy,
     pub sort_order: SortOrder,
+    /// Screen areas of the sort header columns drawn in the last frame
+    pub sort_header_hits: Vec<(ratatui::layout::Rect, SortBy)>,
     pub api_service: Arc<ApiService>,
     pub loading: bool,
     pub story_load_progress: Option<(usize, usize)>,
+    /// `(stories done, total)` while the offline pack is being prepared
+    pub offline_pack_progress: Option<(usize, usize)>,
+    /// Story ids of the lists in the last offline pack, shown when offline
+    pub offline_pack: crate::internal::offline_pack::OfflinePack,
+    /// Stories queued with `R` for background download
+    pub read_later: crate::internal::read_later::ReadLater,
+    /// Read-later queue popup, while open
+    pub read_later_view: Option<crate::internal::ui::read_later_view::ReadLaterView>,
+    /// `(fetched, total)` of the page of stories streaming in
+    pub story_fetch_progress: Option<(usize, usize)>,
     pub selected_story: Option<Story>,
     pub comments: Vec<CommentRow>,
     pub comment_ids: Vec<u32>,
     pub loaded_comments_count: usize,
     pub comments_loading: bool,
+    /// Top-level comments not streamed in yet
+    pub comment_roots_remaining: usize,
+    /// Number of the latest comment fetch; batches of earlier ones are dropped
+    pub comment_fetch: u64,
     /// Scroll offset for comments view (line-by-line scrolling)
-    pub comments_scroll: usize,
+    pub comments_scroll: crate::internal::ui::scroll::ScrollState,
+    /// `(first_line, comment_id)` for each rendered comment, in line order (updated on render)
+    pub comment_line_index: Vec<(usize, u32)>,
+    /// Wrapped comment lines reused across frames
+    pub comment_layout: crate::internal::ui::comment_view::CommentLayout,
+    /// Search through the loaded comments of the open story
+    pub comment_search: crate::internal::ui::comment_view::CommentSearch,
+    /// Wrapped article lines, reused across frames and scrolling
+    pub article_layout: crate::internal::ui::article_view::ArticleLayout,
     pub article_content: Option<Article>,
     pub article_for_story_id: Option<u32>,
     pub article_loading: bool,
-    pub article_scroll: usize,
+    pub article_scroll: crate::internal::ui::scroll::ScrollState,
     pub theme: TuiTheme,
     pub available_themes: Vec<(String, String)>,
     pub current_theme_index: usize,
     #[allow(dead_code)]
     pub terminal_mode: String,
-    pub notification: Option<crate::internal::notification::Notification>,
+    /// Toasts on screen and the notifications kept for the notification center
+    pub notifications: crate::internal::notification::NotificationCenter,
+    /// Notification center popup, while open: rows scrolled past
+    pub notification_center: Option<usize>,
+    /// When errors from background tasks were last shown
+    pub background_errors: crate::internal::notification::ErrorLimiter,
     pub spinner_state: usize,
     pub last_spinner_update: Option<tokio::time::Instant>,
     pub show_help: bool,
+    /// Cache statistics overlay (`Ctrl+k`)
+    pub show_cache_stats: bool,
+    /// Performance HUD (`Ctrl+g`)
+    pub show_perf_hud: bool,
+    /// Program to hand the terminal to before the next frame
+    pub external: Option<crate::internal::external::External>,
+    /// Saved session offered for restoring at launch (`restore_session`)
+    pub session_prompt: Option<crate::internal::session::Session>,
+    /// Story to focus once it shows up in the list, after a session restore
+    pending_selection: Option<u32>,
+    /// Page of the help overlay shown, from 0
     pub help_page: usize,
+    /// Pages the help overlay took when last drawn
+    pub help_pages: usize,
+    /// Release notes shown once after an upgrade; empty once dismissed
+    pub whats_new: Vec<&'static crate::internal::whats_new::Release>,
+    pub whats_new_scroll: crate::internal::ui::scroll::ScrollState,
+    /// Guided tour, while it runs
+    pub tutorial: Option<crate::internal::ui::tutorial::Tutorial>,
     pub input_mode: InputMode,
     pub search_query: crate::internal::search::SearchQuery,
     pub search_history: crate::internal::search::SearchHistory,
@@ -321,21 +820,127 @@ pub struct App {
     pub bookmarks: crate::internal::bookmarks::Bookmarks,
     pub cancellation_token: Option<tokio_util::sync::CancellationToken>,
     pub history: crate::internal::history::History,
+    /// When true, stories that have already been opened are hidden from the list
+    pub hide_read: bool,
+    /// Job and already-seen story filters, per list type
+    pub focus_filters: crate::internal::focus::FocusFilters,
+    /// Saved comment threads, by story id
+    pub thread_snapshots: crate::internal::thread_snapshot::ThreadSnapshots,
+    /// Changes since the snapshot of the open thread, while the diff is shown
+    pub thread_diff: Option<crate::internal::thread_snapshot::ThreadDiff>,
     pub keybindings: crate::internal::ui::keybindings::KeyBindingMap,
     pub theme_editor: crate::internal::ui::theme_editor::ThemeEditor,
     pub log_viewer: crate::internal::ui::log_viewer::LogViewer,
+    pub metrics: crate::internal::metrics::MetricsRegistry,
+    pub auth: crate::api::auth::AuthClient,
+    /// Reduced redraw/ASCII-only rendering for slow remote terminals
+    pub low_bandwidth: bool,
+    /// Frames drawn in default colors only (`NO_COLOR` or `accessibility.no_color`)
+    pub no_color: bool,
+    /// How timestamps are shown; starts as `ui.time_format` and is cycled with `Ctrl+t`
+    pub time_format: crate::utils::datetime::TimeFormat,
+    /// Pane receiving navigation keys when the split layout is showing both panes
+    pub focused_pane: crate::internal::ui::layout::Pane,
+    /// Set after `z` in the comments view; the next key picks the fold command
+    pub pending_fold_prefix: bool,
+    /// Options of the open story when it is a poll
+    pub poll: Option<crate::internal::models::Poll>,
+    /// First key of a chord and when it was pressed, while waiting for the second key
+    pub pending_chord: Option<(KeyEvent, std::time::Instant)>,
+    /// Last failed background operation, shown in the error panel until dismissed
+    pub last_error: Option<AppError>,
+    /// Top-level comments of the open story whose threads use the conversation view
+    pub conversation_threads: std::collections::BTreeSet<u32>,
+    /// Oldest key press not yet reflected on screen
+    pub pending_input: Option<PendingInput>,
+    /// Item ids of the ad-hoc list shown as `StoryListType::Custom`
+    pub custom_list: Vec<u32>,
+    pub reading_lists: crate::internal::reading_lists::ReadingLists,
+    /// Index of the reading list shown in the ReadingLists view
+    pub current_reading_list: usize,
+    /// Open "add to reading list" popup
+    pub list_picker: Option<crate::internal::ui::list_picker::ListPicker>,
+    /// Open story filter panel
+    pub filter_panel: Option<crate::internal::ui::filter_panel::FilterPanel>,
+    /// Open settings panel
+    pub settings_panel: Option<crate::internal::ui::settings_panel::SettingsPanel>,
+    /// Content of the config file as last loaded or saved, to tell our own saves
+    /// from edits made outside the app
+    pub config_text: Option<String>,
+    /// The visible-story refresh and live-update timers, respawned when the network
+    /// settings change
+    network_timers: Vec<tokio::task::JoinHandle<()>>,
+    pub mute_list: crate::internal::mute::MuteList,
+    /// Show muted stories and comments as usual
+    pub show_muted: bool,
+    /// Story whose domain or author the open mute prompt offers to mute
+    pub mute_prompt: Option<Story>,
+    /// Link waiting for confirmation before it opens (`ui.confirm_links`)
+    pub link_preview: Option<crate::internal::ui::link_preview::LinkPreview>,
+    /// Numbered links of the open page (`Ctrl+l`)
+    pub link_picker: Option<crate::internal::ui::link_picker::LinkPicker>,
+    pub command_palette: Option<crate::internal::ui::command_palette::CommandPalette>,
+    /// Draw URLs as OSC 8 hyperlinks (`ui.hyperlinks` and a terminal that supports them)
+    pub hyperlinks: bool,
+    /// Index of opened articles and comments; `None` unless `reading_index.enabled`
+    pub reading_index: Option<Arc<crate::internal::reading_index::ReadingIndex>>,
+    /// Open "Search my reading" popup
+    pub reading_search: Option<crate::internal::ui::reading_search::ReadingSearch>,
+    /// Latest copies of bookmarked and watched stories from the startup prefetch
+    pub prefetched: std::collections::HashMap<u32, Story>,
+    /// Fetches the next stories and the highlighted story's article ahead of time
+    prefetcher: crate::internal::ui::prefetch::Prefetcher,
+    /// Open prompt for the tags of a bookmark
+    pub tag_prompt: Option<crate::internal::ui::tag_prompt::TagPrompt>,
+    /// Open prompt for an item id or URL to jump to
+    pub item_prompt: Option<crate::internal::ui::item_prompt::ItemPrompt>,
+    /// Open editor for the note of a bookmark
+    pub note_editor: Option<crate::internal::ui::note_editor::NoteEditor>,
+    /// Tag (or tag folder) the Bookmarks view is limited to
+    pub bookmark_tag_filter: Option<String>,
+    /// Reading time in minutes of the articles loaded, by story id
+    pub reading_minutes: std::collections::HashMap<u32, usize>,
+    /// Story count of each category and when its ids were last loaded (Unix seconds)
+    pub list_refreshed: std::collections::HashMap<StoryListType, (usize, i64)>,
+    /// Fuzzy search narrowing the History view
+    pub history_query: String,
+    /// Input for `history_query`, open while it is being typed
+    pub history_search: Option<crate::internal::ui::text_input::TextInput>,
+    /// When a refresh last changed a story's score or comment count
+    updated_at: std::collections::HashMap<u32, std::time::Instant>,
+    /// Stories auto-bookmark rules already fired for this session
+    auto_bookmark_seen: std::collections::HashSet<u32>,
+    /// Scores and ranks saved by the previous session
+    pub previous_snapshot: crate::internal::snapshot::StorySnapshot,
+    /// Snapshot written at exit for the next session
+    story_snapshot: crate::internal::snapshot::StorySnapshot,
+    /// Score samples of stories as they load and refresh, for the trend sparklines
+    pub story_trends: crate::internal::trends::StoryTrends,
+    /// Publisher names and brand colors of story sites, for the domain chips
+    pub domain_meta: crate::internal::domain_meta::DomainMeta,
+    /// User scripts from the scripts directory
+    pub scripts: crate::internal::scripting::ScriptHost,
 }
 
 impl App {
+    /// The app with the default config, ignoring any config.ron around.
     #[tracing::instrument]
     pub fn new() -> Self {
+        Self::with_config(AppConfig::default(), false)
+    }
+
+    /// Create the app from an already loaded config; `offline` disables all network access.
+    #[tracing::instrument(skip(config))]
+    pub fn with_config(config: AppConfig, offline: bool) -> Self {
         let start = std::time::Instant::now();
         let (action_tx, action_rx) = mpsc::unbounded_channel();
-        let config = AppConfig::load();
-        let api_service = Arc::new(ApiService::new(
-            config.network.clone(),
-            config.logging.enable_performance_metrics,
-        ));
+        let api_service = Arc::new(
+            ApiService::new(
+                config.network.clone(),
+                config.logging.enable_performance_metrics,
+            )
+            .with_offline(offline),
+        );
 
         // Detect terminal mode (dark or light)
         let terminal_mode = Self::detect_terminal_mode();
@@ -396,19 +1001,54 @@ impl App {
             }
         };
 
-        let history = match crate::internal::history::History::load_or_create(50) {
+        let history = match crate::internal::history::History::load_or_create(
+            config.history.max_entries,
+            config.history.max_read_ids,
+        ) {
             Ok(h) => h,
             Err(e) => {
                 tracing::error!("Failed to load history: {}", e);
-                crate::internal::history::History::new(50)
+                crate::internal::history::History::new(
+                    config.history.max_entries,
+                    config.history.max_read_ids,
+                )
             }
         };
 
-        let mut keybindings =
-            crate::internal::ui::keybindings_default::create_default_keybindings();
-        if let Some(custom_bindings) = &config.keybindings {
-            keybindings.merge_config(custom_bindings);
-        }
+        let previous_snapshot = match crate::internal::snapshot::StorySnapshot::load_or_create() {
+            Ok(s) => s,
+            Err(e) => {
+                tracing::error!("Failed to load story snapshot: {}", e);
+                crate::internal::snapshot::StorySnapshot::new()
+            }
+        };
+
+        let domain_meta = match crate::internal::domain_meta::DomainMeta::load_or_create() {
+            Ok(meta) => meta,
+            Err(e) => {
+                tracing::error!("Failed to load domain metadata: {}", e);
+                crate::internal::domain_meta::DomainMeta::new()
+            }
+        };
+
+        let story_trends = match config.ui.list_view.trends {
+            crate::config::TrendsMode::Persist => {
+                crate::internal::trends::StoryTrends::load_or_create(
+                    jiff::Timestamp::now().as_second(),
+                )
+                .unwrap_or_else(|e| {
+                    tracing::error!("Failed to load story trends: {}", e);
+                    crate::internal::trends::StoryTrends::new()
+                })
+            }
+            _ => crate::internal::trends::StoryTrends::new(),
+        };
+
+        let keybindings = Self::build_keybindings(&config);
+        let row_template = Self::build_row_template(&config);
+        let (status_template, message_template) = Self::build_status_templates(&config);
+
+        let scripts = crate::internal::scripting::ScriptHost::load(&config.scripting);
 
         tracing::info!(elapsed = ?start.elapsed(), "App initialized");
 
@@ -420,31 +1060,85 @@ impl App {
             story_ids: Vec::new(),
             loaded_count: 0,
             story_list_state: ListState::default(),
+            list_viewport_rows: 0,
+            list_row_cache: Default::default(),
+            row_template,
+            status_template,
+            message_template,
             current_list_type: StoryListType::Top,
             sort_by: SortBy::Time,
             sort_order: SortOrder::Descending,
+            sort_header_hits: Vec::new(),
             api_service,
             loading: false,
             story_load_progress: None,
+            offline_pack_progress: None,
+            offline_pack: match crate::internal::offline_pack::OfflinePack::load_or_create() {
+                Ok(pack) => pack,
+                Err(e) => {
+                    tracing::error!("Failed to load offline pack: {}", e);
+                    crate::internal::offline_pack::OfflinePack::new()
+                }
+            },
+            read_later: match crate::internal::read_later::ReadLater::load_or_create() {
+                Ok(mut queue) => {
+                    queue.prune(
+                        jiff::Timestamp::now().as_second(),
+                        config.read_later.keep_days,
+                    );
+                    queue
+                }
+                Err(e) => {
+                    tracing::error!("Failed to load read-later queue: {}", e);
+                    crate::internal::read_later::ReadLater::new()
+                }
+            },
+            read_later_view: None,
+            story_fetch_progress: None,
             selected_story: None,
             comments: Vec::new(),
             comment_ids: Vec::new(),
             loaded_comments_count: 0,
             comments_loading: false,
-            comments_scroll: 0,
+            comment_roots_remaining: 0,
+            comment_fetch: 0,
+            comments_scroll: Default::default(),
+            comment_line_index: Vec::new(),
+            comment_layout: Default::default(),
+            comment_search: Default::default(),
+            article_layout: Default::default(),
             article_content: None,
             article_for_story_id: None,
             article_loading: false,
-            article_scroll: 0,
+            article_scroll: Default::default(),
             theme: theme.clone(),
             available_themes,
             current_theme_index,
             terminal_mode,
-            notification: None,
+            notifications: crate::internal::notification::NotificationCenter::new(
+                config.ui.notifications.clone(),
+            ),
+            notification_center: None,
+            background_errors: Default::default(),
             spinner_state: 0,
             last_spinner_update: None,
             show_help: false,
+            show_cache_stats: false,
+            show_perf_hud: false,
+            external: None,
+            session_prompt: match config.restore_session {
+                true => crate::internal::session::Session::load().unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load the last session: {:#}", e);
+                    None
+                }),
+                false => None,
+            },
+            pending_selection: None,
             help_page: 0,
+            help_pages: 1,
+            whats_new: Vec::new(),
+            whats_new_scroll: Default::default(),
+            tutorial: None,
             input_mode: InputMode::Normal,
             search_query: crate::internal::search::SearchQuery::default(),
             search_history: match crate::internal::search::SearchHistory::load_or_create(20) {
@@ -462,16 +1156,97 @@ impl App {
             bookmarks,
             cancellation_token: None,
             history,
+            hide_read: false,
+            focus_filters: match crate::internal::focus::FocusFilters::load_or_create() {
+                Ok(filters) => filters,
+                Err(e) => {
+                    tracing::error!("Failed to load focus filters: {}", e);
+                    crate::internal::focus::FocusFilters::new()
+                }
+            },
+            thread_snapshots:
+                match crate::internal::thread_snapshot::ThreadSnapshots::load_or_create() {
+                    Ok(snapshots) => snapshots,
+                    Err(e) => {
+                        tracing::error!("Failed to load thread snapshots: {}", e);
+                        crate::internal::thread_snapshot::ThreadSnapshots::new()
+                    }
+                },
+            thread_diff: None,
             keybindings,
             theme_editor: crate::internal::ui::theme_editor::ThemeEditor::new(theme.clone()),
             log_viewer: crate::internal::ui::log_viewer::LogViewer::new(
-                config
-                    .logging
-                    .log_directory
-                    .as_deref()
-                    .unwrap_or("logs")
-                    .to_string(),
+                config.logging.directory().to_string_lossy().to_string(),
             ),
+            metrics: crate::internal::metrics::MetricsRegistry::new(),
+            auth: crate::api::auth::AuthClient::new(),
+            low_bandwidth: crate::internal::ui::low_bandwidth::is_enabled(config.ui.low_bandwidth),
+            no_color: crate::internal::ui::no_color::is_enabled(config.accessibility.no_color),
+            time_format: config.ui.time_format,
+            focused_pane: crate::internal::ui::layout::Pane::default(),
+            pending_fold_prefix: false,
+            poll: None,
+            pending_chord: None,
+            last_error: None,
+            conversation_threads: Default::default(),
+            pending_input: None,
+            custom_list: Vec::new(),
+            reading_lists: match crate::internal::reading_lists::ReadingLists::load_or_create() {
+                Ok(lists) => lists,
+                Err(e) => {
+                    tracing::error!("Failed to load reading lists: {}", e);
+                    crate::internal::reading_lists::ReadingLists::new()
+                }
+            },
+            current_reading_list: 0,
+            list_picker: None,
+            filter_panel: None,
+            settings_panel: None,
+            config_text: None,
+            network_timers: Vec::new(),
+            mute_list: match crate::internal::mute::MuteList::load_or_create() {
+                Ok(list) => list,
+                Err(e) => {
+                    tracing::error!("Failed to load mute list: {}", e);
+                    crate::internal::mute::MuteList::new()
+                }
+            },
+            show_muted: false,
+            mute_prompt: None,
+            link_preview: None,
+            link_picker: None,
+            command_palette: None,
+            hyperlinks: config.ui.hyperlinks && crate::internal::ui::hyperlink::supported(),
+            reading_index: match config.reading_index.enabled {
+                true => match crate::internal::reading_index::ReadingIndex::open(
+                    std::path::Path::new(&config.reading_index.directory),
+                ) {
+                    Ok(index) => Some(Arc::new(index)),
+                    Err(e) => {
+                        tracing::error!("Failed to open reading index: {:#}", e);
+                        None
+                    }
+                },
+                false => None,
+            },
+            reading_search: None,
+            prefetched: std::collections::HashMap::new(),
+            prefetcher: Default::default(),
+            tag_prompt: None,
+            item_prompt: None,
+            note_editor: None,
+            bookmark_tag_filter: None,
+            reading_minutes: std::collections::HashMap::new(),
+            list_refreshed: std::collections::HashMap::new(),
+            history_query: String::new(),
+            history_search: None,
+            auto_bookmark_seen: std::collections::HashSet::new(),
+            updated_at: std::collections::HashMap::new(),
+            previous_snapshot: previous_snapshot.clone(),
+            story_snapshot: previous_snapshot,
+            story_trends,
+            domain_meta,
+            scripts,
         };
 
         // Re-check for conflicts to show notification and log details
@@ -498,35 +1273,43 @@ impl App {
 
     /// Set an info notification
     pub fn notify_info(&mut self, message: impl Into<String>) {
-        self.notification = Some(crate::internal::notification::Notification::info(message));
+        self.notify(crate::internal::notification::Notification::info(message));
+    }
+
+    /// Show `notification` over the others on screen, or count it on one that says
+    /// the same thing.
+    fn notify(&mut self, notification: crate::internal::notification::Notification) {
+        self.notifications.push(notification);
     }
 
     /// Set a warning notification
     #[allow(dead_code)]
     pub fn notify_warning(&mut self, message: impl Into<String>) {
-        self.notification = Some(crate::internal::notification::Notification::warning(
+        self.notify(crate::internal::notification::Notification::warning(
             message,
         ));
     }
 
     /// Set an error notification
     pub fn notify_error(&mut self, message: impl Into<String>) {
-        self.notification = Some(crate::internal::notification::Notification::error(message));
+        self.notify(crate::internal::notification::Notification::error(message));
     }
 
-    /// Clear the current notification
-    pub fn clear_notification(&mut self) {
-        self.notification = None;
+    /// Show an error from a background task, unless the same error was shown recently.
+    fn notify_background_error(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        match self
+            .background_errors
+            .allow(&message, std::time::Instant::now())
+        {
+            true => self.notify_error(message),
+            false => tracing::debug!("Repeated background error not shown: {}", message),
+        }
     }
 
-    /// Check if the current notification should be dismissed
-    #[allow(dead_code)]
-    pub fn should_dismiss_notification(&self) -> bool {
-        if let Some(notification) = &self.notification {
-            notification.should_dismiss()
-        } else {
-            false
-        }
+    /// Drop the toasts whose time is up; true if any went.
+    pub fn clear_notification(&mut self) -> bool {
+        self.notifications.expire()
     }
 
     /// Detect terminal background mode (light or dark)
@@ -576,80 +1359,82 @@ impl App {
             }
         }
 
-        // Collect candidate theme locations in priority order:
-        // 1. Configured theme_directory (from config)
-        // 2. Explicit configured path (theme_file if non-empty)
-        // 3. ./themes in current working directory
-        // 4. <exe_dir>/themes (next to executable)
-        let mut themes = Vec::new();
-        let mut candidates: Vec<PathBuf> = Vec::new();
-
-        // 1) Configured theme_directory
-        if !theme_directory.trim().is_empty() {
-            candidates.push(theme_dir_path.clone());
-        }
-
-        // 2) Configured theme_file path (may be a file or directory)
-        if !configured.trim().is_empty() {
-            candidates.push(PathBuf::from(configured));
-        }
-
-        // 3) Current working directory ./themes
-        candidates.push(PathBuf::from("themes"));
+        // Both variants of every theme file, in priority order, then the built-in
+        // themes no file replaces
+        let files = crate::utils::theme_loader::theme_files(theme_directory, configured);
+        let builtin = crate::utils::theme_loader::builtin_theme_paths(&files);
+        files
+            .into_iter()
+            .chain(builtin)
+            .filter_map(|path| path.to_str().map(str::to_string))
+            .flat_map(|path| {
+                [
+                    (path.clone(), "dark".to_string()),
+                    (path, "light".to_string()),
+                ]
+            })
+            .collect()
+    }
 
-        // 4) themes next to the executable (if available)
-        if let Ok(exe) = std::env::current_exe()
-            && let Some(dir) = exe.parent()
-        {
-            candidates.push(dir.join("themes"));
+    /// Default keys, with the config's own bindings and plugin keys on top.
+    fn build_keybindings(config: &AppConfig) -> crate::internal::ui::keybindings::KeyBindingMap {
+        let mut keybindings =
+            crate::internal::ui::keybindings_default::create_default_keybindings();
+        if let Some(custom_bindings) = &config.keybindings {
+            keybindings.merge_config(custom_bindings);
         }
-
-        // Walk candidates and gather .json theme files. If a candidate is a file,
-        // consider it directly; if it's a directory read its entries.
-        for cand in candidates.into_iter() {
-            if !cand.exists() {
+        // Plugin keys work in every view
+        for (index, plugin) in config.plugins.iter().enumerate() {
+            let Some(key_str) = &plugin.key else {
                 continue;
-            }
-
-            match (cand.is_file(), std::fs::read_dir(&cand)) {
-                (true, _) => {
-                    if let Some(ext) = cand.extension().and_then(|s| s.to_str())
-                        && ext.eq_ignore_ascii_case("json")
-                        && let Some(filename) = cand.to_str()
-                    {
-                        themes.push((filename.to_string(), "dark".to_string()));
-                        themes.push((filename.to_string(), "light".to_string()));
-                    }
-                }
-                (false, Ok(entries)) => {
-                    for entry in entries.flatten() {
-                        let path = entry.path();
-                        if path.extension().and_then(|s| s.to_str()) == Some("json")
-                            && let Some(filename) = path.to_str()
-                        {
-                            themes.push((filename.to_string(), "dark".to_string()));
-                            themes.push((filename.to_string(), "light".to_string()));
-                        }
-                    }
-                }
-                _ => {}
+            };
+            match crate::internal::ui::keybindings::parse_key_str(key_str) {
+                Some(key) => keybindings.add_binding(
+                    crate::internal::ui::keybindings::KeyBindingContext::Global,
+                    key,
+                    Action::RunPlugin(index),
+                ),
+                None => tracing::warn!("Invalid key {:?} for plugin {}", key_str, plugin.name),
             }
         }
+        keybindings
+    }
 
-        // Deduplicate while preserving order of discovery
-        let mut seen = std::collections::HashSet::new();
-        themes.retain(|(p, mode)| {
-            let key = format!("{}:{}", p, mode);
-            match seen.contains(&key) {
-                true => false,
-                false => {
-                    seen.insert(key);
-                    true
-                }
-            }
-        });
+    /// The configured row template; one that does not parse is logged and the
+    /// default layout is used.
+    fn build_row_template(
+        config: &AppConfig,
+    ) -> Option<crate::internal::ui::row_template::RowTemplate> {
+        let template = config.ui.list_view.row_template.as_deref()?;
+        crate::internal::ui::row_template::RowTemplate::parse(template)
+            .inspect_err(|e| tracing::warn!("Invalid row template {:?}: {}", template, e))
+            .ok()
+    }
 
-        themes
+    /// The configured status bar and message line formats. A status bar format that
+    /// does not parse is logged and the built-in status bar is used; a message line
+    /// format, the default one.
+    fn build_status_templates(
+        config: &AppConfig,
+    ) -> (
+        Option<crate::internal::ui::status_template::StatusTemplate>,
+        crate::internal::ui::status_template::StatusTemplate,
+    ) {
+        use crate::internal::ui::status_template::StatusTemplate;
+        let parse = |format: &str| {
+            StatusTemplate::parse(format)
+                .inspect_err(|e| tracing::warn!("Invalid status bar format {:?}: {}", format, e))
+                .ok()
+        };
+        let status = match config.ui.status_bar_format.as_str() {
+            "" => None,
+            format => parse(format),
+        };
+        let message = parse(&config.ui.message_line_format).unwrap_or_else(|| {
+            StatusTemplate::parse(&crate::config::UIConfig::default().message_line_format)
+                .expect("default message line format parses")
+        });
+        (status, message)
     }
 
     /// Centralized theme selection logic extracted from `new`.
@@ -770,7 +1555,7 @@ impl App {
                 mode,
                 config.logging.enable_performance_metrics,
             ) {
-                Ok(theme) => (theme, index),
+                Ok(theme) => (Self::accessible_theme(config, theme, mode), index),
                 Err(e) => {
                     tracing::error!("Failed to load theme '{}': {}", path, e);
                     (TuiTheme::default(), 0)
@@ -780,10 +1565,38 @@ impl App {
         }
     }
 
+    /// The theme as shown: with `accessibility.color_blind_safe` on, its hues are
+    /// moved onto a color-blind-safe palette, and with `high_contrast_mode` on its
+    /// colors are then pushed past the WCAG contrast thresholds.
+    fn accessible_theme(
+        config: &crate::config::AppConfig,
+        theme: TuiTheme,
+        mode: &str,
+    ) -> TuiTheme {
+        let theme = match config.accessibility.color_blind_safe {
+            true => crate::utils::contrast::color_blind_safe(&theme),
+            false => theme,
+        };
+        match config.accessibility.high_contrast_mode {
+            true => {
+                crate::utils::contrast::high_contrast(&theme, !mode.eq_ignore_ascii_case("light"))
+            }
+            false => theme,
+        }
+    }
+
+    /// Cancel the fetches in flight. A story page cut off mid-stream sends no final
+    /// batch, so the list stops loading here; `LoadMoreStories` picks up after the
+    /// stories that did arrive.
     pub fn cancel_previous_request(&mut self) {
         if let Some(token) = self.cancellation_token.take() {
             token.cancel();
         }
+        // Load all reports its partial result itself
+        if self.story_load_progress.is_none() {
+            self.loading = false;
+            self.story_fetch_progress = None;
+        }
         self.cancellation_token = Some(tokio_util::sync::CancellationToken::new());
     }
 
@@ -792,21 +1605,118 @@ impl App {
     }
 
     pub async fn run(&mut self, mut tui: crate::tui::Tui) -> Result<()> {
-        // Initial load
-        let _ = self
-            .action_tx
-            .send(Action::LoadStories(self.current_list_type));
+        // Initial load; offline there is nothing to fetch, so open the saved bookmarks
+        let packed = self.offline_pack.ids(self.current_list_type).is_some();
+        let initial = match (self.api_service.is_offline(), packed) {
+            (true, true) => {
+                self.notify_info("Offline mode: showing the offline pack");
+                Action::LoadStories(self.current_list_type)
+            }
+            (true, false) => {
+                self.notify_info("Offline mode: showing bookmarks");
+                Action::ViewBookmarks
+            }
+            (false, _) => Action::LoadStories(self.current_list_type),
+        };
+        let _ = self.action_tx.send(initial);
+        // Downloads left in the queue by the last session carry on
+        self.download_next_read_later();
+
+        // Show what changed since the previous version, once; a first run starts
+        // the guided tour instead
+        match crate::internal::whats_new::LastRun::load_or_create().and_then(|mut last_run| {
+            let first_run = last_run.version.is_none() && !last_run.untracked_upgrade;
+            last_run
+                .upgrade_to(&self.app_version)
+                .map(|notes| (first_run, notes))
+        }) {
+            Ok((first_run, notes)) => {
+                self.whats_new = notes;
+                if first_run {
+                    self.tutorial = Some(Default::default());
+                }
+            }
+            Err(e) => tracing::warn!("Failed to check the last run version: {:#}", e),
+        }
+        if let Some(summary) = self.config.migration_summary.clone() {
+            self.notify_info(summary);
+        }
+
+        self.scripts.on_start(None);
+        self.apply_script_effects();
+
+        if self.config.startup_prefetch.enabled && !self.api_service.is_offline() {
+            self.spawn_startup_prefetch();
+        }
+
+        let frame = std::time::Duration::from_millis(match self.low_bandwidth {
+            true => crate::internal::ui::low_bandwidth::FRAME_MS,
+            false => 16,
+        });
+        let mut events = EventStream::new();
+
+        if self.config.ui.list_view.sort_header
+            && let Err(e) = crate::tui::enable_mouse_capture()
+        {
+            tracing::warn!("Failed to enable mouse capture: {}", e);
+        }
+
+        self.spawn_network_timers();
+
+        // Ctrl+Z arrives as a key in raw mode; a SIGTSTP sent from elsewhere suspends
+        // the same way, restoring the terminal first
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{SignalKind, signal};
+            match signal(SignalKind::from_raw(libc::SIGTSTP)) {
+                Ok(mut stop) => {
+                    let tx = self.action_tx.clone();
+                    tokio::spawn(async move {
+                        while stop.recv().await.is_some() {
+                            if tx.send(Action::Suspend).is_err() {
+                                break;
+                            }
+                        }
+                    });
+                }
+                Err(e) => tracing::warn!("Failed to listen for SIGTSTP: {}", e),
+            }
+        }
 
-        let mut event_interval = tokio::time::interval(std::time::Duration::from_millis(16));
+        // Reload the config when it is edited outside the app
+        let config_path = self.config.save_path();
+        self.config_text = std::fs::read_to_string(&config_path).ok();
+        let tx = self.action_tx.clone();
+        tokio::spawn(async move {
+            let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+            let mut last = modified(&config_path);
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+            loop {
+                interval.tick().await;
+                let current = modified(&config_path);
+                if current != last {
+                    last = current;
+                    if tx.send(Action::ReloadConfig).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
 
+        // Only redraw when an action, input event or timer changed something, and no
+        // more often than once a frame
+        let mut dirty = true;
+        let mut last_draw: Option<tokio::time::Instant> = None;
         loop {
             // Update spinner animation every 100ms
             let now = tokio::time::Instant::now();
             match self.last_spinner_update {
+                _ if self.low_bandwidth => {}
                 Some(last_update) => {
-                    if now.duration_since(last_update).as_millis() >= 100 {
+                    if now.duration_since(last_update) >= SPINNER_STEP {
                         self.spinner_state = self.spinner_state.wrapping_add(1);
                         self.last_spinner_update = Some(now);
+                        dirty |= self.is_busy();
                     }
                 }
                 None => {
@@ -815,25 +1725,82 @@ impl App {
             }
 
             // Auto-dismiss expired notifications
-            if let Some(notification) = &self.notification
-                && notification.should_dismiss()
-            {
-                self.clear_notification();
+            if self.clear_notification() {
+                dirty = true;
+            }
+
+            self.prefetch_ahead();
+
+            let next_frame = last_draw.map(|at| at + frame);
+            if dirty && next_frame.is_none_or(|at| at <= now) {
+                self.tick_scroll_animations();
+                tui.draw(|f| self.ui(f))?;
+                self.record_input_latency();
+                dirty = false;
+                last_draw = Some(now);
             }
 
-            tui.draw(|f| self.ui(f))?;
+            // Sleep until a timer is due, or until the frame held back above
+            let mut wake = self.next_wake(now, frame);
+            if dirty && let Some(at) = next_frame {
+                wake = wake.min(at);
+            }
 
             tokio::select! {
-                _ = event_interval.tick() => {
-                    // Check for terminal events
-                    if event::poll(std::time::Duration::from_millis(0))?
-                        && let Event::Key(key) = event::read()?
-                            && key.kind == KeyEventKind::Press {
-                                self.handle_key_event(key);
-                            }
+                () = tokio::time::sleep_until(wake) => {
+                    if self.config.logging.enable_performance_metrics {
+                        self.metrics.record(
+                            crate::internal::metrics::EVENT_LOOP_LAG,
+                            wake.elapsed(),
+                        );
+                    }
+                    self.expire_chord();
+                    dirty = true;
                 }
+                event = events.next() => match event {
+                    Some(Ok(Event::Key(key))) if key.kind == KeyEventKind::Press => {
+                        let received = std::time::Instant::now();
+                        self.handle_key_event(key);
+                        self.input_received(received);
+                        dirty = true;
+                    }
+                    Some(Ok(Event::Mouse(mouse))) if mouse.kind != MouseEventKind::Moved => {
+                        self.handle_mouse_event(mouse);
+                        dirty = true;
+                    }
+                    Some(Ok(Event::Resize(..))) => dirty = true,
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return Err(e.into()),
+                    None => break,
+                },
                 Some(action) = self.action_rx.recv() => {
-                    self.handle_action(action).await;
+                    dirty = true;
+                    self.dispatch(action).await;
+                    self.input_action_dispatched();
+                    if self.low_bandwidth {
+                        // Batch whatever else is queued into the same frame
+                        for _ in 0..crate::internal::ui::low_bandwidth::MAX_BATCHED_ACTIONS {
+                            match self.action_rx.try_recv() {
+                                Ok(action) => {
+                                    self.dispatch(action).await;
+                                    self.input_action_dispatched();
+                                }
+                                Err(_) => break,
+                            }
+                        }
+                    }
+                }
+            }
+
+            if let Some(external) = self.external.take() {
+                // Stop reading the terminal so the program gets all the input
+                drop(events);
+                let result = crate::tui::release(&mut tui, || external.run())?;
+                events = EventStream::new();
+                dirty = true;
+                if let Err(e) = result {
+                    tracing::warn!("{:#}", e);
+                    self.notify_error(format!("{:#}", e));
                 }
             }
 
@@ -841,1259 +1808,3922 @@ impl App {
                 break;
             }
         }
+
+        if let Err(e) = self.story_snapshot.save() {
+            tracing::warn!(%e, "Failed to save story snapshot");
+        }
+        if let Err(e) = self.story_trends.save() {
+            tracing::warn!(%e, "Failed to save story trends");
+        }
+        if let Err(e) = self.domain_meta.save() {
+            tracing::warn!("Failed to save domain metadata: {:#}", e);
+        }
+        if self.config.restore_session
+            && let Err(e) = self.session().save()
+        {
+            tracing::warn!("Failed to save the session: {:#}", e);
+        }
+        if let Err(e) = self.api_service.persist_caches() {
+            tracing::warn!("Failed to save disk cache: {:#}", e);
+        }
         Ok(())
     }
 
-    fn handle_key_event(&mut self, key: KeyEvent) {
-        // Global toggle for log viewer with plain 'L'
-        if key.code == KeyCode::Char('L') {
-            self.log_viewer.toggle();
-            return;
+    /// Where this session is, for `restore_session`.
+    pub fn session(&self) -> crate::internal::session::Session {
+        crate::internal::session::Session {
+            list_type: self.current_list_type,
+            custom_list: match self.current_list_type {
+                StoryListType::Custom => self.custom_list.clone(),
+                _ => Vec::new(),
+            },
+            view_mode: self.view_mode,
+            story: self.selected_story.clone(),
+            selected_id: self
+                .story_list_state
+                .selected()
+                .and_then(|idx| self.filtered_story_indices().get(idx).map(|(_, s)| s.id)),
+            comments_offset: self.comments_scroll.offset(),
+            article_offset: self.article_scroll.offset(),
+            sort_by: self.sort_by,
+            sort_order: self.sort_order,
+            search: self.search_query.query.clone(),
+            search_mode: self.search_query.mode,
+            search_type: self.search_query.search_type,
         }
+    }
 
-        // If log viewer is visible, it traps input
-        if self.log_viewer.visible {
-            match key.code {
-                KeyCode::Esc | KeyCode::Char('q') => self.log_viewer.toggle(),
-                KeyCode::Char('j') | KeyCode::Down => self.log_viewer.scroll_down(),
-                KeyCode::Char('k') | KeyCode::Up => self.log_viewer.scroll_up(),
-                KeyCode::Char('G') => self.log_viewer.scroll_to_bottom(),
-                KeyCode::Tab => self.log_viewer.next_tab(),
-                _ => {}
+    /// Go back to a saved session: reload its list, reopen its story or view, then
+    /// apply the sort, search and scroll positions once those have been handled.
+    fn restore_session(&mut self, session: crate::internal::session::Session) {
+        if session.list_type == StoryListType::Custom {
+            self.custom_list = session.custom_list.clone();
+        }
+        self.pending_selection = session.selected_id;
+        let mut actions = vec![Action::LoadStories(session.list_type)];
+        match (session.view_mode, &session.story) {
+            (ViewMode::StoryDetail | ViewMode::Article, Some(story)) => {
+                actions.push(Action::SelectStory(story.clone(), session.list_type));
+                if session.view_mode == ViewMode::Article {
+                    actions.push(Action::ToggleArticleView);
+                }
             }
-            return;
+            (ViewMode::Bookmarks, _) => actions.push(Action::ViewBookmarks),
+            (ViewMode::History, _) => actions.push(Action::ViewHistory),
+            (ViewMode::ReadingLists, _) => actions.push(Action::ViewReadingLists),
+            _ => {}
+        }
+        actions.push(Action::SessionRestored(Box::new(session)));
+        for action in actions {
+            let _ = self.action_tx.send(action);
         }
+    }
 
-        match self.input_mode {
-            InputMode::Search | InputMode::SearchOptions => self.handle_search_input(key),
-            InputMode::Normal => self.handle_normal_input(key),
+    /// Write bookmarks, history and reading lists to disk, for when `run` did not
+    /// finish (a panic). Each is normally saved as it changes.
+    pub fn save_state(&self) {
+        if let Err(e) = self.bookmarks.save() {
+            tracing::warn!("Failed to save bookmarks: {:#}", e);
+        }
+        if let Err(e) = self.history.save() {
+            tracing::warn!("Failed to save history: {:#}", e);
+        }
+        if let Err(e) = self.reading_lists.save() {
+            tracing::warn!("Failed to save reading lists: {:#}", e);
         }
     }
 
-    fn handle_search_input(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Char('/') => {
-                // Ignore / in search mode (it's the key that enters search mode)
-            }
-            KeyCode::Char('m')
-                if key
-                    .modifiers
-                    .contains(crossterm::event::KeyModifiers::CONTROL) =>
-            {
-                // Cycle search mode
-                self.search_query.mode = self.search_query.mode.next();
-            }
-            KeyCode::F(2) => {
-                // Also cycle search mode
-                self.search_query.mode = self.search_query.mode.next();
-            }
-            KeyCode::Char('r')
-                if key
-                    .modifiers
-                    .contains(crossterm::event::KeyModifiers::CONTROL) =>
-            {
-                // Toggle regex
-                self.search_query.search_type = self.search_query.search_type.toggle();
-                // Recompile with current temp input
-                self.search_query = crate::internal::search::SearchQuery::new(
-                    self.temp_search_input.clone(),
-                    self.search_query.mode,
-                    self.search_query.search_type,
-                );
+    /// Advance page-scroll animations by a frame. Low-bandwidth mode skips the
+    /// animation and lands on the target right away, to keep redraws down.
+    fn tick_scroll_animations(&mut self) {
+        for scroll in [
+            &mut self.comments_scroll,
+            &mut self.article_scroll,
+            &mut self.whats_new_scroll,
+            &mut self.log_viewer.scroll,
+        ] {
+            while scroll.tick() && self.low_bandwidth {}
+        }
+    }
+
+    /// Whether something is loading, so the spinner needs to keep turning.
+    fn is_busy(&self) -> bool {
+        self.active_loading_count() > 0
+            || self.story_load_progress.is_some()
+            || self.offline_pack_progress.is_some()
+            || self
+                .link_preview
+                .as_ref()
+                .is_some_and(|preview| preview.resolved.is_none())
+    }
+
+    /// When the run loop has to wake up with no input: the next frame of a scroll
+    /// animation, spinner step, notification or chord timeout, or the end of a row's
+    /// update highlight. With nothing pending the screen is redrawn once a minute, on
+    /// the turn of the minute while times are relative so they stay current.
+    fn next_wake(
+        &self,
+        now: tokio::time::Instant,
+        frame: std::time::Duration,
+    ) -> tokio::time::Instant {
+        let from_std = tokio::time::Instant::from_std;
+        let idle = match self.time_format {
+            crate::utils::datetime::TimeFormat::Relative => {
+                let into_minute = jiff::Timestamp::now().as_millisecond().rem_euclid(60_000);
+                std::time::Duration::from_millis((60_000 - into_minute) as u64)
             }
-            KeyCode::F(3) => {
-                // Also toggle regex
-                self.search_query.search_type = self.search_query.search_type.toggle();
-                self.search_query = crate::internal::search::SearchQuery::new(
-                    self.temp_search_input.clone(),
-                    self.search_query.mode,
-                    self.search_query.search_type,
-                );
-            }
-            KeyCode::Up => {
-                // Navigate history up (older)
-                let current = self.history_index.unwrap_or(0);
-                if current < self.search_history.queries.len().saturating_sub(1) {
-                    let new_index = current + 1;
-                    self.history_index = Some(new_index);
-                    if let Some(query) = self.search_history.get_recent(new_index) {
-                        self.temp_search_input = query.clone();
-                        self.search_query = crate::internal::search::SearchQuery::new(
-                            query.clone(),
-                            self.search_query.mode,
-                            self.search_query.search_type,
-                        );
-                    }
-                }
-            }
-            KeyCode::Down => {
-                // Navigate history down (newer)
-                if let Some(current) = self.history_index {
-                    match current {
-                        0 => {
-                            // At the bottom, clear to empty
-                            self.history_index = None;
-                            self.temp_search_input.clear();
-                            self.search_query = crate::internal::search::SearchQuery::new(
-                                String::new(),
-                                self.search_query.mode,
-                                self.search_query.search_type,
-                            );
-                        }
-                        n => {
-                            let new_index = n - 1;
-                            self.history_index = Some(new_index);
-                            if let Some(query) = self.search_history.get_recent(new_index) {
-                                self.temp_search_input = query.clone();
-                                self.search_query = crate::internal::search::SearchQuery::new(
-                                    query.clone(),
-                                    self.search_query.mode,
-                                    self.search_query.search_type,
-                                );
-                            }
-                        }
-                    }
-                }
-            }
-            KeyCode::Char(c) => {
-                self.temp_search_input.push(c);
-                self.search_query = crate::internal::search::SearchQuery::new(
-                    self.temp_search_input.clone(),
-                    self.search_query.mode,
-                    self.search_query.search_type,
-                );
-                self.history_index = None;
-            }
-            KeyCode::Backspace => {
-                self.temp_search_input.pop();
-                self.search_query = crate::internal::search::SearchQuery::new(
-                    self.temp_search_input.clone(),
-                    self.search_query.mode,
-                    self.search_query.search_type,
-                );
-                self.history_index = None;
-            }
-            KeyCode::Enter => {
-                // Save to history and exit search mode
-                if !self.temp_search_input.is_empty() {
-                    self.search_history.add(self.temp_search_input.clone());
-                    let _ = self.search_history.save();
-                }
-                self.input_mode = InputMode::Normal;
-                self.history_index = None;
-            }
-            KeyCode::Esc => {
-                // Cancel search - clear and exit
-                self.temp_search_input.clear();
-                self.search_query = crate::internal::search::SearchQuery::default();
-                self.input_mode = InputMode::Normal;
-                self.history_index = None;
-            }
-            _ => {}
+            _ => IDLE_REDRAW,
+        };
+        let mut wake = now + idle;
+        let animating = [
+            &self.comments_scroll,
+            &self.article_scroll,
+            &self.whats_new_scroll,
+            &self.log_viewer.scroll,
+        ]
+        .iter()
+        .any(|scroll| scroll.is_animating());
+        if animating {
+            wake = wake.min(now + frame);
+        }
+        if !self.low_bandwidth
+            && self.is_busy()
+            && let Some(last) = self.last_spinner_update
+        {
+            wake = wake.min(last + SPINNER_STEP);
+        }
+        if let Some(dismiss_at) = self.notifications.next_dismiss() {
+            wake = wake.min(from_std(dismiss_at));
+        }
+        let prefetch_delay =
+            std::time::Duration::from_millis(self.config.prefetch.highlight_delay_ms);
+        if let Some(due) = self.prefetcher.due_at(prefetch_delay) {
+            wake = wake.min(from_std(due));
+        }
+        if let Some((_, pressed)) = &self.pending_chord {
+            let timeout = std::time::Duration::from_millis(self.config.ui.chord_timeout_ms);
+            wake = wake.min(from_std(*pressed + timeout));
+        }
+        let std_now = std::time::Instant::now();
+        if let Some(flash_end) = self
+            .updated_at
+            .values()
+            .map(|at| *at + UPDATE_FLASH)
+            .filter(|end| *end > std_now)
+            .min()
+        {
+            wake = wake.min(from_std(flash_end));
         }
+        wake
     }
 
-    fn handle_normal_input(&mut self, key: KeyEvent) {
-        use crate::internal::ui::keybindings::KeyBindingContext;
+    /// A key press was handled. Whatever it sent through the action channel is
+    /// queued behind the actions already there, so the press shows on screen once
+    /// everything now queued has been dispatched.
+    fn input_received(&mut self, received: std::time::Instant) {
+        let actions = self.action_rx.len();
+        match &mut self.pending_input {
+            Some(pending) => pending.actions = pending.actions.max(actions),
+            None => self.pending_input = Some(PendingInput { received, actions }),
+        }
+    }
 
-        // Handle help overlay shortcuts when active
-        if self.show_help {
-            match key.code {
-                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => {
-                    self.show_help = false;
-                    return;
-                }
-                KeyCode::Tab => {
-                    // Toggle between page 1 and 2
-                    self.help_page = match self.help_page {
-                        1 => 2,
-                        _ => 1,
-                    };
-                    return;
-                }
-                // Swallow other keys while help is shown to prevent accidental actions
-                _ => return,
+    fn input_action_dispatched(&mut self) {
+        if let Some(pending) = &mut self.pending_input {
+            pending.actions = pending.actions.saturating_sub(1);
+        }
+    }
+
+    /// Record key-to-frame latency once a frame is drawn after the actions of a
+    /// key press were dispatched.
+    fn record_input_latency(&mut self) {
+        if self
+            .pending_input
+            .as_ref()
+            .is_some_and(|pending| pending.actions == 0)
+            && let Some(PendingInput { received, .. }) = self.pending_input.take()
+        {
+            let elapsed = received.elapsed();
+            self.metrics
+                .record(crate::internal::metrics::INPUT_LATENCY, elapsed);
+            if self.config.logging.enable_performance_metrics {
+                tracing::debug!(?elapsed, "input.latency");
             }
         }
+    }
 
-        // Handle theme editor shortcuts when active
-        if self.theme_editor.active {
-            use crate::internal::ui::theme_editor::EditorState;
+    /// Apply a single action to the application state.
+    ///
+    /// This is the reducer half of `run`: state changes happen synchronously and any
+    /// effects (network fetches, timers) are spawned and report back through the
+    /// action channel. Together with [`App::next_action`] it lets tests and headless
+    /// callers drive the app without a terminal.
+    pub async fn dispatch(&mut self, action: Action) {
+        use tracing::Instrument;
+
+        let span = crate::internal::profiling::action_span(&action);
+        self.observe_tutorial(crate::internal::ui::tutorial::Event::Action(&action));
+        self.handle_action(action).instrument(span).await;
+    }
 
-            match self.theme_editor.state {
-                EditorState::Naming => {
-                    match key.code {
-                        KeyCode::Enter => {
-                            // Save with provided name
-                            let name = self.theme_editor.name_input.trim().to_string();
-                            if !name.is_empty() {
-                                // 1. Save original theme
-                                let _ = self.action_tx.send(Action::ExportTheme(name.clone()));
-
-                                // 2. Generate and save complementary theme
-                                let is_dark = self.theme_editor.is_dark_theme();
-                                let _complementary = self.theme_editor.generate_complementary();
-                                let suffix = match is_dark {
-                                    true => "_light",
-                                    false => "_dark",
-                                };
-                                let _comp_name = format!("{}{}", name, suffix);
+    /// Move the guided tour on if `event` is what its step waits for.
+    fn observe_tutorial(&mut self, event: crate::internal::ui::tutorial::Event) {
+        let Some(tutorial) = &mut self.tutorial else {
+            return;
+        };
+        if tutorial.observe(event) && tutorial.is_finished() {
+            self.tutorial = None;
+            self.notify_info("Tour complete");
+        }
+    }
 
-                                // TODO: Implement saving of complementary theme
-                                // For now, we just save the primary one.
-                            }
-                            self.theme_editor.active = false;
-                            self.theme_editor.state = EditorState::Editing;
-                        }
-                        KeyCode::Esc => {
-                            // Cancel naming, go back to editing
-                            self.theme_editor.state = EditorState::Editing;
-                        }
-                        KeyCode::Char(c) => {
-                            self.theme_editor.name_input.push(c);
-                        }
-                        KeyCode::Backspace => {
-                            self.theme_editor.name_input.pop();
-                        }
-                        _ => {}
-                    }
-                    return;
-                }
-                EditorState::Editing => {
-                    match key.code {
-                        KeyCode::Up => {
-                            self.theme_editor.navigate_property(-1);
-                            self.theme = self.theme_editor.temp_theme.clone();
-                        }
-                        KeyCode::Down => {
-                            self.theme_editor.navigate_property(1);
-                            self.theme = self.theme_editor.temp_theme.clone();
-                        }
-                        KeyCode::Left => self.theme_editor.navigate_channel(false),
-                        KeyCode::Right => self.theme_editor.navigate_channel(true),
-                        KeyCode::Char('+') | KeyCode::Char('=') => {
-                            self.theme_editor.adjust_color(true);
-                            self.theme = self.theme_editor.temp_theme.clone();
-                        }
-                        KeyCode::Char('-') | KeyCode::Char('_') => {
-                            self.theme_editor.adjust_color(false);
-                            self.theme = self.theme_editor.temp_theme.clone();
-                        }
-                        KeyCode::Char('s') | KeyCode::Char('S') => {
-                            self.theme_editor.state = EditorState::Naming;
-                            return;
-                        }
-                        KeyCode::Esc => {
-                            self.theme_editor.active = false;
-                            return;
-                        }
-                        KeyCode::Char('e') | KeyCode::Char('E') => {
-                            self.theme_editor.active = false;
-                            return;
-                        }
-                        _ => {}
-                    }
-                    return;
-                }
+    /// Wait for the next action emitted by a background effect, apply it and
+    /// return it. Returns `None` if the channel is closed.
+    #[allow(dead_code)]
+    pub async fn next_action(&mut self) -> Option<Action> {
+        let action = self.action_rx.recv().await?;
+        self.dispatch(action.clone()).await;
+        Some(action)
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) {
+        // Ctrl+Z suspends from anywhere, text prompts included, as in the shell
+        if key.code == KeyCode::Char('z') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.external = Some(crate::internal::external::External::Suspend);
+            return;
+        }
+
+        // Enter or `r` restores the last session; any other key starts afresh
+        if let Some(session) = self.session_prompt.take() {
+            if matches!(key.code, KeyCode::Enter | KeyCode::Char('r')) {
+                self.restore_session(session);
             }
+            return;
         }
 
-        // Map ViewMode to KeyBindingContext
-        let context = match self.view_mode {
-            ViewMode::List => KeyBindingContext::List,
-            ViewMode::StoryDetail => KeyBindingContext::StoryDetail,
-            ViewMode::Article => KeyBindingContext::Article,
-            ViewMode::Bookmarks => KeyBindingContext::Bookmarks,
-            ViewMode::History => KeyBindingContext::History,
-        };
+        if self.list_picker.is_some() {
+            self.handle_list_picker_input(key);
+            return;
+        }
 
-        // Check for configured keybinding
-        if let Some(action) = self.keybindings.get_action(&key, context) {
-            let _ = self.action_tx.send(action);
+        if self.filter_panel.is_some() {
+            self.handle_filter_panel_input(key);
             return;
         }
 
-        // Handle special cases that are not yet Actions or require immediate state change
-        match key.code {
-            // Toggle auto_switch_dark_to_light at runtime and persist to config.ron.
-            // Pressing 'g' flips the flag, saves config, and shows a short notification.
-            KeyCode::Char('g') => {
-                // Flip the flag and persist the configuration.
-                self.config.auto_switch_dark_to_light = !self.config.auto_switch_dark_to_light;
-                // Attempt to save the config to disk; AppConfig::save preserves comments.
-                self.config.save();
+        if self.settings_panel.is_some() {
+            self.handle_settings_panel_input(key);
+            return;
+        }
 
-                let status = match self.config.auto_switch_dark_to_light {
-                    true => "enabled",
-                    false => "disabled",
-                };
+        if let Some(story) = self.mute_prompt.take() {
+            self.handle_mute_prompt_input(key, story);
+            return;
+        }
 
-                // Notify user briefly
-                self.notify_info(format!("Auto-switch Dark->Light {}", status));
+        if self.reading_search.is_some() {
+            self.handle_reading_search_input(key);
+            return;
+        }
 
-                // Re-evaluate theme selection using the centralized helper and apply it immediately.
-                let term_env = std::env::var("TERM").unwrap_or_default();
-                let (new_theme, new_idx) = Self::select_theme_from_config(
-                    &self.config,
-                    &self.available_themes,
-                    &self.terminal_mode,
-                    &term_env,
-                );
-                self.theme = new_theme;
-                self.current_theme_index = new_idx;
+        if self.tag_prompt.is_some() {
+            self.handle_tag_prompt_input(key);
+            return;
+        }
 
-                // Schedule a clear of the notification after a few seconds
-                let tx = self.action_tx.clone();
-                tokio::spawn(async move {
-                    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
-                    let _ = tx.send(Action::ClearNotification);
-                });
-            }
-            KeyCode::Char('e') | KeyCode::Char('E') => {
-                // Toggle theme editor
-                self.theme_editor.toggle(&self.theme);
-            }
-            KeyCode::Char('/') => {
-                if self.view_mode == ViewMode::List {
-                    self.input_mode = InputMode::Search;
-                    self.temp_search_input = self.search_query.query.clone();
-                    self.history_index = None;
-                }
-            }
-            KeyCode::Char('Q') => {
-                if !self.search_query.is_empty() {
-                    self.search_query = crate::internal::search::SearchQuery::default();
-                    self.temp_search_input.clear();
+        if self.item_prompt.is_some() {
+            self.handle_item_prompt_input(key);
+            return;
+        }
+
+        if self.note_editor.is_some() {
+            self.handle_note_editor_input(key);
+            return;
+        }
+
+        if self.history_search.is_some() {
+            self.handle_history_search_input(key);
+            return;
+        }
+
+        if let Some(palette) = &mut self.command_palette {
+            use crate::internal::ui::command_palette::PaletteOutcome;
+            match palette.handle_key(key) {
+                PaletteOutcome::Pending => {}
+                PaletteOutcome::Cancel => self.command_palette = None,
+                PaletteOutcome::Run(action) => {
+                    self.command_palette = None;
+                    let _ = self.action_tx.send(*action);
                 }
             }
-            KeyCode::Tab => {
-                if self.show_help {
-                    // Toggle between page 1 and 2
-                    self.help_page = match self.help_page {
-                        1 => 2,
-                        _ => 1,
-                    };
+            return;
+        }
+
+        if let Some(offset) = &mut self.notification_center {
+            let toggle = self
+                .keybindings
+                .get_action(
+                    &key,
+                    crate::internal::ui::keybindings::KeyBindingContext::Global,
+                )
+                .is_some_and(|action| matches!(action, Action::ToggleNotificationCenter));
+            let last = self.notifications.history.len().saturating_sub(1);
+            match key.code {
+                _ if toggle => self.notification_center = None,
+                KeyCode::Esc | KeyCode::Char('q') => self.notification_center = None,
+                KeyCode::Char('j') | KeyCode::Down => *offset = (*offset + 1).min(last),
+                KeyCode::Char('k') | KeyCode::Up => *offset = offset.saturating_sub(1),
+                KeyCode::Char('c') => {
+                    self.notifications.clear_history();
+                    *offset = 0;
                 }
+                KeyCode::Char('d') => self.notifications.dismiss_all(),
+                _ => {}
             }
-            _ => {}
+            return;
         }
-    }
 
-    #[tracing::instrument(skip(self, action))]
-    async fn handle_action(&mut self, action: Action) {
-        match action {
-            Action::Quit => self.running = false,
-            Action::NavigateUp => {
-                match self.view_mode {
-                    ViewMode::StoryDetail => {
-                        // Scroll up in comments
-                        self.comments_scroll = self.comments_scroll.saturating_sub(1);
+        if let Some(view) = &mut self.read_later_view {
+            use crate::internal::ui::read_later_view::QueueOutcome;
+            match view.handle_key(key, &self.read_later.items) {
+                QueueOutcome::Pending => {}
+                QueueOutcome::Close => self.read_later_view = None,
+                QueueOutcome::Open(id) => {
+                    self.read_later_view = None;
+                    self.open_read_later(id);
+                }
+                QueueOutcome::Retry(id) => {
+                    if self.read_later.retry(id) {
+                        self.save_read_later();
+                        self.download_next_read_later();
                     }
-                    _ => {
-                        self.select_prev();
+                }
+                QueueOutcome::RetryAll => {
+                    if self.read_later.retry_failed() > 0 {
+                        self.save_read_later();
+                        self.download_next_read_later();
                     }
                 }
+                QueueOutcome::Remove(id) => {
+                    self.read_later.remove(id);
+                    self.save_read_later();
+                }
             }
-            Action::NavigateDown => {
-                match self.view_mode {
-                    ViewMode::StoryDetail => {
-                        // Scroll down in comments
-                        self.comments_scroll = self.comments_scroll.saturating_add(1);
-                    }
-                    _ => {
-                        self.select_next();
+            return;
+        }
+
+        if let Some(picker) = &mut self.link_picker {
+            use crate::internal::ui::link_picker::PickerOutcome;
+            match picker.handle_key(key) {
+                PickerOutcome::Pending => {}
+                PickerOutcome::Cancel => self.link_picker = None,
+                PickerOutcome::Open(url) => {
+                    self.link_picker = None;
+                    self.open_link(url);
+                }
+            }
+            return;
+        }
+
+        if let Some(preview) = &self.link_preview {
+            use crate::internal::ui::link_preview::PreviewOutcome;
+            match preview.handle_key(key) {
+                PreviewOutcome::Pending => {}
+                PreviewOutcome::Cancel => self.link_preview = None,
+                PreviewOutcome::Open => {
+                    if let Some(preview) = self.link_preview.take() {
+                        self.open_in_browser(&preview.url);
                     }
                 }
             }
-            Action::Enter => {
-                if let Some(index) = self.story_list_state.selected() {
-                    // Map the selected index (which refers to the displayed/filtered list)
-                    // back to the original story using the same filter logic used when
-                    // rendering the list. This ensures Enter selects the story shown
-                    // on that row even when a filter/search is active.
-                    let displayed: Vec<_> = match self.search_query.is_empty() {
-                        true => self.stories.iter().enumerate().collect(),
-                        false => self
-                            .stories
-                            .iter()
-                            .enumerate()
-                            .filter(|(_, story)| {
-                                story
-                                    .title
-                                    .as_ref()
-                                    .map(|t| self.search_query.matches(t))
-                                    .unwrap_or(false)
-                            })
-                            .collect(),
-                    };
+            return;
+        }
 
-                    if let Some((_, s)) = displayed.get(index).cloned() {
-                        // Clone the story so we send an owned Story in the action.
-                        let story = s.clone();
-                        // Add to history
-                        self.history.add(&story);
-                        let _ = self.history.save();
+        // Plain 'L' toggles the log viewer, unless a prompt above or the search
+        // field is taking text
+        if key.code == KeyCode::Char('L')
+            && (self.log_viewer.visible || self.input_mode == InputMode::Normal)
+        {
+            self.log_viewer.toggle();
+            return;
+        }
 
-                        let _ = self
-                            .action_tx
-                            .send(Action::SelectStory(story, self.current_list_type));
+        // If log viewer is visible, it traps input
+        if self.log_viewer.visible {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => self.log_viewer.toggle(),
+                KeyCode::Char('j') | KeyCode::Down => self.log_viewer.scroll_down(),
+                KeyCode::Char('k') | KeyCode::Up => self.log_viewer.scroll_up(),
+                KeyCode::Tab => self.log_viewer.next_tab(),
+                _ => {
+                    if let Some(action) = crate::internal::ui::scroll::scroll_key(&key) {
+                        self.log_viewer.scroll.apply(&action);
                     }
                 }
             }
-            Action::ClearHistory => {
-                if self.view_mode == ViewMode::History {
-                    self.history.clear();
-                    let _ = self.history.save();
-                }
+            return;
+        }
+
+        match self.input_mode {
+            InputMode::Search | InputMode::SearchOptions => self.handle_search_input(key),
+            InputMode::Normal => self.handle_normal_input(key),
+        }
+    }
+
+    /// Left click on a sort header column sorts by it; clicking the active column
+    /// flips the order.
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+            return;
+        }
+        let position = ratatui::layout::Position::new(mouse.column, mouse.row);
+        let action = self
+            .sort_header_hits
+            .iter()
+            .find(|(area, _)| area.contains(position))
+            .map(|(_, column)| match (*column == self.sort_by, column) {
+                (true, _) => Action::ToggleSortOrder,
+                (false, SortBy::Score) => Action::SortByScore,
+                (false, SortBy::Comments) => Action::SortByComments,
+                (false, SortBy::Time) => Action::SortByTime,
+                (false, SortBy::Rank) => Action::SortByRank,
+            });
+        if let Some(action) = action {
+            let _ = self.action_tx.send(action);
+        }
+    }
+
+    fn handle_list_picker_input(&mut self, key: KeyEvent) {
+        use crate::internal::ui::list_picker::PickerOutcome;
+
+        let Some(picker) = &mut self.list_picker else {
+            return;
+        };
+        let target = match picker.handle_key(key, self.reading_lists.lists.len()) {
+            PickerOutcome::Pending => return,
+            PickerOutcome::Cancel => None,
+            PickerOutcome::Pick(idx) => Some(idx),
+            PickerOutcome::Create(name) => Some(self.reading_lists.find_or_create(&name)),
+        };
+        let Some(picker) = self.list_picker.take() else {
+            return;
+        };
+
+        if let Some(idx) = target {
+            let name = self.reading_lists.lists[idx].name.clone();
+            match self.reading_lists.add(idx, &picker.story) {
+                true => self.notify_info(format!("Added to {}", name)),
+                false => self.notify_info(format!("Already in {}", name)),
             }
-            Action::ToggleThemeEditor => {
-                self.theme_editor.toggle(&self.theme);
+            self.save_reading_lists();
+        }
+    }
+
+    fn handle_filter_panel_input(&mut self, key: KeyEvent) {
+        use crate::internal::ui::filter_panel::PanelOutcome;
+
+        let Some(panel) = &mut self.filter_panel else {
+            return;
+        };
+        match panel.handle_key(key) {
+            PanelOutcome::Pending => {}
+            PanelOutcome::Close => self.filter_panel = None,
+            PanelOutcome::Apply(filters) => {
+                self.config.filters = filters;
+                self.clamp_list_selection();
+                self.save_config();
             }
-            Action::ExportTheme(name) => {
-                // First, save the main theme
-                if let Err(e) = self.export_theme_to_file(&name, None) {
-                    eprintln!("Failed to save main theme: {}", e);
-                    self.notify_error(format!("Failed to save theme: {}", e));
-                } else {
-                    self.notify_info(format!("Saved theme to {}", name));
-                }
+        }
+    }
 
-                // Then, generate and save the complementary theme
-                let is_dark = self.theme_editor.is_dark_theme();
-                let complementary = self.theme_editor.generate_complementary();
-                let suffix = if is_dark { "_light" } else { "_dark" };
-                let comp_name = format!("{}{}", name, suffix);
+    fn handle_settings_panel_input(&mut self, key: KeyEvent) {
+        use crate::config::settings::Setting;
+        use crate::internal::ui::settings_panel::SettingsOutcome;
 
-                if let Err(e) = self.export_theme_to_file(&comp_name, Some(&complementary)) {
-                    eprintln!("Failed to save complementary theme: {}", e);
+        let Some(panel) = &mut self.settings_panel else {
+            return;
+        };
+        match panel.handle_key(key, &mut self.config) {
+            SettingsOutcome::Pending => {}
+            SettingsOutcome::Close => self.settings_panel = None,
+            SettingsOutcome::Changed(setting) => {
+                self.save_config();
+                match setting {
+                    Setting::MaxRetries
+                    | Setting::ConcurrentRequests
+                    | Setting::RateLimit
+                    | Setting::RequestTimeout => {
+                        self.api_service =
+                            Arc::new(self.api_service.with_network(self.config.network.clone()));
+                    }
+                    Setting::StatusBarFormat => {
+                        (self.status_template, self.message_template) =
+                            Self::build_status_templates(&self.config);
+                    }
+                    _ => self.list_row_cache = Default::default(),
                 }
             }
-            Action::Back => {
-                self.view_mode = ViewMode::List;
-                self.selected_story = None;
-                self.comments.clear();
-                self.comment_ids.clear();
-                self.loaded_comments_count = 0;
-                // Reset comment list state so when returning to a story later the
-                // comments view doesn't retain a prior selection/scroll.
-                self.comments_scroll = 0;
+        }
+    }
+
+    /// Save the config, remembering what was written so the file watcher does not
+    /// reload it.
+    fn save_config(&mut self) {
+        self.config.save();
+        let path = self.config.save_path();
+        self.config_text = std::fs::read_to_string(&path).ok();
+        self.config.source.get_or_insert(path);
+    }
+
+    /// Load the config file again after it changed on disk and apply it.
+    fn reload_config(&mut self) {
+        let path = self.config.save_path();
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            return;
+        };
+        if self.config_text.as_deref() == Some(text.as_str()) {
+            return;
+        }
+        self.config_text = Some(text);
+        match AppConfig::load_from(&path) {
+            Ok(config) => {
+                self.apply_config(config);
+                self.notify_info("Reloaded config");
             }
-            Action::OpenBrowser => {
-                match (&self.selected_story, self.story_list_state.selected()) {
-                    (Some(story), _) => {
-                        if let Some(url) = &story.url {
-                            let _ = open::that(url);
-                        }
-                    }
-                    (None, Some(index)) => {
-                        // Map selected displayed index back to original story so OpenBrowser
-                        // opens the URL for the story visible on that row when filtered.
-                        let displayed: Vec<_> = match self.search_query.is_empty() {
-                            true => self.stories.iter().enumerate().collect(),
-                            false => self
-                                .stories
-                                .iter()
-                                .enumerate()
-                                .filter(|(_, story)| {
-                                    story
-                                        .title
-                                        .as_ref()
-                                        .map(|t| self.search_query.matches(t))
-                                        .unwrap_or(false)
-                                })
-                                .collect(),
-                        };
+            Err(e) => self.notify_error(format!("{:#}", e)),
+        }
+    }
 
-                        if let Some((_, story)) = displayed.get(index).cloned()
-                            && let Some(url) = &story.url
-                        {
-                            let _ = open::that(url);
-                        }
+    /// Start the timers for the network intervals of the config, stopping the ones
+    /// already running: the periodic refresh of the stories on screen and the HN
+    /// updates feed for changed items. An interval of 0 leaves its timer off.
+    fn spawn_network_timers(&mut self) {
+        for timer in self.network_timers.drain(..) {
+            timer.abort();
+        }
+        let timers = [
+            (
+                self.config.network.refresh_interval_secs,
+                Action::RefreshVisibleStories,
+            ),
+            (
+                self.config.network.live_updates_interval_secs,
+                Action::CheckUpdates,
+            ),
+        ];
+        for (secs, action) in timers {
+            if secs == 0 {
+                continue;
+            }
+            let tx = self.action_tx.clone();
+            self.network_timers.push(tokio::spawn(async move {
+                let mut interval = tokio::time::interval(std::time::Duration::from_secs(secs));
+                // The first tick completes immediately; skip it so we don't refresh on startup
+                interval.tick().await;
+                loop {
+                    interval.tick().await;
+                    if tx.send(action.clone()).is_err() {
+                        break;
                     }
-                    _ => {}
                 }
-            }
-            Action::LoadStories(list_type) => {
-                self.cancel_previous_request();
-                self.loading = true;
-                self.current_list_type = list_type;
-                // Reset pagination
-                self.stories.clear();
-                self.story_ids.clear();
-                self.loaded_count = 0;
+            }));
+        }
+    }
+
+    /// Switch to `config`: keys, network limits and theme follow it right away.
+    fn apply_config(&mut self, config: AppConfig) {
+        let network_changed = config.network != self.config.network;
+        let theme_changed = config.theme_name != self.config.theme_name
+            || config.auto_switch_dark_to_light != self.config.auto_switch_dark_to_light
+            || config.accessibility.high_contrast_mode
+                != self.config.accessibility.high_contrast_mode
+            || config.accessibility.color_blind_safe != self.config.accessibility.color_blind_safe;
+        self.keybindings = Self::build_keybindings(&config);
+        self.row_template = Self::build_row_template(&config);
+        (self.status_template, self.message_template) = Self::build_status_templates(&config);
+        if config.ui.time_format != self.config.ui.time_format {
+            self.time_format = config.ui.time_format;
+        }
+        self.no_color = crate::internal::ui::no_color::is_enabled(config.accessibility.no_color);
+        self.notifications
+            .set_config(config.ui.notifications.clone());
+        self.config = config;
+        if network_changed {
+            self.api_service = Arc::new(self.api_service.with_network(self.config.network.clone()));
+            self.spawn_network_timers();
+        }
+        if theme_changed {
+            let term_env = std::env::var("TERM").unwrap_or_default();
+            let (theme, index) = Self::select_theme_from_config(
+                &self.config,
+                &self.available_themes,
+                &self.terminal_mode,
+                &term_env,
+            );
+            self.theme = theme;
+            self.current_theme_index = index;
+        }
+        self.list_row_cache = Default::default();
+        self.clamp_list_selection();
+    }
 
+    fn handle_reading_search_input(&mut self, key: KeyEvent) {
+        use crate::internal::reading_index::ItemKind;
+        use crate::internal::ui::reading_search::SearchOutcome;
+
+        let Some(search) = &mut self.reading_search else {
+            return;
+        };
+        match search.handle_key(key) {
+            SearchOutcome::Pending => {}
+            SearchOutcome::Close => self.reading_search = None,
+            SearchOutcome::Search(query) => {
+                if let Some(index) = &self.reading_index {
+                    search.set_results(index.search(&query, 50).map_err(|e| format!("{:#}", e)));
+                }
+            }
+            SearchOutcome::Open(hit) => {
+                self.reading_search = None;
                 let api = self.api_service.clone();
                 let tx = self.action_tx.clone();
-                let token = self.get_cancellation_token();
-
+                let list_type = self.current_list_type;
                 tokio::spawn(async move {
-                    match api.fetch_story_ids(list_type, token.clone()).await {
-                        Ok(ids) => {
-                            tracing::info!("Fetched {} story IDs for {:?}", ids.len(), list_type);
-                            // Send all IDs first
-                            let all_ids = ids.clone();
-                            let _ = tx.send(Action::StoryIdsLoaded(all_ids));
-
-                            // Fetch first 20 stories concurrently (limit: 10)
-                            let ids_to_fetch = ids.iter().take(20).copied().collect::<Vec<_>>();
-                            tracing::info!("Fetching {} stories concurrently", ids_to_fetch.len());
-                            let results =
-                                api.fetch_stories_concurrent(&ids_to_fetch, 10, token).await;
-                            let stories: Vec<_> =
-                                results.into_iter().filter_map(|r| r.ok()).collect();
-                            tracing::info!("Successfully fetched {} stories", stories.len());
-                            let _ = tx.send(Action::StoriesLoaded(stories));
+                    match api.fetch_story_content(hit.story_id).await {
+                        Ok(story) => {
+                            let _ = tx.send(Action::SelectStory(story, list_type));
+                            if hit.kind == ItemKind::Article {
+                                let _ = tx.send(Action::ToggleArticleView);
+                            }
                         }
                         Err(e) => {
-                            tracing::error!("Failed to fetch story IDs: {}", e);
-                            // Only report error if not cancelled
-                            if e.to_string() != "Request cancelled" {
-                                let _ = tx
-                                    .send(Action::Error(format!("Failed to fetch stories: {}", e)));
-                            }
+                            let _ = tx.send(Action::ItemActionDone(Err(format!(
+                                "Could not open story {}: {:#}",
+                                hit.story_id, e
+                            ))));
                         }
                     }
                 });
             }
-            Action::StoryIdsLoaded(ids) => {
-                self.story_ids = ids;
+        }
+    }
+
+    /// Add an opened article or comment thread to the reading index, if enabled.
+    fn index_reading(&self, kind: crate::internal::reading_index::ItemKind, body: String) {
+        let (Some(index), Some(story)) = (&self.reading_index, &self.selected_story) else {
+            return;
+        };
+        if body.is_empty() {
+            return;
+        }
+        let index = index.clone();
+        let story_id = story.id;
+        let title = story.title.clone().unwrap_or_default();
+        // Committing touches the disk; keep it off the UI thread
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = index.add(story_id, kind, &title, &body) {
+                tracing::warn!(story_id, "Failed to index {}: {:#}", kind.as_str(), e);
             }
-            Action::StoryLoadingProgress(loaded) => {
-                if let Some((_, total)) = self.story_load_progress {
-                    self.story_load_progress = Some((loaded, total));
-                }
+        });
+    }
+
+    /// Typing in the History view's search: the view narrows as it is typed, Enter
+    /// keeps the search and Esc drops it.
+    fn handle_history_search_input(&mut self, key: KeyEvent) {
+        let Some(input) = &mut self.history_search else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                self.history_search = None;
+                self.history_query.clear();
             }
-            Action::StoriesLoaded(stories) => {
-                // Update loaded count and append stories
-                self.loaded_count += stories.len();
-                self.stories.extend(stories);
-                self.sort_stories();
-                self.loading = false;
-                self.story_load_progress = None;
-                if let (true, None) = (!self.stories.is_empty(), self.story_list_state.selected()) {
-                    self.story_list_state.select(Some(0))
+            KeyCode::Enter => self.history_search = None,
+            _ => {
+                if input.handle_key(key) {
+                    self.history_query = input.text().to_string();
+                    self.story_list_state.select(Some(0));
                 }
+                return;
             }
-            Action::LoadMoreStories => match (self.loading, self.story_ids.is_empty()) {
-                (true, _) | (_, true) => {}
-                _ => match self.loaded_count >= self.story_ids.len() {
-                    true => {
-                        let msg = format!(
-                            "{}/{} stories already loaded",
-                            self.loaded_count,
-                            self.story_ids.len()
-                        );
-                        self.notify_info(msg);
+        }
+        self.clamp_list_selection();
+    }
 
-                        // Schedule notification clear
-                        let tx = self.action_tx.clone();
-                        tokio::spawn(async move {
-                            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
-                            let _ = tx.send(Action::ClearNotification);
-                        });
-                    }
-                    false => {
-                        self.cancel_previous_request();
-                        self.loading = true;
-                        let api = self.api_service.clone();
-                        let tx = self.action_tx.clone();
-                        let token = self.get_cancellation_token();
-                        let ids_to_fetch = self
-                            .story_ids
-                            .iter()
-                            .skip(self.loaded_count)
-                            .take(20)
-                            .copied()
-                            .collect::<Vec<_>>();
+    fn handle_item_prompt_input(&mut self, key: KeyEvent) {
+        use crate::internal::ui::item_prompt::ItemOutcome;
+        let Some(prompt) = &mut self.item_prompt else {
+            return;
+        };
+        match prompt.handle_key(key) {
+            ItemOutcome::Pending => {}
+            ItemOutcome::Cancel => self.item_prompt = None,
+            ItemOutcome::Open(permalink) => {
+                self.item_prompt = None;
+                self.open_permalink(permalink);
+            }
+        }
+    }
 
-                        tokio::spawn(async move {
-                            let results =
-                                api.fetch_stories_concurrent(&ids_to_fetch, 10, token).await;
-                            let stories: Vec<_> =
-                                results.into_iter().filter_map(|r| r.ok()).collect();
-                            let _ = tx.send(Action::StoriesLoaded(stories));
-                        });
-                    }
-                },
-            },
-            Action::LoadAllStories => match (
-                self.loading,
-                self.story_ids.is_empty(),
-                self.story_load_progress.is_some(),
-            ) {
-                (true, _, _) | (_, true, _) | (_, _, true) => {}
-                _ => {
-                    // Check if all stories are already loaded
-                    match self.loaded_count >= self.story_ids.len() {
-                        true => {
-                            let msg = format!(
-                                "{}/{} stories already loaded",
-                                self.loaded_count,
-                                self.story_ids.len()
-                            );
-                            self.notify_info(msg);
+    /// Open the story `permalink` points at: the item itself, or the submission
+    /// of an article URL found with HN search.
+    fn open_permalink(&mut self, permalink: crate::internal::ui::item_prompt::Permalink) {
+        use crate::internal::models::Item;
+        use crate::internal::ui::item_prompt::Permalink;
 
-                            // Schedule notification clear
-                            let tx = self.action_tx.clone();
-                            tokio::spawn(async move {
-                                tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
-                                let _ = tx.send(Action::ClearNotification);
-                            });
-                        }
-                        false => {
-                            self.cancel_previous_request();
-                            self.loading = true;
-                            // TODO: Re-enable asynchronous loading for "Load All" feature
-                            let api = self.api_service.clone();
-                            let tx = self.action_tx.clone();
-                            let token = self.get_cancellation_token();
-                            let start_idx = self.loaded_count;
+        if let Permalink::Url(url) = &permalink {
+            self.notify_info(format!("Searching HN for {}", url));
+        }
+        let api = self.api_service.clone();
+        let tx = self.action_tx.clone();
+        let list_type = self.current_list_type;
+        tokio::spawn(async move {
+            let id = match permalink {
+                Permalink::Item(id) => Ok(id),
+                Permalink::Url(url) => match api.find_submission(&url).await {
+                    Ok(Some(id)) => Ok(id),
+                    Ok(None) => Err(format!("{} was not submitted to HN", url)),
+                    Err(e) => Err(format!("Could not search HN for {}: {:#}", url, e)),
+                },
+            };
+            let story = match id {
+                Ok(id) => match api.fetch_item(id).await {
+                    Ok(Item::Story(story) | Item::Job(story) | Item::Poll(story)) => Ok(story),
+                    Ok(Item::Comment(_)) => Err(format!("Item {} is a comment, not a story", id)),
+                    Ok(Item::PollOpt(_)) => Err(format!("Item {} is a poll option", id)),
+                    Err(e) => Err(format!("Could not open item {}: {:#}", id, e)),
+                },
+                Err(e) => Err(e),
+            };
+            let _ = match story {
+                Ok(story) => tx.send(Action::SelectStory(story, list_type)),
+                Err(message) => tx.send(Action::ItemActionDone(Err(message))),
+            };
+        });
+    }
 
-                            // Load ALL remaining stories
-                            let ids_to_fetch: Vec<_> =
-                                self.story_ids.iter().skip(start_idx).copied().collect();
-
-                            match ids_to_fetch.is_empty() {
-                                true => {
-                                    self.loading = false;
-                                }
-                                false => {
-                                    self.story_load_progress = Some((0, ids_to_fetch.len()));
-
-                                    tokio::spawn(async move {
-                                        let mut stories = Vec::new();
-                                        for (i, id) in ids_to_fetch.iter().enumerate() {
-                                            if let Some(token) = &token
-                                                && token.is_cancelled()
-                                            {
-                                                break;
-                                            }
-                                            if let Ok(story) = api.fetch_story_content(*id).await {
-                                                stories.push(story);
-                                            }
-                                            let _ = tx.send(Action::StoryLoadingProgress(i + 1));
-                                            // Add a small delay to avoid hitting API rate limits
-                                            tokio::time::sleep(std::time::Duration::from_millis(
-                                                20,
-                                            ))
-                                            .await;
-                                        }
-                                        let _ = tx.send(Action::StoriesLoaded(stories));
-                                    });
-                                }
-                            }
-                        }
+    fn handle_tag_prompt_input(&mut self, key: KeyEvent) {
+        use crate::internal::ui::tag_prompt::TagOutcome;
+        let Some(prompt) = &mut self.tag_prompt else {
+            return;
+        };
+        let story_id = prompt.story_id;
+        match prompt.handle_key(key) {
+            TagOutcome::Pending => {}
+            TagOutcome::Cancel => self.tag_prompt = None,
+            TagOutcome::Save(tags) => {
+                self.tag_prompt = None;
+                let msg = match tags.is_empty() {
+                    true => "Tags cleared".to_string(),
+                    false => format!("Tagged {}", tags.join(", ")),
+                };
+                self.bookmarks.set_tags(story_id, tags);
+                match self.bookmarks.save() {
+                    Err(e) => {
+                        tracing::error!(%e, "Failed to save bookmarks");
+                        self.notify_error("Failed to save bookmarks".to_string());
                     }
+                    Ok(()) => self.notify_info(msg),
                 }
-            },
-            Action::SelectStory(story, list_type) => {
-                self.cancel_previous_request();
-                self.view_mode = ViewMode::StoryDetail;
-
-                // Check if we are selecting the same story to preserve article state
-                let same_story = self.article_for_story_id == Some(story.id);
-
-                self.selected_story = Some(story.clone());
-                self.current_list_type = list_type;
-                self.comments.clear();
-                self.comment_ids.clear();
-                self.loaded_comments_count = 0;
-                self.comments_scroll = 0;
+                // The bookmark may no longer match the tag filter
+                self.clamp_list_selection();
+            }
+        }
+    }
 
-                if !same_story {
-                    self.article_content = None;
-                    self.article_for_story_id = None;
-                    self.article_scroll = 0;
-                    self.article_loading = false;
-                }
+    fn handle_note_editor_input(&mut self, key: KeyEvent) {
+        use crate::internal::ui::note_editor::NoteOutcome;
+        let Some(editor) = &mut self.note_editor else {
+            return;
+        };
+        let story_id = editor.story_id;
+        let note = match editor.handle_key(key) {
+            NoteOutcome::Pending => return,
+            NoteOutcome::Cancel => {
+                self.note_editor = None;
+                return;
+            }
+            NoteOutcome::Save(note) => note,
+        };
+        self.note_editor = None;
+        let msg = match note {
+            Some(_) => "Note saved",
+            None => "Note removed",
+        };
+        self.bookmarks.set_note(story_id, note);
+        match self.bookmarks.save() {
+            Err(e) => {
+                tracing::error!(%e, "Failed to save bookmarks");
+                self.notify_error("Failed to save bookmarks".to_string());
+            }
+            Ok(()) => self.notify_info(msg.to_string()),
+        }
+    }
 
-                let api = self.api_service.clone();
-                let tx = self.action_tx.clone();
-                let token = self.get_cancellation_token();
+    /// Ask for the tags of a bookmarked story.
+    fn prompt_for_tags(&mut self, story_id: u32, title: String) {
+        self.tag_prompt = Some(crate::internal::ui::tag_prompt::TagPrompt::new(
+            story_id,
+            title,
+            self.bookmarks.tags_of(story_id),
+            self.bookmarks.all_tags(),
+        ));
+    }
 
-                // If the story has a URL, start fetching the article immediately if needed.
-                if let Some(url) = story.url.clone() {
-                    // Fetch if it's a new story OR if we don't have content yet (and not loading)
-                    let should_fetch =
-                        !same_story || (self.article_content.is_none() && !self.article_loading);
+    /// `d` toggles the story's domain, `a` its author; any other key closes the prompt.
+    fn handle_mute_prompt_input(&mut self, key: KeyEvent, story: Story) {
+        let msg = match (
+            key.code,
+            crate::internal::mute::story_domain(&story),
+            &story.by,
+        ) {
+            (KeyCode::Char('d'), Some(domain), _) => match self.mute_list.toggle_domain(&domain) {
+                true => format!("Muted {}", domain),
+                false => format!("Unmuted {}", domain),
+            },
+            (KeyCode::Char('a'), _, Some(by)) => match self.mute_list.toggle_user(by) {
+                true => format!("Muted {}", by),
+                false => format!("Unmuted {}", by),
+            },
+            _ => return,
+        };
+        if let Err(e) = self.mute_list.save() {
+            tracing::error!(%e, "Failed to save mute list");
+        }
+        self.clamp_list_selection();
+        self.notify_info(msg);
+    }
 
-                    if should_fetch {
-                        self.article_loading = true;
-                        let api_clone = api.clone();
-                        let tx_clone = tx.clone();
-                        let story_id = story.id;
-                        // Capture the list/category this selection came from for the response.
-                        let list_for_request = self.current_list_type;
-                        let token_clone = token.clone();
-                        tokio::spawn(async move {
-                            match api_clone.fetch_article_content(&url, token_clone).await {
-                                Ok(content) => {
-                                    let _ = tx_clone.send(Action::ArticleLoaded(
-                                        list_for_request,
-                                        story_id,
-                                        content,
-                                    ));
-                                }
-                                Err(e) => {
-                                    if e.to_string() != "Request cancelled" {
-                                        let _ = tx_clone.send(Action::Error(format!(
-                                            "Failed to fetch article: {}",
-                                            e
-                                        )));
-                                    }
-                                }
-                            }
-                        });
+    /// Edit the search query; in the comments view it searches the loaded comments
+    /// instead of filtering the story list.
+    fn handle_search_input(&mut self, key: KeyEvent) {
+        let in_comments = self.view_mode == ViewMode::StoryDetail && !self.list_has_focus();
+        let mut query = match in_comments {
+            true => self.comment_search.query.clone(),
+            false => self.search_query.clone(),
+        };
+        match key.code {
+            KeyCode::Char('/') => {
+                // Ignore / in search mode (it's the key that enters search mode)
+            }
+            KeyCode::Char('m')
+                if key
+                    .modifiers
+                    .contains(crossterm::event::KeyModifiers::CONTROL) =>
+            {
+                // Cycle search mode
+                query.mode = query.mode.next();
+            }
+            KeyCode::F(2) => {
+                // Also cycle search mode
+                query.mode = query.mode.next();
+            }
+            KeyCode::Char('r')
+                if key
+                    .modifiers
+                    .contains(crossterm::event::KeyModifiers::CONTROL) =>
+            {
+                // Toggle regex
+                query.search_type = query.search_type.toggle();
+                // Recompile with current temp input
+                query = crate::internal::search::SearchQuery::new(
+                    self.temp_search_input.clone(),
+                    query.mode,
+                    query.search_type,
+                );
+            }
+            KeyCode::F(3) => {
+                // Also toggle regex
+                query.search_type = query.search_type.toggle();
+                query = crate::internal::search::SearchQuery::new(
+                    self.temp_search_input.clone(),
+                    query.mode,
+                    query.search_type,
+                );
+            }
+            KeyCode::Up => {
+                // Navigate history up (older)
+                let current = self.history_index.unwrap_or(0);
+                if current < self.search_history.queries.len().saturating_sub(1) {
+                    let new_index = current + 1;
+                    self.history_index = Some(new_index);
+                    if let Some(recent) = self.search_history.get_recent(new_index) {
+                        self.temp_search_input = recent.clone();
+                        query = crate::internal::search::SearchQuery::new(
+                            recent.clone(),
+                            query.mode,
+                            query.search_type,
+                        );
                     }
                 }
-
-                // Fetch comments in the background as before so they are available
-                // if the user switches to the comments view.
-                match story.kids {
-                    Some(kids) => {
-                        // Store all comment IDs for pagination
-                        self.comment_ids = kids.clone();
-                        self.loaded_comments_count = 0;
-
-                        let api_clone = api.clone();
-                        let tx_clone = tx.clone();
-                        tokio::spawn(async move {
-                            // Use fetch_comment_tree to get threaded comments
-                            if let Ok(comment_rows) =
-                                api_clone.fetch_comment_tree(kids, 3, token).await
-                            {
-                                let _ = tx_clone.send(Action::CommentsLoaded(comment_rows));
+            }
+            KeyCode::Down => {
+                // Navigate history down (newer)
+                if let Some(current) = self.history_index {
+                    match current {
+                        0 => {
+                            // At the bottom, clear to empty
+                            self.history_index = None;
+                            self.temp_search_input.clear();
+                            query = crate::internal::search::SearchQuery::new(
+                                String::new(),
+                                query.mode,
+                                query.search_type,
+                            );
+                        }
+                        n => {
+                            let new_index = n - 1;
+                            self.history_index = Some(new_index);
+                            if let Some(recent) = self.search_history.get_recent(new_index) {
+                                self.temp_search_input = recent.clone();
+                                query = crate::internal::search::SearchQuery::new(
+                                    recent.clone(),
+                                    query.mode,
+                                    query.search_type,
+                                );
                             }
-                        });
-                    }
-                    None => {
-                        self.comment_ids.clear();
-                        self.loaded_comments_count = 0;
-                        self.comments_loading = false;
+                        }
                     }
                 }
             }
-            Action::CommentsLoaded(comment_rows) => {
-                // Replace existing comments with the new tree
-                self.loaded_comments_count = comment_rows.len();
-                self.comments = comment_rows;
-                self.comments_loading = false;
-                self.comments_scroll = 0;
+            KeyCode::Char(c) => {
+                self.temp_search_input.push(c);
+                query = crate::internal::search::SearchQuery::new(
+                    self.temp_search_input.clone(),
+                    query.mode,
+                    query.search_type,
+                );
+                self.history_index = None;
             }
-            Action::LoadMoreComments => {
-                // With fetch_comment_tree, we load all comments at once (up to MAX_COMMENTS limit)
-                // So this action is essentially a no-op for now
-                self.notify_info("All comments already loaded");
-
-                // Schedule notification clear
-                let tx = self.action_tx.clone();
-                tokio::spawn(async move {
-                    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
-                    let _ = tx.send(Action::ClearNotification);
-                });
+            KeyCode::Backspace => {
+                self.temp_search_input.pop();
+                query = crate::internal::search::SearchQuery::new(
+                    self.temp_search_input.clone(),
+                    query.mode,
+                    query.search_type,
+                );
+                self.history_index = None;
             }
-            Action::AppendComments(parent_index, new_rows) => {
-                // Insert new rows after the parent
-                // Verify parent index is still valid and matches expected depth
-                if parent_index < self.comments.len() {
-                    // let parent_depth = self.comments[parent_index].depth; // Unused
-                    // Insert after parent
-                    let mut insert_idx = parent_index + 1;
-                    for row in new_rows {
-                        self.comments.insert(insert_idx, row);
-                        insert_idx += 1;
+            KeyCode::Enter => {
+                // Save to history and exit search mode
+                if !self.temp_search_input.is_empty() {
+                    self.search_history.add(self.temp_search_input.clone());
+                    let _ = self.search_history.save();
+                    if !in_comments {
+                        self.observe_tutorial(crate::internal::ui::tutorial::Event::Search);
                     }
-                    // Mark parent as having loaded kids
-                    self.comments[parent_index].loaded_kids = true;
-                    // Ensure parent is expanded
-                    self.comments[parent_index].expanded = true;
                 }
+                self.input_mode = InputMode::Normal;
+                self.history_index = None;
             }
-            Action::ToggleCommentCollapse(index) => {
-                let mut should_fetch = false;
-                let mut kids_to_fetch = None;
-                let mut parent_depth = 0;
+            KeyCode::Esc => {
+                // Cancel search - clear and exit
+                self.temp_search_input.clear();
+                query = crate::internal::search::SearchQuery::default();
+                self.input_mode = InputMode::Normal;
+                self.history_index = None;
+            }
+            _ => {}
+        }
 
-                // Check if we need to fetch kids (immutable borrow)
-                if let Some(row) = self.comments.get(index)
-                    && !row.expanded
-                    && !row.loaded_kids
-                    && let Some(kids) = &row.comment.kids
-                    && !kids.is_empty()
-                {
-                    should_fetch = true;
-                    kids_to_fetch = Some(kids.clone());
-                    parent_depth = row.depth;
+        match in_comments {
+            true => {
+                self.comment_search
+                    .set_query(query, &self.comment_layout.lines);
+                if key.code == KeyCode::Enter {
+                    self.jump_to_comment_match(true);
                 }
+            }
+            false => self.search_query = query,
+        }
+    }
 
-                if should_fetch && let Some(kids) = kids_to_fetch {
-                    let api = self.api_service.clone();
-                    let tx = self.action_tx.clone();
-                    let token = self.get_cancellation_token();
-
-                    self.notify_info("Loading replies...");
+    fn handle_normal_input(&mut self, key: KeyEvent) {
+        use crate::internal::ui::keybindings::KeyBindingContext;
 
-                    tokio::spawn(async move {
-                        if let Ok(mut new_rows) = api.fetch_comment_tree(kids, 3, token).await {
-                            // Adjust depth
-                            for r in &mut new_rows {
-                                r.depth += parent_depth + 1;
-                            }
-                            let _ = tx.send(Action::AppendComments(index, new_rows));
-                        }
-                    });
+        // Handle help overlay shortcuts when active
+        if self.show_help {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => {
+                    self.show_help = false;
+                    return;
                 }
-
-                // Toggle expanded (mutable borrow)
-                if let Some(row) = self.comments.get_mut(index) {
-                    row.expanded = !row.expanded;
+                KeyCode::Char('T') => {
+                    self.show_help = false;
+                    self.tutorial = Some(Default::default());
+                    return;
+                }
+                KeyCode::Tab
+                | KeyCode::PageDown
+                | KeyCode::Right
+                | KeyCode::Down
+                | KeyCode::Char('l' | 'j' | ' ') => {
+                    self.help_page = (self.help_page + 1) % self.help_pages.max(1);
+                    return;
+                }
+                KeyCode::BackTab
+                | KeyCode::PageUp
+                | KeyCode::Left
+                | KeyCode::Up
+                | KeyCode::Char('h' | 'k') => {
+                    let pages = self.help_pages.max(1);
+                    self.help_page = (self.help_page + pages - 1) % pages;
+                    return;
                 }
+                // Swallow other keys while help is shown to prevent accidental actions
+                _ => return,
             }
-            Action::ToggleArticleView => {
-                match self.view_mode {
-                    ViewMode::StoryDetail => {
-                        self.view_mode = ViewMode::Article;
-                        // If we haven't loaded the article yet, start loading it
+        }
+
+        // The cache statistics overlay closes on Esc/q or its own shortcut
+        if self.show_cache_stats {
+            let toggle = self
+                .keybindings
+                .get_action(&key, KeyBindingContext::Global)
+                .is_some_and(|action| matches!(action, Action::ToggleCacheStats));
+            if toggle || matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) {
+                self.show_cache_stats = false;
+            }
+            return;
+        }
+
+        // The release notes overlay scrolls and closes; other keys are swallowed
+        if !self.whats_new.is_empty() {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => self.whats_new.clear(),
+                KeyCode::Char('j') | KeyCode::Down => self.whats_new_scroll.scroll_down(1),
+                KeyCode::Char('k') | KeyCode::Up => self.whats_new_scroll.scroll_up(1),
+                KeyCode::PageDown => self.whats_new_scroll.page_down(),
+                KeyCode::PageUp => self.whats_new_scroll.page_up(),
+                _ => {}
+            }
+            return;
+        }
+
+        // The error panel takes r (retry) and Esc; other keys work as usual
+        if let Some(error) = &self.last_error {
+            let action = match key.code {
+                KeyCode::Char('r') if error.retryable => Some(Action::RetryFailed),
+                KeyCode::Esc => Some(Action::DismissError),
+                _ => None,
+            };
+            if let Some(action) = action {
+                let _ = self.action_tx.send(action);
+                return;
+            }
+        }
+
+        // Handle theme editor shortcuts when active
+        if self.theme_editor.active {
+            use crate::internal::ui::theme_editor::EditorState;
+
+            match self.theme_editor.state {
+                EditorState::Naming => {
+                    match key.code {
+                        KeyCode::Enter => {
+                            // Save with provided name
+                            let name = self.theme_editor.name_input.trim().to_string();
+                            if !name.is_empty() {
+                                let _ = self.action_tx.send(Action::ExportTheme(name));
+                            }
+                            self.theme_editor.active = false;
+                            self.theme_editor.state = EditorState::Editing;
+                        }
+                        KeyCode::Esc => {
+                            // Cancel naming, go back to editing
+                            self.theme_editor.state = EditorState::Editing;
+                        }
+                        KeyCode::Char(c) => {
+                            self.theme_editor.name_input.push(c);
+                        }
+                        KeyCode::Backspace => {
+                            self.theme_editor.name_input.pop();
+                        }
+                        _ => {}
+                    }
+                    return;
+                }
+                EditorState::HexInput => {
+                    match key.code {
+                        KeyCode::Enter => match self.theme_editor.apply_hex() {
+                            true => {
+                                self.theme = self.theme_editor.temp_theme.clone();
+                                self.theme_editor.state = EditorState::Editing;
+                            }
+                            false => self.notify_info(format!(
+                                "Not a hex color: {}",
+                                self.theme_editor.hex_input
+                            )),
+                        },
+                        KeyCode::Esc => self.theme_editor.state = EditorState::Editing,
+                        KeyCode::Char(c)
+                            if (c.is_ascii_hexdigit() || c == '#')
+                                && self.theme_editor.hex_input.len() < 7 =>
+                        {
+                            self.theme_editor.hex_input.push(c);
+                        }
+                        KeyCode::Backspace => {
+                            self.theme_editor.hex_input.pop();
+                        }
+                        _ => {}
+                    }
+                    return;
+                }
+                EditorState::Editing => {
+                    match key.code {
+                        KeyCode::Up => {
+                            self.theme_editor.navigate_property(-1);
+                            self.theme = self.theme_editor.temp_theme.clone();
+                        }
+                        KeyCode::Down => {
+                            self.theme_editor.navigate_property(1);
+                            self.theme = self.theme_editor.temp_theme.clone();
+                        }
+                        KeyCode::Left => self.theme_editor.navigate_channel(false),
+                        KeyCode::Right => self.theme_editor.navigate_channel(true),
+                        KeyCode::Char('+') | KeyCode::Char('=') => {
+                            self.theme_editor.adjust_color(true);
+                            self.theme = self.theme_editor.temp_theme.clone();
+                        }
+                        KeyCode::Char('-') | KeyCode::Char('_') => {
+                            self.theme_editor.adjust_color(false);
+                            self.theme = self.theme_editor.temp_theme.clone();
+                        }
+                        KeyCode::Char('m') | KeyCode::Char('M') => self.theme_editor.toggle_mode(),
+                        KeyCode::Char('#') => {
+                            self.theme_editor.hex_input.clear();
+                            self.theme_editor.state = EditorState::HexInput;
+                        }
+                        KeyCode::Char('s') | KeyCode::Char('S') => {
+                            self.theme_editor.state = EditorState::Naming;
+                            return;
+                        }
+                        KeyCode::Esc => {
+                            self.theme_editor.active = false;
+                            return;
+                        }
+                        KeyCode::Char('e') | KeyCode::Char('E') => {
+                            self.theme_editor.active = false;
+                            return;
+                        }
+                        _ => {}
+                    }
+                    return;
+                }
+            }
+        }
+
+        // Vim-style fold commands in the comments view: za toggles the comment at the
+        // top of the pane, zM folds everything, zR unfolds everything, z1-z9 show
+        // only that many levels
+        if self.pending_fold_prefix {
+            self.pending_fold_prefix = false;
+            let action = match key.code {
+                KeyCode::Char('a') => self
+                    .comment_at_scroll()
+                    .and_then(|id| self.comments.iter().position(|r| r.comment.id == id))
+                    .map(Action::ToggleCommentCollapse),
+                KeyCode::Char('M') => Some(Action::CollapseAllComments),
+                KeyCode::Char('R') => Some(Action::ExpandAllComments),
+                KeyCode::Char(c @ '1'..='9') => {
+                    Some(Action::CollapseToDepth(c.to_digit(10).unwrap_or(1) as usize))
+                }
+                _ => None,
+            };
+            if let Some(action) = action {
+                let _ = self.action_tx.send(action);
+            }
+            return;
+        }
+        if key.code == KeyCode::Char('z')
+            && self.view_mode == ViewMode::StoryDetail
+            && !self.list_has_focus()
+        {
+            self.pending_fold_prefix = true;
+            return;
+        }
+
+        // While a comment search is active, n/N move between its matches instead of
+        // loading more comments
+        if let KeyCode::Char(c @ ('n' | 'N')) = key.code
+            && self.view_mode == ViewMode::StoryDetail
+            && !self.list_has_focus()
+            && self.comment_search.is_active()
+        {
+            self.jump_to_comment_match(c == 'n');
+            return;
+        }
+
+        // The tour takes Enter on steps that wait for it, and Esc ends it; other keys
+        // work as usual so its steps can be followed
+        if let Some(tutorial) = &self.tutorial {
+            use crate::internal::ui::tutorial::{Event, Goal};
+            match (key.code, tutorial.current().map(|step| step.goal)) {
+                (KeyCode::Esc, _) => {
+                    self.tutorial = None;
+                    self.notify_info("Tour ended; press ? then T to take it again");
+                    return;
+                }
+                (KeyCode::Enter, Some(Goal::Continue)) => {
+                    self.observe_tutorial(Event::Continue);
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        let context = self.key_context();
+
+        // Chords: a prefix key waits for the second one. A key that completes no
+        // chord cancels it and is handled on its own; Esc only cancels.
+        match self.pending_chord.take() {
+            Some((first, _)) => match self.keybindings.get_chord_action(&first, &key, context) {
+                Some(action) => {
+                    let _ = self.action_tx.send(action);
+                    return;
+                }
+                None if key.code == KeyCode::Esc => return,
+                None => {}
+            },
+            None if self.keybindings.is_chord_prefix(&key, context) => {
+                self.pending_chord = Some((key, std::time::Instant::now()));
+                return;
+            }
+            None => {}
+        }
+
+        // Check for configured keybinding
+        if let Some(action) = self.keybindings.get_action(&key, context) {
+            // The list pane is not the top-level view in the split layout, so its
+            // quit key closes the story instead of exiting
+            let action = match action {
+                Action::Quit if self.list_has_focus() => Action::Back,
+                other => other,
+            };
+            let _ = self.action_tx.send(action);
+            return;
+        }
+
+        // Handle special cases that are not yet Actions or require immediate state change
+        match key.code {
+            // Toggle auto_switch_dark_to_light at runtime and persist to config.ron.
+            // Pressing 'g' flips the flag, saves config, and shows a short notification.
+            KeyCode::Char('g') => {
+                // Flip the flag and persist the configuration.
+                self.config.auto_switch_dark_to_light = !self.config.auto_switch_dark_to_light;
+                // Attempt to save the config to disk; AppConfig::save preserves comments.
+                self.save_config();
+
+                let status = match self.config.auto_switch_dark_to_light {
+                    true => "enabled",
+                    false => "disabled",
+                };
+
+                // Notify user briefly
+                self.notify_info(format!("Auto-switch Dark->Light {}", status));
+
+                // Re-evaluate theme selection using the centralized helper and apply it immediately.
+                let term_env = std::env::var("TERM").unwrap_or_default();
+                let (new_theme, new_idx) = Self::select_theme_from_config(
+                    &self.config,
+                    &self.available_themes,
+                    &self.terminal_mode,
+                    &term_env,
+                );
+                self.theme = new_theme;
+                self.current_theme_index = new_idx;
+            }
+            KeyCode::Char('e') | KeyCode::Char('E') => {
+                // Toggle theme editor
+                self.theme_editor.toggle(&self.theme);
+            }
+            KeyCode::Char('/') if self.view_mode == ViewMode::List => {
+                self.input_mode = InputMode::Search;
+                self.temp_search_input = self.search_query.query.clone();
+                self.history_index = None;
+            }
+            KeyCode::Char('/') if self.view_mode == ViewMode::History => {
+                self.history_search = Some(crate::internal::ui::text_input::TextInput::new(
+                    &self.history_query,
+                ));
+            }
+            KeyCode::Char('/')
+                if self.view_mode == ViewMode::StoryDetail && !self.list_has_focus() =>
+            {
+                self.input_mode = InputMode::Search;
+                self.temp_search_input = self.comment_search.query.query.clone();
+                self.history_index = None;
+            }
+            KeyCode::Char('Q')
+                if self.view_mode == ViewMode::StoryDetail && self.comment_search.is_active() =>
+            {
+                self.comment_search = Default::default();
+                self.temp_search_input.clear();
+            }
+            KeyCode::Char('Q') if !self.search_query.is_empty() => {
+                self.search_query = crate::internal::search::SearchQuery::default();
+                self.temp_search_input.clear();
+            }
+            _ => {}
+        }
+    }
+
+    #[tracing::instrument(skip(self, action))]
+    async fn handle_action(&mut self, action: Action) {
+        match action {
+            Action::Quit => self.running = false,
+            Action::NavigateUp => {
+                match self.view_mode {
+                    _ if self.list_has_focus() => self.select_prev(),
+                    ViewMode::StoryDetail => {
+                        // Scroll up in comments
+                        self.comments_scroll.scroll_up(1);
+                    }
+                    _ => {
+                        self.select_prev();
+                    }
+                }
+            }
+            Action::NavigateDown => {
+                match self.view_mode {
+                    _ if self.list_has_focus() => self.select_next(),
+                    ViewMode::StoryDetail => {
+                        // Scroll down in comments
+                        self.comments_scroll.scroll_down(1);
+                    }
+                    _ => {
+                        self.select_next();
+                    }
+                }
+            }
+            Action::Enter
+                if matches!(
+                    self.view_mode,
+                    ViewMode::ReadingLists | ViewMode::Bookmarks | ViewMode::History
+                ) =>
+            {
+                // Reading list items, bookmarks and history entries only store a
+                // snapshot; fetch the live story to open it
+                let snapshot = match self.view_mode {
+                    ViewMode::Bookmarks | ViewMode::History => self.focused_story(),
+                    _ => self.story_list_state.selected().and_then(|idx| {
+                        self.reading_lists
+                            .get(self.current_reading_list)?
+                            .items
+                            .get(idx)
+                            .map(|item| item.to_story())
+                    }),
+                };
+                if let Some(fallback) = snapshot {
+                    // Opening a bookmark reads it, like opening a story from a list
+                    if self.view_mode == ViewMode::Bookmarks {
+                        self.history.add(&fallback);
+                        let _ = self.history.save();
+                    }
+                    let id = fallback.id;
+                    let api = self.api_service.clone();
+                    let tx = self.action_tx.clone();
+                    let list_type = self.current_list_type;
+                    tokio::spawn(async move {
+                        let story = api.fetch_story_content(id).await.unwrap_or(fallback);
+                        let _ = tx.send(Action::SelectStory(story, list_type));
+                    });
+                }
+            }
+            Action::Enter => {
+                if let Some(index) = self.story_list_state.selected() {
+                    // Map the selected index (which refers to the displayed/filtered list)
+                    // back to the original story using the same filter logic used when
+                    // rendering the list. This ensures Enter selects the story shown
+                    // on that row even when a filter/search is active.
+                    let displayed = self.filtered_story_indices();
+
+                    if let Some((_, s)) = displayed.get(index).cloned() {
+                        // Clone the story so we send an owned Story in the action.
+                        let story = s.clone();
+                        // Add to history
+                        self.history.add(&story);
+                        let _ = self.history.save();
+
+                        let _ = self
+                            .action_tx
+                            .send(Action::SelectStory(story, self.current_list_type));
+                    }
+                }
+            }
+            Action::ClearHistory => {
+                if self.view_mode == ViewMode::History {
+                    self.history.clear();
+                    let _ = self.history.save();
+                }
+            }
+            Action::ToggleThemeEditor => {
+                self.theme_editor.toggle(&self.theme);
+            }
+            Action::ExportTheme(name) => match self.export_theme_to_file(&name) {
+                Ok(path) => {
+                    self.register_saved_theme(&path);
+                    self.notify_info(format!(
+                        "Saved theme {} (dark and light) to {}",
+                        name,
+                        path.display()
+                    ));
+                }
+                Err(e) => self.notify_error(format!("Failed to save theme: {}", e)),
+            },
+            Action::FocusNextPane => match self.split_active() {
+                true => self.focused_pane = self.focused_pane.other(),
+                false => self.focused_pane = crate::internal::ui::layout::Pane::Detail,
+            },
+            Action::Back => {
+                self.focused_pane = crate::internal::ui::layout::Pane::Detail;
+                self.view_mode = ViewMode::List;
+                self.selected_story = None;
+                self.comments.clear();
+                self.comment_ids.clear();
+                self.loaded_comments_count = 0;
+                // Reset comment list state so when returning to a story later the
+                // comments view doesn't retain a prior selection/scroll.
+                self.comments_scroll.reset();
+            }
+            Action::OpenBrowser => {
+                let url = self.focused_story_link().and_then(|(_, url)| url);
+                if let Some(url) = url {
+                    self.open_link(url);
+                }
+            }
+            Action::NextStory | Action::PrevStory => {
+                self.step_story(action == Action::NextStory);
+            }
+            Action::ScrollHalfPageDown
+            | Action::ScrollHalfPageUp
+            | Action::ScrollPageDown
+            | Action::ScrollPageUp
+            | Action::ScrollToTop
+            | Action::ScrollToBottom => {
+                if let Some(scroll) = self.detail_scroll() {
+                    scroll.apply(&action);
+                }
+            }
+            Action::SendToPhone if self.api_service.is_offline() => {
+                self.notify_info("Offline mode: sending is disabled");
+            }
+            Action::SendToPhone => match (self.config.push.service, self.focused_story()) {
+                (None, _) => self.notify_info("Set up `push` in the config to send stories"),
+                (Some(_), None) => self.notify_info("No story selected"),
+                (Some(service), Some(story)) => {
+                    self.notify_info(format!("Sending to {}...", service));
+                    let config = self.config.push.clone();
+                    let timeout =
+                        std::time::Duration::from_secs(self.config.network.request_timeout_secs);
+                    let tx = self.action_tx.clone();
+                    tokio::spawn(async move {
+                        let title = story.title.clone().unwrap_or_else(|| story.id.to_string());
+                        let result = crate::internal::push::send(&config, &story, timeout)
+                            .await
+                            .map(|_| format!("Sent \"{}\" to {}", title, service))
+                            .map_err(|e| format!("Sending to {} failed: {:#}", service, e));
+                        let _ = tx.send(Action::ItemActionDone(result));
+                    });
+                }
+            },
+            Action::Suspend => {
+                self.external = Some(crate::internal::external::External::Suspend);
+            }
+            Action::QueueReadLater => match self.focused_story() {
+                None => self.notify_info("No story selected"),
+                Some(story) => {
+                    let title = story.title.clone().unwrap_or_else(|| story.id.to_string());
+                    match self
+                        .read_later
+                        .enqueue(&story, jiff::Timestamp::now().as_second())
+                    {
+                        true => {
+                            self.save_read_later();
+                            match self.api_service.is_offline() {
+                                true => self.notify_info(format!(
+                                    "Queued \"{}\"; it downloads once online",
+                                    title
+                                )),
+                                false => self.notify_info(format!("Queued \"{}\"", title)),
+                            }
+                            self.download_next_read_later();
+                        }
+                        false => self.notify_info(format!("\"{}\" is already queued", title)),
+                    }
+                }
+            },
+            Action::ViewReadLater => {
+                self.read_later_view = match self.read_later_view {
+                    Some(_) => None,
+                    None => Some(Default::default()),
+                };
+            }
+            Action::ReadLaterDone(id, result) => {
+                let now = jiff::Timestamp::now().as_second();
+                let title = self
+                    .read_later
+                    .items
+                    .iter()
+                    .find(|item| item.id == id)
+                    .map(|item| item.title.clone());
+                if let Err(e) = &result {
+                    tracing::warn!(id, error = %e, "Read-later download failed");
+                }
+                let failed = result.is_err();
+                self.read_later.finish(id, result, now);
+                self.read_later.prune(now, self.config.read_later.keep_days);
+                self.save_read_later();
+                if let (Some(title), true) = (title, failed) {
+                    self.notify_error(format!("Downloading \"{}\" failed", title));
+                }
+                self.download_next_read_later();
+            }
+            Action::OpenInPager => match self.story_text() {
+                Some((_, text)) => {
+                    self.external = Some(crate::internal::external::External::Pager(text));
+                }
+                None => self.notify_info("No story selected"),
+            },
+            Action::OpenInEditor => match self.story_text() {
+                Some((id, text)) => {
+                    let path = std::env::temp_dir().join(format!("tui-hn-{}.md", id));
+                    match std::fs::write(&path, text) {
+                        Ok(()) => {
+                            self.external = Some(crate::internal::external::External::Editor(path));
+                        }
+                        Err(e) => {
+                            self.notify_error(format!("Failed to write {}: {}", path.display(), e))
+                        }
+                    }
+                }
+                None => self.notify_info("No story selected"),
+            },
+            Action::OpenLinkPicker => match self.page_links() {
+                links if links.is_empty() => self.notify_info("No links on this page"),
+                links => {
+                    self.link_picker =
+                        Some(crate::internal::ui::link_picker::LinkPicker::new(links));
+                }
+            },
+            Action::LinkResolved(url, resolved) => {
+                if let Some(preview) = &mut self.link_preview
+                    && preview.url == url
+                {
+                    preview.resolved = Some(resolved);
+                }
+            }
+            Action::OpenInReader => match (self.view_mode, self.focused_story()) {
+                (ViewMode::Article, _) => {}
+                (ViewMode::StoryDetail, _) if !self.list_has_focus() => {
+                    let _ = self.action_tx.send(Action::ToggleArticleView);
+                }
+                (_, Some(story)) => {
+                    let _ = self
+                        .action_tx
+                        .send(Action::SelectStory(story, self.current_list_type));
+                    let _ = self.action_tx.send(Action::ToggleArticleView);
+                }
+                (_, None) => self.notify_info("No story selected"),
+            },
+            Action::OpenHnPage => match self.focused_story_link() {
+                Some((id, _)) => self.open_in_browser(&crate::utils::url::hn_item_url(id)),
+                None => self.notify_info("No story selected"),
+            },
+            Action::CopyLink | Action::CopyCommentsLink => {
+                match self.focused_story_link() {
+                    Some((id, url)) => {
+                        // Prefer the story URL; Ask/Show HN posts without one get the discussion link
+                        let link = match (action, url) {
+                            (Action::CopyLink, Some(url)) => url,
+                            _ => crate::utils::url::hn_item_url(id),
+                        };
+                        match crate::utils::clipboard::copy_text(&link) {
+                            Ok(_) => self.notify_info(format!("Copied {}", link)),
+                            Err(e) => {
+                                tracing::warn!(%e, "Failed to copy link");
+                                self.notify_error(format!("{:#}", e));
+                            }
+                        }
+                    }
+                    None => self.notify_info("No story selected"),
+                }
+            }
+            Action::ExportStory => match self.selected_story.clone() {
+                Some(story) => {
+                    let article = match self.article_for_story_id == Some(story.id) {
+                        true => self.article_content.clone(),
+                        false => None,
+                    };
+                    let loaded = self.comments.clone();
+                    let api = self.api_service.clone();
+                    let config = self.config.export.clone();
+                    let tx = self.action_tx.clone();
+                    self.notify_info("Exporting…");
+                    tokio::spawn(async move {
+                        // The Comments view only loads three levels; export every reply
+                        let comments = match story.kids.clone() {
+                            Some(kids) => api
+                                .fetch_comment_tree(kids, usize::MAX, None)
+                                .await
+                                .unwrap_or_else(|e| {
+                                    tracing::warn!(%e, "Exporting only the loaded comments");
+                                    loaded
+                                }),
+                            None => Vec::new(),
+                        };
+                        let export = crate::internal::export::StoryExport {
+                            story: &story,
+                            article: article.as_ref(),
+                            comments: &comments,
+                        };
+                        let result = crate::internal::export::write_story(&export, &config)
+                            .map(|path| format!("Exported to {}", path.display()))
+                            .map_err(|e| {
+                                tracing::warn!(%e, "Failed to export story");
+                                format!("{:#}", e)
+                            });
+                        let _ = tx.send(Action::ItemActionDone(result));
+                    });
+                }
+                None => {
+                    self.notify_info("No story selected");
+                }
+            },
+            Action::ExportStoryList => {
+                let list_name = format!("{:?}", self.current_list_type);
+                let displayed = self.filtered_story_indices();
+                let count = displayed.len();
+                let result = crate::internal::export::write_story_list(
+                    displayed.into_iter().map(|(_, story)| story),
+                    &list_name,
+                    &self.config.export,
+                );
+                match result {
+                    Ok(path) => self.notify_info(format!(
+                        "Exported {} stories to {}",
+                        count,
+                        path.display()
+                    )),
+                    Err(e) => {
+                        tracing::warn!(%e, "Failed to export story list");
+                        self.notify_error(format!("{:#}", e));
+                    }
+                }
+            }
+            Action::Upvote | Action::Favorite if self.api_service.is_offline() => {
+                self.notify_info("Offline mode: voting is disabled");
+            }
+            Action::Upvote | Action::Favorite => {
+                use crate::api::auth::ItemAction;
+
+                let item_action = match action {
+                    Action::Upvote => ItemAction::Upvote,
+                    _ => ItemAction::Favorite,
+                };
+                match self.focused_item_id() {
+                    Some(id) => {
+                        let auth = self.auth.clone();
+                        let tx = self.action_tx.clone();
+                        tokio::spawn(async move {
+                            let result = auth
+                                .perform(item_action, id)
+                                .await
+                                .map(|_| format!("{} item {}", item_action.past_tense(), id))
+                                .map_err(|e| format!("{:#}", e));
+                            let _ = tx.send(Action::ItemActionDone(result));
+                        });
+                    }
+                    None => self.notify_info("No item selected"),
+                }
+            }
+            Action::ItemActionDone(result) => match result {
+                Ok(msg) => self.notify_info(msg),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Item action failed");
+                    self.notify_error(e);
+                }
+            },
+            Action::ImportIdList => {
+                // Files of ids are opened with `--ids`; the clipboard is never read as a path
+                let ids = crate::utils::clipboard::read_text()
+                    .map(|text| crate::utils::url::parse_item_ids(&text));
+                match ids {
+                    Ok(ids) if !ids.is_empty() => {
+                        let _ = self.action_tx.send(Action::LoadCustomList(ids));
+                    }
+                    Ok(_) => self.notify_info("No HN item ids or URLs found in clipboard"),
+                    Err(e) => {
+                        tracing::warn!(%e, "Failed to read id list from clipboard");
+                        self.notify_error(format!("{:#}", e));
+                    }
+                }
+            }
+            Action::RunPlugin(index) => {
+                use crate::config::PluginKind;
+
+                if let Some(plugin) = self.config.plugins.get(index).cloned() {
+                    // Item actions get the focused story as JSON on stdin
+                    let input = match plugin.kind {
+                        PluginKind::ListSource => Some(None),
+                        PluginKind::ItemAction => self
+                            .focused_story()
+                            .map(|story| serde_json::to_string(&story).ok()),
+                    };
+                    match input {
+                        Some(input) => {
+                            self.notify_info(format!("Running {}...", plugin.name));
+                            let tx = self.action_tx.clone();
+                            tokio::spawn(async move {
+                                match crate::internal::plugins::run(&plugin, input).await {
+                                    Ok(output) => {
+                                        let _ =
+                                            tx.send(Action::PluginFinished(plugin.name, output));
+                                    }
+                                    Err(e) => {
+                                        tracing::warn!("Plugin {} failed: {:#}", plugin.name, e);
+                                        let _ = tx.send(Action::Error(AppError::new(
+                                            Operation::RunPlugin(index, plugin.name),
+                                            &e,
+                                        )));
+                                    }
+                                }
+                            });
+                        }
+                        None => self.notify_info(format!("{}: no story selected", plugin.name)),
+                    }
+                }
+            }
+            Action::PluginFinished(name, output) => {
+                use crate::internal::plugins::PluginOutput;
+
+                match output {
+                    PluginOutput::Stories(stories) if stories.is_empty() => {
+                        self.notify_info(format!("{} returned no stories", name));
+                    }
+                    // Complete stories are shown as they are
+                    PluginOutput::Stories(stories)
+                        if stories.iter().all(|story| story.title.is_some()) =>
+                    {
+                        self.cancel_previous_request();
+                        let ids: Vec<u32> = stories.iter().map(|story| story.id).collect();
+                        self.notify_info(format!("{}: {} stories", name, ids.len()));
+                        self.custom_list = ids.clone();
+                        self.current_list_type = StoryListType::Custom;
+                        self.view_mode = ViewMode::List;
+                        self.stories.clear();
+                        self.loaded_count = 0;
+                        self.story_list_state.select(None);
+                        self.story_ids = ids;
+                        let _ = self.action_tx.send(Action::StoriesLoaded(stories));
+                    }
+                    // Bare ids are fetched from HN like an imported list
+                    PluginOutput::Stories(stories) => {
+                        let ids = stories.iter().map(|story| story.id).collect();
+                        let _ = self.action_tx.send(Action::LoadCustomList(ids));
+                    }
+                    PluginOutput::Message(text) => match text.lines().next() {
+                        Some(line) => self.notify_info(format!("{}: {}", name, line)),
+                        None => self.notify_info(format!("{} finished", name)),
+                    },
+                }
+            }
+            Action::LoadCustomList(ids) => {
+                self.notify_info(format!("Loaded custom list of {} items", ids.len()));
+                self.custom_list = ids;
+                self.view_mode = ViewMode::List;
+                let _ = self
+                    .action_tx
+                    .send(Action::LoadStories(StoryListType::Custom));
+            }
+            Action::LoadStories(list_type) => {
+                self.cancel_previous_request();
+                self.prefetcher.reset_stories();
+                self.loading = true;
+                // Switching categories applies the category's default sort, if any
+                if (list_type != self.current_list_type || self.stories.is_empty())
+                    && let Some(sort) = self.config.ui.category_sorts.get(&list_type)
+                {
+                    self.sort_by = sort.by;
+                    self.sort_order = sort.order;
+                }
+                self.current_list_type = list_type;
+                // Reset pagination
+                self.stories.clear();
+                self.story_ids.clear();
+                self.loaded_count = 0;
+
+                let api = self.api_service.clone();
+                let tx = self.action_tx.clone();
+                let token = self.get_cancellation_token();
+                let custom_ids = match list_type {
+                    StoryListType::Custom => Some(self.custom_list.clone()),
+                    // Offline, lists come from the last offline pack
+                    _ if self.api_service.is_offline() => self.offline_pack.ids(list_type).cloned(),
+                    _ => None,
+                };
+
+                tokio::spawn(async move {
+                    let ids = match custom_ids {
+                        Some(ids) => Ok(ids),
+                        None => api.fetch_story_ids(list_type, token.clone()).await,
+                    };
+                    match ids {
+                        Ok(ids) => {
+                            tracing::info!("Fetched {} story IDs for {:?}", ids.len(), list_type);
+                            // Send all IDs first
+                            let all_ids = ids.clone();
+                            let _ = tx.send(Action::StoryIdsLoaded(all_ids));
+
+                            // Fetch the first page concurrently (limit: 10), showing
+                            // stories as soon as they arrive
+                            let ids_to_fetch = ids
+                                .iter()
+                                .take(STORY_PAGE_SIZE)
+                                .copied()
+                                .collect::<Vec<_>>();
+                            tracing::info!("Fetching {} stories concurrently", ids_to_fetch.len());
+                            stream_page(&api, &tx, &ids_to_fetch, token).await;
+                        }
+                        Err(e) => {
+                            tracing::error!("Failed to fetch story IDs: {}", e);
+                            // Only report error if not cancelled
+                            let _ = tx.send(Action::Error(AppError::new(
+                                Operation::LoadStories(list_type),
+                                &e,
+                            )));
+                        }
+                    }
+                });
+            }
+            Action::RefreshVisibleStories => {
+                // Only refresh the plain story list; skip while a load is in flight
+                if let (ViewMode::List, false) = (self.view_mode, self.loading) {
+                    let ids = self.visible_story_ids(self.config.network.refresh_margin);
+                    self.spawn_story_refresh(ids);
+                }
+            }
+            Action::CheckUpdates => {
+                let api = self.api_service.clone();
+                let tx = self.action_tx.clone();
+                tokio::spawn(async move {
+                    match api.fetch_updates().await {
+                        Ok(ids) => {
+                            let _ = tx.send(Action::UpdatesReceived(ids));
+                        }
+                        Err(e) => tracing::debug!("Failed to check for updates: {:#}", e),
+                    }
+                });
+            }
+            Action::UpdatesReceived(changed) => {
+                // Changed items are already out of the cache; re-fetch the ones on screen
+                if let (ViewMode::List, false) = (self.view_mode, self.loading) {
+                    let changed: std::collections::HashSet<u32> = changed.into_iter().collect();
+                    let ids = self
+                        .visible_story_ids(self.config.network.refresh_margin)
+                        .into_iter()
+                        .filter(|id| changed.contains(id))
+                        .collect();
+                    self.spawn_story_refresh(ids);
+                }
+            }
+            Action::StoriesRefreshed(updated) => {
+                self.apply_auto_bookmarks(&updated);
+                self.record_snapshot(&updated);
+                self.record_trends(&updated);
+                let now = std::time::Instant::now();
+                self.updated_at
+                    .retain(|_, at| now.duration_since(*at) < UPDATE_FLASH);
+                // Update in place without re-sorting so rows don't jump under the cursor
+                for story in updated {
+                    if let Some(existing) = self.stories.iter_mut().find(|s| s.id == story.id) {
+                        if (existing.score, existing.descendants)
+                            != (story.score, story.descendants)
+                        {
+                            self.updated_at.insert(story.id, now);
+                        }
+                        *existing = story;
+                    }
+                }
+            }
+            Action::SiteMetaFetched(domain, site) => self.domain_meta.record_site(&domain, site),
+            Action::StoriesPrefetched(stories) => {
+                for story in &stories {
+                    if let Some(existing) = self.stories.iter_mut().find(|s| s.id == story.id) {
+                        *existing = story.clone();
+                    }
+                }
+                self.prefetched
+                    .extend(stories.into_iter().map(|story| (story.id, story)));
+            }
+            Action::ToggleHideRead => {
+                self.hide_read = !self.hide_read;
+                self.clamp_list_selection();
+                let msg = match self.hide_read {
+                    true => "Hiding read stories",
+                    false => "Showing all stories",
+                };
+                self.notify_info(msg);
+            }
+            Action::ToggleHideJobs | Action::ToggleHideSeen => {
+                let list_type = self.current_list_type;
+                let focus = self.focus_filters.update(list_type, |focus| match action {
+                    Action::ToggleHideJobs => focus.hide_jobs = !focus.hide_jobs,
+                    _ => focus.hide_seen = !focus.hide_seen,
+                });
+                self.clamp_list_selection();
+                if let Err(e) = self.focus_filters.save() {
+                    tracing::error!(%e, "Failed to save focus filters");
+                }
+                let msg = match focus.label() {
+                    Some(label) => format!("{} stories: {}", list_type, label),
+                    None => format!("Showing all {} stories", list_type),
+                };
+                self.notify_info(msg);
+            }
+            Action::SnapshotThread => {
+                let Some(story_id) = self.selected_story.as_ref().map(|s| s.id) else {
+                    return;
+                };
+                let snapshot =
+                    crate::internal::thread_snapshot::ThreadSnapshot::take(&self.comments);
+                let msg = format!("Saved snapshot of {} comments", snapshot.comments.len());
+                self.thread_snapshots.insert(story_id, snapshot);
+                if let Err(e) = self.thread_snapshots.save() {
+                    tracing::error!(%e, "Failed to save thread snapshots");
+                }
+                // A diff shown from the previous snapshot is now stale
+                self.refresh_thread_diff();
+                self.notify_info(msg);
+            }
+            Action::ToggleThreadDiff => {
+                let snapshot = self
+                    .selected_story
+                    .as_ref()
+                    .and_then(|s| self.thread_snapshots.get(s.id));
+                let msg = match (self.thread_diff.is_some(), snapshot) {
+                    (true, _) => {
+                        self.thread_diff = None;
+                        "Thread diff off".to_string()
+                    }
+                    (false, None) => {
+                        "No snapshot of this thread yet; press s to take one".to_string()
+                    }
+                    (false, Some(snapshot)) => {
+                        let diff = snapshot.diff(&self.comments, &self.comment_ids);
+                        let msg = format!("Changes since snapshot: {}", diff.summary());
+                        self.thread_diff = Some(diff);
+                        msg
+                    }
+                };
+                self.notify_info(msg);
+            }
+            Action::MarkAllRead => {
+                let ids: Vec<u32> = self.stories.iter().map(|s| s.id).collect();
+                let count = ids.len();
+                self.history.mark_all_read(ids);
+                match self.history.save() {
+                    Ok(_) => self.notify_info(format!("Marked {} stories as read", count)),
+                    Err(e) => {
+                        tracing::error!(%e, "Failed to save read state");
+                        self.notify_error("Failed to save read state".to_string());
+                    }
+                }
+                if self.hide_read {
+                    self.story_list_state.select(None);
+                }
+            }
+            Action::StoryIdsLoaded(ids) => {
+                self.list_refreshed.insert(
+                    self.current_list_type,
+                    (ids.len(), jiff::Timestamp::now().as_second()),
+                );
+                self.story_ids = ids;
+            }
+            Action::StoryLoadingProgress(loaded) => {
+                if let Some((_, total)) = self.story_load_progress {
+                    self.story_load_progress = Some((loaded, total));
+                }
+            }
+            Action::StoriesBatchLoaded(stories, remaining) => {
+                let fetched = stories.len();
+                self.append_stories(stories);
+                let (done, total) = self
+                    .story_fetch_progress
+                    .unwrap_or((0, fetched + remaining));
+                self.story_fetch_progress = Some((done + fetched, total));
+            }
+            Action::StoriesLoaded(stories) => {
+                self.append_stories(stories);
+                // A restored selection is only looked for in the first page
+                self.pending_selection = None;
+                self.loading = false;
+                self.story_load_progress = None;
+                self.story_fetch_progress = None;
+            }
+            Action::LoadMoreStories => match (self.loading, self.story_ids.is_empty()) {
+                (true, _) | (_, true) => {}
+                _ => match self.loaded_count >= self.story_ids.len() {
+                    true => {
+                        let msg = format!(
+                            "{}/{} stories already loaded",
+                            self.loaded_count,
+                            self.story_ids.len()
+                        );
+                        self.notify_info(msg);
+                    }
+                    false => {
+                        self.cancel_previous_request();
+                        self.loading = true;
+                        let api = self.api_service.clone();
+                        let tx = self.action_tx.clone();
+                        let token = self.get_cancellation_token();
+                        let ids_to_fetch = self
+                            .story_ids
+                            .iter()
+                            .skip(self.loaded_count)
+                            .take(STORY_PAGE_SIZE)
+                            .copied()
+                            .collect::<Vec<_>>();
+
+                        tokio::spawn(async move {
+                            stream_page(&api, &tx, &ids_to_fetch, token).await;
+                        });
+                    }
+                },
+            },
+            Action::LoadAllStories => match (
+                self.loading,
+                self.story_ids.is_empty(),
+                self.story_load_progress.is_some(),
+            ) {
+                (true, _, _) | (_, true, _) | (_, _, true) => {}
+                _ => {
+                    // Check if all stories are already loaded
+                    match self.loaded_count >= self.story_ids.len() {
+                        true => {
+                            let msg = format!(
+                                "{}/{} stories already loaded",
+                                self.loaded_count,
+                                self.story_ids.len()
+                            );
+                            self.notify_info(msg);
+                        }
+                        false => {
+                            self.cancel_previous_request();
+                            self.loading = true;
+                            // TODO: Re-enable asynchronous loading for "Load All" feature
+                            let api = self.api_service.clone();
+                            let tx = self.action_tx.clone();
+                            let token = self.get_cancellation_token();
+                            let start_idx = self.loaded_count;
+
+                            // Load ALL remaining stories
+                            let ids_to_fetch: Vec<_> =
+                                self.story_ids.iter().skip(start_idx).copied().collect();
+
+                            match ids_to_fetch.is_empty() {
+                                true => {
+                                    self.loading = false;
+                                }
+                                false => {
+                                    self.story_load_progress = Some((0, ids_to_fetch.len()));
+
+                                    tokio::spawn(async move {
+                                        let mut stories = Vec::new();
+                                        for (i, id) in ids_to_fetch.iter().enumerate() {
+                                            if let Some(token) = &token
+                                                && token.is_cancelled()
+                                            {
+                                                break;
+                                            }
+                                            if let Ok(story) = api.fetch_story_content(*id).await {
+                                                stories.push(story);
+                                            }
+                                            let _ = tx.send(Action::StoryLoadingProgress(i + 1));
+                                            // Add a small delay to avoid hitting API rate limits
+                                            tokio::time::sleep(std::time::Duration::from_millis(
+                                                20,
+                                            ))
+                                            .await;
+                                        }
+                                        let _ = tx.send(Action::StoriesLoaded(stories));
+                                    });
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            Action::SelectStory(story, list_type) => {
+                self.cancel_previous_request();
+                self.view_mode = ViewMode::StoryDetail;
+
+                // Check if we are selecting the same story to preserve article state
+                let same_story = self.article_for_story_id == Some(story.id);
+
+                self.scripts.on_open(&story);
+                self.apply_script_effects();
+
+                self.selected_story = Some(story.clone());
+                self.current_list_type = list_type;
+                self.comments.clear();
+                self.comment_ids.clear();
+                self.loaded_comments_count = 0;
+                self.comments_scroll.reset();
+                self.conversation_threads.clear();
+                self.thread_diff = None;
+                self.comment_search = Default::default();
+
+                if !same_story {
+                    self.article_content = None;
+                    self.article_for_story_id = None;
+                    self.article_scroll.reset();
+                    self.article_loading = false;
+                }
+
+                // If the story has a URL, start fetching the article immediately if needed.
+                if let Some(url) = story.url.clone() {
+                    // Fetch if it's a new story OR if we don't have content yet (and not loading)
+                    let should_fetch =
+                        !same_story || (self.article_content.is_none() && !self.article_loading);
+
+                    if should_fetch {
+                        self.spawn_article_fetch(story.id, url);
+                    }
+                }
+
+                // Poll options are separate items; keep them when reopening the same poll
+                if self.poll.as_ref().map(|p| p.id) != Some(story.id) {
+                    self.poll = None;
+                    if let (true, Some(parts)) = (story.is_poll(), &story.parts) {
+                        self.spawn_poll_fetch(story.id, parts.clone());
+                    }
+                }
+
+                // Fetch comments in the background as before so they are available
+                // if the user switches to the comments view.
+                match story.kids {
+                    Some(kids) => self.spawn_comment_fetch(story.id, kids),
+                    None => {
+                        self.comment_ids.clear();
+                        self.loaded_comments_count = 0;
+                        self.comments_loading = false;
+                        self.comment_roots_remaining = 0;
+                    }
+                }
+            }
+            // Batches for a story that is no longer open, or from a fetch that was
+            // replaced by reopening or refreshing it, are dropped
+            Action::CommentsChunkLoaded(story_id, fetch, ..)
+                if self.selected_story.as_ref().map(|s| s.id) != Some(story_id)
+                    || fetch != self.comment_fetch => {}
+            Action::CommentsChunkLoaded(story_id, _, mut comment_rows, remaining) => {
+                // Restore the folds from the last visit to this story
+                if let Some(collapsed) = self.history.folds_for(story_id) {
+                    crate::internal::comment_tree::apply_folds(&mut comment_rows, collapsed);
+                }
+                self.loaded_comments_count += comment_rows.len();
+                self.comments.extend(comment_rows);
+                self.comment_roots_remaining = remaining;
+                self.comments_loading = remaining > 0;
+                self.refresh_thread_diff();
+                if remaining == 0 {
+                    self.index_reading(
+                        crate::internal::reading_index::ItemKind::Comments,
+                        crate::internal::reading_index::thread_text(&self.comments),
+                    );
+                }
+            }
+            Action::LoadMoreComments => {
+                // With fetch_comment_tree, we load all comments at once (up to MAX_COMMENTS limit)
+                // So this action is essentially a no-op for now
+                self.notify_info("All comments already loaded");
+            }
+            Action::AppendComments(parent_index, new_rows) => {
+                // Insert new rows after the parent
+                // Verify parent index is still valid and matches expected depth
+                if parent_index < self.comments.len() {
+                    // let parent_depth = self.comments[parent_index].depth; // Unused
+                    // Insert after parent
+                    let insert_at = parent_index + 1;
+                    self.comments.splice(insert_at..insert_at, new_rows);
+                    // Mark parent as having loaded kids
+                    self.comments[parent_index].loaded_kids = true;
+                    // Ensure parent is expanded
+                    self.comments[parent_index].expanded = true;
+                    self.refresh_thread_diff();
+                }
+            }
+            Action::ToggleCommentCollapse(index) => {
+                let mut should_fetch = false;
+                let mut kids_to_fetch = None;
+                let mut parent_depth = 0;
+
+                // Check if we need to fetch kids (immutable borrow)
+                if let Some(row) = self.comments.get(index)
+                    && !row.expanded
+                    && !row.loaded_kids
+                    && let Some(kids) = &row.comment.kids
+                    && !kids.is_empty()
+                {
+                    should_fetch = true;
+                    kids_to_fetch = Some(kids.clone());
+                    parent_depth = row.depth;
+                }
+
+                if should_fetch && let Some(kids) = kids_to_fetch {
+                    let api = self.api_service.clone();
+                    let tx = self.action_tx.clone();
+                    let token = self.get_cancellation_token();
+
+                    self.notify_info("Loading replies...");
+
+                    tokio::spawn(async move {
+                        if let Ok(mut new_rows) = api.fetch_comment_tree(kids, 3, token).await {
+                            // Adjust depth
+                            for r in &mut new_rows {
+                                r.depth += parent_depth + 1;
+                            }
+                            let _ = tx.send(Action::AppendComments(index, new_rows));
+                        }
+                    });
+                }
+
+                // Toggle expanded (mutable borrow)
+                if let Some(row) = self.comments.get_mut(index) {
+                    row.expanded = !row.expanded;
+                }
+                self.persist_folds();
+            }
+            Action::CollapseAllComments => {
+                crate::internal::comment_tree::set_all_expanded(&mut self.comments, false);
+                self.persist_folds();
+            }
+            Action::ExpandAllComments => {
+                crate::internal::comment_tree::set_all_expanded(&mut self.comments, true);
+                self.persist_folds();
+            }
+            Action::CollapseToDepth(depth) => {
+                crate::internal::comment_tree::collapse_to_depth(&mut self.comments, depth);
+                self.persist_folds();
+            }
+            Action::ToggleConversationView => {
+                use crate::internal::comment_tree::{find_conversations, thread_root};
+                let Some(root) = self
+                    .comment_at_scroll()
+                    .and_then(|id| self.comments.iter().position(|r| r.comment.id == id))
+                    .and_then(|index| thread_root(&self.comments, index))
+                else {
+                    return;
+                };
+                if self.conversation_threads.remove(&root) {
+                    return;
+                }
+                let thread: Vec<_> = self
+                    .comments
+                    .iter()
+                    .skip_while(|r| r.comment.id != root)
+                    .enumerate()
+                    .take_while(|(i, r)| *i == 0 || r.depth > 0)
+                    .map(|(_, r)| r.clone())
+                    .collect();
+                match find_conversations(&thread).is_empty() {
+                    true => self.notify_info("No back-and-forth exchanges in this thread"),
+                    false => {
+                        self.conversation_threads.insert(root);
+                    }
+                }
+            }
+            Action::ToggleArticleView => {
+                match self.view_mode {
+                    ViewMode::StoryDetail => {
+                        self.view_mode = ViewMode::Article;
+                        // If we haven't loaded the article yet, start loading it
                         if self.article_content.is_none()
                             && !self.article_loading
                             && let Some(story) = &self.selected_story
-                            && let Some(url) = &story.url
+                            && let Some(url) = story.url.clone()
+                        {
+                            self.spawn_article_fetch(story.id, url);
+                        }
+                    }
+                    ViewMode::Article => {
+                        self.view_mode = ViewMode::StoryDetail;
+                    }
+                    _ => {}
+                }
+            }
+            Action::ToggleCacheStats => {
+                self.show_cache_stats = !self.show_cache_stats;
+            }
+            Action::SessionRestored(session) => {
+                self.sort_by = session.sort_by;
+                self.sort_order = session.sort_order;
+                if !session.search.is_empty() {
+                    self.search_query = crate::internal::search::SearchQuery::new(
+                        session.search,
+                        session.search_mode,
+                        session.search_type,
+                    );
+                }
+                self.sort_stories();
+                self.comments_scroll
+                    .scroll_to_when_loaded(session.comments_offset);
+                self.article_scroll
+                    .scroll_to_when_loaded(session.article_offset);
+            }
+            Action::TogglePerfHud => match self.config.logging.enable_performance_metrics {
+                true => self.show_perf_hud = !self.show_perf_hud,
+                false => self.notify_info(
+                    "Set logging.enable_performance_metrics to true to use the performance HUD",
+                ),
+            },
+            Action::ToggleHelp => {
+                self.show_help = !self.show_help;
+                if self.show_help {
+                    self.help_page = 0;
+                }
+            }
+            Action::ArticleLoaded(list_type, id, content) => {
+                self.reading_minutes.insert(id, content.reading_minutes());
+                if let (Some(name), Some(domain)) = (
+                    &content.site_name,
+                    self.stories
+                        .iter()
+                        .chain(&self.selected_story)
+                        .find(|story| story.id == id)
+                        .and_then(|story| story.url.as_deref())
+                        .and_then(crate::utils::url::extract_domain),
+                ) {
+                    self.domain_meta.learn_publisher(&domain, name);
+                }
+                // Only apply the loaded article if it matches the currently-selected story
+                // and it was loaded for the same list/category the user selected from.
+                if let Some(selected) = &self.selected_story
+                    && selected.id == id
+                    && self.current_list_type == list_type
+                {
+                    self.index_reading(
+                        crate::internal::reading_index::ItemKind::Article,
+                        crate::internal::reading_index::article_text(&content),
+                    );
+                    self.article_layout.set_article(&content);
+                    self.article_content = Some(content);
+                    self.article_for_story_id = Some(id);
+                }
+                self.article_loading = false;
+            }
+            Action::ScrollArticleUp => self.article_scroll.scroll_up(1),
+            Action::ScrollArticleDown => self.article_scroll.scroll_down(1),
+            Action::SortByScore => {
+                self.sort_by = SortBy::Score;
+                self.sort_stories();
+            }
+            Action::SortByComments if self.current_list_type == StoryListType::Job => {
+                self.notify_info("Job postings have no comments to sort by");
+            }
+            Action::SortByComments => {
+                self.sort_by = SortBy::Comments;
+                self.sort_stories();
+            }
+            Action::SortByTime => {
+                self.sort_by = SortBy::Time;
+                self.sort_stories();
+            }
+            Action::SortByRank => {
+                self.sort_by = SortBy::Rank;
+                self.sort_stories();
+            }
+            Action::ToggleSortOrder => {
+                self.sort_order = match self.sort_order {
+                    SortOrder::Ascending => SortOrder::Descending,
+                    SortOrder::Descending => SortOrder::Ascending,
+                };
+                self.sort_stories();
+            }
+            Action::SwitchTheme => {
+                if self.available_themes.is_empty() {
+                    return;
+                }
+
+                // Determine the currently-active variant mode (e.g., "dark" or "light")
+                let current_mode = self
+                    .available_themes
+                    .get(self.current_theme_index)
+                    .map(|(_, m)| m.to_lowercase())
+                    .unwrap_or_else(|| self.terminal_mode.clone());
+
+                // Collect indices of all discovered themes that have the same mode.
+                let group: Vec<usize> = self
+                    .available_themes
+                    .iter()
+                    .enumerate()
+                    .filter_map(
+                        |(i, (_p, mode))| match mode.eq_ignore_ascii_case(&current_mode) {
+                            true => Some(i),
+                            false => None,
+                        },
+                    )
+                    .collect();
+
+                match group.len() {
+                    n if n > 1 => {
+                        // Cycle within the same-mode group (e.g., all dark themes)
+                        let pos = group
+                            .iter()
+                            .position(|&idx| idx == self.current_theme_index)
+                            .unwrap_or(0);
+                        let next_pos = (pos + 1) % group.len();
+                        let new_idx = group[next_pos];
+                        self.current_theme_index = new_idx;
+                        let (filename, mode) = &self.available_themes[new_idx];
+                        if let Ok(new_theme) = load_theme(
+                            Path::new(filename),
+                            mode,
+                            self.config.logging.enable_performance_metrics,
+                        ) {
+                            self.theme = Self::accessible_theme(&self.config, new_theme, mode);
+                        }
+                    }
+                    _ => {
+                        // Fallback: try to find the next global entry that matches the current mode,
+                        // otherwise just advance by one.
+                        let total = self.available_themes.len();
+                        let mut chosen = (self.current_theme_index + 1) % total;
+                        if let Some(idx) = (0..total)
+                            .map(|n| (self.current_theme_index + 1 + n) % total)
+                            .find(|&i| {
+                                self.available_themes[i]
+                                    .1
+                                    .eq_ignore_ascii_case(&current_mode)
+                            })
                         {
-                            self.article_loading = true;
-                            let api = self.api_service.clone();
-                            let tx = self.action_tx.clone();
-                            let url = url.clone();
-                            let list_type = self.current_list_type;
-                            let story_id = story.id;
-                            let token = self.get_cancellation_token();
-                            tokio::spawn(async move {
-                                match api.fetch_article_content(&url, token).await {
-                                    Ok(content) => {
-                                        let _ = tx.send(Action::ArticleLoaded(
-                                            list_type, story_id, content,
-                                        ));
+                            chosen = idx;
+                        }
+                        self.current_theme_index = chosen;
+                        let (filename, mode) = &self.available_themes[self.current_theme_index];
+                        if let Ok(new_theme) = load_theme(
+                            Path::new(filename),
+                            mode,
+                            self.config.logging.enable_performance_metrics,
+                        ) {
+                            self.theme = Self::accessible_theme(&self.config, new_theme, mode);
+                        }
+                    }
+                }
+            }
+            // Bookmark-related actions
+            Action::ToggleBookmark => {
+                // The open story, or the highlighted row when the list has the focus
+                // (in the Bookmarks view the rows are bookmarks, not `self.stories`).
+                // Capture the current list selection index so we can adjust it after toggling
+                let prev_selected_idx = self.story_list_state.selected();
+                let maybe_story = match &self.selected_story {
+                    Some(story) if !self.list_has_focus() => Some(story.clone()),
+                    _ => self.selected_row(),
+                };
+
+                match maybe_story {
+                    Some(story) => {
+                        self.bookmarks.toggle(&story);
+                        // Persist bookmarks immediately; log on failure
+                        match self.bookmarks.save() {
+                            Err(e) => {
+                                tracing::error!(%e, "Failed to save bookmarks");
+                                self.notify_error("Failed to save bookmarks".to_string());
+                            }
+                            Ok(_) => {
+                                let msg = match self.bookmarks.contains(story.id) {
+                                    true => {
+                                        self.prompt_for_tags(
+                                            story.id,
+                                            story.title.clone().unwrap_or_default(),
+                                        );
+                                        "Bookmarked".to_string()
                                     }
-                                    Err(e) => {
-                                        if e.to_string() != "Request cancelled" {
-                                            let _ = tx.send(Action::Error(format!(
-                                                "Failed to fetch article: {}",
-                                                e
-                                            )));
+                                    false => "Bookmark removed".to_string(),
+                                };
+                                self.notify_info(msg);
+                            }
+                        }
+                        // If we're in the Bookmarks view, adjust the selection so that a removed
+                        // bookmark disappears from the list and selection is clamped to a valid index.
+                        if let ViewMode::Bookmarks = self.view_mode {
+                            // If the story is no longer contained, it was removed.
+                            let shown = self.bookmark_indices().len();
+                            match (
+                                self.bookmarks.contains(story.id),
+                                shown == 0,
+                                prev_selected_idx,
+                            ) {
+                                (false, true, _) => {
+                                    // No bookmarks left: clear selection
+                                    self.story_list_state.select(None);
+                                }
+                                (false, false, Some(prev)) => {
+                                    // Clamp selection to last index if needed
+                                    let max_idx = shown.saturating_sub(1);
+                                    let new_idx = match prev.cmp(&max_idx) {
+                                        std::cmp::Ordering::Greater => max_idx,
+                                        _ => prev,
+                                    };
+                                    self.story_list_state.select(Some(new_idx));
+                                }
+                                (false, false, None) => {
+                                    // No previous selection recorded: select first item
+                                    self.story_list_state.select(Some(0));
+                                }
+                                (true, _, _) => {
+                                    // If the bookmark was added while in Bookmarks view, put selection
+                                    // on the newly-added item (bookmarks.add inserts at 0).
+                                    self.story_list_state.select(Some(0));
+                                }
+                            }
+
+                            // Clear any selected_story because the Bookmarks view is a list view
+                            self.selected_story = None;
+                        }
+                    }
+                    None => {
+                        self.notify_info("No story selected to (un)bookmark".to_string());
+                    }
+                }
+            }
+            Action::ViewBookmarks => {
+                // Switch UI into the Bookmarks view. Selection state in the list is reused
+                // to show bookmarked items; the view rendering code is responsible for
+                // interpreting App.bookmarks when the view_mode == Bookmarks.
+                self.view_mode = ViewMode::Bookmarks;
+                // Reset selection to first item if we have bookmarks
+                match self.bookmark_indices().is_empty() {
+                    false => self.story_list_state.select(Some(0)),
+                    true => self.story_list_state.select(None),
+                }
+            }
+            Action::ExportBookmarks => {
+                // Export bookmarks to a simple file in the config dir
+                match dirs::config_dir() {
+                    Some(dir) => {
+                        let app_dir = dir.join("tui-hn-app");
+                        match std::fs::create_dir_all(&app_dir) {
+                            Err(e) => {
+                                tracing::error!(%e, "Failed to create config dir for export");
+                                self.notify_error("Bookmark export failed".to_string());
+                            }
+                            Ok(_) => {
+                                let export_path = app_dir.join("bookmarks_export.json");
+                                match serde_json::to_string_pretty(&self.bookmarks) {
+                                    Ok(content) => match std::fs::write(&export_path, content) {
+                                        Ok(_) => {
+                                            self.notify_info(format!(
+                                                "Exported to {}",
+                                                export_path.display()
+                                            ));
+                                        }
+                                        Err(e) => {
+                                            tracing::error!(%e, "Failed to write export file");
+                                            self.notify_error("Bookmark export failed".to_string());
                                         }
+                                    },
+                                    Err(e) => {
+                                        tracing::error!(%e, "Failed to serialize bookmarks");
+                                        self.notify_error("Bookmark export failed".to_string());
                                     }
                                 }
-                            });
+                            }
                         }
                     }
-                    ViewMode::Article => {
-                        self.view_mode = ViewMode::StoryDetail;
+                    None => {
+                        self.notify_error("Bookmark export failed (no config dir)".to_string());
                     }
-                    _ => {}
                 }
             }
-            Action::ToggleHelp => {
-                self.show_help = !self.show_help;
-                if self.show_help {
-                    self.help_page = 1; // Reset to page 1 when opening help
+            Action::ImportBookmarks => {
+                // The clipboard holds either a path to an export file or its content
+                let text = crate::utils::clipboard::read_text()
+                    .map(|text| std::fs::read_to_string(text.trim()).unwrap_or(text));
+                // The result clears its own notification when the import finishes
+                let importing = match text {
+                    Ok(text) => {
+                        let parsed = crate::internal::bookmark_import::parse(&text);
+                        match parsed.entries.is_empty() {
+                            true => {
+                                self.notify_info("No HN items found to import");
+                                false
+                            }
+                            false => {
+                                self.notify_info(format!(
+                                    "Importing {} bookmarks...",
+                                    parsed.entries.len()
+                                ));
+                                let existing =
+                                    self.bookmarks.stories.iter().map(|b| b.id).collect();
+                                let limit = self.config.network.concurrent_requests;
+                                let api = self.api_service.clone();
+                                let tx = self.action_tx.clone();
+                                tokio::spawn(async move {
+                                    let found = parsed.entries.len();
+                                    let imported = crate::internal::bookmark_import::resolve(
+                                        &api,
+                                        parsed.entries,
+                                        &existing,
+                                        limit,
+                                    )
+                                    .await;
+                                    let skipped = parsed.skipped + found - imported.len();
+                                    let _ = tx.send(Action::BookmarksImported(imported, skipped));
+                                });
+                                true
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!(%e, "Failed to read bookmarks to import from clipboard");
+                        self.notify_error(format!("{:#}", e));
+                        false
+                    }
+                };
+                if !importing {}
+            }
+            Action::BookmarksImported(imported, skipped) => {
+                let added = self.bookmarks.merge(imported);
+                match self.bookmarks.save() {
+                    Err(e) => {
+                        tracing::error!(%e, "Failed to save imported bookmarks");
+                        self.notify_error("Failed to save bookmarks".to_string());
+                    }
+                    Ok(()) => self.notify_info(match skipped {
+                        0 => format!("Imported {} bookmarks", added),
+                        n => format!("Imported {} bookmarks, skipped {}", added, n),
+                    }),
+                }
+                if self.view_mode == ViewMode::Bookmarks {
+                    self.clamp_list_selection();
+                }
+            }
+            Action::ViewHistory => {
+                self.view_mode = ViewMode::History;
+                self.history_query.clear();
+                self.story_list_state.select(Some(0));
+            }
+            Action::PrepareOfflinePack => {
+                match (self.api_service.is_offline(), self.offline_pack_progress) {
+                    (true, _) => self.notify_info("The offline pack needs a network connection"),
+                    (false, Some(_)) => {
+                        self.notify_info("The offline pack is already being prepared")
+                    }
+                    (false, None) => {
+                        self.offline_pack_progress = Some((0, 0));
+                        let api = self.api_service.clone();
+                        let tx = self.action_tx.clone();
+                        let config = self.config.offline_pack.clone();
+                        let limit = self.config.network.concurrent_requests;
+                        let mut pack = self.offline_pack.clone();
+                        tokio::spawn(async move {
+                            let progress_tx = tx.clone();
+                            let summary = crate::internal::offline_pack::prepare(
+                                &api,
+                                &config,
+                                limit,
+                                &mut pack,
+                                |done, total| {
+                                    let _ =
+                                        progress_tx.send(Action::OfflinePackProgress(done, total));
+                                },
+                            )
+                            .await;
+                            let _ = tx.send(Action::OfflinePackDone(summary, pack));
+                        });
+                    }
+                }
+            }
+            Action::OfflinePackProgress(done, total) => {
+                if self.offline_pack_progress.is_some() {
+                    self.offline_pack_progress = Some((done, total));
+                }
+            }
+            Action::OfflinePackDone(summary, pack) => {
+                self.offline_pack_progress = None;
+                self.offline_pack = pack;
+                for failure in &summary.failures {
+                    tracing::warn!("Offline pack: failed {}", failure);
+                }
+                self.notify_info(summary.line());
+            }
+            Action::OpenRandomBookmark => {
+                let picked = self
+                    .bookmarks
+                    .pick_unread(
+                        |id| self.history.is_read(id),
+                        self.config.random_bookmark.prefer_older,
+                        &jiff::Zoned::now(),
+                        crate::utils::sample::random_unit(),
+                    )
+                    .map(|b| Story {
+                        id: b.id,
+                        title: Some(b.title.clone()),
+                        url: b.url.clone(),
+                        ..Default::default()
+                    });
+                match picked {
+                    Some(fallback) => {
+                        self.notify_info(format!(
+                            "Random bookmark: {}",
+                            fallback.title.as_deref().unwrap_or_default()
+                        ));
+                        self.history.add(&fallback);
+                        let _ = self.history.save();
+                        let id = fallback.id;
+                        let api = self.api_service.clone();
+                        let tx = self.action_tx.clone();
+                        let list_type = self.current_list_type;
+                        tokio::spawn(async move {
+                            let story = api.fetch_story_content(id).await.unwrap_or(fallback);
+                            let _ = tx.send(Action::SelectStory(story, list_type));
+                        });
+                    }
+                    None => self.notify_info("No unread bookmarks"),
+                }
+            }
+            Action::DeleteHistoryEntry => {
+                let focused = self
+                    .story_list_state
+                    .selected()
+                    .and_then(|row| self.history_at(row))
+                    .map(|viewed| (viewed.id, viewed.title.clone()));
+                if let (ViewMode::History, Some((id, title))) = (self.view_mode, focused) {
+                    self.history.remove(id);
+                    match self.history.save() {
+                        Ok(()) => self.notify_info(format!("Removed from history: {}", title)),
+                        Err(e) => {
+                            tracing::error!(%e, "Failed to save history");
+                            self.notify_error("Failed to save history".to_string());
+                        }
+                    }
+                    self.clamp_list_selection();
+                }
+            }
+            Action::AddToReadingList => match self.focused_story() {
+                Some(story) => {
+                    self.list_picker =
+                        Some(crate::internal::ui::list_picker::ListPicker::new(story));
+                }
+                None => self.notify_info("No story selected".to_string()),
+            },
+            Action::MuteStory => match self.focused_story() {
+                Some(story) => self.mute_prompt = Some(story),
+                None => self.notify_info("No story selected".to_string()),
+            },
+            Action::EditBookmarkTags => match self.focused_story() {
+                Some(story) if self.bookmarks.contains(story.id) => {
+                    self.prompt_for_tags(story.id, story.title.unwrap_or_default());
+                }
+                _ => self.notify_info("No bookmark selected".to_string()),
+            },
+            Action::EditBookmarkNote => match self.focused_story() {
+                Some(story) if self.bookmarks.contains(story.id) => {
+                    self.note_editor = Some(crate::internal::ui::note_editor::NoteEditor::new(
+                        story.id,
+                        story.title.unwrap_or_default(),
+                        self.bookmarks.note_of(story.id),
+                    ));
+                }
+                _ => self.notify_info("No bookmark selected".to_string()),
+            },
+            Action::NextBookmarkTag | Action::PrevBookmarkTag => {
+                // Cycle through every tag and back to showing all bookmarks
+                let mut choices: Vec<Option<String>> = vec![None];
+                choices.extend(self.bookmarks.all_tags().into_iter().map(Some));
+                let current = choices
+                    .iter()
+                    .position(|choice| *choice == self.bookmark_tag_filter)
+                    .unwrap_or(0);
+                let next = match action {
+                    Action::NextBookmarkTag => (current + 1) % choices.len(),
+                    _ => (current + choices.len() - 1) % choices.len(),
+                };
+                self.bookmark_tag_filter = choices.swap_remove(next);
+                match self.bookmark_indices().is_empty() {
+                    false => self.story_list_state.select(Some(0)),
+                    true => self.story_list_state.select(None),
+                }
+            }
+            Action::SearchReading => match self.reading_index {
+                Some(_) => {
+                    self.reading_search = Some(Default::default());
                 }
+                None => {
+                    self.notify_info(
+                        "Reading index is off; enable it with reading_index: (enabled: true)"
+                            .to_string(),
+                    );
+                }
+            },
+            Action::ToggleShowMuted => {
+                self.show_muted = !self.show_muted;
+                self.clamp_list_selection();
+                let msg = match self.show_muted {
+                    true => "Showing muted stories and comments",
+                    false => "Hiding muted stories and comments",
+                };
+                self.notify_info(msg.to_string());
             }
-            Action::ArticleLoaded(list_type, id, content) => {
-                // Only apply the loaded article if it matches the currently-selected story
-                // and it was loaded for the same list/category the user selected from.
-                if let Some(selected) = &self.selected_story
-                    && selected.id == id
-                    && self.current_list_type == list_type
+            Action::OpenFilterPanel => {
+                self.filter_panel = Some(crate::internal::ui::filter_panel::FilterPanel::new(
+                    self.config.filters.clone(),
+                ));
+            }
+            Action::OpenSettings => self.settings_panel = Some(Default::default()),
+            Action::OpenCommandPalette => self.command_palette = Some(Default::default()),
+            Action::OpenItemPrompt => self.item_prompt = Some(Default::default()),
+            Action::CycleTimeFormat => {
+                self.time_format = self.time_format.next();
+                self.notify_info(format!("Times: {}", self.time_format.label()));
+            }
+            Action::ReloadConfig => self.reload_config(),
+            Action::ViewReadingLists => {
+                self.view_mode = ViewMode::ReadingLists;
+                if self.current_reading_list >= self.reading_lists.lists.len() {
+                    self.current_reading_list = 0;
+                }
+                self.select_first_reading_item();
+            }
+            Action::NextReadingList | Action::PrevReadingList
+                if self.view_mode == ViewMode::ReadingLists =>
+            {
+                let count = self.reading_lists.lists.len();
+                if count > 0 {
+                    self.current_reading_list = match action {
+                        Action::NextReadingList => (self.current_reading_list + 1) % count,
+                        _ => (self.current_reading_list + count - 1) % count,
+                    };
+                    self.select_first_reading_item();
+                }
+            }
+            Action::MoveItemUp | Action::MoveItemDown
+                if self.view_mode == ViewMode::ReadingLists =>
+            {
+                if let Some(idx) = self.story_list_state.selected()
+                    && let Some(new_idx) = self.reading_lists.move_item(
+                        self.current_reading_list,
+                        idx,
+                        action == Action::MoveItemUp,
+                    )
                 {
-                    self.article_content = Some(content);
-                    self.article_for_story_id = Some(id);
+                    self.story_list_state.select(Some(new_idx));
+                    self.save_reading_lists();
                 }
-                self.article_loading = false;
             }
-            Action::ScrollArticleUp => {
-                if self.article_scroll > 0 {
-                    self.article_scroll -= 1;
+            Action::RemoveFromReadingList if self.view_mode == ViewMode::ReadingLists => {
+                if let Some(idx) = self.story_list_state.selected()
+                    && let Some(item) = self.reading_lists.remove(self.current_reading_list, idx)
+                {
+                    let len = self.displayed_len();
+                    self.story_list_state
+                        .select((len > 0).then(|| idx.min(len - 1)));
+                    self.save_reading_lists();
+                    self.notify_info(format!("Removed: {}", item.title));
                 }
             }
-            Action::ScrollArticleDown => {
-                self.article_scroll += 1;
+            Action::ToggleItemDone if self.view_mode == ViewMode::ReadingLists => {
+                if let Some(idx) = self.story_list_state.selected()
+                    && self
+                        .reading_lists
+                        .toggle_done(self.current_reading_list, idx)
+                        .is_some()
+                {
+                    self.save_reading_lists();
+                }
             }
-            Action::SortByScore => {
-                self.sort_by = SortBy::Score;
-                self.sort_stories();
+            Action::MoveItemUp
+            | Action::MoveItemDown
+            | Action::RemoveFromReadingList
+            | Action::ToggleItemDone
+            | Action::NextReadingList
+            | Action::PrevReadingList => {}
+            Action::ToggleNotificationCenter => {
+                self.notification_center = match self.notification_center {
+                    Some(_) => None,
+                    None => Some(0),
+                };
             }
-            Action::SortByComments => {
-                self.sort_by = SortBy::Comments;
-                self.sort_stories();
+            // Cancellation means the user moved on; nothing to report
+            Action::Error(error) if error.is_cancelled() => {
+                tracing::debug!("{}", error);
+                self.loading = false;
             }
-            Action::SortByTime => {
-                self.sort_by = SortBy::Time;
-                self.sort_stories();
+            Action::Error(error) => {
+                tracing::error!(kind = ?error.kind, "{}", error);
+                match error.operation {
+                    Operation::LoadStories(_) => {
+                        self.loading = false;
+                        self.story_fetch_progress = None;
+                    }
+                    Operation::LoadArticle(_) => self.article_loading = false,
+                    Operation::LoadComments(_)
+                    | Operation::LoadPoll(_)
+                    | Operation::RunPlugin(..) => {}
+                }
+                self.last_error = Some(error);
             }
-            Action::ToggleSortOrder => {
-                self.sort_order = match self.sort_order {
-                    SortOrder::Ascending => SortOrder::Descending,
-                    SortOrder::Descending => SortOrder::Ascending,
-                };
-                self.sort_stories();
+            Action::PollLoaded(poll) => {
+                if self.selected_story.as_ref().map(|s| s.id) == Some(poll.id) {
+                    self.poll = Some(poll);
+                }
             }
-            Action::SwitchTheme => {
-                if self.available_themes.is_empty() {
+            Action::DismissError => self.last_error = None,
+            Action::RetryFailed => {
+                let Some(error) = self.last_error.take() else {
                     return;
+                };
+                let open_story = self.selected_story.clone();
+                match (error.operation, open_story) {
+                    (Operation::LoadStories(list_type), _) => {
+                        let _ = self.action_tx.send(Action::LoadStories(list_type));
+                    }
+                    (Operation::LoadComments(id), Some(story)) if story.id == id => {
+                        self.spawn_comment_fetch(id, story.kids.unwrap_or_default());
+                    }
+                    (Operation::LoadPoll(id), Some(story)) if story.id == id => {
+                        self.spawn_poll_fetch(id, story.parts.unwrap_or_default());
+                    }
+                    (Operation::LoadArticle(id), Some(story)) if story.id == id => {
+                        if let Some(url) = story.url {
+                            self.spawn_article_fetch(id, url);
+                        }
+                    }
+                    (Operation::RunPlugin(index, _), _) => {
+                        let _ = self.action_tx.send(Action::RunPlugin(index));
+                    }
+                    // The story that failed is no longer open
+                    _ => self.notify_info("Nothing to retry"),
                 }
+            }
+        }
+    }
 
-                // Determine the currently-active variant mode (e.g., "dark" or "light")
-                let current_mode = self
-                    .available_themes
-                    .get(self.current_theme_index)
-                    .map(|(_, m)| m.to_lowercase())
-                    .unwrap_or_else(|| self.terminal_mode.clone());
+    /// Fetch the article of the open story in the background.
+    fn spawn_article_fetch(&mut self, story_id: u32, url: String) {
+        self.article_loading = true;
+        let api = self.api_service.clone();
+        let tx = self.action_tx.clone();
+        // Capture the list/category this selection came from for the response
+        let list_type = self.current_list_type;
+        let token = self.get_cancellation_token();
+        tokio::spawn(async move {
+            match api.fetch_article_content(&url, token).await {
+                Ok(content) => {
+                    let _ = tx.send(Action::ArticleLoaded(list_type, story_id, content));
+                }
+                Err(e) => {
+                    let _ = tx.send(Action::Error(AppError::new(
+                        Operation::LoadArticle(story_id),
+                        &e,
+                    )));
+                }
+            }
+        });
+    }
 
-                // Collect indices of all discovered themes that have the same mode.
-                let group: Vec<usize> = self
-                    .available_themes
-                    .iter()
-                    .enumerate()
-                    .filter_map(
-                        |(i, (_p, mode))| match mode.eq_ignore_ascii_case(&current_mode) {
-                            true => Some(i),
-                            false => None,
-                        },
-                    )
-                    .collect();
+    /// Fetch the options of the open poll in the background.
+    fn spawn_poll_fetch(&mut self, story_id: u32, parts: Vec<u32>) {
+        let api = self.api_service.clone();
+        let tx = self.action_tx.clone();
+        let token = self.get_cancellation_token();
+        tokio::spawn(async move {
+            let action = match api.fetch_poll(story_id, &parts, token).await {
+                Ok(poll) => Action::PollLoaded(poll),
+                Err(e) => Action::Error(AppError::new(Operation::LoadPoll(story_id), &e)),
+            };
+            let _ = tx.send(action);
+        });
+    }
 
-                match group.len() {
-                    n if n > 1 => {
-                        // Cycle within the same-mode group (e.g., all dark themes)
-                        let pos = group
-                            .iter()
-                            .position(|&idx| idx == self.current_theme_index)
-                            .unwrap_or(0);
-                        let next_pos = (pos + 1) % group.len();
-                        let new_idx = group[next_pos];
-                        self.current_theme_index = new_idx;
-                        let (filename, mode) = &self.available_themes[new_idx];
-                        if let Ok(new_theme) = load_theme(
-                            Path::new(filename),
-                            mode,
-                            self.config.logging.enable_performance_metrics,
-                        ) {
-                            self.theme = new_theme;
-                        }
-                    }
-                    _ => {
-                        // Fallback: try to find the next global entry that matches the current mode,
-                        // otherwise just advance by one.
-                        let total = self.available_themes.len();
-                        let mut chosen = (self.current_theme_index + 1) % total;
-                        if let Some(idx) = (0..total)
-                            .map(|n| (self.current_theme_index + 1 + n) % total)
-                            .find(|&i| {
-                                self.available_themes[i]
-                                    .1
-                                    .eq_ignore_ascii_case(&current_mode)
-                            })
-                        {
-                            chosen = idx;
-                        }
-                        self.current_theme_index = chosen;
-                        let (filename, mode) = &self.available_themes[self.current_theme_index];
-                        if let Ok(new_theme) = load_theme(
-                            Path::new(filename),
-                            mode,
-                            self.config.logging.enable_performance_metrics,
-                        ) {
-                            self.theme = new_theme;
-                        }
-                    }
+    /// Stream the comment threads under `kids` for the open story.
+    fn spawn_comment_fetch(&mut self, story_id: u32, kids: Vec<u32>) {
+        // Store all comment IDs for pagination
+        self.comment_ids = kids.clone();
+        self.loaded_comments_count = 0;
+        self.comments.clear();
+        self.comments_loading = true;
+        self.comment_roots_remaining = kids.len();
+        self.comment_fetch += 1;
+
+        let api = self.api_service.clone();
+        let tx = self.action_tx.clone();
+        let token = self.get_cancellation_token();
+        let fetch = self.comment_fetch;
+        tokio::spawn(async move {
+            // Stream threads in batches so the first ones render right away
+            let streamed = api
+                .stream_comment_tree(kids, 3, token.clone(), |rows, remaining| {
+                    let _ = tx.send(Action::CommentsChunkLoaded(
+                        story_id, fetch, rows, remaining,
+                    ));
+                })
+                .await;
+            // A cancelled fetch was replaced or left; it reports nothing
+            if token.is_some_and(|token| token.is_cancelled()) {
+                return;
+            }
+            if let Err(e) = streamed {
+                // Keep what arrived and clear the loading state
+                let _ = tx.send(Action::CommentsChunkLoaded(story_id, fetch, Vec::new(), 0));
+                let _ = tx.send(Action::Error(AppError::new(
+                    Operation::LoadComments(story_id),
+                    &e,
+                )));
+            }
+        });
+    }
+
+    /// Return `(id, url)` for the story the user is currently looking at: the open
+    /// story in StoryDetail/Article, or the selected row in List, Bookmarks and History.
+    fn focused_story_link(&self) -> Option<(u32, Option<String>)> {
+        match &self.selected_story {
+            Some(story) if !self.list_has_focus() => Some((story.id, story.url.clone())),
+            _ => self.selected_row().map(|story| (story.id, story.url)),
+        }
+    }
+
+    /// Run the configured auto-bookmark rules over freshly loaded or refreshed stories.
+    fn apply_auto_bookmarks(&mut self, stories: &[Story]) {
+        let config = &self.config.auto_bookmark;
+        if config.rules.is_empty() {
+            return;
+        }
+        let hits = crate::internal::auto_bookmark::evaluate(&config.rules, stories, |id| {
+            self.auto_bookmark_seen.contains(&id) || self.bookmarks.contains(id)
+        });
+        if hits.is_empty() {
+            return;
+        }
+
+        let dry_run = config.dry_run;
+        for hit in &hits {
+            self.auto_bookmark_seen.insert(hit.story_id);
+            tracing::info!(rule = %hit.rule, story = hit.story_id, dry_run, "auto-bookmark rule fired");
+            if let (false, Some(story)) = (dry_run, stories.iter().find(|s| s.id == hit.story_id)) {
+                self.bookmarks.add_tagged(story, hit.tag.as_deref());
+            }
+        }
+
+        let summary = crate::internal::auto_bookmark::summarize(&hits);
+        match dry_run {
+            true => self.notify_info(format!(
+                "Dry run: rules would bookmark {} stories ({})",
+                hits.len(),
+                summary
+            )),
+            false => match self.bookmarks.save() {
+                Ok(_) => self.notify_info(format!(
+                    "Auto-bookmarked {} stories ({})",
+                    hits.len(),
+                    summary
+                )),
+                Err(e) => {
+                    tracing::error!(%e, "Failed to save auto-bookmarks");
+                    self.notify_background_error("Failed to save auto-bookmarks");
                 }
+            },
+        }
+    }
+
+    /// Remember scores and ranks of loaded stories for the next launch.
+    fn record_snapshot(&mut self, stories: &[Story]) {
+        if self.current_list_type == crate::api::StoryListType::Custom {
+            return;
+        }
+        let ids = &self.story_ids;
+        self.story_snapshot
+            .record(&self.current_list_type.to_string(), stories, |id| {
+                ids.iter().position(|i| *i == id)
+            });
+    }
+
+    /// Sample the scores of loaded or refreshed stories for the trend sparklines.
+    fn record_trends(&mut self, stories: &[Story]) {
+        if self.config.ui.list_view.trends != crate::config::TrendsMode::Off {
+            self.story_trends
+                .record(stories, jiff::Timestamp::now().as_second());
+        }
+    }
+
+    /// Sparkline and points gained of a story during the session (or the last
+    /// days, with trends persisted).
+    pub fn story_trend(&self, story: &Story) -> Option<String> {
+        match self.config.ui.list_view.trends {
+            crate::config::TrendsMode::Off => None,
+            _ => self.story_trends.label(story.id),
+        }
+    }
+
+    /// Change of a story in the current list since the previous launch.
+    pub fn story_delta(&self, story: &Story) -> Option<crate::internal::snapshot::StoryDelta> {
+        match (
+            self.config.ui.list_view.show_deltas,
+            self.view_mode,
+            self.current_list_type,
+        ) {
+            (false, _, _) | (_, _, crate::api::StoryListType::Custom) => None,
+            (true, ViewMode::List | ViewMode::StoryDetail | ViewMode::Article, list) => {
+                let rank = self.story_ids.iter().position(|id| *id == story.id);
+                self.previous_snapshot.delta(&list.to_string(), story, rank)
             }
-            // Bookmark-related actions
-            Action::ToggleBookmark => {
-                // Determine the story to toggle:
-                // - Prefer the currently selected detailed story (`selected_story`)
-                // - Otherwise use the selected item from the list
-                // For the Bookmarks view the list indices correspond to `self.bookmarks.stories`
-                // (not `self.stories`), so handle that case explicitly.
-                // Capture the current list selection index so we can adjust it after toggling
-                let prev_selected_idx = self.story_list_state.selected();
-                let maybe_story: Option<Story> =
-                    match (&self.selected_story, self.story_list_state.selected()) {
-                        (Some(s), _) => Some(s.clone()),
-                        (None, Some(idx)) => {
-                            match self.view_mode {
-                                ViewMode::Bookmarks => {
-                                    // Selected index refers into bookmarks.stories
-                                    self.bookmarks.stories.get(idx).map(|bookmarked| Story {
-                                        id: bookmarked.id,
-                                        title: Some(bookmarked.title.clone()),
-                                        url: bookmarked.url.clone(),
-                                        by: None,
-                                        score: None,
-                                        time: None,
-                                        descendants: None,
-                                        kids: None,
-                                    })
-                                }
-                                _ => {
-                                    // Normal list: map displayed indices to stories
-                                    let displayed = self.filtered_story_indices();
-                                    displayed.get(idx).map(|(_, s)| (*s).clone())
-                                }
-                            }
-                        }
-                        _ => None,
-                    };
+            _ => None,
+        }
+    }
+
+    fn select_first_reading_item(&mut self) {
+        let len = self.displayed_len();
+        self.story_list_state.select((len > 0).then_some(0));
+    }
+
+    fn save_reading_lists(&mut self) {
+        if let Err(e) = self.reading_lists.save() {
+            tracing::warn!(%e, "Failed to save reading lists");
+            self.notify_error(format!("{:#}", e));
+        }
+    }
+
+    /// Save which comment subtrees of the open story are folded.
+    fn persist_folds(&mut self) {
+        if let Some(story) = &self.selected_story {
+            let collapsed = crate::internal::comment_tree::collapsed_ids(&self.comments);
+            self.history.set_folds(story.id, collapsed);
+            if let Err(e) = self.history.save() {
+                tracing::warn!(%e, "Failed to save fold state");
+            }
+        }
+    }
+
+    /// `timestamp` (Unix seconds) in the current time format.
+    pub fn format_time(&self, timestamp: &i64) -> String {
+        crate::utils::datetime::format_time(timestamp, self.time_format)
+    }
+
+    /// Keybinding context of the current view; a focused list pane uses List bindings.
+    pub fn key_context(&self) -> crate::internal::ui::keybindings::KeyBindingContext {
+        use crate::internal::ui::keybindings::KeyBindingContext;
+        match self.view_mode {
+            _ if self.list_has_focus() => KeyBindingContext::List,
+            ViewMode::List => KeyBindingContext::List,
+            ViewMode::StoryDetail => KeyBindingContext::StoryDetail,
+            ViewMode::Article => KeyBindingContext::Article,
+            ViewMode::Bookmarks => KeyBindingContext::Bookmarks,
+            ViewMode::History => KeyBindingContext::History,
+            ViewMode::ReadingLists => KeyBindingContext::ReadingLists,
+        }
+    }
+
+    /// Give up on a chord whose second key did not come in time, running the prefix
+    /// key's own binding instead (so a lone `y` still copies the link).
+    pub fn expire_chord(&mut self) {
+        let timeout = std::time::Duration::from_millis(self.config.ui.chord_timeout_ms);
+        let Some((first, _)) = self
+            .pending_chord
+            .take_if(|(_, pressed)| pressed.elapsed() >= timeout)
+        else {
+            return;
+        };
+        if let Some(action) = self.keybindings.get_action(&first, self.key_context()) {
+            let _ = self.action_tx.send(action);
+        }
+    }
+
+    /// True when the split layout is showing the list beside a story.
+    pub fn split_active(&self) -> bool {
+        crate::internal::ui::layout::is_split(self.view_mode, self.config.ui.split_layout)
+    }
+
+    /// True when navigation keys should go to the list pane of the split layout.
+    pub fn list_has_focus(&self) -> bool {
+        self.split_active() && self.focused_pane == crate::internal::ui::layout::Pane::List
+    }
+
+    /// Scroll state of the comments or article pane, unless the list has the focus.
+    fn detail_scroll(&mut self) -> Option<&mut crate::internal::ui::scroll::ScrollState> {
+        match (self.view_mode, self.list_has_focus()) {
+            (_, true) => None,
+            (ViewMode::StoryDetail, false) => Some(&mut self.comments_scroll),
+            (ViewMode::Article, false) => Some(&mut self.article_scroll),
+            _ => None,
+        }
+    }
+
+    /// Id of the comment whose header is at (or just above) the top of the comments pane.
+    pub fn comment_at_scroll(&self) -> Option<u32> {
+        self.comment_line_index
+            .iter()
+            .take_while(|(line, _)| *line <= self.comments_scroll.offset())
+            .last()
+            .or(self.comment_line_index.first())
+            .map(|(_, id)| *id)
+    }
+
+    /// Item targeted by vote/favorite: the top visible comment in the comments view,
+    /// otherwise the focused story.
+    fn focused_item_id(&self) -> Option<u32> {
+        match self.view_mode {
+            ViewMode::StoryDetail if !self.list_has_focus() => self
+                .comment_at_scroll()
+                .or(self.selected_story.as_ref().map(|s| s.id)),
+            _ => self.focused_story_link().map(|(id, _)| id),
+        }
+    }
+
+    /// Return a vector of (original_index, &Story) representing the currently-displayed stories
+    /// after applying the search filter. This ensures selection indices used by `ListState`
+    /// correspond to the displayed items.
+    pub fn filtered_story_indices(&self) -> Vec<(usize, &Story)> {
+        let focus = self.focus_filters.get(self.current_list_type);
+        let now = jiff::Timestamp::now().as_second();
+        let is_hidden = |story: &Story| {
+            (self.hide_read && self.history.is_read(story.id))
+                || (focus.hide_jobs && story.is_job())
+                || (focus.hide_seen && self.history.has_seen(story))
+                || self.scripts.is_hidden(story.id)
+                || (!self.show_muted && self.mute_list.mutes_story(story))
+                || !crate::internal::story_filter::matches(&self.config.filters, story, now)
+        };
+        // Comment modes search the comments already in the cache
+        let in_comments = |story: &Story| {
+            self.api_service.cached_comments_match(story, |comment| {
+                comment.text.as_deref().is_some_and(|text| {
+                    self.search_query
+                        .matches(&crate::utils::html::extract_text_from_html(text))
+                })
+            })
+        };
+        crate::internal::search::filter_stories(
+            &self.stories,
+            &self.search_query,
+            is_hidden,
+            in_comments,
+        )
+    }
+
+    /// Indices into `bookmarks.stories` of the bookmarks shown in the Bookmarks
+    /// view, which are those with the selected tag when one is chosen.
+    pub fn bookmark_indices(&self) -> Vec<usize> {
+        self.bookmarks
+            .stories
+            .iter()
+            .enumerate()
+            .filter(|(_, bookmark)| {
+                self.bookmark_tag_filter
+                    .as_deref()
+                    .is_none_or(|tag| bookmark.has_tag(tag))
+            })
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Bookmark on row `row` of the Bookmarks view.
+    fn bookmark_at(&self, row: usize) -> Option<&crate::internal::bookmarks::BookmarkedStory> {
+        self.bookmark_indices()
+            .get(row)
+            .and_then(|idx| self.bookmarks.stories.get(*idx))
+    }
+
+    /// Indices into `history.stories` of the rows of the History view, narrowed by
+    /// the history search.
+    pub fn history_indices(&self) -> Vec<usize> {
+        self.history.search(&self.history_query)
+    }
+
+    /// History entry on row `row` of the History view.
+    fn history_at(&self, row: usize) -> Option<&crate::internal::history::ViewedStory> {
+        self.history_indices()
+            .get(row)
+            .and_then(|idx| self.history.stories.get(*idx))
+    }
+
+    /// Scroll the comments to the next (or previous) match of the comment search. A
+    /// match off screen is brought to the middle so the thread around it shows too.
+    fn jump_to_comment_match(&mut self, forward: bool) {
+        if let Some(line) = self
+            .comment_search
+            .step(forward, self.comments_scroll.offset())
+        {
+            match self.comments_scroll.visible_range().contains(&line) {
+                true => self.comments_scroll.ensure_visible(line, 3),
+                false => self.comments_scroll.center_on(line),
+            }
+        }
+    }
+
+    /// Open the story after (or before) the open one in the displayed list, staying
+    /// in the Article view if that is where the reader is. The list selection follows,
+    /// so going back lands on the story last read.
+    fn step_story(&mut self, forward: bool) {
+        if !matches!(self.view_mode, ViewMode::StoryDetail | ViewMode::Article) {
+            return;
+        }
+        let displayed = self.filtered_story_indices();
+        let open_id = self.selected_story.as_ref().map(|s| s.id);
+        let current = displayed
+            .iter()
+            .position(|(_, story)| Some(story.id) == open_id)
+            .or(self.story_list_state.selected());
+        let target = match (current, forward) {
+            (Some(i), true) => i + 1,
+            (Some(i), false) => match i.checked_sub(1) {
+                Some(i) => i,
+                None => return self.notify_info("First story in the list"),
+            },
+            (None, _) => 0,
+        };
+        let Some(story) = displayed.get(target).map(|(_, story)| (*story).clone()) else {
+            return self.notify_info("Last loaded story in the list");
+        };
 
-                match maybe_story {
-                    Some(story) => {
-                        self.bookmarks.toggle(&story);
-                        // Persist bookmarks immediately; log on failure
-                        match self.bookmarks.save() {
-                            Err(e) => {
-                                tracing::error!(%e, "Failed to save bookmarks");
-                                self.notify_error("Failed to save bookmarks".to_string());
-                            }
-                            Ok(_) => {
-                                let msg = match self.bookmarks.contains(story.id) {
-                                    true => "Bookmarked".to_string(),
-                                    false => "Bookmark removed".to_string(),
-                                };
-                                self.notify_info(msg);
-                            }
-                        }
-                        // If we're in the Bookmarks view, adjust the selection so that a removed
-                        // bookmark disappears from the list and selection is clamped to a valid index.
-                        if let ViewMode::Bookmarks = self.view_mode {
-                            // If the story is no longer contained, it was removed.
-                            match (
-                                self.bookmarks.contains(story.id),
-                                self.bookmarks.stories.is_empty(),
-                                prev_selected_idx,
-                            ) {
-                                (false, true, _) => {
-                                    // No bookmarks left: clear selection
-                                    self.story_list_state.select(None);
-                                }
-                                (false, false, Some(prev)) => {
-                                    // Clamp selection to last index if needed
-                                    let max_idx = self.bookmarks.stories.len().saturating_sub(1);
-                                    let new_idx = match prev.cmp(&max_idx) {
-                                        std::cmp::Ordering::Greater => max_idx,
-                                        _ => prev,
-                                    };
-                                    self.story_list_state.select(Some(new_idx));
-                                }
-                                (false, false, None) => {
-                                    // No previous selection recorded: select first item
-                                    self.story_list_state.select(Some(0));
-                                }
-                                (true, _, _) => {
-                                    // If the bookmark was added while in Bookmarks view, put selection
-                                    // on the newly-added item (bookmarks.add inserts at 0).
-                                    self.story_list_state.select(Some(0));
-                                }
-                            }
+        let in_article = self.view_mode == ViewMode::Article && story.url.is_some();
+        self.story_list_state.select(Some(target));
+        self.history.add(&story);
+        let _ = self.history.save();
+        let _ = self
+            .action_tx
+            .send(Action::SelectStory(story, self.current_list_type));
+        if in_article {
+            let _ = self.action_tx.send(Action::ToggleArticleView);
+        }
+    }
 
-                            // Clear any selected_story because the Bookmarks view is a list view
-                            self.selected_story = None;
-                        }
+    /// Open `url` in the browser, through the confirmation popup if `ui.confirm_links` is set.
+    fn open_link(&mut self, url: String) {
+        match self.config.ui.confirm_links {
+            true => self.preview_link(url),
+            false => self.open_in_browser(&url),
+        }
+    }
 
-                        // Schedule notification clear after a short delay
-                        let tx = self.action_tx.clone();
-                        tokio::spawn(async move {
-                            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
-                            let _ = tx.send(Action::ClearNotification);
-                        });
-                    }
-                    None => {
-                        self.notify_info("No story selected to (un)bookmark".to_string());
+    /// Hand `url` to the system browser, reporting a failure in the status bar.
+    fn open_in_browser(&mut self, url: &str) {
+        if let Err(e) = open::that(url) {
+            self.notify_error(format!("Could not open {}: {}", url, e));
+        }
+    }
 
-                        // Schedule notification clear after a short delay
-                        let tx = self.action_tx.clone();
-                        tokio::spawn(async move {
-                            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
-                            let _ = tx.send(Action::ClearNotification);
-                        });
-                    }
+    /// Links of the open story: its URL, then the links of the article in the
+    /// Article view, or of the post text and the loaded comments in the Comments view.
+    pub fn page_links(&self) -> Vec<crate::internal::models::Link> {
+        use crate::internal::models::Link;
+        use crate::utils::html::extract_links;
+
+        let Some(story) = &self.selected_story else {
+            return Vec::new();
+        };
+        let mut links: Vec<Link> = story
+            .url
+            .iter()
+            .map(|url| Link {
+                text: story.title.clone().unwrap_or_else(|| url.clone()),
+                url: url.clone(),
+            })
+            .collect();
+        match self.view_mode {
+            ViewMode::Article => {
+                if let Some(article) = &self.article_content {
+                    links.extend(article.links.iter().cloned());
                 }
             }
-            Action::ViewBookmarks => {
-                // Switch UI into the Bookmarks view. Selection state in the list is reused
-                // to show bookmarked items; the view rendering code is responsible for
-                // interpreting App.bookmarks when the view_mode == Bookmarks.
-                self.view_mode = ViewMode::Bookmarks;
-                // Reset selection to first item if we have bookmarks
-                match self.bookmarks.stories.first() {
-                    Some(_) => self.story_list_state.select(Some(0)),
-                    None => self.story_list_state.select(None),
+            _ => {
+                links.extend(story.text.as_deref().map(extract_links).unwrap_or_default());
+                for row in &self.comments {
+                    links.extend(
+                        row.comment
+                            .text
+                            .as_deref()
+                            .map(extract_links)
+                            .unwrap_or_default(),
+                    );
                 }
             }
-            Action::ExportBookmarks => {
-                // Export bookmarks to a simple file in the config dir
-                match dirs::config_dir() {
-                    Some(dir) => {
-                        let app_dir = dir.join("tui-hn-app");
-                        match std::fs::create_dir_all(&app_dir) {
-                            Err(e) => {
-                                tracing::error!(%e, "Failed to create config dir for export");
-                                self.notify_error("Bookmark export failed".to_string());
-                            }
-                            Ok(_) => {
-                                let export_path = app_dir.join("bookmarks_export.json");
-                                match serde_json::to_string_pretty(&self.bookmarks) {
-                                    Ok(content) => match std::fs::write(&export_path, content) {
-                                        Ok(_) => {
-                                            self.notify_info(format!(
-                                                "Exported to {}",
-                                                export_path.display()
-                                            ));
-                                        }
-                                        Err(e) => {
-                                            tracing::error!(%e, "Failed to write export file");
-                                            self.notify_error("Bookmark export failed".to_string());
-                                        }
-                                    },
-                                    Err(e) => {
-                                        tracing::error!(%e, "Failed to serialize bookmarks");
-                                        self.notify_error("Bookmark export failed".to_string());
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    None => {
-                        self.notify_error("Bookmark export failed (no config dir)".to_string());
-                    }
-                }
+        }
+        let mut seen = std::collections::HashSet::new();
+        links.retain(|link| seen.insert(link.url.clone()));
+        links
+    }
+
+    /// Ask before opening `url`, resolving its redirects in the background.
+    fn preview_link(&mut self, url: String) {
+        self.link_preview = Some(crate::internal::ui::link_preview::LinkPreview::new(
+            url.clone(),
+        ));
+        let api = self.api_service.clone();
+        let tx = self.action_tx.clone();
+        tokio::spawn(async move {
+            let resolved = api.resolve_url(&url).await.map_err(|e| format!("{:#}", e));
+            let _ = tx.send(Action::LinkResolved(url, resolved));
+        });
+    }
+
+    /// Re-fetch `ids` bypassing the cache; the results arrive as `StoriesRefreshed`.
+    fn spawn_story_refresh(&self, ids: Vec<u32>) {
+        if ids.is_empty() {
+            return;
+        }
+        let api = self.api_service.clone();
+        let tx = self.action_tx.clone();
+        let token = self.get_cancellation_token();
+        let limit = self.config.network.concurrent_requests;
+        tokio::spawn(async move {
+            tracing::debug!("Refreshing {} visible stories", ids.len());
+            let stories: Vec<_> = api
+                .refresh_stories(&ids, limit, token)
+                .await
+                .into_iter()
+                .filter_map(|r| r.ok())
+                .collect();
+            let _ = tx.send(Action::StoriesRefreshed(stories));
+        });
+    }
+
+    /// Refresh every bookmarked story and watched thread (those with a saved
+    /// snapshot), then the articles of the bookmarks, a few at a time. Articles
+    /// still fresh in the cache are left alone.
+    fn spawn_startup_prefetch(&self) {
+        let mut ids: Vec<u32> = self.bookmarks.stories.iter().map(|b| b.id).collect();
+        for id in self.thread_snapshots.threads.keys() {
+            if !ids.contains(id) {
+                ids.push(*id);
             }
-            Action::ImportBookmarks => {
-                // Try to load bookmarks from disk (bookmarks.json). If it fails, keep current bookmarks.
-                match crate::internal::bookmarks::Bookmarks::load_or_create() {
-                    Ok(b) => {
-                        self.bookmarks = b;
-                        self.notify_info("Bookmarks imported".to_string());
-                    }
-                    Err(e) => {
-                        tracing::error!(%e, "Failed to import bookmarks");
-                        self.notify_error("Bookmark import failed".to_string());
+        }
+        if ids.is_empty() {
+            return;
+        }
+        let bookmarked: std::collections::HashSet<u32> =
+            self.bookmarks.stories.iter().map(|b| b.id).collect();
+        let prefetch = self.config.startup_prefetch.clone();
+        let limit = prefetch.concurrency.max(1);
+        let api = self.api_service.clone();
+        let tx = self.action_tx.clone();
+        let token = self.get_cancellation_token();
+        tokio::spawn(async move {
+            use futures::stream::{self, StreamExt};
+
+            let stories: Vec<Story> = api
+                .refresh_stories(&ids, limit, token.clone())
+                .await
+                .into_iter()
+                .filter_map(|r| r.ok())
+                .collect();
+            tracing::info!(
+                "Prefetched {} of {} bookmarked and watched stories",
+                stories.len(),
+                ids.len()
+            );
+            let urls: Vec<String> = stories
+                .iter()
+                .filter(|story| bookmarked.contains(&story.id))
+                .filter_map(|story| story.url.clone())
+                .collect();
+            let _ = tx.send(Action::StoriesPrefetched(stories));
+            if !prefetch.articles || urls.is_empty() {
+                return;
+            }
+
+            let fetched = stream::iter(urls)
+                .map(|url| {
+                    let api = api.clone();
+                    let token = token.clone();
+                    async move { api.fetch_article_content(&url, token).await.is_ok() }
+                })
+                .buffer_unordered(limit)
+                .filter(|ok| std::future::ready(*ok))
+                .count()
+                .await;
+            tracing::info!("Prefetched {} bookmarked articles", fetched);
+            // Keep the offline copies even if the app does not exit cleanly
+            if let Err(e) = api.persist_caches() {
+                tracing::warn!("Failed to save prefetched articles: {:#}", e);
+            }
+        });
+    }
+
+    /// Fetch what the list selection is likely to open next (`prefetch`): the next
+    /// stories once it nears the end of the list, and the article and first comments
+    /// of a story highlighted for `highlight_delay_ms`. Called on every turn of the
+    /// run loop; moving the selection cancels the previous story's prefetch.
+    fn prefetch_ahead(&mut self) {
+        use crate::internal::ui::prefetch;
+
+        if !self.config.prefetch.enabled || self.api_service.is_offline() {
+            return;
+        }
+        let now = std::time::Instant::now();
+        let selected = match self.view_mode {
+            ViewMode::List => self.story_list_state.selected(),
+            _ => None,
+        };
+        let rows = self.filtered_story_indices();
+        let row_count = rows.len();
+        let highlighted = selected
+            .and_then(|idx| rows.get(idx))
+            .map(|(_, story)| (*story).clone());
+        self.prefetcher
+            .highlight(highlighted.as_ref().map(|story| story.id), now);
+
+        if let Some(selected) = selected
+            && !self.loading
+            && let Some((ids, token)) = self.prefetcher.next_stories(
+                selected,
+                row_count,
+                &self.story_ids,
+                self.loaded_count,
+                self.config.prefetch.stories,
+            )
+        {
+            tracing::debug!(count = ids.len(), "Prefetching the next stories");
+            let api = self.api_service.clone();
+            let limit = self.config.network.concurrent_requests.max(1);
+            tokio::spawn(async move {
+                api.fetch_stories_concurrent(&ids, limit, Some(token)).await;
+            });
+        }
+
+        let delay = std::time::Duration::from_millis(self.config.prefetch.highlight_delay_ms);
+        if let Some(story) = highlighted
+            && let Some((_, token)) = self.prefetcher.take_due(delay, now)
+        {
+            tracing::debug!(id = story.id, "Prefetching the highlighted story");
+            let kids: Vec<u32> = story
+                .kids
+                .iter()
+                .flatten()
+                .take(prefetch::COMMENTS)
+                .copied()
+                .collect();
+            let api = self.api_service.clone();
+            tokio::spawn(async move {
+                let article = async {
+                    if let Some(url) = &story.url {
+                        let _ = api.fetch_article_content(url, Some(token.clone())).await;
                     }
-                }
+                };
+                let comments = api.fetch_comment_tree(kids, 0, Some(token.clone()));
+                let _ = futures::join!(article, comments);
+            });
+        }
+    }
+
+    /// Fetch the home pages of story sites not looked up lately, a few at a time, for
+    /// their names and brand colors (`network.fetch_domain_metadata`).
+    fn look_up_domains(&mut self, stories: &[Story]) {
+        if !self.config.network.fetch_domain_metadata || self.api_service.is_offline() {
+            return;
+        }
+        let now = jiff::Timestamp::now().as_second();
+        let mut domains = Vec::new();
+        for domain in stories
+            .iter()
+            .filter_map(|story| story.url.as_deref())
+            .filter_map(crate::utils::url::extract_domain)
+            .map(|domain| crate::internal::domain_meta::normalize(&domain))
+        {
+            if self.domain_meta.needs_lookup(&domain, now) {
+                self.domain_meta.mark_checked(&domain, now);
+                domains.push(domain);
             }
-            Action::ViewHistory => {
-                self.view_mode = ViewMode::History;
-                self.story_list_state.select(Some(0));
+        }
+        if domains.is_empty() {
+            return;
+        }
+        let api = self.api_service.clone();
+        let tx = self.action_tx.clone();
+        tokio::spawn(async move {
+            use futures::stream::{self, StreamExt};
+
+            stream::iter(domains)
+                .for_each_concurrent(DOMAIN_LOOKUPS, |domain| {
+                    let api = api.clone();
+                    let tx = tx.clone();
+                    async move {
+                        match api.fetch_site_meta(&domain).await {
+                            Ok(site) => {
+                                let _ = tx.send(Action::SiteMetaFetched(domain, site));
+                            }
+                            Err(e) => tracing::debug!("Site metadata lookup failed: {:#}", e),
+                        }
+                    }
+                })
+                .await;
+        });
+    }
+
+    /// Whether a refresh changed this story's score or comment count a moment ago.
+    pub fn recently_updated(&self, id: u32) -> bool {
+        self.updated_at
+            .get(&id)
+            .is_some_and(|at| at.elapsed() < UPDATE_FLASH)
+    }
+
+    /// Recompute the shown thread diff after comments were loaded or a new snapshot taken.
+    fn refresh_thread_diff(&mut self) {
+        if self.thread_diff.is_none() {
+            return;
+        }
+        self.thread_diff = self
+            .selected_story
+            .as_ref()
+            .and_then(|s| self.thread_snapshots.get(s.id))
+            .map(|snapshot| snapshot.diff(&self.comments, &self.comment_ids));
+    }
+
+    /// Keep the selection within the (possibly shorter) filtered list.
+    fn clamp_list_selection(&mut self) {
+        let len = self.displayed_len();
+        match (len, self.story_list_state.selected()) {
+            (0, _) => self.story_list_state.select(None),
+            (n, Some(i)) if i >= n => self.story_list_state.select(Some(n - 1)),
+            (_, None) => self.story_list_state.select(Some(0)),
+            _ => {}
+        }
+    }
+
+    /// IDs of the stories currently visible in the list viewport, plus `margin`
+    /// rows above and below, so background refreshes don't touch the whole list.
+    pub fn visible_story_ids(&self, margin: usize) -> Vec<u32> {
+        let displayed = self.filtered_story_indices();
+        let window = visible_window(
+            self.story_list_state.offset(),
+            self.list_viewport_rows,
+            displayed.len(),
+            margin,
+        );
+        displayed[window].iter().map(|(_, s)| s.id).collect()
+    }
+
+    /// Save the edited theme and its generated light or dark counterpart as the two
+    /// variants of one theme file.
+    fn export_theme_to_file(&self, name: &str) -> anyhow::Result<std::path::PathBuf> {
+        use std::fs;
+        use std::path::PathBuf;
+
+        let edited = &self.theme_editor.temp_theme;
+        let complementary = self.theme_editor.generate_complementary();
+        let variants = match self.theme_editor.is_dark_theme() {
+            true => [("dark", edited), ("light", &complementary)],
+            false => [("light", edited), ("dark", &complementary)],
+        };
+        let theme_data = crate::utils::theme_loader::theme_file_json(name, &variants);
+
+        // Ensure themes directory exists
+        let themes_dir = PathBuf::from(&self.config.theme_directory);
+        fs::create_dir_all(&themes_dir)?;
+
+        let filename = format!("{}_custom.json", name.to_lowercase().replace(' ', "_"));
+        let path = themes_dir.join(&filename);
+
+        let json = serde_json::to_string_pretty(&theme_data)?;
+        fs::write(&path, json)?;
+
+        Ok(path)
+    }
+
+    /// Make both variants of a just saved theme file available to theme switching,
+    /// with the edited variant as the current theme.
+    fn register_saved_theme(&mut self, path: &Path) {
+        let path = path.to_string_lossy().to_string();
+        for mode in ["dark", "light"] {
+            let entry = (path.clone(), mode.to_string());
+            if !self.available_themes.contains(&entry) {
+                self.available_themes.push(entry);
             }
-            Action::ClearNotification => {
-                self.clear_notification();
+        }
+        let mode = match self.theme_editor.is_dark_theme() {
+            true => "dark",
+            false => "light",
+        };
+        if let Some(index) = self
+            .available_themes
+            .iter()
+            .position(|(p, m)| *p == path && m == mode)
+        {
+            self.current_theme_index = index;
+        }
+    }
+
+    /// Add loaded stories to the list, keeping the focused story selected while the
+    /// list re-sorts around it.
+    fn append_stories(&mut self, stories: Vec<Story>) {
+        self.apply_auto_bookmarks(&stories);
+        self.look_up_domains(&stories);
+        self.record_snapshot(&stories);
+        self.record_trends(&stories);
+        for story in &stories {
+            self.scripts.on_story(story);
+        }
+        let focused_id = |app: &Self| match (app.view_mode, app.story_list_state.selected()) {
+            (ViewMode::List, Some(idx)) => app
+                .filtered_story_indices()
+                .get(idx)
+                .map(|(_, story)| story.id),
+            _ => None,
+        };
+        let selected_id = self.pending_selection.or_else(|| focused_id(self));
+        // Update loaded count and append stories
+        self.loaded_count += stories.len();
+        self.stories.extend(stories);
+        self.sort_stories();
+        let row_of = |id| {
+            self.filtered_story_indices()
+                .iter()
+                .position(|(_, story)| story.id == id)
+        };
+        match selected_id.and_then(row_of) {
+            Some(idx) => {
+                self.pending_selection = None;
+                self.story_list_state.select(Some(idx))
             }
-            Action::Error(msg) => {
-                self.loading = false;
-                // TODO: Show error
-                tracing::error!("{}", msg);
+            None if !self.stories.is_empty() && self.story_list_state.selected().is_none() => {
+                self.story_list_state.select(Some(0))
             }
+            None => {}
         }
+        self.apply_script_effects();
     }
 
-    /// Return a vector of (original_index, &Story) representing the currently-displayed stories
-    /// after applying the search filter. This ensures selection indices used by `ListState`
-    /// correspond to the displayed items.
-    pub fn filtered_story_indices(&self) -> Vec<(usize, &Story)> {
-        match self.search_query.is_empty() {
-            true => self.stories.iter().enumerate().collect(),
-            false => self
+    /// Show what user scripts reported and carry out the actions and tags they asked for.
+    fn apply_script_effects(&mut self) {
+        let effects = self.scripts.take_effects();
+        for message in effects.notifications {
+            self.notify_info(message);
+        }
+        for error in effects.errors {
+            self.notify_background_error(error);
+        }
+        for action in effects.actions {
+            let _ = self.action_tx.send(action);
+        }
+        if effects.tags.is_empty() {
+            return;
+        }
+        for (id, tag) in &effects.tags {
+            let story = self
                 .stories
                 .iter()
-                .enumerate()
-                .filter(|(_, story)| {
-                    story
-                        .title
-                        .as_ref()
-                        .map(|t| self.search_query.matches(t))
-                        .unwrap_or(false)
-                })
-                .collect(),
+                .chain(self.selected_story.as_ref())
+                .find(|s| s.id == *id)
+                .cloned();
+            match story {
+                Some(story) => self.bookmarks.add_tagged(&story, Some(tag)),
+                None => tracing::warn!(story = id, "Script tagged a story that is not loaded"),
+            }
+        }
+        if let Err(e) = self.bookmarks.save() {
+            tracing::error!(%e, "Failed to save script bookmarks");
+            self.notify_background_error("Failed to save bookmarks tagged by scripts");
         }
     }
 
-    fn export_theme_to_file(
-        &self,
-        name: &str,
-        theme_override: Option<&crate::utils::theme_loader::TuiTheme>,
-    ) -> anyhow::Result<std::path::PathBuf> {
-        use std::collections::HashMap;
-        use std::fs;
-        use std::path::PathBuf;
+    fn sort_stories(&mut self) {
+        // Delegate actual sorting implementation to the `sort` module so that
+        // sorting logic can be maintained and tested separately.
+        //
+        // The concrete implementation is expected to be provided in
+        // `crate::internal::ui::sort::sort_stories`.
+        crate::internal::ui::sort::sort_stories(&mut self.stories, self.sort_by, self.sort_order);
+    }
 
-        let theme = theme_override.unwrap_or(&self.theme_editor.temp_theme);
+    /// Number of rows in the list the selection moves through.
+    fn displayed_len(&self) -> usize {
+        match self.view_mode {
+            ViewMode::Bookmarks => self.bookmark_indices().len(),
+            ViewMode::History => self.history_indices().len(),
+            ViewMode::ReadingLists => self
+                .reading_lists
+                .get(self.current_reading_list)
+                .map_or(0, |l| l.items.len()),
+            _ => self.filtered_story_indices().len(),
+        }
+    }
 
-        // Helper to convert Color to hex string
-        let color_to_hex = |color: ratatui::style::Color| -> String {
-            match color {
-                ratatui::style::Color::Rgb(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
-                _ => "#000000".to_string(),
-            }
+    /// Story on the focused row (or the open story), used to add it to a reading list.
+    /// The focused story as Markdown, with its article and comments when loaded.
+    fn story_text(&self) -> Option<(u32, String)> {
+        let story = self.focused_story()?;
+        let article = match self.article_for_story_id == Some(story.id) {
+            true => self.article_content.as_ref(),
+            false => None,
         };
+        let comments: &[CommentRow] = match &self.selected_story {
+            Some(selected) if selected.id == story.id => &self.comments,
+            _ => &[],
+        };
+        let export = crate::internal::export::StoryExport {
+            story: &story,
+            article,
+            comments,
+        };
+        Some((story.id, crate::internal::export::render_markdown(&export)))
+    }
 
-        // Create theme JSON structure
-        let mut colors = HashMap::new();
-        colors.insert("background".to_string(), color_to_hex(theme.background));
-        colors.insert("foreground".to_string(), color_to_hex(theme.foreground));
-        colors.insert(
-            "selection.background".to_string(),
-            color_to_hex(theme.selection_bg),
-        );
-        colors.insert(
-            "accent.foreground".to_string(),
-            color_to_hex(theme.selection_fg),
-        );
-        colors.insert("border".to_string(), color_to_hex(theme.border));
-        colors.insert("base.blue".to_string(), color_to_hex(theme.link));
-        colors.insert("base.yellow".to_string(), color_to_hex(theme.score));
-        colors.insert(
-            "muted.foreground".to_string(),
-            color_to_hex(theme.comment_time),
-        );
+    fn save_read_later(&self) {
+        if let Err(e) = self.read_later.save() {
+            tracing::error!("Failed to save read-later queue: {}", e);
+        }
+    }
 
-        let theme_data = serde_json::json!({
-            "name": name,
-            "themes": [{
-                "name": format!("{} Custom", name),
-                "mode": "dark",
-                "colors": colors
-            }]
+    /// Start downloading the next queued story, unless one is downloading already or
+    /// there is no network.
+    fn download_next_read_later(&mut self) {
+        if self.api_service.is_offline() {
+            return;
+        }
+        let Some(id) = self.read_later.next_to_download() else {
+            return;
+        };
+        self.read_later.start(id);
+        let api = self.api_service.clone();
+        let tx = self.action_tx.clone();
+        let depth = self.config.read_later.comment_depth;
+        tokio::spawn(async move {
+            let result = crate::internal::read_later::download(&api, id, depth)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(Action::ReadLaterDone(id, result));
         });
+    }
 
-        // Ensure themes directory exists
-        let themes_dir = PathBuf::from("./themes");
-        fs::create_dir_all(&themes_dir)?;
-
-        let filename = format!("{}_custom.json", name.to_lowercase().replace(' ', "_"));
-        let path = themes_dir.join(&filename);
-
-        let json = serde_json::to_string_pretty(&theme_data)?;
-        fs::write(&path, json)?;
+    /// Open a story of the read-later queue; downloaded ones come from the cache.
+    fn open_read_later(&mut self, id: u32) {
+        let Some(item) = self.read_later.items.iter().find(|item| item.id == id) else {
+            return;
+        };
+        let fallback = Story {
+            id,
+            title: Some(item.title.clone()),
+            url: item.url.clone(),
+            ..Default::default()
+        };
+        self.history.add(&fallback);
+        let _ = self.history.save();
+        let api = self.api_service.clone();
+        let tx = self.action_tx.clone();
+        let list_type = self.current_list_type;
+        tokio::spawn(async move {
+            let story = api.fetch_story_content(id).await.unwrap_or(fallback);
+            let _ = tx.send(Action::SelectStory(story, list_type));
+        });
+    }
 
-        Ok(path)
+    /// The open story, unless the list pane has the focus; then the selected row.
+    fn focused_story(&self) -> Option<Story> {
+        match (&self.selected_story, self.view_mode) {
+            (Some(story), _) if !self.list_has_focus() => Some(story.clone()),
+            (_, ViewMode::ReadingLists) => None,
+            _ => self.selected_row(),
+        }
     }
 
-    fn sort_stories(&mut self) {
-        // Delegate actual sorting implementation to the `sort` module so that
-        // sorting logic can be maintained and tested separately.
-        //
-        // The concrete implementation is expected to be provided in
-        // `crate::internal::ui::sort::sort_stories`.
-        crate::internal::ui::sort::sort_stories(&mut self.stories, self.sort_by, self.sort_order);
+    /// The story on the selected row of the list on screen, which is the story list
+    /// beside an open story in the split layout.
+    fn selected_row(&self) -> Option<Story> {
+        let idx = self.story_list_state.selected()?;
+        match self.view_mode {
+            ViewMode::Bookmarks => self.bookmark_at(idx).map(|b| Story {
+                id: b.id,
+                title: Some(b.title.clone()),
+                url: b.url.clone(),
+                ..Default::default()
+            }),
+            ViewMode::History => self.history_at(idx).map(|v| Story {
+                id: v.id,
+                title: Some(v.title.clone()),
+                url: v.url.clone(),
+                by: v.by.clone(),
+                score: v.score,
+                descendants: v.descendants,
+                ..Default::default()
+            }),
+            ViewMode::ReadingLists => self
+                .reading_lists
+                .get(self.current_reading_list)
+                .and_then(|l| l.items.get(idx))
+                .map(|item| Story {
+                    id: item.id,
+                    title: Some(item.title.clone()),
+                    url: item.url.clone(),
+                    ..Default::default()
+                }),
+            _ => self
+                .filtered_story_indices()
+                .get(idx)
+                .map(|(_, s)| (*s).clone()),
+        }
     }
 
     fn select_next(&mut self) {
-        let displayed = self.filtered_story_indices();
-        if displayed.is_empty() {
+        let len = self.displayed_len();
+        if len == 0 {
             return;
         }
 
         let i = match self.story_list_state.selected() {
             Some(i) => match i {
-                n if n >= displayed.len() - 1 => 0,
+                n if n >= len - 1 => 0,
                 _ => i + 1,
             },
             None => 0,
@@ -2102,14 +5732,14 @@ impl App {
     }
 
     fn select_prev(&mut self) {
-        let displayed = self.filtered_story_indices();
-        if displayed.is_empty() {
+        let len = self.displayed_len();
+        if len == 0 {
             return;
         }
 
         let i = match self.story_list_state.selected() {
             Some(i) => match i {
-                0 => displayed.len() - 1,
+                0 => len - 1,
                 n => n - 1,
             },
             None => 0,
@@ -2118,6 +5748,9 @@ impl App {
     }
 
     pub fn get_spinner_char(&self) -> &'static str {
+        if self.low_bandwidth {
+            return crate::internal::ui::low_bandwidth::STATIC_SPINNER;
+        }
         const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
         SPINNER_FRAMES[self.spinner_state % SPINNER_FRAMES.len()]
     }
@@ -2140,7 +5773,10 @@ impl App {
     pub fn loading_description(&self) -> Option<String> {
         match (self.article_loading, self.comments_loading, self.loading) {
             (true, _, _) => Some("Loading article...".to_string()),
-            (_, true, _) => Some("Loading comments...".to_string()),
+            (_, true, _) => match (self.comments.len(), self.comment_roots_remaining) {
+                (0, _) | (_, 0) => Some("Loading comments...".to_string()),
+                (_, remaining) => Some(format!("Loading comments... ({} threads left)", remaining)),
+            },
             (_, _, true) => Some("Loading stories...".to_string()),
             _ => None,
         }
@@ -2151,9 +5787,28 @@ impl App {
 
     pub fn ui(&mut self, f: &mut Frame) {
         super::view::draw(self, f);
+        if self.no_color {
+            super::no_color::strip(f.buffer_mut(), self.theme.background);
+        }
+        if self.low_bandwidth {
+            super::low_bandwidth::asciify(f.buffer_mut());
+        }
     }
 }
 
+/// Range of list indices on screen (`offset..offset + rows`) widened by `margin`
+/// on both sides and clamped to `len`.
+pub fn visible_window(
+    offset: usize,
+    rows: usize,
+    len: usize,
+    margin: usize,
+) -> std::ops::Range<usize> {
+    let start = offset.saturating_sub(margin).min(len);
+    let end = offset.saturating_add(rows).saturating_add(margin).min(len);
+    start..end
+}
+
 impl Default for App {
     fn default() -> Self {
         Self::new()
@@ -2296,6 +5951,9 @@ mod tests {
             time: Some(1234567890),
             descendants: Some(50),
             kids: Some(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]),
+            parts: None,
+            item_type: None,
+            text: None,
         };
 
         // Before selection
@@ -2334,6 +5992,7 @@ mod tests {
                     time: Some(1234567890),
                     kids: None,
                     deleted: false,
+                    dead: false,
                 },
                 depth: 0,
                 expanded: true,
@@ -2348,6 +6007,7 @@ mod tests {
                     time: Some(1234567891),
                     kids: None,
                     deleted: false,
+                    dead: false,
                 },
                 depth: 0,
                 expanded: true,
@@ -2356,7 +6016,7 @@ mod tests {
             },
         ];
 
-        // Simulate what CommentsLoaded handler does (it now replaces, not extends)
+        // Simulate what the first CommentsChunkLoaded batch does
         app.loaded_comments_count = first_batch.len();
         app.comments = first_batch;
 
@@ -2474,6 +6134,760 @@ mod tests {
         assert_eq!(app.stories[2].id, 3); // 50
     }
 
+    #[test]
+    fn test_sort_header_click_dispatches_sort() {
+        use ratatui::layout::Rect;
+
+        let mut app = App::new();
+        app.sort_header_hits = vec![
+            (Rect::new(50, 0, 8, 1), SortBy::Score),
+            (Rect::new(59, 0, 6, 1), SortBy::Time),
+        ];
+        let click = |column| MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row: 0,
+            modifiers: crossterm::event::KeyModifiers::NONE,
+        };
+
+        app.handle_mouse_event(click(52));
+        assert!(matches!(app.action_rx.try_recv(), Ok(Action::SortByScore)));
+        // The active column toggles the order instead
+        app.sort_by = SortBy::Time;
+        app.handle_mouse_event(click(60));
+        assert!(matches!(
+            app.action_rx.try_recv(),
+            Ok(Action::ToggleSortOrder)
+        ));
+        app.handle_mouse_event(click(58));
+        assert!(app.action_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_whats_new_overlay_swallows_keys_until_closed() {
+        let mut app = App::new();
+        app.whats_new = crate::internal::whats_new::RELEASES.iter().collect();
+        let press = |c| KeyEvent::new(KeyCode::Char(c), crossterm::event::KeyModifiers::NONE);
+
+        app.handle_key_event(press('?'));
+        assert!(!app.show_help);
+        app.handle_key_event(press('j'));
+        assert_eq!(app.whats_new_scroll.offset(), 1);
+        app.handle_key_event(press('q'));
+        assert!(app.whats_new.is_empty());
+        assert!(app.running);
+    }
+
+    #[tokio::test]
+    async fn test_notification_center_keeps_dismissed_notifications() {
+        let mut app = App::new();
+        let press = |c| KeyEvent::new(KeyCode::Char(c), crossterm::event::KeyModifiers::NONE);
+        app.notify_info("Saved");
+        app.notify_error("Offline");
+
+        app.handle_key_event(press('N'));
+        let action = app.action_rx.try_recv().unwrap();
+        assert!(matches!(action, Action::ToggleNotificationCenter));
+        app.dispatch(action).await;
+        assert_eq!(app.notification_center, Some(0));
+
+        app.handle_key_event(press('d'));
+        assert!(app.notifications.toasts.is_empty());
+        assert_eq!(app.notifications.history.len(), 2);
+        app.handle_key_event(press('j'));
+        assert_eq!(app.notification_center, Some(1));
+        app.handle_key_event(press('c'));
+        assert!(app.notifications.history.is_empty());
+        app.handle_key_event(press('N'));
+        assert_eq!(app.notification_center, None);
+    }
+
+    #[test]
+    fn test_chords_complete_or_fall_back_after_timeout() {
+        let mut app = App::new();
+        let press = |c| KeyEvent::new(KeyCode::Char(c), crossterm::event::KeyModifiers::NONE);
+
+        app.handle_key_event(press('y'));
+        assert!(app.pending_chord.is_some());
+        assert!(app.action_rx.try_recv().is_err());
+        app.handle_key_event(press('c'));
+        assert!(matches!(
+            app.action_rx.try_recv(),
+            Ok(Action::CopyCommentsLink)
+        ));
+
+        // A lone prefix runs its own binding once the timeout passes
+        app.config.ui.chord_timeout_ms = 0;
+        app.handle_key_event(press('o'));
+        app.expire_chord();
+        assert!(matches!(app.action_rx.try_recv(), Ok(Action::OpenBrowser)));
+        assert!(app.pending_chord.is_none());
+    }
+
+    #[test]
+    fn test_visible_window_clamps_to_list() {
+        assert_eq!(visible_window(0, 10, 100, 3), 0..13);
+        assert_eq!(visible_window(20, 10, 100, 3), 17..33);
+        assert_eq!(visible_window(95, 10, 100, 3), 92..100);
+        assert_eq!(visible_window(5, 10, 0, 3), 0..0);
+    }
+
+    #[test]
+    fn test_visible_story_ids_limited_to_viewport() {
+        let mut app = App::new();
+        app.stories = (1..=50)
+            .map(|id| Story {
+                id,
+                ..Default::default()
+            })
+            .collect();
+        app.list_viewport_rows = 5;
+        *app.story_list_state.offset_mut() = 10;
+
+        let ids = app.visible_story_ids(2);
+        assert_eq!(ids, (9..=17).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn test_hide_read_filters_opened_stories() {
+        let mut app = App::new();
+        app.history = crate::internal::history::History::new(10, 100);
+        app.stories = (1..=4)
+            .map(|id| Story {
+                id,
+                ..Default::default()
+            })
+            .collect();
+        app.history.mark_all_read([2, 4]);
+
+        assert_eq!(app.filtered_story_indices().len(), 4);
+
+        app.hide_read = true;
+        let ids: Vec<u32> = app
+            .filtered_story_indices()
+            .iter()
+            .map(|(_, s)| s.id)
+            .collect();
+        assert_eq!(ids, vec![1, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_focus_filters_hide_jobs_and_reposts_per_list() {
+        let mut app = App::new();
+        app.history = crate::internal::history::History::new(10, 100);
+        app.focus_filters = crate::internal::focus::FocusFilters::new();
+        app.current_list_type = StoryListType::Top;
+        let story = |id, url: &str, item_type: &str| Story {
+            id,
+            url: Some(url.to_string()),
+            item_type: Some(item_type.to_string()),
+            ..Default::default()
+        };
+        app.history
+            .add(&story(9, "https://example.com/post", "story"));
+        app.stories = vec![
+            story(1, "https://example.com/new", "story"),
+            story(2, "https://jobs.example.com", "job"),
+            story(3, "http://www.example.com/post/", "story"),
+        ];
+        let shown = |app: &App| -> Vec<u32> {
+            app.filtered_story_indices()
+                .iter()
+                .map(|(_, s)| s.id)
+                .collect()
+        };
+
+        let press = |c| KeyEvent::new(KeyCode::Char(c), crossterm::event::KeyModifiers::NONE);
+        app.handle_key_event(press('z'));
+        app.handle_key_event(press('j'));
+        let action = app.action_rx.try_recv().unwrap();
+        assert_eq!(action, Action::ToggleHideJobs);
+        app.dispatch(action).await;
+        assert_eq!(shown(&app), vec![1, 3]);
+
+        app.dispatch(Action::ToggleHideSeen).await;
+        assert_eq!(shown(&app), vec![1]);
+
+        // Other lists keep their own filters
+        app.current_list_type = StoryListType::New;
+        assert_eq!(shown(&app), vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_reading_index_records_opened_articles() {
+        use crate::internal::models::{Article, ArticleElement};
+        use crate::internal::reading_index::{ItemKind, ReadingIndex};
+
+        let mut app = App::new();
+        app.dispatch(Action::SearchReading).await;
+        assert!(app.reading_search.is_none());
+
+        let index = Arc::new(ReadingIndex::in_memory().unwrap());
+        app.reading_index = Some(index.clone());
+        app.selected_story = Some(Story {
+            id: 9,
+            title: Some("Zig build system".to_string()),
+            ..Default::default()
+        });
+        let article = Article {
+            elements: vec![ArticleElement::Paragraph(
+                "Comptime replaces macros".to_string(),
+            )],
+            ..Default::default()
+        };
+        app.dispatch(Action::ArticleLoaded(app.current_list_type, 9, article))
+            .await;
+        // Indexing runs on the blocking pool
+        for _ in 0..100 {
+            if !index.search("comptime", 5).unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+
+        app.dispatch(Action::SearchReading).await;
+        for c in "comptime".chars() {
+            app.handle_key_event(KeyEvent::new(
+                KeyCode::Char(c),
+                crossterm::event::KeyModifiers::NONE,
+            ));
+        }
+        let search = app.reading_search.as_ref().unwrap();
+        assert_eq!(search.hits.len(), 1);
+        assert_eq!(search.hits[0].story_id, 9);
+        assert_eq!(search.hits[0].kind, ItemKind::Article);
+    }
+
+    fn comment_row(id: u32, text: &str) -> crate::internal::models::CommentRow {
+        crate::internal::models::CommentRow {
+            comment: crate::internal::models::Comment {
+                id,
+                by: None,
+                text: Some(text.to_string()),
+                time: None,
+                kids: None,
+                deleted: false,
+                dead: false,
+            },
+            depth: 0,
+            expanded: true,
+            parent_id: None,
+            loaded_kids: true,
+        }
+    }
+
+    /// Lay out `app.comments` 80 columns wide with the default thread options.
+    fn lay_out_comments(app: &mut App) {
+        let conversations = std::collections::BTreeSet::new();
+        let options = crate::internal::ui::comment_view::ThreadOptions {
+            conversations: &conversations,
+            diff: None,
+            muted_users: None,
+            hints: Default::default(),
+            time_format: Default::default(),
+            op: None,
+            me: None,
+            palette: Default::default(),
+        };
+        app.comment_layout
+            .update(&app.comments, options, &app.theme, 80);
+    }
+
+    #[tokio::test]
+    async fn test_comment_search_centers_a_match_off_screen() {
+        let mut app = App::new();
+        app.search_history = crate::internal::search::SearchHistory::new(20);
+        app.view_mode = ViewMode::StoryDetail;
+        app.comments = (1..40).map(|id| comment_row(id, "nothing")).collect();
+        app.comments.push(comment_row(40, "rust"));
+        app.comments.push(comment_row(41, "more rust"));
+        app.comments
+            .extend((42..60).map(|id| comment_row(id, "nothing")));
+        lay_out_comments(&mut app);
+        app.comments_scroll
+            .update(app.comment_layout.lines.len(), 10);
+        let press = |c| KeyEvent::new(c, crossterm::event::KeyModifiers::NONE);
+
+        app.handle_key_event(press(KeyCode::Char('/')));
+        for c in "rust".chars() {
+            app.handle_key_event(press(KeyCode::Char(c)));
+        }
+        app.handle_key_event(press(KeyCode::Enter));
+        let first = app.comment_search.matches[0].0;
+        assert_eq!(app.comments_scroll.offset(), first - 5);
+
+        // The next match is already on screen: it only gets its three lines of
+        // context below instead of being centered
+        app.handle_key_event(press(KeyCode::Char('n')));
+        assert_eq!(app.comment_search.status(), "match 2/2");
+        let second = app.comment_search.matches[1].0;
+        assert_eq!(app.comments_scroll.visible_range().end, second + 4);
+    }
+
+    #[tokio::test]
+    async fn test_comment_search_jumps_between_matches() {
+        let mut app = App::new();
+        app.search_history = crate::internal::search::SearchHistory::new(20);
+        app.view_mode = ViewMode::StoryDetail;
+        app.comments = vec![
+            comment_row(1, "Rust is nice"),
+            comment_row(2, "nothing"),
+            comment_row(3, "more rust"),
+        ];
+        lay_out_comments(&mut app);
+        let press = |c| KeyEvent::new(c, crossterm::event::KeyModifiers::NONE);
+
+        app.handle_key_event(press(KeyCode::Char('/')));
+        assert_eq!(app.input_mode, InputMode::Search);
+        for c in "rust".chars() {
+            app.handle_key_event(press(KeyCode::Char(c)));
+        }
+        app.handle_key_event(press(KeyCode::Enter));
+        // The story list filter is left alone
+        assert!(app.search_query.is_empty());
+        assert_eq!(app.comment_search.matches.len(), 2);
+        assert_eq!(app.comment_search.status(), "match 1/2");
+
+        app.handle_key_event(press(KeyCode::Char('n')));
+        assert_eq!(app.comment_search.status(), "match 2/2");
+        app.handle_key_event(press(KeyCode::Char('N')));
+        assert_eq!(app.comment_search.status(), "match 1/2");
+        assert!(app.action_rx.try_recv().is_err());
+
+        // Once cleared, n loads more comments again
+        app.handle_key_event(press(KeyCode::Char('Q')));
+        assert!(!app.comment_search.is_active());
+        app.handle_key_event(press(KeyCode::Char('n')));
+        assert_eq!(app.action_rx.try_recv().unwrap(), Action::LoadMoreComments);
+    }
+
+    #[tokio::test]
+    async fn test_thread_diff_marks_changes_since_snapshot() {
+        use crate::internal::thread_snapshot::{Change, ThreadSnapshots};
+
+        let mut app = App::new();
+        app.thread_snapshots = ThreadSnapshots::new();
+        app.view_mode = ViewMode::StoryDetail;
+        app.selected_story = Some(Story {
+            id: 7,
+            ..Default::default()
+        });
+        app.comment_ids = vec![1, 2];
+        app.comments = vec![comment_row(1, "first"), comment_row(2, "second")];
+
+        app.dispatch(Action::ToggleThreadDiff).await;
+        assert!(app.thread_diff.is_none());
+
+        app.handle_key_event(KeyEvent::new(
+            KeyCode::Char('s'),
+            crossterm::event::KeyModifiers::NONE,
+        ));
+        let action = app.action_rx.try_recv().unwrap();
+        assert_eq!(action, Action::SnapshotThread);
+        app.dispatch(action).await;
+
+        app.comment_ids = vec![1, 3];
+        app.comments = vec![comment_row(1, "first, edited"), comment_row(3, "third")];
+        app.dispatch(Action::ToggleThreadDiff).await;
+        let diff = app.thread_diff.as_ref().unwrap();
+        assert_eq!(diff.changes.get(&1), Some(&Change::Edited));
+        assert_eq!(diff.changes.get(&3), Some(&Change::Added));
+        assert_eq!(diff.summary(), "+1 ~1 -1");
+
+        app.dispatch(Action::ToggleThreadDiff).await;
+        assert!(app.thread_diff.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_story_filters_combine_with_search() {
+        let mut app = App::new();
+        app.history = crate::internal::history::History::new(10, 100);
+        app.focus_filters = crate::internal::focus::FocusFilters::new();
+        let story = |id, title: &str, score, url: &str| Story {
+            id,
+            title: Some(title.to_string()),
+            score: Some(score),
+            url: Some(url.to_string()),
+            ..Default::default()
+        };
+        app.stories = vec![
+            story(1, "Rust release", 200, "https://blog.rust-lang.org/a"),
+            story(2, "Rust on Medium", 300, "https://medium.com/b"),
+            story(3, "Rust tips", 10, "https://example.com/c"),
+            story(4, "Go release", 500, "https://go.dev/d"),
+        ];
+        let shown = |app: &App| -> Vec<u32> {
+            app.filtered_story_indices()
+                .iter()
+                .map(|(_, s)| s.id)
+                .collect()
+        };
+
+        app.handle_key_event(KeyEvent::new(
+            KeyCode::Char('F'),
+            crossterm::event::KeyModifiers::NONE,
+        ));
+        let action = app.action_rx.try_recv().unwrap();
+        assert_eq!(action, Action::OpenFilterPanel);
+        app.dispatch(action).await;
+        assert!(app.filter_panel.is_some());
+
+        app.config.filters.min_score = 100;
+        app.config.filters.exclude_domains = vec!["medium.com".to_string()];
+        assert_eq!(shown(&app), vec![1, 4]);
+
+        app.search_query.query = "rust".to_string();
+        assert_eq!(shown(&app), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn test_muting_an_author_hides_their_stories_until_revealed() {
+        let mut app = App::new();
+        app.history = crate::internal::history::History::new(10, 100);
+        app.focus_filters = crate::internal::focus::FocusFilters::new();
+        app.mute_list = crate::internal::mute::MuteList::new();
+        app.config.filters = Default::default();
+        let story = |id, by: &str| Story {
+            id,
+            by: Some(by.to_string()),
+            url: Some(format!("https://site{}.com", id)),
+            ..Default::default()
+        };
+        app.stories = vec![story(1, "alice"), story(2, "bob"), story(3, "alice")];
+        app.story_list_state.select(Some(0));
+        let shown = |app: &App| -> Vec<u32> {
+            app.filtered_story_indices()
+                .iter()
+                .map(|(_, s)| s.id)
+                .collect()
+        };
+        let press = |c| KeyEvent::new(KeyCode::Char(c), crossterm::event::KeyModifiers::NONE);
+
+        app.handle_key_event(press('x'));
+        let action = app.action_rx.try_recv().unwrap();
+        assert_eq!(action, Action::MuteStory);
+        app.dispatch(action).await;
+        assert_eq!(app.mute_prompt.as_ref().map(|s| s.id), Some(1));
+        app.handle_key_event(press('a'));
+        assert!(app.mute_prompt.is_none());
+        assert_eq!(shown(&app), vec![2]);
+
+        app.dispatch(Action::ToggleShowMuted).await;
+        assert_eq!(shown(&app), vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_prefetched_stories_update_in_place() {
+        let mut app = App::new();
+        let story = |id, score| Story {
+            id,
+            score: Some(score),
+            ..Default::default()
+        };
+        app.stories = vec![story(1, 10)];
+        app.dispatch(Action::StoriesPrefetched(vec![story(1, 50), story(2, 7)]))
+            .await;
+        assert_eq!(app.stories[0].score, Some(50));
+        assert_eq!(app.prefetched.get(&2).and_then(|s| s.score), Some(7));
+        assert_eq!(app.stories.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn input_latency_waits_for_the_actions_of_the_key() {
+        use crate::internal::metrics::INPUT_LATENCY;
+        let mut app = App::new();
+        let samples = |app: &App| {
+            app.metrics
+                .histogram(INPUT_LATENCY)
+                .map_or(0, |h| h.count())
+        };
+
+        app.handle_key_event(KeyEvent::new(
+            KeyCode::Char('S'),
+            crossterm::event::KeyModifiers::NONE,
+        ));
+        app.input_received(std::time::Instant::now());
+        // A frame drawn before the sort is dispatched does not reflect the key
+        app.record_input_latency();
+        assert_eq!(samples(&app), 0);
+
+        let action = app.action_rx.try_recv().unwrap();
+        app.dispatch(action).await;
+        app.input_action_dispatched();
+        app.record_input_latency();
+        assert_eq!(samples(&app), 1);
+        assert!(app.pending_input.is_none());
+    }
+
+    #[tokio::test]
+    async fn cache_stats_key_is_not_tab() {
+        let mut app = App::new();
+        let press = |code, modifiers| KeyEvent::new(code, modifiers);
+        app.handle_key_event(press(
+            KeyCode::Char('k'),
+            crossterm::event::KeyModifiers::CONTROL,
+        ));
+        assert_eq!(app.action_rx.try_recv().unwrap(), Action::ToggleCacheStats);
+        // Legacy terminals report Ctrl+i as Tab
+        app.handle_key_event(press(KeyCode::Tab, crossterm::event::KeyModifiers::NONE));
+        assert_ne!(
+            app.action_rx.try_recv().ok(),
+            Some(Action::ToggleCacheStats)
+        );
+    }
+
+    #[test]
+    fn settings_panel_applies_the_status_bar_format() {
+        use crate::config::settings::Setting;
+        use crate::internal::ui::status_template::StatusTemplate;
+
+        let dir = std::env::temp_dir().join(format!("hn-settings-status-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut app = App::new();
+        app.config.source = Some(dir.join("config.ron"));
+        let format = "{mode}{|}{count}";
+        app.settings_panel = Some(crate::internal::ui::settings_panel::SettingsPanel {
+            selected: Setting::ALL
+                .iter()
+                .position(|s| *s == Setting::StatusBarFormat)
+                .unwrap(),
+            editing: Some(format.to_string()),
+            error: None,
+        });
+        app.handle_key_event(KeyEvent::new(
+            KeyCode::Enter,
+            crossterm::event::KeyModifiers::NONE,
+        ));
+        assert_eq!(app.status_template, StatusTemplate::parse(format).ok());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_item_prompt_opens_story_by_link() {
+        let mut server = mockito::Server::new_async().await;
+        let _m = server
+            .mock("GET", "/item/8863.json")
+            .with_status(200)
+            .with_body(r#"{"id": 8863, "type": "story", "title": "My YC app"}"#)
+            .create();
+        let _c = server
+            .mock("GET", "/item/8870.json")
+            .with_status(200)
+            .with_body(r#"{"id": 8870, "type": "comment", "text": "hi"}"#)
+            .create();
+        let mut app = App::new();
+        app.api_service = Arc::new(crate::api::ApiService::with_base_url(format!(
+            "{}/",
+            server.url()
+        )));
+        let press = |code| KeyEvent::new(code, crossterm::event::KeyModifiers::NONE);
+        let open = |app: &mut App, text: &str| {
+            app.item_prompt = Some(Default::default());
+            for c in text.chars() {
+                app.handle_key_event(press(KeyCode::Char(c)));
+            }
+            app.handle_key_event(press(KeyCode::Enter));
+        };
+
+        app.handle_key_event(KeyEvent::new(
+            KeyCode::Char('o'),
+            crossterm::event::KeyModifiers::CONTROL,
+        ));
+        assert_eq!(app.action_rx.try_recv().unwrap(), Action::OpenItemPrompt);
+
+        open(&mut app, "https://news.ycombinator.com/item?id=8863");
+        assert!(app.item_prompt.is_none());
+        match app.action_rx.recv().await {
+            Some(Action::SelectStory(story, _)) => assert_eq!(story.id, 8863),
+            other => panic!("unexpected action {:?}", other),
+        }
+
+        open(&mut app, "8870");
+        assert!(matches!(
+            app.action_rx.recv().await,
+            Some(Action::ItemActionDone(Err(message))) if message.contains("comment")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_bookmark_tags_prompt_and_filter() {
+        let mut app = App::new();
+        app.bookmarks = crate::internal::bookmarks::Bookmarks::new();
+        app.history = crate::internal::history::History::new(10, 100);
+        let story = |id| Story {
+            id,
+            title: Some(format!("Story {}", id)),
+            ..Default::default()
+        };
+        app.stories = vec![story(1), story(2)];
+        let press = |code| KeyEvent::new(code, crossterm::event::KeyModifiers::NONE);
+        let type_text = |app: &mut App, text: &str| {
+            for c in text.chars() {
+                app.handle_key_event(press(KeyCode::Char(c)));
+            }
+        };
+
+        // Bookmarking asks for tags
+        app.selected_story = Some(story(1));
+        app.dispatch(Action::ToggleBookmark).await;
+        assert_eq!(app.tag_prompt.as_ref().map(|p| p.story_id), Some(1));
+        type_text(&mut app, "lang/Rust, to-read");
+        app.handle_key_event(press(KeyCode::Enter));
+        assert!(app.tag_prompt.is_none());
+        assert_eq!(app.bookmarks.tags_of(1), ["lang/rust", "to-read"]);
+
+        // Esc keeps the bookmark without tags
+        app.selected_story = Some(story(2));
+        app.dispatch(Action::ToggleBookmark).await;
+        app.handle_key_event(press(KeyCode::Esc));
+        assert!(app.tag_prompt.is_none() && app.bookmarks.contains(2));
+
+        // Filtering by a tag folder; rows map to the bookmarks shown
+        app.selected_story = None;
+        app.dispatch(Action::ViewBookmarks).await;
+        assert_eq!(app.bookmark_indices(), vec![0, 1]);
+        app.dispatch(Action::NextBookmarkTag).await;
+        assert_eq!(app.bookmark_tag_filter.as_deref(), Some("lang"));
+        assert_eq!(app.bookmark_indices(), vec![1]);
+        assert_eq!(app.focused_story().map(|s| s.id), Some(1));
+        app.dispatch(Action::PrevBookmarkTag).await;
+        assert_eq!(app.bookmark_tag_filter, None);
+
+        // Editing the tags from the Bookmarks view
+        app.story_list_state.select(Some(0));
+        app.dispatch(Action::EditBookmarkTags).await;
+        assert_eq!(app.tag_prompt.as_ref().map(|p| p.story_id), Some(2));
+        type_text(&mut app, "news");
+        app.handle_key_event(press(KeyCode::Enter));
+        assert_eq!(app.bookmarks.tags_of(2), ["news"]);
+
+        // Notes span lines and are saved with Ctrl+s
+        app.dispatch(Action::EditBookmarkNote).await;
+        type_text(&mut app, "one");
+        app.handle_key_event(press(KeyCode::Enter));
+        type_text(&mut app, "two");
+        app.handle_key_event(KeyEvent::new(
+            KeyCode::Char('s'),
+            crossterm::event::KeyModifiers::CONTROL,
+        ));
+        assert!(app.note_editor.is_none());
+        assert_eq!(app.bookmarks.note_of(2), Some("one\ntwo"));
+    }
+
+    #[tokio::test]
+    async fn test_network_timers_follow_config_changes() {
+        let mut app = App::new();
+        app.config.network.refresh_interval_secs = 0;
+        app.config.network.live_updates_interval_secs = 0;
+        app.spawn_network_timers();
+        assert!(app.network_timers.is_empty());
+
+        // Turning an interval on starts its timer
+        let mut config = app.config.clone();
+        config.network.live_updates_interval_secs = 60;
+        app.apply_config(config.clone());
+        assert_eq!(app.network_timers.len(), 1);
+        let old = app.network_timers[0].abort_handle();
+
+        // A changed interval replaces the timer; 0 turns it off
+        config.network.live_updates_interval_secs = 0;
+        config.network.refresh_interval_secs = 30;
+        app.apply_config(config);
+        tokio::task::yield_now().await;
+        assert!(old.is_finished());
+        assert_eq!(app.network_timers.len(), 1);
+    }
+
+    #[test]
+    fn test_capital_l_is_typed_into_prompts() {
+        let mut app = App::new();
+        let press = |code| KeyEvent::new(code, crossterm::event::KeyModifiers::NONE);
+
+        app.item_prompt = Some(Default::default());
+        app.handle_key_event(press(KeyCode::Char('L')));
+        assert!(!app.log_viewer.visible);
+        assert_eq!(app.item_prompt.as_ref().unwrap().input.text(), "L");
+        app.item_prompt = None;
+
+        app.input_mode = InputMode::Search;
+        app.handle_key_event(press(KeyCode::Char('L')));
+        assert!(!app.log_viewer.visible);
+        app.input_mode = InputMode::Normal;
+
+        app.handle_key_event(press(KeyCode::Char('L')));
+        assert!(app.log_viewer.visible);
+        app.handle_key_event(press(KeyCode::Char('L')));
+        assert!(!app.log_viewer.visible);
+    }
+
+    #[test]
+    fn test_focused_story_follows_the_list_pane_in_split_layout() {
+        let mut app = App::new();
+        let story = |id| Story {
+            id,
+            title: Some(format!("Story {}", id)),
+            ..Default::default()
+        };
+        app.stories = vec![story(1), story(2)];
+        app.config.ui.split_layout = true;
+        app.view_mode = ViewMode::StoryDetail;
+        app.selected_story = Some(story(1));
+        app.story_list_state.select(Some(1));
+        assert_eq!(app.focused_story().map(|s| s.id), Some(1));
+
+        app.focused_pane = crate::internal::ui::layout::Pane::List;
+        assert_eq!(app.focused_story().map(|s| s.id), Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_confirm_links_previews_before_opening() {
+        let mut app = App::new();
+        app.config.ui.confirm_links = true;
+        app.selected_story = Some(Story {
+            id: 1,
+            url: Some("https://bit.ly/abc".to_string()),
+            ..Default::default()
+        });
+        app.dispatch(Action::OpenBrowser).await;
+        assert_eq!(
+            app.link_preview.as_ref().map(|p| p.url.as_str()),
+            Some("https://bit.ly/abc")
+        );
+
+        // A result for another link is ignored
+        app.dispatch(Action::LinkResolved(
+            "https://example.com".to_string(),
+            Ok("https://example.org".to_string()),
+        ))
+        .await;
+        assert!(app.link_preview.as_ref().unwrap().resolved.is_none());
+
+        app.dispatch(Action::LinkResolved(
+            "https://bit.ly/abc".to_string(),
+            Ok("https://example.org/post".to_string()),
+        ))
+        .await;
+        let preview = app.link_preview.as_ref().unwrap();
+        assert_eq!(preview.redirected_to(), Some("https://example.org/post"));
+        assert_eq!(preview.warnings().len(), 2);
+
+        app.handle_key_event(KeyEvent::new(
+            KeyCode::Esc,
+            crossterm::event::KeyModifiers::NONE,
+        ));
+        assert!(app.link_preview.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_job_list_ignores_sort_by_comments() {
+        let mut app = App::new();
+        app.current_list_type = StoryListType::Job;
+        app.sort_by = SortBy::Time;
+        app.dispatch(Action::SortByComments).await;
+        assert_eq!(app.sort_by, SortBy::Time);
+        assert!(app.notifications.latest().is_some());
+    }
+
     #[test]
     fn test_serialize_action() {
         let _action = Action::Quit;
@@ -2481,4 +6895,184 @@ mod tests {
         fn check_serialize<T: serde::Serialize>() {}
         check_serialize::<Action>();
     }
+
+    #[tokio::test]
+    async fn test_history_search_and_delete() {
+        let mut app = App::new();
+        app.history = crate::internal::history::History::new(10, 100);
+        for (id, title) in [(1, "Rust compiler internals"), (2, "Go generics")] {
+            app.history.add(&Story {
+                id,
+                title: Some(title.to_string()),
+                ..Default::default()
+            });
+        }
+        app.dispatch(Action::ViewHistory).await;
+        let press = |code| KeyEvent::new(code, crossterm::event::KeyModifiers::NONE);
+
+        app.handle_key_event(press(KeyCode::Char('/')));
+        for c in "rst".chars() {
+            app.handle_key_event(press(KeyCode::Char(c)));
+        }
+        app.handle_key_event(press(KeyCode::Enter));
+        assert!(app.history_search.is_none());
+        assert_eq!(app.history_query, "rst");
+        assert_eq!(app.history_at(0).map(|v| v.id), Some(1));
+
+        // The selected row is the one removed, even while the view is narrowed
+        app.dispatch(Action::DeleteHistoryEntry).await;
+        assert_eq!(app.history.stories.len(), 1);
+        assert_eq!(app.history.stories[0].id, 2);
+        assert_eq!(app.displayed_len(), 0);
+
+        app.handle_key_event(press(KeyCode::Char('/')));
+        app.handle_key_event(press(KeyCode::Esc));
+        assert!(app.history_query.is_empty());
+        assert_eq!(app.displayed_len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_switching_category_applies_its_default_sort() {
+        let mut app = App::new();
+        app.config.ui.category_sorts.insert(
+            StoryListType::Ask,
+            crate::config::CategorySort {
+                by: SortBy::Comments,
+                order: SortOrder::Ascending,
+            },
+        );
+        app.current_list_type = StoryListType::Top;
+        app.stories = vec![Story::default()];
+        app.sort_by = SortBy::Score;
+
+        app.dispatch(Action::LoadStories(StoryListType::Ask)).await;
+        assert_eq!(
+            (app.sort_by, app.sort_order),
+            (SortBy::Comments, SortOrder::Ascending)
+        );
+
+        // Categories without a default keep the current sort
+        app.dispatch(Action::SortByTime).await;
+        app.dispatch(Action::LoadStories(StoryListType::New)).await;
+        assert_eq!(app.sort_by, SortBy::Time);
+    }
+
+    #[tokio::test]
+    async fn test_next_story_keeps_the_article_view() {
+        let mut app = App::new();
+        let story = |id: u32, url: Option<&str>| Story {
+            id,
+            url: url.map(str::to_string),
+            ..Default::default()
+        };
+        app.stories = vec![
+            story(1, Some("https://a.example")),
+            story(2, Some("https://b.example")),
+            story(3, None),
+        ];
+        app.view_mode = ViewMode::Article;
+        app.selected_story = Some(app.stories[0].clone());
+
+        app.dispatch(Action::NextStory).await;
+        assert_eq!(app.story_list_state.selected(), Some(1));
+        match app.action_rx.try_recv() {
+            Ok(Action::SelectStory(next, _)) => assert_eq!(next.id, 2),
+            other => panic!("unexpected action {:?}", other),
+        }
+        assert!(matches!(
+            app.action_rx.try_recv(),
+            Ok(Action::ToggleArticleView)
+        ));
+
+        // A story without an article opens in the Comments view
+        app.selected_story = Some(app.stories[1].clone());
+        app.dispatch(Action::NextStory).await;
+        assert!(matches!(
+            app.action_rx.try_recv(),
+            Ok(Action::SelectStory(next, _)) if next.id == 3
+        ));
+        assert!(app.action_rx.try_recv().is_err());
+
+        // Nothing before the first story
+        app.selected_story = Some(app.stories[0].clone());
+        app.dispatch(Action::PrevStory).await;
+        assert!(app.action_rx.try_recv().is_err());
+        assert!(app.notifications.latest().is_some());
+    }
+
+    #[test]
+    fn idle_loop_waits_for_the_next_timer() {
+        let mut app = App::new();
+        app.low_bandwidth = false;
+        let frame = std::time::Duration::from_millis(16);
+        let now = tokio::time::Instant::now();
+        app.notifications.dismiss_all();
+        app.time_format = crate::utils::datetime::TimeFormat::Absolute;
+        assert_eq!(app.next_wake(now, frame), now + IDLE_REDRAW);
+        // Relative times are redrawn when the minute turns
+        app.time_format = crate::utils::datetime::TimeFormat::Relative;
+        assert!(app.next_wake(now, frame) <= now + IDLE_REDRAW);
+
+        // Absolute times again, so a minute turning does not wake the loop first
+        app.time_format = crate::utils::datetime::TimeFormat::Absolute;
+        app.notify_info("Saved");
+        let dismiss_at = app.notifications.latest().unwrap().dismiss_at();
+        assert_eq!(
+            app.next_wake(now, frame),
+            tokio::time::Instant::from_std(dismiss_at)
+        );
+
+        // Animations and the spinner need frames of their own
+        app.comments_scroll.update(100, 10);
+        app.comments_scroll.page_down();
+        assert_eq!(app.next_wake(now, frame), now + frame);
+        app.comments_scroll.reset();
+        app.loading = true;
+        app.last_spinner_update = Some(now);
+        assert_eq!(app.next_wake(now, frame), now + SPINNER_STEP);
+    }
+
+    #[tokio::test]
+    async fn restores_the_last_session() {
+        use crate::internal::ui::sort::{SortBy, SortOrder};
+
+        let mut app = App::new();
+        let story = Story {
+            id: 7,
+            title: Some("Story".to_string()),
+            ..Default::default()
+        };
+        app.current_list_type = StoryListType::Ask;
+        app.view_mode = ViewMode::Article;
+        app.selected_story = Some(story.clone());
+        app.sort_by = SortBy::Comments;
+        app.search_query.query = "rust".to_string();
+        let session = app.session();
+        assert_eq!(session.story.as_ref().map(|s| s.id), Some(7));
+
+        let mut app = App::new();
+        app.session_prompt = Some(session);
+        app.handle_key_event(KeyEvent::new(
+            KeyCode::Enter,
+            crossterm::event::KeyModifiers::NONE,
+        ));
+        assert!(app.session_prompt.is_none());
+        let mut queued = Vec::new();
+        while let Ok(action) = app.action_rx.try_recv() {
+            queued.push(action);
+        }
+        assert_eq!(queued[0], Action::LoadStories(StoryListType::Ask));
+        assert_eq!(queued[1], Action::SelectStory(story, StoryListType::Ask));
+        assert_eq!(queued[2], Action::ToggleArticleView);
+
+        let Some(Action::SessionRestored(session)) = queued.pop() else {
+            panic!("expected the restore to finish last");
+        };
+        app.dispatch(Action::SessionRestored(session)).await;
+        assert_eq!(
+            (app.sort_by, app.sort_order),
+            (SortBy::Comments, SortOrder::Descending)
+        );
+        assert_eq!(app.search_query.query, "rust");
+    }
 }
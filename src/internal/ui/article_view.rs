@@ -0,0 +1,274 @@
+//! Article lines wrapped to the pane width, rebuilt only when the article, width or theme change.
+
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+use std::hash::{Hash, Hasher};
+
+use crate::internal::models::{Article, ArticleElement};
+use crate::utils::theme_loader::TuiTheme;
+
+/// Cached, pre-wrapped article content for the open story.
+#[derive(Default)]
+pub struct ArticleLayout {
+    key: Option<u64>,
+    /// Hash of the article's content, taken once by `set_article`
+    content: u64,
+    pub lines: Vec<Line<'static>>,
+}
+
+impl ArticleLayout {
+    /// Note a newly stored article, so a re-fetched one for the same story is not
+    /// drawn from the layout of the old one.
+    pub fn set_article(&mut self, article: &Article) {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        article.hash(&mut hasher);
+        self.content = hasher.finish();
+    }
+
+    /// Rebuild the lines if anything they depend on changed. Returns true when rebuilt.
+    pub fn update(
+        &mut self,
+        story_id: u32,
+        article: &Article,
+        theme: &TuiTheme,
+        width: usize,
+    ) -> bool {
+        let key = layout_key(story_id, self.content, theme, width);
+        if self.key == Some(key) {
+            return false;
+        }
+        self.lines = build_lines(article, theme, width);
+        self.key = Some(key);
+        true
+    }
+}
+
+/// Hash of (story id, article content, width, theme).
+fn layout_key(story_id: u32, content: u64, theme: &TuiTheme, width: usize) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    (story_id, content, width).hash(&mut hasher);
+    theme.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Wrap `text` to `width` columns; the first line starts with `first`, the others with `rest`.
+fn wrapped(
+    lines: &mut Vec<Line<'static>>,
+    text: &str,
+    style: Style,
+    width: usize,
+    (first, rest): (Span<'static>, Span<'static>),
+) {
+    let available = width.saturating_sub(first.width()).max(10);
+    for (i, part) in textwrap::wrap(text, available).into_iter().enumerate() {
+        let prefix = match i {
+            0 => first.clone(),
+            _ => rest.clone(),
+        };
+        lines.push(Line::from(vec![
+            prefix,
+            Span::styled(part.into_owned(), style),
+        ]));
+    }
+}
+
+#[tracing::instrument(name = "render.article_layout", level = "debug", skip_all)]
+fn build_lines(article: &Article, theme: &TuiTheme, width: usize) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let plain = || (Span::raw(""), Span::raw(""));
+
+    if !article.title.is_empty() {
+        wrapped(
+            &mut lines,
+            &article.title,
+            Style::default()
+                .fg(theme.foreground)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            width,
+            plain(),
+        );
+
+        // Byline, publish date and publisher extracted from the page
+        let meta: Vec<String> = [
+            article.byline.as_ref().map(|b| format!("By {}", b)),
+            article.published.clone(),
+            article.site_name.clone(),
+            match article.words() {
+                0 => None,
+                words => Some(format!(
+                    "{} words · {} min read",
+                    words,
+                    article.reading_minutes()
+                )),
+            },
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if !meta.is_empty() {
+            wrapped(
+                &mut lines,
+                &meta.join(" · "),
+                Style::default()
+                    .fg(theme.comment_time)
+                    .add_modifier(Modifier::ITALIC),
+                width,
+                plain(),
+            );
+        }
+        lines.push(Line::from(""));
+    }
+
+    for element in &article.elements {
+        match element {
+            ArticleElement::Paragraph(text) => {
+                wrapped(
+                    &mut lines,
+                    text,
+                    Style::default().fg(theme.foreground),
+                    width,
+                    plain(),
+                );
+            }
+            ArticleElement::Heading(level, text) => {
+                let style = match level {
+                    1 => Style::default()
+                        .fg(theme.foreground)
+                        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                    2 => Style::default()
+                        .fg(theme.foreground)
+                        .add_modifier(Modifier::BOLD),
+                    _ => Style::default()
+                        .fg(theme.foreground)
+                        .add_modifier(Modifier::ITALIC),
+                };
+                wrapped(&mut lines, text, style, width, plain());
+            }
+            ArticleElement::CodeBlock { lang, code } => {
+                let fence = Style::default().fg(theme.comment_time);
+                lines.push(Line::from(Span::styled(
+                    format!("```{}", lang.as_deref().unwrap_or("text")),
+                    fence,
+                )));
+                // Use a different color for code
+                let style = Style::default().fg(theme.comment_author);
+                for line in code.lines() {
+                    wrapped(&mut lines, line, style, width, plain());
+                }
+                lines.push(Line::from(Span::styled("```", fence)));
+            }
+            ArticleElement::List(items) => {
+                let bullet = Style::default().fg(theme.border);
+                for item in items {
+                    wrapped(
+                        &mut lines,
+                        item,
+                        Style::default().fg(theme.foreground),
+                        width,
+                        (Span::styled(" • ", bullet), Span::raw("   ")),
+                    );
+                }
+            }
+            ArticleElement::Table(rows) => {
+                lines.push(Line::from(Span::styled(
+                    "[Table]",
+                    Style::default()
+                        .fg(theme.comment_time)
+                        .add_modifier(Modifier::ITALIC),
+                )));
+                // Simple ASCII rendering for now
+                for row in rows {
+                    wrapped(
+                        &mut lines,
+                        &format!("| {} |", row.join(" | ")),
+                        Style::default().fg(theme.foreground),
+                        width,
+                        plain(),
+                    );
+                }
+            }
+            ArticleElement::Image(alt) => {
+                wrapped(
+                    &mut lines,
+                    &format!("[IMAGE: {}]", alt),
+                    Style::default()
+                        .fg(theme.comment_time)
+                        .add_modifier(Modifier::ITALIC),
+                    width,
+                    plain(),
+                );
+            }
+            ArticleElement::Quote(text) => {
+                let bar = Span::styled("│ ", Style::default().fg(theme.border));
+                wrapped(
+                    &mut lines,
+                    text,
+                    Style::default()
+                        .fg(theme.foreground)
+                        .add_modifier(Modifier::ITALIC),
+                    width,
+                    (bar.clone(), bar),
+                );
+            }
+        }
+        lines.push(Line::from(""));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article() -> Article {
+        Article {
+            title: "Title".to_string(),
+            elements: vec![
+                ArticleElement::Paragraph("word ".repeat(40)),
+                ArticleElement::List(vec!["item ".repeat(10)]),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn wraps_to_width_and_rebuilds_only_on_change() {
+        let theme = TuiTheme::default();
+        let article = article();
+        let mut layout = ArticleLayout::default();
+
+        assert!(layout.update(1, &article, &theme, 40));
+        assert!(!layout.update(1, &article, &theme, 40));
+        assert!(layout.lines.iter().all(|line| line.width() <= 40));
+        let narrow_rows = layout.lines.len();
+
+        assert!(layout.update(1, &article, &theme, 80));
+        assert!(layout.lines.len() < narrow_rows);
+        assert!(layout.update(2, &article, &theme, 80));
+
+        // A re-fetched article of the same shape is laid out again
+        let mut edited = article.clone();
+        edited.title = "Eltit".to_string();
+        layout.set_article(&edited);
+        assert!(layout.update(2, &edited, &theme, 80));
+        assert!(layout.lines[0].to_string().starts_with("Eltit"));
+
+        // List items keep a hanging indent under the bullet
+        let item_lines: Vec<String> = layout
+            .lines
+            .iter()
+            .map(|line| line.to_string())
+            .filter(|text| text.starts_with(" • ") || text.starts_with("   item"))
+            .collect();
+        assert_eq!(item_lines.len(), 1);
+        layout.update(2, &article, &theme, 20);
+        assert!(
+            layout
+                .lines
+                .iter()
+                .any(|line| line.to_string().starts_with("   item"))
+        );
+    }
+}
@@ -0,0 +1,319 @@
+//! Command palette opened with `:`: every action by name, narrowed with a fuzzy
+//! search as in VS Code, to run the selected one without knowing its key.
+
+use std::cmp::Reverse;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::api::StoryListType;
+use crate::internal::ui::app::Action;
+use crate::internal::ui::text_input::TextInput;
+
+/// Bonus for a matched character that starts a word of the name.
+const WORD_START: i32 = 8;
+/// Bonus for a matched character right after the previous one; more than a word
+/// start, so "sort" matches "Sort by time" in "Sort" rather than at "time".
+const CONSECUTIVE: i32 = 10;
+
+/// An action as the palette lists it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Command {
+    pub name: &'static str,
+    pub action: Action,
+}
+
+/// Every action a key can be bound to, by display name, in the order the palette
+/// lists them before anything is typed.
+pub fn commands() -> Vec<Command> {
+    use Action::*;
+
+    let named = [
+        ("Stories: Top", LoadStories(StoryListType::Top)),
+        ("Stories: New", LoadStories(StoryListType::New)),
+        ("Stories: Best", LoadStories(StoryListType::Best)),
+        ("Stories: Ask HN", LoadStories(StoryListType::Ask)),
+        ("Stories: Show HN", LoadStories(StoryListType::Show)),
+        ("Stories: Jobs", LoadStories(StoryListType::Job)),
+        ("Stories: Load more", LoadMoreStories),
+        ("Stories: Load all", LoadAllStories),
+        ("Stories: Refresh visible", RefreshVisibleStories),
+        ("Stories: Mark all read", MarkAllRead),
+        ("Stories: Export list", ExportStoryList),
+        ("Stories: Import id list", ImportIdList),
+        ("Sort by score", SortByScore),
+        ("Sort by comments", SortByComments),
+        ("Sort by time", SortByTime),
+        ("Sort by rank (front page formula)", SortByRank),
+        ("Toggle sort order", ToggleSortOrder),
+        ("Filter: Open filter panel", OpenFilterPanel),
+        ("Filter: Toggle hide read", ToggleHideRead),
+        ("Filter: Toggle hide jobs", ToggleHideJobs),
+        ("Filter: Toggle hide seen", ToggleHideSeen),
+        ("Filter: Toggle show muted", ToggleShowMuted),
+        ("Mute story domain or author", MuteStory),
+        ("Open selected", Enter),
+        ("Back", Back),
+        ("Quit", Quit),
+        ("Move down", NavigateDown),
+        ("Move up", NavigateUp),
+        ("Next story", NextStory),
+        ("Previous story", PrevStory),
+        ("Focus next pane", FocusNextPane),
+        ("Scroll half page down", ScrollHalfPageDown),
+        ("Scroll half page up", ScrollHalfPageUp),
+        ("Scroll page down", ScrollPageDown),
+        ("Scroll page up", ScrollPageUp),
+        ("Scroll to top", ScrollToTop),
+        ("Scroll to bottom", ScrollToBottom),
+        ("Article: Toggle article view", ToggleArticleView),
+        ("Article: Scroll up", ScrollArticleUp),
+        ("Article: Scroll down", ScrollArticleDown),
+        ("Article: Open in reader", OpenInReader),
+        ("Comments: Load more", LoadMoreComments),
+        ("Comments: Collapse all", CollapseAllComments),
+        ("Comments: Expand all", ExpandAllComments),
+        ("Comments: Toggle conversation view", ToggleConversationView),
+        ("Comments: Snapshot thread", SnapshotThread),
+        ("Comments: Toggle thread diff", ToggleThreadDiff),
+        ("Open in browser", OpenBrowser),
+        ("Open HN discussion page", OpenHnPage),
+        ("Open link…", OpenLinkPicker),
+        ("Open item by id or URL…", OpenItemPrompt),
+        ("Open in $PAGER", OpenInPager),
+        ("Open in $EDITOR", OpenInEditor),
+        ("Copy link", CopyLink),
+        ("Copy comments link", CopyCommentsLink),
+        ("Send to phone", SendToPhone),
+        ("Export story", ExportStory),
+        ("Upvote", Upvote),
+        ("Favorite", Favorite),
+        ("Bookmarks: Toggle bookmark", ToggleBookmark),
+        ("Bookmarks: View", ViewBookmarks),
+        ("Bookmarks: Export", ExportBookmarks),
+        ("Bookmarks: Import", ImportBookmarks),
+        ("Bookmarks: Edit tags", EditBookmarkTags),
+        ("Bookmarks: Edit note", EditBookmarkNote),
+        ("Bookmarks: Next tag", NextBookmarkTag),
+        ("Bookmarks: Previous tag", PrevBookmarkTag),
+        ("Bookmarks: Open a random one", OpenRandomBookmark),
+        ("History: View", ViewHistory),
+        ("History: Clear", ClearHistory),
+        ("History: Delete entry", DeleteHistoryEntry),
+        ("Reading lists: Add story", AddToReadingList),
+        ("Reading lists: View", ViewReadingLists),
+        ("Reading lists: Move item up", MoveItemUp),
+        ("Reading lists: Move item down", MoveItemDown),
+        ("Reading lists: Remove item", RemoveFromReadingList),
+        ("Reading lists: Toggle item done", ToggleItemDone),
+        ("Reading lists: Next list", NextReadingList),
+        ("Reading lists: Previous list", PrevReadingList),
+        ("Read later: Queue story", QueueReadLater),
+        ("Read later: View queue", ViewReadLater),
+        ("Search saved articles and comments", SearchReading),
+        ("Prepare offline pack", PrepareOfflinePack),
+        ("Switch theme", SwitchTheme),
+        ("Cycle time format", CycleTimeFormat),
+        ("Settings", OpenSettings),
+        ("Toggle help", ToggleHelp),
+        ("Notification center", ToggleNotificationCenter),
+        ("Toggle cache statistics", ToggleCacheStats),
+        ("Toggle performance HUD", TogglePerfHud),
+        ("Suspend to shell", Suspend),
+    ];
+    named
+        .into_iter()
+        .map(|(name, action)| Command { name, action })
+        .collect()
+}
+
+/// How well `query` matches `name`, or None if its characters do not all appear
+/// in order. Spaces in the query are ignored and case does not matter; matches at
+/// word starts and runs of adjacent characters score higher ("tt" prefers
+/// "Toggle theme" to "Toggle settings").
+pub fn fuzzy_score(query: &str, name: &str) -> Option<i32> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    let name: Vec<char> = name.chars().flat_map(char::to_lowercase).collect();
+    let Some((&first, rest)) = query.split_first() else {
+        return Some(0);
+    };
+    let bonus = |j: usize| match j == 0 || !name[j - 1].is_alphanumeric() {
+        true => 1 + WORD_START,
+        false => 1,
+    };
+
+    // Best score of the query so far with its last character matched at each position
+    let mut best: Vec<Option<i32>> = (0..name.len())
+        .map(|j| (name[j] == first).then(|| bonus(j)))
+        .collect();
+    for &wanted in rest {
+        best = (0..name.len())
+            .map(|j| {
+                if name[j] != wanted {
+                    return None;
+                }
+                (0..j)
+                    .filter_map(|k| {
+                        best[k].map(|score| score + if k + 1 == j { CONSECUTIVE } else { 0 })
+                    })
+                    .max()
+                    .map(|score| score + bonus(j))
+            })
+            .collect();
+    }
+    best.into_iter().flatten().max()
+}
+
+/// What the palette asks the app to do after a key press.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaletteOutcome {
+    Pending,
+    Cancel,
+    /// Run this action; boxed as actions are large
+    Run(Box<Action>),
+}
+
+#[derive(Debug, Clone)]
+pub struct CommandPalette {
+    pub input: TextInput,
+    commands: Vec<Command>,
+    /// Indices into `commands` of those matching the input, best first
+    matches: Vec<usize>,
+    /// Row of `matches` that Enter runs
+    pub selected: usize,
+}
+
+impl Default for CommandPalette {
+    fn default() -> Self {
+        Self::new(commands())
+    }
+}
+
+impl CommandPalette {
+    pub fn new(commands: Vec<Command>) -> Self {
+        let matches = (0..commands.len()).collect();
+        Self {
+            input: TextInput::default(),
+            commands,
+            matches,
+            selected: 0,
+        }
+    }
+
+    /// Commands matching the input, best first.
+    pub fn matches(&self) -> impl Iterator<Item = &Command> {
+        self.matches.iter().map(|&i| &self.commands[i])
+    }
+
+    pub fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn refilter(&mut self) {
+        let query = self.input.text();
+        let mut scored: Vec<(usize, i32)> = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(i, command)| fuzzy_score(query, command.name).map(|score| (i, score)))
+            .collect();
+        scored.sort_by_key(|&(i, score)| (Reverse(score), i));
+        self.matches = scored.into_iter().map(|(i, _)| i).collect();
+        self.selected = 0;
+    }
+
+    /// Typing narrows the list; Up/Down (or Ctrl+p/Ctrl+n) move through it and
+    /// Enter runs the selected command.
+    pub fn handle_key(&mut self, key: KeyEvent) -> PaletteOutcome {
+        let count = self.matches.len();
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return PaletteOutcome::Cancel,
+            KeyCode::Enter => {
+                return match self.matches.get(self.selected) {
+                    Some(&i) => PaletteOutcome::Run(Box::new(self.commands[i].action.clone())),
+                    None => PaletteOutcome::Cancel,
+                };
+            }
+            KeyCode::Down | KeyCode::Tab if count > 0 => {
+                self.selected = (self.selected + 1) % count;
+            }
+            KeyCode::Char('n') if ctrl && count > 0 => {
+                self.selected = (self.selected + 1) % count;
+            }
+            KeyCode::Up | KeyCode::BackTab if count > 0 => {
+                self.selected = (self.selected + count - 1) % count;
+            }
+            KeyCode::Char('p') if ctrl && count > 0 => {
+                self.selected = (self.selected + count - 1) % count;
+            }
+            _ => {
+                if self.input.handle_key(key) {
+                    self.refilter();
+                }
+            }
+        }
+        PaletteOutcome::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(palette: &mut CommandPalette, text: &str) {
+        for c in text.chars() {
+            palette.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()));
+        }
+    }
+
+    #[test]
+    fn scores_word_starts_and_runs_higher() {
+        assert_eq!(fuzzy_score("", "Quit"), Some(0));
+        assert_eq!(fuzzy_score("xyz", "Toggle bookmark"), None);
+        assert!(fuzzy_score("tt", "Toggle theme") > fuzzy_score("tt", "Toggle settings"));
+        assert!(fuzzy_score("ab", "xabx") > fuzzy_score("ab", "xaxb"));
+        assert!(fuzzy_score("BY SC", "Sort by score").is_some());
+    }
+
+    #[test]
+    fn registry_names_every_command_once() {
+        let commands = commands();
+        let mut names: Vec<_> = commands.iter().map(|c| c.name).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), commands.len());
+        // Every listed action can be bound in the config
+        for command in &commands {
+            assert!(ron::to_string(&command.action).is_ok(), "{}", command.name);
+        }
+    }
+
+    #[test]
+    fn narrows_moves_and_runs() {
+        let mut palette = CommandPalette::default();
+        assert_eq!(palette.match_count(), commands().len());
+
+        type_text(&mut palette, "sort");
+        let names: Vec<_> = palette.matches().map(|c| c.name).take(3).collect();
+        assert_eq!(
+            names,
+            vec!["Sort by score", "Sort by comments", "Sort by time"]
+        );
+        palette.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
+        assert_eq!(
+            palette.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty())),
+            PaletteOutcome::Run(Box::new(Action::SortByComments))
+        );
+
+        type_text(&mut palette, "zzz");
+        assert_eq!(palette.match_count(), 0);
+        assert_eq!(
+            palette.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty())),
+            PaletteOutcome::Cancel
+        );
+    }
+}
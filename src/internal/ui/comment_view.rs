@@ -0,0 +1,979 @@
+//! Wrapped comment lines, rebuilt only when the thread, folds, theme or width change.
+//!
+//! Threads with the conversation view on render back-and-forth exchanges between
+//! two authors flat, one author's turns shifted right, instead of as a staircase.
+//! With a thread diff, comments are marked as new or edited since the snapshot and
+//! deleted ones are listed at the end. Comments by muted users are greyed out and
+//! their text replaced with a placeholder. Dead comments are marked and greyed out,
+//! replies can be numbered by their position among their siblings and siblings
+//! reordered by reply count. Comments by the story's submitter and by the logged-in
+//! user carry an "OP" or "you" badge, and authors can be colored by name. A comment search highlights its matches
+//! in the wrapped lines. Italics, code and links in comments keep their formatting,
+//! quotes are drawn behind a bar and links end with a `↗` marker.
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+use std::collections::BTreeSet;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+
+use crate::config::{CommentHintsConfig, DomainPalette};
+use crate::internal::comment_tree::{Turn, find_conversations, order_by_replies, sibling_ranks};
+use crate::internal::models::CommentRow;
+use crate::internal::search::SearchQuery;
+use crate::internal::thread_snapshot::{Change, ThreadDiff};
+use crate::internal::ui::domain_chip::domain_color;
+use crate::utils::datetime::TimeFormat;
+use crate::utils::html::{CommentBlock, Run, RunKind};
+use crate::utils::theme_loader::TuiTheme;
+
+/// What the comment pane draws on top of the plain thread.
+#[derive(Clone, Copy)]
+pub struct ThreadOptions<'a> {
+    /// Top-level comments whose threads use the conversation view
+    pub conversations: &'a BTreeSet<u32>,
+    pub diff: Option<&'a ThreadDiff>,
+    /// Authors whose comments are greyed out; `None` while muted content is revealed
+    pub muted_users: Option<&'a BTreeSet<String>>,
+    pub hints: CommentHintsConfig,
+    pub time_format: TimeFormat,
+    /// Submitter of the story, badged "OP"
+    pub op: Option<&'a str>,
+    /// Logged-in user, badged "you"
+    pub me: Option<&'a str>,
+    /// Colors of `hints.author_colors`
+    pub palette: DomainPalette,
+}
+
+/// Cached comment pane content for the open story.
+#[derive(Default)]
+pub struct CommentLayout {
+    key: Option<u64>,
+    pub lines: Vec<Line<'static>>,
+    /// `(line, comment id)` for every comment header line
+    pub line_index: Vec<(usize, u32)>,
+}
+
+impl CommentLayout {
+    /// Rebuild the lines if anything they depend on changed. Returns true when rebuilt.
+    pub fn update(
+        &mut self,
+        comments: &[CommentRow],
+        options: ThreadOptions,
+        theme: &TuiTheme,
+        width: usize,
+    ) -> bool {
+        let minute = jiff::Timestamp::now().as_second() / 60;
+        self.update_at(comments, options, theme, width, minute)
+    }
+
+    fn update_at(
+        &mut self,
+        comments: &[CommentRow],
+        options: ThreadOptions,
+        theme: &TuiTheme,
+        width: usize,
+        minute: i64,
+    ) -> bool {
+        let key = layout_key(comments, options, theme, width, minute);
+        if self.key == Some(key) {
+            return false;
+        }
+        let (lines, line_index) = build_lines(comments, options, theme, width);
+        self.lines = lines;
+        self.line_index = line_index;
+        self.key = Some(key);
+        true
+    }
+}
+
+/// Search through the wrapped comment lines of the open story; `n`/`N` move
+/// between the matches.
+#[derive(Debug, Clone, Default)]
+pub struct CommentSearch {
+    pub query: SearchQuery,
+    /// Line and byte range of every match, in reading order
+    pub matches: Vec<(usize, Range<usize>)>,
+    /// Index into `matches` of the match last jumped to
+    pub current: Option<usize>,
+}
+
+impl CommentSearch {
+    pub fn is_active(&self) -> bool {
+        !self.query.is_empty()
+    }
+
+    pub fn set_query(&mut self, query: SearchQuery, lines: &[Line]) {
+        self.query = query;
+        self.current = None;
+        self.find(lines);
+    }
+
+    /// Find the matches again, e.g. after the layout was rebuilt.
+    pub fn find(&mut self, lines: &[Line]) {
+        let mut matches = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+            matches.extend(self.query.find_ranges(&text).into_iter().map(|r| (i, r)));
+        }
+        self.matches = matches;
+        self.current = self.current.filter(|c| *c < self.matches.len());
+    }
+
+    /// Select the next match, or the previous one when `forward` is false, and
+    /// return its line. Without a current match the search starts at `from_line`.
+    pub fn step(&mut self, forward: bool, from_line: usize) -> Option<usize> {
+        let len = self.matches.len();
+        if len == 0 {
+            return None;
+        }
+        let next = match (self.current, forward) {
+            (Some(i), true) => (i + 1) % len,
+            (Some(i), false) => (i + len - 1) % len,
+            (None, true) => self
+                .matches
+                .iter()
+                .position(|(line, _)| *line >= from_line)
+                .unwrap_or(0),
+            (None, false) => self
+                .matches
+                .iter()
+                .rposition(|(line, _)| *line < from_line)
+                .unwrap_or(len - 1),
+        };
+        self.current = Some(next);
+        Some(self.matches[next].0)
+    }
+
+    /// "match 2/7" for the status bar.
+    pub fn status(&self) -> String {
+        match (self.current, self.matches.len()) {
+            (_, 0) => "no matches".to_string(),
+            (Some(i), len) => format!("match {}/{}", i + 1, len),
+            (None, len) => format!("{} matches", len),
+        }
+    }
+
+    /// Highlight the matches in `lines`, the current one in the selection colors.
+    pub fn highlight(&self, lines: &mut [Line], theme: &TuiTheme) {
+        let normal = Style::default().add_modifier(Modifier::REVERSED);
+        let current = Style::default()
+            .fg(theme.selection_fg)
+            .bg(theme.selection_bg)
+            .add_modifier(Modifier::BOLD);
+        let mut i = 0;
+        while i < self.matches.len() {
+            let line = self.matches[i].0;
+            let ranges: Vec<(Range<usize>, Style)> = self.matches[i..]
+                .iter()
+                .enumerate()
+                .take_while(|(_, (l, _))| *l == line)
+                .map(|(j, (_, range))| match self.current == Some(i + j) {
+                    true => (range.clone(), current),
+                    false => (range.clone(), normal),
+                })
+                .collect();
+            i += ranges.len();
+            if let Some(target) = lines.get_mut(line) {
+                *target = highlight_ranges(target, &ranges);
+            }
+        }
+    }
+}
+
+/// Copy of `line` with the byte `ranges` of its text restyled.
+fn highlight_ranges(line: &Line, ranges: &[(Range<usize>, Style)]) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    for span in &line.spans {
+        let text = span.content.as_ref();
+        let end = offset + text.len();
+        let mut cut = 0;
+        for (range, style) in ranges {
+            let start = range.start.clamp(offset, end) - offset;
+            let stop = range.end.clamp(offset, end) - offset;
+            if start >= stop {
+                continue;
+            }
+            if start > cut {
+                spans.push(Span::styled(text[cut..start].to_string(), span.style));
+            }
+            spans.push(Span::styled(
+                text[start..stop].to_string(),
+                span.style.patch(*style),
+            ));
+            cut = stop;
+        }
+        if cut < text.len() {
+            spans.push(Span::styled(text[cut..].to_string(), span.style));
+        }
+        offset = end;
+    }
+    Line {
+        spans,
+        style: line.style,
+        alignment: line.alignment,
+    }
+}
+
+/// Hash of the inputs that affect the layout. Relative timestamps ("5 minutes ago")
+/// are kept fresh by folding the current minute into the key.
+fn layout_key(
+    comments: &[CommentRow],
+    options: ThreadOptions,
+    theme: &TuiTheme,
+    width: usize,
+    minute: i64,
+) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    width.hash(&mut hasher);
+    options.conversations.hash(&mut hasher);
+    options
+        .diff
+        .map(|diff| (diff.taken_at.timestamp(), diff.summary()))
+        .hash(&mut hasher);
+    options.muted_users.hash(&mut hasher);
+    options.hints.hash(&mut hasher);
+    options.time_format.hash(&mut hasher);
+    (options.op, options.me, options.palette).hash(&mut hasher);
+    theme.hash(&mut hasher);
+    minute.hash(&mut hasher);
+    for row in comments {
+        (
+            row.comment.id,
+            row.depth,
+            row.expanded,
+            &row.comment.text,
+            row.comment.dead,
+        )
+            .hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Extra indent of the second author's turns in the conversation view.
+const REPLY_SHIFT: usize = 6;
+
+#[tracing::instrument(name = "render.comment_lines", level = "debug", skip_all, fields(comments = comments.len()))]
+fn build_lines(
+    comments: &[CommentRow],
+    options: ThreadOptions,
+    theme: &TuiTheme,
+    comment_area_width: usize,
+) -> (Vec<Line<'static>>, Vec<(usize, u32)>) {
+    let ThreadOptions {
+        conversations,
+        diff,
+        muted_users,
+        hints,
+        time_format,
+        op,
+        me,
+        palette,
+    } = options;
+    let mut all_lines: Vec<Line<'static>> = Vec::new();
+    let mut line_index = Vec::new();
+    let mut skip_until_depth: Option<usize> = None;
+    let turns = match conversations.is_empty() {
+        true => Default::default(),
+        false => find_conversations(comments),
+    };
+    let mut thread = None;
+    // Depth of each exchange's opening comment, where its turns are drawn
+    let mut opener_depth = std::collections::HashMap::new();
+    let ranks = match hints.sibling_rank {
+        true => sibling_ranks(comments),
+        false => Default::default(),
+    };
+    let order: Vec<usize> = match hints.sort_by_replies {
+        true => order_by_replies(comments),
+        false => (0..comments.len()).collect(),
+    };
+
+    for row in order.into_iter().map(|i| &comments[i]) {
+        if row.depth == 0 {
+            thread = Some(row.comment.id);
+        }
+        // Skip collapsed children
+        if let Some(until_depth) = skip_until_depth {
+            match row.depth.cmp(&until_depth) {
+                std::cmp::Ordering::Greater => continue,
+                _ => skip_until_depth = None,
+            }
+        }
+
+        let author = row.comment.by.as_deref().unwrap_or("unknown");
+        let muted = muted_users.is_some_and(|users| users.contains(author));
+        let text = row.comment.text.as_deref().unwrap_or("[deleted]");
+        let blocks = match muted {
+            true => vec![CommentBlock::Text(vec![Run {
+                text: "[muted]".to_string(),
+                kind: RunKind::Plain,
+            }])],
+            false => crate::utils::html::parse_comment(text),
+        };
+        let dead = hints.mark_dead && row.comment.dead;
+        let (author_color, text_color) = match muted || dead {
+            true => (theme.comment_time, theme.comment_time),
+            false if hints.author_colors => {
+                let (r, g, b) = domain_color(author, palette, theme.background);
+                (Color::Rgb(r, g, b), theme.foreground)
+            }
+            false => (theme.comment_author, theme.foreground),
+        };
+        let badges = match hints.author_badges {
+            true => author_badges(author, op, me, theme),
+            false => Vec::new(),
+        };
+        let time = row
+            .comment
+            .time
+            .as_ref()
+            .map(|t| crate::utils::datetime::format_time(t, time_format))
+            .unwrap_or_else(|| "unknown".to_string());
+        let mut hint = String::new();
+        if dead {
+            hint.push_str(" [dead]");
+        }
+        if let Some(rank) = ranks.get(&row.comment.id) {
+            hint.push_str(&format!(" · #{}", rank));
+        }
+
+        // Indentation and visual guides
+        let indent = "  ".repeat(row.depth);
+        let mut guide = String::new();
+        for i in 0..row.depth {
+            match i.cmp(&row.depth.saturating_sub(1)) {
+                std::cmp::Ordering::Less => guide.push_str("│ "),
+                _ => guide.push_str("└─"),
+            }
+        }
+
+        // Collapse indicator
+        let has_kids = row
+            .comment
+            .kids
+            .as_ref()
+            .is_some_and(|kids| !kids.is_empty());
+        let collapse_indicator = match (has_kids, row.expanded) {
+            (true, true) => "[-] ",
+            (true, false) => "[+] ",
+            _ => "",
+        };
+
+        // Set skip flag if collapsed
+        if has_kids && !row.expanded {
+            skip_until_depth = Some(row.depth);
+        }
+
+        let marker = diff
+            .and_then(|diff| diff.changes.get(&row.comment.id))
+            .map(|change| change_marker(*change, theme));
+
+        let turn = turns
+            .get(&row.comment.id)
+            .filter(|_| thread.is_some_and(|id| conversations.contains(&id)));
+        if let Some(turn) = turn {
+            let depth = *opener_depth.entry(turn.conversation).or_insert(row.depth);
+            line_index.push((
+                all_lines.len() + usize::from(turn.index == 0),
+                row.comment.id,
+            ));
+            push_turn(
+                &mut all_lines,
+                turn,
+                depth,
+                ([collapse_indicator, author, &time, &hint], &blocks),
+                (badges, marker),
+                theme,
+                comment_area_width,
+            );
+            continue;
+        }
+
+        // Author and time line with indentation
+        line_index.push((all_lines.len(), row.comment.id));
+        let mut header = vec![
+            Span::styled(guide, Style::default().fg(theme.border)),
+            Span::styled(collapse_indicator, Style::default().fg(theme.comment_time)),
+            Span::styled(author.to_string(), Style::default().fg(author_color)),
+        ];
+        header.extend(badges);
+        header.push(Span::styled(
+            format!(" ({}){}", time, hint),
+            Style::default().fg(theme.comment_time),
+        ));
+        header.extend(marker);
+        all_lines.push(Line::from(header));
+
+        // Wrapped text lines with indentation
+        let available_width = comment_area_width.saturating_sub(row.depth * 2);
+        for spans in comment_body(&blocks, text_color, theme, available_width.max(20)) {
+            let mut line = vec![Span::styled(indent.clone(), Style::default())];
+            line.extend(spans);
+            all_lines.push(Line::from(line));
+        }
+
+        // Separator
+        all_lines.push(Line::from(Span::styled(
+            "---",
+            Style::default().fg(theme.border),
+        )));
+        all_lines.push(Line::from("")); // Empty line for spacing
+    }
+
+    if let Some(diff) = diff.filter(|diff| !diff.deleted.is_empty()) {
+        push_deleted(&mut all_lines, diff, theme, comment_area_width);
+    }
+
+    (all_lines, line_index)
+}
+
+/// " OP " and " you " chips after the author's name.
+fn author_badges(
+    author: &str,
+    op: Option<&str>,
+    me: Option<&str>,
+    theme: &TuiTheme,
+) -> Vec<Span<'static>> {
+    let chip = Style::default()
+        .fg(theme.selection_fg)
+        .bg(theme.selection_bg)
+        .add_modifier(Modifier::BOLD);
+    [(op, " OP "), (me, " you ")]
+        .into_iter()
+        .filter(|(name, _)| *name == Some(author))
+        .flat_map(|(_, label)| [Span::raw(" "), Span::styled(label, chip)])
+        .collect()
+}
+
+fn change_marker(change: Change, theme: &TuiTheme) -> Span<'static> {
+    match change {
+        Change::Added => Span::styled(" + new", Style::default().fg(Color::Green)),
+        Change::Edited => Span::styled(" ~ edited", Style::default().fg(theme.score)),
+    }
+}
+
+/// Section listing the comments deleted since the snapshot, with their saved excerpts.
+fn push_deleted(lines: &mut Vec<Line<'static>>, diff: &ThreadDiff, theme: &TuiTheme, width: usize) {
+    lines.push(Line::from(Span::styled(
+        format!(
+            "Deleted since {} ({})",
+            crate::utils::datetime::format_timestamp(&diff.taken_at.timestamp().as_second()),
+            diff.deleted.len()
+        ),
+        Style::default().fg(Color::Red),
+    )));
+    for comment in &diff.deleted {
+        lines.push(Line::from(vec![
+            Span::styled("- ", Style::default().fg(Color::Red)),
+            Span::styled(
+                comment.by.clone().unwrap_or_else(|| "unknown".to_string()),
+                Style::default().fg(theme.comment_author),
+            ),
+        ]));
+        for line in textwrap::wrap(&comment.excerpt, width.saturating_sub(2).max(20)) {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", line),
+                Style::default().fg(theme.comment_time),
+            )));
+        }
+    }
+    lines.push(Line::from(""));
+}
+
+/// Lines for one turn of an exchange: a header naming both authors before the first
+/// turn, then the comment with a colored bar, the second author's turns shifted right.
+fn push_turn(
+    lines: &mut Vec<Line<'static>>,
+    turn: &Turn,
+    depth: usize,
+    ([collapse_indicator, author, time, hint], body): ([&str; 4], &[CommentBlock]),
+    (badges, marker): (Vec<Span<'static>>, Option<Span<'static>>),
+    theme: &TuiTheme,
+    width: usize,
+) {
+    let indent = "  ".repeat(depth);
+    if turn.index == 0 {
+        lines.push(Line::from(vec![
+            Span::raw(indent.clone()),
+            Span::styled(
+                format!(
+                    "⇄ {} and {} · {} replies",
+                    turn.authors[0], turn.authors[1], turn.length
+                ),
+                Style::default().fg(theme.comment_time),
+            ),
+        ]));
+    }
+
+    let color = match turn.side {
+        0 => theme.comment_author,
+        _ => theme.link,
+    };
+    let indent = format!("{}{}", indent, " ".repeat(turn.side * REPLY_SHIFT));
+    let bar = Span::styled("┃ ", Style::default().fg(color));
+    let mut header = vec![
+        Span::raw(indent.clone()),
+        bar.clone(),
+        Span::styled(
+            collapse_indicator.to_string(),
+            Style::default().fg(theme.comment_time),
+        ),
+        Span::styled(author.to_string(), Style::default().fg(color)),
+    ];
+    header.extend(badges);
+    header.push(Span::styled(
+        format!(" ({}){}", time, hint),
+        Style::default().fg(theme.comment_time),
+    ));
+    header.extend(marker);
+    lines.push(Line::from(header));
+    let available_width = width.saturating_sub(indent.len() + 2);
+    for spans in comment_body(body, theme.foreground, theme, available_width.max(20)) {
+        let mut line = vec![Span::raw(indent.clone()), bar.clone()];
+        line.extend(spans);
+        lines.push(Line::from(line));
+    }
+    lines.push(Line::from(""));
+}
+
+/// Comment paragraphs wrapped to `width` columns, with a blank line between them.
+fn comment_body(
+    blocks: &[CommentBlock],
+    color: Color,
+    theme: &TuiTheme,
+    width: usize,
+) -> Vec<Vec<Span<'static>>> {
+    let mut lines = Vec::new();
+    for (i, block) in blocks.iter().enumerate() {
+        if i > 0 {
+            lines.push(Vec::new());
+        }
+        match block {
+            CommentBlock::Text(runs) => {
+                lines.extend(wrap_runs(runs, Style::default().fg(color), theme, width));
+            }
+            CommentBlock::Quote(runs) => {
+                let bar = Span::styled("│ ", Style::default().fg(theme.border));
+                let style = Style::default()
+                    .fg(theme.comment_time)
+                    .add_modifier(Modifier::ITALIC);
+                for spans in wrap_runs(runs, style, theme, width.saturating_sub(2).max(10)) {
+                    let mut line = vec![bar.clone()];
+                    line.extend(spans);
+                    lines.push(line);
+                }
+            }
+            CommentBlock::Code(code) => {
+                let style = Style::default().fg(theme.comment_author);
+                for line in code.lines() {
+                    for part in textwrap::wrap(line, width) {
+                        lines.push(vec![Span::styled(part.into_owned(), style)]);
+                    }
+                }
+            }
+        }
+    }
+    lines
+}
+
+/// A word of a paragraph, as pieces of differently styled text.
+type Word = Vec<(String, Style)>;
+
+/// Wrap styled runs to `width` columns, breaking between words. `base` is the style
+/// of plain text; words longer than a line are split.
+fn wrap_runs(runs: &[Run], base: Style, theme: &TuiTheme, width: usize) -> Vec<Vec<Span<'static>>> {
+    let link = Style::default().fg(theme.link);
+    let mut words: Vec<Word> = Vec::new();
+    let mut word = Word::new();
+    for run in runs {
+        let style = match &run.kind {
+            RunKind::Plain => base,
+            RunKind::Italic => base.add_modifier(Modifier::ITALIC),
+            RunKind::Code => Style::default().fg(theme.comment_author),
+            RunKind::Link(_) => link.add_modifier(Modifier::UNDERLINED),
+        };
+        for c in run.text.chars() {
+            match c.is_whitespace() {
+                true if !word.is_empty() => words.push(std::mem::take(&mut word)),
+                true => {}
+                false => push_piece(&mut word, c.to_string(), style),
+            }
+        }
+        if matches!(run.kind, RunKind::Link(_)) {
+            push_piece(&mut word, "↗".to_string(), link);
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    let mut lines = Vec::new();
+    let mut line: Vec<Span<'static>> = Vec::new();
+    let mut used = 0;
+    for word in words.into_iter().flat_map(|word| split_long(word, width)) {
+        let word_width: usize = word.iter().map(|(text, _)| display_width(text)).sum();
+        if used > 0 && used + 1 + word_width > width {
+            lines.push(std::mem::take(&mut line));
+            used = 0;
+        }
+        if used > 0 {
+            // A space inside a link is part of the link
+            let before = line.last().map(|span| span.style);
+            let space = match before == word.first().map(|(_, style)| *style) {
+                true => before.unwrap_or(base),
+                false => base,
+            };
+            push_span(&mut line, " ", space);
+            used += 1;
+        }
+        for (text, style) in word {
+            push_span(&mut line, &text, style);
+        }
+        used += word_width;
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+fn display_width(text: &str) -> usize {
+    textwrap::core::display_width(text)
+}
+
+fn push_piece(word: &mut Word, text: String, style: Style) {
+    match word.last_mut() {
+        Some((last, last_style)) if *last_style == style => last.push_str(&text),
+        _ => word.push((text, style)),
+    }
+}
+
+/// Append to the line, merging with the last span if it has the same style.
+fn push_span(line: &mut Vec<Span<'static>>, text: &str, style: Style) {
+    match line.last_mut() {
+        Some(last) if last.style == style => last.content.to_mut().push_str(text),
+        _ => line.push(Span::styled(text.to_string(), style)),
+    }
+}
+
+/// Split a word wider than `width` into words that fit.
+fn split_long(word: Word, width: usize) -> Vec<Word> {
+    let total: usize = word.iter().map(|(text, _)| display_width(text)).sum();
+    if total <= width {
+        return vec![word];
+    }
+    let mut parts = vec![Word::new()];
+    let mut used = 0;
+    for (text, style) in word {
+        for c in text.chars() {
+            let w = display_width(c.encode_utf8(&mut [0; 4]));
+            if used + w > width && used > 0 {
+                parts.push(Word::new());
+                used = 0;
+            }
+            if let Some(part) = parts.last_mut() {
+                push_piece(part, c.to_string(), style);
+            }
+            used += w;
+        }
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::models::Comment;
+
+    fn row(id: u32, depth: usize, kids: Option<Vec<u32>>) -> CommentRow {
+        CommentRow {
+            comment: Comment {
+                id,
+                by: Some("pg".to_string()),
+                text: Some("hello world".to_string()),
+                time: None,
+                kids,
+                deleted: false,
+                dead: false,
+            },
+            depth,
+            expanded: true,
+            parent_id: None,
+            loaded_kids: true,
+        }
+    }
+
+    fn plain(conversations: &BTreeSet<u32>) -> ThreadOptions<'_> {
+        ThreadOptions {
+            conversations,
+            diff: None,
+            muted_users: None,
+            hints: CommentHintsConfig::default(),
+            time_format: TimeFormat::Relative,
+            op: None,
+            me: None,
+            palette: DomainPalette::Default,
+        }
+    }
+
+    #[test]
+    fn rebuilds_only_when_inputs_change() {
+        let theme = TuiTheme::default();
+        let mut comments = vec![row(1, 0, Some(vec![2])), row(2, 1, None)];
+        let mut layout = CommentLayout::default();
+        let none = BTreeSet::new();
+
+        let ids = |layout: &CommentLayout| -> Vec<u32> {
+            layout.line_index.iter().map(|(_, id)| *id).collect()
+        };
+
+        assert!(layout.update_at(&comments, plain(&none), &theme, 80, 0));
+        assert!(!layout.update_at(&comments, plain(&none), &theme, 80, 0));
+        assert_eq!(ids(&layout), vec![1, 2]);
+
+        // Folding the parent hides the child
+        comments[0].expanded = false;
+        assert!(layout.update_at(&comments, plain(&none), &theme, 80, 0));
+        assert_eq!(ids(&layout), vec![1]);
+
+        assert!(layout.update_at(&comments, plain(&none), &theme, 60, 0));
+        assert!(!layout.update_at(&comments, plain(&none), &theme, 60, 0));
+        // Relative timestamps are refreshed once a minute
+        assert!(layout.update_at(&comments, plain(&none), &theme, 60, 1));
+        // An edit that keeps the length still shows
+        comments[0].comment.text = Some("hello earth".to_string());
+        assert!(layout.update_at(&comments, plain(&none), &theme, 60, 1));
+    }
+
+    #[test]
+    fn conversation_view_flattens_exchanges_per_thread() {
+        let theme = TuiTheme::default();
+        let reply = |id, depth, parent, author: &str| CommentRow {
+            comment: Comment {
+                by: Some(author.to_string()),
+                ..row(id, depth, None).comment
+            },
+            parent_id: parent,
+            ..row(id, depth, None)
+        };
+        let comments = vec![
+            reply(1, 0, None, "alice"),
+            reply(2, 1, Some(1), "bob"),
+            reply(3, 2, Some(2), "alice"),
+            reply(4, 0, None, "carol"),
+        ];
+        let mut layout = CommentLayout::default();
+
+        layout.update_at(&comments, plain(&BTreeSet::new()), &theme, 80, 0);
+        let plain_len = layout.lines.len();
+        assert!(!layout.lines.iter().any(|l| l.to_string().contains('⇄')));
+
+        assert!(layout.update_at(&comments, plain(&BTreeSet::from([1])), &theme, 80, 0));
+        let text: Vec<String> = layout.lines.iter().map(|l| l.to_string()).collect();
+        assert!(text[0].starts_with("⇄ alice and bob · 3 replies"));
+        // Headers point past the exchange header; bob's turn is shifted right
+        let (bob_line, _) = layout.line_index[1];
+        assert!(text[bob_line].starts_with("      ┃ bob"));
+        assert!(text[layout.line_index[2].0].starts_with("┃ alice"));
+        assert!(layout.lines.len() < plain_len);
+    }
+
+    #[test]
+    fn search_highlights_matches_across_spans() {
+        let mut lines = vec![
+            Line::from(vec![Span::raw("ru"), Span::raw("st and Rust")]),
+            Line::from("nothing here"),
+            Line::from("rust"),
+        ];
+        let mut search = CommentSearch::default();
+        let query = SearchQuery::new(
+            "rust".to_string(),
+            crate::internal::search::SearchMode::Comments,
+            crate::internal::search::SearchType::Literal,
+        );
+        search.set_query(query, &lines);
+        assert_eq!(search.matches, vec![(0, 0..4), (0, 9..13), (2, 0..4)]);
+
+        assert_eq!(search.step(true, 1), Some(2));
+        assert_eq!(search.step(true, 1), Some(0));
+        assert_eq!(search.step(false, 1), Some(2));
+        assert_eq!(search.status(), "match 3/3");
+
+        search.highlight(&mut lines, &TuiTheme::default());
+        let contents: Vec<&str> = lines[0].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(contents, vec!["ru", "st", " and ", "Rust"]);
+        assert!(
+            lines[0].spans[0]
+                .style
+                .add_modifier
+                .contains(Modifier::REVERSED)
+        );
+        assert!(
+            lines[2].spans[0]
+                .style
+                .add_modifier
+                .contains(Modifier::BOLD)
+        );
+        assert_eq!(lines[1].spans.len(), 1);
+    }
+
+    #[test]
+    fn muted_authors_are_greyed_out() {
+        let theme = TuiTheme::default();
+        let comments = vec![row(1, 0, None)];
+        let mut layout = CommentLayout::default();
+        let none = BTreeSet::new();
+        let muted = BTreeSet::from(["pg".to_string()]);
+
+        layout.update_at(&comments, plain(&none), &theme, 80, 0);
+        assert!(layout.lines[1].to_string().contains("hello world"));
+
+        let options = ThreadOptions {
+            muted_users: Some(&muted),
+            ..plain(&none)
+        };
+        assert!(layout.update_at(&comments, options, &theme, 80, 0));
+        assert!(layout.lines[1].to_string().contains("[muted]"));
+        assert_eq!(layout.lines[0].spans[2].style.fg, Some(theme.comment_time));
+    }
+
+    #[test]
+    fn hints_mark_dead_comments_and_rank_siblings() {
+        let theme = TuiTheme::default();
+        let reply = |id, parent, kids: Vec<u32>| CommentRow {
+            parent_id: Some(parent),
+            ..row(id, 1, Some(kids))
+        };
+        let mut comments = vec![
+            row(1, 0, Some(vec![2, 3])),
+            reply(2, 1, vec![]),
+            reply(3, 1, vec![4]),
+        ];
+        comments[1].comment.dead = true;
+        comments.push(CommentRow {
+            parent_id: Some(3),
+            ..row(4, 2, None)
+        });
+        let none = BTreeSet::new();
+        let mut layout = CommentLayout::default();
+
+        layout.update_at(&comments, plain(&none), &theme, 80, 0);
+        let header = |layout: &CommentLayout, id| {
+            let (line, _) = layout.line_index.iter().find(|(_, i)| *i == id).unwrap();
+            layout.lines[*line].to_string()
+        };
+        assert!(header(&layout, 2).ends_with("(unknown) [dead]"));
+        assert!(!header(&layout, 3).contains('#'));
+
+        let options = ThreadOptions {
+            hints: CommentHintsConfig {
+                sibling_rank: true,
+                sort_by_replies: true,
+                ..Default::default()
+            },
+            ..plain(&none)
+        };
+        assert!(layout.update_at(&comments, options, &theme, 80, 0));
+        assert!(header(&layout, 3).ends_with("(unknown) · #2"));
+        // 3 has a reply and 2 has none, so 3 comes first
+        let ids: Vec<u32> = layout.line_index.iter().map(|(_, id)| *id).collect();
+        assert_eq!(ids, vec![1, 3, 4, 2]);
+    }
+
+    #[test]
+    fn comments_keep_their_formatting() {
+        let theme = TuiTheme::default();
+        let mut comments = vec![row(1, 0, None)];
+        comments[0].comment.text = Some(
+            "See <a href=\"https://a.io/x\">the docs</a>, <i>really</i><p>&gt; a quote\
+             <p><pre><code>fn main() {}\n</code></pre>"
+                .to_string(),
+        );
+        let mut layout = CommentLayout::default();
+        layout.update_at(&comments, plain(&BTreeSet::new()), &theme, 80, 0);
+
+        let text: Vec<String> = layout.lines.iter().map(|l| l.to_string()).collect();
+        assert_eq!(
+            text[1..6],
+            ["See the docs↗, really", "", "│ a quote", "", "fn main() {}"]
+        );
+        let style_of = |line: usize, content: &str| {
+            layout.lines[line]
+                .spans
+                .iter()
+                .find(|span| span.content == content)
+                .map(|span| span.style)
+                .unwrap()
+        };
+        let link = style_of(1, "the docs");
+        assert_eq!(link.fg, Some(theme.link));
+        assert!(link.add_modifier.contains(Modifier::UNDERLINED));
+        assert!(
+            style_of(1, "really")
+                .add_modifier
+                .contains(Modifier::ITALIC)
+        );
+        assert_eq!(style_of(5, "fn main() {}").fg, Some(theme.comment_author));
+
+        // Long words are split to fit
+        let runs = vec![Run {
+            text: "abcdefghij".to_string(),
+            kind: RunKind::Plain,
+        }];
+        let wrapped = wrap_runs(&runs, Style::default(), &theme, 4);
+        assert_eq!(wrapped.len(), 3);
+    }
+
+    #[test]
+    fn badges_and_colors_authors() {
+        let theme = TuiTheme::default();
+        let by = |id, depth, author: &str| CommentRow {
+            comment: Comment {
+                by: Some(author.to_string()),
+                ..row(id, depth, None).comment
+            },
+            ..row(id, depth, None)
+        };
+        let comments = vec![by(1, 0, "alice"), by(2, 0, "bob"), by(3, 0, "carol")];
+        let none = BTreeSet::new();
+        let mut layout = CommentLayout::default();
+        let options = ThreadOptions {
+            op: Some("alice"),
+            me: Some("bob"),
+            ..plain(&none)
+        };
+        layout.update_at(&comments, options, &theme, 80, 0);
+        let headers: Vec<String> = layout
+            .line_index
+            .iter()
+            .map(|(line, _)| layout.lines[*line].to_string())
+            .collect();
+        assert!(headers[0].starts_with("alice  OP  ("));
+        assert!(headers[1].starts_with("bob  you  ("));
+        assert!(headers[2].starts_with("carol ("));
+        assert_eq!(
+            layout.lines[0].spans[2].style.fg,
+            Some(theme.comment_author)
+        );
+
+        let options = ThreadOptions {
+            hints: CommentHintsConfig {
+                author_badges: false,
+                author_colors: true,
+                ..Default::default()
+            },
+            ..options
+        };
+        assert!(layout.update_at(&comments, options, &theme, 80, 0));
+        assert!(!layout.lines[0].to_string().contains("OP"));
+        let alice = domain_color("alice", DomainPalette::Default, theme.background);
+        assert_eq!(
+            layout.lines[0].spans[2].style.fg,
+            Some(Color::Rgb(alice.0, alice.1, alice.2))
+        );
+    }
+}
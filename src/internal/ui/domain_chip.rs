@@ -0,0 +1,178 @@
+//! Stable per-domain colors, so stories from the same source are easy to spot, and
+//! the publisher initial shown in front of them.
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Span,
+};
+
+use crate::config::DomainPalette;
+use crate::utils::theme_loader::TuiTheme;
+
+/// Soft colors that sit well on dark backgrounds.
+const DARK_BG: [(u8, u8, u8); 10] = [
+    (0x7a, 0xa2, 0xf7),
+    (0x9e, 0xce, 0x6a),
+    (0xe0, 0xaf, 0x68),
+    (0xbb, 0x9a, 0xf7),
+    (0x7d, 0xcf, 0xff),
+    (0xf7, 0x76, 0x8e),
+    (0xff, 0x9e, 0x64),
+    (0x73, 0xda, 0xca),
+    (0xc0, 0xca, 0xf5),
+    (0xd1, 0x9a, 0x66),
+];
+
+/// Deeper versions of the same hues for light backgrounds.
+const LIGHT_BG: [(u8, u8, u8); 10] = [
+    (0x2e, 0x5c, 0xb8),
+    (0x3f, 0x7d, 0x20),
+    (0x9a, 0x67, 0x00),
+    (0x6f, 0x42, 0xc1),
+    (0x00, 0x7a, 0x99),
+    (0xb8, 0x30, 0x5a),
+    (0xc2, 0x4e, 0x00),
+    (0x00, 0x80, 0x70),
+    (0x55, 0x5f, 0x99),
+    (0x8b, 0x5a, 0x2b),
+];
+
+/// Okabe-Ito palette, distinguishable with the common forms of color blindness.
+const COLOR_BLIND: [(u8, u8, u8); 8] = [
+    (0xe6, 0x9f, 0x00),
+    (0x56, 0xb4, 0xe9),
+    (0x00, 0x9e, 0x73),
+    (0xf0, 0xe4, 0x42),
+    (0x00, 0x72, 0xb2),
+    (0xd5, 0x5e, 0x00),
+    (0xcc, 0x79, 0xa7),
+    (0x99, 0x99, 0x99),
+];
+
+/// FNV-1a; unlike `DefaultHasher` it is stable across Rust releases, so a domain
+/// keeps its color between builds.
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn luminance((r, g, b): (u8, u8, u8)) -> f32 {
+    0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32
+}
+
+/// Terminal default and named colors are treated as dark backgrounds.
+fn is_light(background: Color) -> bool {
+    match background {
+        Color::Rgb(r, g, b) => luminance((r, g, b)) > 140.0,
+        Color::White => true,
+        _ => false,
+    }
+}
+
+/// Color assigned to `domain`. Subdomains like `www.` are ignored.
+pub fn domain_color(domain: &str, palette: DomainPalette, background: Color) -> (u8, u8, u8) {
+    let domain = domain.trim_start_matches("www.").to_lowercase();
+    let colors: &[(u8, u8, u8)] = match (palette, is_light(background)) {
+        (DomainPalette::ColorBlind, _) => &COLOR_BLIND,
+        (DomainPalette::Default, true) => &LIGHT_BG,
+        (DomainPalette::Default, false) => &DARK_BG,
+    };
+    colors[(fnv1a(&domain) % colors.len() as u64) as usize]
+}
+
+/// Style with `rgb` as background and readable text on top.
+fn badge_style((r, g, b): (u8, u8, u8)) -> Style {
+    let fg = match luminance((r, g, b)) > 140.0 {
+        true => Color::Black,
+        false => Color::White,
+    };
+    Style::default().bg(Color::Rgb(r, g, b)).fg(fg)
+}
+
+/// ` label ` badge with the color of `domain` as background; the label is the
+/// domain itself or its publisher's name.
+pub fn chip(domain: &str, label: &str, palette: DomainPalette, theme: &TuiTheme) -> Span<'static> {
+    let rgb = domain_color(domain, palette, theme.background);
+    Span::styled(format!(" {} ", label), badge_style(rgb))
+}
+
+/// ` G` favicon stand-in put before the chip: the publisher's initial on its brand
+/// color, or on the domain color when the brand color is not known.
+pub fn icon(
+    glyph: char,
+    brand: Option<(u8, u8, u8)>,
+    domain: &str,
+    palette: DomainPalette,
+    theme: &TuiTheme,
+) -> Span<'static> {
+    let rgb = brand.unwrap_or_else(|| domain_color(domain, palette, theme.background));
+    Span::styled(
+        format!(" {}", glyph),
+        badge_style(rgb).add_modifier(Modifier::BOLD),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors_are_stable_and_theme_aware() {
+        let dark = Color::Rgb(0x1d, 0x20, 0x21);
+        let light = Color::Rgb(0xfb, 0xf1, 0xc7);
+
+        let a = domain_color("github.com", DomainPalette::Default, dark);
+        assert_eq!(
+            a,
+            domain_color("www.GitHub.com", DomainPalette::Default, dark)
+        );
+        assert!(DARK_BG.contains(&a));
+        assert!(LIGHT_BG.contains(&domain_color("github.com", DomainPalette::Default, light)));
+        assert!(COLOR_BLIND.contains(&domain_color(
+            "github.com",
+            DomainPalette::ColorBlind,
+            light
+        )));
+
+        // Not every domain lands on the same color
+        let colors: std::collections::HashSet<_> = [
+            "arxiv.org",
+            "github.com",
+            "nytimes.com",
+            "lwn.net",
+            "rust-lang.org",
+        ]
+        .iter()
+        .map(|d| domain_color(d, DomainPalette::Default, dark))
+        .collect();
+        assert!(colors.len() > 1);
+    }
+
+    #[test]
+    fn chip_text_contrasts_with_its_color() {
+        let theme = TuiTheme::default();
+        let span = chip("lwn.net", "LWN.net", DomainPalette::ColorBlind, &theme);
+        assert_eq!(span.content, " LWN.net ");
+        let (Some(Color::Rgb(r, g, b)), Some(fg)) = (span.style.bg, span.style.fg) else {
+            panic!("chip should set both colors");
+        };
+        let expected = match luminance((r, g, b)) > 140.0 {
+            true => Color::Black,
+            false => Color::White,
+        };
+        assert_eq!(fg, expected);
+
+        // The icon takes the brand color when there is one
+        let icon = icon(
+            'L',
+            Some((0xff, 0xff, 0xff)),
+            "lwn.net",
+            DomainPalette::Default,
+            &theme,
+        );
+        assert_eq!(icon.content, " L");
+        assert_eq!(icon.style.bg, Some(Color::Rgb(0xff, 0xff, 0xff)));
+        assert_eq!(icon.style.fg, Some(Color::Black));
+    }
+}
@@ -0,0 +1,223 @@
+//! Popup for editing the story list filters. Each row edits one filter; domain
+//! and author lists are typed comma-separated.
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::config::StoryFilterConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterField {
+    MinScore,
+    MaxAge,
+    IncludeDomains,
+    ExcludeDomains,
+    AllowAuthors,
+    BlockAuthors,
+}
+
+impl FilterField {
+    pub const ALL: [FilterField; 6] = [
+        FilterField::MinScore,
+        FilterField::MaxAge,
+        FilterField::IncludeDomains,
+        FilterField::ExcludeDomains,
+        FilterField::AllowAuthors,
+        FilterField::BlockAuthors,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FilterField::MinScore => "Minimum score",
+            FilterField::MaxAge => "Maximum age (hours)",
+            FilterField::IncludeDomains => "Only domains",
+            FilterField::ExcludeDomains => "Hide domains",
+            FilterField::AllowAuthors => "Only authors",
+            FilterField::BlockAuthors => "Hide authors",
+        }
+    }
+
+    /// Current value as typed in the panel; empty when the filter is off.
+    pub fn value(self, filters: &StoryFilterConfig) -> String {
+        let number = |n: u32| match n {
+            0 => String::new(),
+            n => n.to_string(),
+        };
+        match self {
+            FilterField::MinScore => number(filters.min_score),
+            FilterField::MaxAge => number(filters.max_age_hours),
+            FilterField::IncludeDomains => filters.include_domains.join(", "),
+            FilterField::ExcludeDomains => filters.exclude_domains.join(", "),
+            FilterField::AllowAuthors => filters.allow_authors.join(", "),
+            FilterField::BlockAuthors => filters.block_authors.join(", "),
+        }
+    }
+
+    /// Set the filter from typed text; an empty value turns it off.
+    fn set(self, filters: &mut StoryFilterConfig, text: &str) -> Result<(), String> {
+        let number = |text: &str| match text.trim() {
+            "" => Ok(0),
+            text => text
+                .parse::<u32>()
+                .map_err(|_| format!("{} must be a whole number", self.label())),
+        };
+        let list = |text: &str| -> Vec<String> {
+            text.split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(str::to_string)
+                .collect()
+        };
+        match self {
+            FilterField::MinScore => filters.min_score = number(text)?,
+            FilterField::MaxAge => filters.max_age_hours = number(text)?,
+            FilterField::IncludeDomains => filters.include_domains = list(text),
+            FilterField::ExcludeDomains => filters.exclude_domains = list(text),
+            FilterField::AllowAuthors => filters.allow_authors = list(text),
+            FilterField::BlockAuthors => filters.block_authors = list(text),
+        }
+        Ok(())
+    }
+}
+
+/// What the panel asks the app to do after a key press.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PanelOutcome {
+    /// Keep the panel open
+    Pending,
+    Close,
+    /// Use and save these filters; the panel stays open
+    Apply(StoryFilterConfig),
+}
+
+#[derive(Debug, Clone)]
+pub struct FilterPanel {
+    pub filters: StoryFilterConfig,
+    pub selected: usize,
+    /// Text being typed for the selected row
+    pub editing: Option<String>,
+    /// Why the last edit was rejected
+    pub error: Option<String>,
+}
+
+impl FilterPanel {
+    pub fn new(filters: StoryFilterConfig) -> Self {
+        Self {
+            filters,
+            selected: 0,
+            editing: None,
+            error: None,
+        }
+    }
+
+    pub fn field(&self) -> FilterField {
+        FilterField::ALL[self.selected]
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> PanelOutcome {
+        if let Some(text) = &mut self.editing {
+            match key.code {
+                KeyCode::Esc => {
+                    self.editing = None;
+                    self.error = None;
+                }
+                KeyCode::Enter => {
+                    let text = text.clone();
+                    return match self.field().set(&mut self.filters, &text) {
+                        Ok(()) => {
+                            self.editing = None;
+                            self.error = None;
+                            PanelOutcome::Apply(self.filters.clone())
+                        }
+                        Err(error) => {
+                            self.error = Some(error);
+                            PanelOutcome::Pending
+                        }
+                    };
+                }
+                KeyCode::Backspace => {
+                    text.pop();
+                }
+                KeyCode::Char(c) => text.push(c),
+                _ => {}
+            }
+            return PanelOutcome::Pending;
+        }
+
+        let rows = FilterField::ALL.len();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('F') => PanelOutcome::Close,
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected = (self.selected + 1) % rows;
+                PanelOutcome::Pending
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = (self.selected + rows - 1) % rows;
+                PanelOutcome::Pending
+            }
+            KeyCode::Enter => {
+                self.editing = Some(self.field().value(&self.filters));
+                PanelOutcome::Pending
+            }
+            KeyCode::Char('x') | KeyCode::Delete | KeyCode::Backspace => {
+                // Clearing cannot fail
+                let _ = self.field().set(&mut self.filters, "");
+                PanelOutcome::Apply(self.filters.clone())
+            }
+            KeyCode::Char('C') => {
+                self.filters = StoryFilterConfig::default();
+                PanelOutcome::Apply(self.filters.clone())
+            }
+            _ => PanelOutcome::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn press(panel: &mut FilterPanel, code: KeyCode) -> PanelOutcome {
+        panel.handle_key(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    fn type_text(panel: &mut FilterPanel, text: &str) {
+        for c in text.chars() {
+            press(panel, KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn edits_rows_and_rejects_bad_numbers() {
+        let mut panel = FilterPanel::new(StoryFilterConfig::default());
+        press(&mut panel, KeyCode::Enter);
+        type_text(&mut panel, "5o");
+        assert_eq!(press(&mut panel, KeyCode::Enter), PanelOutcome::Pending);
+        assert!(panel.error.is_some());
+        press(&mut panel, KeyCode::Backspace);
+        type_text(&mut panel, "0");
+        let PanelOutcome::Apply(filters) = press(&mut panel, KeyCode::Enter) else {
+            panic!("expected the filters to apply");
+        };
+        assert_eq!(filters.min_score, 50);
+
+        // Lists are comma-separated
+        press(&mut panel, KeyCode::Char('j'));
+        press(&mut panel, KeyCode::Char('j'));
+        press(&mut panel, KeyCode::Char('j'));
+        press(&mut panel, KeyCode::Enter);
+        type_text(&mut panel, "a.com, ,b.com");
+        press(&mut panel, KeyCode::Enter);
+        assert_eq!(panel.filters.exclude_domains, vec!["a.com", "b.com"]);
+        assert_eq!(
+            FilterField::ExcludeDomains.value(&panel.filters),
+            "a.com, b.com"
+        );
+
+        let PanelOutcome::Apply(filters) = press(&mut panel, KeyCode::Char('x')) else {
+            panic!("expected the filters to apply");
+        };
+        assert!(filters.exclude_domains.is_empty());
+        assert_eq!(press(&mut panel, KeyCode::Esc), PanelOutcome::Close);
+    }
+}
@@ -0,0 +1,254 @@
+//! Content of the help overlay, generated from the key bindings in effect, so keys
+//! changed in the config show up as they are. Bindings are grouped by the view
+//! they work in and laid out in pages as tall as the overlay.
+
+use crate::config::PluginConfig;
+use crate::internal::ui::app::Action;
+use crate::internal::ui::command_palette::commands;
+use crate::internal::ui::keybindings::{KeyBindingContext, KeyBindingMap};
+use crate::internal::ui::keybindings_default::create_default_keybindings;
+
+/// Views with their own bindings, in the order the help lists them.
+const CONTEXTS: [(KeyBindingContext, &str); 7] = [
+    (KeyBindingContext::Global, "Global"),
+    (KeyBindingContext::List, "Story list"),
+    (KeyBindingContext::StoryDetail, "Comments"),
+    (KeyBindingContext::Article, "Article"),
+    (KeyBindingContext::Bookmarks, "Bookmarks"),
+    (KeyBindingContext::History, "History"),
+    (KeyBindingContext::ReadingLists, "Reading lists"),
+];
+
+/// Keys handled by the views themselves rather than through bindings.
+const OTHER_KEYS: [(&str, &str); 6] = [
+    ("/", "Search stories, history or comments"),
+    ("Q", "Clear the search"),
+    ("g", "Toggle auto-switch theme (list)"),
+    ("za zM zR", "Fold comment / all / none"),
+    ("z1-9", "Fold comments to depth"),
+    ("e", "Open/close the theme editor"),
+];
+
+const THEME_EDITOR_KEYS: [(&str, &str); 6] = [
+    ("↑ / ↓", "Navigate theme properties"),
+    ("← / →", "Switch RGB channels"),
+    ("+ / =", "Increase color value (+5)"),
+    ("- / _", "Decrease color value (-5)"),
+    ("s", "Save theme to JSON"),
+    ("Esc", "Close editor (discard changes)"),
+];
+
+const HELP_KEYS: [(&str, &str); 3] = [
+    ("Tab / Shift+Tab", "Next / previous page"),
+    ("T", "Take the guided tour"),
+    ("Esc / q / ?", "Close this help"),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub keys: String,
+    pub description: String,
+    /// Bound by the config rather than by default
+    pub customized: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    pub title: String,
+    pub entries: Vec<Entry>,
+}
+
+/// A row of a help page.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Row {
+    Heading(String),
+    Entry(Entry),
+    Blank,
+}
+
+/// What `action` does, as the command palette names it.
+fn describe(action: &Action, plugins: &[PluginConfig]) -> String {
+    match action {
+        Action::OpenCommandPalette => "Command palette".to_string(),
+        Action::RunPlugin(index) => match plugins.get(*index) {
+            Some(plugin) => format!("Plugin: {}", plugin.name),
+            None => format!("Plugin {}", index),
+        },
+        other => commands()
+            .into_iter()
+            .find(|command| command.action == *other)
+            .map_or_else(
+                || format!("{:?}", other),
+                |command| command.name.to_string(),
+            ),
+    }
+}
+
+fn fixed_section(title: &str, keys: &[(&str, &str)]) -> Section {
+    Section {
+        title: title.to_string(),
+        entries: keys
+            .iter()
+            .map(|(keys, description)| Entry {
+                keys: keys.to_string(),
+                description: description.to_string(),
+                customized: false,
+            })
+            .collect(),
+    }
+}
+
+/// Every binding of `keybindings`, an entry per action with all its keys, grouped
+/// by view in command palette order; then the keys views handle themselves.
+pub fn sections(keybindings: &KeyBindingMap, plugins: &[PluginConfig]) -> Vec<Section> {
+    let defaults = create_default_keybindings();
+    let order = commands();
+    let position = |action: &Action| {
+        order
+            .iter()
+            .position(|command| command.action == *action)
+            .unwrap_or(order.len())
+    };
+
+    let mut sections: Vec<Section> = CONTEXTS
+        .iter()
+        .filter_map(|&(context, title)| {
+            let default = defaults.bindings(context);
+            let mut grouped: Vec<(Action, Vec<String>, bool)> = Vec::new();
+            for (label, action) in keybindings.bindings(context) {
+                let customized = !default.contains(&(label.clone(), action.clone()));
+                match grouped.iter_mut().find(|(bound, _, _)| *bound == action) {
+                    Some((_, labels, any_customized)) => {
+                        labels.push(label);
+                        *any_customized |= customized;
+                    }
+                    None => grouped.push((action, vec![label], customized)),
+                }
+            }
+            grouped.sort_by_key(|(action, _, _)| position(action));
+            let entries: Vec<Entry> = grouped
+                .into_iter()
+                .map(|(action, mut labels, customized)| {
+                    labels.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+                    Entry {
+                        keys: labels.join(" / "),
+                        description: describe(&action, plugins),
+                        customized,
+                    }
+                })
+                .collect();
+            (!entries.is_empty()).then(|| Section {
+                title: title.to_string(),
+                entries,
+            })
+        })
+        .collect();
+    sections.push(fixed_section("Other keys", &OTHER_KEYS));
+    sections.push(fixed_section("Theme editor", &THEME_EDITOR_KEYS));
+    sections.push(fixed_section("In this help", &HELP_KEYS));
+    sections
+}
+
+/// `sections` laid out in pages of `rows` rows. A section starts a new page when
+/// its heading and first entry do not fit, and one longer than a page goes on
+/// over the next with "(cont.)" after its heading.
+pub fn paginate(sections: &[Section], rows: usize) -> Vec<Vec<Row>> {
+    let rows = rows.max(2);
+    let mut pages = vec![Vec::new()];
+    for section in sections {
+        let page = pages.last_mut().expect("at least one page");
+        let gap = usize::from(!page.is_empty());
+        if page.len() + gap + 2 > rows {
+            pages.push(Vec::new());
+        } else if gap == 1 {
+            page.push(Row::Blank);
+        }
+        pages
+            .last_mut()
+            .expect("at least one page")
+            .push(Row::Heading(section.title.clone()));
+        for entry in &section.entries {
+            if pages.last().is_some_and(|page| page.len() >= rows) {
+                pages.push(vec![Row::Heading(format!("{} (cont.)", section.title))]);
+            }
+            pages
+                .last_mut()
+                .expect("at least one page")
+                .push(Row::Entry(entry.clone()));
+        }
+    }
+    pages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::ui::keybindings::parse_key_str;
+
+    #[test]
+    fn lists_bindings_by_view_and_marks_custom_keys() {
+        let mut keybindings = create_default_keybindings();
+        keybindings.add_binding(
+            KeyBindingContext::Global,
+            parse_key_str("Ctrl+s").unwrap(),
+            Action::SortByScore,
+        );
+        let sections = sections(&keybindings, &[]);
+        assert_eq!(sections[0].title, "Global");
+        assert!(
+            sections[0]
+                .entries
+                .iter()
+                .any(|entry| entry.keys == ":" && entry.description == "Command palette")
+        );
+
+        let global = &sections[0];
+        let sort = global
+            .entries
+            .iter()
+            .find(|entry| entry.description == "Sort by score")
+            .unwrap();
+        assert_eq!(sort.keys, "S / Ctrl+s");
+        assert!(sort.customized);
+        assert!(
+            global
+                .entries
+                .iter()
+                .filter(|entry| entry.description != "Sort by score")
+                .all(|entry| !entry.customized)
+        );
+        // View sections only list what they bind differently from the global keys
+        assert!(sections.iter().skip(1).all(|section| {
+            section
+                .entries
+                .iter()
+                .all(|entry| entry.description != "Sort by score")
+        }));
+    }
+
+    #[test]
+    fn pages_fit_and_split_long_sections() {
+        let section = |title: &str, count: usize| Section {
+            title: title.to_string(),
+            entries: (0..count)
+                .map(|i| Entry {
+                    keys: i.to_string(),
+                    description: String::new(),
+                    customized: false,
+                })
+                .collect(),
+        };
+        let pages = paginate(&[section("A", 2), section("B", 1), section("C", 6)], 6);
+        assert!(pages.iter().all(|page| page.len() <= 6));
+        // A, blank, B fill the first page; C does not fit after them
+        assert_eq!(pages[0].len(), 6);
+        assert_eq!(pages[1][0], Row::Heading("C".to_string()));
+        assert_eq!(pages[2][0], Row::Heading("C (cont.)".to_string()));
+        let entries: usize = pages
+            .iter()
+            .flatten()
+            .filter(|row| matches!(row, Row::Entry(_)))
+            .count();
+        assert_eq!(entries, 9);
+    }
+}
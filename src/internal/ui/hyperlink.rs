@@ -0,0 +1,93 @@
+//! OSC 8 hyperlinks, which make text clickable in terminals that support them.
+//!
+//! ratatui measures a cell by the width of its whole symbol, escape sequence
+//! included, so a link is written as one escape sequence per pair of cells: the
+//! pair's first cell holds both characters and the second is skipped when drawn.
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+
+/// Terminals known to handle OSC 8, judged from the environment they set.
+pub fn supported_by(var: impl Fn(&str) -> Option<String>) -> bool {
+    let term_program = var("TERM_PROGRAM").unwrap_or_default();
+    let term = var("TERM").unwrap_or_default();
+    let vte = var("VTE_VERSION").and_then(|v| v.parse::<u32>().ok());
+    matches!(
+        term_program.as_str(),
+        "iTerm.app" | "WezTerm" | "vscode" | "ghostty" | "Hyper" | "rio"
+    ) || ["kitty", "foot", "alacritty", "wezterm", "ghostty"]
+        .iter()
+        .any(|name| term.contains(name))
+        || vte.is_some_and(|v| v >= 5000)
+        || var("WT_SESSION").is_some()
+        || var("KITTY_WINDOW_ID").is_some()
+}
+
+pub fn supported() -> bool {
+    supported_by(|name| std::env::var(name).ok())
+}
+
+/// Turn the text already drawn in the one-row `area` into a link to `url`. Rows
+/// with wide characters are left as they are, and so is the last cell of an area
+/// of odd width.
+pub fn apply(buf: &mut Buffer, area: Rect, url: &str) {
+    let area = area.intersection(buf.area);
+    if area.is_empty() || url.chars().any(char::is_control) {
+        return;
+    }
+    let y = area.y;
+    let narrow = (area.left()..area.right())
+        .all(|x| buf[(x, y)].symbol().chars().count() == 1 && buf[(x, y)].symbol().is_ascii());
+    if !narrow {
+        return;
+    }
+    let mut x = area.left();
+    while x + 1 < area.right() {
+        let pair = format!("{}{}", buf[(x, y)].symbol(), buf[(x + 1, y)].symbol());
+        buf[(x, y)].set_symbol(&format!("\x1B]8;;{}\x1B\\{}\x1B]8;;\x1B\\", url, pair));
+        buf[(x + 1, y)].set_skip(true);
+        x += 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_cells_in_pairs() {
+        let mut buf = Buffer::with_lines(["URL: https://a.io x"]);
+        // 13 cells: the URL and the space after it
+        apply(&mut buf, Rect::new(5, 0, 13, 1), "https://a.io");
+        assert_eq!(
+            buf[(5, 0)].symbol(),
+            "\x1B]8;;https://a.io\x1B\\ht\x1B]8;;\x1B\\"
+        );
+        assert!(buf[(6, 0)].skip);
+        // The odd last cell and the text around the link are untouched
+        assert_eq!(buf[(17, 0)].symbol(), " ");
+        assert!(!buf[(17, 0)].skip);
+        assert_eq!(buf[(4, 0)].symbol(), " ");
+
+        // Every cell of the line is still drawn exactly once
+        let drawn: String = Buffer::filled(buf.area, ratatui::buffer::Cell::new("#"))
+            .diff(&buf)
+            .iter()
+            .map(|(_, _, cell)| cell.symbol().replace("\x1B]8;;https://a.io\x1B\\", ""))
+            .map(|s| s.replace("\x1B]8;;\x1B\\", ""))
+            .collect();
+        assert_eq!(drawn, "URL: https://a.io x");
+
+        let env = |pairs: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                pairs
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert!(supported_by(env(&[("TERM_PROGRAM", "WezTerm")])));
+        assert!(supported_by(env(&[("VTE_VERSION", "7200")])));
+        assert!(!supported_by(env(&[("TERM", "xterm-256color")])));
+    }
+}
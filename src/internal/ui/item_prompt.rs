@@ -0,0 +1,148 @@
+//! Prompt to jump straight to a story: its HN item id, its news.ycombinator.com
+//! link, or the URL of the article it submitted, looked up on HN search.
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use super::text_input::TextInput;
+
+/// What the text typed in the prompt points at.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Permalink {
+    Item(u32),
+    /// An article whose HN submission is searched for
+    Url(String),
+}
+
+/// `12345`, `https://news.ycombinator.com/item?id=12345`, or any other link, with
+/// or without its scheme. None for text that is neither.
+pub fn parse_permalink(input: &str) -> Option<Permalink> {
+    let input = input.trim();
+    if let Ok(id) = input.parse() {
+        return Some(Permalink::Item(id));
+    }
+    if input.is_empty() || input.contains(char::is_whitespace) {
+        return None;
+    }
+    let rest = input
+        .strip_prefix("https://")
+        .or_else(|| input.strip_prefix("http://"))
+        .unwrap_or(input);
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+    if host
+        .trim_start_matches("www.")
+        .eq_ignore_ascii_case("news.ycombinator.com")
+    {
+        let (_, query) = path.split_once('?')?;
+        return query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("id="))
+            .and_then(|id| id.parse().ok())
+            .map(Permalink::Item);
+    }
+    if !host.contains('.') {
+        return None;
+    }
+    match rest.len() == input.len() {
+        true => Some(Permalink::Url(format!("https://{}", input))),
+        false => Some(Permalink::Url(input.to_string())),
+    }
+}
+
+/// What the prompt asks the app to do after a key press.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemOutcome {
+    Pending,
+    Cancel,
+    Open(Permalink),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ItemPrompt {
+    pub input: TextInput,
+    /// Why the text last submitted could not be opened
+    pub error: Option<String>,
+}
+
+impl ItemPrompt {
+    /// Enter opens what the text points at; text that points nowhere keeps the
+    /// prompt open with an error until it is edited.
+    pub fn handle_key(&mut self, key: KeyEvent) -> ItemOutcome {
+        match key.code {
+            KeyCode::Esc => ItemOutcome::Cancel,
+            KeyCode::Enter => match parse_permalink(self.input.text()) {
+                Some(permalink) => ItemOutcome::Open(permalink),
+                None => {
+                    self.error = Some("Not an item id, HN link or URL".to_string());
+                    ItemOutcome::Pending
+                }
+            },
+            _ => {
+                if self.input.handle_key(key) {
+                    self.error = None;
+                }
+                ItemOutcome::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    #[test]
+    fn parses_ids_hn_links_and_urls() {
+        assert_eq!(parse_permalink(" 8863 "), Some(Permalink::Item(8863)));
+        for link in [
+            "https://news.ycombinator.com/item?id=8863",
+            "news.ycombinator.com/item?id=8863&p=2",
+            "http://www.news.ycombinator.com/item?p=2&id=8863",
+        ] {
+            assert_eq!(
+                parse_permalink(link),
+                Some(Permalink::Item(8863)),
+                "{}",
+                link
+            );
+        }
+        assert_eq!(
+            parse_permalink("http://example.com/post"),
+            Some(Permalink::Url("http://example.com/post".to_string()))
+        );
+        assert_eq!(
+            parse_permalink("example.com/post"),
+            Some(Permalink::Url("https://example.com/post".to_string()))
+        );
+        for text in [
+            "",
+            "hello world",
+            "news",
+            "https://news.ycombinator.com/news",
+        ] {
+            assert_eq!(parse_permalink(text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn opens_valid_text_and_flags_the_rest() {
+        let mut prompt = ItemPrompt::default();
+        let mut press = |code| prompt.handle_key(KeyEvent::new(code, KeyModifiers::empty()));
+        press(KeyCode::Char('x'));
+        assert_eq!(press(KeyCode::Enter), ItemOutcome::Pending);
+        press(KeyCode::Backspace);
+        press(KeyCode::Char('4'));
+        press(KeyCode::Char('2'));
+        assert_eq!(
+            press(KeyCode::Enter),
+            ItemOutcome::Open(Permalink::Item(42))
+        );
+        assert_eq!(press(KeyCode::Esc), ItemOutcome::Cancel);
+
+        let mut prompt = ItemPrompt::default();
+        prompt.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert!(prompt.error.is_some());
+        prompt.handle_key(KeyEvent::new(KeyCode::Char('1'), KeyModifiers::empty()));
+        assert!(prompt.error.is_none());
+    }
+}
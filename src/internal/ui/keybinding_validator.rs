@@ -45,6 +45,7 @@ pub fn detect_conflicts(config: &KeyBindingConfig) -> Vec<ConflictReport> {
     check_context("Article View", &config.article);
     check_context("Bookmarks View", &config.bookmarks);
     check_context("History View", &config.history);
+    check_context("Reading Lists View", &config.reading_lists);
 
     conflicts
 }
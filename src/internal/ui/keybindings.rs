@@ -11,6 +11,7 @@ pub enum KeyBindingContext {
     Article,
     Bookmarks,
     History,
+    ReadingLists,
 }
 
 /// Maps key events to actions
@@ -22,6 +23,9 @@ pub struct KeyBindingMap {
     article: HashMap<KeyEvent, Action>,
     bookmarks: HashMap<KeyEvent, Action>,
     history: HashMap<KeyEvent, Action>,
+    reading_lists: HashMap<KeyEvent, Action>,
+    /// Two-key sequences such as `y c`, by context, first key and second key
+    chords: HashMap<(KeyBindingContext, KeyEvent, KeyEvent), Action>,
 }
 
 impl KeyBindingMap {
@@ -34,6 +38,8 @@ impl KeyBindingMap {
             article: HashMap::new(),
             bookmarks: HashMap::new(),
             history: HashMap::new(),
+            reading_lists: HashMap::new(),
+            chords: HashMap::new(),
         }
     }
 
@@ -48,6 +54,7 @@ impl KeyBindingMap {
             KeyBindingContext::Article => &self.article,
             KeyBindingContext::Bookmarks => &self.bookmarks,
             KeyBindingContext::History => &self.history,
+            KeyBindingContext::ReadingLists => &self.reading_lists,
         };
 
         if let Some(action) = context_map.get(key) {
@@ -67,19 +74,154 @@ impl KeyBindingMap {
             KeyBindingContext::Article => &mut self.article,
             KeyBindingContext::Bookmarks => &mut self.bookmarks,
             KeyBindingContext::History => &mut self.history,
+            KeyBindingContext::ReadingLists => &mut self.reading_lists,
         };
         map.insert(key, action);
     }
 
+    /// Add a two-key chord for a specific context
+    pub fn add_chord(
+        &mut self,
+        context: KeyBindingContext,
+        first: KeyEvent,
+        second: KeyEvent,
+        action: Action,
+    ) {
+        self.chords.insert((context, first, second), action);
+    }
+
+    /// Whether `key` starts a chord in `context` (or globally). A prefix key waits for
+    /// the second key; its single-key binding runs only if none follows in time.
+    pub fn is_chord_prefix(&self, key: &KeyEvent, context: KeyBindingContext) -> bool {
+        self.chords.keys().any(|(ctx, first, _)| {
+            first == key && (*ctx == context || *ctx == KeyBindingContext::Global)
+        })
+    }
+
+    /// Action for the chord `first second`, checking the context before global chords
+    pub fn get_chord_action(
+        &self,
+        first: &KeyEvent,
+        second: &KeyEvent,
+        context: KeyBindingContext,
+    ) -> Option<Action> {
+        self.chords
+            .get(&(context, *first, *second))
+            .or_else(|| {
+                self.chords
+                    .get(&(KeyBindingContext::Global, *first, *second))
+            })
+            .cloned()
+    }
+
+    /// Chords that can complete after `first`, as `(second key, action)` sorted by key
+    pub fn chords_after(
+        &self,
+        first: &KeyEvent,
+        context: KeyBindingContext,
+    ) -> Vec<(KeyEvent, Action)> {
+        let mut chords: Vec<_> = self
+            .chords
+            .keys()
+            .filter(|(ctx, key, _)| {
+                key == first && (*ctx == context || *ctx == KeyBindingContext::Global)
+            })
+            .filter_map(|(_, _, second)| {
+                self.get_chord_action(first, second, context)
+                    .map(|action| (*second, action))
+            })
+            .collect();
+        chords.sort_by_key(|(second, _)| key_label(second));
+        chords.dedup_by_key(|(second, _)| *second);
+        chords
+    }
+
+    /// Labels of the keys and chords that run `action` in `context`, shortest first;
+    /// global keys that the context binds to something else are left out.
+    pub fn keys_for(&self, action: &Action, context: KeyBindingContext) -> Vec<String> {
+        let context_map = match context {
+            KeyBindingContext::Global => &self.global,
+            KeyBindingContext::List => &self.list,
+            KeyBindingContext::StoryDetail => &self.story_detail,
+            KeyBindingContext::Article => &self.article,
+            KeyBindingContext::Bookmarks => &self.bookmarks,
+            KeyBindingContext::History => &self.history,
+            KeyBindingContext::ReadingLists => &self.reading_lists,
+        };
+        let keys = context_map
+            .iter()
+            .chain(&self.global)
+            .filter(|(key, bound)| {
+                *bound == action && self.get_action(key, context).as_ref() == Some(action)
+            })
+            .map(|(key, _)| key_label(key));
+        let chords = self
+            .chords
+            .iter()
+            .filter(|((ctx, first, second), bound)| {
+                (*ctx == context || *ctx == KeyBindingContext::Global)
+                    && *bound == action
+                    && self.get_chord_action(first, second, context).as_ref() == Some(action)
+            })
+            .map(|((_, first, second), _)| format!("{} {}", key_label(first), key_label(second)));
+        let mut labels: Vec<String> = keys.chain(chords).collect();
+        labels.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        labels.dedup();
+        labels
+    }
+
+    /// Keys and chords bound in `context` itself, as `(label, action)` sorted by
+    /// label; for a view context, those that only repeat a global binding are left
+    /// out.
+    pub fn bindings(&self, context: KeyBindingContext) -> Vec<(String, Action)> {
+        let context_map = match context {
+            KeyBindingContext::Global => &self.global,
+            KeyBindingContext::List => &self.list,
+            KeyBindingContext::StoryDetail => &self.story_detail,
+            KeyBindingContext::Article => &self.article,
+            KeyBindingContext::Bookmarks => &self.bookmarks,
+            KeyBindingContext::History => &self.history,
+            KeyBindingContext::ReadingLists => &self.reading_lists,
+        };
+        let global = context == KeyBindingContext::Global;
+        let keys = context_map
+            .iter()
+            .filter(|(key, action)| global || self.global.get(*key) != Some(*action))
+            .map(|(key, action)| (key_label(key), action.clone()));
+        let chords = self
+            .chords
+            .iter()
+            .filter(|((ctx, first, second), action)| {
+                *ctx == context
+                    && (global
+                        || self
+                            .chords
+                            .get(&(KeyBindingContext::Global, *first, *second))
+                            != Some(*action))
+            })
+            .map(|((_, first, second), action)| {
+                (
+                    format!("{} {}", key_label(first), key_label(second)),
+                    action.clone(),
+                )
+            });
+        let mut bindings: Vec<_> = keys.chain(chords).collect();
+        bindings.sort_by(|a, b| a.0.cmp(&b.0));
+        bindings
+    }
+
     /// Merge custom keybindings from configuration
     pub fn merge_config(&mut self, config: &crate::config::KeyBindingConfig) {
         let mut merge = |ctx: KeyBindingContext, bindings: &HashMap<String, Action>| {
             for (key_str, action) in bindings {
-                match parse_key_str(key_str) {
-                    Some(key_event) => {
+                match (parse_chord_str(key_str), parse_key_str(key_str)) {
+                    (Some((first, second)), _) => {
+                        self.add_chord(ctx, first, second, action.clone());
+                    }
+                    (None, Some(key_event)) => {
                         self.add_binding(ctx, key_event, action.clone());
                     }
-                    None => {
+                    (None, None) => {
                         tracing::warn!("Invalid key string in config: {}", key_str);
                     }
                 }
@@ -92,6 +234,7 @@ impl KeyBindingMap {
         merge(KeyBindingContext::Article, &config.article);
         merge(KeyBindingContext::Bookmarks, &config.bookmarks);
         merge(KeyBindingContext::History, &config.history);
+        merge(KeyBindingContext::ReadingLists, &config.reading_lists);
     }
 
     /// Detect conflicts within a single context
@@ -105,6 +248,7 @@ impl KeyBindingMap {
             KeyBindingContext::Article => &self.article,
             KeyBindingContext::Bookmarks => &self.bookmarks,
             KeyBindingContext::History => &self.history,
+            KeyBindingContext::ReadingLists => &self.reading_lists,
         };
 
         // In our implementation, HashMap prevents conflicts by design
@@ -169,6 +313,35 @@ pub fn parse_key_str(key_str: &str) -> Option<KeyEvent> {
     Some(KeyEvent::new(code, modifiers))
 }
 
+/// Parse a two-key chord written as two keys separated by a space, e.g. "y c" or "g Ctrl+t"
+pub fn parse_chord_str(chord_str: &str) -> Option<(KeyEvent, KeyEvent)> {
+    match chord_str.split_whitespace().collect::<Vec<_>>().as_slice() {
+        [first, second] => Some((parse_key_str(first)?, parse_key_str(second)?)),
+        _ => None,
+    }
+}
+
+/// Format a key the way `parse_key_str` reads it, e.g. "Ctrl+w" or "Enter"
+pub fn key_label(key: &KeyEvent) -> String {
+    let mut label = String::new();
+    for (modifier, name) in [
+        (KeyModifiers::CONTROL, "Ctrl+"),
+        (KeyModifiers::ALT, "Alt+"),
+        (KeyModifiers::SHIFT, "Shift+"),
+    ] {
+        if key.modifiers.contains(modifier) {
+            label.push_str(name);
+        }
+    }
+    match key.code {
+        KeyCode::Char(' ') => label.push_str("Space"),
+        KeyCode::Char(c) => label.push(c),
+        KeyCode::F(n) => label.push_str(&format!("F{}", n)),
+        code => label.push_str(&format!("{:?}", code)),
+    }
+    label
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -193,6 +366,47 @@ mod tests {
         assert!(key.modifiers.contains(KeyModifiers::CONTROL));
     }
 
+    #[test]
+    fn test_chords() {
+        let mut map = KeyBindingMap::new();
+        let (y, c) = (parse_key_str("y").unwrap(), parse_key_str("c").unwrap());
+        assert_eq!(parse_chord_str("y c"), Some((y, c)));
+        assert_eq!(parse_chord_str("y"), None);
+        assert_eq!(parse_chord_str("y Nope"), None);
+
+        map.add_binding(KeyBindingContext::Global, y, Action::CopyLink);
+        map.add_chord(KeyBindingContext::List, y, c, Action::CopyCommentsLink);
+        map.add_chord(KeyBindingContext::Global, y, y, Action::CopyLink);
+
+        assert!(map.is_chord_prefix(&y, KeyBindingContext::List));
+        assert!(map.is_chord_prefix(&y, KeyBindingContext::Article));
+        assert!(!map.is_chord_prefix(&c, KeyBindingContext::List));
+        assert_eq!(
+            map.get_chord_action(&y, &c, KeyBindingContext::List),
+            Some(Action::CopyCommentsLink)
+        );
+        assert_eq!(
+            map.get_chord_action(&y, &c, KeyBindingContext::Article),
+            None
+        );
+        let seconds: Vec<_> = map
+            .chords_after(&y, KeyBindingContext::List)
+            .into_iter()
+            .map(|(key, _)| key_label(&key))
+            .collect();
+        assert_eq!(seconds, vec!["c", "y"]);
+        assert_eq!(key_label(&parse_key_str("Ctrl+w").unwrap()), "Ctrl+w");
+
+        assert_eq!(
+            map.keys_for(&Action::CopyLink, KeyBindingContext::List),
+            vec!["y", "y y"]
+        );
+        assert_eq!(
+            map.keys_for(&Action::CopyCommentsLink, KeyBindingContext::Article),
+            Vec::<String>::new()
+        );
+    }
+
     #[test]
     fn test_keybinding_map_global_fallback() {
         let mut map = KeyBindingMap::new();
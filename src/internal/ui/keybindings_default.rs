@@ -16,6 +16,7 @@ pub fn create_default_keybindings() -> KeyBindingMap {
     add_article_bindings(&mut map);
     add_bookmarks_bindings(&mut map);
     add_history_bindings(&mut map);
+    add_reading_lists_bindings(&mut map);
 
     map
 }
@@ -26,6 +27,19 @@ fn add_global_bindings(map: &mut KeyBindingMap) {
     // Help
     map.add_binding(ctx, key('?'), Action::ToggleHelp);
 
+    // Settings panel
+    map.add_binding(ctx, key(','), Action::OpenSettings);
+
+    // Command palette
+    map.add_binding(ctx, key(':'), Action::OpenCommandPalette);
+
+    // Relative / local / ISO 8601 timestamps
+    map.add_binding(
+        ctx,
+        KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL),
+        Action::CycleTimeFormat,
+    );
+
     // Quit / Back
     map.add_binding(ctx, key('q'), Action::Back);
     map.add_binding(ctx, key_code(KeyCode::Esc), Action::Back);
@@ -39,8 +53,44 @@ fn add_global_bindings(map: &mut KeyBindingMap) {
     // Selection
     map.add_binding(ctx, key_code(KeyCode::Enter), Action::Enter);
 
+    // Split layout focus
+    map.add_binding(
+        ctx,
+        KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL),
+        Action::FocusNextPane,
+    );
+
+    // Cache statistics overlay
+    map.add_binding(
+        ctx,
+        KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL),
+        Action::ToggleCacheStats,
+    );
+
+    // Performance HUD
+    map.add_binding(
+        ctx,
+        KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL),
+        Action::TogglePerfHud,
+    );
+
+    // Jump to a story by item id, HN link or article URL
+    map.add_binding(
+        ctx,
+        KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL),
+        Action::OpenItemPrompt,
+    );
+
     // Browser
     map.add_binding(ctx, key('o'), Action::OpenBrowser);
+    map.add_binding(ctx, key('O'), Action::OpenHnPage);
+
+    // Sharing
+    map.add_binding(ctx, key('y'), Action::CopyLink);
+
+    // HN account actions (require login)
+    map.add_binding(ctx, key('v'), Action::Upvote);
+    map.add_binding(ctx, key('f'), Action::Favorite);
 
     // Story categories
     map.add_binding(ctx, key('1'), Action::LoadStories(StoryListType::Top));
@@ -50,11 +100,21 @@ fn add_global_bindings(map: &mut KeyBindingMap) {
     map.add_binding(ctx, key('5'), Action::LoadStories(StoryListType::Show));
     map.add_binding(ctx, key('6'), Action::LoadStories(StoryListType::Job));
 
+    // Quick-action chords; a lone `y` or `o` still copies/opens after the chord timeout
+    map.add_chord(ctx, key('y'), key('y'), Action::CopyLink);
+    map.add_chord(ctx, key('y'), key('c'), Action::CopyCommentsLink);
+    map.add_chord(ctx, key('y'), key('p'), Action::SendToPhone);
+    map.add_chord(ctx, key('o'), key('b'), Action::OpenBrowser);
+    map.add_chord(ctx, key('o'), key('r'), Action::OpenInReader);
+    map.add_chord(ctx, key('o'), key('p'), Action::OpenInPager);
+    map.add_chord(ctx, key('o'), key('e'), Action::OpenInEditor);
+
     // Sorting
     map.add_binding(ctx, key('S'), Action::SortByScore);
     map.add_binding(ctx, key('C'), Action::SortByComments);
     map.add_binding(ctx, key('T'), Action::SortByTime);
-    map.add_binding(ctx, key('O'), Action::ToggleSortOrder);
+    map.add_binding(ctx, key('K'), Action::SortByRank);
+    map.add_binding(ctx, key('D'), Action::ToggleSortOrder);
 
     // Theme
     map.add_binding(ctx, key('t'), Action::SwitchTheme);
@@ -62,9 +122,32 @@ fn add_global_bindings(map: &mut KeyBindingMap) {
     // Bookmarks
     map.add_binding(ctx, key('b'), Action::ToggleBookmark);
     map.add_binding(ctx, key('B'), Action::ViewBookmarks);
+    map.add_binding(ctx, key('P'), Action::OpenRandomBookmark);
 
     // History
     map.add_binding(ctx, key('H'), Action::ViewHistory);
+
+    // Reading lists
+    map.add_binding(ctx, key('a'), Action::AddToReadingList);
+    map.add_binding(ctx, key('V'), Action::ViewReadingLists);
+
+    // Notification center
+    map.add_binding(ctx, key('N'), Action::ToggleNotificationCenter);
+
+    // Read later: background downloads for offline reading
+    map.add_binding(ctx, key('R'), Action::QueueReadLater);
+    map.add_binding(
+        ctx,
+        KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL),
+        Action::ViewReadLater,
+    );
+
+    // Full-text search of what was read
+    map.add_binding(
+        ctx,
+        KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL),
+        Action::SearchReading,
+    );
 }
 
 fn add_list_bindings(map: &mut KeyBindingMap) {
@@ -78,6 +161,27 @@ fn add_list_bindings(map: &mut KeyBindingMap) {
     map.add_binding(ctx, key('m'), Action::LoadMoreStories);
     map.add_binding(ctx, key('A'), Action::LoadAllStories);
 
+    // Read state
+    map.add_binding(ctx, key('h'), Action::ToggleHideRead);
+    map.add_binding(ctx, key('M'), Action::MarkAllRead);
+
+    // Focus filters, remembered per list
+    map.add_chord(ctx, key('z'), key('j'), Action::ToggleHideJobs);
+    map.add_chord(ctx, key('z'), key('s'), Action::ToggleHideSeen);
+
+    // Write the displayed list to CSV/TSV
+    map.add_binding(ctx, key('W'), Action::ExportStoryList);
+
+    // Build a custom list from ids/URLs on the clipboard
+    map.add_binding(ctx, key('I'), Action::ImportIdList);
+
+    // Score, age, domain and author filters
+    map.add_binding(ctx, key('F'), Action::OpenFilterPanel);
+
+    // Mute the story's domain or author, and reveal muted content
+    map.add_binding(ctx, key('x'), Action::MuteStory);
+    map.add_binding(ctx, key('X'), Action::ToggleShowMuted);
+
     // Toggle search mode is handled differently as it changes InputMode
     // Not included here as it's a special case in handle_input
 }
@@ -90,6 +194,55 @@ fn add_story_detail_bindings(map: &mut KeyBindingMap) {
 
     // Load more comments
     map.add_binding(ctx, key('n'), Action::LoadMoreComments);
+
+    // Group back-and-forth replies in the thread at the top of the pane
+    map.add_binding(ctx, key('c'), Action::ToggleConversationView);
+
+    // Next / previous story of the list
+    map.add_binding(
+        ctx,
+        KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL),
+        Action::NextStory,
+    );
+    map.add_binding(
+        ctx,
+        KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL),
+        Action::PrevStory,
+    );
+
+    // Numbered links of the story and its comments
+    map.add_binding(
+        ctx,
+        KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL),
+        Action::OpenLinkPicker,
+    );
+
+    // Snapshot the thread, and later show what changed since
+    map.add_binding(ctx, key('s'), Action::SnapshotThread);
+    map.add_binding(ctx, key('d'), Action::ToggleThreadDiff);
+
+    // Reveal comments by muted users
+    map.add_binding(ctx, key('X'), Action::ToggleShowMuted);
+
+    // Write story, article and comments to a file
+    map.add_binding(ctx, key('W'), Action::ExportStory);
+
+    add_scroll_bindings(map, ctx);
+}
+
+/// Half-page, page and jump keys of the scrolling panes.
+fn add_scroll_bindings(map: &mut KeyBindingMap, ctx: KeyBindingContext) {
+    let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+    map.add_binding(ctx, ctrl('d'), Action::ScrollHalfPageDown);
+    map.add_binding(ctx, ctrl('u'), Action::ScrollHalfPageUp);
+    map.add_binding(ctx, ctrl('f'), Action::ScrollPageDown);
+    map.add_binding(ctx, ctrl('b'), Action::ScrollPageUp);
+    map.add_binding(ctx, key_code(KeyCode::PageDown), Action::ScrollPageDown);
+    map.add_binding(ctx, key_code(KeyCode::PageUp), Action::ScrollPageUp);
+    map.add_binding(ctx, key('g'), Action::ScrollToTop);
+    map.add_binding(ctx, key('G'), Action::ScrollToBottom);
+    map.add_binding(ctx, key_code(KeyCode::Home), Action::ScrollToTop);
+    map.add_binding(ctx, key_code(KeyCode::End), Action::ScrollToBottom);
 }
 
 fn add_article_bindings(map: &mut KeyBindingMap) {
@@ -103,20 +256,62 @@ fn add_article_bindings(map: &mut KeyBindingMap) {
 
     // Tab to toggle back to comments
     map.add_binding(ctx, key_code(KeyCode::Tab), Action::ToggleArticleView);
+
+    // Next / previous story, staying in the Article view
+    map.add_binding(
+        ctx,
+        KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL),
+        Action::NextStory,
+    );
+    map.add_binding(
+        ctx,
+        KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL),
+        Action::PrevStory,
+    );
+
+    // Numbered links of the article
+    map.add_binding(
+        ctx,
+        KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL),
+        Action::OpenLinkPicker,
+    );
+
+    map.add_binding(ctx, key('W'), Action::ExportStory);
+
+    add_scroll_bindings(map, ctx);
 }
 
-fn add_bookmarks_bindings(_map: &mut KeyBindingMap) {
-    let _ctx = KeyBindingContext::Bookmarks;
+fn add_bookmarks_bindings(map: &mut KeyBindingMap) {
+    let ctx = KeyBindingContext::Bookmarks;
 
-    // Bookmarks view uses mostly global bindings
-    // No specific overrides needed currently
+    // Tag the focused bookmark and filter the view by tag
+    map.add_binding(ctx, key('#'), Action::EditBookmarkTags);
+    map.add_binding(ctx, key(']'), Action::NextBookmarkTag);
+    map.add_binding(ctx, key('['), Action::PrevBookmarkTag);
+    map.add_binding(ctx, key('e'), Action::EditBookmarkNote);
 }
 
 fn add_history_bindings(map: &mut KeyBindingMap) {
     let ctx = KeyBindingContext::History;
 
-    // Clear history
+    // Clear history, or remove the focused entry
     map.add_binding(ctx, key('X'), Action::ClearHistory);
+    map.add_binding(ctx, key('d'), Action::DeleteHistoryEntry);
+}
+
+fn add_reading_lists_bindings(map: &mut KeyBindingMap) {
+    let ctx = KeyBindingContext::ReadingLists;
+
+    // Reorder, remove and tick off queued stories
+    map.add_binding(ctx, key('K'), Action::MoveItemUp);
+    map.add_binding(ctx, key('J'), Action::MoveItemDown);
+    map.add_binding(ctx, key('x'), Action::RemoveFromReadingList);
+    map.add_binding(ctx, key(' '), Action::ToggleItemDone);
+
+    // Switch between lists
+    map.add_binding(ctx, key(']'), Action::NextReadingList);
+    map.add_binding(ctx, key_code(KeyCode::Tab), Action::NextReadingList);
+    map.add_binding(ctx, key('['), Action::PrevReadingList);
 }
 
 /// Helper to create a simple char key event
@@ -168,4 +363,25 @@ mod tests {
             Some(Action::ScrollArticleDown)
         ));
     }
+
+    #[test]
+    fn test_default_keybindings_share_in_every_view() {
+        let map = create_default_keybindings();
+        for ctx in [
+            KeyBindingContext::List,
+            KeyBindingContext::StoryDetail,
+            KeyBindingContext::Article,
+            KeyBindingContext::Bookmarks,
+            KeyBindingContext::History,
+        ] {
+            assert!(matches!(
+                map.get_action(&key('O'), ctx),
+                Some(Action::OpenHnPage)
+            ));
+            assert!(matches!(
+                map.get_action(&key('y'), ctx),
+                Some(Action::CopyLink)
+            ));
+        }
+    }
 }
@@ -0,0 +1,107 @@
+//! Pane layout for the main content area.
+//!
+//! With the split layout enabled, opening a story keeps the story list visible in
+//! a left pane while StoryDetail/Article renders on the right. Input is routed to
+//! whichever pane has focus (`Ctrl+w` switches).
+
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+
+use super::app::ViewMode;
+
+/// A focusable region of the main content area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Pane {
+    List,
+    #[default]
+    Detail,
+}
+
+impl Pane {
+    pub fn other(self) -> Self {
+        match self {
+            Self::List => Self::Detail,
+            Self::Detail => Self::List,
+        }
+    }
+}
+
+/// Where each pane is drawn; `None` means the pane is hidden.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaneLayout {
+    pub list: Option<Rect>,
+    pub detail: Option<Rect>,
+}
+
+/// Whether the given view shows both panes side by side.
+pub fn is_split(view_mode: ViewMode, split_enabled: bool) -> bool {
+    split_enabled && matches!(view_mode, ViewMode::StoryDetail | ViewMode::Article)
+}
+
+/// Divide `area` into panes for `view_mode`. `list_percent` is the list pane width
+/// in the split layout and is clamped to 20..=80.
+pub fn compute(
+    area: Rect,
+    view_mode: ViewMode,
+    split_enabled: bool,
+    list_percent: u16,
+) -> PaneLayout {
+    match (is_split(view_mode, split_enabled), view_mode) {
+        (true, _) => {
+            let list_percent = list_percent.clamp(20, 80);
+            let chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Percentage(list_percent),
+                    Constraint::Percentage(100 - list_percent),
+                ])
+                .split(area);
+            PaneLayout {
+                list: Some(chunks[0]),
+                detail: Some(chunks[1]),
+            }
+        }
+        (false, ViewMode::StoryDetail | ViewMode::Article) => PaneLayout {
+            list: None,
+            detail: Some(area),
+        },
+        (false, _) => PaneLayout {
+            list: Some(area),
+            detail: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AREA: Rect = Rect::new(0, 0, 100, 40);
+
+    #[test]
+    fn single_pane_without_split() {
+        let layout = compute(AREA, ViewMode::StoryDetail, false, 40);
+        assert_eq!(layout.list, None);
+        assert_eq!(layout.detail, Some(AREA));
+
+        let layout = compute(AREA, ViewMode::List, false, 40);
+        assert_eq!(layout.list, Some(AREA));
+        assert_eq!(layout.detail, None);
+    }
+
+    #[test]
+    fn split_places_list_left_of_detail() {
+        let layout = compute(AREA, ViewMode::Article, true, 40);
+        let (list, detail) = (layout.list.unwrap(), layout.detail.unwrap());
+        assert_eq!(list.width, 40);
+        assert_eq!(detail.x, list.x + list.width);
+        assert_eq!(list.width + detail.width, AREA.width);
+    }
+
+    #[test]
+    fn list_views_stay_full_width_when_split_enabled() {
+        for mode in [ViewMode::List, ViewMode::Bookmarks, ViewMode::History] {
+            assert!(!is_split(mode, true));
+            assert_eq!(compute(AREA, mode, true, 40).list, Some(AREA));
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Popup listing the links of the open article or comment thread, numbered, so one
+//! can be opened in the browser.
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::internal::models::Link;
+
+/// What the picker asks the app to do after a key press.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PickerOutcome {
+    Pending,
+    Cancel,
+    /// Open this URL
+    Open(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct LinkPicker {
+    pub links: Vec<Link>,
+    pub selected: usize,
+    /// Link number typed so far
+    pub typed: String,
+}
+
+impl LinkPicker {
+    pub fn new(links: Vec<Link>) -> Self {
+        Self {
+            links,
+            selected: 0,
+            typed: String::new(),
+        }
+    }
+
+    /// Number keys pick a link by its number. A number opens its link as soon as no
+    /// longer number could start with it; otherwise Enter does.
+    pub fn handle_key(&mut self, key: KeyEvent) -> PickerOutcome {
+        let count = self.links.len();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return PickerOutcome::Cancel,
+            KeyCode::Char('j') | KeyCode::Down if count > 0 => {
+                self.selected = (self.selected + 1) % count;
+                self.typed.clear();
+            }
+            KeyCode::Char('k') | KeyCode::Up if count > 0 => {
+                self.selected = (self.selected + count - 1) % count;
+                self.typed.clear();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                self.typed.push(c);
+                match self.typed.parse::<usize>() {
+                    Ok(number) if (1..=count).contains(&number) => {
+                        self.selected = number - 1;
+                        if number * 10 > count {
+                            return self.open();
+                        }
+                    }
+                    _ => self.typed.clear(),
+                }
+            }
+            KeyCode::Backspace => {
+                self.typed.pop();
+            }
+            KeyCode::Enter => return self.open(),
+            _ => {}
+        }
+        PickerOutcome::Pending
+    }
+
+    fn open(&self) -> PickerOutcome {
+        match self.links.get(self.selected) {
+            Some(link) => PickerOutcome::Open(link.url.clone()),
+            None => PickerOutcome::Cancel,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn press(picker: &mut LinkPicker, code: KeyCode) -> PickerOutcome {
+        picker.handle_key(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    #[test]
+    fn opens_links_by_number_or_row() {
+        let links = (1..=12)
+            .map(|i| Link {
+                text: format!("link {}", i),
+                url: format!("https://example.com/{}", i),
+            })
+            .collect();
+        let mut picker = LinkPicker::new(links);
+
+        // "3" cannot start a longer number among 12 links, so it opens at once
+        assert_eq!(
+            press(&mut picker, KeyCode::Char('3')),
+            PickerOutcome::Open("https://example.com/3".to_string())
+        );
+        // "1" could be 10, 11 or 12: it waits for the next digit or Enter
+        picker.typed.clear();
+        assert_eq!(
+            press(&mut picker, KeyCode::Char('1')),
+            PickerOutcome::Pending
+        );
+        assert_eq!(
+            press(&mut picker, KeyCode::Char('2')),
+            PickerOutcome::Open("https://example.com/12".to_string())
+        );
+        picker.typed.clear();
+        press(&mut picker, KeyCode::Char('1'));
+        assert_eq!(
+            press(&mut picker, KeyCode::Enter),
+            PickerOutcome::Open("https://example.com/1".to_string())
+        );
+
+        // Out of range numbers are dropped; j/k move between rows
+        picker.typed.clear();
+        press(&mut picker, KeyCode::Char('0'));
+        assert!(picker.typed.is_empty());
+        press(&mut picker, KeyCode::Char('k'));
+        assert_eq!(picker.selected, 11);
+        assert_eq!(press(&mut picker, KeyCode::Esc), PickerOutcome::Cancel);
+    }
+}
@@ -0,0 +1,108 @@
+//! Confirmation popup shown before a story link opens in the browser when
+//! `ui.confirm_links` is on. It shows where the link's redirects lead and warns
+//! about URL shorteners and destinations on a different site than the one shown.
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::utils::url::{extract_domain, is_url_shortener, same_site};
+
+/// What the popup asks the app to do after a key press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewOutcome {
+    Pending,
+    Cancel,
+    Open,
+}
+
+#[derive(Debug, Clone)]
+pub struct LinkPreview {
+    pub url: String,
+    /// Final URL after redirects, or why it could not be resolved; `None` while resolving
+    pub resolved: Option<Result<String, String>>,
+}
+
+impl LinkPreview {
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            resolved: None,
+        }
+    }
+
+    /// Redirects leading somewhere else than the link itself.
+    pub fn redirected_to(&self) -> Option<&str> {
+        match &self.resolved {
+            Some(Ok(target)) if target.trim_end_matches('/') != self.url.trim_end_matches('/') => {
+                Some(target)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let domain = extract_domain(&self.url);
+        if let Some(domain) = domain.as_deref().filter(|d| is_url_shortener(d)) {
+            warnings.push(format!("{} is a URL shortener", domain));
+        }
+        if let Some(target) = self.redirected_to() {
+            if let (Some(from), Some(to)) = (domain.as_deref(), extract_domain(target))
+                && !same_site(from, &to)
+            {
+                warnings.push(format!("Leads to {}, not {}", to, from));
+            }
+            if self.url.starts_with("https://") && target.starts_with("http://") {
+                warnings.push("Redirects to an unencrypted http:// page".to_string());
+            }
+        }
+        warnings
+    }
+
+    pub fn handle_key(&self, key: KeyEvent) -> PreviewOutcome {
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('o') | KeyCode::Char('y') => PreviewOutcome::Open,
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('n') => PreviewOutcome::Cancel,
+            _ => PreviewOutcome::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolved(url: &str, target: &str) -> LinkPreview {
+        LinkPreview {
+            url: url.to_string(),
+            resolved: Some(Ok(target.to_string())),
+        }
+    }
+
+    #[test]
+    fn flags_shorteners_and_other_sites() {
+        let preview = resolved("https://bit.ly/abc", "https://evil.example.net/login");
+        assert_eq!(
+            preview.warnings(),
+            vec![
+                "bit.ly is a URL shortener".to_string(),
+                "Leads to evil.example.net, not bit.ly".to_string()
+            ]
+        );
+
+        let same = resolved("https://example.com/a", "https://www.example.com/a/");
+        assert!(same.warnings().is_empty());
+
+        let downgrade = resolved("https://example.com/a", "http://example.com/b");
+        assert_eq!(
+            downgrade.warnings(),
+            vec!["Redirects to an unencrypted http:// page".to_string()]
+        );
+
+        // Nothing to compare with until the link is resolved
+        assert!(
+            LinkPreview::new("https://example.com".to_string())
+                .warnings()
+                .is_empty()
+        );
+    }
+}
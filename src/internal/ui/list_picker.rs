@@ -0,0 +1,138 @@
+//! Popup for choosing which reading list a story is added to.
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::internal::models::Story;
+
+/// What the picker asks the app to do after a key press.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PickerOutcome {
+    /// Keep the picker open
+    Pending,
+    Cancel,
+    /// Add the story to the existing list at this index
+    Pick(usize),
+    /// Create a list with this name and add the story to it
+    Create(String),
+}
+
+/// State of the "add to reading list" popup. Rows are the existing lists followed
+/// by a "New list" row that switches to name entry.
+#[derive(Debug, Clone)]
+pub struct ListPicker {
+    pub story: Story,
+    pub selected: usize,
+    /// Name being typed for a new list
+    pub new_name: Option<String>,
+}
+
+impl ListPicker {
+    pub fn new(story: Story) -> Self {
+        Self {
+            story,
+            selected: 0,
+            new_name: None,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, list_count: usize) -> PickerOutcome {
+        if let Some(name) = &mut self.new_name {
+            match key.code {
+                KeyCode::Esc => self.new_name = None,
+                KeyCode::Enter if !name.trim().is_empty() => {
+                    return PickerOutcome::Create(name.trim().to_string());
+                }
+                KeyCode::Backspace => {
+                    name.pop();
+                }
+                KeyCode::Char(c) => name.push(c),
+                _ => {}
+            }
+            return PickerOutcome::Pending;
+        }
+
+        // One row per list plus the "New list" row
+        let rows = list_count + 1;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => PickerOutcome::Cancel,
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected = (self.selected + 1) % rows;
+                PickerOutcome::Pending
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = (self.selected + rows - 1) % rows;
+                PickerOutcome::Pending
+            }
+            KeyCode::Char(c @ '1'..='9') => match c.to_digit(10).map(|d| d as usize - 1) {
+                Some(idx) if idx < list_count => PickerOutcome::Pick(idx),
+                _ => PickerOutcome::Pending,
+            },
+            KeyCode::Char('n') => self.start_new_list(list_count),
+            KeyCode::Enter if self.selected < list_count => PickerOutcome::Pick(self.selected),
+            KeyCode::Enter => self.start_new_list(list_count),
+            _ => PickerOutcome::Pending,
+        }
+    }
+
+    fn start_new_list(&mut self, list_count: usize) -> PickerOutcome {
+        // Suggest the default name for the very first list
+        self.new_name = Some(match list_count {
+            0 => crate::internal::reading_lists::DEFAULT_LIST_NAME.to_string(),
+            _ => String::new(),
+        });
+        PickerOutcome::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn press(picker: &mut ListPicker, code: KeyCode, lists: usize) -> PickerOutcome {
+        picker.handle_key(KeyEvent::new(code, KeyModifiers::empty()), lists)
+    }
+
+    #[test]
+    fn picks_existing_list_by_row_or_number() {
+        let mut picker = ListPicker::new(Story::default());
+        assert_eq!(
+            press(&mut picker, KeyCode::Char('j'), 2),
+            PickerOutcome::Pending
+        );
+        assert_eq!(
+            press(&mut picker, KeyCode::Enter, 2),
+            PickerOutcome::Pick(1)
+        );
+        assert_eq!(
+            press(&mut picker, KeyCode::Char('1'), 2),
+            PickerOutcome::Pick(0)
+        );
+        assert_eq!(
+            press(&mut picker, KeyCode::Char('3'), 2),
+            PickerOutcome::Pending
+        );
+    }
+
+    #[test]
+    fn new_list_row_collects_a_name() {
+        let mut picker = ListPicker::new(Story::default());
+        // With no lists the only row is "New list", prefilled with the default name
+        press(&mut picker, KeyCode::Enter, 0);
+        assert_eq!(picker.new_name.as_deref(), Some("Reading"));
+        for _ in 0.."Reading".len() {
+            press(&mut picker, KeyCode::Backspace, 0);
+        }
+        assert_eq!(
+            press(&mut picker, KeyCode::Enter, 0),
+            PickerOutcome::Pending
+        );
+        for c in "Later".chars() {
+            press(&mut picker, KeyCode::Char(c), 0);
+        }
+        assert_eq!(
+            press(&mut picker, KeyCode::Enter, 0),
+            PickerOutcome::Create("Later".to_string())
+        );
+    }
+}
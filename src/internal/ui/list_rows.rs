@@ -0,0 +1,173 @@
+//! Virtualized story list rows: only the visible window (plus overscan) is built,
+//! and formatted rows are cached across frames.
+
+use ratatui::text::{Line, Span};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+
+/// Rows built above and below the viewport so short scrolls need no rebuild.
+pub const OVERSCAN: usize = 5;
+
+/// Rows to build for a list of `total` rows, and the offset the viewport is expected
+/// to settle on once the selection is scrolled into view.
+///
+/// `rows` is the number of rows that fit in the viewport.
+pub fn window(
+    total: usize,
+    offset: usize,
+    selected: Option<usize>,
+    rows: usize,
+) -> (Range<usize>, usize) {
+    let rows = rows.max(1);
+    let anchor = match selected {
+        Some(s) if s < offset => s,
+        Some(s) if s >= offset + rows => s + 1 - rows,
+        _ => offset,
+    }
+    .min(total.saturating_sub(1));
+    let range = crate::internal::ui::app::visible_window(anchor, rows, total, OVERSCAN);
+    (range, anchor)
+}
+
+struct CachedRow {
+    fingerprint: u64,
+    lines: Vec<Line<'static>>,
+}
+
+/// Formatted rows keyed by story id, valid for one theme and list width.
+#[derive(Default)]
+pub struct RowCache {
+    theme_key: String,
+    width: u16,
+    rows: HashMap<u32, CachedRow>,
+    /// Ids used in the current frame; everything else is evicted at `finish`
+    used: Vec<u32>,
+}
+
+impl RowCache {
+    /// Start a frame; drops every row when the theme or width changed.
+    pub fn begin(&mut self, theme_key: String, width: u16) {
+        if self.theme_key != theme_key || self.width != width {
+            self.rows.clear();
+            self.theme_key = theme_key;
+            self.width = width;
+        }
+        self.used.clear();
+    }
+
+    /// Mark `id` as shown this frame, rebuilding its lines when `fingerprint`
+    /// differs from the cached one.
+    pub fn ensure(
+        &mut self,
+        id: u32,
+        fingerprint: u64,
+        build: impl FnOnce() -> Vec<Line<'static>>,
+    ) {
+        self.used.push(id);
+        let row = self
+            .rows
+            .entry(id)
+            .and_modify(|row| {
+                if row.fingerprint != fingerprint {
+                    row.lines.clear();
+                }
+            })
+            .or_insert_with(|| CachedRow {
+                fingerprint,
+                lines: Vec::new(),
+            });
+        if row.lines.is_empty() {
+            row.fingerprint = fingerprint;
+            row.lines = build();
+        }
+    }
+
+    /// Evict rows that were not displayed this frame.
+    pub fn finish(&mut self) {
+        let used = std::mem::take(&mut self.used);
+        self.rows.retain(|id, _| used.contains(id));
+        self.used = used;
+    }
+
+    #[allow(dead_code)]
+    pub fn cached_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Borrowed copies of the cached lines of every row built this frame, in order.
+    pub fn frame_rows(&self) -> impl Iterator<Item = Vec<Line<'_>>> {
+        self.used.iter().map(|id| {
+            self.rows
+                .get(id)
+                .map(|row| row.lines.iter().map(borrow_line).collect())
+                .unwrap_or_default()
+        })
+    }
+}
+
+/// Line borrowing the text of a cached line, so drawing it copies no strings.
+pub fn borrow_line<'a>(line: &'a Line<'static>) -> Line<'a> {
+    Line::from(
+        line.spans
+            .iter()
+            .map(|span| Span::styled(span.content.as_ref(), span.style))
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Hash of everything a row's appearance depends on.
+pub fn fingerprint(parts: impl Hash) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    parts.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_follows_selection() {
+        // Selection inside the viewport keeps the offset
+        assert_eq!(window(100, 10, Some(12), 10), (5..25, 10));
+        // Jumping to the end anchors the selection at the bottom row
+        assert_eq!(window(100, 0, Some(99), 10), (85..100, 90));
+        // Moving above the viewport scrolls up to the selection
+        assert_eq!(window(100, 50, Some(20), 10), (15..35, 20));
+        assert_eq!(window(0, 0, None, 10), (0..0, 0));
+    }
+
+    #[test]
+    fn cache_rebuilds_on_change_and_evicts_hidden_rows() {
+        let mut cache = RowCache::default();
+        let mut builds = 0;
+        let mut build = |text: &'static str| {
+            builds += 1;
+            vec![Line::from(text)]
+        };
+
+        cache.begin("theme".to_string(), 80);
+        cache.ensure(1, 1, || build("a"));
+        cache.ensure(2, 1, || build("b"));
+        cache.finish();
+
+        cache.begin("theme".to_string(), 80);
+        cache.ensure(1, 1, || build("a"));
+        cache.ensure(2, 2, || build("b2"));
+        cache.finish();
+        let rows: Vec<String> = cache.frame_rows().map(|l| l[0].to_string()).collect();
+        assert_eq!(rows, vec!["a", "b2"]);
+
+        cache.begin("theme".to_string(), 80);
+        cache.ensure(2, 2, || build("b2"));
+        cache.finish();
+        assert_eq!(cache.cached_rows(), 1);
+
+        // A new width invalidates everything
+        cache.begin("theme".to_string(), 60);
+        cache.ensure(2, 2, || build("b2"));
+        cache.finish();
+        assert_eq!(builds, 4);
+    }
+}
@@ -3,12 +3,14 @@ use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::symbols;
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Padding, Paragraph, Wrap};
+use ratatui::widgets::{Block, Borders, Padding, Paragraph};
 use regex::Regex;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 
+use super::scroll::ScrollState;
+
 #[derive(Debug, Clone)]
 pub struct LogEntry {
     pub timestamp: String,
@@ -20,7 +22,7 @@ pub struct LogEntry {
 pub struct LogViewer {
     pub visible: bool,
     pub entries: Vec<LogEntry>,
-    pub scroll: u16,
+    pub scroll: ScrollState,
     pub active_tab: usize,
     pub tabs: Vec<String>,
     #[allow(dead_code)]
@@ -35,7 +37,7 @@ impl LogViewer {
         Self {
             visible: false,
             entries: Vec::new(),
-            scroll: 0,
+            scroll: ScrollState::default(),
             active_tab: 0,
             tabs: vec!["Logs".to_string(), "Metrics".to_string()],
             filter_level: None,
@@ -120,23 +122,17 @@ impl LogViewer {
     }
 
     pub fn scroll_up(&mut self) {
-        if self.scroll > 0 {
-            self.scroll -= 1;
-        }
+        self.scroll.scroll_up(1);
     }
 
     pub fn scroll_down(&mut self) {
-        // Simple bound check, can be improved
-        self.scroll += 1;
+        self.scroll.scroll_down(1);
     }
 
+    /// Lands on the last entry once the next render has measured the view.
     pub fn scroll_to_bottom(&mut self) {
-        let count = self.filtered_entries().len();
-        if count > 20 {
-            self.scroll = (count - 20) as u16;
-        } else {
-            self.scroll = 0;
-        }
+        self.scroll.reset();
+        self.scroll.scroll_to_bottom();
     }
 
     fn filtered_entries(&self) -> Vec<&LogEntry> {
@@ -151,7 +147,12 @@ impl LogViewer {
         }
     }
 
-    pub fn render(&self, f: &mut Frame, area: Rect) {
+    pub fn render(
+        &mut self,
+        f: &mut Frame,
+        area: Rect,
+        metrics: &crate::internal::metrics::MetricsRegistry,
+    ) {
         if !self.visible {
             return;
         }
@@ -229,41 +230,132 @@ impl LogViewer {
 
         f.render_widget(separator_block, full_width_separator_area);
 
-        // 5. Render Logs Content
-        let filtered_entries = self.filtered_entries();
-        let log_lines: Vec<Line> = filtered_entries
-            .iter()
-            .skip(self.scroll as usize)
-            .map(|entry| {
-                let level_style = match entry.level.as_str() {
-                    "ERROR" => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-                    "WARN" => Style::default().fg(Color::Yellow),
-                    "INFO" => Style::default().fg(Color::Blue),
-                    "DEBUG" => Style::default().fg(Color::Green),
-                    "TRACE" => Style::default().fg(Color::Magenta),
-                    _ => Style::default(),
-                };
-
-                Line::from(vec![
+        // 5. On the Metrics tab, pin a latency summary above the performance logs
+        let logs_area = match self.active_tab {
+            1 => {
+                let parts = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(1), Constraint::Min(0)])
+                    .split(logs_area);
+                let summary = metrics
+                    .summary(crate::internal::metrics::INPUT_LATENCY)
+                    .unwrap_or_else(|| "no samples yet".to_string());
+                let line = Line::from(vec![
                     Span::styled(
-                        format!("{} ", entry.timestamp),
-                        Style::default().fg(Color::DarkGray),
+                        "Input latency: ",
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD),
                     ),
-                    Span::styled(format!("{:5} ", entry.level), level_style),
-                    Span::styled(
-                        format!("{}: ", entry.module),
-                        Style::default().fg(Color::Cyan),
-                    ),
-                    Span::raw(&entry.message),
-                ])
-            })
+                    Span::raw(summary),
+                ]);
+                f.render_widget(
+                    Paragraph::new(line).block(Block::default().padding(Padding::horizontal(1))),
+                    parts[0],
+                );
+                parts[1]
+            }
+            _ => logs_area,
+        };
+
+        // 6. Render Logs Content, wrapped here so scrolling counts screen lines
+        let width = logs_area.width.saturating_sub(2).max(20) as usize;
+        let log_lines: Vec<Line> = self
+            .filtered_entries()
+            .iter()
+            .flat_map(|entry| entry_lines(entry, width))
             .collect();
+        self.scroll
+            .update(log_lines.len(), logs_area.height as usize);
 
         let logs = Paragraph::new(log_lines)
-            .wrap(Wrap { trim: false })
+            .scroll((self.scroll.offset_u16(), 0))
             // Add horizontal padding to logs so they don't touch the borders
             .block(Block::default().padding(Padding::horizontal(1)));
 
         f.render_widget(&logs, logs_area);
+        super::scroll::render_scrollbar(
+            f,
+            Rect::new(
+                logs_area.x,
+                logs_area.y.saturating_sub(1),
+                logs_area.width + 1,
+                logs_area.height + 2,
+            ),
+            &self.scroll,
+            Style::default().fg(Color::DarkGray),
+        );
+    }
+}
+
+/// An entry wrapped to `width` columns: timestamp, level and module in color on
+/// the first line, continuation lines indented.
+fn entry_lines(entry: &LogEntry, width: usize) -> Vec<Line<'static>> {
+    let level_style = match entry.level.as_str() {
+        "ERROR" => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        "WARN" => Style::default().fg(Color::Yellow),
+        "INFO" => Style::default().fg(Color::Blue),
+        "DEBUG" => Style::default().fg(Color::Green),
+        "TRACE" => Style::default().fg(Color::Magenta),
+        _ => Style::default(),
+    };
+    let prefix = [
+        (
+            format!("{} ", entry.timestamp),
+            Style::default().fg(Color::DarkGray),
+        ),
+        (format!("{:5} ", entry.level), level_style),
+        (
+            format!("{}: ", entry.module),
+            Style::default().fg(Color::Cyan),
+        ),
+    ];
+    let text: String = prefix
+        .iter()
+        .map(|(part, _)| part.as_str())
+        .chain([entry.message.as_str()])
+        .collect();
+    let options = textwrap::Options::new(width).subsequent_indent("  ");
+    textwrap::wrap(&text, options)
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let mut rest = line.into_owned();
+            let mut spans = Vec::new();
+            // Color the prefix parts that made it onto the first line whole
+            if i == 0 {
+                for (part, style) in &prefix {
+                    match rest.strip_prefix(part.as_str()) {
+                        Some(after) => {
+                            spans.push(Span::styled(part.clone(), *style));
+                            rest = after.to_string();
+                        }
+                        None => break,
+                    }
+                }
+            }
+            spans.push(Span::raw(rest));
+            Line::from(spans)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_wrap_to_screen_lines() {
+        let entry = LogEntry {
+            timestamp: "2025-11-29T09:30:15.123Z".to_string(),
+            level: "INFO".to_string(),
+            module: "app".to_string(),
+            message: "a message long enough to need a second line".to_string(),
+        };
+        let lines = entry_lines(&entry, 60);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].spans[1].content, "INFO  ");
+        assert!(lines[1].to_string().starts_with("  "));
+        assert_eq!(entry_lines(&entry, 200).len(), 1);
     }
 }
@@ -0,0 +1,92 @@
+//! Low-bandwidth rendering for SSH and other high-latency terminals.
+//!
+//! In this mode the screen is redrawn at most every `FRAME_MS` and queued actions are
+//! batched into a single frame, the spinner is replaced by a static marker, and every frame is
+//! rewritten to plain ASCII so borders and placeholder bars cost one byte per cell.
+
+use ratatui::buffer::Buffer;
+
+use crate::config::LowBandwidthMode;
+
+/// Shortest time between frames in low-bandwidth mode (normally 16ms).
+pub const FRAME_MS: u64 = 100;
+/// Maximum number of queued actions applied before the next frame is drawn.
+pub const MAX_BATCHED_ACTIONS: usize = 64;
+/// Static replacement for the animated spinner.
+pub const STATIC_SPINNER: &str = "*";
+
+/// Resolve the configured mode, auto-detecting SSH sessions from the environment.
+pub fn is_enabled(mode: LowBandwidthMode) -> bool {
+    match mode {
+        LowBandwidthMode::On => true,
+        LowBandwidthMode::Off => false,
+        LowBandwidthMode::Auto => {
+            std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some()
+        }
+    }
+}
+
+/// ASCII replacement for box-drawing, block and braille characters.
+fn ascii_for(symbol: &str) -> Option<&'static str> {
+    let c = symbol.chars().next()?;
+    match c {
+        '─' | '━' | '═' | '╌' | '┄' => Some("-"),
+        '│' | '┃' | '║' | '╎' | '┆' => Some("|"),
+        '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' | '╭' | '╮' | '╰' | '╯' | '╔' | '╗'
+        | '╚' | '╝' | '┏' | '┓' | '┗' | '┛' => Some("+"),
+        '▁'..='█' | '░' | '▒' | '▓' => Some("."),
+        '⠀'..='⣿' => Some(STATIC_SPINNER),
+        '•' | '·' => Some("*"),
+        '↑' => Some("^"),
+        '↓' => Some("v"),
+        _ => None,
+    }
+}
+
+/// Rewrite the frame buffer so it only contains ASCII decorations.
+pub fn asciify(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        if let Some(ascii) = ascii_for(cell.symbol()) {
+            cell.set_symbol(ascii);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+    use ratatui::widgets::{Block, Borders, Widget};
+
+    #[test]
+    fn borders_become_ascii() {
+        let area = Rect::new(0, 0, 4, 3);
+        let mut buffer = Buffer::empty(area);
+        Block::default()
+            .borders(Borders::ALL)
+            .render(area, &mut buffer);
+        asciify(&mut buffer);
+
+        let rows: Vec<String> = (0..3)
+            .map(|y| {
+                (0..4)
+                    .map(|x| buffer[(x, y)].symbol().to_string())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(rows, vec!["+--+", "|  |", "+--+"]);
+    }
+
+    #[test]
+    fn text_is_left_alone() {
+        assert_eq!(ascii_for("a"), None);
+        assert_eq!(ascii_for("é"), None);
+        assert_eq!(ascii_for("⠋"), Some(STATIC_SPINNER));
+    }
+
+    #[test]
+    fn explicit_modes_ignore_environment() {
+        assert!(is_enabled(LowBandwidthMode::On));
+        assert!(!is_enabled(LowBandwidthMode::Off));
+    }
+}
@@ -1,8 +1,35 @@
 pub mod app;
+pub mod article_view;
+pub mod command_palette;
+pub mod comment_view;
+pub mod domain_chip;
+pub mod filter_panel;
+pub mod help;
+pub mod hyperlink;
+pub mod item_prompt;
 pub mod keybinding_validator;
 pub mod keybindings;
 pub mod keybindings_default;
+pub mod layout;
+pub mod link_picker;
+pub mod link_preview;
+pub mod list_picker;
+pub mod list_rows;
 pub mod log_viewer;
+pub mod low_bandwidth;
+pub mod no_color;
+pub mod note_editor;
+pub mod prefetch;
+pub mod read_later_view;
+pub mod reading_search;
+pub mod row_template;
+pub mod scroll;
+pub mod settings_panel;
+pub mod skeleton;
 pub mod sort;
+pub mod status_template;
+pub mod tag_prompt;
+pub mod text_input;
 pub mod theme_editor;
+pub mod tutorial;
 pub mod view;
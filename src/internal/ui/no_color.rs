@@ -0,0 +1,69 @@
+//! Rendering without colors, for `NO_COLOR` (<https://no-color.org>) and
+//! `accessibility.no_color`.
+//!
+//! Views draw as usual and the finished frame is stripped of its colors, keeping
+//! the terminal's own foreground and background. Bold and underline survive, and
+//! cells drawn on a background of their own (the selection, chips, bars) are shown
+//! in reverse video so they still stand out.
+
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier, Style};
+
+/// Whether colors are off: by the config flag, or by `NO_COLOR` set to anything
+/// but the empty string.
+pub fn is_enabled(configured: bool) -> bool {
+    configured || std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// Rewrite the frame buffer in default colors. `background` is the theme's
+/// background, which cells drawn on it do not need to be reversed for.
+pub fn strip(buffer: &mut Buffer, background: Color) {
+    for cell in buffer.content.iter_mut() {
+        let mut modifier = cell.modifier;
+        if cell.bg != Color::Reset && cell.bg != background {
+            modifier.insert(Modifier::REVERSED);
+        }
+        cell.set_style(Style::reset());
+        cell.modifier = modifier;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn colors_become_reverse_video_and_modifiers_stay() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 3, 1));
+        let dark = Color::Rgb(20, 20, 20);
+        buffer[(0, 0)].set_style(Style::default().fg(Color::Red).bg(dark));
+        buffer[(1, 0)].set_style(
+            Style::default()
+                .fg(Color::Yellow)
+                .bg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+        );
+        buffer[(2, 0)].set_style(
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::UNDERLINED),
+        );
+        strip(&mut buffer, dark);
+
+        let cells: Vec<_> = (0..3).map(|x| buffer[(x, 0)].clone()).collect();
+        assert!(
+            cells
+                .iter()
+                .all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset)
+        );
+        assert_eq!(cells[0].modifier, Modifier::empty());
+        assert_eq!(cells[1].modifier, Modifier::BOLD | Modifier::REVERSED);
+        assert_eq!(cells[2].modifier, Modifier::UNDERLINED);
+    }
+
+    #[test]
+    fn config_flag_turns_colors_off() {
+        assert!(is_enabled(true));
+    }
+}
@@ -0,0 +1,75 @@
+//! Popup for the note attached to a bookmark. Enter starts a new line; Ctrl+s
+//! saves, and saving an empty note removes it.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::text_input::TextInput;
+
+/// What the editor asks the app to do after a key press.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NoteOutcome {
+    Pending,
+    Cancel,
+    Save(Option<String>),
+}
+
+#[derive(Debug, Clone)]
+pub struct NoteEditor {
+    pub story_id: u32,
+    pub title: String,
+    pub input: TextInput,
+}
+
+impl NoteEditor {
+    pub fn new(story_id: u32, title: String, note: Option<&str>) -> Self {
+        Self {
+            story_id,
+            title,
+            input: TextInput::multiline(note.unwrap_or_default()),
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> NoteOutcome {
+        match (key.code, key.modifiers.contains(KeyModifiers::CONTROL)) {
+            (KeyCode::Esc, _) => NoteOutcome::Cancel,
+            (KeyCode::Char('s'), true) => {
+                let note = self.input.text().trim();
+                NoteOutcome::Save(match note.is_empty() {
+                    true => None,
+                    false => Some(note.to_string()),
+                })
+            }
+            _ => {
+                self.input.handle_key(key);
+                NoteOutcome::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saves_trimmed_multiline_notes() {
+        let mut editor = NoteEditor::new(1, "Story".to_string(), Some("read"));
+        let press = |editor: &mut NoteEditor, code| {
+            editor.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+        };
+        press(&mut editor, KeyCode::Enter);
+        for c in "later ".chars() {
+            press(&mut editor, KeyCode::Char(c));
+        }
+        let save = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        assert_eq!(
+            editor.handle_key(save),
+            NoteOutcome::Save(Some("read\nlater".to_string()))
+        );
+
+        let mut empty = NoteEditor::new(1, "Story".to_string(), None);
+        press(&mut empty, KeyCode::Char(' '));
+        assert_eq!(empty.handle_key(save), NoteOutcome::Save(None));
+        assert_eq!(press(&mut empty, KeyCode::Esc), NoteOutcome::Cancel);
+    }
+}
@@ -0,0 +1,147 @@
+//! Prefetching of what is likely to be opened next, so it comes from the cache.
+//!
+//! Two things are fetched ahead: the next page of stories once the selection gets
+//! near the end of the loaded list, and the article and first top-level comments of
+//! a story that stays highlighted for a moment. Both go through the `ApiService`,
+//! and so its rate limit, and each has a token to cancel it once it is not needed.
+
+use std::time::{Duration, Instant};
+
+use tokio_util::sync::CancellationToken;
+
+/// Rows from the end of the list at which the next stories are fetched.
+pub const NEAR_END_ROWS: usize = 5;
+/// Top-level comments fetched for a highlighted story: about the first screen.
+pub const COMMENTS: usize = 10;
+
+#[derive(Debug)]
+struct Highlight {
+    id: u32,
+    since: Instant,
+    fetched: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Prefetcher {
+    highlighted: Option<Highlight>,
+    highlight_token: Option<CancellationToken>,
+    stories_token: Option<CancellationToken>,
+    /// Position in the story ids up to which stories were fetched ahead
+    stories_until: usize,
+}
+
+impl Prefetcher {
+    /// Note which story is highlighted. Another one than before restarts the delay
+    /// and cancels the prefetch of the previous one.
+    pub fn highlight(&mut self, id: Option<u32>, now: Instant) {
+        if self.highlighted.as_ref().map(|h| h.id) == id {
+            return;
+        }
+        if let Some(token) = self.highlight_token.take() {
+            token.cancel();
+        }
+        self.highlighted = id.map(|id| Highlight {
+            id,
+            since: now,
+            fetched: false,
+        });
+    }
+
+    /// When the highlighted story is due to be prefetched, for the run loop to wake up.
+    pub fn due_at(&self, delay: Duration) -> Option<Instant> {
+        self.highlighted
+            .as_ref()
+            .filter(|h| !h.fetched)
+            .map(|h| h.since + delay)
+    }
+
+    /// The highlighted story once it has been highlighted for `delay`, with the
+    /// token that cancels its prefetch. Each highlight is handed out once.
+    pub fn take_due(&mut self, delay: Duration, now: Instant) -> Option<(u32, CancellationToken)> {
+        let highlight = self.highlighted.as_mut()?;
+        if highlight.fetched || now < highlight.since + delay {
+            return None;
+        }
+        highlight.fetched = true;
+        let token = CancellationToken::new();
+        self.highlight_token = Some(token.clone());
+        Some((highlight.id, token))
+    }
+
+    /// The `count` ids after the `loaded` ones once row `selected` of `rows` is near
+    /// the end of the list, leaving out those already fetched ahead.
+    pub fn next_stories(
+        &mut self,
+        selected: usize,
+        rows: usize,
+        ids: &[u32],
+        loaded: usize,
+        count: usize,
+    ) -> Option<(Vec<u32>, CancellationToken)> {
+        if selected + NEAR_END_ROWS < rows {
+            return None;
+        }
+        let start = loaded.max(self.stories_until);
+        let end = (loaded + count).min(ids.len());
+        if start >= end {
+            return None;
+        }
+        self.stories_until = end;
+        let token = self
+            .stories_token
+            .get_or_insert_with(CancellationToken::new);
+        Some((ids[start..end].to_vec(), token.clone()))
+    }
+
+    /// Cancel and forget the stories fetched ahead, for a new list.
+    pub fn reset_stories(&mut self) {
+        if let Some(token) = self.stories_token.take() {
+            token.cancel();
+        }
+        self.stories_until = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlight_is_due_once_after_the_delay() {
+        let mut prefetcher = Prefetcher::default();
+        let delay = Duration::from_millis(500);
+        let now = Instant::now();
+        prefetcher.highlight(Some(1), now);
+        assert_eq!(prefetcher.due_at(delay), Some(now + delay));
+        assert!(prefetcher.take_due(delay, now).is_none());
+
+        let (id, token) = prefetcher.take_due(delay, now + delay).unwrap();
+        assert_eq!(id, 1);
+        assert!(prefetcher.take_due(delay, now + delay).is_none());
+        assert_eq!(prefetcher.due_at(delay), None);
+
+        // The same story again keeps its state; another one cancels the prefetch
+        prefetcher.highlight(Some(1), now + delay);
+        assert!(!token.is_cancelled());
+        prefetcher.highlight(Some(2), now + delay);
+        assert!(token.is_cancelled());
+        assert_eq!(prefetcher.due_at(delay), Some(now + delay * 2));
+    }
+
+    #[test]
+    fn next_stories_near_the_end_of_the_list() {
+        let mut prefetcher = Prefetcher::default();
+        let ids: Vec<u32> = (1..=50).collect();
+        assert!(prefetcher.next_stories(10, 30, &ids, 30, 20).is_none());
+
+        let (next, token) = prefetcher.next_stories(25, 30, &ids, 30, 20).unwrap();
+        assert_eq!(next, (31..=50).collect::<Vec<_>>());
+        // Already fetched ahead
+        assert!(prefetcher.next_stories(29, 30, &ids, 30, 20).is_none());
+
+        prefetcher.reset_stories();
+        assert!(token.is_cancelled());
+        let (next, _) = prefetcher.next_stories(0, 3, &ids[..5], 3, 20).unwrap();
+        assert_eq!(next, vec![4, 5]);
+    }
+}
@@ -0,0 +1,86 @@
+//! Popup listing the read-later queue with the download state of each story, to
+//! open, retry or remove them.
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::internal::read_later::QueuedStory;
+
+/// What the queue view asks the app to do after a key press.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueueOutcome {
+    Pending,
+    Close,
+    Open(u32),
+    Retry(u32),
+    /// Retry every failed download
+    RetryAll,
+    Remove(u32),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReadLaterView {
+    pub selected: usize,
+}
+
+impl ReadLaterView {
+    /// j/k move, Enter opens the story, r retries it, R retries every failure and
+    /// d removes it from the queue.
+    pub fn handle_key(&mut self, key: KeyEvent, items: &[QueuedStory]) -> QueueOutcome {
+        let count = items.len();
+        self.selected = self.selected.min(count.saturating_sub(1));
+        let selected = items.get(self.selected).map(|item| item.id);
+        match (key.code, selected) {
+            (KeyCode::Esc | KeyCode::Char('q'), _) => return QueueOutcome::Close,
+            (KeyCode::Char('j') | KeyCode::Down, Some(_)) => {
+                self.selected = (self.selected + 1) % count;
+            }
+            (KeyCode::Char('k') | KeyCode::Up, Some(_)) => {
+                self.selected = (self.selected + count - 1) % count;
+            }
+            (KeyCode::Enter, Some(id)) => return QueueOutcome::Open(id),
+            (KeyCode::Char('r'), Some(id)) => return QueueOutcome::Retry(id),
+            (KeyCode::Char('R'), _) => return QueueOutcome::RetryAll,
+            (KeyCode::Char('d') | KeyCode::Delete, Some(id)) => return QueueOutcome::Remove(id),
+            _ => {}
+        }
+        QueueOutcome::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::models::Story;
+    use crate::internal::read_later::ReadLater;
+    use crossterm::event::KeyModifiers;
+
+    #[test]
+    fn moves_and_acts_on_the_selected_story() {
+        let mut queue = ReadLater::new();
+        for id in [7, 8] {
+            queue.enqueue(
+                &Story {
+                    id,
+                    ..Default::default()
+                },
+                0,
+            );
+        }
+        let mut view = ReadLaterView::default();
+        let mut press =
+            |code| view.handle_key(KeyEvent::new(code, KeyModifiers::empty()), &queue.items);
+        assert_eq!(press(KeyCode::Enter), QueueOutcome::Open(7));
+        assert_eq!(press(KeyCode::Char('j')), QueueOutcome::Pending);
+        assert_eq!(press(KeyCode::Char('r')), QueueOutcome::Retry(8));
+        assert_eq!(press(KeyCode::Char('j')), QueueOutcome::Pending);
+        assert_eq!(press(KeyCode::Char('d')), QueueOutcome::Remove(7));
+        assert_eq!(press(KeyCode::Esc), QueueOutcome::Close);
+
+        // An empty queue only closes and retries
+        let mut view = ReadLaterView { selected: 3 };
+        assert_eq!(
+            view.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()), &[]),
+            QueueOutcome::Pending
+        );
+    }
+}
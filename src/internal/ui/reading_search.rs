@@ -0,0 +1,111 @@
+//! "Search my reading" popup: full-text search over the reading index, with the
+//! results updated as the query is typed.
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::internal::reading_index::Hit;
+
+/// What the popup asks the app to do after a key press.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchOutcome {
+    Pending,
+    Close,
+    /// The query changed; search again
+    Search(String),
+    Open(Hit),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReadingSearch {
+    pub query: String,
+    pub hits: Vec<Hit>,
+    pub selected: usize,
+    /// Why the last search failed
+    pub error: Option<String>,
+}
+
+impl ReadingSearch {
+    pub fn set_results(&mut self, results: Result<Vec<Hit>, String>) {
+        self.selected = 0;
+        match results {
+            Ok(hits) => {
+                self.hits = hits;
+                self.error = None;
+            }
+            Err(error) => {
+                self.hits.clear();
+                self.error = Some(error);
+            }
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> SearchOutcome {
+        match key.code {
+            KeyCode::Esc => SearchOutcome::Close,
+            KeyCode::Enter => match self.hits.get(self.selected) {
+                Some(hit) => SearchOutcome::Open(hit.clone()),
+                None => SearchOutcome::Pending,
+            },
+            KeyCode::Down => {
+                if self.selected + 1 < self.hits.len() {
+                    self.selected += 1;
+                }
+                SearchOutcome::Pending
+            }
+            KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                SearchOutcome::Pending
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                SearchOutcome::Search(self.query.clone())
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                SearchOutcome::Search(self.query.clone())
+            }
+            _ => SearchOutcome::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::reading_index::ItemKind;
+    use crossterm::event::KeyModifiers;
+
+    fn press(search: &mut ReadingSearch, code: KeyCode) -> SearchOutcome {
+        search.handle_key(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    #[test]
+    fn typing_searches_and_enter_opens_the_selection() {
+        let mut search = ReadingSearch::default();
+        assert_eq!(
+            press(&mut search, KeyCode::Char('r')),
+            SearchOutcome::Search("r".to_string())
+        );
+        assert_eq!(press(&mut search, KeyCode::Enter), SearchOutcome::Pending);
+
+        let hit = |story_id| Hit {
+            story_id,
+            kind: ItemKind::Article,
+            title: String::new(),
+            snippet: String::new(),
+            highlighted: Vec::new(),
+        };
+        search.set_results(Ok(vec![hit(1), hit(2)]));
+        press(&mut search, KeyCode::Down);
+        press(&mut search, KeyCode::Down);
+        assert_eq!(
+            press(&mut search, KeyCode::Enter),
+            SearchOutcome::Open(hit(2))
+        );
+        assert_eq!(
+            press(&mut search, KeyCode::Backspace),
+            SearchOutcome::Search(String::new())
+        );
+        assert_eq!(press(&mut search, KeyCode::Esc), SearchOutcome::Close);
+    }
+}
@@ -0,0 +1,421 @@
+//! Story list rows laid out by `ui.list_view.row_template`, such as
+//! `"{rank} {score} {title} [{domain}] — {age} · {comments}c by {author}"`.
+//!
+//! The template is parsed once, when the config is loaded, into a plan of text and
+//! fields; drawing a row only fills the fields in and fits them to the width. A
+//! field can have a width and alignment, as in `{score:>4}`, or `*` to take the
+//! rest of the line; `{{` and `}}` are literal braces and `\n` starts another line.
+
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use textwrap::core::display_width;
+
+/// What a row can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Field {
+    Rank,
+    /// ★ on bookmarked stories
+    Bookmark,
+    Score,
+    Title,
+    Domain,
+    Age,
+    Comments,
+    Author,
+    ReadingTime,
+    Tags,
+    /// Changes since the previous launch
+    Delta,
+    /// Sparkline of the score during the session
+    Trend,
+}
+
+impl Field {
+    const ALL: [(&'static str, Field); 12] = [
+        ("rank", Field::Rank),
+        ("bookmark", Field::Bookmark),
+        ("score", Field::Score),
+        ("title", Field::Title),
+        ("domain", Field::Domain),
+        ("age", Field::Age),
+        ("comments", Field::Comments),
+        ("author", Field::Author),
+        ("reading_time", Field::ReadingTime),
+        ("tags", Field::Tags),
+        ("delta", Field::Delta),
+        ("trend", Field::Trend),
+    ];
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|(known, _)| *known == name)
+            .map(|(_, field)| *field)
+    }
+
+    /// Counts line up on the right unless the template says otherwise.
+    fn default_align(self) -> Align {
+        match self {
+            Field::Score | Field::Comments => Align::Right,
+            _ => Align::Left,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Width {
+    /// As wide as the value; a title without a width is cut to fit the line
+    Natural,
+    Fixed(usize),
+    /// Whatever the rest of the line leaves
+    Fill,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Text(String),
+    Field {
+        field: Field,
+        width: Width,
+        align: Align,
+    },
+}
+
+/// A parsed row template: lines of text and fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowTemplate {
+    lines: Vec<Vec<Part>>,
+}
+
+impl RowTemplate {
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let mut lines = vec![Vec::new()];
+        let mut text = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '}' => return Err("unmatched `}` (write `}}` for a brace)".to_string()),
+                '{' => {
+                    let mut spec = String::new();
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        spec.push(c);
+                    }
+                    if !closed {
+                        return Err(format!("unclosed `{{{}`", spec));
+                    }
+                    let line = lines.last_mut().expect("at least one line");
+                    if !text.is_empty() {
+                        line.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    line.push(parse_field(&spec)?);
+                }
+                '\n' => {
+                    let line = lines.last_mut().expect("at least one line");
+                    if !text.is_empty() {
+                        line.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    lines.push(Vec::new());
+                }
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            lines
+                .last_mut()
+                .expect("at least one line")
+                .push(Part::Text(text));
+        }
+        Ok(Self { lines })
+    }
+
+    /// Lines each row takes.
+    pub fn height(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// The row's lines, `width` columns wide at most. `value` gives the text and
+    /// style of each field, empty when the story has nothing to show there.
+    pub fn render(
+        &self,
+        width: usize,
+        text_style: Style,
+        value: impl Fn(Field) -> (String, Style),
+    ) -> Vec<Line<'static>> {
+        self.lines
+            .iter()
+            .map(|parts| render_line(parts, width, text_style, &value))
+            .collect()
+    }
+}
+
+/// `name`, `name:12`, `name:>4`, `name:*`, ...
+fn parse_field(spec: &str) -> Result<Part, String> {
+    let (name, format) = spec.split_once(':').unwrap_or((spec, ""));
+    let field = Field::from_name(name.trim()).ok_or_else(|| {
+        let names: Vec<_> = Field::ALL.iter().map(|(name, _)| *name).collect();
+        format!(
+            "unknown field `{{{}}}`; known fields: {}",
+            spec,
+            names.join(", ")
+        )
+    })?;
+    let (align, rest) = match format.chars().next() {
+        Some('<') => (Align::Left, &format[1..]),
+        Some('>') => (Align::Right, &format[1..]),
+        Some('^') => (Align::Center, &format[1..]),
+        _ => (field.default_align(), format),
+    };
+    let width = match rest {
+        "" => Width::Natural,
+        "*" => Width::Fill,
+        digits => Width::Fixed(
+            digits
+                .parse()
+                .map_err(|_| format!("invalid width `{}` in `{{{}}}`", digits, spec))?,
+        ),
+    };
+    Ok(Part::Field {
+        field,
+        width,
+        align,
+    })
+}
+
+/// `text` cut to `max` columns, ending in … when it was cut.
+pub fn truncate(text: &str, max: usize) -> String {
+    if display_width(text) <= max {
+        return text.to_string();
+    }
+    let mut cut = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = display_width(c.encode_utf8(&mut [0; 4]));
+        if used + w + 1 > max {
+            break;
+        }
+        cut.push(c);
+        used += w;
+    }
+    if max > 0 {
+        cut.push('…');
+    }
+    cut
+}
+
+/// `text` cut or padded to exactly `width` columns.
+fn fit(text: &str, width: usize, align: Align) -> String {
+    let text = truncate(text, width);
+    let gap = width.saturating_sub(display_width(&text));
+    let (left, right) = match align {
+        Align::Left => (0, gap),
+        Align::Right => (gap, 0),
+        Align::Center => (gap / 2, gap - gap / 2),
+    };
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(right))
+}
+
+fn closing(open: char) -> Option<char> {
+    match open {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '<' => Some('>'),
+        '"' => Some('"'),
+        _ => None,
+    }
+}
+
+fn render_line(
+    parts: &[Part],
+    width: usize,
+    text_style: Style,
+    value: &impl Fn(Field) -> (String, Style),
+) -> Line<'static> {
+    let mut texts: Vec<(String, Style)> = parts
+        .iter()
+        .map(|part| match part {
+            Part::Text(text) => (text.clone(), text_style),
+            Part::Field { field, .. } => value(*field),
+        })
+        .collect();
+
+    // An empty field takes the brackets around it along, and the space before them
+    // when a space follows, so "{title} [{domain}] by" reads "Title by" for Ask HN
+    for i in 0..parts.len() {
+        let empty_field = matches!(parts[i], Part::Field { .. }) && texts[i].0.is_empty();
+        if !empty_field || i == 0 || !matches!(parts[i - 1], Part::Text(_)) {
+            continue;
+        }
+        let next_is_text = matches!(parts.get(i + 1), Some(Part::Text(_)));
+        let close = texts[i - 1].0.chars().next_back().and_then(closing);
+        if let (Some(close), true) = (close, next_is_text)
+            && texts[i + 1].0.starts_with(close)
+        {
+            texts[i - 1].0.pop();
+            texts[i + 1].0.remove(0);
+        }
+        let space_follows = texts
+            .get(i + 1)
+            .is_none_or(|(next, _)| next.is_empty() || next.starts_with(' '));
+        if space_follows {
+            let trimmed = texts[i - 1].0.trim_end().len();
+            texts[i - 1].0.truncate(trimmed);
+        }
+    }
+
+    // The first `*` field, or else a title of natural width, gets what is left
+    let elastic = parts
+        .iter()
+        .position(|part| {
+            matches!(
+                part,
+                Part::Field {
+                    width: Width::Fill,
+                    ..
+                }
+            )
+        })
+        .or_else(|| {
+            parts.iter().position(|part| {
+                matches!(
+                    part,
+                    Part::Field {
+                        field: Field::Title,
+                        width: Width::Natural,
+                        ..
+                    }
+                )
+            })
+        });
+    for (part, (text, _)) in parts.iter().zip(texts.iter_mut()) {
+        if let Part::Field {
+            width: Width::Fixed(width),
+            align,
+            ..
+        } = part
+        {
+            *text = fit(text, *width, *align);
+        }
+    }
+    if let Some(i) = elastic {
+        let others: usize = texts
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, (text, _))| display_width(text))
+            .sum();
+        let left = width.saturating_sub(others);
+        texts[i].0 = match parts[i] {
+            Part::Field {
+                width: Width::Fill,
+                align,
+                ..
+            } => fit(&texts[i].0, left, align),
+            _ => truncate(&texts[i].0, left),
+        };
+    }
+
+    Line::from(
+        texts
+            .into_iter()
+            .filter(|(text, _)| !text.is_empty())
+            .map(|(text, style)| Span::styled(text, style))
+            .collect::<Vec<_>>(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(template: &str, width: usize, value: impl Fn(Field) -> &'static str) -> Vec<String> {
+        RowTemplate::parse(template)
+            .unwrap()
+            .render(width, Style::default(), |field| {
+                (value(field).to_string(), Style::default())
+            })
+            .iter()
+            .map(|line| line.to_string())
+            .collect()
+    }
+
+    fn story(field: Field) -> &'static str {
+        match field {
+            Field::Rank => "1",
+            Field::Score => "120",
+            Field::Title => "A rather long title about Rust",
+            Field::Domain => "example.com",
+            Field::Age => "3 hours ago",
+            Field::Comments => "45",
+            Field::Author => "pg",
+            _ => "",
+        }
+    }
+
+    #[test]
+    fn fills_fields_and_cuts_the_title_to_fit() {
+        let template = "{rank} {score} {title} [{domain}] — {age} · {comments}c by {author}";
+        assert_eq!(
+            row(template, 100, story),
+            vec!["1 120 A rather long title about Rust [example.com] — 3 hours ago · 45c by pg"]
+        );
+        assert_eq!(
+            row(template, 60, story),
+            vec!["1 120 A rather long… [example.com] — 3 hours ago · 45c by pg"]
+        );
+    }
+
+    #[test]
+    fn widths_alignment_and_lines() {
+        assert_eq!(
+            row("{rank:<3}{score:5}|{author:^6}|\n{title:*}|", 20, story),
+            vec!["1    120|  pg  |", "A rather long titl…|"]
+        );
+        assert_eq!(row("{title:>*}", 34, story)[0].len(), 34);
+        assert_eq!(row("{{{score}}}", 10, story), vec!["{120}"]);
+    }
+
+    #[test]
+    fn empty_fields_take_their_brackets_along() {
+        let ask = |field| match field {
+            Field::Domain => "",
+            other => story(other),
+        };
+        assert_eq!(
+            row("{score} [{domain}] by {author}", 80, ask),
+            vec!["120 by pg"]
+        );
+        assert_eq!(row("{score} ({domain})", 80, ask), vec!["120"]);
+    }
+
+    #[test]
+    fn reports_template_errors() {
+        assert!(
+            RowTemplate::parse("{nope}")
+                .unwrap_err()
+                .contains("unknown field `{nope}`")
+        );
+        assert!(RowTemplate::parse("{title").is_err());
+        assert!(RowTemplate::parse("title}").is_err());
+        assert!(RowTemplate::parse("{score:>x}").is_err());
+    }
+}
@@ -0,0 +1,362 @@
+//! Scroll position shared by the article, comments, log viewer and help views.
+//!
+//! All of them scroll the same way: `Ctrl+d`/`Ctrl+u` by half a screen,
+//! `Ctrl+f`/`Ctrl+b` (and `PageDown`/`PageUp`) by a screen, `g`/`G` (and
+//! `Home`/`End`) to either end, always counted in rendered, wrapped lines.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::Frame;
+use ratatui::layout::{Margin, Rect};
+use ratatui::style::Style;
+use ratatui::widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState};
+use std::ops::Range;
+
+use crate::internal::ui::app::Action;
+
+/// Offset into a block of lines, clamped to the content once the view has been
+/// measured. Multi-line jumps can be animated: the offset then moves towards its
+/// target over a few frames (see `tick`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScrollState {
+    offset: usize,
+    /// Where an animated scroll is heading; equal to `offset` when idle
+    target: usize,
+    content_len: usize,
+    /// Visible lines; 0 until the first `update`, and then nothing is clamped
+    viewport: usize,
+    /// Offset to jump to once the content reaches it (see `scroll_to_when_loaded`)
+    pending: Option<usize>,
+}
+
+impl ScrollState {
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Offset for `Paragraph::scroll`.
+    pub fn offset_u16(&self) -> u16 {
+        self.offset.min(u16::MAX as usize) as u16
+    }
+
+    /// Record the size of the content and the view at render time, and clamp to it.
+    pub fn update(&mut self, content_len: usize, viewport: usize) {
+        self.content_len = content_len;
+        self.viewport = viewport;
+        if let Some(offset) = self.pending
+            && viewport > 0
+            && offset <= self.max_offset()
+        {
+            self.pending = None;
+            self.offset = offset;
+            self.target = offset;
+        }
+        self.offset = self.clamp(self.offset);
+        self.target = self.clamp(self.target);
+    }
+
+    pub fn max_offset(&self) -> usize {
+        self.content_len.saturating_sub(self.viewport)
+    }
+
+    fn clamp(&self, offset: usize) -> usize {
+        match self.viewport {
+            0 => offset,
+            _ => offset.min(self.max_offset()),
+        }
+    }
+
+    /// Jump to `offset` as soon as content still loading is long enough to show it,
+    /// unless the view is scrolled before then.
+    pub fn scroll_to_when_loaded(&mut self, offset: usize) {
+        self.pending = Some(offset);
+    }
+
+    /// Jump to `offset` immediately.
+    pub fn scroll_to(&mut self, offset: usize) {
+        self.pending = None;
+        self.offset = self.clamp(offset);
+        self.target = self.offset;
+    }
+
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll_to(self.target.saturating_sub(lines));
+    }
+
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll_to(self.target.saturating_add(lines));
+    }
+
+    /// Start an animated scroll towards `offset`.
+    pub fn glide_to(&mut self, offset: usize) {
+        self.pending = None;
+        self.target = self.clamp(offset);
+    }
+
+    pub fn page_up(&mut self) {
+        self.glide_to(self.target.saturating_sub(self.page()));
+    }
+
+    pub fn page_down(&mut self) {
+        self.glide_to(self.target.saturating_add(self.page()));
+    }
+
+    pub fn half_page_up(&mut self) {
+        self.glide_to(self.target.saturating_sub(self.half_page()));
+    }
+
+    pub fn half_page_down(&mut self) {
+        self.glide_to(self.target.saturating_add(self.half_page()));
+    }
+
+    fn half_page(&self) -> usize {
+        (self.viewport / 2).max(1)
+    }
+
+    /// Carry out one of the scroll actions; others are ignored.
+    pub fn apply(&mut self, action: &Action) {
+        match action {
+            Action::ScrollHalfPageDown => self.half_page_down(),
+            Action::ScrollHalfPageUp => self.half_page_up(),
+            Action::ScrollPageDown => self.page_down(),
+            Action::ScrollPageUp => self.page_up(),
+            Action::ScrollToTop => self.scroll_to_top(),
+            Action::ScrollToBottom => self.scroll_to_bottom(),
+            _ => {}
+        }
+    }
+
+    /// One screen minus a line of overlap, so the reader keeps their place.
+    fn page(&self) -> usize {
+        self.viewport.saturating_sub(1).max(1)
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.scroll_to(0);
+    }
+
+    /// Scroll to the end; before the first `update` this lands once the size is known.
+    pub fn scroll_to_bottom(&mut self) {
+        match self.viewport {
+            0 => {
+                self.offset = usize::MAX;
+                self.target = usize::MAX;
+            }
+            _ => self.scroll_to(self.max_offset()),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Advance an animated scroll by one frame, covering a third of the remaining
+    /// distance (at least one line). Returns true while still moving.
+    pub fn tick(&mut self) -> bool {
+        let distance = self.offset.abs_diff(self.target);
+        let step = distance.div_ceil(3);
+        match self.offset < self.target {
+            true => self.offset += step,
+            false => self.offset -= step,
+        }
+        self.offset != self.target
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.offset != self.target
+    }
+
+    /// Position as 0-100, for "42%" indicators. Content that fits counts as 100%.
+    pub fn percentage(&self) -> u16 {
+        match self.max_offset() {
+            0 => 100,
+            max => (self.offset.min(max) * 100 / max) as u16,
+        }
+    }
+
+    /// Share of the content that has been on screen down to the bottom of the view,
+    /// as 0-100: a 100-line article shows 10% read on its first 10-line screen.
+    pub fn read_percentage(&self) -> u16 {
+        match self.content_len {
+            0 => 100,
+            len => ((self.offset + self.viewport).min(len) * 100 / len) as u16,
+        }
+    }
+
+    /// Lines of the content on screen.
+    pub fn visible_range(&self) -> Range<usize> {
+        let start = self.offset.min(self.content_len);
+        start..(start + self.viewport).min(self.content_len)
+    }
+
+    /// Scroll just enough to show `line` with `margin` lines of context around it.
+    pub fn ensure_visible(&mut self, line: usize, margin: usize) {
+        let margin = margin.min(self.viewport.saturating_sub(1) / 2);
+        let bottom = (self.target + self.viewport).saturating_sub(1 + margin);
+        if line < self.target + margin {
+            self.scroll_to(line.saturating_sub(margin));
+        } else if line > bottom {
+            self.scroll_to(line + margin + 1 - self.viewport);
+        }
+    }
+
+    /// Scroll so that `line` sits in the middle of the view.
+    pub fn center_on(&mut self, line: usize) {
+        self.scroll_to(line.saturating_sub(self.viewport / 2));
+    }
+}
+
+/// The scroll action of a key in views that handle their keys themselves, like the
+/// log viewer.
+pub fn scroll_key(key: &KeyEvent) -> Option<Action> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match (key.code, ctrl) {
+        (KeyCode::Char('d'), true) => Some(Action::ScrollHalfPageDown),
+        (KeyCode::Char('u'), true) => Some(Action::ScrollHalfPageUp),
+        (KeyCode::Char('f'), true) | (KeyCode::PageDown, _) => Some(Action::ScrollPageDown),
+        (KeyCode::Char('b'), true) | (KeyCode::PageUp, _) => Some(Action::ScrollPageUp),
+        (KeyCode::Char('g'), false) | (KeyCode::Home, _) => Some(Action::ScrollToTop),
+        (KeyCode::Char('G'), false) | (KeyCode::End, _) => Some(Action::ScrollToBottom),
+        _ => None,
+    }
+}
+
+/// Draw a scrollbar over the right border of `area`, a bordered pane, when its
+/// content does not fit.
+pub fn render_scrollbar(f: &mut Frame, area: Rect, scroll: &ScrollState, style: Style) {
+    if scroll.max_offset() == 0 {
+        return;
+    }
+    let mut state = ScrollbarState::new(scroll.max_offset() + 1)
+        .position(scroll.offset().min(scroll.max_offset()))
+        .viewport_content_length(scroll.viewport);
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .style(style);
+    f.render_stateful_widget(
+        scrollbar,
+        area.inner(Margin {
+            vertical: 1,
+            horizontal: 0,
+        }),
+        &mut state,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measured(content_len: usize, viewport: usize) -> ScrollState {
+        let mut scroll = ScrollState::default();
+        scroll.update(content_len, viewport);
+        scroll
+    }
+
+    #[test]
+    fn clamps_once_measured() {
+        // Unmeasured views scroll freely; the first render clamps
+        let mut scroll = ScrollState::default();
+        scroll.scroll_down(50);
+        assert_eq!(scroll.offset(), 50);
+        scroll.update(30, 10);
+        assert_eq!(scroll.offset(), 20);
+        scroll.scroll_down(5);
+        assert_eq!(scroll.offset(), 20);
+        assert_eq!(scroll.percentage(), 100);
+        scroll.scroll_up(25);
+        assert_eq!((scroll.offset(), scroll.percentage()), (0, 0));
+
+        let mut early = ScrollState::default();
+        early.scroll_to_bottom();
+        early.update(30, 10);
+        assert_eq!(early.offset(), 20);
+        assert_eq!(measured(5, 10).percentage(), 100);
+
+        let mut article = measured(100, 10);
+        assert_eq!(article.read_percentage(), 10);
+        article.scroll_down(500);
+        assert_eq!((article.offset(), article.read_percentage()), (90, 100));
+        assert_eq!(measured(0, 10).read_percentage(), 100);
+    }
+
+    #[test]
+    fn restored_offset_waits_for_the_content() {
+        let mut scroll = measured(5, 10);
+        scroll.scroll_to_when_loaded(40);
+        scroll.update(30, 10);
+        assert_eq!(scroll.offset(), 0);
+        scroll.update(60, 10);
+        assert_eq!(scroll.offset(), 40);
+
+        // Scrolling first cancels it
+        let mut scroll = measured(5, 10);
+        scroll.scroll_to_when_loaded(40);
+        scroll.scroll_down(1);
+        scroll.update(60, 10);
+        assert_eq!(scroll.offset(), 0);
+    }
+
+    #[test]
+    fn page_jumps_glide_over_a_few_frames() {
+        let mut scroll = measured(100, 10);
+        scroll.page_down();
+        assert_eq!(scroll.offset(), 0);
+        let mut frames = 0;
+        while scroll.tick() {
+            frames += 1;
+        }
+        assert_eq!(scroll.offset(), 9);
+        assert!(frames > 1);
+
+        // Line steps continue from the target, not the in-between position
+        scroll.page_down();
+        scroll.tick();
+        scroll.scroll_down(1);
+        assert_eq!(scroll.offset(), 19);
+        assert!(!scroll.is_animating());
+    }
+
+    #[test]
+    fn half_pages_and_keys() {
+        let mut scroll = measured(100, 10);
+        let key = |code, modifiers| scroll_key(&KeyEvent::new(code, modifiers));
+        let ctrl = KeyModifiers::CONTROL;
+        for action in [
+            key(KeyCode::Char('d'), ctrl),
+            key(KeyCode::Char('d'), ctrl),
+            key(KeyCode::Char('u'), ctrl),
+            key(KeyCode::Char('f'), ctrl),
+        ] {
+            scroll.apply(&action.unwrap());
+        }
+        while scroll.tick() {}
+        assert_eq!(scroll.offset(), 5 + 9);
+
+        scroll.apply(&key(KeyCode::Char('G'), KeyModifiers::SHIFT).unwrap());
+        assert_eq!(scroll.offset(), 90);
+        scroll.apply(&key(KeyCode::Char('b'), ctrl).unwrap());
+        while scroll.tick() {}
+        assert_eq!(scroll.offset(), 81);
+        scroll.apply(&key(KeyCode::Home, KeyModifiers::empty()).unwrap());
+        assert_eq!(scroll.offset(), 0);
+        assert_eq!(key(KeyCode::Char('d'), KeyModifiers::empty()), None);
+    }
+
+    #[test]
+    fn margins_and_centering() {
+        let mut scroll = measured(100, 10);
+        scroll.ensure_visible(12, 2);
+        assert_eq!(scroll.visible_range(), 5..15);
+        scroll.ensure_visible(4, 2);
+        assert_eq!(scroll.offset(), 2);
+        // Already comfortably visible: no movement
+        scroll.ensure_visible(6, 2);
+        assert_eq!(scroll.offset(), 2);
+
+        scroll.center_on(50);
+        assert_eq!(scroll.visible_range(), 45..55);
+        scroll.center_on(99);
+        assert_eq!(scroll.offset(), 90);
+    }
+}
@@ -0,0 +1,143 @@
+//! Popup for editing common config values while the app runs. Each row edits one
+//! `Setting`; on/off rows flip with Enter, the others are typed.
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::config::AppConfig;
+use crate::config::settings::Setting;
+
+/// What the panel asks the app to do after a key press.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingsOutcome {
+    /// Keep the panel open
+    Pending,
+    Close,
+    /// The setting changed in the config; save and apply it. The panel stays open
+    Changed(Setting),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SettingsPanel {
+    pub selected: usize,
+    /// Text being typed for the selected row
+    pub editing: Option<String>,
+    /// Why the last edit was rejected
+    pub error: Option<String>,
+}
+
+impl SettingsPanel {
+    pub fn setting(&self) -> Setting {
+        Setting::ALL[self.selected]
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, config: &mut AppConfig) -> SettingsOutcome {
+        if let Some(text) = &mut self.editing {
+            match key.code {
+                KeyCode::Esc => {
+                    self.editing = None;
+                    self.error = None;
+                }
+                KeyCode::Enter => {
+                    let text = text.clone();
+                    return match self.setting().set(config, &text) {
+                        Ok(()) => {
+                            self.editing = None;
+                            self.error = None;
+                            SettingsOutcome::Changed(self.setting())
+                        }
+                        Err(error) => {
+                            self.error = Some(error);
+                            SettingsOutcome::Pending
+                        }
+                    };
+                }
+                KeyCode::Backspace => {
+                    text.pop();
+                }
+                KeyCode::Char(c) => text.push(c),
+                _ => {}
+            }
+            return SettingsOutcome::Pending;
+        }
+
+        let rows = Setting::ALL.len();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char(',') => SettingsOutcome::Close,
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected = (self.selected + 1) % rows;
+                SettingsOutcome::Pending
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = (self.selected + rows - 1) % rows;
+                SettingsOutcome::Pending
+            }
+            KeyCode::Enter | KeyCode::Char(' ') if self.setting().is_toggle() => {
+                self.setting().toggle(config);
+                SettingsOutcome::Changed(self.setting())
+            }
+            KeyCode::Enter => {
+                self.editing = Some(self.setting().value(config));
+                SettingsOutcome::Pending
+            }
+            _ => SettingsOutcome::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn press(panel: &mut SettingsPanel, config: &mut AppConfig, code: KeyCode) -> SettingsOutcome {
+        panel.handle_key(KeyEvent::new(code, KeyModifiers::empty()), config)
+    }
+
+    #[test]
+    fn edits_and_toggles_settings() {
+        let mut config = AppConfig::default();
+        let mut panel = SettingsPanel::default();
+
+        // Typed values start from the current one and are checked
+        press(&mut panel, &mut config, KeyCode::Enter);
+        assert_eq!(
+            panel.editing,
+            Some(config.ui.padding.horizontal.to_string())
+        );
+        press(&mut panel, &mut config, KeyCode::Backspace);
+        press(&mut panel, &mut config, KeyCode::Char('x'));
+        assert_eq!(
+            press(&mut panel, &mut config, KeyCode::Enter),
+            SettingsOutcome::Pending
+        );
+        assert!(panel.error.is_some());
+        press(&mut panel, &mut config, KeyCode::Backspace);
+        press(&mut panel, &mut config, KeyCode::Char('4'));
+        assert_eq!(
+            press(&mut panel, &mut config, KeyCode::Enter),
+            SettingsOutcome::Changed(Setting::PaddingHorizontal)
+        );
+        assert_eq!(config.ui.padding.horizontal, 4);
+        assert_eq!(panel.error, None);
+
+        // On/off rows flip without typing
+        press(&mut panel, &mut config, KeyCode::Char('j'));
+        press(&mut panel, &mut config, KeyCode::Char('j'));
+        let shown = config.ui.list_view.show_score;
+        assert_eq!(
+            press(&mut panel, &mut config, KeyCode::Enter),
+            SettingsOutcome::Changed(Setting::ShowScore)
+        );
+        assert_eq!(config.ui.list_view.show_score, !shown);
+        assert_eq!(panel.editing, None);
+
+        press(&mut panel, &mut config, KeyCode::Char('k'));
+        press(&mut panel, &mut config, KeyCode::Char('k'));
+        press(&mut panel, &mut config, KeyCode::Char('k'));
+        assert_eq!(panel.setting(), Setting::RequestTimeout);
+        assert_eq!(
+            press(&mut panel, &mut config, KeyCode::Esc),
+            SettingsOutcome::Close
+        );
+    }
+}
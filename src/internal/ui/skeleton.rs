@@ -0,0 +1,138 @@
+//! Skeleton placeholders rendered while content is loading.
+//!
+//! Instead of a bare spinner, loading views draw gray bars that approximate the
+//! layout of the content that is about to arrive (story rows, comment blocks,
+//! article paragraphs). Bars are sized deterministically from the row index so
+//! the placeholder does not flicker between frames.
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::ListItem,
+};
+
+/// Default number of story rows shown when the expected count is unknown.
+pub const DEFAULT_LIST_ROWS: usize = 20;
+/// Upper bound on comment placeholders (more would be off-screen anyway).
+pub const MAX_COMMENT_BLOCKS: usize = 12;
+/// Number of paragraph placeholders shown for an article.
+pub const ARTICLE_PARAGRAPHS: usize = 8;
+
+const BAR: char = '▆';
+
+/// Relative bar widths (in percent) cycled through to make rows look natural.
+const WIDTH_PATTERN: [usize; 8] = [82, 64, 91, 57, 73, 88, 69, 78];
+
+fn bar_style(color: Color) -> Style {
+    Style::default().fg(color).add_modifier(Modifier::DIM)
+}
+
+/// Width of the bar for row `index` given the available width.
+fn bar_width(index: usize, available: usize) -> usize {
+    let pct = WIDTH_PATTERN[index % WIDTH_PATTERN.len()];
+    (available * pct / 100).max(4).min(available.max(1))
+}
+
+fn bar(len: usize) -> String {
+    std::iter::repeat_n(BAR, len).collect()
+}
+
+/// Build `count` list placeholders mirroring the two-line story row layout
+/// (index + title on the first line, indented metadata on the second).
+pub fn list_items(count: usize, width: u16, color: Color) -> Vec<ListItem<'static>> {
+    let available = (width as usize).saturating_sub(10);
+    (0..count)
+        .map(|i| {
+            let title = Line::from(vec![
+                Span::raw("    "),
+                Span::styled(bar(bar_width(i, available)), bar_style(color)),
+            ]);
+            let meta = Line::from(vec![
+                Span::raw("    "),
+                Span::styled(bar(bar_width(i + 3, available / 3)), bar_style(color)),
+            ]);
+            ListItem::new(vec![title, meta])
+        })
+        .collect()
+}
+
+/// Build placeholder lines for `count` comment blocks: an author line, a couple of
+/// text lines and the separator used by the real comment renderer.
+pub fn comment_lines(count: usize, width: usize, color: Color) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for i in 0..count {
+        // Alternate between top-level and nested placeholders
+        let depth = match i % 3 {
+            1 => 1,
+            _ => 0,
+        };
+        let indent = "  ".repeat(depth);
+        let available = width.saturating_sub(depth * 2);
+
+        lines.push(Line::from(vec![
+            Span::raw(indent.clone()),
+            Span::styled(bar(bar_width(i, available / 4)), bar_style(color)),
+        ]));
+        for row in 0..2 {
+            lines.push(Line::from(vec![
+                Span::raw(indent.clone()),
+                Span::styled(bar(bar_width(i + row + 1, available)), bar_style(color)),
+            ]));
+        }
+        lines.push(Line::from(Span::styled("---", bar_style(color))));
+        lines.push(Line::from(""));
+    }
+    lines
+}
+
+/// Build placeholder lines for an article: a heading bar followed by paragraphs.
+pub fn article_lines(width: usize, color: Color) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(Span::styled(bar(bar_width(3, width / 2)), bar_style(color))),
+        Line::from(""),
+    ];
+    for i in 0..ARTICLE_PARAGRAPHS {
+        let rows = 2 + i % 3;
+        for row in 0..rows {
+            let len = match row + 1 == rows {
+                true => bar_width(i + row, width / 2),
+                false => bar_width(i + row, width),
+            };
+            lines.push(Line::from(Span::styled(bar(len), bar_style(color))));
+        }
+        lines.push(Line::from(""));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_items_match_requested_count() {
+        assert_eq!(list_items(7, 80, Color::Gray).len(), 7);
+        assert!(list_items(0, 80, Color::Gray).is_empty());
+    }
+
+    #[test]
+    fn comment_lines_follow_block_layout() {
+        // author + 2 text lines + separator + spacer per block
+        assert_eq!(comment_lines(3, 60, Color::Gray).len(), 15);
+    }
+
+    #[test]
+    fn bars_never_exceed_available_width() {
+        for i in 0..20 {
+            assert!(bar_width(i, 40) <= 40);
+        }
+        assert_eq!(bar_width(0, 0), 1);
+    }
+
+    #[test]
+    fn article_lines_are_stable_between_calls() {
+        let a = article_lines(50, Color::Gray);
+        let b = article_lines(50, Color::Gray);
+        assert_eq!(a, b);
+    }
+}
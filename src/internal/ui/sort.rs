@@ -1,14 +1,17 @@
 use crate::internal::models::Story;
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum SortBy {
     Score,
     Comments,
     Time,
+    /// HN's front-page ranking, computed from score, age and penalties
+    Rank,
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum SortOrder {
     Ascending,
     Descending,
@@ -29,6 +32,37 @@ fn cmp_time(a: &Story, b: &Story) -> Ordering {
     a.time.cmp(&b.time)
 }
 
+/// How fast stories fall off the front page as they age.
+const GRAVITY: f64 = 1.8;
+/// Stories without a link (Ask HN and other text posts) rank lower.
+const NO_URL_FACTOR: f64 = 0.4;
+/// Comments above which a discussion outpacing the votes counts as a flamewar.
+const CONTROVERSY_COMMENTS: u32 = 20;
+
+/// The front-page rank score of `story` at `now` (Unix seconds), as HN computes it:
+/// `(score - 1)^0.8 / (hours + 2)^1.8`, lowered for stories without a link and
+/// for those with more than 20 comments and fewer votes than comments. HN also
+/// applies penalties by hand and for some domains, which the API does not show.
+pub fn rank_score(story: &Story, now: i64) -> f64 {
+    let points = f64::from(story.score.unwrap_or(0)) - 1.0;
+    let base = match points > 0.0 {
+        true => points.powf(0.8),
+        false => points,
+    };
+    let hours = (now - story.time.unwrap_or(now)).max(0) as f64 / 3600.0;
+    let penalty = match (story.is_job(), story.url.is_none(), story.descendants) {
+        (true, _, _) => 1.0,
+        (false, true, _) => NO_URL_FACTOR,
+        (false, false, Some(comments)) if comments > CONTROVERSY_COMMENTS => {
+            (f64::from(story.score.unwrap_or(0)) / f64::from(comments))
+                .powi(2)
+                .min(1.0)
+        }
+        _ => 1.0,
+    };
+    base / (hours + 2.0).powf(GRAVITY) * penalty
+}
+
 /// Apply the requested sort order (ascending/descending) to a base Ordering.
 fn apply_ordering(ord: Ordering, sort_order: SortOrder) -> Ordering {
     match sort_order {
@@ -37,13 +71,16 @@ fn apply_ordering(ord: Ordering, sort_order: SortOrder) -> Ordering {
     }
 }
 
-/// Sort stories in-place based on the specified criteria and order
+/// Sort stories in-place based on the specified criteria and order.
+/// The sort is stable so stories with equal keys keep their API ranking.
 pub fn sort_stories(stories: &mut [Story], sort_by: SortBy, sort_order: SortOrder) {
-    stories.sort_unstable_by(|a, b| {
+    let now = jiff::Timestamp::now().as_second();
+    stories.sort_by(|a, b| {
         let base = match sort_by {
             SortBy::Score => cmp_score(a, b),
             SortBy::Comments => cmp_comments(a, b),
             SortBy::Time => cmp_time(a, b),
+            SortBy::Rank => rank_score(a, now).total_cmp(&rank_score(b, now)),
         };
         apply_ordering(base, sort_order)
     });
@@ -177,6 +214,35 @@ mod tests {
         assert_eq!(stories[2].id, 2);
     }
 
+    #[test]
+    fn rank_score_weighs_votes_against_age_and_penalties() {
+        let hour = 3600;
+        let story = |score, age_hours, url: bool, comments| Story {
+            id: 1,
+            score: Some(score),
+            time: Some(100 * hour - age_hours * hour),
+            url: url.then(|| "https://example.com".to_string()),
+            descendants: Some(comments),
+            ..Default::default()
+        };
+        let now = 100 * hour;
+
+        // 101 points two hours in: 100^0.8 / 4^1.8
+        let score = rank_score(&story(101, 2, true, 0), now);
+        assert!((score - 100f64.powf(0.8) / 4f64.powf(1.8)).abs() < 1e-9);
+        // A fresh story with fewer votes outranks an old one with more
+        assert!(
+            rank_score(&story(20, 0, true, 0), now) > rank_score(&story(200, 24, true, 0), now)
+        );
+        // Text posts and flamewars are held back
+        let text_post = rank_score(&story(101, 2, false, 0), now);
+        assert!((text_post - score * NO_URL_FACTOR).abs() < 1e-9);
+        assert!(rank_score(&story(101, 2, true, 202), now) < score / 3.0);
+        assert_eq!(rank_score(&story(101, 2, true, 20), now), score);
+        // A story with a single point has nothing to rank on
+        assert_eq!(rank_score(&story(1, 0, true, 0), now), 0.0);
+    }
+
     #[test]
     fn sort_with_load_more_combines_and_sorts() {
         // Initial two stories
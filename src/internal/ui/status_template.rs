@@ -0,0 +1,367 @@
+//! Status bar and message line laid out by `ui.status_bar_format` and
+//! `ui.message_line_format`, such as `"{mode} | {category}{|}{?search:/{search} }{count}/{total}"`.
+//!
+//! The format is parsed once, when the config is loaded, into sections of text and
+//! tokens; drawing the bar only fills the tokens in. `{|}` splits the bar into
+//! sections: one marker puts what follows on the right, two make left, center and
+//! right sections. `{?token:...}` shows what it encloses only while `token` has a
+//! value, and a token can have a width and alignment as in row templates, with a
+//! range for a minimum and maximum, as in `{theme:..12}` or `{count:>3..}`.
+//! `{{` and `}}` are literal braces.
+
+use std::iter::Peekable;
+use std::str::Chars;
+
+use textwrap::core::display_width;
+
+use super::row_template::{Align, truncate};
+
+/// What the bar can show.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Token {
+    Mode,
+    Category,
+    Count,
+    Total,
+    Sort,
+    Order,
+    Search,
+    Spinner,
+    LoadingText,
+    Theme,
+    Shortcuts,
+    /// The newest notification
+    Message,
+    /// Match count of the active search
+    Matches,
+    /// Pending chord prefix and the keys that can follow it
+    Chord,
+    Bookmarks,
+    /// Stories of the list not read yet
+    Unread,
+    CacheHitRate,
+    /// Requests waiting for the rate limit
+    RateLimit,
+    /// Value set by a user script with `set_status(name, value)`
+    Script(String),
+}
+
+impl Token {
+    const ALL: [(&'static str, Token); 18] = [
+        ("mode", Token::Mode),
+        ("category", Token::Category),
+        ("count", Token::Count),
+        ("total", Token::Total),
+        ("sort", Token::Sort),
+        ("order", Token::Order),
+        ("search", Token::Search),
+        ("spinner", Token::Spinner),
+        ("loading_text", Token::LoadingText),
+        ("theme", Token::Theme),
+        ("shortcuts", Token::Shortcuts),
+        ("message", Token::Message),
+        ("matches", Token::Matches),
+        ("chord", Token::Chord),
+        ("bookmarks", Token::Bookmarks),
+        ("unread", Token::Unread),
+        ("cache_hit_rate", Token::CacheHitRate),
+        ("rate_limit", Token::RateLimit),
+    ];
+
+    /// `name`, or `script:name` for script values.
+    fn from_name(name: &str) -> Result<Self, String> {
+        if let Some(script) = name.strip_prefix("script:") {
+            return Ok(Token::Script(script.to_string()));
+        }
+        Self::ALL
+            .iter()
+            .find(|(known, _)| *known == name)
+            .map(|(_, token)| token.clone())
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(|(name, _)| *name).collect();
+                format!(
+                    "unknown token `{{{}}}`; known tokens: {}, script:name",
+                    name,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Text(String),
+    Token {
+        token: Token,
+        align: Align,
+        min: usize,
+        max: Option<usize>,
+    },
+    /// Shown only while `token` has a value
+    Conditional {
+        token: Token,
+        body: Vec<Part>,
+    },
+}
+
+/// A parsed status bar format: its left, center and right sections.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusTemplate {
+    sections: Vec<Vec<Part>>,
+}
+
+impl StatusTemplate {
+    pub fn parse(format: &str) -> Result<Self, String> {
+        let mut chars = format.chars().peekable();
+        let sections = parse_parts(&mut chars, false)?;
+        if sections.len() > 3 {
+            return Err("at most two `{|}` markers (left, center and right)".to_string());
+        }
+        Ok(Self { sections })
+    }
+
+    /// True when the format has no `{|}`, so the text starts at the left and is
+    /// only as wide as its content.
+    pub fn is_single(&self) -> bool {
+        self.sections.len() == 1
+    }
+
+    /// The bar filled in by `value`, laid out on `width` columns: the left section
+    /// first, the center one in the middle and the right one against the edge. The
+    /// left section is cut when they do not all fit.
+    pub fn render(&self, width: usize, value: impl Fn(&Token) -> String) -> String {
+        let texts: Vec<String> = self
+            .sections
+            .iter()
+            .map(|parts| render_parts(parts, &value))
+            .collect();
+        let (left, center, right) = match texts.as_slice() {
+            [left] => return left.clone(),
+            [left, right] => (left.as_str(), "", right.as_str()),
+            [left, center, right, ..] => (left.as_str(), center.as_str(), right.as_str()),
+            [] => return String::new(),
+        };
+        let (center_width, right_width) = (display_width(center), display_width(right));
+        let gaps = usize::from(!center.is_empty()) + 1;
+        let left = truncate(
+            left,
+            width.saturating_sub(center_width + right_width + gaps),
+        );
+        let left_width = display_width(&left);
+        let mut line = left;
+        if !center.is_empty() {
+            let start = width.saturating_sub(center_width) / 2;
+            line.push_str(&" ".repeat(start.saturating_sub(left_width).max(1)));
+            line.push_str(center);
+        }
+        let used = display_width(&line);
+        line.push_str(&" ".repeat(width.saturating_sub(used + right_width).max(1)));
+        line.push_str(right);
+        line
+    }
+}
+
+/// Sections of text and tokens up to the end, or up to the `}` closing a
+/// conditional when `nested`.
+fn parse_parts(chars: &mut Peekable<Chars>, nested: bool) -> Result<Vec<Vec<Part>>, String> {
+    let mut sections = vec![Vec::new()];
+    let mut text = String::new();
+    let flush = |sections: &mut Vec<Vec<Part>>, text: &mut String| {
+        if !text.is_empty() {
+            sections
+                .last_mut()
+                .expect("at least one section")
+                .push(Part::Text(std::mem::take(text)));
+        }
+    };
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+            '}' if nested => {
+                flush(&mut sections, &mut text);
+                return Ok(sections);
+            }
+            '}' => return Err("unmatched `}` (write `}}` for a brace)".to_string()),
+            '{' if chars.peek() == Some(&'?') => {
+                chars.next();
+                let name: String = chars.by_ref().take_while(|&c| c != ':').collect();
+                let token = Token::from_name(name.trim())?;
+                let mut body =
+                    parse_parts(chars, true).map_err(|e| format!("in `{{?{}:…}}`: {}", name, e))?;
+                if body.len() > 1 {
+                    return Err(format!("`{{|}}` inside `{{?{}:…}}`", name));
+                }
+                flush(&mut sections, &mut text);
+                sections
+                    .last_mut()
+                    .expect("at least one section")
+                    .push(Part::Conditional {
+                        token,
+                        body: body.remove(0),
+                    });
+            }
+            '{' => {
+                let mut spec = String::new();
+                let mut closed = false;
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        closed = true;
+                        break;
+                    }
+                    spec.push(c);
+                }
+                if !closed {
+                    return Err(format!("unclosed `{{{}`", spec));
+                }
+                flush(&mut sections, &mut text);
+                match spec.as_str() {
+                    "|" => sections.push(Vec::new()),
+                    _ => sections
+                        .last_mut()
+                        .expect("at least one section")
+                        .push(parse_token(&spec)?),
+                }
+            }
+            c => text.push(c),
+        }
+    }
+    if nested {
+        return Err("unclosed conditional".to_string());
+    }
+    flush(&mut sections, &mut text);
+    Ok(sections)
+}
+
+/// `name`, `name:12`, `name:>4`, `name:..20`, `name:^8..16`, `script:name:..10`, ...
+fn parse_token(spec: &str) -> Result<Part, String> {
+    let (name, format) = match spec.strip_prefix("script:") {
+        Some(rest) => {
+            let (script, format) = rest.split_once(':').unwrap_or((rest, ""));
+            (format!("script:{}", script), format)
+        }
+        None => {
+            let (name, format) = spec.split_once(':').unwrap_or((spec, ""));
+            (name.trim().to_string(), format)
+        }
+    };
+    let token = Token::from_name(&name)?;
+    let (align, rest) = match format.chars().next() {
+        Some('<') => (Align::Left, &format[1..]),
+        Some('>') => (Align::Right, &format[1..]),
+        Some('^') => (Align::Center, &format[1..]),
+        _ => (Align::Left, format),
+    };
+    let number = |digits: &str| -> Result<Option<usize>, String> {
+        match digits {
+            "" => Ok(None),
+            digits => digits
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("invalid width `{}` in `{{{}}}`", digits, spec)),
+        }
+    };
+    let (min, max) = match rest.split_once("..") {
+        Some((min, max)) => (number(min)?.unwrap_or(0), number(max)?),
+        None => {
+            let fixed = number(rest)?;
+            (fixed.unwrap_or(0), fixed)
+        }
+    };
+    if max.is_some_and(|max| max < min) {
+        return Err(format!("minimum above maximum in `{{{}}}`", spec));
+    }
+    Ok(Part::Token {
+        token,
+        align,
+        min,
+        max,
+    })
+}
+
+fn render_parts(parts: &[Part], value: &impl Fn(&Token) -> String) -> String {
+    parts
+        .iter()
+        .map(|part| match part {
+            Part::Text(text) => text.clone(),
+            Part::Token {
+                token,
+                align,
+                min,
+                max,
+            } => {
+                let text = value(token);
+                let text = match max {
+                    Some(max) => truncate(&text, *max),
+                    None => text,
+                };
+                let gap = min.saturating_sub(display_width(&text));
+                let (left, right) = match align {
+                    Align::Left => (0, gap),
+                    Align::Right => (gap, 0),
+                    Align::Center => (gap / 2, gap - gap / 2),
+                };
+                format!("{}{}{}", " ".repeat(left), text, " ".repeat(right))
+            }
+            Part::Conditional { token, body } => match value(token).is_empty() {
+                true => String::new(),
+                false => render_parts(body, value),
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(format: &str, width: usize) -> String {
+        StatusTemplate::parse(format)
+            .unwrap()
+            .render(width, |token| match token {
+                Token::Mode => "List".to_string(),
+                Token::Count => "7".to_string(),
+                Token::Theme => "solarized-dark".to_string(),
+                Token::Script(name) if name == "weather" => "sunny".to_string(),
+                _ => String::new(),
+            })
+    }
+
+    #[test]
+    fn fills_tokens_widths_and_conditions() {
+        assert_eq!(bar("{mode} | {count}", 80), "List | 7");
+        assert_eq!(bar("[{count:>3}] [{mode:^8}]", 80), "[  7] [  List  ]");
+        assert_eq!(bar("{theme:..6}|{count:2..}|", 80), "solar…|7 |");
+        assert_eq!(bar("{?search:/{search} }{?count:#{count}}", 80), "#7");
+        assert_eq!(bar("{script:weather:..3} {{x}}", 80), "su… {x}");
+    }
+
+    #[test]
+    fn lays_sections_out_across_the_width() {
+        assert_eq!(bar("{mode}{|}{count}", 12), "List       7");
+        assert_eq!(bar("{mode}{|}{count}{|}end", 13), "List  7   end");
+        // The left section gives way when the line is too narrow
+        assert_eq!(bar("{theme}{|}{mode}", 12), "solari… List");
+    }
+
+    #[test]
+    fn reports_malformed_formats() {
+        for format in [
+            "{nope}",
+            "{mode",
+            "a } b",
+            "{?mode:x",
+            "{count:9..2}",
+            "a{|}b{|}c{|}d",
+        ] {
+            assert!(StatusTemplate::parse(format).is_err(), "{}", format);
+        }
+        assert!(StatusTemplate::parse("{?unread:{unread} new}").is_ok());
+    }
+}
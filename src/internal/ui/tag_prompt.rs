@@ -0,0 +1,110 @@
+//! Prompt for the tags of a bookmark, shown when a story is bookmarked and from
+//! the Bookmarks view. Tags are typed comma-separated; `/` nests them in folders.
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use super::text_input::TextInput;
+use crate::internal::bookmarks::parse_tags;
+
+/// What the prompt asks the app to do after a key press.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TagOutcome {
+    Pending,
+    /// Leave the tags as they are
+    Cancel,
+    Save(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+pub struct TagPrompt {
+    pub story_id: u32,
+    pub title: String,
+    pub input: TextInput,
+    /// Tags already in use, offered by Tab
+    pub known: Vec<String>,
+}
+
+impl TagPrompt {
+    pub fn new(story_id: u32, title: String, tags: &[String], known: Vec<String>) -> Self {
+        Self {
+            story_id,
+            title,
+            input: TextInput::new(&tags.join(", ")),
+            known,
+        }
+    }
+
+    /// The tag being typed, after the last comma.
+    pub fn current(&self) -> &str {
+        self.input
+            .text()
+            .rsplit(',')
+            .next()
+            .unwrap_or_default()
+            .trim_start()
+    }
+
+    /// First known tag that starts with the tag being typed.
+    pub fn completion(&self) -> Option<&str> {
+        let current = self.current().to_lowercase();
+        match current.is_empty() {
+            true => None,
+            false => self
+                .known
+                .iter()
+                .find(|tag| tag.starts_with(&current) && **tag != current)
+                .map(String::as_str),
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> TagOutcome {
+        match key.code {
+            KeyCode::Esc => TagOutcome::Cancel,
+            KeyCode::Enter => TagOutcome::Save(parse_tags(self.input.text())),
+            KeyCode::Tab => {
+                if let Some(tag) = self.completion() {
+                    let text = self.input.text();
+                    let completed =
+                        format!("{}{}", &text[..text.len() - self.current().len()], tag);
+                    self.input.set_text(completed);
+                }
+                TagOutcome::Pending
+            }
+            _ => {
+                self.input.handle_key(key);
+                TagOutcome::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn press(prompt: &mut TagPrompt, code: KeyCode) -> TagOutcome {
+        prompt.handle_key(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    #[test]
+    fn completes_known_tags_and_saves_parsed_tags() {
+        let known = vec!["lang/rust".to_string(), "to-read".to_string()];
+        let mut prompt = TagPrompt::new(1, "Story".to_string(), &["ai".to_string()], known);
+        assert_eq!(prompt.input.text(), "ai");
+
+        for c in ", La".chars() {
+            press(&mut prompt, KeyCode::Char(c));
+        }
+        assert_eq!(prompt.completion(), Some("lang/rust"));
+        press(&mut prompt, KeyCode::Tab);
+        assert_eq!(prompt.input.text(), "ai, lang/rust");
+        assert_eq!(prompt.completion(), None);
+
+        assert_eq!(
+            press(&mut prompt, KeyCode::Enter),
+            TagOutcome::Save(vec!["ai".to_string(), "lang/rust".to_string()])
+        );
+        assert_eq!(press(&mut prompt, KeyCode::Esc), TagOutcome::Cancel);
+    }
+}
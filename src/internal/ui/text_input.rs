@@ -0,0 +1,218 @@
+//! Editable text field for popups: a cursor that moves by character, word and
+//! line, and optionally several lines of text.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextInput {
+    text: String,
+    /// Byte offset of the cursor in `text`, always on a character boundary
+    cursor: usize,
+    /// Enter inserts a line break instead of being left to the popup
+    multiline: bool,
+}
+
+impl TextInput {
+    /// A single-line input holding `text`, with the cursor at the end.
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            cursor: text.len(),
+            multiline: false,
+        }
+    }
+
+    pub fn multiline(text: &str) -> Self {
+        Self {
+            multiline: true,
+            ..Self::new(text)
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    #[cfg(test)]
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Replace the text and move the cursor to the end.
+    pub fn set_text(&mut self, text: String) {
+        self.cursor = text.len();
+        self.text = text;
+    }
+
+    pub fn insert(&mut self, c: char) {
+        self.text.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    fn prev_boundary(&self) -> usize {
+        self.text[..self.cursor]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(i, _)| i)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.text[self.cursor..]
+            .chars()
+            .next()
+            .map_or(self.cursor, |c| self.cursor + c.len_utf8())
+    }
+
+    /// Start of the word before the cursor, skipping whitespace first.
+    fn word_start(&self) -> usize {
+        let before = &self.text[..self.cursor];
+        let trimmed = before.trim_end();
+        trimmed
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(0, |(i, c)| i + c.len_utf8())
+    }
+
+    fn line_start(&self) -> usize {
+        self.text[..self.cursor].rfind('\n').map_or(0, |i| i + 1)
+    }
+
+    fn line_end(&self) -> usize {
+        self.text[self.cursor..]
+            .find('\n')
+            .map_or(self.text.len(), |i| self.cursor + i)
+    }
+
+    /// Move to the line above (or below), keeping the column where possible.
+    fn move_line(&mut self, up: bool) {
+        let start = self.line_start();
+        let column = self.text[start..self.cursor].chars().count();
+        let target = match up {
+            true if start > 0 => self.text[..start - 1].rfind('\n').map_or(0, |i| i + 1),
+            false if self.line_end() < self.text.len() => self.line_end() + 1,
+            _ => return,
+        };
+        let line = self.text[target..].split('\n').next().unwrap_or_default();
+        self.cursor = target
+            + line
+                .char_indices()
+                .nth(column)
+                .map_or(line.len(), |(i, _)| i);
+    }
+
+    /// Edit the text for `key`. Returns false for keys the input leaves to the
+    /// popup, such as Esc, Tab and (on a single line) Enter.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('w') | KeyCode::Backspace
+                if ctrl || key.modifiers.contains(KeyModifiers::ALT) =>
+            {
+                let start = self.word_start();
+                self.text.replace_range(start..self.cursor, "");
+                self.cursor = start;
+            }
+            KeyCode::Char('u') if ctrl => {
+                let start = self.line_start();
+                self.text.replace_range(start..self.cursor, "");
+                self.cursor = start;
+            }
+            KeyCode::Char('a') if ctrl => self.cursor = self.line_start(),
+            KeyCode::Char('e') if ctrl => self.cursor = self.line_end(),
+            _ if ctrl => return false,
+            KeyCode::Char(c) => self.insert(c),
+            KeyCode::Enter if self.multiline => self.insert('\n'),
+            KeyCode::Backspace => {
+                let prev = self.prev_boundary();
+                self.text.replace_range(prev..self.cursor, "");
+                self.cursor = prev;
+            }
+            KeyCode::Delete => {
+                let next = self.next_boundary();
+                self.text.replace_range(self.cursor..next, "");
+            }
+            KeyCode::Left => self.cursor = self.prev_boundary(),
+            KeyCode::Right => self.cursor = self.next_boundary(),
+            KeyCode::Home => self.cursor = self.line_start(),
+            KeyCode::End => self.cursor = self.line_end(),
+            KeyCode::Up if self.multiline => self.move_line(true),
+            KeyCode::Down if self.multiline => self.move_line(false),
+            _ => return false,
+        }
+        true
+    }
+
+    /// The text as lines, with the character under the cursor drawn in `cursor_style`.
+    pub fn lines(&self, style: Style, cursor_style: Style) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        let mut offset = 0;
+        for line in self.text.split('\n') {
+            let end = offset + line.len();
+            let spans = match (offset..=end).contains(&self.cursor) {
+                true => {
+                    let at = self.cursor - offset;
+                    let under = line[at..].chars().next();
+                    let after = at + under.map_or(0, char::len_utf8);
+                    vec![
+                        Span::styled(line[..at].to_string(), style),
+                        Span::styled(under.unwrap_or(' ').to_string(), cursor_style),
+                        Span::styled(line[after..].to_string(), style),
+                    ]
+                }
+                false => vec![Span::styled(line.to_string(), style)],
+            };
+            lines.push(Line::from(spans));
+            offset = end + 1;
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(input: &mut TextInput, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        input.handle_key(KeyEvent::new(code, modifiers))
+    }
+
+    #[test]
+    fn edits_at_the_cursor_and_moves_between_lines() {
+        let none = KeyModifiers::NONE;
+        let mut input = TextInput::multiline("héllo");
+        press(&mut input, KeyCode::Left, none);
+        press(&mut input, KeyCode::Left, none);
+        press(&mut input, KeyCode::Backspace, none);
+        assert_eq!(input.text(), "hélo");
+        press(&mut input, KeyCode::End, none);
+        press(&mut input, KeyCode::Enter, none);
+        for c in "second line".chars() {
+            press(&mut input, KeyCode::Char(c), none);
+        }
+        press(&mut input, KeyCode::Backspace, KeyModifiers::CONTROL);
+        assert_eq!(input.text(), "hélo\nsecond ");
+
+        // Up keeps the column, clamped to the shorter line
+        press(&mut input, KeyCode::Up, none);
+        assert_eq!(input.cursor(), "hélo".len());
+        press(&mut input, KeyCode::Home, none);
+        press(&mut input, KeyCode::Down, none);
+        assert_eq!(input.cursor(), "hélo\n".len());
+        press(&mut input, KeyCode::Delete, none);
+        assert_eq!(input.text(), "hélo\necond ");
+
+        // A single-line input leaves Enter and Esc to the popup
+        let mut single = TextInput::new("a");
+        assert!(!press(&mut single, KeyCode::Enter, none));
+        assert!(!press(&mut single, KeyCode::Esc, none));
+        assert!(press(
+            &mut single,
+            KeyCode::Char('u'),
+            KeyModifiers::CONTROL
+        ));
+        assert_eq!(single.text(), "");
+    }
+}
@@ -1,3 +1,4 @@
+use crate::utils::contrast::{self, AA_TEXT_CONTRAST, UI_CONTRAST};
 use crate::utils::theme_loader::TuiTheme;
 use ratatui::style::Color;
 
@@ -76,17 +77,105 @@ impl ThemeProperty {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
-#[allow(dead_code)]
 pub enum ColorChannel {
     Red,
     Green,
     Blue,
+    Hue,
+    Saturation,
+    Lightness,
 }
 
+/// What the sliders edit: red/green/blue, or hue/saturation/lightness.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorMode {
+    Rgb,
+    Hsl,
+}
+
+impl ColorMode {
+    pub fn channels(&self) -> [ColorChannel; 3] {
+        match self {
+            ColorMode::Rgb => [ColorChannel::Red, ColorChannel::Green, ColorChannel::Blue],
+            ColorMode::Hsl => [
+                ColorChannel::Hue,
+                ColorChannel::Saturation,
+                ColorChannel::Lightness,
+            ],
+        }
+    }
+}
+
+/// Hue in degrees (0-360), saturation and lightness as 0-1.
+pub type Hsl = (f64, f64, f64);
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum EditorState {
     Editing,
     Naming,
+    /// Typing a hex color for the selected property
+    HexInput,
+}
+
+/// Text colors and the background they are read on, with the contrast they need.
+const CONTRAST_PAIRS: [(ThemeProperty, ThemeProperty, f64); 7] = [
+    (
+        ThemeProperty::Foreground,
+        ThemeProperty::Background,
+        AA_TEXT_CONTRAST,
+    ),
+    (
+        ThemeProperty::SelectionFg,
+        ThemeProperty::SelectionBg,
+        AA_TEXT_CONTRAST,
+    ),
+    (
+        ThemeProperty::Link,
+        ThemeProperty::Background,
+        AA_TEXT_CONTRAST,
+    ),
+    (
+        ThemeProperty::Score,
+        ThemeProperty::Background,
+        AA_TEXT_CONTRAST,
+    ),
+    (
+        ThemeProperty::CommentAuthor,
+        ThemeProperty::Background,
+        AA_TEXT_CONTRAST,
+    ),
+    (
+        ThemeProperty::CommentTime,
+        ThemeProperty::Background,
+        AA_TEXT_CONTRAST,
+    ),
+    (
+        ThemeProperty::Border,
+        ThemeProperty::Background,
+        UI_CONTRAST,
+    ),
+];
+
+/// A color pair of the edited theme below the contrast it needs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContrastWarning {
+    pub foreground: ThemeProperty,
+    pub background: ThemeProperty,
+    pub ratio: f64,
+    pub needed: f64,
+}
+
+impl std::fmt::Display for ContrastWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} on {}: {:.1}:1, needs {:.1}:1",
+            self.foreground.name(),
+            self.background.name(),
+            self.ratio,
+            self.needed
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -94,11 +183,15 @@ pub struct ThemeEditor {
     pub active: bool,
     pub state: EditorState,
     pub selected_property: usize,
-    #[allow(dead_code)]
     pub selected_channel: ColorChannel,
+    pub mode: ColorMode,
+    /// HSL of the selected color while in HSL mode, kept apart from its RGB so
+    /// the hue survives passing through gray, black or white
+    pub hsl: Hsl,
     pub editing: bool,
     pub temp_theme: TuiTheme,
     pub name_input: String,
+    pub hex_input: String,
 }
 
 impl ThemeEditor {
@@ -108,9 +201,12 @@ impl ThemeEditor {
             state: EditorState::Editing,
             selected_property: 0,
             selected_channel: ColorChannel::Red,
+            mode: ColorMode::Rgb,
+            hsl: (0.0, 0.0, 0.5),
             editing: false,
             temp_theme: current_theme,
             name_input: String::new(),
+            hex_input: String::new(),
         }
     }
 
@@ -123,39 +219,74 @@ impl ThemeEditor {
             self.editing = false;
             self.state = EditorState::Editing;
             self.name_input.clear();
+            self.sync_hsl();
         }
     }
 
-    #[allow(dead_code)]
     pub fn navigate_property(&mut self, delta: i32) {
         let properties = ThemeProperty::all();
         let new_index =
             (self.selected_property as i32 + delta).rem_euclid(properties.len() as i32) as usize;
         self.selected_property = new_index;
+        self.sync_hsl();
     }
 
-    #[allow(dead_code)]
     pub fn navigate_channel(&mut self, next: bool) {
-        self.selected_channel = match (self.selected_channel, next) {
-            (ColorChannel::Red, true) => ColorChannel::Green,
-            (ColorChannel::Green, true) => ColorChannel::Blue,
-            (ColorChannel::Blue, true) => ColorChannel::Red,
-            (ColorChannel::Red, false) => ColorChannel::Blue,
-            (ColorChannel::Green, false) => ColorChannel::Red,
-            (ColorChannel::Blue, false) => ColorChannel::Green,
+        let channels = self.mode.channels();
+        let current = channels
+            .iter()
+            .position(|c| *c == self.selected_channel)
+            .unwrap_or(0);
+        let step = match next {
+            true => 1,
+            false => channels.len() - 1,
         };
+        self.selected_channel = channels[(current + step) % channels.len()];
+    }
+
+    /// Switch between RGB and HSL sliders.
+    pub fn toggle_mode(&mut self) {
+        self.mode = match self.mode {
+            ColorMode::Rgb => ColorMode::Hsl,
+            ColorMode::Hsl => ColorMode::Rgb,
+        };
+        self.selected_channel = self.mode.channels()[0];
+        self.sync_hsl();
+    }
+
+    /// RGB of the selected color; named colors use the common xterm palette and
+    /// `Reset` reads as mid gray.
+    pub fn current_rgb(&self) -> (u8, u8, u8) {
+        self.get_current_property()
+            .and_then(|property| contrast::rgb(property.get_color(&self.temp_theme)))
+            .unwrap_or((128, 128, 128))
+    }
+
+    fn sync_hsl(&mut self) {
+        self.hsl = rgb_to_hsl(self.current_rgb());
     }
 
-    #[allow(dead_code)]
     pub fn adjust_color(&mut self, increase: bool) {
-        let properties = ThemeProperty::all();
-        if self.selected_property >= properties.len() {
+        let Some(property) = self.get_current_property() else {
+            return;
+        };
+        if self.mode == ColorMode::Hsl {
+            let (h, s, l) = self.hsl;
+            let sign = match increase {
+                true => 1.0,
+                false => -1.0,
+            };
+            self.hsl = match self.selected_channel {
+                ColorChannel::Hue => ((h + sign * 5.0).rem_euclid(360.0), s, l),
+                ColorChannel::Saturation => (h, (s + sign * 0.02).clamp(0.0, 1.0), l),
+                _ => (h, s, (l + sign * 0.02).clamp(0.0, 1.0)),
+            };
+            let (r, g, b) = hsl_to_rgb(self.hsl);
+            property.set_color(&mut self.temp_theme, Color::Rgb(r, g, b));
             return;
         }
 
-        let property = properties[self.selected_property];
         let current_color = property.get_color(&self.temp_theme);
-
         match current_color {
             Color::Rgb(r, g, b) => {
                 let (new_r, new_g, new_b) = match self.selected_channel {
@@ -175,7 +306,7 @@ impl ThemeEditor {
                         };
                         (r, new_g, b)
                     }
-                    ColorChannel::Blue => {
+                    _ => {
                         let new_b = if increase {
                             b.saturating_add(5)
                         } else {
@@ -187,13 +318,45 @@ impl ThemeEditor {
                 property.set_color(&mut self.temp_theme, Color::Rgb(new_r, new_g, new_b));
             }
             _ => {
-                // Convert non-RGB colors to RGB(128, 128, 128) as starting point
-                property.set_color(&mut self.temp_theme, Color::Rgb(128, 128, 128));
+                // Named colors start from their usual RGB value, `Reset` from gray
+                let (r, g, b) = self.current_rgb();
+                property.set_color(&mut self.temp_theme, Color::Rgb(r, g, b));
             }
         }
     }
 
-    #[allow(dead_code)]
+    /// Set the selected color from `hex_input`. False if it is not a color.
+    pub fn apply_hex(&mut self) -> bool {
+        match (self.get_current_property(), parse_hex(&self.hex_input)) {
+            (Some(property), Some((r, g, b))) => {
+                property.set_color(&mut self.temp_theme, Color::Rgb(r, g, b));
+                self.sync_hsl();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Text colors of the edited theme that are hard to read on their background:
+    /// WCAG AA for text, 3:1 for the border. Colors left to the terminal (`Reset`)
+    /// are not checked.
+    pub fn contrast_warnings(&self) -> Vec<ContrastWarning> {
+        CONTRAST_PAIRS
+            .iter()
+            .filter_map(|&(foreground, background, needed)| {
+                let fg = contrast::rgb(foreground.get_color(&self.temp_theme))?;
+                let bg = contrast::rgb(background.get_color(&self.temp_theme))?;
+                let ratio = contrast::contrast_ratio(fg, bg);
+                (ratio < needed).then_some(ContrastWarning {
+                    foreground,
+                    background,
+                    ratio,
+                    needed,
+                })
+            })
+            .collect()
+    }
+
     pub fn get_current_property(&self) -> Option<ThemeProperty> {
         let properties = ThemeProperty::all();
         properties.get(self.selected_property).copied()
@@ -244,3 +407,98 @@ impl ThemeEditor {
         new_theme
     }
 }
+
+/// `#rrggbb`, `rrggbb` or the short `#rgb`.
+pub fn parse_hex(text: &str) -> Option<(u8, u8, u8)> {
+    let hex = text.trim().trim_start_matches('#');
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |digits: &str| u8::from_str_radix(digits, 16).ok();
+    match hex.len() {
+        6 => Some((
+            channel(&hex[0..2])?,
+            channel(&hex[2..4])?,
+            channel(&hex[4..6])?,
+        )),
+        3 => {
+            let short = |i: usize| channel(&hex[i..i + 1]).map(|v| v * 17);
+            Some((short(0)?, short(1)?, short(2)?))
+        }
+        _ => None,
+    }
+}
+
+pub fn rgb_to_hsl((r, g, b): (u8, u8, u8)) -> Hsl {
+    let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+    if d == 0.0 {
+        return (0.0, 0.0, l);
+    }
+    let s = d / (1.0 - (2.0 * l - 1.0).abs());
+    let h = match max {
+        m if m == r => 60.0 * ((g - b) / d).rem_euclid(6.0),
+        m if m == g => 60.0 * ((b - r) / d + 2.0),
+        _ => 60.0 * ((r - g) / d + 4.0),
+    };
+    (h, s, l)
+}
+
+pub fn hsl_to_rgb((h, s, l): Hsl) -> (u8, u8, u8) {
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let x = c * (1.0 - ((h / 60.0).rem_euclid(2.0) - 1.0).abs());
+    let m = l - c / 2.0;
+    let (r, g, b) = match (h.rem_euclid(360.0) / 60.0) as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let to_u8 = |v: f64| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    (to_u8(r), to_u8(g), to_u8(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edits_in_hsl_and_hex() {
+        assert_eq!(parse_hex("#1e90ff"), Some((30, 144, 255)));
+        assert_eq!(parse_hex("fa0"), Some((255, 170, 0)));
+        assert_eq!(parse_hex("#12345"), None);
+        for rgb in [(30, 144, 255), (255, 170, 0), (128, 128, 128), (0, 0, 0)] {
+            assert_eq!(hsl_to_rgb(rgb_to_hsl(rgb)), rgb);
+        }
+
+        let mut editor = ThemeEditor::new(TuiTheme {
+            background: Color::Rgb(255, 255, 255),
+            foreground: Color::Rgb(20, 20, 20),
+            ..TuiTheme::default()
+        });
+        // Foreground, in HSL: lightness up by 2%
+        editor.navigate_property(1);
+        editor.toggle_mode();
+        assert_eq!(editor.selected_channel, ColorChannel::Hue);
+        editor.navigate_channel(false);
+        assert_eq!(editor.selected_channel, ColorChannel::Lightness);
+        editor.adjust_color(true);
+        assert_eq!(editor.temp_theme.foreground, Color::Rgb(25, 25, 25));
+
+        editor.hex_input = "#aaaaaa".to_string();
+        assert!(editor.apply_hex());
+        let warnings = editor.contrast_warnings();
+        assert_eq!(warnings[0].foreground, ThemeProperty::Foreground);
+        assert_eq!(
+            warnings[0].to_string(),
+            "Foreground on Background: 2.3:1, needs 4.5:1"
+        );
+        editor.hex_input = "nope".to_string();
+        assert!(!editor.apply_hex());
+    }
+}
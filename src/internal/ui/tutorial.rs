@@ -0,0 +1,164 @@
+//! Guided tour of the core flows, started on the first run or with `T` from the
+//! help overlay. Each step points at a part of the screen and waits for the user to
+//! do what it asks with the real keys: the app reports the actions it handles, and
+//! one that meets the current step's goal moves the tour on.
+
+use crate::internal::ui::app::Action;
+
+/// Part of the screen a step points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Region {
+    List,
+    Detail,
+    StatusBar,
+}
+
+/// What a step waits for.
+#[derive(Debug, Clone, Copy)]
+pub enum Goal {
+    /// Enter, read by the tour itself
+    Continue,
+    /// A handled action the function accepts
+    Action(fn(&Action) -> bool),
+    /// A story search applied with Enter
+    Search,
+}
+
+/// Something the user did, as reported to the tour.
+#[derive(Debug, Clone, Copy)]
+pub enum Event<'a> {
+    Continue,
+    Action(&'a Action),
+    Search,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Step {
+    pub title: &'static str,
+    pub text: &'static str,
+    pub region: Option<Region>,
+    pub goal: Goal,
+}
+
+pub const STEPS: &[Step] = &[
+    Step {
+        title: "Welcome",
+        text: "This short tour walks through the core keys. Each step waits for you to press the real key. Press Enter to begin; Esc ends the tour at any time.",
+        region: None,
+        goal: Goal::Continue,
+    },
+    Step {
+        title: "Move through the list",
+        text: "Press j or k (or the arrow keys) to move between stories.",
+        region: Some(Region::List),
+        goal: Goal::Action(|action| matches!(action, Action::NavigateDown | Action::NavigateUp)),
+    },
+    Step {
+        title: "Open a story",
+        text: "Press Enter to open the selected story and its comments.",
+        region: Some(Region::List),
+        goal: Goal::Action(|action| matches!(action, Action::Enter)),
+    },
+    Step {
+        title: "Article and comments",
+        text: "Press Tab to switch between the comments and the article's text.",
+        region: Some(Region::Detail),
+        goal: Goal::Action(|action| matches!(action, Action::ToggleArticleView)),
+    },
+    Step {
+        title: "Bookmark",
+        text: "Press b to bookmark the story (b again removes it). B lists your bookmarks.",
+        region: Some(Region::Detail),
+        goal: Goal::Action(|action| matches!(action, Action::ToggleBookmark)),
+    },
+    Step {
+        title: "Back to the list",
+        text: "Press q to close the story and go back to the list.",
+        region: Some(Region::Detail),
+        goal: Goal::Action(|action| matches!(action, Action::Back)),
+    },
+    Step {
+        title: "Search",
+        text: "Press /, type a few letters of a title and press Enter to filter the list.",
+        region: Some(Region::List),
+        goal: Goal::Search,
+    },
+    Step {
+        title: "That's it",
+        text: "The status bar keeps a few reminders, ? shows every key and T in the help takes this tour again. Press Enter to finish.",
+        region: Some(Region::StatusBar),
+        goal: Goal::Continue,
+    },
+];
+
+/// Progress through `STEPS`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Tutorial {
+    pub step: usize,
+}
+
+impl Tutorial {
+    pub fn current(&self) -> Option<&'static Step> {
+        STEPS.get(self.step)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.step >= STEPS.len()
+    }
+
+    /// Move to the next step if `event` meets the current one's goal. Returns true
+    /// when it did.
+    pub fn observe(&mut self, event: Event) -> bool {
+        let Some(step) = self.current() else {
+            return false;
+        };
+        let met = match (step.goal, event) {
+            (Goal::Continue, Event::Continue) | (Goal::Search, Event::Search) => true,
+            (Goal::Action(accepts), Event::Action(action)) => accepts(action),
+            _ => false,
+        };
+        if met {
+            self.step += 1;
+        }
+        met
+    }
+
+    /// "3/8" for the step's title.
+    pub fn position(&self) -> String {
+        format!("{}/{}", (self.step + 1).min(STEPS.len()), STEPS.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_wait_for_their_goal() {
+        let mut tutorial = Tutorial::default();
+        assert!(!tutorial.observe(Event::Action(&Action::NavigateDown)));
+        assert!(tutorial.observe(Event::Continue));
+        assert_eq!(tutorial.position(), "2/8");
+
+        // Unrelated actions leave the step where it is
+        assert!(!tutorial.observe(Event::Action(&Action::ToggleBookmark)));
+        assert!(tutorial.observe(Event::Action(&Action::NavigateUp)));
+        for action in [
+            Action::Enter,
+            Action::ToggleArticleView,
+            Action::ToggleBookmark,
+            Action::Back,
+        ] {
+            assert!(tutorial.observe(Event::Action(&action)));
+        }
+        assert!(!tutorial.observe(Event::Continue));
+        assert!(tutorial.observe(Event::Search));
+        assert_eq!(
+            tutorial.current().map(|step| step.region),
+            Some(Some(Region::StatusBar))
+        );
+        assert!(tutorial.observe(Event::Continue));
+        assert!(tutorial.is_finished());
+        assert!(!tutorial.observe(Event::Continue));
+    }
+}
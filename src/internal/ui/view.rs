@@ -6,70 +6,111 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Padding, Paragraph, Wrap},
+    widgets::{
+        Block, Borders, Clear, HighlightSpacing, List, ListItem, ListState, Padding, Paragraph,
+        Wrap,
+    },
 };
 use textwrap;
 
 use super::app::{App, InputMode, ViewMode};
+use super::layout::Pane;
 use super::sort::{SortBy, SortOrder};
+use super::status_template::Token;
+use crate::internal::history::Period;
 use crate::internal::models::Story;
 
-#[tracing::instrument(skip(app, f))]
+#[tracing::instrument(name = "render.frame", level = "debug", skip_all)]
 pub fn draw(app: &mut App, f: &mut Frame) {
     // High level render timing. This is conditionalally logged at the end of draw
     // when performance metrics are enabled and in debug builds.
     let start = std::time::Instant::now();
 
-    let chunks = Layout::default()
+    let message_rows = app.config.ui.message_line_height;
+    let category_rows = u16::from(app.config.ui.category_bar);
+    let [top, categories, main, message, status] = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(1),
+            Constraint::Length(category_rows),
             Constraint::Min(0),
+            Constraint::Length(message_rows),
             Constraint::Length(1),
         ])
-        .split(f.area());
+        .areas(f.area());
 
-    render_top_bar(app, f, chunks[0]);
+    render_top_bar(app, f, top);
+    if category_rows > 0 {
+        render_category_bar(app, f, categories);
+    }
 
-    match app.view_mode {
-        ViewMode::List => {
-            let view_start = std::time::Instant::now();
-            render_list(app, f, chunks[1]);
-            if app.config.logging.enable_performance_metrics && cfg!(debug_assertions) {
-                tracing::debug!(elapsed = ?view_start.elapsed(), view = "list", "render.list");
-            }
-        }
-        ViewMode::StoryDetail => {
-            let view_start = std::time::Instant::now();
-            render_detail(app, f, chunks[1]);
-            if app.config.logging.enable_performance_metrics && cfg!(debug_assertions) {
-                tracing::debug!(elapsed = ?view_start.elapsed(), view = "detail", "render.detail");
+    // Lay out the list and detail panes (side by side when the split layout is on)
+    let panes = super::layout::compute(
+        main,
+        app.view_mode,
+        app.config.ui.split_layout,
+        app.config.ui.split_ratio,
+    );
+
+    if let Some(area) = panes.list {
+        let view_start = std::time::Instant::now();
+        render_list(app, f, area);
+        if app.config.logging.enable_performance_metrics {
+            let view = match app.view_mode {
+                ViewMode::Bookmarks => "bookmarks",
+                ViewMode::History => "history",
+                ViewMode::ReadingLists => "reading_lists",
+                _ => "list",
+            };
+            let elapsed = view_start.elapsed();
+            app.metrics
+                .record(crate::internal::metrics::view_render(view), elapsed);
+            if cfg!(debug_assertions) {
+                tracing::debug!(?elapsed, view, "render.list");
             }
         }
-        ViewMode::Article => {
-            let view_start = std::time::Instant::now();
-            render_article(app, f, chunks[1]);
-            if app.config.logging.enable_performance_metrics && cfg!(debug_assertions) {
-                tracing::debug!(elapsed = ?view_start.elapsed(), view = "article", "render.article");
+    }
+
+    if let Some(area) = panes.detail {
+        let view_start = std::time::Instant::now();
+        let view = match app.view_mode {
+            ViewMode::Article => {
+                render_article(app, f, area);
+                "article"
             }
-        }
-        ViewMode::Bookmarks => {
-            let view_start = std::time::Instant::now();
-            render_list(app, f, chunks[1]);
-            if app.config.logging.enable_performance_metrics && cfg!(debug_assertions) {
-                tracing::debug!(elapsed = ?view_start.elapsed(), view = "bookmarks", "render.bookmarks");
+            _ => {
+                render_detail(app, f, area);
+                "detail"
             }
-        }
-        ViewMode::History => {
-            let view_start = std::time::Instant::now();
-            render_list(app, f, chunks[1]);
-            if app.config.logging.enable_performance_metrics && cfg!(debug_assertions) {
-                tracing::debug!(elapsed = ?view_start.elapsed(), view = "history", "render.history");
+        };
+        if app.config.logging.enable_performance_metrics {
+            let elapsed = view_start.elapsed();
+            app.metrics
+                .record(crate::internal::metrics::view_render(view), elapsed);
+            if cfg!(debug_assertions) {
+                tracing::debug!(?elapsed, view, "render.detail");
             }
         }
     }
 
-    render_status_bar(app, f, chunks[2]);
+    if message_rows > 0 {
+        render_message_line(app, f, message);
+    }
+    render_status_bar(app, f, status);
+
+    if let Some(tutorial) = &app.tutorial {
+        use super::tutorial::Region;
+        let region =
+            tutorial
+                .current()
+                .and_then(|step| step.region)
+                .and_then(|region| match region {
+                    Region::List => panes.list,
+                    Region::Detail => panes.detail,
+                    Region::StatusBar => Some(status),
+                });
+        render_tutorial(app, f, main, region);
+    }
 
     // Render search overlay if in search mode
     match app.input_mode {
@@ -77,15 +118,22 @@ pub fn draw(app: &mut App, f: &mut Frame) {
         _ => {}
     }
 
-    // Render notification overlay if present
-    if app.notification.is_some() {
+    if app.last_error.is_some() {
+        render_error_panel(app, f, message.union(status));
+    }
+
+    // Render notification overlay if present; the message line shows it instead
+    if !app.notifications.toasts.is_empty() && message_rows == 0 {
         render_notification(app, f);
     }
 
-    // Render progress overlay if loading all stories
-    match app.story_load_progress {
-        Some(_) => render_progress_overlay(app, f),
-        None => {}
+    // Render progress overlay if loading all stories or preparing the offline pack
+    match (app.story_load_progress, app.offline_pack_progress) {
+        (Some(progress), _) => render_progress_overlay(app, f, "Loading Stories", progress),
+        (None, Some(progress)) => {
+            render_progress_overlay(app, f, "Preparing Offline Pack", progress)
+        }
+        (None, None) => {}
     }
 
     // Render help overlay if active
@@ -98,311 +146,1559 @@ pub fn draw(app: &mut App, f: &mut Frame) {
         render_theme_editor_overlay(app, f);
     }
 
+    if app.list_picker.is_some() {
+        render_list_picker(app, f);
+    }
+
+    if app.filter_panel.is_some() {
+        render_filter_panel(app, f);
+    }
+
+    if app.settings_panel.is_some() {
+        render_settings_panel(app, f);
+    }
+
+    if app.mute_prompt.is_some() {
+        render_mute_prompt(app, f);
+    }
+
+    if app.link_picker.is_some() {
+        render_link_picker(app, f);
+    }
+
+    if app.read_later_view.is_some() {
+        render_read_later(app, f);
+    }
+
+    if app.notification_center.is_some() {
+        render_notification_center(app, f);
+    }
+
+    if app.command_palette.is_some() {
+        render_command_palette(app, f);
+    }
+
+    if app.link_preview.is_some() {
+        render_link_preview(app, f);
+    }
+
+    if app.reading_search.is_some() {
+        render_reading_search(app, f);
+    }
+
+    if app.tag_prompt.is_some() {
+        render_tag_prompt(app, f);
+    }
+
+    if app.item_prompt.is_some() {
+        render_item_prompt(app, f);
+    }
+
+    if app.note_editor.is_some() {
+        render_note_editor(app, f);
+    }
+
+    if app.show_cache_stats {
+        render_cache_stats_overlay(app, f);
+    }
+
+    if !app.whats_new.is_empty() {
+        render_whats_new_overlay(app, f);
+    }
+
+    // Takes the first key, so it goes over the release notes
+    if app.session_prompt.is_some() {
+        render_session_prompt(app, f);
+    }
+
     // Render log viewer overlay if active
     if app.log_viewer.visible {
-        app.log_viewer.render(f, f.area());
+        app.log_viewer.render(f, f.area(), &app.metrics);
+    }
+
+    if app.show_perf_hud {
+        render_perf_hud(app, f);
     }
 
-    // Conditional render timing: only emit when the config allows it and during debug builds
-    if app.config.logging.enable_performance_metrics && cfg!(debug_assertions) {
-        tracing::debug!(elapsed = ?start.elapsed(), "render.draw");
+    // Frame timing goes to the HUD; the log only gets it in debug builds
+    if app.config.logging.enable_performance_metrics {
+        let elapsed = start.elapsed();
+        app.metrics
+            .record(crate::internal::metrics::FRAME_RENDER, elapsed);
+        if cfg!(debug_assertions) {
+            tracing::debug!(?elapsed, "render.draw");
+        }
     }
 }
 
-fn render_progress_overlay(app: &App, f: &mut Frame) {
-    match app.story_load_progress {
-        Some((loaded, total)) => {
-            let area = f.area();
-            let popup_width = 50.min(area.width - 4);
-            let popup_height = 3; // Compact: title + progress bar only
-            let popup_x = (area.width.saturating_sub(popup_width)) / 2;
-            let popup_y = (area.height.saturating_sub(popup_height)) / 2;
-            let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+/// Border style for a pane; the focused pane is highlighted in the split layout.
+fn pane_border_style(app: &App, pane: Pane) -> Style {
+    match app.split_active() && app.focused_pane == pane {
+        true => Style::default().fg(app.theme.selection_bg),
+        false => Style::default().fg(app.theme.border),
+    }
+}
 
-            let spinner = app.get_spinner_char();
-            let percent = match total {
-                0 => 0,
-                t => (loaded as f64 / t as f64 * 100.0) as u16,
-            };
+/// Live cache hit rates, sizes and request deduplication of the API service.
+/// Live timings in the top-right corner, over whatever is on screen; keys still
+/// go to the view underneath.
+fn render_perf_hud(app: &App, f: &mut Frame) {
+    use crate::internal::metrics::{EVENT_LOOP_LAG, FRAME_RENDER, INPUT_LATENCY};
+
+    let label = Style::default().fg(app.theme.comment_time);
+    let value = Style::default().fg(app.theme.foreground);
+    let ms = |d: Option<std::time::Duration>| match d {
+        Some(d) => format!("{:.1}ms", d.as_secs_f64() * 1000.0),
+        None => "-".to_string(),
+    };
+    let row = |name: &str, hist: Option<&crate::internal::metrics::Histogram>| {
+        let text = match hist {
+            Some(hist) => format!(
+                "p50 {:>7} p95 {:>7} {:>6}",
+                ms(hist.percentile(50.0)),
+                ms(hist.percentile(95.0)),
+                hist.count()
+            ),
+            None => "-".to_string(),
+        };
+        Line::from(vec![
+            Span::styled(format!("{:<14}", name), label),
+            Span::styled(text, value),
+        ])
+    };
 
-            // Compact title with all info: spinner, action, progress, percentage
-            let title = format!(
-                "{} Loading Stories [{}/{}] {}%",
-                spinner, loaded, total, percent
-            );
+    let mut lines = vec![
+        row("frame", app.metrics.histogram(FRAME_RENDER)),
+        row("loop lag", app.metrics.histogram(EVENT_LOOP_LAG)),
+        row("key → frame", app.metrics.histogram(INPUT_LATENCY)),
+    ];
+    lines.extend(
+        app.metrics
+            .names()
+            .into_iter()
+            .filter_map(|name| Some((name.strip_prefix("render.")?, name)))
+            .map(|(view, name)| row(view, app.metrics.histogram(name))),
+    );
+    let requests = app.api_service.request_timings();
+    lines.push(row("API request", Some(&requests)));
+    let cache = app.api_service.cache_stats();
+    lines.push(Line::from(vec![
+        Span::styled(format!("{:<14}", "loaded"), label),
+        Span::styled(
+            format!(
+                "{} stories · {} comments",
+                app.stories.len(),
+                app.comments.len()
+            ),
+            value,
+        ),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(format!("{:<14}", "cached"), label),
+        Span::styled(
+            format!(
+                "{} stories · {} comments · {} articles",
+                cache.stories.entries, cache.comments.entries, cache.articles.entries
+            ),
+            value,
+        ),
+    ]));
 
-            let block = Block::default()
-                .title(title)
-                .title_style(
-                    Style::default()
-                        .fg(app.theme.foreground)
-                        .add_modifier(Modifier::BOLD),
-                )
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(app.theme.border))
-                .style(Style::default().bg(app.theme.background));
+    let area = f.area();
+    let width = 60.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height.saturating_sub(1));
+    let hud = Rect::new(area.width.saturating_sub(width), 1, width, height);
+    let block = Block::default()
+        .title(" Performance ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border))
+        .style(Style::default().bg(app.theme.background));
 
-            f.render_widget(Clear, popup_area);
-            f.render_widget(&block, popup_area);
+    f.render_widget(Clear, hud);
+    f.render_widget(Paragraph::new(lines).block(block), hud);
+}
 
-            let inner_area = block.inner(popup_area);
+fn render_cache_stats_overlay(app: &App, f: &mut Frame) {
+    use crate::internal::cache::CacheStats;
 
-            // Single progress bar with visual indicator
-            let gauge = ratatui::widgets::Gauge::default()
-                .gauge_style(
-                    Style::default()
-                        .fg(app.theme.selection_bg)
-                        .bg(app.theme.background),
-                )
-                .ratio(percent as f64 / 100.0)
-                .label("");
-            f.render_widget(gauge, inner_area);
-        }
-        None => {}
+    let stats = app.api_service.cache_stats();
+    let area = f.area();
+    let popup_width = 72.min(area.width.saturating_sub(4));
+    // Tuning hints, wrapped to the popup
+    let hints: Vec<String> = stats
+        .tuning_hints(&app.config.network)
+        .iter()
+        .flat_map(|hint| {
+            textwrap::wrap(
+                hint,
+                textwrap::Options::new(popup_width.saturating_sub(2).max(20) as usize)
+                    .initial_indent("» ")
+                    .subsequent_indent("  "),
+            )
+            .into_iter()
+            .map(|line| line.into_owned())
+            .collect::<Vec<_>>()
+        })
+        .collect();
+    let hint_rows = match hints.len() {
+        0 => 0,
+        n => n as u16 + 1,
+    };
+    let popup_height = (14 + hint_rows).min(area.height.saturating_sub(4));
+    let popup_area = Rect::new(
+        (area.width.saturating_sub(popup_width)) / 2,
+        (area.height.saturating_sub(popup_height)) / 2,
+        popup_width,
+        popup_height,
+    );
+
+    let label = Style::default().fg(app.theme.comment_time);
+    let value = Style::default().fg(app.theme.foreground);
+    let kib = |bytes: usize| format!("{:.1} KiB", bytes as f64 / 1024.0);
+    let row = |name: &str, s: &CacheStats| {
+        let ratio = s
+            .hit_ratio()
+            .map_or("-".to_string(), |r| format!("{:.0}%", r * 100.0));
+        let limit = s.max_entries.map_or("∞".to_string(), |m| m.to_string());
+        Line::from(vec![
+            Span::styled(format!("{:<10}", name), label),
+            Span::styled(
+                format!(
+                    "{:>6}/{:<6} {:>5} stale {:>5} {:>7} {:>7} {:>6} {:>10}",
+                    s.entries,
+                    limit,
+                    s.expired,
+                    ratio,
+                    s.hits,
+                    s.misses,
+                    s.evictions,
+                    kib(s.approx_bytes)
+                ),
+                value,
+            ),
+        ])
+    };
+
+    let total_bytes =
+        stats.stories.approx_bytes + stats.comments.approx_bytes + stats.articles.approx_bytes;
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!(
+                "{:<10}{:>13} {:>11} {:>5} {:>7} {:>7} {:>6} {:>10}",
+                "", "entries", "", "hit%", "hits", "misses", "evict", "memory"
+            ),
+            label,
+        )),
+        row("Stories", &stats.stories),
+        row("Comments", &stats.comments),
+        row("Articles", &stats.articles),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Memory (approx.)   ", label),
+            Span::styled(kib(total_bytes), value),
+        ]),
+        Line::from(vec![
+            Span::styled("In-flight requests ", label),
+            Span::styled(stats.inflight.to_string(), value),
+        ]),
+        Line::from(vec![
+            Span::styled("Deduplicated       ", label),
+            Span::styled(format!("{} requests joined", stats.dedup_joins), value),
+        ]),
+        Line::from(vec![
+            Span::styled("Served stale       ", label),
+            Span::styled(
+                format!(
+                    "{} stories · {} comments · {} articles",
+                    stats.stories.stale_serves,
+                    stats.comments.stale_serves,
+                    stats.articles.stale_serves
+                ),
+                value,
+            ),
+        ]),
+    ];
+    if !hints.is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(
+            hints
+                .into_iter()
+                .map(|hint| Line::from(Span::styled(hint, Style::default().fg(app.theme.score)))),
+        );
     }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Esc/q: Close", label)));
+
+    let block = Block::default()
+        .title(" Cache statistics ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.selection_bg))
+        .style(Style::default().bg(app.theme.background));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(Paragraph::new(lines).block(block), popup_area);
 }
 
-fn render_list(app: &mut App, f: &mut Frame, area: Rect) {
-    use std::borrow::Cow;
+/// One-time release notes after an upgrade: new features and changed default keys.
+fn render_whats_new_overlay(app: &mut App, f: &mut Frame) {
+    let area = f.area();
+    let popup_width = 72.min(area.width.saturating_sub(4));
+    let popup_height = 24.min(area.height.saturating_sub(4));
+    let popup_area = Rect::new(
+        (area.width.saturating_sub(popup_width)) / 2,
+        (area.height.saturating_sub(popup_height)) / 2,
+        popup_width,
+        popup_height,
+    );
 
-    // Determine which stories to display based on view mode
-    let stories_to_display: Vec<(usize, Cow<Story>)> = match app.view_mode {
-        ViewMode::Bookmarks => {
-            // Convert bookmarked stories to Story objects for display
-            app.bookmarks
-                .stories
-                .iter()
-                .enumerate()
-                .filter_map(|(idx, bookmarked)| {
-                    // Find the full story in app.stories if available
-                    app.stories
-                        .iter()
-                        .find(|s| s.id == bookmarked.id)
-                        .map(|story| (idx, Cow::Borrowed(story)))
-                })
-                .collect()
+    let heading = Style::default()
+        .fg(app.theme.selection_bg)
+        .add_modifier(Modifier::BOLD);
+    let label = Style::default().fg(app.theme.comment_time);
+    let value = Style::default().fg(app.theme.foreground);
+    let mut lines = Vec::new();
+    for release in &app.whats_new {
+        if app.whats_new.len() > 1 {
+            lines.push(Line::from(Span::styled(
+                format!("v{}", release.version),
+                heading,
+            )));
         }
-        ViewMode::History => {
-            app.history
-                .stories
+        lines.extend(
+            release
+                .features
                 .iter()
-                .enumerate()
-                .map(|(idx, viewed)| {
-                    let story = crate::internal::models::Story {
-                        id: viewed.id,
-                        title: Some(viewed.title.clone()),
-                        url: viewed.url.clone(),
-                        by: viewed.by.clone(),
-                        score: viewed.score,
-                        // Use viewed_at as the time so it shows "viewed X ago"
-                        time: Some(viewed.viewed_at.timestamp().as_second()),
-                        descendants: viewed.descendants,
-                        kids: None,
-                    };
-                    (idx, Cow::Owned(story))
-                })
-                .collect()
-        }
-        _ => {
-            // Filter stories based on search query for normal list view
-            match app.search_query.is_empty() {
-                true => app
-                    .stories
+                .map(|feature| Line::from(Span::styled(format!(" • {}", feature), value))),
+        );
+        if !release.keybindings.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("Keybindings", heading)));
+            lines.extend(
+                release
+                    .keybindings
                     .iter()
-                    .enumerate()
-                    .map(|(i, s)| (i, Cow::Borrowed(s)))
-                    .collect(),
-                false => {
-                    use crate::internal::search::SearchMode;
-                    app.stories
-                        .iter()
-                        .enumerate()
-                        .filter(|(_, story)| {
-                            let title_match = story
-                                .title
-                                .as_ref()
-                                .map(|t| app.search_query.matches(t))
-                                .unwrap_or(false);
-
-                            match app.search_query.mode {
-                                SearchMode::Title => title_match,
-                                SearchMode::Comments => false,
-                                SearchMode::TitleAndComments => title_match,
-                            }
-                        })
-                        .map(|(i, s)| (i, Cow::Borrowed(s)))
-                        .collect()
-                }
-            }
+                    .map(|binding| Line::from(Span::styled(format!(" • {}", binding), value))),
+            );
         }
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(Span::styled(
+        "Enter/Esc: Close · j/k: Scroll",
+        label,
+    )));
+
+    let block = Block::default()
+        .title(format!(" What's new in v{} ", app.app_version))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.selection_bg))
+        .padding(Padding::horizontal(1))
+        .style(Style::default().bg(app.theme.background));
+    let inner_area = block.inner(popup_area);
+    let width = inner_area.width.max(1) as usize;
+    let rows = lines
+        .iter()
+        .map(|line| line.width().div_ceil(width).max(1))
+        .sum();
+    app.whats_new_scroll
+        .update(rows, inner_area.height as usize);
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .scroll((app.whats_new_scroll.offset_u16(), 0)),
+        popup_area,
+    );
+}
+
+/// The guided tour's current step in a card at the bottom right of `main`, with the
+/// part of the screen it is about outlined.
+fn render_tutorial(app: &App, f: &mut Frame, main: Rect, region: Option<Rect>) {
+    let Some(tutorial) = &app.tutorial else {
+        return;
+    };
+    let Some(step) = tutorial.current() else {
+        return;
     };
+    let highlight = Style::default()
+        .fg(app.theme.score)
+        .add_modifier(Modifier::BOLD);
+    match region {
+        Some(area) if area.height >= 3 => f.render_widget(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Thick)
+                .border_style(highlight),
+            area,
+        ),
+        Some(area) => f
+            .buffer_mut()
+            .set_style(area, Style::default().add_modifier(Modifier::REVERSED)),
+        None => {}
+    }
 
-    // Optimization: Only render visible items
-    // We calculate the visible range based on the current offset and area height
-    let offset = app.story_list_state.offset();
-    let height = area.height as usize;
-    let item_height = 2; // Title + Metadata
-    let buffer = 5; // Render a few extra items to ensure smooth scrolling
-    let num_visible = (height / item_height) + buffer;
-
-    let start_index = offset;
-    let end_index = (start_index + num_visible).min(stories_to_display.len());
-
-    let items: Vec<ListItem> = (0..stories_to_display.len())
-        .map(|i| {
-            // If item is within visible range (with buffer), render it fully
-            if i >= start_index && i < end_index {
-                let (idx, story) = &stories_to_display[i];
-                let title = story.title.as_deref().unwrap_or("No Title");
-                let score = story.score.unwrap_or(0);
-                let by = story.by.as_deref().unwrap_or("unknown");
-                let comments = story.descendants.unwrap_or(0);
-
-                // Extract domain from URL
-                let domain = story
-                    .url
-                    .as_ref()
-                    .and_then(|url| crate::utils::url::extract_domain(url))
-                    .map(|d| format!(" ({})", d))
-                    .unwrap_or_default();
+    let width = 50.min(main.width.saturating_sub(2));
+    let text_width = width.saturating_sub(4).max(10) as usize;
+    let mut lines: Vec<Line> = textwrap::wrap(step.text, text_width)
+        .into_iter()
+        .map(|line| {
+            Line::from(Span::styled(
+                line.into_owned(),
+                Style::default().fg(app.theme.foreground),
+            ))
+        })
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Esc: End the tour",
+        Style::default().fg(app.theme.comment_time),
+    )));
+    let height = (lines.len() as u16 + 2).min(main.height);
+    let card = Rect::new(
+        main.right().saturating_sub(width + 1),
+        main.bottom().saturating_sub(height + 1),
+        width,
+        height,
+    );
+    let block = Block::default()
+        .title(format!(" Tour {} · {} ", tutorial.position(), step.title))
+        .title_style(highlight)
+        .borders(Borders::ALL)
+        .border_style(highlight)
+        .padding(Padding::horizontal(1))
+        .style(Style::default().bg(app.theme.background));
+    f.render_widget(Clear, card);
+    f.render_widget(Paragraph::new(lines).block(block), card);
+}
 
-                let time = story
-                    .time
-                    .as_ref()
-                    .map(crate::utils::datetime::format_timestamp)
-                    .unwrap_or_else(|| "unknown".to_string());
+/// Popup listing the reading lists the focused story can be added to.
+fn render_list_picker(app: &App, f: &mut Frame) {
+    let Some(picker) = &app.list_picker else {
+        return;
+    };
+    let lists = &app.reading_lists.lists;
 
-                // Show score with leading space for proper alignment
-                let score = format!("{:3} ", score);
+    let area = f.area();
+    let popup_width = 50.min(area.width.saturating_sub(4));
+    let popup_height = (lists.len() as u16 + 5).min(area.height.saturating_sub(4));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
 
-                // Check if story is bookmarked
-                let bookmark_indicator = match app.bookmarks.contains(story.id) {
-                    true => "★ ",
-                    false => "",
-                };
+    let row_style = |row: usize| match row == picker.selected {
+        true => Style::default()
+            .fg(app.theme.selection_fg)
+            .bg(app.theme.selection_bg),
+        false => Style::default().fg(app.theme.foreground),
+    };
 
-                // Calculate available width for title
-                let prefix_len = 4 + 2 + // index + bookmark
-                    if app.config.ui.list_view.show_score { 5 } else { 0 }; // score with spacing
-
-                let wrapped_title = calculate_wrapped_title(title, area.width, prefix_len);
-
-                // Create title line(s)
-                let mut title_lines = Vec::new();
-                for (i, title_part) in wrapped_title.iter().enumerate() {
-                    let line_spans = match i {
-                        0 => {
-                            // First line: include index, bookmark, score, and title
-                            let mut spans = vec![
-                                Span::styled(
-                                    format!("{:<4}", idx + 1),
-                                    Style::default().fg(app.theme.comment_time),
-                                ),
-                                Span::styled(
-                                    bookmark_indicator,
-                                    Style::default().fg(app.theme.selection_bg),
-                                ),
-                            ];
-
-                            if app.config.ui.list_view.show_score {
-                                spans.push(Span::styled(
-                                    format!("{} ", score),
-                                    Style::default().fg(app.theme.score),
-                                ));
-                            }
-
-                            spans.push(Span::styled(
-                                title_part.to_string(),
-                                Style::default().fg(app.theme.foreground),
-                            ));
-
-                            // Add domain on first line if configured and only one line
-                            if app.config.ui.list_view.show_domain && wrapped_title.len() == 1 {
-                                spans.push(Span::styled(
-                                    domain.clone(),
-                                    Style::default().fg(app.theme.comment_time),
-                                ));
-                            }
-
-                            spans
-                        }
-                        _ => {
-                            // Continuation lines: indent and show title part only
-                            vec![Span::styled(
-                                format!("    {}", title_part),
-                                Style::default().fg(app.theme.foreground),
-                            )]
-                        }
-                    };
+    let mut lines: Vec<Line> = lists
+        .iter()
+        .enumerate()
+        .map(|(i, list)| {
+            let marker = match list.contains(picker.story.id) {
+                true => " ✓",
+                false => "",
+            };
+            Line::from(Span::styled(
+                format!("{} {} ({}){}", i + 1, list.name, list.items.len(), marker),
+                row_style(i),
+            ))
+        })
+        .collect();
+    lines.push(match &picker.new_name {
+        Some(name) => Line::from(Span::styled(
+            format!("New list: {}_", name),
+            Style::default().fg(app.theme.selection_bg),
+        )),
+        None => Line::from(Span::styled("n New list…", row_style(lists.len()))),
+    });
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        match picker.new_name {
+            Some(_) => "Enter: Create • Esc: Cancel",
+            None => "j/k: Move • Enter/1-9: Add • Esc: Cancel",
+        },
+        Style::default().fg(app.theme.comment_time),
+    )));
 
-                    title_lines.push(Line::from(line_spans));
-                }
+    let block = Block::default()
+        .title(" Add to reading list ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.selection_bg))
+        .style(Style::default().bg(app.theme.background));
 
-                // If domain configured and title wrapped to multiple lines, add domain on last line
-                if app.config.ui.list_view.show_domain
-                    && wrapped_title.len() > 1
-                    && let Some(last_line) = title_lines.last_mut()
-                {
-                    last_line.spans.push(Span::styled(
-                        format!(" {}", domain),
-                        Style::default().fg(app.theme.comment_time),
-                    ));
-                }
+    f.render_widget(Clear, popup_area);
+    f.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
 
-                // Build metadata line with optional fields
-                let mut meta_spans = vec![Span::styled("    ", Style::default())]; // Indent
-                let mut first_field = true;
+fn render_filter_panel(app: &App, f: &mut Frame) {
+    use crate::internal::ui::filter_panel::FilterField;
 
-                // Add time if configured
-                if app.config.ui.list_view.show_age {
-                    meta_spans.push(Span::styled(
-                        time,
-                        Style::default().fg(app.theme.comment_time),
-                    ));
-                    first_field = false;
-                }
+    let Some(panel) = &app.filter_panel else {
+        return;
+    };
 
-                // Add comments if configured
-                if app.config.ui.list_view.show_comments {
-                    if !first_field {
-                        meta_spans.push(Span::styled(" | ", Style::default().fg(app.theme.border)));
-                    }
-                    meta_spans.push(Span::styled(
-                        format!("{} comments", comments),
-                        Style::default().fg(app.theme.comment_time),
-                    ));
-                    first_field = false;
-                }
+    let area = f.area();
+    let popup_width = 64.min(area.width.saturating_sub(4));
+    let popup_height = (FilterField::ALL.len() as u16 + 6).min(area.height.saturating_sub(4));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
 
-                // Always show author
-                match first_field {
-                    false => meta_spans.push(Span::styled(
-                        " | by ",
-                        Style::default().fg(app.theme.border),
-                    )),
-                    true => {
-                        meta_spans.push(Span::styled("by ", Style::default().fg(app.theme.border)))
-                    }
-                }
-                meta_spans.push(Span::styled(
-                    by,
-                    Style::default().fg(app.theme.comment_author),
-                ));
+    let mut lines: Vec<Line> = FilterField::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let (value, style) = match (i == panel.selected, &panel.editing) {
+                (true, Some(text)) => (
+                    format!("{}_", text),
+                    Style::default().fg(app.theme.selection_bg),
+                ),
+                (true, None) => (
+                    field.value(&panel.filters),
+                    Style::default()
+                        .fg(app.theme.selection_fg)
+                        .bg(app.theme.selection_bg),
+                ),
+                (false, _) => (
+                    field.value(&panel.filters),
+                    Style::default().fg(app.theme.foreground),
+                ),
+            };
+            let value = match value.is_empty() {
+                true => "off".to_string(),
+                false => value,
+            };
+            Line::from(Span::styled(
+                format!("{:<20} {}", field.label(), value),
+                style,
+            ))
+        })
+        .collect();
+    lines.push(Line::from(""));
+    if let Some(error) = &panel.error {
+        lines.push(Line::from(Span::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    lines.push(Line::from(Span::styled(
+        match panel.editing {
+            Some(_) => "Enter: Apply • Esc: Cancel • Lists are comma-separated",
+            None => "j/k: Move • Enter: Edit • x: Clear • C: Clear all • Esc: Close",
+        },
+        Style::default().fg(app.theme.comment_time),
+    )));
+
+    let block = Block::default()
+        .title(" Story filters ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.selection_bg))
+        .style(Style::default().bg(app.theme.background));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
+
+fn render_settings_panel(app: &App, f: &mut Frame) {
+    use crate::config::settings::Setting;
+
+    let Some(panel) = &app.settings_panel else {
+        return;
+    };
+
+    let area = f.area();
+    let popup_width = 72.min(area.width.saturating_sub(4));
+    let popup_height = (Setting::ALL.len() as u16 + 6).min(area.height.saturating_sub(4));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    let mut lines: Vec<Line> = Setting::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, setting)| {
+            let (value, style) = match (i == panel.selected, &panel.editing) {
+                (true, Some(text)) => (
+                    format!("{}_", text),
+                    Style::default().fg(app.theme.selection_bg),
+                ),
+                (true, None) => (
+                    setting.value(&app.config),
+                    Style::default()
+                        .fg(app.theme.selection_fg)
+                        .bg(app.theme.selection_bg),
+                ),
+                (false, _) => (
+                    setting.value(&app.config),
+                    Style::default().fg(app.theme.foreground),
+                ),
+            };
+            let value = match (setting.is_toggle(), value.as_str()) {
+                (true, "true") => "on".to_string(),
+                (true, _) => "off".to_string(),
+                (false, _) => value,
+            };
+            Line::from(Span::styled(
+                format!("{:<22} {}", setting.label(), value),
+                style,
+            ))
+        })
+        .collect();
+    lines.push(Line::from(""));
+    if let Some(error) = &panel.error {
+        lines.push(Line::from(Span::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    lines.push(Line::from(Span::styled(
+        match panel.editing {
+            Some(_) => "Enter: Apply • Esc: Cancel".to_string(),
+            None => format!(
+                "j/k: Move • Enter: Edit/Toggle • Esc: Close • Saved to {}",
+                app.config.save_path().display()
+            ),
+        },
+        Style::default().fg(app.theme.comment_time),
+    )));
+
+    let block = Block::default()
+        .title(" Settings ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.selection_bg))
+        .style(Style::default().bg(app.theme.background));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
+
+fn render_reading_search(app: &App, f: &mut Frame) {
+    use crate::internal::reading_index::ItemKind;
+
+    let Some(search) = &app.reading_search else {
+        return;
+    };
+
+    let area = f.area();
+    let popup_width = 90.min(area.width.saturating_sub(4));
+    let popup_height = 24.min(area.height.saturating_sub(4));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("> {}█", search.query),
+            Style::default().fg(app.theme.foreground),
+        )),
+        Line::from(""),
+    ];
+    if let Some(error) = &search.error {
+        lines.push(Line::from(Span::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        )));
+    } else if search.hits.is_empty() && !search.query.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No matches",
+            Style::default().fg(app.theme.comment_time),
+        )));
+    }
+
+    // Two lines per hit; keep the selected one in view
+    let visible = (popup_height.saturating_sub(7) as usize / 2).max(1);
+    let start = search.selected.saturating_sub(visible - 1);
+    for (i, hit) in search.hits.iter().enumerate().skip(start).take(visible) {
+        let kind = match hit.kind {
+            ItemKind::Article => "article ",
+            ItemKind::Comments => "comments",
+        };
+        let title_style = match i == search.selected {
+            true => Style::default()
+                .fg(app.theme.selection_fg)
+                .bg(app.theme.selection_bg),
+            false => Style::default().fg(app.theme.foreground),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{} ", kind),
+                Style::default().fg(app.theme.comment_time),
+            ),
+            Span::styled(hit.title.clone(), title_style.add_modifier(Modifier::BOLD)),
+        ]));
+
+        // Newlines in the snippet become spaces, which keeps the byte ranges valid
+        let snippet = hit.snippet.replace('\n', " ");
+        let mut spans = vec![Span::raw("         ")];
+        let mut cut = 0;
+        for range in &hit.highlighted {
+            spans.push(Span::styled(
+                snippet[cut..range.start].to_string(),
+                Style::default().fg(app.theme.comment_time),
+            ));
+            spans.push(Span::styled(
+                snippet[range.clone()].to_string(),
+                Style::default()
+                    .fg(app.theme.score)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            cut = range.end;
+        }
+        spans.push(Span::styled(
+            snippet[cut..].to_string(),
+            Style::default().fg(app.theme.comment_time),
+        ));
+        lines.push(Line::from(spans));
+    }
+
+    let block = Block::default()
+        .title(" Search my reading ")
+        .title_bottom(Line::from(Span::styled(
+            " ↑/↓: Select • Enter: Open • Esc: Close ",
+            Style::default().fg(app.theme.comment_time),
+        )))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.selection_bg))
+        .style(Style::default().bg(app.theme.background));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
+
+fn render_session_prompt(app: &App, f: &mut Frame) {
+    let Some(session) = &app.session_prompt else {
+        return;
+    };
+    let lines = vec![
+        Line::from(Span::styled(
+            session.summary(),
+            Style::default().fg(app.theme.foreground),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Enter/r: Restore • any other key: Start fresh",
+            Style::default().fg(app.theme.comment_time),
+        )),
+    ];
+
+    let area = f.area();
+    let popup_width = 60.min(area.width.saturating_sub(4));
+    let popup_height = 5.min(area.height.saturating_sub(4));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    let block = Block::default()
+        .title(" Restore last session? ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.selection_bg))
+        .style(Style::default().bg(app.theme.background));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: true }).block(block),
+        popup_area,
+    );
+}
+
+fn render_mute_prompt(app: &App, f: &mut Frame) {
+    let Some(story) = &app.mute_prompt else {
+        return;
+    };
+    let state = |muted: bool| match muted {
+        true => " (muted)",
+        false => "",
+    };
+    let mut lines = Vec::new();
+    if let Some(domain) = crate::internal::mute::story_domain(story) {
+        let muted = app.mute_list.is_domain_muted(&domain);
+        lines.push(Line::from(vec![
+            Span::styled("d ", Style::default().fg(app.theme.selection_bg)),
+            Span::styled(
+                format!("Domain {}{}", domain, state(muted)),
+                Style::default().fg(app.theme.foreground),
+            ),
+        ]));
+    }
+    if let Some(by) = &story.by {
+        let muted = app.mute_list.is_user_muted(by);
+        lines.push(Line::from(vec![
+            Span::styled("a ", Style::default().fg(app.theme.selection_bg)),
+            Span::styled(
+                format!("Author {}{}", by, state(muted)),
+                Style::default().fg(app.theme.foreground),
+            ),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "d/a: Mute or unmute • Esc: Cancel",
+        Style::default().fg(app.theme.comment_time),
+    )));
+
+    let area = f.area();
+    let popup_width = 50.min(area.width.saturating_sub(4));
+    let popup_height = (lines.len() as u16 + 2).min(area.height.saturating_sub(4));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    let block = Block::default()
+        .title(" Mute ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.selection_bg))
+        .style(Style::default().bg(app.theme.background));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
+
+fn render_tag_prompt(app: &App, f: &mut Frame) {
+    let Some(prompt) = &app.tag_prompt else {
+        return;
+    };
+    let text_style = Style::default().fg(app.theme.foreground);
+    let mut input = Line::from(Span::styled(
+        "> ",
+        Style::default().fg(app.theme.selection_bg),
+    ));
+    for line in prompt
+        .input
+        .lines(text_style, text_style.add_modifier(Modifier::REVERSED))
+    {
+        input.spans.extend(line.spans);
+    }
+    // Show the rest of the Tab completion after the text
+    if let Some(tag) = prompt.completion() {
+        input.spans.push(Span::styled(
+            tag.get(prompt.current().len()..)
+                .unwrap_or_default()
+                .to_string(),
+            Style::default().fg(app.theme.comment_time),
+        ));
+    }
+    let lines = vec![
+        Line::from(Span::styled(
+            prompt.title.clone(),
+            Style::default().fg(app.theme.comment_author),
+        )),
+        Line::from(""),
+        input,
+        Line::from(""),
+        Line::from(Span::styled(
+            "Comma-separated, a/b for folders • Tab: Complete • Enter: Save • Esc: Skip",
+            Style::default().fg(app.theme.comment_time),
+        )),
+    ];
+
+    let area = f.area();
+    let popup_width = 60.min(area.width.saturating_sub(4));
+    let popup_height = (lines.len() as u16 + 2).min(area.height.saturating_sub(4));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    let block = Block::default()
+        .title(" Bookmark tags ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.selection_bg))
+        .style(Style::default().bg(app.theme.background));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(ratatui::widgets::Wrap { trim: false }),
+        popup_area,
+    );
+}
+
+fn render_item_prompt(app: &App, f: &mut Frame) {
+    let Some(prompt) = &app.item_prompt else {
+        return;
+    };
+    let text_style = Style::default().fg(app.theme.foreground);
+    let mut input = Line::from(Span::styled(
+        "> ",
+        Style::default().fg(app.theme.selection_bg),
+    ));
+    for line in prompt
+        .input
+        .lines(text_style, text_style.add_modifier(Modifier::REVERSED))
+    {
+        input.spans.extend(line.spans);
+    }
+    let status = match &prompt.error {
+        Some(error) => Span::styled(error.clone(), Style::default().fg(Color::Red)),
+        None => Span::styled(
+            "Item id, HN link or article URL • Enter: Open • Esc: Cancel",
+            Style::default().fg(app.theme.comment_time),
+        ),
+    };
+    let lines = vec![input, Line::from(""), Line::from(status)];
+
+    let area = f.area();
+    let popup_width = 60.min(area.width.saturating_sub(4));
+    let popup_height = (lines.len() as u16 + 2).min(area.height.saturating_sub(4));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    let block = Block::default()
+        .title(" Open item ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.selection_bg))
+        .style(Style::default().bg(app.theme.background));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(ratatui::widgets::Wrap { trim: false }),
+        popup_area,
+    );
+}
+
+fn render_note_editor(app: &App, f: &mut Frame) {
+    let Some(editor) = &app.note_editor else {
+        return;
+    };
+    let text_style = Style::default().fg(app.theme.foreground);
+    let mut lines = vec![
+        Line::from(Span::styled(
+            editor.title.clone(),
+            Style::default().fg(app.theme.comment_author),
+        )),
+        Line::from(""),
+    ];
+    lines.extend(
+        editor
+            .input
+            .lines(text_style, text_style.add_modifier(Modifier::REVERSED)),
+    );
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Enter: New line • Ctrl+s: Save • Esc: Cancel",
+        Style::default().fg(app.theme.comment_time),
+    )));
+
+    let area = f.area();
+    let popup_width = 70.min(area.width.saturating_sub(4));
+    let popup_height = (lines.len() as u16 + 2)
+        .max(10)
+        .min(area.height.saturating_sub(4));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    let block = Block::default()
+        .title(" Bookmark note ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.selection_bg))
+        .style(Style::default().bg(app.theme.background));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(ratatui::widgets::Wrap { trim: false }),
+        popup_area,
+    );
+}
+
+/// Commands matching the typed text, each with the keys bound to it in the current
+/// view, near the top of the screen as in VS Code.
+fn render_command_palette(app: &App, f: &mut Frame) {
+    let Some(palette) = &app.command_palette else {
+        return;
+    };
+
+    let area = f.area();
+    let popup_width = 70.min(area.width.saturating_sub(4));
+    let popup_height = 20.min(area.height.saturating_sub(2));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height / 8).min(area.height.saturating_sub(popup_height));
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    let text_style = Style::default().fg(app.theme.foreground);
+    let mut input = Line::from(Span::styled(
+        ": ",
+        Style::default().fg(app.theme.selection_bg),
+    ));
+    for line in palette
+        .input
+        .lines(text_style, text_style.add_modifier(Modifier::REVERSED))
+    {
+        input.spans.extend(line.spans);
+    }
+    let mut lines = vec![input, Line::from("")];
+    if palette.match_count() == 0 {
+        lines.push(Line::from(Span::styled(
+            "No matching commands",
+            Style::default().fg(app.theme.comment_time),
+        )));
+    }
+
+    // Keep the selected row in view
+    let rows = popup_height.saturating_sub(4).max(1) as usize;
+    let offset = palette.selected.saturating_sub(rows - 1);
+    let width = popup_width.saturating_sub(2) as usize;
+    let context = app.key_context();
+    for (i, command) in palette.matches().enumerate().skip(offset).take(rows) {
+        let keys = app
+            .keybindings
+            .keys_for(&command.action, context)
+            .join(", ");
+        let (name_style, key_style) = match i == palette.selected {
+            true => {
+                let style = Style::default()
+                    .fg(app.theme.selection_fg)
+                    .bg(app.theme.selection_bg);
+                (style, style)
+            }
+            false => (text_style, Style::default().fg(app.theme.comment_time)),
+        };
+        let gap = width.saturating_sub(command.name.chars().count() + keys.chars().count() + 2);
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {}{}", command.name, " ".repeat(gap)), name_style),
+            Span::styled(format!("{} ", keys), key_style),
+        ]));
+    }
+
+    let block = Block::default()
+        .title(format!(" Commands ({}) ", palette.match_count()))
+        .title_bottom(Line::from(Span::styled(
+            " ↑/↓: Select • Enter: Run • Esc: Close ",
+            Style::default().fg(app.theme.comment_time),
+        )))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.selection_bg))
+        .style(Style::default().bg(app.theme.background));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
+
+/// Numbered links of the open page; with hyperlinks on, each row is also clickable.
+fn render_read_later(app: &App, f: &mut Frame) {
+    use crate::internal::read_later::ItemState;
+
+    let Some(view) = &app.read_later_view else {
+        return;
+    };
+    let items = &app.read_later.items;
+
+    let area = f.area();
+    let popup_width = 80.min(area.width.saturating_sub(4));
+    let popup_height = (items.len().max(1) as u16 + 4).min(area.height.saturating_sub(4));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    // Scroll so the selected row stays in view
+    let rows = popup_height.saturating_sub(4).max(1) as usize;
+    let selected = view.selected.min(items.len().saturating_sub(1));
+    let offset = selected.saturating_sub(rows - 1);
+    let width = popup_width.saturating_sub(2) as usize;
+    let mut lines: Vec<Line> = items
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(rows)
+        .map(|(i, item)| {
+            let (marker, state) = match &item.state {
+                ItemState::Queued => ("·", "queued".to_string()),
+                ItemState::Downloading => ("⇣", "downloading…".to_string()),
+                ItemState::Done => (
+                    "✓",
+                    match item.downloaded.article {
+                        true => format!("article, {} comments", item.downloaded.comments),
+                        false => format!("{} comments", item.downloaded.comments),
+                    },
+                ),
+                ItemState::Failed(e) => ("✗", format!("failed: {}", e)),
+            };
+            let state = super::row_template::truncate(&state, width / 2);
+            let title_width = width.saturating_sub(textwrap::core::display_width(&state) + 4);
+            let title = super::row_template::truncate(&item.title, title_width);
+            let title_style = match i == selected {
+                true => Style::default()
+                    .fg(app.theme.selection_fg)
+                    .bg(app.theme.selection_bg),
+                false => Style::default().fg(app.theme.foreground),
+            };
+            let state_style = match &item.state {
+                ItemState::Failed(_) => Style::default().fg(Color::Red),
+                _ => Style::default().fg(app.theme.comment_time),
+            };
+            Line::from(vec![
+                Span::styled(format!("{} ", marker), state_style),
+                Span::styled(title, title_style),
+                Span::styled(format!("  {}", state), state_style),
+            ])
+        })
+        .collect();
+    if items.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "Nothing queued; R queues the focused story",
+            Style::default().fg(app.theme.comment_time),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Enter: Open • r: Retry • R: Retry all failed • d: Remove • Esc: Close",
+        Style::default().fg(app.theme.comment_time),
+    )));
+
+    let (done, total) = app.read_later.progress();
+    let block = Block::default()
+        .title(format!(" Read later ({}/{} downloaded) ", done, total))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.selection_bg))
+        .style(Style::default().bg(app.theme.background));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
+
+fn render_link_picker(app: &App, f: &mut Frame) {
+    let Some(picker) = &app.link_picker else {
+        return;
+    };
+
+    let area = f.area();
+    let popup_width = 80.min(area.width.saturating_sub(4));
+    let popup_height = (picker.links.len() as u16 + 4).min(area.height.saturating_sub(4));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    // Scroll so the selected row stays in view
+    let rows = popup_height.saturating_sub(4).max(1) as usize;
+    let offset = picker.selected.saturating_sub(rows - 1);
+    let number_width = picker.links.len().to_string().len();
+    let mut lines: Vec<Line> = picker
+        .links
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(rows)
+        .map(|(i, link)| {
+            let (text_style, url_style) = match i == picker.selected {
+                true => {
+                    let style = Style::default()
+                        .fg(app.theme.selection_fg)
+                        .bg(app.theme.selection_bg);
+                    (style, style)
+                }
+                false => (
+                    Style::default().fg(app.theme.foreground),
+                    Style::default().fg(app.theme.comment_time),
+                ),
+            };
+            let mut spans = vec![
+                Span::styled(
+                    format!("{:>width$} ", i + 1, width = number_width),
+                    Style::default().fg(app.theme.comment_time),
+                ),
+                Span::styled(link.text.clone(), text_style),
+            ];
+            if link.text != link.url {
+                spans.push(Span::styled(format!("  {}", link.url), url_style));
+            }
+            Line::from(spans)
+        })
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        match picker.typed.is_empty() {
+            true => "1-9: Open by number • j/k: Move • Enter: Open • Esc: Cancel".to_string(),
+            false => format!("Link {}… • Enter: Open • Esc: Cancel", picker.typed),
+        },
+        Style::default().fg(app.theme.comment_time),
+    )));
+
+    let block = Block::default()
+        .title(format!(" Links ({}) ", picker.links.len()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.selection_bg))
+        .style(Style::default().bg(app.theme.background));
+    let inner = block.inner(popup_area);
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(Paragraph::new(lines).block(block), popup_area);
+
+    if app.hyperlinks {
+        let prefix = number_width as u16 + 1;
+        for (row, link) in picker.links.iter().skip(offset).take(rows).enumerate() {
+            let row_area = Rect::new(
+                inner.x + prefix,
+                inner.y + row as u16,
+                inner.width.saturating_sub(prefix),
+                1,
+            );
+            super::hyperlink::apply(f.buffer_mut(), row_area, &link.url);
+        }
+    }
+}
+
+/// Make the URL in the story details block at `area` a hyperlink. Titles may wrap,
+/// so the `URL: ` row is looked up in what was drawn.
+fn link_details_url(app: &App, f: &mut Frame, area: Rect, url: &str) {
+    if !app.hyperlinks {
+        return;
+    }
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    let prefix = "URL: ";
+    let buf = f.buffer_mut();
+    let found = (inner.top()..inner.bottom()).find_map(|y| {
+        (inner.left()..inner.right().saturating_sub(prefix.len() as u16))
+            .find(|&x| {
+                prefix
+                    .chars()
+                    .enumerate()
+                    .all(|(i, c)| buf[(x + i as u16, y)].symbol() == c.to_string())
+            })
+            .map(|x| (x, y))
+    });
+    if let Some((x, y)) = found {
+        let x = x + prefix.len() as u16;
+        let width = (inner.right() - x).min(url.chars().count() as u16);
+        super::hyperlink::apply(buf, Rect::new(x, y, width, 1), url);
+    }
+}
+
+fn render_link_preview(app: &App, f: &mut Frame) {
+    let Some(preview) = &app.link_preview else {
+        return;
+    };
+    let label =
+        |text: &'static str| Span::styled(text, Style::default().fg(app.theme.comment_time));
+    let value = |text: String| Span::styled(text, Style::default().fg(app.theme.foreground));
+
+    let mut lines = vec![Line::from(vec![
+        label("Link:  "),
+        value(preview.url.clone()),
+    ])];
+    lines.push(match (&preview.resolved, preview.redirected_to()) {
+        (None, _) => Line::from(vec![
+            label("Opens: "),
+            value(format!("{} resolving…", app.get_spinner_char())),
+        ]),
+        (Some(Ok(_)), Some(target)) => {
+            Line::from(vec![label("Opens: "), value(target.to_string())])
+        }
+        (Some(Ok(_)), None) => {
+            Line::from(vec![label("Opens: "), value("no redirects".to_string())])
+        }
+        (Some(Err(e)), _) => Line::from(vec![
+            label("Opens: "),
+            Span::styled(
+                format!("could not resolve ({})", e),
+                Style::default().fg(Color::Yellow),
+            ),
+        ]),
+    });
+    let warnings = preview.warnings();
+    if !warnings.is_empty() {
+        lines.push(Line::from(""));
+    }
+    for warning in warnings {
+        lines.push(Line::from(Span::styled(
+            format!("⚠ {}", warning),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Enter/o: Open in browser • Esc: Cancel",
+        Style::default().fg(app.theme.comment_time),
+    )));
+
+    let area = f.area();
+    let popup_width = 80.min(area.width.saturating_sub(4));
+    let inner_width = popup_width.saturating_sub(2).max(1);
+    let wrapped_rows: u16 = lines
+        .iter()
+        .map(|line| (line.width() as u16).div_ceil(inner_width).max(1))
+        .sum();
+    let popup_height = (wrapped_rows + 2).min(area.height.saturating_sub(4));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    let block = Block::default()
+        .title(" Open link? ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.selection_bg))
+        .style(Style::default().bg(app.theme.background));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        popup_area,
+    );
+}
+
+fn render_progress_overlay(app: &App, f: &mut Frame, label: &str, progress: (usize, usize)) {
+    let (loaded, total) = progress;
+    let area = f.area();
+    let popup_width = 50.min(area.width - 4);
+    let popup_height = 3; // Compact: title + progress bar only
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    let spinner = app.get_spinner_char();
+    let percent = match total {
+        0 => 0,
+        t => (loaded as f64 / t as f64 * 100.0) as u16,
+    };
+
+    // Compact title with all info: spinner, action, progress, percentage
+    let title = format!("{} {} [{}/{}] {}%", spinner, label, loaded, total, percent);
+
+    let block = Block::default()
+        .title(title)
+        .title_style(
+            Style::default()
+                .fg(app.theme.foreground)
+                .add_modifier(Modifier::BOLD),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border))
+        .style(Style::default().bg(app.theme.background));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(&block, popup_area);
+
+    let inner_area = block.inner(popup_area);
+
+    // Single progress bar with visual indicator
+    let gauge = ratatui::widgets::Gauge::default()
+        .gauge_style(
+            Style::default()
+                .fg(app.theme.selection_bg)
+                .bg(app.theme.background),
+        )
+        .ratio(percent as f64 / 100.0)
+        .label("");
+    f.render_widget(gauge, inner_area);
+}
+
+#[tracing::instrument(name = "render.list", level = "debug", skip_all)]
+fn render_list(app: &mut App, f: &mut Frame, area: Rect) {
+    use std::borrow::Cow;
+
+    // Remember how many rows fit so background refreshes can target them; rows take
+    // two lines unless a row template says otherwise
+    let row_height = app.row_template.as_ref().map_or(2, |t| t.height());
+    app.list_viewport_rows = area.height as usize / row_height;
+
+    // Only the rows in the viewport (plus overscan) are built; see `list_rows`
+    let mut cache = std::mem::take(&mut app.list_row_cache);
+    let selected = app.story_list_state.selected();
+    let offset = app.story_list_state.offset();
+    let viewport_rows = app.list_viewport_rows;
+    let window_for =
+        |total: usize| super::list_rows::window(total, offset, selected, viewport_rows);
+
+    // Determine which stories to display based on view mode
+    let (total, (range, anchor), stories_to_display): (usize, _, Vec<(usize, Cow<Story>)>) =
+        match app.view_mode {
+            ViewMode::Bookmarks => {
+                // Rows line up with `bookmark_indices`, which the selection refers to.
+                // Stories loaded in app.stories or prefetched show their full details.
+                let shown = app.bookmark_indices();
+                let window = window_for(shown.len());
+                let rows = shown[window.0.clone()]
+                    .iter()
+                    .zip(window.0.clone())
+                    .map(|(idx, row)| {
+                        let bookmarked = &app.bookmarks.stories[*idx];
+                        let loaded = app
+                            .stories
+                            .iter()
+                            .find(|s| s.id == bookmarked.id)
+                            .or_else(|| app.prefetched.get(&bookmarked.id));
+                        let story = match loaded {
+                            Some(story) => Cow::Borrowed(story),
+                            None => Cow::Owned(Story {
+                                id: bookmarked.id,
+                                title: Some(bookmarked.title.clone()),
+                                url: bookmarked.url.clone(),
+                                time: Some(bookmarked.bookmarked_at.timestamp().as_second()),
+                                ..Default::default()
+                            }),
+                        };
+                        (row, story)
+                    })
+                    .collect();
+                (shown.len(), window, rows)
+            }
+            ViewMode::History => {
+                // Rows line up with `history_indices`, narrowed by the history search
+                let shown = app.history_indices();
+                let window = window_for(shown.len());
+                let rows = shown[window.0.clone()]
+                    .iter()
+                    .zip(window.0.clone())
+                    .map(|(entry, idx)| {
+                        let viewed = &app.history.stories[*entry];
+                        let story = crate::internal::models::Story {
+                            id: viewed.id,
+                            title: Some(viewed.title.clone()),
+                            url: viewed.url.clone(),
+                            by: viewed.by.clone(),
+                            score: viewed.score,
+                            // Use viewed_at as the time so it shows "viewed X ago"
+                            time: Some(viewed.viewed_at.timestamp().as_second()),
+                            descendants: viewed.descendants,
+                            kids: None,
+                            parts: None,
+                            item_type: None,
+                            text: None,
+                        };
+                        (idx, Cow::Owned(story))
+                    })
+                    .collect();
+                (shown.len(), window, rows)
+            }
+            ViewMode::ReadingLists => {
+                let items = app
+                    .reading_lists
+                    .get(app.current_reading_list)
+                    .map(|list| list.items.as_slice())
+                    .unwrap_or_default();
+                let window = window_for(items.len());
+                let rows = items[window.0.clone()]
+                    .iter()
+                    .zip(window.0.clone())
+                    .map(|(item, idx)| {
+                        // Tick off finished items in the title; time shows when it was queued
+                        let mut story = item.to_story();
+                        let check = match item.done {
+                            true => "[x] ",
+                            false => "[ ] ",
+                        };
+                        story.title = story.title.map(|t| format!("{}{}", check, t));
+                        (idx, Cow::Owned(story))
+                    })
+                    .collect();
+                (items.len(), window, rows)
+            }
+            _ => {
+                // Filter stories (search query, hidden read stories) for normal list view
+                let all = app.filtered_story_indices();
+                let window = window_for(all.len());
+                let rows = all[window.0.clone()]
+                    .iter()
+                    .map(|(i, story)| (*i, Cow::Borrowed(*story)))
+                    .collect();
+                (all.len(), window, rows)
+            }
+        };
 
-                let meta_line = Line::from(meta_spans);
+    // Reuse formatted rows from earlier frames; a row is rebuilt when anything it
+    // shows changes (rank, score, comments, age text, bookmark/read state)
+    // The prominent selection indicator takes two columns for its margin marker
+    let row_width = match app.config.accessibility.prominent_selection {
+        true => area.width.saturating_sub(2),
+        false => area.width,
+    };
+    cache.begin(format!("{:?}", app.theme), row_width);
+    let list_view = &app.config.ui.list_view;
+    for (idx, story) in &stories_to_display {
+        let time = story.time.as_ref().map(|t| app.format_time(t));
+        let fingerprint = super::list_rows::fingerprint((
+            idx,
+            &story.title,
+            &story.url,
+            &story.by,
+            (story.score, story.descendants, time),
+            (
+                app.bookmarks.contains(story.id),
+                app.bookmarks.tags_of(story.id),
+                // Notes are only drawn in the Bookmarks view
+                app.bookmarks
+                    .note_of(story.id)
+                    .filter(|_| app.view_mode == ViewMode::Bookmarks),
+            ),
+            app.history.is_read(story.id),
+            app.reading_minutes.get(&story.id),
+            (app.story_delta(story), app.story_trend(story)),
+            (
+                list_view.show_score,
+                list_view.show_domain,
+                list_view.show_age,
+                list_view.show_comments,
+                list_view.domain_chips,
+                list_view.domain_palette,
+                (list_view.publisher_names, list_view.domain_icons),
+            ),
+            app.domain_meta.version(),
+        ));
+        cache.ensure(story.id, fingerprint, || {
+            story_row_lines(app, *idx, story, row_width)
+        });
+    }
+    cache.finish();
+    drop(stories_to_display);
 
-                // Combine title lines with metadata line
-                let mut all_lines = title_lines;
-                all_lines.push(meta_line);
+    let mut items: Vec<ListItem> = match app.view_mode {
+        ViewMode::History => {
+            // The first row of each day group carries its heading
+            let now = jiff::Zoned::now();
+            let periods: Vec<Period> = app
+                .history_indices()
+                .iter()
+                .map(|idx| Period::of(&app.history.stories[*idx].viewed_at, &now))
+                .collect();
+            let heading = Style::default()
+                .fg(app.theme.comment_time)
+                .add_modifier(Modifier::BOLD);
+            cache
+                .frame_rows()
+                .zip(range.clone())
+                .map(|(mut lines, row)| {
+                    let period = periods[row];
+                    if row == 0 || periods[row - 1] != period {
+                        lines.insert(0, Line::styled(period.label(), heading));
+                    }
+                    ListItem::new(lines)
+                })
+                .collect()
+        }
+        _ => cache.frame_rows().map(ListItem::new).collect(),
+    };
 
-                ListItem::new(all_lines)
-            } else {
-                // Render cheap empty item for off-screen items
-                // This maintains the list indices so scrolling works correctly
-                ListItem::new(Span::raw(""))
-            }
-        })
-        .collect();
+    // While stories are being fetched, pad the list with skeleton rows sized to the
+    // number of stories we expect so the layout does not jump when they arrive.
+    if let (ViewMode::List, true, true, true) = (
+        app.view_mode,
+        app.loading,
+        app.search_query.is_empty(),
+        range.end == total,
+    ) {
+        let expected = match app.story_ids.is_empty() {
+            true => super::skeleton::DEFAULT_LIST_ROWS,
+            false => app
+                .story_ids
+                .len()
+                .saturating_sub(app.loaded_count)
+                .min(super::skeleton::DEFAULT_LIST_ROWS),
+        };
+        items.extend(super::skeleton::list_items(
+            expected,
+            area.width,
+            app.theme.border,
+        ));
+    }
 
     // Place the version next to the "Hacker News" label in the title
     let sort_indicator = format!(
@@ -411,6 +1707,7 @@ fn render_list(app: &mut App, f: &mut Frame, area: Rect) {
             SortBy::Score => "Score",
             SortBy::Comments => "Comments",
             SortBy::Time => "Time",
+            SortBy::Rank => "Rank",
         },
         match app.sort_order {
             SortOrder::Ascending => "asc",
@@ -433,15 +1730,64 @@ fn render_list(app: &mut App, f: &mut Frame, area: Rect) {
         ),
     };
 
+    let title = match app.hide_read {
+        true => format!("{} [unread only]", title),
+        false => title,
+    };
+    let title = match app.focus_filters.get(app.current_list_type).label() {
+        Some(label) => format!("{} [focus: {}]", title, label),
+        None => title,
+    };
+
+    // Point out that rows show changes since the previous launch
+    let list_name = app.current_list_type.to_string();
+    let title = match (
+        app.config.ui.list_view.show_deltas,
+        app.previous_snapshot.has_list(&list_name),
+        &app.previous_snapshot.taken_at,
+    ) {
+        (true, true, Some(taken_at)) => format!(
+            "{} [changes since {}]",
+            title,
+            crate::utils::datetime::format_timestamp(&taken_at.timestamp().as_second())
+        ),
+        _ => title,
+    };
+
     let title = match app.view_mode {
-        ViewMode::History => format!("History ({} stories)", items.len()),
-        ViewMode::Bookmarks => format!("Bookmarks ({} stories)", items.len()),
+        ViewMode::History => match (&app.history_search, app.history_query.is_empty()) {
+            (Some(input), _) => format!("History ({} stories) /{}_", total, input.text()),
+            (None, false) => format!("History ({} stories) /{}", total, app.history_query),
+            (None, true) => format!("History ({} stories)", total),
+        },
+        ViewMode::Bookmarks => match &app.bookmark_tag_filter {
+            Some(tag) => format!("Bookmarks #{} ({} stories)", tag, total),
+            None => format!("Bookmarks ({} stories)", total),
+        },
+        ViewMode::ReadingLists => match app.reading_lists.get(app.current_reading_list) {
+            Some(list) => format!(
+                "Reading list: {} ({} left) [{}/{}]",
+                list.name,
+                list.remaining(),
+                app.current_reading_list + 1,
+                app.reading_lists.lists.len()
+            ),
+            None => "Reading lists (none yet - press a on a story)".to_string(),
+        },
         _ => title,
     };
 
+    let mut block = Block::default();
+    app.sort_header_hits.clear();
+    if let (true, ViewMode::List) = (app.config.ui.list_view.sort_header, app.view_mode) {
+        let (header, hits) = sort_header(app, area);
+        block = block.title_top(header.right_aligned());
+        app.sort_header_hits = hits;
+    }
+
     let list = List::new(items)
         .block(
-            Block::default()
+            block
                 .borders(Borders::ALL)
                 .padding(Padding::new(
                     app.config.ui.padding.horizontal,
@@ -449,43 +1795,551 @@ fn render_list(app: &mut App, f: &mut Frame, area: Rect) {
                     app.config.ui.padding.vertical,
                     app.config.ui.padding.vertical,
                 ))
-                .border_style(Style::default().fg(app.theme.border))
+                .border_style(pane_border_style(app, Pane::List))
                 .title(title)
                 .title_style(Style::default().fg(app.theme.foreground)),
         )
-        .style(Style::default().bg(app.theme.background))
-        .highlight_style(
+        .style(Style::default().bg(app.theme.background));
+    // The prominent indicator keeps a margin marker column on every row so the
+    // titles do not shift when the selection moves
+    let list = match app.config.accessibility.prominent_selection {
+        true => list
+            .highlight_style(
+                Style::default()
+                    .fg(app.theme.foreground)
+                    .bg(app.theme.background)
+                    .add_modifier(Modifier::REVERSED | Modifier::BOLD),
+            )
+            .highlight_symbol("▶ ")
+            .highlight_spacing(HighlightSpacing::Always),
+        false => list.highlight_style(
             Style::default()
                 .bg(app.theme.selection_bg)
                 .fg(app.theme.selection_fg)
                 .add_modifier(Modifier::BOLD),
-        );
+        ),
+    };
+
+    // Render the window with a state relative to its first row, then carry the
+    // scroll position ratatui settled on back to the full list
+    let mut state = ListState::default()
+        .with_offset(anchor - range.start)
+        .with_selected(selected.map(|s| s.saturating_sub(range.start)));
+    f.render_stateful_widget(list, area, &mut state);
+    app.list_row_cache = cache;
+    *app.story_list_state.offset_mut() = range.start + state.offset();
+}
+
+/// Score/Comments/Time header for the top border of a list drawn in `area`, with
+/// an arrow on the active sort column, and the screen area of each column.
+fn sort_header(app: &App, area: Rect) -> (Line<'static>, Vec<(Rect, super::sort::SortBy)>) {
+    use super::sort::{SortBy, SortOrder};
+
+    // Job postings have no comments to sort by
+    let columns: Vec<_> = [
+        (SortBy::Score, "Score"),
+        (SortBy::Comments, "Comments"),
+        (SortBy::Time, "Time"),
+        (SortBy::Rank, "Rank"),
+    ]
+    .into_iter()
+    .filter(|(column, _)| {
+        *column != SortBy::Comments || app.current_list_type != crate::api::StoryListType::Job
+    })
+    .collect();
+    let mut spans = Vec::new();
+    let mut widths = Vec::new();
+    for (i, (column, name)) in columns.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled("│", Style::default().fg(app.theme.border)));
+        }
+        let (label, style) = match (*column == app.sort_by, app.sort_order) {
+            (true, SortOrder::Descending) => (
+                format!(" {} ▼ ", name),
+                Style::default()
+                    .fg(app.theme.selection_bg)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            (true, SortOrder::Ascending) => (
+                format!(" {} ▲ ", name),
+                Style::default()
+                    .fg(app.theme.selection_bg)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            (false, _) => (
+                format!(" {} ", name),
+                Style::default().fg(app.theme.comment_time),
+            ),
+        };
+        let span = Span::styled(label, style);
+        widths.push((spans.len(), span.width() as u16, *column));
+        spans.push(span);
+    }
+    let line = Line::from(spans);
+
+    // Right-aligned titles end just inside the top-right corner
+    let start = area.right().saturating_sub(1 + line.width() as u16);
+    let hits = widths
+        .into_iter()
+        .map(|(index, width, column)| {
+            let before: u16 = line.spans[..index]
+                .iter()
+                .map(|span| span.width() as u16)
+                .sum();
+            (Rect::new(start + before, area.y, width, 1), column)
+        })
+        .collect();
+    (line, hits)
+}
+
+/// Domain label for list rows and the story header: the publisher's initial and a
+/// colored chip, or dimmed "(domain)" text. Shows the publisher's name when known.
+fn domain_spans(app: &App, domain: &str) -> Vec<Span<'static>> {
+    let list_view = &app.config.ui.list_view;
+    let label = match list_view.publisher_names {
+        true => app.domain_meta.publisher(domain).unwrap_or(domain),
+        false => domain,
+    };
+    match list_view.domain_chips {
+        true => {
+            let mut spans = Vec::new();
+            if list_view.domain_icons {
+                spans.push(super::domain_chip::icon(
+                    app.domain_meta.glyph(domain),
+                    app.domain_meta.color(domain),
+                    domain,
+                    list_view.domain_palette,
+                    &app.theme,
+                ));
+            }
+            spans.push(super::domain_chip::chip(
+                domain,
+                label,
+                list_view.domain_palette,
+                &app.theme,
+            ));
+            spans
+        }
+        false => vec![Span::styled(
+            format!("({})", label),
+            Style::default().fg(app.theme.comment_time),
+        )],
+    }
+}
+
+/// Lines of one story row laid out by the configured row template.
+fn templated_row_lines(
+    app: &App,
+    template: &super::row_template::RowTemplate,
+    idx: usize,
+    story: &Story,
+    width: u16,
+) -> Vec<Line<'static>> {
+    use super::row_template::Field;
+
+    let job = story.is_job().then(|| {
+        crate::internal::jobs::JobTitle::parse(story.title.as_deref().unwrap_or_default())
+    });
+    let dim = Style::default().fg(app.theme.comment_time);
+    let value = |field: Field| -> (String, Style) {
+        match field {
+            Field::Rank => ((idx + 1).to_string(), dim),
+            Field::Bookmark => (
+                match app.bookmarks.contains(story.id) {
+                    true => "★".to_string(),
+                    false => String::new(),
+                },
+                Style::default().fg(app.theme.selection_bg),
+            ),
+            Field::Score => {
+                let style = match app.recently_updated(story.id) {
+                    true => Style::default()
+                        .fg(app.theme.score)
+                        .add_modifier(Modifier::REVERSED),
+                    false => Style::default().fg(app.theme.score),
+                };
+                let score = match job {
+                    Some(_) => "job".to_string(),
+                    None => story.score.unwrap_or(0).to_string(),
+                };
+                (score, style)
+            }
+            Field::Title => {
+                let title = job
+                    .as_ref()
+                    .map(|job| job.label())
+                    .filter(|label| !label.is_empty())
+                    .or_else(|| story.title.clone())
+                    .unwrap_or_else(|| "No Title".to_string());
+                let style = match app.history.is_read(story.id) {
+                    true => dim,
+                    false => Style::default()
+                        .fg(app.theme.foreground)
+                        .add_modifier(Modifier::BOLD),
+                };
+                (title, style)
+            }
+            Field::Domain => {
+                let domain = story
+                    .url
+                    .as_deref()
+                    .and_then(crate::utils::url::extract_domain)
+                    .unwrap_or_default();
+                let label = match app.config.ui.list_view.publisher_names {
+                    true => app.domain_meta.publisher(&domain).map(str::to_string),
+                    false => None,
+                };
+                (label.unwrap_or(domain), dim)
+            }
+            Field::Age => (
+                story
+                    .time
+                    .as_ref()
+                    .map(|t| app.format_time(t))
+                    .unwrap_or_default(),
+                dim,
+            ),
+            Field::Comments => match &job {
+                Some(job) => (job.batch.clone().unwrap_or_default(), dim),
+                None => (story.descendants.unwrap_or(0).to_string(), dim),
+            },
+            Field::Author => (
+                story.by.clone().unwrap_or_default(),
+                Style::default().fg(app.theme.comment_author),
+            ),
+            Field::ReadingTime => (
+                app.reading_minutes
+                    .get(&story.id)
+                    .filter(|m| **m > 0)
+                    .map(|m| format!("{} min", m))
+                    .unwrap_or_default(),
+                dim,
+            ),
+            Field::Tags => {
+                let tags: Vec<String> = app
+                    .bookmarks
+                    .tags_of(story.id)
+                    .iter()
+                    .map(|tag| format!("#{}", tag))
+                    .collect();
+                (tags.join(" "), Style::default().fg(app.theme.selection_bg))
+            }
+            Field::Delta => (
+                app.story_delta(story)
+                    .map(|d| d.label())
+                    .unwrap_or_default(),
+                Style::default().fg(app.theme.score),
+            ),
+            Field::Trend => (
+                app.story_trend(story).unwrap_or_default(),
+                Style::default().fg(app.theme.score),
+            ),
+        }
+    };
+
+    // Inside the list borders and padding
+    let inner = width.saturating_sub(2 + 2 * app.config.ui.padding.horizontal);
+    template.render(inner as usize, Style::default().fg(app.theme.border), value)
+}
+
+/// Formatted lines (title, wrapped title continuation, metadata) of one story row.
+fn story_row_lines(app: &App, idx: usize, story: &Story, width: u16) -> Vec<Line<'static>> {
+    if let Some(template) = &app.row_template {
+        return templated_row_lines(app, template, idx, story, width);
+    }
+
+    let title = story.title.as_deref().unwrap_or("No Title");
+    let score = story.score.unwrap_or(0);
+    let by = story.by.as_deref().unwrap_or("unknown");
+    let comments = story.descendants.unwrap_or(0);
+
+    // Job postings have no discussion: show who is hiring for what instead
+    let job = story
+        .is_job()
+        .then(|| crate::internal::jobs::JobTitle::parse(title));
+    let job_label = job
+        .as_ref()
+        .map(|job| job.label())
+        .filter(|label| !label.is_empty());
+    let title = job_label.as_deref().unwrap_or(title);
+
+    // Domain as a colored chip, or as dimmed "(domain)" text
+    let domain_spans = story
+        .url
+        .as_ref()
+        .and_then(|url| crate::utils::url::extract_domain(url))
+        .map(|d| domain_spans(app, &d));
+
+    let time = story
+        .time
+        .as_ref()
+        .map(|t| app.format_time(t))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    // Show score with leading space for proper alignment
+    let score = match job {
+        Some(_) => format!("{:>3} ", "job"),
+        None => format!("{:3} ", score),
+    };
+
+    // Check if story is bookmarked
+    let bookmark_indicator = match app.bookmarks.contains(story.id) {
+        true => "★ ",
+        false => "",
+    };
+
+    // Calculate available width for title
+    let prefix_len = 4 + 2 + // index + bookmark
+        if app.config.ui.list_view.show_score { 5 } else { 0 }; // score with spacing
+
+    let wrapped_title = calculate_wrapped_title(title, width, prefix_len);
+
+    // Unread stories stand out in bold; already-opened ones are muted
+    let title_style = match app.history.is_read(story.id) {
+        true => Style::default().fg(app.theme.comment_time),
+        false => Style::default()
+            .fg(app.theme.foreground)
+            .add_modifier(Modifier::BOLD),
+    };
+
+    // Create title line(s)
+    let mut title_lines = Vec::new();
+    for (i, title_part) in wrapped_title.iter().enumerate() {
+        let line_spans = match i {
+            0 => {
+                // First line: include index, bookmark, score, and title
+                let mut spans = vec![
+                    Span::styled(
+                        format!("{:<4}", idx + 1),
+                        Style::default().fg(app.theme.comment_time),
+                    ),
+                    Span::styled(
+                        bookmark_indicator,
+                        Style::default().fg(app.theme.selection_bg),
+                    ),
+                ];
+
+                if app.config.ui.list_view.show_score {
+                    // Briefly invert the score when a live update changed it
+                    let score_style = match app.recently_updated(story.id) {
+                        true => Style::default()
+                            .fg(app.theme.score)
+                            .add_modifier(Modifier::REVERSED),
+                        false => Style::default().fg(app.theme.score),
+                    };
+                    spans.push(Span::styled(format!("{} ", score), score_style));
+                }
+
+                spans.push(Span::styled(title_part.to_string(), title_style));
+
+                // Add domain on first line if configured and only one line
+                if let (true, 1, Some(domain)) = (
+                    app.config.ui.list_view.show_domain,
+                    wrapped_title.len(),
+                    &domain_spans,
+                ) {
+                    spans.push(Span::raw(" "));
+                    spans.extend(domain.iter().cloned());
+                }
+
+                spans
+            }
+            _ => {
+                // Continuation lines: indent and show title part only
+                vec![Span::styled(format!("    {}", title_part), title_style)]
+            }
+        };
+
+        title_lines.push(Line::from(line_spans));
+    }
+
+    // If domain configured and title wrapped to multiple lines, add domain on last line
+    if app.config.ui.list_view.show_domain
+        && wrapped_title.len() > 1
+        && let (Some(last_line), Some(domain)) = (title_lines.last_mut(), domain_spans)
+    {
+        last_line.spans.push(Span::raw(" "));
+        last_line.spans.extend(domain);
+    }
+
+    // Build metadata line with optional fields
+    let mut meta_spans = vec![Span::styled("    ", Style::default())]; // Indent
+    let mut first_field = true;
+
+    // Add time if configured
+    if app.config.ui.list_view.show_age {
+        meta_spans.push(Span::styled(
+            time,
+            Style::default().fg(app.theme.comment_time),
+        ));
+        first_field = false;
+    }
+
+    // Add comments if configured; jobs show their YC batch in that spot
+    let comments = match &job {
+        Some(job) => job.batch.clone(),
+        None => Some(format!("{} comments", comments)),
+    };
+    if let (true, Some(comments)) = (app.config.ui.list_view.show_comments, comments) {
+        if !first_field {
+            meta_spans.push(Span::styled(" | ", Style::default().fg(app.theme.border)));
+        }
+        meta_spans.push(Span::styled(
+            comments,
+            Style::default().fg(app.theme.comment_time),
+        ));
+        first_field = false;
+    }
+
+    // Reading time of a loaded article
+    if let (true, Some(minutes)) = (
+        app.config.ui.list_view.show_reading_time,
+        app.reading_minutes.get(&story.id).filter(|m| **m > 0),
+    ) {
+        if !first_field {
+            meta_spans.push(Span::styled(" | ", Style::default().fg(app.theme.border)));
+        }
+        meta_spans.push(Span::styled(
+            format!("{} min", minutes),
+            Style::default().fg(app.theme.comment_time),
+        ));
+        first_field = false;
+    }
+
+    // Always show author
+    match first_field {
+        false => meta_spans.push(Span::styled(
+            " | by ",
+            Style::default().fg(app.theme.border),
+        )),
+        true => meta_spans.push(Span::styled("by ", Style::default().fg(app.theme.border))),
+    }
+    meta_spans.push(Span::styled(
+        by.to_string(),
+        Style::default().fg(app.theme.comment_author),
+    ));
+
+    // Tags of bookmarked stories
+    let tags = app.bookmarks.tags_of(story.id);
+    if !tags.is_empty() {
+        meta_spans.push(Span::styled(" | ", Style::default().fg(app.theme.border)));
+        let chips: Vec<String> = tags.iter().map(|tag| format!("#{}", tag)).collect();
+        meta_spans.push(Span::styled(
+            chips.join(" "),
+            Style::default().fg(app.theme.selection_bg),
+        ));
+    }
+
+    // The first line of a bookmark's note, in the Bookmarks view
+    if let (ViewMode::Bookmarks, Some(note)) = (app.view_mode, app.bookmarks.note_of(story.id)) {
+        meta_spans.push(Span::styled(" | ✎ ", Style::default().fg(app.theme.border)));
+        meta_spans.push(Span::styled(
+            note.lines().next().unwrap_or_default().to_string(),
+            Style::default()
+                .fg(app.theme.foreground)
+                .add_modifier(Modifier::ITALIC),
+        ));
+    }
 
-    f.render_stateful_widget(list, area, &mut app.story_list_state);
+    // What changed since the previous launch
+    let delta = app
+        .story_delta(story)
+        .map(|d| d.label())
+        .unwrap_or_default();
+    if !delta.is_empty() {
+        meta_spans.push(Span::styled(" | ", Style::default().fg(app.theme.border)));
+        meta_spans.push(Span::styled(delta, Style::default().fg(app.theme.score)));
+    }
+    // How the score moved while the app ran
+    if let Some(trend) = app.story_trend(story) {
+        meta_spans.push(Span::styled(" | ", Style::default().fg(app.theme.border)));
+        meta_spans.push(Span::styled(trend, Style::default().fg(app.theme.score)));
+    }
+    if app.recently_updated(story.id) {
+        meta_spans.push(Span::styled(
+            " ↻ updated",
+            Style::default().fg(app.theme.score),
+        ));
+    }
+
+    let meta_line = Line::from(meta_spans);
+
+    // Combine title lines with metadata line
+    let mut all_lines = title_lines;
+    all_lines.push(meta_line);
+
+    all_lines
 }
 
+#[tracing::instrument(name = "render.comments", level = "debug", skip_all)]
 fn render_detail(app: &mut App, f: &mut Frame, area: Rect) {
     if let Some(story) = &app.selected_story {
-        let chunks = Layout::default()
+        // Polls get a panel of options between the details and the comments
+        let poll_height = match (&app.poll, story.is_poll()) {
+            (Some(poll), _) => (poll.options.len() as u16 + 2).min(area.height / 3),
+            (None, true) => 3,
+            (None, false) => 0,
+        };
+        let rows = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Length(5), Constraint::Min(0)])
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Length(poll_height),
+                Constraint::Min(0),
+            ])
             .split(area);
+        let chunks = [rows[0], rows[2]];
+        if poll_height > 0 {
+            render_poll(app, f, rows[1]);
+        }
 
         let title = story.title.as_deref().unwrap_or("No Title");
         let url = story.url.as_deref().unwrap_or("No URL");
         let time = story
             .time
             .as_ref()
-            .map(crate::utils::datetime::format_timestamp)
+            .map(|t| app.format_time(t))
             .unwrap_or_else(|| "unknown".to_string());
-        let text = format!(
-            "Title: {}\nURL: {}\nScore: {}\nBy: {}\nTime: {}",
-            title,
-            url,
-            story.score.unwrap_or(0),
-            story.by.as_deref().unwrap_or("unknown"),
-            time
-        );
+        let mut url_line = vec![Span::raw(format!("URL: {}", url))];
+        if let Some(domain) = story
+            .url
+            .as_deref()
+            .and_then(crate::utils::url::extract_domain)
+        {
+            url_line.push(Span::raw(" "));
+            url_line.extend(domain_spans(app, &domain));
+        }
+        let (text, details_title) = match story.is_job() {
+            true => {
+                let job = crate::internal::jobs::JobTitle::parse(title);
+                let company = match (&job.company, &job.batch) {
+                    (Some(company), Some(batch)) => format!("{} ({})", company, batch),
+                    (Some(company), None) => company.clone(),
+                    (None, _) => story.by.clone().unwrap_or_else(|| "unknown".to_string()),
+                };
+                let text = vec![
+                    Line::from(format!("Company: {}", company)),
+                    Line::from(format!("Role: {}", job.role.as_deref().unwrap_or(title))),
+                    Line::from(url_line),
+                    Line::from(format!("Posted: {}", time)),
+                ];
+                (text, "Job Posting")
+            }
+            false => {
+                let text = vec![
+                    Line::from(format!("Title: {}", title)),
+                    Line::from(url_line),
+                    Line::from(format!(
+                        "Score: {} · Rank score: {:.2}",
+                        story.score.unwrap_or(0),
+                        super::sort::rank_score(story, jiff::Timestamp::now().as_second())
+                    )),
+                    Line::from(format!("By: {}", story.by.as_deref().unwrap_or("unknown"))),
+                    Line::from(format!("Time: {}", time)),
+                ];
+                (text, "Story Details")
+            }
+        };
 
         let p = Paragraph::new(text)
             .style(
@@ -502,104 +2356,94 @@ fn render_detail(app: &mut App, f: &mut Frame, area: Rect) {
                         app.config.ui.padding.vertical,
                         app.config.ui.padding.vertical,
                     ))
-                    .border_style(Style::default().fg(app.theme.border))
-                    .title("Story Details")
+                    .border_style(pane_border_style(app, Pane::Detail))
+                    .title(details_title)
                     .title_style(Style::default().fg(app.theme.foreground)),
             )
             .wrap(Wrap { trim: true });
         f.render_widget(p, chunks[0]);
+        if let Some(url) = &story.url {
+            link_details_url(app, f, chunks[0], url);
+        }
 
-        let comment_area_width = chunks[1].width.saturating_sub(4).max(20) as usize; // Ensure minimum width
-
-        let mut all_lines: Vec<Line> = Vec::new();
-        let mut skip_until_depth: Option<usize> = None;
-
-        for row in &app.comments {
-            // Skip collapsed children
-            if let Some(until_depth) = skip_until_depth {
-                match row.depth.cmp(&until_depth) {
-                    std::cmp::Ordering::Greater => continue,
-                    _ => skip_until_depth = None,
-                }
-            }
-
-            let author = row.comment.by.as_deref().unwrap_or("unknown");
-            let text = row.comment.text.as_deref().unwrap_or("[deleted]");
-            let clean_text = crate::utils::html::extract_text_from_html(text);
-            let time = row
-                .comment
-                .time
-                .as_ref()
-                .map(crate::utils::datetime::format_timestamp)
-                .unwrap_or_else(|| "unknown".to_string());
-
-            // Indentation and visual guides
-            let indent = "  ".repeat(row.depth);
-            let mut guide = String::new();
-            for i in 0..row.depth {
-                match i.cmp(&row.depth.saturating_sub(1)) {
-                    std::cmp::Ordering::Less => guide.push_str("│ "),
-                    _ => guide.push_str("└─"),
-                }
-            }
-
-            // Collapse indicator
-            let has_kids =
-                row.comment.kids.is_some() && !row.comment.kids.as_ref().unwrap().is_empty();
-            let collapse_indicator = match (has_kids, row.expanded) {
-                (true, true) => "[-] ",
-                (true, false) => "[+] ",
-                _ => "",
-            };
-
-            // Set skip flag if collapsed
-            if has_kids && !row.expanded {
-                skip_until_depth = Some(row.depth);
-            }
+        if story.is_job() {
+            return render_job_description(app, f, chunks[1]);
+        }
 
-            // Author and time line with indentation
-            all_lines.push(Line::from(vec![
-                Span::styled(guide.clone(), Style::default().fg(app.theme.border)),
-                Span::styled(
-                    collapse_indicator,
-                    Style::default().fg(app.theme.comment_time),
-                ),
-                Span::styled(author, Style::default().fg(app.theme.comment_author)),
-                Span::styled(
-                    format!(" ({})", time),
-                    Style::default().fg(app.theme.comment_time),
-                ),
-            ]));
-
-            // Wrapped text lines with indentation
-            let available_width = comment_area_width.saturating_sub(row.depth * 2);
-            let wrapped_text = textwrap::wrap(&clean_text, available_width.max(20));
-            for line in wrapped_text {
-                all_lines.push(Line::from(vec![
-                    Span::styled(indent.clone(), Style::default()),
-                    Span::styled(line.to_string(), Style::default().fg(app.theme.foreground)),
-                ]));
-            }
+        let comment_area_width = chunks[1].width.saturating_sub(4).max(20) as usize; // Ensure minimum width
 
-            // Separator
-            all_lines.push(Line::from(Span::styled(
-                "---",
-                Style::default().fg(app.theme.border),
-            )));
-            all_lines.push(Line::from("")); // Empty line for spacing
+        // Wrapping every comment is expensive; reuse the layout until something changes
+        let me = app.auth.session().map(|session| session.username);
+        let options = crate::internal::ui::comment_view::ThreadOptions {
+            conversations: &app.conversation_threads,
+            diff: app.thread_diff.as_ref(),
+            muted_users: (!app.show_muted).then_some(&app.mute_list.users),
+            hints: app.config.ui.comment_hints,
+            time_format: app.time_format,
+            op: story.by.as_deref(),
+            me: me.as_deref(),
+            palette: app.config.ui.list_view.domain_palette,
+        };
+        if app
+            .comment_layout
+            .update(&app.comments, options, &app.theme, comment_area_width)
+        {
+            app.comment_line_index
+                .clone_from(&app.comment_layout.line_index);
+            app.comment_search.find(&app.comment_layout.lines);
         }
+        let mut all_lines: Vec<Line> = app
+            .comment_layout
+            .lines
+            .iter()
+            .map(super::list_rows::borrow_line)
+            .collect();
+        app.comment_search.highlight(&mut all_lines, &app.theme);
+
+        // Show placeholder comment blocks until the tree arrives
+        if app.comments_loading && app.comments.is_empty() {
+            let expected = story
+                .descendants
+                .map(|d| d as usize)
+                .unwrap_or(app.comment_ids.len())
+                .min(super::skeleton::MAX_COMMENT_BLOCKS);
+            all_lines =
+                super::skeleton::comment_lines(expected, comment_area_width, app.theme.border);
+        }
+
+        let padding = &app.config.ui.padding;
+        let height = chunks[1].height.saturating_sub(2 + 2 * padding.vertical) as usize;
+        app.comments_scroll.update(all_lines.len(), height);
 
+        // While threads stream in, show how many top-level comments are still coming
+        let progress = match (app.comments_loading, app.comment_roots_remaining) {
+            (true, remaining) if remaining > 0 && !app.comments.is_empty() => format!(
+                " · {} loading {} more threads",
+                app.get_spinner_char(),
+                remaining
+            ),
+            _ => String::new(),
+        };
+        let diff = app
+            .thread_diff
+            .as_ref()
+            .map(|diff| format!(" · diff {}", diff.summary()))
+            .unwrap_or_default();
         let comments_title = match app.comment_ids.len() {
-            0 => "Comments (Tab to view Article)".to_string(),
+            0 => format!("Comments (Tab to view Article){}", diff),
             len => format!(
-                "Comments ({}/{}) - n: Load More | Tab: Article",
-                app.loaded_comments_count, len
+                "Comments ({}/{}) - n: Load More | Tab: Article{}{}{}",
+                app.loaded_comments_count,
+                len,
+                progress,
+                diff,
+                scroll_position(&app.comments_scroll)
             ),
         };
 
         let paragraph = Paragraph::new(all_lines)
             .style(Style::default().bg(app.theme.background))
-            .scroll((app.comments_scroll as u16, 0))
+            .scroll((app.comments_scroll.offset_u16(), 0))
             .block(
                 Block::default()
                     .borders(Borders::ALL)
@@ -609,15 +2453,38 @@ fn render_detail(app: &mut App, f: &mut Frame, area: Rect) {
                         app.config.ui.padding.vertical,
                         app.config.ui.padding.vertical,
                     ))
-                    .border_style(Style::default().fg(app.theme.border))
+                    .border_style(pane_border_style(app, Pane::Detail))
                     .title(comments_title)
                     .title_style(Style::default().fg(app.theme.foreground)),
             );
         f.render_widget(paragraph, chunks[1]);
+        super::scroll::render_scrollbar(
+            f,
+            chunks[1],
+            &app.comments_scroll,
+            Style::default().fg(app.theme.border),
+        );
+    }
+}
+
+/// " · 42%" for views that don't fit on screen, nothing otherwise.
+fn scroll_position(scroll: &super::scroll::ScrollState) -> String {
+    match scroll.max_offset() {
+        0 => String::new(),
+        _ => format!(" · {}%", scroll.percentage()),
+    }
+}
+
+/// " · 42% read" for articles that don't fit on screen, nothing otherwise.
+fn read_position(scroll: &super::scroll::ScrollState) -> String {
+    match scroll.max_offset() {
+        0 => String::new(),
+        _ => format!(" · {}% read", scroll.read_percentage()),
     }
 }
 
-fn render_article(app: &App, f: &mut Frame, area: Rect) {
+#[tracing::instrument(name = "render.article", level = "debug", skip_all)]
+fn render_article(app: &mut App, f: &mut Frame, area: Rect) {
     // If we have a selected story, show the same metadata block as in the detail view
     match &app.selected_story {
         Some(story) => {
@@ -631,7 +2498,7 @@ fn render_article(app: &App, f: &mut Frame, area: Rect) {
             let time = story
                 .time
                 .as_ref()
-                .map(crate::utils::datetime::format_timestamp)
+                .map(|t| app.format_time(t))
                 .unwrap_or_else(|| "unknown".to_string());
             let meta_text = format!(
                 "Title: {}\nURL: {}\nScore: {}\nBy: {}\nTime: {}",
@@ -657,125 +2524,51 @@ fn render_article(app: &App, f: &mut Frame, area: Rect) {
                             app.config.ui.padding.vertical,
                             app.config.ui.padding.vertical,
                         ))
-                        .border_style(Style::default().fg(app.theme.border))
+                        .border_style(pane_border_style(app, Pane::Detail))
                         .title("Story Details")
                         .title_style(Style::default().fg(app.theme.foreground)),
                 )
                 .wrap(Wrap { trim: true });
             f.render_widget(meta_p, chunks[0]);
+            if let Some(url) = &story.url {
+                link_details_url(app, f, chunks[0], url);
+            }
 
-            let content_lines = match (app.article_loading, &app.article_content) {
-                (true, _) => vec![Line::from("Loading article...")],
+            // Inner size of the article pane (borders and padding excluded)
+            let padding = &app.config.ui.padding;
+            let width = chunks[1]
+                .width
+                .saturating_sub(2 + 2 * padding.horizontal)
+                .max(20) as usize;
+            let height = chunks[1].height.saturating_sub(2 + 2 * padding.vertical) as usize;
+
+            // Wrapping a long article is expensive; wrap once per story/width/theme
+            // and draw only the rows in view
+            let content_lines: Vec<Line> = match (app.article_loading, &app.article_content) {
+                (true, _) => super::skeleton::article_lines(width, app.theme.border),
                 (false, Some(article)) => {
-                    let mut lines = Vec::new();
-                    if !article.title.is_empty() {
-                        lines.push(Line::from(Span::styled(
-                            &article.title,
-                            Style::default()
-                                .fg(app.theme.foreground)
-                                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
-                        )));
-                        lines.push(Line::from(""));
-                    }
-
-                    for element in &article.elements {
-                        match element {
-                            crate::internal::models::ArticleElement::Paragraph(text) => {
-                                lines.push(Line::from(Span::styled(
-                                    text,
-                                    Style::default().fg(app.theme.foreground),
-                                )));
-                                lines.push(Line::from(""));
-                            }
-                            crate::internal::models::ArticleElement::Heading(level, text) => {
-                                let style = match level {
-                                    1 => Style::default()
-                                        .fg(app.theme.foreground)
-                                        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
-                                    2 => Style::default()
-                                        .fg(app.theme.foreground)
-                                        .add_modifier(Modifier::BOLD),
-                                    _ => Style::default()
-                                        .fg(app.theme.foreground)
-                                        .add_modifier(Modifier::ITALIC),
-                                };
-                                lines.push(Line::from(Span::styled(text, style)));
-                                lines.push(Line::from(""));
-                            }
-                            crate::internal::models::ArticleElement::CodeBlock { lang, code } => {
-                                let lang_info = lang.as_deref().unwrap_or("text");
-                                lines.push(Line::from(Span::styled(
-                                    format!("```{}", lang_info),
-                                    Style::default().fg(app.theme.comment_time),
-                                )));
-                                for line in code.lines() {
-                                    lines.push(Line::from(Span::styled(
-                                        line,
-                                        Style::default().fg(app.theme.comment_author), // Use a different color for code
-                                    )));
-                                }
-                                lines.push(Line::from(Span::styled(
-                                    "```",
-                                    Style::default().fg(app.theme.comment_time),
-                                )));
-                                lines.push(Line::from(""));
-                            }
-                            crate::internal::models::ArticleElement::List(items) => {
-                                for item in items {
-                                    lines.push(Line::from(vec![
-                                        Span::styled(" • ", Style::default().fg(app.theme.border)),
-                                        Span::styled(
-                                            item,
-                                            Style::default().fg(app.theme.foreground),
-                                        ),
-                                    ]));
-                                }
-                                lines.push(Line::from(""));
-                            }
-                            crate::internal::models::ArticleElement::Table(rows) => {
-                                lines.push(Line::from(Span::styled(
-                                    "[Table]",
-                                    Style::default()
-                                        .fg(app.theme.comment_time)
-                                        .add_modifier(Modifier::ITALIC),
-                                )));
-                                // Simple ASCII rendering for now
-                                for row in rows {
-                                    let row_text = row.join(" | ");
-                                    lines.push(Line::from(Span::styled(
-                                        format!("| {} |", row_text),
-                                        Style::default().fg(app.theme.foreground),
-                                    )));
-                                }
-                                lines.push(Line::from(""));
-                            }
-                            crate::internal::models::ArticleElement::Image(alt) => {
-                                lines.push(Line::from(Span::styled(
-                                    format!("[IMAGE: {}]", alt),
-                                    Style::default()
-                                        .fg(app.theme.comment_time)
-                                        .add_modifier(Modifier::ITALIC),
-                                )));
-                                lines.push(Line::from(""));
-                            }
-                            crate::internal::models::ArticleElement::Quote(text) => {
-                                lines.push(Line::from(vec![
-                                    Span::styled("│ ", Style::default().fg(app.theme.border)),
-                                    Span::styled(
-                                        text,
-                                        Style::default()
-                                            .fg(app.theme.foreground)
-                                            .add_modifier(Modifier::ITALIC),
-                                    ),
-                                ]));
-                                lines.push(Line::from(""));
-                            }
-                        }
-                    }
-                    lines
+                    app.article_layout
+                        .update(story.id, article, &app.theme, width);
+                    let lines = &app.article_layout.lines;
+                    app.article_scroll.update(lines.len(), height);
+                    lines[app.article_scroll.visible_range()]
+                        .iter()
+                        .map(super::list_rows::borrow_line)
+                        .collect()
                 }
                 (false, None) => vec![Line::from("No content available or failed to load.")],
             };
+            let scroll = match app.article_loading {
+                true => app.article_scroll.offset_u16(),
+                false => 0,
+            };
+            let title = match app.article_loading {
+                true => "Article View (Tab to view Comments)".to_string(),
+                false => format!(
+                    "Article View (Tab to view Comments){}",
+                    read_position(&app.article_scroll)
+                ),
+            };
 
             let p = Paragraph::new(content_lines)
                 .style(
@@ -792,18 +2585,28 @@ fn render_article(app: &App, f: &mut Frame, area: Rect) {
                             app.config.ui.padding.vertical,
                             app.config.ui.padding.vertical,
                         ))
-                        .border_style(Style::default().fg(app.theme.border))
-                        .title("Article View (Tab to view Comments)")
+                        .border_style(pane_border_style(app, Pane::Detail))
+                        .title(title)
                         .title_style(Style::default().fg(app.theme.foreground)),
                 )
-                .wrap(Wrap { trim: true })
-                .scroll((app.article_scroll as u16, 0));
+                .scroll((scroll, 0));
             f.render_widget(p, chunks[1]);
+            if !app.article_loading {
+                super::scroll::render_scrollbar(
+                    f,
+                    chunks[1],
+                    &app.article_scroll,
+                    Style::default().fg(app.theme.border),
+                );
+            }
         }
         None => {
             // Fallback: no selected story, render empty or loading
             let content_lines = match (app.article_loading, &app.article_content) {
-                (true, _) => vec![Line::from("Loading article...")],
+                (true, _) => super::skeleton::article_lines(
+                    area.width.saturating_sub(4) as usize,
+                    app.theme.border,
+                ),
                 (false, Some(_)) => vec![Line::from("Select a story to view article.")],
                 (false, None) => vec![Line::from("Select a story to view article.")],
             };
@@ -823,12 +2626,12 @@ fn render_article(app: &App, f: &mut Frame, area: Rect) {
                             app.config.ui.padding.vertical,
                             app.config.ui.padding.vertical,
                         ))
-                        .border_style(Style::default().fg(app.theme.border))
+                        .border_style(pane_border_style(app, Pane::Detail))
                         .title("Article View (Tab to view Comments)")
                         .title_style(Style::default().fg(app.theme.foreground)),
                 )
                 .wrap(Wrap { trim: true })
-                .scroll((app.article_scroll as u16, 0));
+                .scroll((app.article_scroll.offset_u16(), 0));
             f.render_widget(p, area);
         }
     }
@@ -869,91 +2672,223 @@ fn render_top_bar(app: &App, f: &mut Frame, area: Rect) {
     f.render_widget(p, area);
 }
 
-/// Parse status bar format tokens and replace with actual values
-fn parse_status_bar_format(app: &App, format: &str) -> String {
-    let mut result = format.to_string();
-
-    // {mode} - Current view mode
-    let mode_str = match app.view_mode {
-        ViewMode::List => "List",
-        ViewMode::StoryDetail => "Story",
-        ViewMode::Article => "Article",
-        ViewMode::Bookmarks => "Bookmarks",
-        ViewMode::History => "History",
-    };
-    result = result.replace("{mode}", mode_str);
-
-    // {category} - Story category
-    let category_str = match app.current_list_type {
-        crate::api::StoryListType::Top => "Top",
-        crate::api::StoryListType::New => "New",
-        crate::api::StoryListType::Best => "Best",
-        crate::api::StoryListType::Ask => "Ask",
-        crate::api::StoryListType::Show => "Show",
-        crate::api::StoryListType::Job => "Job",
-    };
-    result = result.replace("{category}", category_str);
-
-    // {count} - Loaded story count
-    result = result.replace("{count}", &app.loaded_count.to_string());
-
-    // {total} - Total story count
-    result = result.replace("{total}", &app.story_ids.len().to_string());
+/// The story categories with their keys, story counts and when they were loaded;
+/// the one shown is highlighted.
+fn render_category_bar(app: &App, f: &mut Frame, area: Rect) {
+    use crate::api::StoryListType;
+    let in_list = matches!(
+        app.view_mode,
+        ViewMode::List | ViewMode::StoryDetail | ViewMode::Article
+    );
+    let categories = [
+        StoryListType::Top,
+        StoryListType::New,
+        StoryListType::Best,
+        StoryListType::Ask,
+        StoryListType::Show,
+        StoryListType::Job,
+    ];
+    let mut spans = Vec::new();
+    for (key, list_type) in categories.into_iter().enumerate() {
+        let (style, stats_style) = match in_list && list_type == app.current_list_type {
+            true => {
+                let active = Style::default()
+                    .fg(app.theme.selection_fg)
+                    .bg(app.theme.selection_bg)
+                    .add_modifier(Modifier::BOLD);
+                (active, active)
+            }
+            false => (
+                Style::default().fg(app.theme.foreground),
+                Style::default().fg(app.theme.comment_time),
+            ),
+        };
+        spans.push(Span::styled(format!(" {} {}", key + 1, list_type), style));
+        let stats = match app.list_refreshed.get(&list_type) {
+            Some((count, at)) => format!(
+                " {} · {} ",
+                count,
+                crate::utils::datetime::format_timestamp(at)
+            ),
+            None => " ".to_string(),
+        };
+        spans.push(Span::styled(stats, stats_style));
+        spans.push(Span::raw(" "));
+    }
+    let p = Paragraph::new(Line::from(spans))
+        .block(
+            Block::default()
+                .padding(Padding::horizontal(app.config.ui.padding.horizontal))
+                .style(Style::default().bg(app.theme.background)),
+        )
+        .style(Style::default().fg(app.theme.foreground));
+    f.render_widget(p, area);
+}
 
-    // {sort} - Sort field
-    let sort_str = match app.sort_by {
-        crate::internal::ui::sort::SortBy::Score => "Score",
-        crate::internal::ui::sort::SortBy::Comments => "Comments",
-        crate::internal::ui::sort::SortBy::Time => "Time",
-    };
-    result = result.replace("{sort}", sort_str);
+/// Value of a status bar token; empty when there is nothing to show.
+fn status_token(app: &App, token: &Token) -> String {
+    match token {
+        Token::Mode => match app.view_mode {
+            ViewMode::List => "List",
+            ViewMode::StoryDetail => "Story",
+            ViewMode::Article => "Article",
+            ViewMode::Bookmarks => "Bookmarks",
+            ViewMode::History => "History",
+            ViewMode::ReadingLists => "Reading Lists",
+        }
+        .to_string(),
+        Token::Category => match app.current_list_type {
+            crate::api::StoryListType::Top => "Top",
+            crate::api::StoryListType::New => "New",
+            crate::api::StoryListType::Best => "Best",
+            crate::api::StoryListType::Ask => "Ask",
+            crate::api::StoryListType::Show => "Show",
+            crate::api::StoryListType::Job => "Job",
+            crate::api::StoryListType::Custom => "Custom",
+        }
+        .to_string(),
+        Token::Count => app.loaded_count.to_string(),
+        Token::Total => app.story_ids.len().to_string(),
+        Token::Sort => match app.sort_by {
+            crate::internal::ui::sort::SortBy::Score => "Score",
+            crate::internal::ui::sort::SortBy::Comments => "Comments",
+            crate::internal::ui::sort::SortBy::Time => "Time",
+            crate::internal::ui::sort::SortBy::Rank => "Rank",
+        }
+        .to_string(),
+        Token::Order => match app.sort_order {
+            crate::internal::ui::sort::SortOrder::Ascending => "↑",
+            crate::internal::ui::sort::SortOrder::Descending => "↓",
+        }
+        .to_string(),
+        Token::Search => app.search_query.query.clone(),
+        Token::Spinner => match app.loading || app.comments_loading || app.article_loading {
+            true => app.get_spinner_char().to_string(),
+            false => String::new(),
+        },
+        Token::LoadingText => app.loading_description().unwrap_or_default(),
+        Token::Theme => app
+            .available_themes
+            .get(app.current_theme_index)
+            .map(|(filename, _)| {
+                std::path::Path::new(filename)
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("Unknown")
+            })
+            .unwrap_or("Default")
+            .to_string(),
+        // Context-sensitive shortcuts (fallback to default behavior)
+        Token::Shortcuts => match app.view_mode {
+            ViewMode::List => "j/k:Nav | Enter:View | b:Bookmark | ?:Help | L:Log | q:Quit",
+            ViewMode::StoryDetail => "Esc:Back | o:Browser | Tab:Article | ?:Help",
+            ViewMode::Article => "Esc:Back | j/k:Scroll | Tab:Comments | ?:Help",
+            ViewMode::Bookmarks => {
+                "Enter:View | #:Tags | e:Note | [/]:Tag filter | Esc:Back | ?:Help"
+            }
+            ViewMode::History => "Enter:View | /:Search | d:Delete | X:Clear | Esc:Back | ?:Help",
+            ViewMode::ReadingLists => "Enter:View | Space:Done | J/K:Move | x:Remove | ]:Next list",
+        }
+        .to_string(),
+        Token::Message => app
+            .notifications
+            .latest()
+            .map(|n| n.text())
+            .unwrap_or_default(),
+        Token::Matches => match_summary(app).unwrap_or_default(),
+        Token::Chord => chord_hint(app)
+            .map(|(prefix, options)| format!("{} … {}", prefix, options))
+            .unwrap_or_default(),
+        Token::Bookmarks => app.bookmarks.stories.len().to_string(),
+        Token::Unread => app
+            .stories
+            .iter()
+            .filter(|story| !app.history.is_read(story.id))
+            .count()
+            .to_string(),
+        // Share of cache lookups served from the story, comment and article caches
+        Token::CacheHitRate => {
+            let stats = app.api_service.cache_stats();
+            let (hits, lookups) = [stats.stories, stats.comments, stats.articles]
+                .iter()
+                .fold((0, 0), |(hits, lookups), cache| {
+                    (hits + cache.hits, lookups + cache.hits + cache.misses)
+                });
+            match lookups {
+                0 => String::new(),
+                lookups => format!("{:.0}%", hits as f64 * 100.0 / lookups as f64),
+            }
+        }
+        Token::RateLimit => match app.api_service.throttled_requests() {
+            0 => String::new(),
+            waiting => waiting.to_string(),
+        },
+        Token::Script(name) => app.scripts.status_tokens().remove(name).unwrap_or_default(),
+    }
+}
 
-    // {order} - Sort order
-    let order_str = match app.sort_order {
-        crate::internal::ui::sort::SortOrder::Ascending => "↑",
-        crate::internal::ui::sort::SortOrder::Descending => "↓",
-    };
-    result = result.replace("{order}", order_str);
+/// Match count of the comment search, or of the story list filter, while one is active.
+fn match_summary(app: &App) -> Option<String> {
+    match (app.view_mode, app.comment_search.is_active()) {
+        (ViewMode::StoryDetail, true) => Some(format!(
+            "Search \"{}\": {}",
+            app.comment_search.query.query,
+            app.comment_search.status()
+        )),
+        _ if !app.search_query.is_empty() => {
+            let count = app.filtered_story_indices().len();
+            Some(format!(
+                "Filter \"{}\": {} {}",
+                app.search_query.query,
+                count,
+                match count {
+                    1 => "match",
+                    _ => "matches",
+                }
+            ))
+        }
+        _ => None,
+    }
+}
 
-    // {search} - Search query
-    let search_str = app.search_query.query.as_str();
-    result = result.replace("{search}", search_str);
+/// The chord prefix and the keys that can follow it, such as
+/// `("y", "c:CopyCommentsLink  y:CopyLink")`, while waiting for the second key.
+fn chord_hint(app: &App) -> Option<(String, String)> {
+    use crate::internal::ui::keybindings::key_label;
+    let (first, _) = app.pending_chord.as_ref()?;
+    let options = app
+        .keybindings
+        .chords_after(first, app.key_context())
+        .iter()
+        .map(|(second, action)| format!("{}:{:?}", key_label(second), action))
+        .collect::<Vec<_>>()
+        .join("  ");
+    Some((key_label(first), options))
+}
 
-    // {spinner} - Loading spinner
-    let spinner_str = match (app.loading, app.comments_loading, app.article_loading) {
-        (true, _, _) | (_, true, _) | (_, _, true) => app.get_spinner_char().to_string(),
-        _ => String::new(),
+/// Transient messages on their own line above the status bar.
+fn render_message_line(app: &App, f: &mut Frame, area: Rect) {
+    let template = &app.message_template;
+    let width = area
+        .width
+        .saturating_sub(app.config.ui.padding.horizontal * 2) as usize;
+    let text = template.render(width, |token| status_token(app, token));
+    // Tokens without a value leave their spaces behind
+    let text = match template.is_single() {
+        true => text.split_whitespace().collect::<Vec<_>>().join(" "),
+        false => text,
     };
-    result = result.replace("{spinner}", &spinner_str);
-
-    // {loading_text} - Loading description
-    let loading_text = app.loading_description().unwrap_or_default();
-    result = result.replace("{loading_text}", &loading_text);
-
-    // {theme} - Current theme name
-    let theme_name = app
-        .available_themes
-        .get(app.current_theme_index)
-        .map(|(filename, _)| {
-            std::path::Path::new(filename)
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or("Unknown")
-        })
-        .unwrap_or("Default");
-    result = result.replace("{theme}", theme_name);
-
-    // {shortcuts} - Context-sensitive shortcuts (fallback to default behavior)
-    let shortcuts = match app.view_mode {
-        ViewMode::List => "j/k:Nav | Enter:View | b:Bookmark | ?:Help | L:Log | q:Quit",
-        ViewMode::StoryDetail => "Esc:Back | o:Browser | Tab:Article | ?:Help",
-        ViewMode::Article => "Esc:Back | j/k:Scroll | Tab:Comments | ?:Help",
-        ViewMode::Bookmarks => "Enter:View | Esc:Back | ?:Help",
-        ViewMode::History => "Enter:View | X:Clear | Esc:Back | ?:Help",
+    use crate::internal::notification::NotificationType;
+    let color = match app.notifications.latest().map(|n| &n.notification_type) {
+        Some(NotificationType::Error) => Color::Red,
+        Some(NotificationType::Warning) => Color::Yellow,
+        _ => app.theme.foreground,
     };
-    result = result.replace("{shortcuts}", shortcuts);
-
-    result
+    let p = Paragraph::new(text)
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .block(Block::default().padding(Padding::horizontal(app.config.ui.padding.horizontal)))
+        .style(Style::default().fg(color).bg(app.theme.background));
+    f.render_widget(p, area);
 }
 
 fn get_verbose_status(app: &App) -> String {
@@ -998,35 +2933,238 @@ fn get_verbose_status(app: &App) -> String {
     }
 }
 
+/// Body of a job posting in place of the comments, which jobs don't have.
+fn render_job_description(app: &mut App, f: &mut Frame, area: Rect) {
+    let padding = &app.config.ui.padding;
+    let width = area.width.saturating_sub(2 + 2 * padding.horizontal) as usize;
+    let height = area.height.saturating_sub(2 + 2 * padding.vertical) as usize;
+    let text = app
+        .selected_story
+        .as_ref()
+        .and_then(|story| story.text.as_deref())
+        .map(|html| crate::utils::html::extract_wrapped_text(html, width))
+        .unwrap_or_default();
+    let lines: Vec<Line> = match text.trim().is_empty() {
+        true => vec![Line::styled(
+            "No description; press Tab to read the linked page.",
+            Style::default().fg(app.theme.comment_time),
+        )],
+        false => text
+            .lines()
+            .map(|line| Line::from(line.to_string()))
+            .collect(),
+    };
+    app.comments_scroll.update(lines.len(), height);
+
+    let p = Paragraph::new(lines)
+        .style(
+            Style::default()
+                .fg(app.theme.foreground)
+                .bg(app.theme.background),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .padding(Padding::new(
+                    app.config.ui.padding.horizontal,
+                    app.config.ui.padding.horizontal,
+                    app.config.ui.padding.vertical,
+                    app.config.ui.padding.vertical,
+                ))
+                .border_style(pane_border_style(app, Pane::Detail))
+                .title("Description - Tab: Article")
+                .title_style(Style::default().fg(app.theme.foreground)),
+        )
+        .scroll((app.comments_scroll.offset_u16(), 0));
+    f.render_widget(p, area);
+    super::scroll::render_scrollbar(
+        f,
+        area,
+        &app.comments_scroll,
+        Style::default().fg(app.theme.border),
+    );
+}
+
+/// Poll options with their vote counts and a bar relative to the leading option.
+fn render_poll(app: &App, f: &mut Frame, area: Rect) {
+    let (lines, title) = match &app.poll {
+        Some(poll) => {
+            let max = poll
+                .options
+                .iter()
+                .filter_map(|opt| opt.score)
+                .max()
+                .unwrap_or(0);
+            let votes_width = max.to_string().len();
+            let bar_width = 12usize;
+            let lines: Vec<Line> = poll
+                .options
+                .iter()
+                .map(|opt| {
+                    let votes = opt.score.unwrap_or(0);
+                    let filled = match max {
+                        0 => 0,
+                        _ => (votes as usize * bar_width).div_ceil(max as usize),
+                    };
+                    let text = crate::utils::html::extract_text_from_html(
+                        opt.text.as_deref().unwrap_or(""),
+                    );
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{}{} ", "█".repeat(filled), " ".repeat(bar_width - filled)),
+                            Style::default().fg(app.theme.score),
+                        ),
+                        Span::styled(
+                            format!("{:>width$} ", votes, width = votes_width),
+                            Style::default().fg(app.theme.comment_time),
+                        ),
+                        Span::raw(text),
+                    ])
+                })
+                .collect();
+            let title = format!(
+                "Poll · {} options · {} votes",
+                poll.options.len(),
+                poll.total_votes()
+            );
+            (lines, title)
+        }
+        None => (
+            vec![Line::from(format!(
+                "{} Loading options...",
+                app.get_spinner_char()
+            ))],
+            "Poll".to_string(),
+        ),
+    };
+
+    let p = Paragraph::new(lines)
+        .style(
+            Style::default()
+                .fg(app.theme.foreground)
+                .bg(app.theme.background),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .padding(Padding::horizontal(app.config.ui.padding.horizontal))
+                .border_style(pane_border_style(app, Pane::Detail))
+                .title(title)
+                .title_style(Style::default().fg(app.theme.foreground)),
+        );
+    f.render_widget(p, area);
+}
+
+/// Text gauge like "▰▰▰▱▱▱▱▱▱▱" for `done` out of `total`.
+fn progress_gauge(done: usize, total: usize, width: usize) -> String {
+    let filled = match total {
+        0 => width,
+        _ => (done * width / total).min(width),
+    };
+    format!("{}{}", "▰".repeat(filled), "▱".repeat(width - filled))
+}
+
+#[tracing::instrument(name = "render.status_bar", level = "debug", skip_all)]
 fn render_status_bar(app: &App, f: &mut Frame, area: Rect) {
+    // Transient parts move to the message line when there is one
+    let message_line = app.config.ui.message_line_height > 0;
+    // Banner while the circuit breaker holds back API requests
+    let banner = match app.api_service.api_health() {
+        crate::api::circuit_breaker::ApiHealth::Healthy => None,
+        crate::api::circuit_breaker::ApiHealth::Degraded { retry_in } => Some(format!(
+            " ⚠ API degraded · retrying in {}s ",
+            retry_in.as_secs() + 1
+        )),
+        crate::api::circuit_breaker::ApiHealth::Probing => {
+            Some(" ⚠ API degraded · checking… ".to_string())
+        }
+    };
+    let mut spans = Vec::new();
+    // Waiting for the second key of a chord: list what it can complete to
+    if let (false, Some((prefix, options))) = (message_line, chord_hint(app)) {
+        spans.push(Span::styled(
+            format!(" {} … ", prefix),
+            Style::default()
+                .bg(app.theme.selection_bg)
+                .fg(app.theme.selection_fg)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw(format!(" {} ", options)));
+    }
+    // Story filters apply to the lists; show they are on so a short list is explained
+    if let (ViewMode::List, Some(chip)) = (
+        app.view_mode,
+        crate::internal::story_filter::chip(&app.config.filters),
+    ) {
+        spans.push(Span::styled(
+            format!(" ⚲ {} ", chip),
+            Style::default()
+                .bg(app.theme.selection_fg)
+                .fg(app.theme.selection_bg)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw(" "));
+    }
+    // Requests held back by the rate limit
+    if let waiting @ 1.. = app.api_service.throttled_requests() {
+        spans.push(Span::styled(
+            format!(" ⏳ throttled · {} waiting ", waiting),
+            Style::default()
+                .bg(app.theme.selection_fg)
+                .fg(app.theme.selection_bg),
+        ));
+        spans.push(Span::raw(" "));
+    }
+    if let Some(banner) = banner {
+        spans.push(Span::styled(
+            banner,
+            Style::default()
+                .bg(Color::Red)
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw(" "));
+    }
     // Check if custom format is configured
     let status = match (
         app.config.accessibility.verbose_status,
-        app.config.ui.status_bar_format.is_empty(),
+        app.status_template.as_ref(),
         app.loading || app.comments_loading || app.article_loading,
         &app.input_mode,
         &app.view_mode,
     ) {
         (true, _, _, _, _) => get_verbose_status(app),
-        (false, false, _, _, _) => {
-            // Use custom format with token parsing
-            parse_status_bar_format(app, &app.config.ui.status_bar_format)
+        (false, Some(template), _, _, _) => {
+            // Use the custom format, laid out on what the chips and banners leave
+            let chips: usize = spans.iter().map(|span| span.width()).sum();
+            let width = area
+                .width
+                .saturating_sub(app.config.ui.padding.horizontal * 2)
+                as usize;
+            template.render(width.saturating_sub(chips), |token| {
+                status_token(app, token)
+            })
         }
-        (false, true, true, _, &ViewMode::List) if !app.story_ids.is_empty() => {
+        (false, None, true, _, &ViewMode::List) if !app.story_ids.is_empty() => {
             // Show animated spinner with loading description and story counts
             let spinner = app.get_spinner_char();
             let desc = app
                 .loading_description()
                 .unwrap_or_else(|| "Loading...".to_string());
+            let gauge = match app.story_fetch_progress {
+                Some((done, total)) => format!(" | {} {}/{}", progress_gauge(done, total, 10), done, total),
+                None => String::new(),
+            };
             format!(
-                "{} {} | {}/{}",
+                "{} {} | {}/{}{}",
                 spinner,
                 desc,
                 app.loaded_count,
-                app.story_ids.len()
+                app.story_ids.len(),
+                gauge
             )
         }
-        (false, true, true, _, _) => {
+        (false, None, true, _, _) => {
             // Show animated spinner with loading description
             let spinner = app.get_spinner_char();
             let desc = app
@@ -1034,11 +3172,11 @@ fn render_status_bar(app: &App, f: &mut Frame, area: Rect) {
                 .unwrap_or_else(|| "Loading...".to_string());
             format!("{} {}", spinner, desc)
         }
-        (false, true, false, &InputMode::Search, _) => {
+        (false, None, false, &InputMode::Search, _) => {
             // Enhanced status bar for search mode with shortcuts
             "Search: Type | ↑↓: History | Ctrl+M/F2: Mode | Ctrl+R/F3: Regex | Enter: OK | Esc: Cancel".to_string()
         }
-        (false, true, false, _, &ViewMode::List) => {
+        (false, None, false, _, &ViewMode::List) => {
             let loaded_info = match app.story_ids.len() {
                 0 => String::new(),
                 len => format!(" | {}/{}", app.loaded_count, len),
@@ -1057,15 +3195,20 @@ fn render_status_bar(app: &App, f: &mut Frame, area: Rect) {
                 loaded_info, filter_hint, clear_hint
             )
         }
-        (false, true, false, _, &ViewMode::StoryDetail) => {
-            "Esc/q: Back | o: Browser | b: Bookmark | n: More Comments | Tab: Article | t: Theme | ?: Help"
-                .to_string()
-        }
-        (false, true, false, _, &ViewMode::Article) => {
+        (false, None, false, _, &ViewMode::StoryDetail) => match app.comment_search.is_active() && !message_line {
+            true => format!(
+                "Search \"{}\": {} | n/N: Next/Prev | Q: Clear | /: Edit | Esc/q: Back",
+                app.comment_search.query.query,
+                app.comment_search.status()
+            ),
+            false => "Esc/q: Back | o: Browser | b: Bookmark | n: More Comments | /: Search | Tab: Article | t: Theme | ?: Help"
+                .to_string(),
+        },
+        (false, None, false, _, &ViewMode::Article) => {
             "Esc/q: Back | o: Browser | Tab: Comments | j/k: Scroll | t: Theme | ?: Help"
                 .to_string()
         }
-        (false, true, false, _, &ViewMode::Bookmarks) => {
+        (false, None, false, _, &ViewMode::Bookmarks) => {
             // Show a compact status for bookmarks view, including count
             let count = app.bookmarks.stories.len();
             let bookmark_info = match count {
@@ -1073,24 +3216,36 @@ fn render_status_bar(app: &App, f: &mut Frame, area: Rect) {
                 n => format!("Bookmarks: {}", n),
             };
             format!(
-                "Esc/q: Back | Enter: View | t: Theme | ?: Help | {}",
+                "Esc/q: Back | Enter: View | #: Tags | e: Note | [/]: Filter by tag | ?: Help | {}",
                 bookmark_info
             )
         }
-        (false, true, false, _, &ViewMode::History) => {
+        (false, None, false, _, &ViewMode::History) => {
             let count = app.history.stories.len();
             let history_info = match count {
                 0 => "No history".to_string(),
                 n => format!("History: {}", n),
             };
             format!(
-                "Esc/q: Back | Enter: View | X: Clear History | t: Theme | ?: Help | {}",
+                "Esc/q: Back | Enter: View | /: Search | d: Delete | X: Clear History | t: Theme | ?: Help | {}",
                 history_info
             )
         }
+        (false, None, false, _, &ViewMode::ReadingLists) => {
+            "Esc/q: Back | Enter: View | Space: Done | J/K: Move | x: Remove | [/]: Switch list | ?: Help"
+                .to_string()
+        }
     };
 
-    let p = Paragraph::new(status)
+    spans.push(Span::raw(status));
+    // Script widgets go at the end unless a custom format places them
+    if app.status_template.is_none() {
+        for value in app.scripts.status_tokens().into_values() {
+            spans.push(Span::raw(format!(" | {}", value)));
+        }
+    }
+
+    let p = Paragraph::new(Line::from(spans))
         .block(
             Block::default()
                 .padding(Padding::new(
@@ -1105,28 +3260,34 @@ fn render_status_bar(app: &App, f: &mut Frame, area: Rect) {
     f.render_widget(p, area);
 }
 
+/// Toasts stacked in the middle of the screen, newest at the bottom.
 fn render_notification(app: &App, f: &mut Frame) {
-    if let Some(notification) = &app.notification {
-        let area = f.area();
-
-        // Create centered popup
-        let popup_width = (notification.message.len() as u16 + 4).min(area.width - 4);
-        let popup_height = 3;
+    use crate::internal::notification::NotificationType;
 
+    let area = f.area();
+    let toasts = &app.notifications.toasts;
+    let popup_height = 3;
+    let stack_height = popup_height * toasts.len() as u16;
+    let mut popup_y = (area.height.saturating_sub(stack_height)) / 2;
+
+    for notification in toasts {
+        if popup_y + popup_height > area.height {
+            break;
+        }
+        let text = notification.text();
+        let popup_width = (text.chars().count() as u16 + 4).min(area.width.saturating_sub(4));
         let popup_x = (area.width.saturating_sub(popup_width)) / 2;
-        let popup_y = (area.height.saturating_sub(popup_height)) / 2;
-
         let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+        popup_y += popup_height;
 
         // Color code based on notification type
-        use crate::internal::notification::NotificationType;
-        let (bg_color, title) = match notification.notification_type {
-            NotificationType::Info => (Color::Blue, "Info"),
-            NotificationType::Warning => (Color::Yellow, "Warning"),
-            NotificationType::Error => (Color::Red, "Error"),
+        let bg_color = match notification.notification_type {
+            NotificationType::Info => Color::Blue,
+            NotificationType::Warning => Color::Yellow,
+            NotificationType::Error => Color::Red,
         };
 
-        let popup = Paragraph::new(notification.message.as_str())
+        let popup = Paragraph::new(text)
             .style(
                 Style::default()
                     .bg(bg_color)
@@ -1137,7 +3298,7 @@ fn render_notification(app: &App, f: &mut Frame) {
                 Block::default()
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(app.theme.border))
-                    .title(title)
+                    .title(notification.notification_type.label())
                     .title_style(Style::default().fg(app.theme.foreground)),
             )
             .alignment(Alignment::Center);
@@ -1147,6 +3308,128 @@ fn render_notification(app: &App, f: &mut Frame) {
     }
 }
 
+/// The last notifications, newest first, with when they came and their severity.
+fn render_notification_center(app: &App, f: &mut Frame) {
+    use crate::internal::notification::NotificationType;
+
+    let Some(offset) = app.notification_center else {
+        return;
+    };
+    let history = &app.notifications.history;
+
+    let area = f.area();
+    let popup_width = 80.min(area.width.saturating_sub(4));
+    let popup_height = (history.len().max(1) as u16 + 4).min(area.height.saturating_sub(4));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    let rows = popup_height.saturating_sub(4).max(1) as usize;
+    let width = popup_width.saturating_sub(2) as usize;
+    let mut lines: Vec<Line> = history
+        .iter()
+        .skip(offset)
+        .take(rows)
+        .map(|notification| {
+            let severity_style = match notification.notification_type {
+                NotificationType::Info => Style::default().fg(app.theme.comment_time),
+                NotificationType::Warning => Style::default().fg(Color::Yellow),
+                NotificationType::Error => Style::default().fg(Color::Red),
+            };
+            let time = app.format_time(&notification.time);
+            let label = format!("{:<7}", notification.notification_type.label());
+            let text_width =
+                width.saturating_sub(textwrap::core::display_width(&time) + label.len() + 2);
+            let text = super::row_template::truncate(&notification.text(), text_width);
+            Line::from(vec![
+                Span::styled(
+                    format!("{} ", time),
+                    Style::default().fg(app.theme.comment_time),
+                ),
+                Span::styled(format!("{} ", label), severity_style),
+                Span::styled(text, Style::default().fg(app.theme.foreground)),
+            ])
+        })
+        .collect();
+    if history.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No notifications yet",
+            Style::default().fg(app.theme.comment_time),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "j/k: Scroll • d: Dismiss toasts • c: Clear history • Esc: Close",
+        Style::default().fg(app.theme.comment_time),
+    )));
+
+    let block = Block::default()
+        .title(format!(" Notifications ({}) ", history.len()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.selection_bg))
+        .style(Style::default().bg(app.theme.background));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
+
+/// Panel above the status bar describing the last failed operation.
+fn render_error_panel(app: &App, f: &mut Frame, status_bar: Rect) {
+    let Some(error) = &app.last_error else {
+        return;
+    };
+    use crate::internal::error::ErrorKind;
+
+    let area = f.area();
+    let width = area.width.saturating_sub(4).min(80);
+    let inner_width = width.saturating_sub(4).max(1) as usize;
+    let message: Vec<Line> = textwrap::wrap(&error.message, inner_width)
+        .into_iter()
+        .take(3)
+        .map(|line| Line::from(line.into_owned()))
+        .collect();
+    let height = (message.len() as u16 + 3).min(status_bar.y);
+    let panel = Rect::new(
+        (area.width.saturating_sub(width)) / 2,
+        status_bar.y.saturating_sub(height),
+        width,
+        height,
+    );
+
+    let kind = match error.kind {
+        ErrorKind::Network => "Network error",
+        ErrorKind::Parse => "Unexpected response",
+        ErrorKind::Cancelled => "Cancelled",
+        ErrorKind::Other => "Error",
+    };
+    let hint = match error.retryable {
+        true => " r: retry · Esc: dismiss ",
+        false => " Esc: dismiss ",
+    };
+    let mut lines = vec![Line::from(Span::styled(
+        format!("{} failed", error.operation),
+        Style::default().add_modifier(Modifier::BOLD),
+    ))];
+    lines.extend(message);
+
+    let popup = Paragraph::new(lines)
+        .style(
+            Style::default()
+                .fg(app.theme.foreground)
+                .bg(app.theme.background),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red))
+                .title(format!(" {} ", kind))
+                .title_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+                .title_bottom(Line::from(hint).right_aligned()),
+        );
+    f.render_widget(Clear, panel);
+    f.render_widget(popup, panel);
+}
+
 fn render_search_overlay(app: &App, f: &mut Frame) {
     let area = f.area();
 
@@ -1160,11 +3443,13 @@ fn render_search_overlay(app: &App, f: &mut Frame) {
     let search_area = Rect::new(search_x, search_y, search_width, search_height);
 
     // Build title with mode and type indicators
-    let title = format!(
-        " Search: {} | {} ",
-        app.search_query.mode.as_str(),
-        app.search_query.search_type.as_str()
-    );
+    let (query, mode) = match app.view_mode {
+        ViewMode::StoryDetail if !app.list_has_focus() => {
+            (&app.comment_search.query, "Loaded comments")
+        }
+        _ => (&app.search_query, app.search_query.mode.as_str()),
+    };
+    let title = format!(" Search: {} | {} ", mode, query.search_type.as_str());
 
     // Display the temp search input with cursor
     let mut display_lines = vec![];
@@ -1177,7 +3462,7 @@ fn render_search_overlay(app: &App, f: &mut Frame) {
     )));
 
     // Show regex error if present
-    if let Some(ref error) = app.search_query.regex_error {
+    if let Some(ref error) = query.regex_error {
         display_lines.push(Line::from(Span::styled(
             error.clone(),
             Style::default().fg(app.theme.score), // Use score color (typically red/orange)
@@ -1203,12 +3488,16 @@ fn render_search_overlay(app: &App, f: &mut Frame) {
     f.render_widget(search_box, search_area);
 }
 
-fn render_help_overlay(app: &App, f: &mut Frame) {
+fn render_help_overlay(app: &mut App, f: &mut Frame) {
+    use crate::internal::ui::help::{Row, paginate, sections};
+    use crate::internal::ui::row_template::truncate;
+    use textwrap::core::display_width;
+
     let area = f.area();
 
-    // Create centered popup
-    let popup_width = 56.min(area.width - 4);
-    let popup_height = 30.min(area.height - 4);
+    // Create centered popup; as tall as the screen allows, so fewer pages are needed
+    let popup_width = 64.min(area.width.saturating_sub(4));
+    let popup_height = 40.min(area.height.saturating_sub(4));
 
     let popup_x = (area.width.saturating_sub(popup_width)) / 2;
     let popup_y = (area.height.saturating_sub(popup_height)) / 2;
@@ -1237,241 +3526,97 @@ fn render_help_overlay(app: &App, f: &mut Frame) {
         .style(Style::default().bg(app.theme.background));
 
     let inner_area = block.inner(popup_area);
-    f.render_widget(block, popup_area);
 
-    // Shortcuts content based on page
-    let shortcuts = match app.help_page {
-        1 => vec![
-            Line::from(vec![Span::styled(
-                "General Shortcuts (Tab for Theme Editor)",
-                Style::default()
-                    .add_modifier(Modifier::BOLD)
-                    .fg(app.theme.selection_bg),
-            )]),
-            Line::from(""),
-            Line::from(vec![Span::styled(
-                "Global",
-                Style::default()
-                    .add_modifier(Modifier::BOLD)
-                    .fg(app.theme.selection_bg),
-            )]),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("?", Style::default().fg(app.theme.comment_time)),
-                Span::raw("        Show this help"),
-            ]),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("q / Esc", Style::default().fg(app.theme.comment_time)),
-                Span::raw("  Quit / Back / Close overlay"),
-            ]),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("t", Style::default().fg(app.theme.comment_time)),
-                Span::raw("        Toggle theme (Light/Dark)"),
-            ]),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("g", Style::default().fg(app.theme.comment_time)),
-                Span::raw("        Toggle auto-switch theme"),
-            ]),
-            Line::from(""),
-            Line::from(vec![Span::styled(
-                "Navigation",
-                Style::default()
-                    .add_modifier(Modifier::BOLD)
-                    .fg(app.theme.selection_bg),
-            )]),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("j / k", Style::default().fg(app.theme.comment_time)),
-                Span::raw("    Move selection down / up"),
-            ]),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("Enter", Style::default().fg(app.theme.comment_time)),
-                Span::raw("    View story details"),
-            ]),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("Tab", Style::default().fg(app.theme.comment_time)),
-                Span::raw("      Toggle Article/Comments view"),
-            ]),
-            Line::from(""),
-            Line::from(vec![Span::styled(
-                "Story List",
-                Style::default()
-                    .add_modifier(Modifier::BOLD)
-                    .fg(app.theme.selection_bg),
-            )]),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("1-6", Style::default().fg(app.theme.comment_time)),
-                Span::raw("      Switch categories (Top, New, Best...)"),
-            ]),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("S/C/T", Style::default().fg(app.theme.comment_time)),
-                Span::raw("    Sort by Score/Comments/Time"),
-            ]),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("O", Style::default().fg(app.theme.comment_time)),
-                Span::raw("        Toggle sort order (asc/desc)"),
-            ]),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("/", Style::default().fg(app.theme.comment_time)),
-                Span::raw("        Search stories"),
-            ]),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("m", Style::default().fg(app.theme.comment_time)),
-                Span::raw("        Load more stories"),
-            ]),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("A", Style::default().fg(app.theme.comment_time)),
-                Span::raw("        Load ALL stories"),
-            ]),
-            Line::from(""),
-            Line::from(vec![Span::styled(
-                "Bookmarks & History",
-                Style::default()
-                    .add_modifier(Modifier::BOLD)
-                    .fg(app.theme.selection_bg),
-            )]),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("b", Style::default().fg(app.theme.comment_time)),
-                Span::raw("        Toggle bookmark • "),
-                Span::styled("B", Style::default().fg(app.theme.comment_time)),
-                Span::raw(" View bookmarks"),
-            ]),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("H", Style::default().fg(app.theme.comment_time)),
-                Span::raw("        View history • "),
-                Span::styled("X", Style::default().fg(app.theme.comment_time)),
-                Span::raw(" Clear history"),
-            ]),
-            Line::from(""),
-            Line::from(vec![Span::styled(
-                "Article / Comments",
-                Style::default()
-                    .add_modifier(Modifier::BOLD)
-                    .fg(app.theme.selection_bg),
-            )]),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("o", Style::default().fg(app.theme.comment_time)),
-                Span::raw("        Open in browser"),
-            ]),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("n", Style::default().fg(app.theme.comment_time)),
-                Span::raw("        Load more comments"),
-            ]),
-        ],
-        _ => {
-            // Page 2: Theme Editor
-            vec![
-                Line::from(vec![Span::styled(
-                    "Theme Editor Shortcuts (Tab for General)",
-                    Style::default()
-                        .add_modifier(Modifier::BOLD)
-                        .fg(app.theme.selection_bg),
-                )]),
-                Line::from(""),
-                Line::from(vec![Span::styled(
-                    "Toggle Editor",
-                    Style::default()
-                        .add_modifier(Modifier::BOLD)
-                        .fg(app.theme.selection_bg),
-                )]),
-                Line::from(vec![
-                    Span::raw("  "),
-                    Span::styled("E", Style::default().fg(app.theme.comment_time)),
-                    Span::raw("        Open/close theme editor"),
-                ]),
-                Line::from(""),
-                Line::from(vec![Span::styled(
-                    "When Editor is Active",
-                    Style::default()
-                        .add_modifier(Modifier::BOLD)
-                        .fg(app.theme.selection_bg),
-                )]),
-                Line::from(vec![
-                    Span::raw("  "),
-                    Span::styled("↑ / ↓", Style::default().fg(app.theme.comment_time)),
-                    Span::raw("      Navigate theme properties"),
-                ]),
-                Line::from(vec![Span::raw(
-                    "            (Background, Foreground, Selection, etc.)",
-                )]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::raw("  "),
-                    Span::styled("← / →", Style::default().fg(app.theme.comment_time)),
-                    Span::raw("      Switch RGB channels (Red/Green/Blue)"),
-                ]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::raw("  "),
-                    Span::styled("+ / =", Style::default().fg(app.theme.comment_time)),
-                    Span::raw("      Increase color value (+5)"),
-                ]),
-                Line::from(vec![
-                    Span::raw("  "),
-                    Span::styled("- / _", Style::default().fg(app.theme.comment_time)),
-                    Span::raw("      Decrease color value (-5)"),
-                ]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::raw("  "),
-                    Span::styled("s", Style::default().fg(app.theme.comment_time)),
-                    Span::raw("        Save theme to JSON"),
-                ]),
-                Line::from(vec![Span::raw(
-                    "            (Exports to ./themes/custom_custom.json)",
-                )]),
-                Line::from(""),
+    // Pages of the bindings in effect, so remapped keys show as the user set them
+    let sections = sections(&app.keybindings, &app.config.plugins);
+    let pages = paginate(&sections, inner_area.height as usize);
+    app.help_pages = pages.len();
+    app.help_page = app.help_page.min(pages.len() - 1);
+    let page = &pages[app.help_page];
+
+    let key_width = page
+        .iter()
+        .filter_map(|row| match row {
+            Row::Entry(entry) => Some(display_width(&entry.keys)),
+            _ => None,
+        })
+        .max()
+        .unwrap_or(0)
+        .min(16);
+    let width = inner_area.width as usize;
+    let heading = Style::default()
+        .add_modifier(Modifier::BOLD)
+        .fg(app.theme.selection_bg);
+    let lines: Vec<Line> = page
+        .iter()
+        .map(|row| match row {
+            Row::Heading(title) => Line::from(Span::styled(title.clone(), heading)),
+            Row::Blank => Line::from(""),
+            Row::Entry(entry) => {
+                let keys = truncate(&entry.keys, key_width);
+                let pad = key_width.saturating_sub(display_width(&keys));
+                let key_style = match entry.customized {
+                    true => Style::default()
+                        .fg(app.theme.comment_time)
+                        .add_modifier(Modifier::BOLD),
+                    false => Style::default().fg(app.theme.comment_time),
+                };
+                let marker = match entry.customized {
+                    true => "*",
+                    false => " ",
+                };
+                let description = truncate(&entry.description, width.saturating_sub(key_width + 5));
                 Line::from(vec![
                     Span::raw("  "),
-                    Span::styled("Esc", Style::default().fg(app.theme.comment_time)),
-                    Span::raw("      Close editor (discard changes)"),
-                ]),
-                Line::from(""),
-                Line::from(""),
-                Line::from(vec![Span::styled(
-                    "ℹ Note",
-                    Style::default()
-                        .add_modifier(Modifier::BOLD)
-                        .fg(app.theme.comment_time),
-                )]),
-                Line::from(vec![Span::raw(
-                    "  Theme changes apply in real-time as you edit.",
-                )]),
-                Line::from(vec![Span::raw("  All colors use RGB values (0-255).")]),
-            ]
-        }
+                    Span::styled(keys, key_style),
+                    Span::styled(marker, key_style),
+                    Span::raw(" ".repeat(pad + 2)),
+                    Span::raw(description),
+                ])
+            }
+        })
+        .collect();
+
+    let customized = sections
+        .iter()
+        .flat_map(|section| &section.entries)
+        .any(|entry| entry.customized);
+    let hint = match (pages.len(), customized) {
+        (1, false) => String::new(),
+        (1, true) => " * your keys ".to_string(),
+        (n, false) => format!(" Page {}/{} · Tab: next ", app.help_page + 1, n),
+        (n, true) => format!(
+            " Page {}/{} · Tab: next · * your keys ",
+            app.help_page + 1,
+            n
+        ),
+    };
+    let block = match hint.is_empty() {
+        true => block,
+        false => block.title_bottom(
+            Line::from(Span::styled(
+                hint,
+                Style::default().fg(app.theme.comment_time),
+            ))
+            .right_aligned(),
+        ),
     };
+    f.render_widget(block, popup_area);
 
-    let p = Paragraph::new(shortcuts)
-        .style(Style::default().fg(app.theme.foreground))
-        .wrap(Wrap { trim: false }); // Don't trim to preserve indentation
+    let p = Paragraph::new(lines).style(Style::default().fg(app.theme.foreground));
 
     f.render_widget(p, inner_area);
 }
 
 fn render_theme_editor_overlay(app: &App, f: &mut Frame) {
-    use crate::internal::ui::theme_editor::{ColorChannel, ThemeProperty};
+    use crate::internal::ui::theme_editor::{ColorChannel, ColorMode, EditorState, ThemeProperty};
 
     let area = f.area();
+    let warnings = app.theme_editor.contrast_warnings();
 
-    // Create centered popup
+    // Create centered popup, with a row for each contrast warning
     let popup_width = 60.min(area.width - 4);
-    let popup_height = 20.min(area.height - 4);
+    let popup_height = (22 + warnings.len() as u16).min(area.height - 4);
 
     let popup_x = (area.width.saturating_sub(popup_width)) / 2;
     let popup_y = (area.height.saturating_sub(popup_height)) / 2;
@@ -1493,9 +3638,25 @@ fn render_theme_editor_overlay(app: &App, f: &mut Frame) {
         )
         .style(Style::default().bg(app.theme.background));
 
-    let inner_area = block.inner(popup_area);
+    let [inner_area, warnings_area] = Layout::vertical([
+        Constraint::Min(0),
+        Constraint::Length(warnings.len() as u16),
+    ])
+    .areas(block.inner(popup_area));
     f.render_widget(block, popup_area);
 
+    // Pairs of colors too close to read, e.g. "Link on Background: 3.1:1"
+    let warning_lines: Vec<Line> = warnings
+        .iter()
+        .map(|warning| {
+            Line::from(Span::styled(
+                format!(" ⚠ {}", warning),
+                Style::default().fg(app.theme.score),
+            ))
+        })
+        .collect();
+    f.render_widget(Paragraph::new(warning_lines), warnings_area);
+
     // Split into Property List (Left) and Color Editor (Right)
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -1533,12 +3694,8 @@ fn render_theme_editor_overlay(app: &App, f: &mut Frame) {
     // 2. Color Editor
     if let Some(property) = app.theme_editor.get_current_property() {
         let color = property.get_color(&app.theme_editor.temp_theme);
-
-        // Extract RGB values
-        let (r, g, b) = match color {
-            ratatui::style::Color::Rgb(r, g, b) => (r, g, b),
-            _ => (128, 128, 128), // Fallback for non-RGB
-        };
+        let (r, g, b) = app.theme_editor.current_rgb();
+        let (h, s, l) = app.theme_editor.hsl;
 
         let editor_chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -1554,7 +3711,15 @@ fn render_theme_editor_overlay(app: &App, f: &mut Frame) {
 
         // Header
         f.render_widget(
-            Paragraph::new(format!("Editing: {}", property.name())).style(
+            Paragraph::new(format!(
+                "Editing: {} ({})",
+                property.name(),
+                match app.theme_editor.mode {
+                    ColorMode::Rgb => "RGB",
+                    ColorMode::Hsl => "HSL",
+                }
+            ))
+            .style(
                 Style::default()
                     .fg(app.theme.foreground)
                     .add_modifier(Modifier::BOLD),
@@ -1563,36 +3728,52 @@ fn render_theme_editor_overlay(app: &App, f: &mut Frame) {
         );
 
         // Helper to render channel slider
-        let render_channel =
-            |f: &mut Frame, area: Rect, name: &str, value: u8, channel: ColorChannel| {
-                let is_selected = app.theme_editor.selected_channel == channel;
-                let label_style = match is_selected {
-                    true => Style::default()
-                        .fg(app.theme.selection_bg)
-                        .add_modifier(Modifier::BOLD),
-                    false => Style::default().fg(app.theme.foreground),
-                };
+        let render_channel = |f: &mut Frame, area: Rect, channel: ColorChannel| {
+            let is_selected = app.theme_editor.selected_channel == channel;
+            let label_style = match is_selected {
+                true => Style::default()
+                    .fg(app.theme.selection_bg)
+                    .add_modifier(Modifier::BOLD),
+                false => Style::default().fg(app.theme.foreground),
+            };
 
-                let gauge = ratatui::widgets::Gauge::default()
-                    .block(Block::default().title(name).title_style(label_style))
-                    .gauge_style(
-                        Style::default()
-                            .fg(match channel {
-                                ColorChannel::Red => ratatui::style::Color::Red,
-                                ColorChannel::Green => ratatui::style::Color::Green,
-                                ColorChannel::Blue => ratatui::style::Color::Blue,
-                            })
-                            .bg(ratatui::style::Color::DarkGray),
-                    )
-                    .ratio(value as f64 / 255.0)
-                    .label(format!("{}", value));
-
-                f.render_widget(gauge, area);
+            // The hue slider is drawn in the pure hue, the others in the color
+            let pure_hue = crate::internal::ui::theme_editor::hsl_to_rgb((h, 1.0, 0.5));
+            let (name, ratio, label, fill) = match channel {
+                ColorChannel::Red => ("Red", r as f64 / 255.0, r.to_string(), Color::Red),
+                ColorChannel::Green => ("Green", g as f64 / 255.0, g.to_string(), Color::Green),
+                ColorChannel::Blue => ("Blue", b as f64 / 255.0, b.to_string(), Color::Blue),
+                ColorChannel::Hue => (
+                    "Hue",
+                    h / 360.0,
+                    format!("{:.0}°", h),
+                    Color::Rgb(pure_hue.0, pure_hue.1, pure_hue.2),
+                ),
+                ColorChannel::Saturation => (
+                    "Saturation",
+                    s,
+                    format!("{:.0}%", s * 100.0),
+                    Color::Rgb(r, g, b),
+                ),
+                ColorChannel::Lightness => (
+                    "Lightness",
+                    l,
+                    format!("{:.0}%", l * 100.0),
+                    Color::Rgb(r, g, b),
+                ),
             };
+            let gauge = ratatui::widgets::Gauge::default()
+                .block(Block::default().title(name).title_style(label_style))
+                .gauge_style(Style::default().fg(fill).bg(Color::DarkGray))
+                .ratio(ratio.clamp(0.0, 1.0))
+                .label(label);
+
+            f.render_widget(gauge, area);
+        };
 
-        render_channel(f, editor_chunks[1], "Red", r, ColorChannel::Red);
-        render_channel(f, editor_chunks[2], "Green", g, ColorChannel::Green);
-        render_channel(f, editor_chunks[3], "Blue", b, ColorChannel::Blue);
+        for (i, channel) in app.theme_editor.mode.channels().into_iter().enumerate() {
+            render_channel(f, editor_chunks[i + 1], channel);
+        }
 
         // Footer: Shortcuts (Left) + Hex/Preview (Right)
         let footer_chunks = Layout::default()
@@ -1624,6 +3805,24 @@ fn render_theme_editor_overlay(app: &App, f: &mut Frame) {
                 ),
                 Span::raw(" Adjust"),
             ]),
+            Line::from(vec![
+                Span::styled(
+                    "m  ",
+                    Style::default()
+                        .fg(app.theme.link)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" RGB/HSL"),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "#  ",
+                    Style::default()
+                        .fg(app.theme.link)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" Hex"),
+            ]),
             Line::from(vec![
                 Span::styled(
                     "s  ",
@@ -1639,8 +3838,11 @@ fn render_theme_editor_overlay(app: &App, f: &mut Frame) {
             footer_chunks[0],
         );
 
-        // Hex Code and Preview
-        let hex_code = format!("#{:02X}{:02X}{:02X}", r, g, b);
+        // Hex Code (or the one being typed) and Preview
+        let hex_code = match app.theme_editor.state {
+            EditorState::HexInput => format!("> {}_", app.theme_editor.hex_input),
+            _ => format!("#{:02X}{:02X}{:02X}", r, g, b),
+        };
         let preview_layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -1669,7 +3871,7 @@ fn render_theme_editor_overlay(app: &App, f: &mut Frame) {
     }
 
     // Render Naming Popup if in Naming state
-    if let crate::internal::ui::theme_editor::EditorState::Naming = app.theme_editor.state {
+    if let EditorState::Naming = app.theme_editor.state {
         let area = {
             let area = f.area();
             let vertical = Layout::default()
@@ -0,0 +1,167 @@
+//! Release notes shown once after an upgrade, and the version of the previous run.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Highlights of one release, newest first in `RELEASES`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Release {
+    pub version: &'static str,
+    pub features: &'static [&'static str],
+    /// Default keybindings that were added or changed
+    pub keybindings: &'static [&'static str],
+}
+
+pub const RELEASES: &[Release] = &[Release {
+    version: "0.9.5",
+    features: &[
+        "Article extraction keeps headings, lists, quotes and page metadata",
+        "Read stories are dimmed and can be hidden",
+        "Score, comment and rank changes since your last launch",
+        "Named reading lists, auto-bookmark rules and list export to CSV/TSV",
+        "Export a story with its comments to Markdown or HTML",
+        "Split-pane layout and low-bandwidth mode for SSH sessions",
+        "HN login with upvote and favorite",
+        "Colored domain chips (set domain_palette: ColorBlind for an accessible palette)",
+        "Optional disk cache, configurable TTLs and a cache statistics overlay",
+        "Request timeouts, proxy support and a circuit breaker when HN is down",
+    ],
+    keybindings: &[
+        "D toggles the sort order (was O)",
+        "O opens the HN discussion page, y copies the link",
+        "v upvote, f favorite",
+        "a add to reading list, V view reading lists",
+        "h hide read stories, M mark all read",
+        "W export, I import ids from the clipboard",
+        "Ctrl+w switch pane focus, Ctrl+k cache statistics",
+    ],
+}];
+
+/// Treated as the previous version of an install that has data but no
+/// `last_run.json`: the file only exists from the 0.9.5 release notes on.
+const UNTRACKED_VERSION: &str = "0.9.4";
+
+/// Files any earlier version leaves in the config directory.
+const EXISTING_DATA: &[&str] = &["bookmarks.json", "history.json"];
+
+/// `(major, minor, patch)`; missing or non-numeric parts count as 0.
+fn version_key(version: &str) -> (u32, u32, u32) {
+    let mut parts = version
+        .trim_start_matches('v')
+        .split(['.', '-', '+'])
+        .map(|part| part.parse().unwrap_or(0));
+    let mut next = || parts.next().unwrap_or(0);
+    (next(), next(), next())
+}
+
+/// Releases newer than `previous`, up to and including `current`.
+pub fn notes_since(previous: &str, current: &str) -> Vec<&'static Release> {
+    let (previous, current) = (version_key(previous), version_key(current));
+    RELEASES
+        .iter()
+        .filter(|release| {
+            let version = version_key(release.version);
+            version > previous && version <= current
+        })
+        .collect()
+}
+
+/// Version of the app that last ran, stored in `last_run.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LastRun {
+    pub version: Option<String>,
+    /// No `last_run.json` was found, but an earlier version's data was
+    #[serde(skip)]
+    pub untracked_upgrade: bool,
+    #[serde(skip)]
+    file_path: Option<PathBuf>,
+}
+
+impl LastRun {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load_or_create() -> Result<Self> {
+        let config_dir = dirs::config_dir()
+            .context("Could not find config directory")?
+            .join("tui-hn-app");
+        fs::create_dir_all(&config_dir).with_context(|| {
+            format!("Failed to create config directory {}", config_dir.display())
+        })?;
+
+        let file_path = config_dir.join("last_run.json");
+        let mut last_run: LastRun = match file_path.exists() {
+            true => {
+                let content =
+                    fs::read_to_string(&file_path).context("Failed to read last run info")?;
+                serde_json::from_str(&content).context("Failed to parse last run info")?
+            }
+            false => Self {
+                untracked_upgrade: EXISTING_DATA
+                    .iter()
+                    .any(|name| config_dir.join(name).exists()),
+                ..Self::new()
+            },
+        };
+        last_run.file_path = Some(file_path);
+        Ok(last_run)
+    }
+
+    /// Record `current` as the last run and return the notes to show for it.
+    /// A first install has nothing to compare against and shows none.
+    pub fn upgrade_to(&mut self, current: &str) -> Result<Vec<&'static Release>> {
+        let notes = match (self.version.as_deref(), self.untracked_upgrade) {
+            (Some(previous), _) if previous != current => notes_since(previous, current),
+            (None, true) => notes_since(UNTRACKED_VERSION, current),
+            _ => Vec::new(),
+        };
+        if self.version.as_deref() != Some(current) {
+            self.version = Some(current.to_string());
+            if let Some(path) = &self.file_path {
+                let content = serde_json::to_string_pretty(self)
+                    .context("Failed to serialize last run info")?;
+                fs::write(path, content).context("Failed to write last run info")?;
+            }
+        }
+        Ok(notes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn releases_are_newest_first() {
+        let versions: Vec<_> = RELEASES.iter().map(|r| version_key(r.version)).collect();
+        assert!(versions.windows(2).all(|pair| pair[0] > pair[1]));
+        assert!(version_key(RELEASES[0].version) <= version_key(env!("CARGO_PKG_VERSION")));
+    }
+
+    #[test]
+    fn notes_only_after_an_upgrade() {
+        assert_eq!(version_key("v0.10.2-beta"), (0, 10, 2));
+        assert_eq!(notes_since("0.9.3", "0.9.5").len(), 1);
+        assert!(notes_since("0.9.5", "0.9.5").is_empty());
+        // Downgrades and versions without notes show nothing
+        assert!(notes_since("1.0.0", "0.9.5").is_empty());
+        assert!(notes_since("0.9.3", "0.9.4").is_empty());
+
+        let mut last_run = LastRun::new();
+        assert!(last_run.upgrade_to("0.9.5").unwrap().is_empty());
+        last_run.version = Some("0.9.3".to_string());
+        assert_eq!(last_run.upgrade_to("0.9.5").unwrap()[0].version, "0.9.5");
+        assert!(last_run.upgrade_to("0.9.5").unwrap().is_empty());
+
+        // Data from before last_run.json existed counts as an upgrade
+        let mut last_run = LastRun {
+            untracked_upgrade: true,
+            ..LastRun::new()
+        };
+        assert_eq!(last_run.upgrade_to("0.9.5").unwrap()[0].version, "0.9.5");
+        assert!(last_run.upgrade_to("0.9.5").unwrap().is_empty());
+    }
+}
@@ -1,16 +1,36 @@
 mod api;
+mod cli;
 mod config;
 mod internal;
 mod tui;
 mod utils;
 
 use anyhow::Result;
+use clap::Parser;
+use futures::FutureExt;
 use internal::ui::app::App;
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let cli = cli::Cli::parse();
+    if cli.validate {
+        std::process::exit(match cli::validate(cli.config.as_deref()) {
+            true => 0,
+            false => 1,
+        });
+    }
+
     // Load configuration first to get logging settings
-    let config = config::AppConfig::load();
+    let mut config = cli.load_config()?;
+    let custom_list = cli.custom_list()?;
+
+    // Headless subcommands skip the TUI entirely
+    if let Some(command) = &cli.command {
+        return cli::run(command, &config, cli.offline).await;
+    }
 
     // Try to initialize the terminal first so we can decide where tracing should write.
     // When the TUI is running we must avoid writing logs to stderr/stdout (which would
@@ -18,11 +38,15 @@ async fn main() -> Result<()> {
     // we enable console logging so messages are visible to the user.
     match tui::init() {
         Ok(terminal) => {
+            // Logs and themes used to live under the working directory; bring them
+            // over before the log file is opened
+            let moved = config::migration::migrate_legacy_files(&config);
+
             // Running TUI: log to a daily rotating file.
-            // Use configured directory or default to "logs"
-            let log_dir = config.logging.log_directory.as_deref().unwrap_or("logs");
-            let file_appender = tracing_appender::rolling::daily(log_dir, "tui-hn-app.log");
-            let (non_blocking, _guard) = tracing_appender::non_blocking(file_appender);
+            // Use configured directory or default to the state directory
+            let log_dir = config.logging.directory();
+            let file_appender = tracing_appender::rolling::daily(&log_dir, "tui-hn-app.log");
+            let (non_blocking, log_guard) = tracing_appender::non_blocking(file_appender);
 
             // Build EnvFilter
             // If RUST_LOG is set, it takes precedence.
@@ -38,20 +62,98 @@ async fn main() -> Result<()> {
                 }
             };
 
-            tracing_subscriber::fmt()
-                .with_env_filter(env_filter)
-                .with_writer(non_blocking)
-                .with_ansi(false)
-                .compact()
+            // With --profile, spans also go to a Chrome trace written on exit
+            let trace_path = cli
+                .profile
+                .as_ref()
+                .map(|requested| internal::profiling::trace_path(requested.as_deref(), &log_dir));
+            let (trace_layer, trace_guard) = match &trace_path {
+                Some(path) => match internal::profiling::layer(path) {
+                    Ok((layer, guard)) => (Some(layer), Some(guard)),
+                    Err(e) => {
+                        tui::restore()?;
+                        return Err(e);
+                    }
+                },
+                None => (None, None),
+            };
+
+            tracing_subscriber::registry()
+                .with(
+                    tracing_subscriber::fmt::layer()
+                        .with_writer(non_blocking)
+                        .with_ansi(false)
+                        .compact()
+                        .with_filter(env_filter),
+                )
+                .with(trace_layer.with_filter(tracing_subscriber::EnvFilter::new(
+                    internal::profiling::FILTER,
+                )))
                 .init();
 
+            // Panics go to the log rather than over the TUI; on the main thread the
+            // terminal is restored first
+            std::panic::set_hook(Box::new(|info| {
+                let thread = std::thread::current();
+                let backtrace = std::backtrace::Backtrace::force_capture();
+                tracing::error!(
+                    thread = thread.name().unwrap_or("unnamed"),
+                    "{}\n{}",
+                    info,
+                    backtrace
+                );
+            }));
+            tui::install_panic_hook();
+
+            config.write_migration();
+            for change in &moved {
+                tracing::info!("Moved legacy files: {}", change);
+            }
+            if !moved.is_empty() {
+                let summary = format!("Files moved: {}", moved.join("; "));
+                config.migration_summary = Some(match config.migration_summary.take() {
+                    Some(previous) => format!("{}. {}", previous, summary),
+                    None => summary,
+                });
+            }
+
             // Start the application using the terminal we successfully initialized.
-            let mut app = App::new();
-            let res = app.run(terminal).await;
+            let mut app = App::with_config(config, cli.offline);
+            // A list asked for on the command line wins over the last session
+            if cli.list.is_some() || custom_list.is_some() {
+                app.session_prompt = None;
+            }
+            if let Some(list) = cli.list {
+                app.current_list_type = list;
+            }
+            if let Some(ids) = custom_list {
+                app.custom_list = ids;
+                app.current_list_type = api::StoryListType::Custom;
+            }
+            let res = std::panic::AssertUnwindSafe(app.run(terminal))
+                .catch_unwind()
+                .await;
 
             // Restore terminal state before exiting so the console is usable again.
             tui::restore()?;
 
+            let Ok(res) = res else {
+                app.save_state();
+                eprintln!(
+                    "tui-hn-app crashed. Bookmarks and history were saved; the log in {} has the details.",
+                    log_dir.display()
+                );
+                drop(trace_guard);
+                drop(log_guard);
+                std::process::exit(101);
+            };
+
+            // Dropping the guard finishes the trace file
+            drop(trace_guard);
+            if let Some(path) = trace_path {
+                println!("Profile written to {}", path.display());
+            }
+
             if let Err(err) = res {
                 // Print a short error to stderr as well so it's visible if someone runs the binary
                 // directly; detailed traces will be available in the log file.
@@ -1,6 +1,8 @@
 use std::io::{self, Stdout, stdout};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
@@ -8,14 +10,51 @@ use ratatui::{Terminal, backend::CrosstermBackend};
 
 pub type Tui = Terminal<CrosstermBackend<Stdout>>;
 
+/// Whether mouse capture was turned on, to turn it back on after `release`.
+static MOUSE_CAPTURE: AtomicBool = AtomicBool::new(false);
+
 pub fn init() -> io::Result<Tui> {
     execute!(stdout(), EnterAlternateScreen)?;
     enable_raw_mode()?;
     Terminal::new(CrosstermBackend::new(stdout()))
 }
 
+/// Report mouse clicks to the app (clickable sort header). Only enabled on request,
+/// since it takes over the terminal's own text selection.
+pub fn enable_mouse_capture() -> io::Result<()> {
+    MOUSE_CAPTURE.store(true, Ordering::Relaxed);
+    execute!(stdout(), EnableMouseCapture)
+}
+
+/// Run `f` with the terminal back in its normal state, e.g. while an editor or the
+/// shell has it, then take the terminal over again and redraw from scratch.
+pub fn release<T>(tui: &mut Tui, f: impl FnOnce() -> T) -> io::Result<T> {
+    restore()?;
+    let result = f();
+    execute!(stdout(), EnterAlternateScreen)?;
+    enable_raw_mode()?;
+    if MOUSE_CAPTURE.load(Ordering::Relaxed) {
+        execute!(stdout(), EnableMouseCapture)?;
+    }
+    tui.clear()?;
+    Ok(result)
+}
+
+/// Restore the terminal before a panic on the main thread is reported, so the shell
+/// is usable again. A panic in a background task is caught by the runtime and the
+/// TUI keeps running, so the terminal is left alone then.
+pub fn install_panic_hook() {
+    let report = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if std::thread::current().name() == Some("main") {
+            let _ = restore();
+        }
+        report(info);
+    }));
+}
+
 pub fn restore() -> io::Result<()> {
-    execute!(stdout(), LeaveAlternateScreen)?;
+    execute!(stdout(), DisableMouseCapture, LeaveAlternateScreen)?;
     disable_raw_mode()?;
     Ok(())
 }
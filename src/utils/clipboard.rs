@@ -0,0 +1,36 @@
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+
+/// Shared clipboard handle.
+///
+/// On X11/Wayland the copied text is served by the owning process, so the
+/// handle is kept alive for the lifetime of the app instead of being dropped
+/// right after `set_text`.
+static CLIPBOARD: Lazy<Mutex<Option<arboard::Clipboard>>> = Lazy::new(|| Mutex::new(None));
+
+fn with_clipboard<T>(f: impl FnOnce(&mut arboard::Clipboard) -> Result<T>) -> Result<T> {
+    let mut guard = CLIPBOARD
+        .lock()
+        .map_err(|_| anyhow::anyhow!("Clipboard lock poisoned"))?;
+
+    if guard.is_none() {
+        *guard = Some(arboard::Clipboard::new().context("Clipboard unavailable")?);
+    }
+
+    f(guard.as_mut().context("Clipboard unavailable")?)
+}
+
+/// Copy `text` to the system clipboard.
+pub fn copy_text(text: &str) -> Result<()> {
+    with_clipboard(|clipboard| {
+        clipboard
+            .set_text(text.to_string())
+            .context("Failed to copy to clipboard")
+    })
+}
+
+/// Read the text currently on the system clipboard.
+pub fn read_text() -> Result<String> {
+    with_clipboard(|clipboard| clipboard.get_text().context("Clipboard holds no text"))
+}
@@ -0,0 +1,284 @@
+//! WCAG contrast ratios, and high-contrast and color-blind-safe transforms that
+//! work on any theme.
+//!
+//! Colors are pushed towards white (on dark backgrounds) or black (on light ones)
+//! until they reach the WCAG AAA ratio of 7:1 for text, and 3:1 for borders and
+//! other non-text elements. Colors already past the threshold are left alone, so a
+//! theme keeps its hues wherever they are readable.
+//!
+//! For red-green color blindness (deuteranopia and protanopia) the hues of a theme
+//! are moved to the nearest color of the Okabe-Ito palette, whose colors stay
+//! apart for those viewers; grays are kept as they are.
+
+use ratatui::style::Color;
+
+use super::theme_loader::TuiTheme;
+
+/// WCAG AAA contrast for normal text
+pub const TEXT_CONTRAST: f64 = 7.0;
+/// WCAG AA contrast for normal text; the theme editor warns below it
+pub const AA_TEXT_CONTRAST: f64 = 4.5;
+/// WCAG contrast for borders and other non-text elements
+pub const UI_CONTRAST: f64 = 3.0;
+
+/// RGB value of a color; named colors use the common xterm palette. `None` for
+/// `Reset` and indexed colors, whose value depends on the terminal.
+pub fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Black => Some((0, 0, 0)),
+        Color::Red => Some((205, 0, 0)),
+        Color::Green => Some((0, 205, 0)),
+        Color::Yellow => Some((205, 205, 0)),
+        Color::Blue => Some((0, 0, 238)),
+        Color::Magenta => Some((205, 0, 205)),
+        Color::Cyan => Some((0, 205, 205)),
+        Color::Gray => Some((229, 229, 229)),
+        Color::DarkGray => Some((127, 127, 127)),
+        Color::LightRed => Some((255, 0, 0)),
+        Color::LightGreen => Some((0, 255, 0)),
+        Color::LightYellow => Some((255, 255, 0)),
+        Color::LightBlue => Some((92, 92, 255)),
+        Color::LightMagenta => Some((255, 0, 255)),
+        Color::LightCyan => Some((0, 255, 255)),
+        Color::White => Some((255, 255, 255)),
+        Color::Reset | Color::Indexed(_) => None,
+    }
+}
+
+/// Relative luminance as defined by WCAG 2.
+pub fn luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    let channel = |c: u8| {
+        let c = c as f64 / 255.0;
+        match c <= 0.039_28 {
+            true => c / 12.92,
+            false => ((c + 0.055) / 1.055).powf(2.4),
+        }
+    };
+    0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b)
+}
+
+/// Contrast ratio between two colors, from 1 to 21.
+pub fn contrast_ratio(a: (u8, u8, u8), b: (u8, u8, u8)) -> f64 {
+    let (la, lb) = (luminance(a), luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Blend `color` towards white or black, whichever contrasts more with
+/// `against`, in 10% steps until the ratio reaches `target`.
+fn push(color: Color, against: (u8, u8, u8), target: f64) -> Color {
+    let Some(start) = rgb(color) else {
+        return color;
+    };
+    if contrast_ratio(start, against) >= target {
+        return color;
+    }
+    let extreme =
+        match contrast_ratio((255, 255, 255), against) >= contrast_ratio((0, 0, 0), against) {
+            true => 255.0,
+            false => 0.0,
+        };
+    let mix = |c: u8, t: f64| (c as f64 + (extreme - c as f64) * t).round() as u8;
+    (1..=10)
+        .map(|step| {
+            let t = step as f64 / 10.0;
+            (mix(start.0, t), mix(start.1, t), mix(start.2, t))
+        })
+        .find(|blended| contrast_ratio(*blended, against) >= target)
+        .map(|(r, g, b)| Color::Rgb(r, g, b))
+        .unwrap_or(Color::Rgb(extreme as u8, extreme as u8, extreme as u8))
+}
+
+/// `theme` with every color pushed past the WCAG thresholds. `dark` stands in for
+/// the terminal background when the theme leaves it at `Reset`.
+pub fn high_contrast(theme: &TuiTheme, dark: bool) -> TuiTheme {
+    let background = rgb(theme.background).unwrap_or(match dark {
+        true => (0, 0, 0),
+        false => (255, 255, 255),
+    });
+    // `Reset` text would keep the terminal's own foreground, which may be dim
+    let foreground = match theme.foreground {
+        Color::Reset => Color::Rgb(128, 128, 128),
+        color => color,
+    };
+    // A mid-tone selection that stands out from the background can leave no room
+    // for readable text on it; the selection is then drawn in inverse instead
+    let mut selection_bg = push(theme.selection_bg, background, UI_CONTRAST);
+    let mut selection_fg = theme.selection_fg;
+    if let Some(against) = rgb(selection_bg) {
+        selection_fg = push(theme.selection_fg, against, TEXT_CONTRAST);
+        if rgb(selection_fg).is_some_and(|fg| contrast_ratio(fg, against) < TEXT_CONTRAST) {
+            selection_bg = push(Color::Rgb(128, 128, 128), background, 21.0);
+            selection_fg = push(
+                theme.selection_fg,
+                rgb(selection_bg).unwrap_or(against),
+                TEXT_CONTRAST,
+            );
+        }
+    }
+    TuiTheme {
+        background: theme.background,
+        foreground: push(foreground, background, TEXT_CONTRAST),
+        selection_bg,
+        selection_fg,
+        border: push(theme.border, background, UI_CONTRAST),
+        link: push(theme.link, background, TEXT_CONTRAST),
+        score: push(theme.score, background, TEXT_CONTRAST),
+        comment_author: push(theme.comment_author, background, TEXT_CONTRAST),
+        comment_time: push(theme.comment_time, background, TEXT_CONTRAST),
+    }
+}
+
+/// The Okabe-Ito palette without black: colors that viewers with any kind of
+/// red-green color blindness can tell apart.
+const OKABE_ITO: [(u8, u8, u8); 7] = [
+    (230, 159, 0),   // orange
+    (86, 180, 233),  // sky blue
+    (0, 158, 115),   // bluish green
+    (240, 228, 66),  // yellow
+    (0, 114, 178),   // blue
+    (213, 94, 0),    // vermillion
+    (204, 121, 167), // reddish purple
+];
+
+/// Saturation below which a color counts as gray and keeps its value.
+const GRAY_SATURATION: f64 = 0.2;
+
+/// Hue in degrees, saturation and value of a color.
+fn hsv((r, g, b): (u8, u8, u8)) -> (f64, f64, f64) {
+    let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let delta = max - r.min(g).min(b);
+    let hue = match delta {
+        0.0 => 0.0,
+        _ if max == r => 60.0 * ((g - b) / delta).rem_euclid(6.0),
+        _ if max == g => 60.0 * ((b - r) / delta + 2.0),
+        _ => 60.0 * ((r - g) / delta + 4.0),
+    };
+    let saturation = match max {
+        0.0 => 0.0,
+        _ => delta / max,
+    };
+    (hue, saturation, max)
+}
+
+/// The Okabe-Ito color closest in hue to `color`, brightness breaking ties.
+fn color_blind_color(color: Color) -> Color {
+    let Some(start) = rgb(color) else {
+        return color;
+    };
+    let (hue, saturation, value) = hsv(start);
+    if saturation < GRAY_SATURATION {
+        return color;
+    }
+    let distance = |candidate: &(u8, u8, u8)| {
+        let (h, _, v) = hsv(*candidate);
+        let apart = (hue - h).abs();
+        apart.min(360.0 - apart) + 100.0 * (value - v).abs()
+    };
+    OKABE_ITO
+        .iter()
+        .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+        .map(|&(r, g, b)| Color::Rgb(r, g, b))
+        .unwrap_or(color)
+}
+
+/// `theme` with its hues moved onto the Okabe-Ito palette, safe for deuteranopia
+/// and protanopia. The background is kept, so combined with [`high_contrast`] the
+/// new colors are then made readable on it.
+pub fn color_blind_safe(theme: &TuiTheme) -> TuiTheme {
+    TuiTheme {
+        background: theme.background,
+        foreground: color_blind_color(theme.foreground),
+        selection_bg: color_blind_color(theme.selection_bg),
+        selection_fg: color_blind_color(theme.selection_fg),
+        border: color_blind_color(theme.border),
+        link: color_blind_color(theme.link),
+        score: color_blind_color(theme.score),
+        comment_author: color_blind_color(theme.comment_author),
+        comment_time: color_blind_color(theme.comment_time),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contrast_ratio_matches_wcag_examples() {
+        assert!((contrast_ratio((0, 0, 0), (255, 255, 255)) - 21.0).abs() < 1e-9);
+        assert!((contrast_ratio((119, 119, 119), (255, 255, 255)) - 4.48).abs() < 0.01);
+    }
+
+    #[test]
+    fn high_contrast_reaches_thresholds_on_dark_and_light_themes() {
+        let dim = TuiTheme {
+            background: Color::Rgb(30, 30, 30),
+            foreground: Color::Rgb(150, 150, 150),
+            selection_bg: Color::Rgb(40, 40, 60),
+            selection_fg: Color::Rgb(120, 120, 120),
+            border: Color::Rgb(50, 50, 50),
+            link: Color::Blue,
+            score: Color::Rgb(128, 100, 0),
+            comment_author: Color::Rgb(60, 60, 160),
+            comment_time: Color::DarkGray,
+        };
+        for (theme, dark) in [(dim.clone(), true), (TuiTheme::default(), false)] {
+            let bg = rgb(theme.background).unwrap_or(match dark {
+                true => (0, 0, 0),
+                false => (255, 255, 255),
+            });
+            let hc = high_contrast(&theme, dark);
+            for color in [
+                hc.foreground,
+                hc.link,
+                hc.score,
+                hc.comment_author,
+                hc.comment_time,
+            ] {
+                assert!(contrast_ratio(rgb(color).unwrap(), bg) >= TEXT_CONTRAST);
+            }
+            assert!(contrast_ratio(rgb(hc.border).unwrap(), bg) >= UI_CONTRAST);
+            let selection = rgb(hc.selection_bg).unwrap();
+            assert!(contrast_ratio(selection, bg) >= UI_CONTRAST);
+            assert!(contrast_ratio(rgb(hc.selection_fg).unwrap(), selection) >= TEXT_CONTRAST);
+        }
+
+        // Colors that are already readable keep their hue
+        let white_on_black = TuiTheme {
+            background: Color::Black,
+            foreground: Color::White,
+            ..dim
+        };
+        assert_eq!(
+            high_contrast(&white_on_black, true).foreground,
+            Color::White
+        );
+    }
+
+    #[test]
+    fn color_blind_safe_moves_red_and_green_apart_and_keeps_grays() {
+        let theme = TuiTheme {
+            background: Color::Rgb(30, 30, 30),
+            foreground: Color::Rgb(200, 200, 200),
+            link: Color::Green,
+            score: Color::Red,
+            comment_time: Color::DarkGray,
+            ..TuiTheme::default()
+        };
+        let safe = color_blind_safe(&theme);
+        assert_eq!(safe.background, theme.background);
+        assert_eq!(safe.foreground, theme.foreground);
+        assert_eq!(safe.comment_time, Color::DarkGray);
+        assert_eq!(safe.link, Color::Rgb(0, 158, 115));
+        assert_eq!(safe.score, Color::Rgb(213, 94, 0));
+        assert_eq!(safe.border, Color::White);
+        for color in [safe.link, safe.score, safe.comment_author] {
+            assert!(
+                OKABE_ITO
+                    .iter()
+                    .any(|&(r, g, b)| color == Color::Rgb(r, g, b))
+            );
+        }
+    }
+}
@@ -1,5 +1,55 @@
+use jiff::tz::TimeZone;
+use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// How timestamps of stories, comments and history entries are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum TimeFormat {
+    /// "3h ago", kept current while the app runs
+    #[default]
+    Relative,
+    /// Local date and time, "2025-01-31 14:05"
+    Absolute,
+    /// ISO 8601 with the local offset, "2025-01-31T14:05:00+01:00"
+    Iso8601,
+}
+
+impl TimeFormat {
+    /// The format after this one, for the toggle.
+    pub fn next(self) -> Self {
+        match self {
+            TimeFormat::Relative => TimeFormat::Absolute,
+            TimeFormat::Absolute => TimeFormat::Iso8601,
+            TimeFormat::Iso8601 => TimeFormat::Relative,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TimeFormat::Relative => "relative",
+            TimeFormat::Absolute => "local time",
+            TimeFormat::Iso8601 => "ISO 8601",
+        }
+    }
+}
+
+/// Format a unix timestamp (seconds) in `format`, in the system time zone.
+pub fn format_time(timestamp: &i64, format: TimeFormat) -> String {
+    format_time_in(timestamp, format, &TimeZone::system())
+}
+
+fn format_time_in(timestamp: &i64, format: TimeFormat, tz: &TimeZone) -> String {
+    let pattern = match format {
+        TimeFormat::Relative => return format_timestamp(timestamp),
+        TimeFormat::Absolute => "%Y-%m-%d %H:%M",
+        TimeFormat::Iso8601 => "%Y-%m-%dT%H:%M:%S%:z",
+    };
+    match jiff::Timestamp::from_second(*timestamp) {
+        Ok(ts) => ts.to_zoned(tz.clone()).strftime(pattern).to_string(),
+        Err(_) => "unknown".to_string(),
+    }
+}
+
 /// Format a unix timestamp (seconds) into a short relative string like "2d ago",
 /// "3h ago", "15m ago", or "just now".
 pub fn format_timestamp(timestamp: &i64) -> String {
@@ -37,7 +87,7 @@ pub fn format_timestamp(timestamp: &i64) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::format_timestamp;
+    use super::{TimeFormat, TimeZone, format_time_in, format_timestamp};
     use std::time::{SystemTime, UNIX_EPOCH};
 
     // Helper to get current unix seconds
@@ -73,4 +123,25 @@ mod tests {
         // 3 days ago
         assert_eq!(format_timestamp(&(now - 3 * 86_400)), "3d ago");
     }
+
+    #[test]
+    fn absolute_and_iso_times_in_a_time_zone() {
+        // 2025-01-31 14:05:00 UTC
+        let ts = 1_738_332_300;
+        let utc = TimeZone::UTC;
+        assert_eq!(
+            format_time_in(&ts, TimeFormat::Absolute, &utc),
+            "2025-01-31 14:05"
+        );
+        assert_eq!(
+            format_time_in(&ts, TimeFormat::Iso8601, &utc),
+            "2025-01-31T14:05:00+00:00"
+        );
+        let paris = TimeZone::fixed(jiff::tz::offset(1));
+        assert_eq!(
+            format_time_in(&ts, TimeFormat::Iso8601, &paris),
+            "2025-01-31T15:05:00+01:00"
+        );
+        assert_eq!(TimeFormat::Iso8601.next(), TimeFormat::Relative);
+    }
 }
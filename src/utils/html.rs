@@ -10,18 +10,217 @@ static IMG_REGEX: Lazy<Regex> =
 /// Strips tags and decodes basic entities using `html2text` crate.
 /// Also replaces <img> tags with [Image: alt] placeholders.
 pub fn extract_text_from_html(html: &str) -> String {
+    // html2text emits wrapped lines; we can join them for now.
+    extract_wrapped_text(html, 80)
+}
+
+/// Like `extract_text_from_html`, with lines wrapped to `width` columns.
+pub fn extract_wrapped_text(html: &str, width: usize) -> String {
     // Pre-process HTML to replace images with text placeholders
     let html_with_placeholders = IMG_REGEX.replace_all(html, "[Image: $1]");
 
-    // html2text emits wrapped lines; we can join them for now.
     let mut bytes = html_with_placeholders.as_bytes();
-    from_read(&mut bytes, 80).unwrap_or_default()
+    from_read(&mut bytes, width.max(20)).unwrap_or_default()
+}
+
+/// Links in an HTML fragment such as a comment, with absolute http(s) URLs only.
+pub fn extract_links(html: &str) -> Vec<crate::internal::models::Link> {
+    let fragment = scraper::Html::parse_fragment(html);
+    let selector = scraper::Selector::parse("a[href]").expect("valid selector");
+    fragment
+        .select(&selector)
+        .filter_map(|a| {
+            let url = a.value().attr("href")?.trim();
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return None;
+            }
+            let text = a.text().collect::<String>().trim().to_string();
+            Some(crate::internal::models::Link {
+                text: match text.is_empty() {
+                    true => url.to_string(),
+                    false => text,
+                },
+                url: url.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// How a run of comment text is set.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunKind {
+    Plain,
+    Italic,
+    Code,
+    /// Link text, with the URL it points to
+    Link(String),
+}
+
+/// Text set one way.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    pub text: String,
+    pub kind: RunKind,
+}
+
+/// A paragraph of a comment.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommentBlock {
+    Text(Vec<Run>),
+    /// A paragraph starting with `>`, without it
+    Quote(Vec<Run>),
+    /// A `<pre>` block, lines as written
+    Code(String),
+}
+
+/// Paragraphs of HN comment HTML. Comments use `<p>` to start all but the first
+/// paragraph, `<i>` for emphasis, `<pre><code>` for code and `<a href>` for links;
+/// quotes are paragraphs starting with `>`. Whitespace inside paragraphs is collapsed.
+#[tracing::instrument(name = "parse.comment", level = "debug", skip_all)]
+pub fn parse_comment(html: &str) -> Vec<CommentBlock> {
+    let fragment = scraper::Html::parse_fragment(html);
+    let mut blocks = Vec::new();
+    let mut runs = Vec::new();
+    for child in fragment.root_element().children() {
+        let Some(element) = scraper::ElementRef::wrap(child) else {
+            if let scraper::Node::Text(text) = child.value() {
+                push_run(&mut runs, text, &RunKind::Plain);
+            }
+            continue;
+        };
+        match element.value().name() {
+            "p" => {
+                push_paragraph(&mut blocks, std::mem::take(&mut runs));
+                collect_runs(element, &RunKind::Plain, &mut runs);
+            }
+            "pre" => {
+                push_paragraph(&mut blocks, std::mem::take(&mut runs));
+                let code = element.text().collect::<String>();
+                blocks.push(CommentBlock::Code(code.trim_end_matches('\n').to_string()));
+            }
+            _ => collect_runs(element, &kind_inside(element, &RunKind::Plain), &mut runs),
+        }
+    }
+    push_paragraph(&mut blocks, runs);
+    blocks
+}
+
+/// Runs of the text inside `element`, set as `kind` unless a tag inside says otherwise.
+fn collect_runs(element: scraper::ElementRef, kind: &RunKind, runs: &mut Vec<Run>) {
+    for child in element.children() {
+        if let scraper::Node::Text(text) = child.value() {
+            push_run(runs, text, kind);
+            continue;
+        }
+        if let Some(inner) = scraper::ElementRef::wrap(child) {
+            collect_runs(inner, &kind_inside(inner, kind), runs);
+        }
+    }
+}
+
+/// How the text inside `element` is set, when the text around it is set as `kind`.
+fn kind_inside(element: scraper::ElementRef, kind: &RunKind) -> RunKind {
+    match (element.value().name(), kind) {
+        ("i" | "em", RunKind::Plain) => RunKind::Italic,
+        ("code", _) => RunKind::Code,
+        ("a", _) => match element.value().attr("href") {
+            Some(url) => RunKind::Link(url.trim().to_string()),
+            None => kind.clone(),
+        },
+        _ => kind.clone(),
+    }
+}
+
+/// Add `text` with its whitespace collapsed to single spaces.
+fn push_run(runs: &mut Vec<Run>, text: &str, kind: &RunKind) {
+    let mut collapsed = String::new();
+    for c in text.chars() {
+        match c.is_whitespace() {
+            true if collapsed.ends_with(' ') => {}
+            true => collapsed.push(' '),
+            false => collapsed.push(c),
+        }
+    }
+    match runs.last_mut() {
+        Some(last) if last.kind == *kind => {
+            if last.text.ends_with(' ') && collapsed.starts_with(' ') {
+                collapsed.remove(0);
+            }
+            last.text.push_str(&collapsed);
+        }
+        _ => runs.push(Run {
+            text: collapsed,
+            kind: kind.clone(),
+        }),
+    }
+}
+
+/// End a paragraph, telling quotes from plain text. Empty paragraphs are dropped.
+fn push_paragraph(blocks: &mut Vec<CommentBlock>, mut runs: Vec<Run>) {
+    while runs.first().is_some_and(|run| run.text.trim().is_empty()) {
+        runs.remove(0);
+    }
+    let Some(first) = runs.first_mut() else {
+        return;
+    };
+    first.text = first.text.trim_start().to_string();
+    if let Some(last) = runs.last_mut() {
+        last.text = last.text.trim_end().to_string();
+    }
+    let first = &mut runs[0];
+    match first.text.strip_prefix('>') {
+        Some(rest) => {
+            first.text = rest.trim_start().to_string();
+            blocks.push(CommentBlock::Quote(runs));
+        }
+        None => blocks.push(CommentBlock::Text(runs)),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn extracts_links_from_comments() {
+        let html = r#"See <a href="https:&#x2F;&#x2F;example.com&#x2F;a?b=1&amp;c=2" rel="nofollow">https:&#x2F;&#x2F;example.com&#x2F;a?b=1...</a> and <a href="item?id=1">this</a>"#;
+        let links = extract_links(html);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "https://example.com/a?b=1&c=2");
+        assert_eq!(links[0].text, "https://example.com/a?b=1...");
+    }
+
+    #[test]
+    fn parses_comment_formatting() {
+        let html = "Like <i>this</i>:<p>&gt; quoted <a href=\"https:&#x2F;&#x2F;a.io\">a.io</a>\
+            <p><pre><code>  let x = 1;\n</code></pre>";
+        let plain = |text: &str| Run {
+            text: text.to_string(),
+            kind: RunKind::Plain,
+        };
+        assert_eq!(
+            parse_comment(html),
+            vec![
+                CommentBlock::Text(vec![
+                    plain("Like "),
+                    Run {
+                        text: "this".to_string(),
+                        kind: RunKind::Italic,
+                    },
+                    plain(":"),
+                ]),
+                CommentBlock::Quote(vec![
+                    plain("quoted "),
+                    Run {
+                        text: "a.io".to_string(),
+                        kind: RunKind::Link("https://a.io".to_string()),
+                    },
+                ]),
+                CommentBlock::Code("  let x = 1;".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn extracts_text() {
         let html = "<p>Hello <strong>World</strong> &amp; friends</p>";
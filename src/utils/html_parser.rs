@@ -1,27 +1,308 @@
-use crate::internal::models::ArticleElement;
-use scraper::{Html, Selector};
+use crate::internal::models::{Article, ArticleElement, Link, SiteMeta};
+use scraper::{ElementRef, Html, Selector};
+use std::collections::HashMap;
 
-pub fn parse_article_html(html: &str) -> Vec<ArticleElement> {
+/// Tags whose content is page chrome rather than article text.
+const BOILERPLATE_TAGS: &[&str] = &[
+    "nav", "header", "footer", "aside", "form", "script", "style", "noscript",
+];
+
+/// Words of class/id names that usually mark non-content blocks. They are matched
+/// against whole words (`site-nav`, `comments`), not inside longer ones like
+/// `unavailable` or `commentary`.
+const BOILERPLATE_HINTS: &[&str] = &[
+    "comment",
+    "sidebar",
+    "footer",
+    "nav",
+    "navbar",
+    "navigation",
+    "menu",
+    "share",
+    "social",
+    "related",
+    "promo",
+    "advert",
+    "cookie",
+    "newsletter",
+    "subscribe",
+];
+
+/// Extract a readable article from a full HTML page.
+///
+/// This is a small readability-style pipeline: metadata (title, byline, publish
+/// date, site name) comes from `<meta>`/`<title>` tags, the main content block is
+/// chosen by scoring paragraph-bearing containers, and only that block is
+/// converted to `ArticleElement`s with navigation and other chrome stripped.
+#[tracing::instrument(name = "parse.article", level = "debug", skip_all, fields(bytes = html.len()))]
+pub fn extract_article(html: &str) -> Article {
     let document = Html::parse_document(html);
-    let mut elements = Vec::new();
 
-    // Select the main content. This is a heuristic.
-    // In a real app, we might use something like readability.js logic.
-    // For now, we'll try to find common article containers or just parse the body.
-    let _body_selector = Selector::parse("body").unwrap();
+    let (elements, links) = match find_main_content(&document) {
+        Some(root) => (parse_elements(root), parse_links(root)),
+        None => (Vec::new(), Vec::new()),
+    };
+
+    Article {
+        title: extract_title(&document).unwrap_or_else(|| "Article".to_string()),
+        word_count: Article::count_words(&elements),
+        elements,
+        byline: extract_byline(&document),
+        published: extract_published(&document),
+        site_name: meta_content(&document, &["meta[property='og:site_name']"]),
+        links,
+    }
+}
+
+/// Name and brand color a page declares in its `<meta>` tags.
+pub fn extract_site_meta(html: &str) -> SiteMeta {
+    let document = Html::parse_document(html);
+    SiteMeta {
+        name: meta_content(
+            &document,
+            &[
+                "meta[property='og:site_name']",
+                "meta[name='application-name']",
+            ],
+        ),
+        theme_color: meta_content(&document, &["meta[name='theme-color']"])
+            .and_then(|color| parse_hex_color(&color)),
+    }
+}
+
+/// `#rrggbb` or `#rgb`; named and `rgb()` colors are not worth the trouble here.
+fn parse_hex_color(color: &str) -> Option<(u8, u8, u8)> {
+    let hex = color.trim().strip_prefix('#')?;
+    let channel = |digits: &str| u8::from_str_radix(digits, 16).ok();
+    match hex.len() {
+        6 => Some((
+            channel(&hex[0..2])?,
+            channel(&hex[2..4])?,
+            channel(&hex[4..6])?,
+        )),
+        3 => {
+            let short = |i: usize| channel(&hex[i..i + 1]).map(|v| v * 17);
+            Some((short(0)?, short(1)?, short(2)?))
+        }
+        _ => None,
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn meta_content(document: &Html, selectors: &[&str]) -> Option<String> {
+    selectors.iter().find_map(|sel| {
+        let selector = Selector::parse(sel).ok()?;
+        document
+            .select(&selector)
+            .filter_map(|el| el.value().attr("content"))
+            .map(collapse_whitespace)
+            .find(|c| !c.is_empty())
+    })
+}
+
+fn first_text(document: &Html, selectors: &[&str]) -> Option<String> {
+    selectors.iter().find_map(|sel| {
+        let selector = Selector::parse(sel).ok()?;
+        document
+            .select(&selector)
+            .map(|el| collapse_whitespace(&el.text().collect::<String>()))
+            .find(|t| !t.is_empty())
+    })
+}
+
+fn extract_title(document: &Html) -> Option<String> {
+    if let Some(title) = meta_content(
+        document,
+        &["meta[property='og:title']", "meta[name='twitter:title']"],
+    ) {
+        return Some(title);
+    }
+
+    // <title> often carries a " | Site Name" suffix; keep the leading part when
+    // it still looks like a real headline.
+    if let Some(title) = first_text(document, &["title"]) {
+        let trimmed = [" | ", " — ", " - "]
+            .iter()
+            .find_map(|sep| {
+                title
+                    .split_once(sep)
+                    .map(|(head, _)| head.trim())
+                    .filter(|head| head.split_whitespace().count() >= 3)
+            })
+            .unwrap_or(&title);
+        return Some(trimmed.to_string());
+    }
+
+    first_text(document, &["h1"])
+}
+
+fn extract_byline(document: &Html) -> Option<String> {
+    meta_content(
+        document,
+        &["meta[name='author']", "meta[property='article:author']"],
+    )
+    .filter(|a| !a.starts_with("http"))
+    .or_else(|| first_text(document, &["[rel='author']", ".byline", ".author"]))
+    .map(|a| {
+        a.strip_prefix("By ")
+            .or_else(|| a.strip_prefix("by "))
+            .unwrap_or(&a)
+            .to_string()
+    })
+}
+
+fn extract_published(document: &Html) -> Option<String> {
+    meta_content(
+        document,
+        &[
+            "meta[property='article:published_time']",
+            "meta[name='date']",
+            "meta[itemprop='datePublished']",
+        ],
+    )
+    .or_else(|| {
+        let selector = Selector::parse("time[datetime]").ok()?;
+        document
+            .select(&selector)
+            .filter_map(|t| t.value().attr("datetime"))
+            .map(|d| d.trim().to_string())
+            .find(|d| !d.is_empty())
+    })
+}
+
+fn is_boilerplate(element: &ElementRef) -> bool {
+    let value = element.value();
+    if BOILERPLATE_TAGS.contains(&value.name()) {
+        return true;
+    }
+    let names = format!(
+        "{} {}",
+        value.attr("class").unwrap_or(""),
+        value.attr("id").unwrap_or("")
+    )
+    .to_lowercase();
+    // Words of the names, split at spaces, dashes and underscores; plurals count
+    names
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .map(|word| word.strip_suffix('s').unwrap_or(word))
+        .any(|word| BOILERPLATE_HINTS.contains(&word))
+}
+
+/// True if `element` sits inside a boilerplate block below `root`.
+fn inside_boilerplate(element: &ElementRef, root: &ElementRef) -> bool {
+    element
+        .ancestors()
+        .take_while(|node| node.id() != root.id())
+        .filter_map(ElementRef::wrap)
+        .any(|el| is_boilerplate(&el))
+}
+
+/// Fraction of an element's text that is link text.
+fn link_density(element: &ElementRef) -> f64 {
+    let text_len = element.text().map(|t| t.trim().len()).sum::<usize>();
+    if text_len == 0 {
+        return 0.0;
+    }
+    let link_selector = Selector::parse("a").unwrap();
+    let link_len: usize = element
+        .select(&link_selector)
+        .flat_map(|a| a.text())
+        .map(|t| t.trim().len())
+        .sum();
+    link_len as f64 / text_len as f64
+}
+
+/// Pick the element most likely to hold the article body.
+///
+/// Explicit `<article>`/`<main>` containers win when they hold real text;
+/// otherwise paragraph scores are propagated to parents and grandparents
+/// (as readability does) and the best container, penalized by link density, is chosen.
+fn find_main_content(document: &Html) -> Option<ElementRef<'_>> {
+    let explicit = Selector::parse("article, main, [role='main']").unwrap();
+    let paragraph = Selector::parse("p").unwrap();
+
+    if let Some(container) = document.select(&explicit).max_by_key(|el| {
+        el.select(&paragraph)
+            .map(|p| p.text().map(str::len).sum::<usize>())
+            .sum::<usize>()
+    }) && container.select(&paragraph).next().is_some()
+    {
+        return Some(container);
+    }
+
+    let mut scores = HashMap::<_, f64>::new();
+    for p in document.select(&paragraph) {
+        let text = collapse_whitespace(&p.text().collect::<String>());
+        if text.len() < 25 {
+            continue;
+        }
+        let score = 1.0 + text.matches(',').count() as f64 + (text.len() / 100).min(3) as f64;
+
+        let mut ancestors = p.ancestors().filter_map(ElementRef::wrap);
+        if let Some(parent) = ancestors.next() {
+            *scores.entry(parent.id()).or_default() += score;
+        }
+        if let Some(grandparent) = ancestors.next() {
+            *scores.entry(grandparent.id()).or_default() += score / 2.0;
+        }
+    }
+
+    let best = scores
+        .into_iter()
+        .filter_map(|(id, score)| {
+            let el = document.tree.get(id).and_then(ElementRef::wrap)?;
+            match is_boilerplate(&el) {
+                true => None,
+                false => Some((el, score * (1.0 - link_density(&el)))),
+            }
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(el, _)| el);
+
+    best.or_else(|| {
+        let body = Selector::parse("body").unwrap();
+        document.select(&body).next()
+    })
+}
 
-    // Simple approach: Iterate over all direct children of body or a main container
-    // and convert them to ArticleElements.
-    // Since we don't know the structure, let's just traverse important tags.
+/// Links in the content below `root`, as written in the page. Links within the
+/// page and to scripts or mail addresses are left out.
+fn parse_links(root: ElementRef) -> Vec<Link> {
+    let selector = Selector::parse("a[href]").unwrap();
+    root.select(&selector)
+        .filter(|a| !inside_boilerplate(a, &root))
+        .filter_map(|a| {
+            let href = a.value().attr("href")?.trim();
+            let skipped = href.is_empty()
+                || href.starts_with('#')
+                || href.starts_with("javascript:")
+                || href.starts_with("mailto:");
+            let text = collapse_whitespace(&a.text().collect::<String>());
+            (!skipped).then(|| Link {
+                text: match text.is_empty() {
+                    true => href.to_string(),
+                    false => text,
+                },
+                url: href.to_string(),
+            })
+        })
+        .collect()
+}
 
-    // Better approach for generic HTML:
-    // Select all p, h1-h6, pre, ul/ol, table, img tags in document order
-    // and convert them.
+/// Convert the content below `root` into article elements in document order.
+fn parse_elements(root: ElementRef) -> Vec<ArticleElement> {
+    let mut elements = Vec::new();
 
     let selector =
         Selector::parse("p, h1, h2, h3, h4, h5, h6, pre, ul, ol, table, img, blockquote").unwrap();
 
-    for element in document.select(&selector) {
+    for element in root.select(&selector) {
+        if inside_boilerplate(&element, &root) {
+            continue;
+        }
         let tag_name = element.value().name();
 
         match tag_name {
@@ -126,7 +407,7 @@ mod tests {
                 </body>
             </html>
         "#;
-        let elements = parse_article_html(html);
+        let elements = extract_article(html).elements;
         assert_eq!(elements.len(), 3);
         assert!(matches!(elements[0], ArticleElement::Heading(1, _)));
         assert!(matches!(elements[1], ArticleElement::Paragraph(_)));
@@ -137,7 +418,7 @@ mod tests {
         let html = r#"
             <pre><code class="rust">fn main() {}</code></pre>
         "#;
-        let elements = parse_article_html(html);
+        let elements = extract_article(html).elements;
         assert_eq!(elements.len(), 1);
         match &elements[0] {
             ArticleElement::CodeBlock { lang, code } => {
@@ -147,4 +428,157 @@ mod tests {
             _ => panic!("Expected CodeBlock"),
         }
     }
+
+    #[test]
+    fn test_extract_metadata() {
+        let html = r#"
+            <html>
+                <head>
+                    <title>A Fairly Long Headline | Example Site</title>
+                    <meta name="author" content="Jane Doe">
+                    <meta property="article:published_time" content="2024-05-01T10:00:00Z">
+                    <meta property="og:site_name" content="Example">
+                </head>
+                <body><p>Body text that is long enough to count as content.</p></body>
+            </html>
+        "#;
+        let article = extract_article(html);
+        assert_eq!(article.title, "A Fairly Long Headline");
+        assert_eq!(article.byline.as_deref(), Some("Jane Doe"));
+        assert_eq!(article.published.as_deref(), Some("2024-05-01T10:00:00Z"));
+        assert_eq!(article.site_name.as_deref(), Some("Example"));
+        assert_eq!(article.word_count, 10);
+        assert_eq!(article.reading_minutes(), 1);
+    }
+
+    #[test]
+    fn test_extract_site_meta() {
+        let html = r##"
+            <html><head>
+                <meta name="application-name" content="Example News">
+                <meta name="theme-color" content="#1a2B3c">
+            </head><body></body></html>
+        "##;
+        let meta = extract_site_meta(html);
+        assert_eq!(meta.name.as_deref(), Some("Example News"));
+        assert_eq!(meta.theme_color, Some((0x1a, 0x2b, 0x3c)));
+
+        assert_eq!(parse_hex_color("#f80"), Some((0xff, 0x88, 0x00)));
+        assert_eq!(parse_hex_color("red"), None);
+        assert_eq!(extract_site_meta("<p>No meta</p>"), SiteMeta::default());
+    }
+
+    #[test]
+    fn test_reading_time_skips_code() {
+        let article = Article {
+            elements: vec![
+                ArticleElement::Heading(1, "Two words".to_string()),
+                ArticleElement::Paragraph("word ".repeat(458)),
+                ArticleElement::CodeBlock {
+                    lang: None,
+                    code: "let x = 1; ".repeat(100),
+                },
+                ArticleElement::List(vec!["one".to_string(), "two items".to_string()]),
+            ],
+            ..Default::default()
+        };
+        // Counted lazily for articles cached before word counts existed
+        assert_eq!(article.words(), 463);
+        assert_eq!(article.reading_minutes(), 3);
+        assert_eq!(Article::default().reading_minutes(), 0);
+    }
+
+    #[test]
+    fn test_main_content_skips_chrome() {
+        let html = r#"
+            <html>
+                <body>
+                    <nav><p>Home, About, Contact, Blog, Careers and more links</p></nav>
+                    <div class="sidebar"><p>Subscribe to our newsletter, it is great, really</p></div>
+                    <div id="story">
+                        <p>The first paragraph of the story, which has plenty of words, commas, and detail.</p>
+                        <p>The second paragraph continues the story with even more words, and, commas.</p>
+                    </div>
+                    <footer><p>Copyright notice and legal text that nobody reads at all</p></footer>
+                </body>
+            </html>
+        "#;
+        let elements = extract_article(html).elements;
+        assert_eq!(elements.len(), 2);
+        assert!(
+            elements
+                .iter()
+                .all(|e| matches!(e, ArticleElement::Paragraph(t) if t.contains("paragraph")))
+        );
+    }
+
+    #[test]
+    fn test_boilerplate_hints_match_whole_words() {
+        let html = r#"
+            <html><body>
+                <div class="notice unavailable commentary">
+                    <div id="story">
+                        <p>The first paragraph of the story, which has plenty of words, commas, and detail.</p>
+                        <p>The second paragraph continues the story with even more words, and, commas.</p>
+                    </div>
+                    <div class="site-comments"><p>A reader comment, with words, commas, and opinions.</p></div>
+                </div>
+            </body></html>
+        "#;
+        let elements = extract_article(html).elements;
+        assert_eq!(elements.len(), 2);
+        assert!(
+            elements
+                .iter()
+                .all(|e| matches!(e, ArticleElement::Paragraph(t) if t.contains("paragraph")))
+        );
+    }
+
+    #[test]
+    fn test_prefers_article_element() {
+        let html = r#"
+            <html><body>
+                <div><p>Unrelated teaser text outside of the article container.</p></div>
+                <article><h2>Heading</h2><p>Inside the article.</p></article>
+            </body></html>
+        "#;
+        let article = extract_article(html);
+        assert_eq!(article.elements.len(), 2);
+        assert!(matches!(article.elements[0], ArticleElement::Heading(2, _)));
+        // Falls back to the first <h1>/"Article" when no title metadata exists
+        assert_eq!(article.title, "Article");
+    }
+
+    #[test]
+    fn test_collects_links_resolved_against_the_page() {
+        let html = r##"
+            <html><body>
+                <nav><a href="/home">Home</a></nav>
+                <article>
+                    <p>See <a href="../docs/intro.html#setup">the  intro</a>,
+                    <a href="https://example.org/">example.org</a> and
+                    <a href="#top">top</a> or <a href="mailto:me@example.com">mail</a>.</p>
+                    <p><a href="https://example.org/">again</a></p>
+                </article>
+            </body></html>
+        "##;
+        let mut article = extract_article(html);
+        assert_eq!(article.links.len(), 3);
+        article.resolve_links("https://blog.example.com/posts/first/");
+        let links: Vec<(&str, &str)> = article
+            .links
+            .iter()
+            .map(|l| (l.text.as_str(), l.url.as_str()))
+            .collect();
+        assert_eq!(
+            links,
+            vec![
+                (
+                    "the intro",
+                    "https://blog.example.com/posts/docs/intro.html"
+                ),
+                ("example.org", "https://example.org/"),
+            ]
+        );
+    }
 }
@@ -1,6 +1,9 @@
+pub mod clipboard;
+pub mod contrast;
 pub mod datetime;
 pub mod html;
 pub mod html_parser;
+pub mod sample;
 pub mod theme;
 pub mod theme_loader;
 pub mod url;
@@ -0,0 +1,55 @@
+//! Weighted random choice without an RNG dependency.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A random number in `[0, 1)`, from the randomly keyed hasher of the standard
+/// library. Good enough to pick something to read, not for anything secret.
+pub fn random_unit() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    hasher.write_u128(nanos);
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Index chosen with probability proportional to its weight, for a `roll` in
+/// `[0, 1)`. `None` when no weight is positive.
+pub fn weighted_index(weights: &[f64], roll: f64) -> Option<usize> {
+    let total: f64 = weights.iter().filter(|w| **w > 0.0).sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let mut target = roll.clamp(0.0, 1.0) * total;
+    let mut last = None;
+    for (idx, weight) in weights.iter().enumerate().filter(|(_, w)| **w > 0.0) {
+        if target < *weight {
+            return Some(idx);
+        }
+        target -= weight;
+        last = Some(idx);
+    }
+    // A roll of 1.0 (or rounding) lands past the end
+    last
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_in_proportion_to_weight() {
+        let weights = [1.0, 0.0, 3.0];
+        assert_eq!(weighted_index(&weights, 0.0), Some(0));
+        assert_eq!(weighted_index(&weights, 0.24), Some(0));
+        assert_eq!(weighted_index(&weights, 0.25), Some(2));
+        assert_eq!(weighted_index(&weights, 1.0), Some(2));
+        assert_eq!(weighted_index(&[0.0, -1.0], 0.5), None);
+        assert_eq!(weighted_index(&[], 0.5), None);
+
+        let roll = random_unit();
+        assert!((0.0..1.0).contains(&roll));
+    }
+}
@@ -3,7 +3,26 @@ use ratatui::style::Color;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Directory the built-in themes are listed under; nothing is read from it.
+pub const BUILTIN_DIR: &str = "(built-in)";
+
+/// Themes compiled into the binary, by file name, so there is a choice of themes
+/// without a themes directory.
+const BUILTIN_THEMES: [(&str, &str); 5] = [
+    ("gruvbox.json", include_str!("../../themes/gruvbox.json")),
+    (
+        "solarized.json",
+        include_str!("../../themes/solarized.json"),
+    ),
+    (
+        "catppuccin.json",
+        include_str!("../../themes/catppuccin.json"),
+    ),
+    ("nord.json", include_str!("../../themes/nord.json")),
+    ("dracula.json", include_str!("../../themes/dracula.json")),
+];
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct ThemeFile {
@@ -20,7 +39,7 @@ pub struct ThemeVariant {
     pub colors: HashMap<String, String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Hash)]
 pub struct TuiTheme {
     pub background: Color,
     pub foreground: Color,
@@ -53,7 +72,10 @@ impl Default for TuiTheme {
 #[tracing::instrument(skip(path, mode), fields(path = ?path, mode = %mode))]
 pub fn load_theme(path: &Path, mode: &str, enable_performance_metrics: bool) -> Result<TuiTheme> {
     let start = std::time::Instant::now();
-    let content = fs::read_to_string(path).context("Failed to read theme file")?;
+    let content = match builtin_theme(path) {
+        Some(content) => content.to_string(),
+        None => fs::read_to_string(path).context("Failed to read theme file")?,
+    };
     let theme_file: ThemeFile =
         serde_json::from_str(&content).context("Failed to parse theme JSON")?;
 
@@ -131,6 +153,162 @@ pub fn load_theme(path: &Path, mode: &str, enable_performance_metrics: bool) ->
     Ok(theme)
 }
 
+/// The colors a theme variant provides, each with the keys it may be given under,
+/// in the order `load_theme` tries them.
+pub const THEME_PROPERTIES: [&[&str]; 8] = [
+    &["background"],
+    &["foreground"],
+    &[
+        "selection.background",
+        "list.active.background",
+        "primary.background",
+    ],
+    &["accent.foreground", "foreground"],
+    &["border"],
+    &["base.blue"],
+    &["base.yellow"],
+    &["muted.foreground"],
+];
+
+/// What is wrong with a variant: properties it lacks (falling back to a default
+/// color) and values that are not colors.
+pub fn variant_problems(variant: &ThemeVariant) -> Vec<String> {
+    let mut problems: Vec<String> = THEME_PROPERTIES
+        .iter()
+        .filter(|keys| !keys.iter().any(|key| variant.colors.contains_key(*key)))
+        .map(|keys| format!("missing property \"{}\"", keys.join("\" or \"")))
+        .collect();
+    let mut values: Vec<(&String, &String)> = variant.colors.iter().collect();
+    values.sort();
+    for (key, value) in values {
+        if !is_color(value) {
+            problems.push(format!("\"{}\": \"{}\" is not a color", key, value));
+        }
+    }
+    problems
+}
+
+/// A color name ratatui knows or a `#rrggbb`/`#rrggbbaa` hex value.
+pub fn is_color(value: &str) -> bool {
+    let hex = value.trim_start_matches('#');
+    value.parse::<Color>().is_ok()
+        || (matches!(hex.len(), 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// The `.json` theme files found, in priority order: `theme_directory`, then
+/// `theme_file` (a file or a directory), `./themes` and the `themes` directory next
+/// to the executable. Each file is listed once.
+pub fn theme_files(theme_directory: &str, configured: &str) -> Vec<PathBuf> {
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    if !theme_directory.trim().is_empty() {
+        candidates.push(PathBuf::from(theme_directory));
+    }
+    if !configured.trim().is_empty() {
+        candidates.push(PathBuf::from(configured));
+    }
+    if let Ok(exe) = std::env::current_exe()
+        && let Some(dir) = exe.parent()
+    {
+        candidates.push(dir.join("themes"));
+    }
+
+    let is_json = |path: &Path| {
+        path.extension()
+            .and_then(|s| s.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+    };
+    let mut files = Vec::new();
+    for cand in candidates {
+        match (cand.is_file(), fs::read_dir(&cand)) {
+            (true, _) if is_json(&cand) => files.push(cand),
+            (false, Ok(entries)) => {
+                files.extend(
+                    entries
+                        .flatten()
+                        .map(|entry| entry.path())
+                        .filter(|p| is_json(p)),
+                );
+            }
+            _ => {}
+        }
+    }
+    let mut seen = std::collections::HashSet::new();
+    files.retain(|path| seen.insert(path.clone()));
+    files
+}
+
+/// A theme file holding `variants`, each a mode (`"dark"` or `"light"`) and its
+/// colors, in the format `load_theme` reads.
+pub fn theme_file_json(name: &str, variants: &[(&str, &TuiTheme)]) -> serde_json::Value {
+    let themes: Vec<serde_json::Value> = variants
+        .iter()
+        .map(|(mode, theme)| {
+            let colors: HashMap<&str, String> = [
+                ("background", theme.background),
+                ("foreground", theme.foreground),
+                ("selection.background", theme.selection_bg),
+                ("accent.foreground", theme.selection_fg),
+                ("border", theme.border),
+                ("base.blue", theme.link),
+                ("base.yellow", theme.score),
+                ("muted.foreground", theme.comment_time),
+            ]
+            .into_iter()
+            .map(|(key, color)| (key, color_to_hex(color)))
+            .collect();
+            let title = match *mode {
+                "light" => "Light",
+                _ => "Dark",
+            };
+            serde_json::json!({
+                "name": format!("{} {}", name, title),
+                "mode": mode,
+                "colors": colors,
+            })
+        })
+        .collect();
+    serde_json::json!({ "name": name, "themes": themes })
+}
+
+/// `#rrggbb`, or black for colors that are not RGB.
+fn color_to_hex(color: Color) -> String {
+    match color {
+        Color::Rgb(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        _ => "#000000".to_string(),
+    }
+}
+
+/// Paths of the built-in themes, under `BUILTIN_DIR`, except those a file in
+/// `files` overrides by having the same name.
+pub fn builtin_theme_paths(files: &[PathBuf]) -> Vec<PathBuf> {
+    let name = |path: &Path| {
+        path.file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default()
+            .replace(['-', '_'], " ")
+            .to_lowercase()
+    };
+    let overridden: std::collections::HashSet<String> =
+        files.iter().map(|path| name(path)).collect();
+    BUILTIN_THEMES
+        .iter()
+        .map(|(file, _)| Path::new(BUILTIN_DIR).join(file))
+        .filter(|path| !overridden.contains(&name(path)))
+        .collect()
+}
+
+/// Content of the built-in theme `path` names, if it is one.
+pub fn builtin_theme(path: &Path) -> Option<&'static str> {
+    let file = path.file_name()?;
+    match path.parent() == Some(Path::new(BUILTIN_DIR)) {
+        true => BUILTIN_THEMES
+            .iter()
+            .find(|(name, _)| file == *name)
+            .map(|(_, content)| *content),
+        false => None,
+    }
+}
+
 fn parse_color(hex: &str) -> Color {
     if let Ok(c) = hex.parse::<Color>() {
         return c;
@@ -148,3 +326,73 @@ fn parse_color(hex: &str) -> Color {
         _ => Color::Reset,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_variants_load_back() {
+        let dark = TuiTheme {
+            background: Color::Rgb(16, 16, 32),
+            foreground: Color::Rgb(230, 230, 230),
+            score: Color::Rgb(250, 200, 0),
+            ..TuiTheme::default()
+        };
+        let light = TuiTheme {
+            background: Color::Rgb(239, 239, 223),
+            ..dark.clone()
+        };
+        let json = theme_file_json("Dusk", &[("dark", &dark), ("light", &light)]);
+        let path = std::env::temp_dir().join(format!("hn-theme-{}.json", std::process::id()));
+        fs::write(&path, json.to_string()).unwrap();
+
+        let loaded = load_theme(&path, "light", false).unwrap();
+        assert_eq!(loaded.background, Color::Rgb(239, 239, 223));
+        assert_eq!(loaded.score, Color::Rgb(250, 200, 0));
+        assert_eq!(
+            load_theme(&path, "dark", false).unwrap().background,
+            Color::Rgb(16, 16, 32)
+        );
+        let file: ThemeFile = serde_json::from_value(json).unwrap();
+        assert_eq!(file.themes[1].name, "Dusk Light");
+        assert!(file.themes.iter().all(|v| variant_problems(v).is_empty()));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn builtin_themes_load_unless_overridden() {
+        let paths = builtin_theme_paths(&[]);
+        assert_eq!(paths.len(), BUILTIN_THEMES.len());
+        for path in &paths {
+            let file: ThemeFile = serde_json::from_str(builtin_theme(path).unwrap()).unwrap();
+            for mode in ["dark", "light"] {
+                assert!(file.themes.iter().any(|v| v.mode == mode), "{:?}", path);
+            }
+            assert!(load_theme(path, "light", false).is_ok());
+        }
+        assert_eq!(
+            load_theme(Path::new("(built-in)/nord.json"), "dark", false)
+                .unwrap()
+                .background,
+            Color::Rgb(0x2e, 0x34, 0x40)
+        );
+
+        // A file of the same name replaces the built-in one
+        let files = [PathBuf::from("/home/me/themes/Nord.json")];
+        let names: Vec<String> = builtin_theme_paths(&files)
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "gruvbox.json",
+                "solarized.json",
+                "catppuccin.json",
+                "dracula.json"
+            ]
+        );
+        assert!(builtin_theme(Path::new("themes/nord.json")).is_none());
+    }
+}
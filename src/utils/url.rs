@@ -29,10 +29,133 @@ pub fn extract_domain(url: &str) -> Option<String> {
     }
 }
 
+/// `href` made absolute against the page at `base`; `None` unless it leads to an
+/// http(s) page other than `base` itself.
+/// Example: ("https://a.io/post/", "../about") -> Some("https://a.io/about")
+pub fn join(base: &str, href: &str) -> Option<String> {
+    let base = reqwest::Url::parse(base).ok()?;
+    let mut url = base.join(href.trim()).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    url.set_fragment(None);
+    match url == base {
+        true => None,
+        false => Some(url.to_string()),
+    }
+}
+
+/// The part of a URL that identifies the page: no scheme, leading "www.", fragment
+/// or trailing slash, so resubmissions of the same link compare equal.
+/// Example: "https://www.example.com/post/#top" -> "example.com/post"
+pub fn page_key(url: &str) -> &str {
+    let url = url.trim();
+    let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let url = url.strip_prefix("www.").unwrap_or(url);
+    let url = url.split('#').next().unwrap_or(url);
+    url.trim_end_matches('/')
+}
+
+/// Link shorteners whose destination cannot be told from the link itself.
+const SHORTENERS: &[&str] = &[
+    "bit.ly",
+    "buff.ly",
+    "cutt.ly",
+    "goo.gl",
+    "is.gd",
+    "lnkd.in",
+    "ow.ly",
+    "rb.gy",
+    "rebrand.ly",
+    "s.id",
+    "shorturl.at",
+    "t.co",
+    "t.ly",
+    "tiny.cc",
+    "tinyurl.com",
+    "trib.al",
+];
+
+/// Whether `domain` (as returned by `extract_domain`) is a known URL shortener.
+pub fn is_url_shortener(domain: &str) -> bool {
+    let domain = domain.trim_start_matches("www.").to_lowercase();
+    SHORTENERS.contains(&domain.as_str())
+}
+
+/// Whether two domains belong to the same site: equal once "www." is dropped, or
+/// one a subdomain of the other ("blog.example.com" and "example.com").
+pub fn same_site(a: &str, b: &str) -> bool {
+    let a = a.trim_start_matches("www.").to_lowercase();
+    let b = b.trim_start_matches("www.").to_lowercase();
+    a == b || a.ends_with(&format!(".{}", b)) || b.ends_with(&format!(".{}", a))
+}
+
+/// Build the Hacker News discussion URL for an item id
+/// Example: 123 -> "https://news.ycombinator.com/item?id=123"
+pub fn hn_item_url(id: u32) -> String {
+    format!("https://news.ycombinator.com/item?id={}", id)
+}
+
+/// Parse an HN item reference: a bare id (`8863`) or an HN item URL
+/// (`https://news.ycombinator.com/item?id=8863`).
+pub fn parse_item_id(text: &str) -> Option<u32> {
+    let text = text.trim();
+    match text.parse() {
+        Ok(id) => Some(id),
+        Err(_) if text.contains("item?") => text
+            .split(['?', '&', '#'])
+            .find_map(|param| param.strip_prefix("id="))
+            .and_then(|id| id.parse().ok()),
+        Err(_) => None,
+    }
+}
+
+/// Collect item ids from newline (or whitespace) separated ids/URLs, keeping the
+/// first occurrence of each. Anything that is not an item reference is ignored.
+pub fn parse_item_ids(text: &str) -> Vec<u32> {
+    let mut seen = std::collections::HashSet::new();
+    text.split_whitespace()
+        .filter_map(parse_item_id)
+        .filter(|id| seen.insert(*id))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_shorteners_and_same_site() {
+        assert!(is_url_shortener("bit.ly"));
+        assert!(is_url_shortener("www.T.co"));
+        assert!(!is_url_shortener("bitly.example.com"));
+
+        assert!(same_site("www.example.com", "example.com"));
+        assert!(same_site("example.com", "blog.example.com"));
+        assert!(!same_site("example.com", "notexample.com"));
+    }
+
+    #[test]
+    fn test_page_key_ignores_scheme_www_and_fragment() {
+        assert_eq!(
+            page_key("https://www.example.com/post/#top"),
+            "example.com/post"
+        );
+        assert_eq!(page_key("http://example.com/post"), "example.com/post");
+        assert_ne!(
+            page_key("https://example.com/post?id=2"),
+            "example.com/post"
+        );
+    }
+
+    #[test]
+    fn test_parse_item_ids_accepts_ids_and_urls() {
+        let text =
+            "8863\nhttps://news.ycombinator.com/item?id=121003#c1\n\nnot an id\n8863\n item?id=42 ";
+        assert_eq!(parse_item_ids(text), vec![8863, 121003, 42]);
+        assert_eq!(parse_item_id("https://example.com/?id=5"), None);
+    }
+
     #[test]
     fn test_extract_domain_with_https() {
         assert_eq!(
@@ -85,4 +208,12 @@ mod tests {
             Some("news.ycombinator.com".to_string())
         );
     }
+
+    #[test]
+    fn test_hn_item_url() {
+        assert_eq!(
+            hn_item_url(8863),
+            "https://news.ycombinator.com/item?id=8863"
+        );
+    }
 }
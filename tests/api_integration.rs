@@ -1,3 +1,4 @@
+use tui_hn_app::api::auth::{AuthClient, ItemAction};
 use tui_hn_app::api::{ApiService, StoryListType};
 
 #[tokio::test]
@@ -19,6 +20,23 @@ async fn test_integration_fetch_top_stories() {
     assert_eq!(stories, vec![1001, 1002, 1003]);
 }
 
+#[tokio::test]
+async fn test_integration_offline_mode_skips_network() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/topstories.json")
+        .with_status(200)
+        .with_body("[1001]")
+        .expect(0)
+        .create();
+
+    let service = ApiService::with_base_url(format!("{}/", server.url())).with_offline(true);
+    let result = service.fetch_story_ids(StoryListType::Top, None).await;
+
+    assert!(result.is_err());
+    mock.assert();
+}
+
 #[tokio::test]
 async fn test_integration_fetch_story_details() {
     let mut server = mockito::Server::new_async().await;
@@ -49,3 +67,149 @@ async fn test_integration_fetch_story_details() {
     assert_eq!(story.title.unwrap(), "Integration Test Story");
     assert_eq!(story.by.unwrap(), "tester");
 }
+
+#[tokio::test]
+async fn test_integration_find_submission_of_url() {
+    let mut server = mockito::Server::new_async().await;
+    let _m = server
+        .mock("GET", "/search")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "query".into(),
+            "https://example.com/post".into(),
+        ))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"hits": [
+                {"objectID": "3001", "url": "https://example.com/post/comments", "points": 900},
+                {"objectID": "3002", "url": "https://example.com/post", "points": 120},
+                {"objectID": "3003", "url": "http://example.com/post/", "points": 8}
+            ]}"#,
+        )
+        .create();
+
+    let service = ApiService::with_base_url(format!("{}/", server.url()));
+    let id = service
+        .find_submission("https://example.com/post")
+        .await
+        .expect("Failed to search submissions");
+
+    assert_eq!(id, Some(3002));
+}
+
+#[tokio::test]
+async fn test_integration_login_and_upvote() {
+    let mut server = mockito::Server::new_async().await;
+    let _login = server
+        .mock("POST", "/login")
+        .match_body(mockito::Matcher::UrlEncoded("acct".into(), "pg".into()))
+        .with_status(302)
+        .with_header("location", "news")
+        .with_header("set-cookie", "user=pg&secret; Path=/; Secure")
+        .create();
+    let _item = server
+        .mock("GET", "/item?id=42")
+        .match_header("cookie", "user=pg&secret")
+        .with_status(200)
+        .with_body(
+            r#"<a href="logout?auth=x">logout</a>
+            <a id="up_42" href="vote?id=42&amp;how=up&amp;auth=tok&amp;goto=news">up</a>"#,
+        )
+        .create();
+    let vote = server
+        .mock("GET", "/vote")
+        .match_query(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded("id".into(), "42".into()),
+            mockito::Matcher::UrlEncoded("how".into(), "up".into()),
+            mockito::Matcher::UrlEncoded("auth".into(), "tok".into()),
+        ]))
+        .with_status(302)
+        .create();
+
+    let auth = AuthClient::with_base_url(format!("{}/", server.url()));
+    let session = auth.login("pg", "hunter2").await.expect("login failed");
+    assert_eq!(session.cookie, "pg&secret");
+
+    auth.perform(ItemAction::Upvote, 42)
+        .await
+        .expect("upvote failed");
+    vote.assert();
+}
+
+#[tokio::test]
+async fn test_integration_login_rejected() {
+    let mut server = mockito::Server::new_async().await;
+    let _login = server
+        .mock("POST", "/login")
+        .with_status(200)
+        .with_body("Bad login.")
+        .create();
+
+    let auth = AuthClient::with_base_url(format!("{}/", server.url()));
+    assert!(auth.login("pg", "wrong").await.is_err());
+    assert!(auth.session().is_none());
+}
+
+#[tokio::test]
+async fn test_integration_offline_pack_survives_a_restart() {
+    use tui_hn_app::config::OfflinePackConfig;
+    use tui_hn_app::internal::offline_pack::{self, OfflinePack};
+
+    let mut server = mockito::Server::new_async().await;
+    let base = format!("{}/", server.url());
+    let article_url = format!("{}article", base);
+    let _list = server
+        .mock("GET", "/topstories.json")
+        .with_body("[1]")
+        .create_async()
+        .await;
+    let _story = server
+        .mock("GET", "/item/1.json")
+        .with_body(format!(
+            r#"{{"id": 1, "type": "story", "title": "Packed", "url": "{}", "kids": [2]}}"#,
+            article_url
+        ))
+        .create_async()
+        .await;
+    let _comment = server
+        .mock("GET", "/item/2.json")
+        .with_body(r#"{"id": 2, "type": "comment", "parent": 1, "text": "Read me offline"}"#)
+        .create_async()
+        .await;
+    let _article = server
+        .mock("GET", "/article")
+        .with_header("content-type", "text/html")
+        .with_body(
+            "<html><body><article><p>Article body for the commute.</p></article></body></html>",
+        )
+        .create_async()
+        .await;
+
+    // The disk cache stays disabled, as it is by default
+    let dir = std::env::temp_dir().join(format!("hn-offline-pack-{}", std::process::id()));
+    let api = ApiService::with_base_url(base.clone()).with_cache_dir(dir.clone());
+    let config = OfflinePackConfig {
+        lists: vec![StoryListType::Top],
+        stories_per_list: 5,
+        comment_depth: 2,
+    };
+    let summary = offline_pack::prepare(&api, &config, 4, &mut OfflinePack::new(), |_, _| {}).await;
+    assert!(summary.failures.is_empty(), "{:?}", summary.failures);
+    assert_eq!(
+        (summary.stories, summary.articles, summary.comments),
+        (1, 1, 1)
+    );
+
+    // A fresh start with --offline reads the pack back from the cache directory
+    let offline = ApiService::with_base_url(base)
+        .with_cache_dir(dir.clone())
+        .with_offline(true);
+    let story = offline.fetch_story_content(1).await;
+    let article = offline.fetch_article_content(&article_url, None).await;
+    let comments = offline.fetch_comment_tree(vec![2], 2, None).await;
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert_eq!(story.unwrap().title.as_deref(), Some("Packed"));
+    assert!(!article.unwrap().elements.is_empty());
+    assert_eq!(comments.unwrap().len(), 1);
+}
@@ -0,0 +1,595 @@
+//! End-to-end tests driving `App` against a scripted fake HN server.
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::fake_hn::{FakeHn, comment, poll, pollopt, story};
+use tui_hn_app::api::{ApiService, StoryListType};
+use tui_hn_app::internal::error::Operation;
+use tui_hn_app::internal::history::History;
+use tui_hn_app::internal::ui::app::{Action, App, ViewMode};
+
+/// Build an app wired to the fake server, with persistence disabled. The fake
+/// server is local, so the rate limit is lifted to keep page loads in one batch
+/// and failed requests are retried without much of a wait.
+fn app_for(server: &FakeHn) -> App {
+    let mut app = App::new();
+    let network = tui_hn_app::config::NetworkConfig {
+        rate_limit_per_second: 1000.0,
+        initial_retry_delay_ms: 10,
+        ..Default::default()
+    };
+    app.api_service = Arc::new(ApiService::with_base_url(server.base_url()).with_network(network));
+    app.history = History::new(50, 1000);
+    app
+}
+
+/// Process effect actions until `done` returns true for one of them.
+async fn drive_until(app: &mut App, done: impl Fn(&Action) -> bool) -> Action {
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let action = app.next_action().await.expect("action channel closed");
+            if done(&action) {
+                return action;
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for action")
+}
+
+fn seed_stories(server: &FakeHn, count: u32) {
+    let ids: Vec<u32> = (1..=count).collect();
+    server.story_list("topstories", &ids);
+    for id in ids {
+        server.item(story(id, &format!("Story {}", id), &[]));
+    }
+}
+
+#[tokio::test]
+async fn loads_first_page_of_stories() {
+    let server = FakeHn::start().await;
+    seed_stories(&server, 3);
+    // Stories posted at the same time keep list order under the default sort, so
+    // the first row is the same however the page is split into batches
+    for id in 1..=3 {
+        let mut item = story(id, &format!("Story {}", id), &[]);
+        item["time"] = 1_700_000_000.into();
+        server.item(item);
+    }
+    let mut app = app_for(&server);
+
+    app.dispatch(Action::LoadStories(StoryListType::Top)).await;
+    assert!(app.loading);
+
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+
+    assert!(!app.loading);
+    assert_eq!(app.story_ids, vec![1, 2, 3]);
+    assert_eq!(app.stories.len(), 3);
+    assert_eq!(app.story_list_state.selected(), Some(0));
+}
+
+#[tokio::test]
+async fn first_page_streams_in_batches() {
+    let server = FakeHn::start().await;
+    seed_stories(&server, 12);
+    let mut app = app_for(&server);
+
+    app.dispatch(Action::LoadStories(StoryListType::Top)).await;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesBatchLoaded(..))).await;
+    assert!(app.loading);
+    assert!((1..=5).contains(&app.stories.len()));
+    let (done, total) = app.story_fetch_progress.expect("progress while streaming");
+    assert_eq!((done, total), (app.stories.len(), 12));
+    assert_eq!(app.story_list_state.selected(), Some(0));
+
+    // Keep the focus on the same story while later batches re-sort the list
+    app.story_list_state.select(Some(app.stories.len() - 1));
+    let focused = app.stories[app.stories.len() - 1].id;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+    assert!(!app.loading);
+    assert_eq!(app.stories.len(), 12);
+    assert_eq!(app.story_fetch_progress, None);
+    let idx = app.story_list_state.selected().unwrap();
+    assert_eq!(app.stories[idx].id, focused);
+}
+
+#[tokio::test]
+async fn custom_list_loads_imported_ids_in_order() {
+    let server = FakeHn::start().await;
+    seed_stories(&server, 5);
+    let mut app = app_for(&server);
+
+    app.dispatch(Action::LoadCustomList(vec![4, 2])).await;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+
+    assert_eq!(app.current_list_type, StoryListType::Custom);
+    assert_eq!(app.story_ids, vec![4, 2]);
+    assert_eq!(app.stories.len(), 2);
+}
+
+#[tokio::test]
+async fn paginates_with_load_more() {
+    let server = FakeHn::start().await;
+    seed_stories(&server, 25);
+    let mut app = app_for(&server);
+
+    app.dispatch(Action::LoadStories(StoryListType::Top)).await;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+    assert_eq!(app.stories.len(), 20);
+
+    app.dispatch(Action::LoadMoreStories).await;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+    assert_eq!(app.stories.len(), 25);
+    assert_eq!(app.loaded_count, 25);
+
+    // Everything is loaded now; asking again only notifies
+    app.dispatch(Action::LoadMoreStories).await;
+    assert!(!app.loading);
+    assert!(app.notifications.latest().is_some());
+}
+
+#[tokio::test]
+async fn selecting_a_story_loads_threaded_comments() {
+    let server = FakeHn::start().await;
+    server.story_list("topstories", &[1]);
+    server.item(story(1, "With comments", &[10, 20]));
+    server.item(comment(10, 1, "first", &[11]));
+    server.item(comment(11, 10, "reply", &[]));
+    server.item(comment(20, 1, "second", &[]));
+    let mut app = app_for(&server);
+
+    app.dispatch(Action::LoadStories(StoryListType::Top)).await;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+
+    app.dispatch(Action::Enter).await;
+    drive_until(&mut app, |a| matches!(a, Action::SelectStory(..))).await;
+    assert_eq!(app.view_mode, ViewMode::StoryDetail);
+    assert!(app.comments_loading);
+
+    drive_until(&mut app, |a| {
+        matches!(a, Action::CommentsChunkLoaded(.., 0))
+    })
+    .await;
+    let ids: Vec<(u32, usize)> = app
+        .comments
+        .iter()
+        .map(|r| (r.comment.id, r.depth))
+        .collect();
+    assert_eq!(ids, vec![(10, 0), (11, 1), (20, 0)]);
+    assert!(!app.comments_loading);
+    assert!(app.history.is_read(1));
+}
+
+#[tokio::test]
+async fn comment_threads_stream_in_batches() {
+    let server = FakeHn::start().await;
+    let roots: Vec<u32> = (10..20).collect();
+    server.story_list("topstories", &[1]);
+    server.item(story(1, "Busy thread", &roots));
+    for &id in &roots {
+        server.item(comment(id, 1, "reply", &[]));
+    }
+    let mut app = app_for(&server);
+
+    app.dispatch(Action::LoadStories(StoryListType::Top)).await;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+    app.dispatch(Action::Enter).await;
+
+    // The first batch renders while the rest are still loading
+    drive_until(&mut app, |a| matches!(a, Action::CommentsChunkLoaded(..))).await;
+    assert_eq!(app.comments.len(), 4);
+    assert_eq!(app.comment_roots_remaining, 6);
+    assert!(app.comments_loading);
+
+    drive_until(&mut app, |a| {
+        matches!(a, Action::CommentsChunkLoaded(.., 0))
+    })
+    .await;
+    let ids: Vec<u32> = app.comments.iter().map(|r| r.comment.id).collect();
+    assert_eq!(ids, roots);
+    assert!(!app.comments_loading);
+
+    // Late batches for a story that was left are ignored
+    let fetch = app.comment_fetch;
+    app.dispatch(Action::CommentsChunkLoaded(
+        2,
+        fetch,
+        app.comments.clone(),
+        0,
+    ))
+    .await;
+    assert_eq!(app.comments.len(), 10);
+}
+
+#[tokio::test]
+async fn reopening_a_story_mid_stream_does_not_duplicate_comments() {
+    let server = FakeHn::start().await;
+    let roots: Vec<u32> = (10..20).collect();
+    server.story_list("topstories", &[1]);
+    server.item(story(1, "Busy thread", &roots));
+    for &id in &roots {
+        server.item(comment(id, 1, "reply", &[]));
+    }
+    server.delay("item/15.json", Duration::from_millis(300));
+    let mut app = app_for(&server);
+
+    app.dispatch(Action::LoadStories(StoryListType::Top)).await;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+    app.dispatch(Action::Enter).await;
+    let opened = match drive_until(&mut app, |a| matches!(a, Action::SelectStory(..))).await {
+        Action::SelectStory(story, _) => story,
+        _ => unreachable!(),
+    };
+    drive_until(&mut app, |a| matches!(a, Action::CommentsChunkLoaded(..))).await;
+
+    // Reopen while the rest of the first stream is still on its way
+    app.dispatch(Action::SelectStory(opened, StoryListType::Top))
+        .await;
+    let fetch = app.comment_fetch;
+    drive_until(
+        &mut app,
+        |a| matches!(a, Action::CommentsChunkLoaded(_, f, _, 0) if *f == fetch),
+    )
+    .await;
+    let ids: Vec<u32> = app.comments.iter().map(|r| r.comment.id).collect();
+    assert_eq!(ids, roots);
+    assert!(!app.comments_loading);
+}
+
+#[tokio::test]
+async fn export_fetches_replies_below_the_loaded_depth() {
+    let server = FakeHn::start().await;
+    server.story_list("topstories", &[1]);
+    server.item(story(1, "Deep thread", &[10]));
+    for id in 10..15 {
+        let kids = match id {
+            14 => vec![],
+            _ => vec![id + 1],
+        };
+        server.item(comment(id, id - 1, &format!("level {}", id - 10), &kids));
+    }
+    let mut app = app_for(&server);
+    let dir = std::env::temp_dir().join(format!("hn-story-export-{}", std::process::id()));
+    app.config.export.directory = dir.to_string_lossy().into_owned();
+
+    app.dispatch(Action::LoadStories(StoryListType::Top)).await;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+    app.dispatch(Action::Enter).await;
+    drive_until(&mut app, |a| {
+        matches!(a, Action::CommentsChunkLoaded(.., 0))
+    })
+    .await;
+    assert_eq!(app.comments.len(), 4, "the view stops three levels down");
+
+    app.dispatch(Action::ExportStory).await;
+    let path = match drive_until(&mut app, |a| matches!(a, Action::ItemActionDone(_))).await {
+        Action::ItemActionDone(Ok(message)) => {
+            message.trim_start_matches("Exported to ").to_string()
+        }
+        other => panic!("export failed: {:?}", other),
+    };
+    let exported = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(exported.contains("level 4"), "{}", exported);
+}
+
+#[tokio::test]
+async fn loads_article_for_selected_story() {
+    let server = FakeHn::start().await;
+    server.page(
+        "articles/1",
+        "<html><head><title>Fixture Article Headline Here</title></head>\
+         <body><article><p>Article body text.</p></article></body></html>",
+    );
+    let mut fixture = story(1, "Has article", &[]);
+    fixture["url"] = server.url("articles/1").into();
+    server.story_list("topstories", &[1]);
+    server.item(fixture);
+    let mut app = app_for(&server);
+
+    app.dispatch(Action::LoadStories(StoryListType::Top)).await;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+    app.dispatch(Action::Enter).await;
+    drive_until(&mut app, |a| matches!(a, Action::SelectStory(..))).await;
+    assert!(app.article_loading);
+
+    drive_until(&mut app, |a| matches!(a, Action::ArticleLoaded(..))).await;
+    let article = app.article_content.as_ref().expect("article loaded");
+    assert_eq!(article.title, "Fixture Article Headline Here");
+    assert_eq!(article.elements.len(), 1);
+}
+
+#[tokio::test]
+async fn slow_items_keep_loading_state_until_they_arrive() {
+    let server = FakeHn::start().await;
+    seed_stories(&server, 2);
+    server.delay("item/2.json", Duration::from_millis(300));
+    let mut app = app_for(&server);
+
+    app.dispatch(Action::LoadStories(StoryListType::Top)).await;
+    drive_until(&mut app, |a| matches!(a, Action::StoryIdsLoaded(_))).await;
+    assert!(app.loading, "still loading while an item is delayed");
+
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+    assert_eq!(app.stories.len(), 2);
+}
+
+#[tokio::test]
+async fn opening_a_story_mid_stream_lets_load_more_resume() {
+    let server = FakeHn::start().await;
+    seed_stories(&server, 12);
+    server.delay("item/9.json", Duration::from_millis(300));
+    let mut app = app_for(&server);
+
+    app.dispatch(Action::LoadStories(StoryListType::Top)).await;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesBatchLoaded(..))).await;
+    app.dispatch(Action::Enter).await;
+    drive_until(&mut app, |a| matches!(a, Action::SelectStory(..))).await;
+    assert!(!app.loading);
+    assert_eq!(app.story_fetch_progress, None);
+    let shown = app.stories.len();
+    assert!(shown < 12);
+
+    // m fetches the stories the cancelled page never delivered
+    app.dispatch(Action::Back).await;
+    app.dispatch(Action::LoadMoreStories).await;
+    assert!(app.loading);
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+    assert!(!app.loading);
+    assert_eq!(app.stories.len(), 12);
+}
+
+#[tokio::test]
+async fn failing_list_endpoint_surfaces_error() {
+    let server = FakeHn::start().await;
+    server.fail("topstories.json", 500);
+    let mut app = app_for(&server);
+
+    app.dispatch(Action::LoadStories(StoryListType::Top)).await;
+    drive_until(&mut app, |a| matches!(a, Action::Error(_))).await;
+
+    assert!(!app.loading);
+    assert!(app.stories.is_empty());
+    let error = app.last_error.clone().expect("error panel");
+    assert_eq!(error.operation, Operation::LoadStories(StoryListType::Top));
+    assert!(error.retryable, "{:?}", error);
+
+    // Retrying after the server recovers loads the list and closes the panel
+    seed_stories(&server, 2);
+    app.dispatch(Action::RetryFailed).await;
+    assert!(app.last_error.is_none());
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+    assert_eq!(app.stories.len(), 2);
+}
+
+#[tokio::test]
+async fn opening_a_poll_loads_its_options() {
+    let server = FakeHn::start().await;
+    server.story_list("topstories", &[1]);
+    server.item(poll(1, "Tabs or spaces?", &[2, 3, 4]));
+    server.item(pollopt(2, 1, "Tabs", 40));
+    server.item(pollopt(3, 1, "Spaces", 60));
+    server.fail("item/4.json", 500);
+    let mut app = app_for(&server);
+
+    app.dispatch(Action::LoadStories(StoryListType::Top)).await;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+    assert!(app.stories[0].is_poll());
+
+    app.dispatch(Action::Enter).await;
+    drive_until(&mut app, |a| matches!(a, Action::PollLoaded(_))).await;
+
+    // The option that failed to load is left out
+    let poll = app.poll.clone().expect("poll options");
+    let options: Vec<_> = poll
+        .options
+        .iter()
+        .map(|opt| (opt.text.as_deref().unwrap_or(""), opt.score))
+        .collect();
+    assert_eq!(options, vec![("Tabs", Some(40)), ("Spaces", Some(60))]);
+    assert_eq!(poll.total_votes(), 100);
+}
+
+#[tokio::test]
+async fn live_updates_refresh_changed_visible_stories() {
+    let server = FakeHn::start().await;
+    seed_stories(&server, 3);
+    let mut app = app_for(&server);
+    app.list_viewport_rows = 10;
+
+    app.dispatch(Action::LoadStories(StoryListType::Top)).await;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+
+    // Story 2 gained points; story 3 changed on HN but not in a way that shows
+    let mut bumped = story(2, "Story 2", &[]);
+    bumped["score"] = 500.into();
+    server.item(bumped);
+    server.updates(&[2, 3, 99]);
+
+    app.dispatch(Action::CheckUpdates).await;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesRefreshed(_))).await;
+
+    let score = |app: &App, id| {
+        app.stories
+            .iter()
+            .find(|s| s.id == id)
+            .and_then(|s| s.score)
+    };
+    assert_eq!(score(&app, 2), Some(500));
+    assert!(app.recently_updated(2));
+    assert!(!app.recently_updated(3));
+    // Only the changed rows were fetched again
+    assert_eq!(server.hits("item/1.json"), 1);
+    assert_eq!(server.hits("item/3.json"), 2);
+}
+
+#[tokio::test]
+async fn failing_items_are_skipped() {
+    let server = FakeHn::start().await;
+    seed_stories(&server, 3);
+    server.fail("item/2.json", 500);
+    let mut app = app_for(&server);
+
+    app.dispatch(Action::LoadStories(StoryListType::Top)).await;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+
+    let mut ids: Vec<u32> = app.stories.iter().map(|s| s.id).collect();
+    ids.sort();
+    assert_eq!(ids, vec![1, 3]);
+    // The first try and three retries
+    assert_eq!(server.hits("item/2.json"), 4);
+}
+
+#[tokio::test]
+async fn split_layout_routes_navigation_to_focused_pane() {
+    let server = FakeHn::start().await;
+    seed_stories(&server, 3);
+    let mut app = app_for(&server);
+    app.config.ui.split_layout = true;
+
+    app.dispatch(Action::LoadStories(StoryListType::Top)).await;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+    // The page may arrive in two batches, with the focus following the first story
+    app.story_list_state.select(Some(0));
+    app.dispatch(Action::Enter).await;
+    drive_until(&mut app, |a| matches!(a, Action::SelectStory(..))).await;
+    assert!(app.split_active());
+    assert!(!app.list_has_focus());
+
+    // Detail pane focused: j scrolls comments
+    app.dispatch(Action::NavigateDown).await;
+    assert_eq!(app.comments_scroll.offset(), 1);
+    assert_eq!(app.story_list_state.selected(), Some(0));
+
+    // List pane focused: j moves the list selection, story stays open
+    app.dispatch(Action::FocusNextPane).await;
+    assert!(app.list_has_focus());
+    app.dispatch(Action::NavigateDown).await;
+    assert_eq!(app.story_list_state.selected(), Some(1));
+    assert_eq!(app.comments_scroll.offset(), 1);
+    assert_eq!(app.view_mode, ViewMode::StoryDetail);
+
+    // Enter from the list opens the newly selected story on the right
+    app.dispatch(Action::Enter).await;
+    match drive_until(&mut app, |a| matches!(a, Action::SelectStory(..))).await {
+        Action::SelectStory(story, _) => assert_eq!(story.id, 2),
+        _ => unreachable!(),
+    }
+}
+
+#[tokio::test]
+async fn story_actions_follow_the_list_pane_when_it_has_focus() {
+    let server = FakeHn::start().await;
+    seed_stories(&server, 3);
+    let mut app = app_for(&server);
+    app.bookmarks = tui_hn_app::internal::bookmarks::Bookmarks::new();
+    app.config.ui.split_layout = true;
+
+    app.dispatch(Action::LoadStories(StoryListType::Top)).await;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+    app.story_list_state.select(Some(0));
+    app.dispatch(Action::Enter).await;
+    drive_until(&mut app, |a| matches!(a, Action::SelectStory(..))).await;
+    app.dispatch(Action::FocusNextPane).await;
+    app.dispatch(Action::NavigateDown).await;
+    assert!(app.list_has_focus());
+    assert_eq!(app.selected_story.as_ref().map(|s| s.id), Some(3));
+
+    // b bookmarks the highlighted row, not the story open on the right
+    app.dispatch(Action::ToggleBookmark).await;
+    assert!(app.bookmarks.contains(2));
+    assert!(!app.bookmarks.contains(3));
+
+    // y finds the highlighted row too (copying may fail without a clipboard)
+    app.dispatch(Action::CopyLink).await;
+    let message = &app.notifications.latest().unwrap().message;
+    assert_ne!(message, "No story selected");
+    assert!(!message.starts_with("Copied") || message.ends_with("id=2"));
+}
+
+#[tokio::test]
+async fn fold_state_is_restored_when_story_is_reopened() {
+    let server = FakeHn::start().await;
+    server.story_list("topstories", &[1]);
+    server.item(story(1, "Folded", &[10, 20]));
+    server.item(comment(10, 1, "first", &[11]));
+    server.item(comment(11, 10, "reply", &[]));
+    server.item(comment(20, 1, "second", &[21]));
+    server.item(comment(21, 20, "reply", &[]));
+    let mut app = app_for(&server);
+
+    app.dispatch(Action::LoadStories(StoryListType::Top)).await;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+    app.dispatch(Action::Enter).await;
+    drive_until(&mut app, |a| {
+        matches!(a, Action::CommentsChunkLoaded(.., 0))
+    })
+    .await;
+
+    app.dispatch(Action::ToggleCommentCollapse(2)).await;
+    assert_eq!(
+        app.history.folds_for(1).cloned(),
+        Some([20].into_iter().collect())
+    );
+
+    app.dispatch(Action::Back).await;
+    app.dispatch(Action::Enter).await;
+    drive_until(&mut app, |a| {
+        matches!(a, Action::CommentsChunkLoaded(.., 0))
+    })
+    .await;
+    let expanded: Vec<(u32, bool)> = app
+        .comments
+        .iter()
+        .map(|r| (r.comment.id, r.expanded))
+        .collect();
+    assert_eq!(
+        expanded,
+        vec![(10, true), (11, true), (20, false), (21, true)]
+    );
+
+    app.dispatch(Action::ExpandAllComments).await;
+    assert_eq!(app.history.folds_for(1), None);
+}
+
+#[tokio::test]
+async fn plugin_output_opens_as_custom_list() {
+    use tui_hn_app::internal::models::Story;
+    use tui_hn_app::internal::plugins::PluginOutput;
+
+    let server = FakeHn::start().await;
+    server.item(story(5, "Fetched from HN", &[]));
+    let mut app = app_for(&server);
+
+    // Complete stories are shown without touching the API
+    let printed = vec![Story {
+        id: 42,
+        title: Some("From a plugin".to_string()),
+        ..Default::default()
+    }];
+    app.dispatch(Action::PluginFinished(
+        "src".to_string(),
+        PluginOutput::Stories(printed),
+    ))
+    .await;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+    assert_eq!(app.current_list_type, StoryListType::Custom);
+    assert_eq!(app.stories[0].title.as_deref(), Some("From a plugin"));
+
+    // Bare ids are looked up like an imported list
+    let ids = vec![Story {
+        id: 5,
+        ..Default::default()
+    }];
+    app.dispatch(Action::PluginFinished(
+        "src".to_string(),
+        PluginOutput::Stories(ids),
+    ))
+    .await;
+    drive_until(&mut app, |a| matches!(a, Action::StoriesLoaded(_))).await;
+    assert_eq!(app.stories[0].title.as_deref(), Some("Fetched from HN"));
+}
@@ -0,0 +1,262 @@
+//! A scripted fake of the Hacker News Firebase API.
+//!
+//! Routes are mockito mocks registered per path with a status, body and
+//! optional latency, so tests can inject slow or failing endpoints while the app
+//! under test talks to a real socket. Paths without a route answer 404 `null`,
+//! like Firebase does for a missing item.
+
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use mockito::{Matcher, Mock, ServerGuard};
+use serde_json::{Value, json};
+
+#[derive(Clone)]
+struct Route {
+    status: usize,
+    content_type: &'static str,
+    body: String,
+    delay: Duration,
+}
+
+impl Default for Route {
+    fn default() -> Self {
+        Self {
+            status: 404,
+            content_type: "application/json",
+            body: "null".to_string(),
+            delay: Duration::ZERO,
+        }
+    }
+}
+
+type Hits = Arc<Mutex<HashMap<String, usize>>>;
+
+pub struct FakeHn {
+    server: Mutex<ServerGuard>,
+    /// The mock serving each path, replaced whenever the route changes
+    routes: Mutex<HashMap<String, (Route, Mock)>>,
+    hits: Hits,
+    _not_found: Mock,
+}
+
+impl FakeHn {
+    /// Start a mock server answering 404 until routes are added.
+    pub async fn start() -> Self {
+        let mut server = mockito::Server::new_async().await;
+        let hits = Hits::default();
+        let counted = hits.clone();
+        // Every mock accepts any number of hits, so mockito serves the one created
+        // last and routes take precedence over this catch-all.
+        let not_found = server
+            .mock("GET", Matcher::Any)
+            .expect_at_least(0)
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body_from_request(move |request| {
+                count(&counted, request.path());
+                b"null".to_vec()
+            })
+            .create_async()
+            .await;
+
+        Self {
+            server: Mutex::new(server),
+            routes: Mutex::default(),
+            hits,
+            _not_found: not_found,
+        }
+    }
+
+    /// Base URL to hand to `ApiService::with_base_url`.
+    pub fn base_url(&self) -> String {
+        format!("{}/", self.server.lock().unwrap().url())
+    }
+
+    /// Absolute URL for an arbitrary path on the fake server (e.g. articles).
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url(), path.trim_start_matches('/'))
+    }
+
+    /// Serve `route` at `path`, replacing the mock that served it before.
+    fn route(&self, path: &str, route: Route) {
+        let hits = self.hits.clone();
+        let counted = path.to_string();
+        let Route { body, delay, .. } = route.clone();
+        let mock = self
+            .server
+            .lock()
+            .unwrap()
+            .mock("GET", format!("/{}", path).as_str())
+            .expect_at_least(0)
+            .with_status(route.status)
+            .with_header("content-type", route.content_type);
+        let mock = match delay.is_zero() {
+            true => mock.with_body_from_request(move |_| {
+                count(&hits, &counted);
+                body.clone().into_bytes()
+            }),
+            // A delayed body is written on its own thread, so it holds up only this response
+            false => mock.with_chunked_body(move |writer| {
+                count(&hits, &counted);
+                std::thread::sleep(delay);
+                writer.write_all(body.as_bytes())
+            }),
+        }
+        .create();
+
+        let replaced = self
+            .routes
+            .lock()
+            .unwrap()
+            .insert(path.to_string(), (route, mock));
+        if let Some((_, old)) = replaced {
+            old.remove();
+        }
+    }
+
+    fn set(&self, path: &str, status: usize, content_type: &'static str, body: String) {
+        let delay = self.delay_of(path);
+        self.route(
+            path,
+            Route {
+                status,
+                content_type,
+                body,
+                delay,
+            },
+        );
+    }
+
+    fn delay_of(&self, path: &str) -> Duration {
+        self.routes
+            .lock()
+            .unwrap()
+            .get(path)
+            .map(|(route, _)| route.delay)
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Serve `ids` for a list endpoint such as `topstories`.
+    pub fn story_list(&self, list: &str, ids: &[u32]) {
+        self.set(
+            &format!("{}.json", list),
+            200,
+            "application/json",
+            json!(ids).to_string(),
+        );
+    }
+
+    /// Serve an item fixture at `item/<id>.json`; the id is read from the JSON.
+    pub fn item(&self, item: Value) {
+        let id = item["id"].as_u64().expect("fixture needs an id");
+        self.set(
+            &format!("item/{}.json", id),
+            200,
+            "application/json",
+            item.to_string(),
+        );
+    }
+
+    /// Serve an HTML page (used for article fetches).
+    pub fn page(&self, path: &str, html: &str) {
+        self.set(
+            path.trim_start_matches('/'),
+            200,
+            "text/html",
+            html.to_string(),
+        );
+    }
+
+    /// Serve `items` as the recently changed ids at `updates.json`.
+    pub fn updates(&self, items: &[u32]) {
+        self.set(
+            "updates.json",
+            200,
+            "application/json",
+            json!({ "items": items, "profiles": [] }).to_string(),
+        );
+    }
+
+    /// Make `path` fail with the given status and a non-JSON body.
+    pub fn fail(&self, path: &str, status: usize) {
+        self.set(path, status, "text/plain", "internal error".to_string());
+    }
+
+    /// Add latency to every response served for `path`.
+    pub fn delay(&self, path: &str, delay: Duration) {
+        let route = self
+            .routes
+            .lock()
+            .unwrap()
+            .get(path)
+            .map(|(route, _)| route.clone())
+            .unwrap_or_default();
+        self.route(path, Route { delay, ..route });
+    }
+
+    /// Number of requests received for `path`.
+    pub fn hits(&self, path: &str) -> usize {
+        self.hits.lock().unwrap().get(path).copied().unwrap_or(0)
+    }
+}
+
+fn count(hits: &Hits, path: &str) {
+    let path = path.trim_start_matches('/').to_string();
+    *hits.lock().unwrap().entry(path).or_default() += 1;
+}
+
+/// Story fixture with sensible defaults.
+pub fn story(id: u32, title: &str, kids: &[u32]) -> Value {
+    json!({
+        "id": id,
+        "type": "story",
+        "title": title,
+        "by": "fixture",
+        "score": 100 + id,
+        "time": 1_700_000_000 + id as i64,
+        "descendants": kids.len(),
+        "kids": kids,
+    })
+}
+
+/// Comment fixture.
+pub fn comment(id: u32, parent: u32, text: &str, kids: &[u32]) -> Value {
+    json!({
+        "id": id,
+        "type": "comment",
+        "by": format!("user{}", id),
+        "parent": parent,
+        "text": text,
+        "time": 1_700_000_100 + id as i64,
+        "kids": kids,
+    })
+}
+
+/// Poll fixture whose options are the items in `parts`.
+pub fn poll(id: u32, title: &str, parts: &[u32]) -> Value {
+    json!({
+        "id": id,
+        "type": "poll",
+        "title": title,
+        "by": "fixture",
+        "score": 100 + id,
+        "time": 1_700_000_000 + id as i64,
+        "descendants": 0,
+        "parts": parts,
+    })
+}
+
+/// Poll option fixture.
+pub fn pollopt(id: u32, poll: u32, text: &str, score: u32) -> Value {
+    json!({
+        "id": id,
+        "type": "pollopt",
+        "poll": poll,
+        "text": text,
+        "score": score,
+    })
+}
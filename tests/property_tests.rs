@@ -27,3 +27,180 @@ proptest! {
         let _ = ron::from_str::<AppConfig>(&s);
     }
 }
+
+mod invariants {
+    use std::collections::HashMap;
+
+    use proptest::prelude::*;
+    use tui_hn_app::internal::comment_tree::flatten_comment_tree;
+    use tui_hn_app::internal::models::{Comment, Story};
+    use tui_hn_app::internal::search::{SearchMode, SearchQuery, SearchType, filter_stories};
+    use tui_hn_app::internal::ui::sort::{SortBy, SortOrder, sort_stories};
+
+    fn arb_stories() -> impl Strategy<Value = Vec<Story>> {
+        prop::collection::vec(
+            (
+                prop::option::of(0u32..5),
+                prop::option::of(0u32..5),
+                prop::option::of(0i64..5),
+                "[a-c ]{0,6}",
+            ),
+            0..40,
+        )
+        .prop_map(|rows| {
+            rows.into_iter()
+                .enumerate()
+                .map(|(i, (score, descendants, time, title))| Story {
+                    id: i as u32,
+                    title: Some(title),
+                    score,
+                    descendants,
+                    time,
+                    ..Default::default()
+                })
+                .collect()
+        })
+    }
+
+    fn arb_sort() -> impl Strategy<Value = (SortBy, SortOrder)> {
+        (
+            prop_oneof![
+                Just(SortBy::Score),
+                Just(SortBy::Comments),
+                Just(SortBy::Time)
+            ],
+            prop_oneof![Just(SortOrder::Ascending), Just(SortOrder::Descending)],
+        )
+    }
+
+    fn key(story: &Story, sort_by: SortBy) -> Option<i64> {
+        match sort_by {
+            SortBy::Score => story.score.map(i64::from),
+            SortBy::Comments => story.descendants.map(i64::from),
+            SortBy::Time => story.time,
+            // Rank scores are floats that depend on the clock; arb_sort leaves them out
+            SortBy::Rank => unreachable!("not generated"),
+        }
+    }
+
+    /// Random forest: comment `i` (1-based) is a root or a child of an earlier comment.
+    fn arb_forest() -> impl Strategy<Value = (Vec<u32>, HashMap<u32, Comment>)> {
+        prop::collection::vec(prop::option::of(any::<prop::sample::Index>()), 1..60).prop_map(
+            |parents| {
+                let mut kids: HashMap<u32, Vec<u32>> = HashMap::new();
+                let mut roots = Vec::new();
+                for (i, parent) in parents.iter().enumerate() {
+                    let id = i as u32 + 1;
+                    match (i, parent) {
+                        (0, _) | (_, None) => roots.push(id),
+                        (_, Some(idx)) => kids.entry(idx.index(i) as u32 + 1).or_default().push(id),
+                    }
+                }
+                let comments = (1..=parents.len() as u32)
+                    .map(|id| {
+                        let comment = Comment {
+                            id,
+                            by: None,
+                            text: None,
+                            time: None,
+                            kids: kids.get(&id).cloned(),
+                            deleted: false,
+                            dead: false,
+                        };
+                        (id, comment)
+                    })
+                    .collect();
+                (roots, comments)
+            },
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn sort_is_ordered_stable_and_a_permutation(mut stories in arb_stories(), (sort_by, order) in arb_sort()) {
+            let mut before: Vec<u32> = stories.iter().map(|s| s.id).collect();
+            sort_stories(&mut stories, sort_by, order);
+
+            for pair in stories.windows(2) {
+                let (a, b) = (key(&pair[0], sort_by), key(&pair[1], sort_by));
+                match order {
+                    SortOrder::Ascending => prop_assert!(a <= b),
+                    SortOrder::Descending => prop_assert!(a >= b),
+                }
+                // Stability: ids were assigned in input order
+                if a == b {
+                    prop_assert!(pair[0].id < pair[1].id);
+                }
+            }
+
+            let mut after: Vec<u32> = stories.iter().map(|s| s.id).collect();
+            before.sort();
+            after.sort();
+            prop_assert_eq!(before, after);
+        }
+
+        #[test]
+        fn filtered_indices_point_at_the_same_stories(
+            stories in arb_stories(),
+            query in "[a-c(\\[]{0,3}",
+            regex in any::<bool>(),
+            hidden_mod in 1u32..5,
+        ) {
+            let search_type = match regex {
+                true => SearchType::Regex,
+                false => SearchType::Literal,
+            };
+            let query = SearchQuery::new(query, SearchMode::Title, search_type);
+            let filtered = filter_stories(&stories, &query, |s| s.id % hidden_mod == 0 && hidden_mod > 1, |_| false);
+
+            let mut last = None;
+            for (idx, story) in &filtered {
+                prop_assert!(*idx < stories.len());
+                prop_assert_eq!(stories[*idx].id, story.id);
+                prop_assert!(last.is_none_or(|l| l < *idx));
+                last = Some(*idx);
+            }
+            if query.is_empty() && hidden_mod == 1 {
+                prop_assert_eq!(filtered.len(), stories.len());
+            }
+        }
+
+        #[test]
+        fn flattened_tree_is_dfs_with_parents_first((roots, comments) in arb_forest(), max_depth in 0usize..6) {
+            let rows = flatten_comment_tree(&roots, &comments, max_depth);
+
+            let mut position = HashMap::new();
+            for (i, row) in rows.iter().enumerate() {
+                prop_assert!(position.insert(row.comment.id, i).is_none(), "duplicate row");
+                prop_assert!(row.depth <= max_depth);
+                match row.parent_id {
+                    None => prop_assert_eq!(row.depth, 0),
+                    Some(parent) => {
+                        let p = position.get(&parent).copied();
+                        prop_assert!(p.is_some(), "parent must precede child");
+                        prop_assert_eq!(rows[p.unwrap()].depth + 1, row.depth);
+                    }
+                }
+                // Pre-order: depth can only grow by one step at a time
+                if i > 0 {
+                    prop_assert!(row.depth <= rows[i - 1].depth + 1);
+                }
+            }
+
+            // Every comment within the depth limit is emitted
+            let reachable = comments
+                .values()
+                .filter(|c| {
+                    let mut depth = 0;
+                    let mut id = c.id;
+                    while let Some(parent) = comments.values().find(|p| p.kids.as_ref().is_some_and(|k| k.contains(&id))) {
+                        depth += 1;
+                        id = parent.id;
+                    }
+                    depth <= max_depth
+                })
+                .count();
+            prop_assert_eq!(rows.len(), reachable);
+        }
+    }
+}
@@ -1,4 +1,6 @@
 use ratatui::{Terminal, backend::TestBackend};
+use std::time::Duration;
+use tui_hn_app::internal::metrics::{INPUT_LATENCY, MetricsRegistry};
 use tui_hn_app::internal::ui::log_viewer::LogViewer;
 
 #[test]
@@ -14,10 +16,132 @@ fn test_log_viewer_render() {
     terminal
         .draw(|f| {
             let area = f.area();
-            log_viewer.render(f, area);
+            log_viewer.render(f, area, &MetricsRegistry::new());
         })
         .unwrap();
 
     let buffer = terminal.backend().buffer();
     insta::assert_debug_snapshot!(buffer);
 }
+
+#[test]
+fn test_log_viewer_metrics_tab_shows_input_latency() {
+    let backend = TestBackend::new(80, 24);
+    let mut terminal = Terminal::new(backend).unwrap();
+
+    let mut log_viewer = LogViewer::new("logs".to_string());
+    log_viewer.visible = true;
+    log_viewer.active_tab = 1;
+
+    let mut metrics = MetricsRegistry::new();
+    for ms in [2, 4, 6, 40] {
+        metrics.record(INPUT_LATENCY, Duration::from_millis(ms));
+    }
+
+    terminal
+        .draw(|f| {
+            let area = f.area();
+            log_viewer.render(f, area, &metrics);
+        })
+        .unwrap();
+
+    let buffer = terminal.backend().buffer();
+    let text: String = buffer.content().iter().map(|c| c.symbol()).collect();
+    assert!(text.contains("Input latency: p50 4.0ms · p95 40.0ms (4 samples)"));
+}
+
+#[test]
+fn test_sort_header_hits_match_rendered_columns() {
+    use tui_hn_app::internal::ui::app::App;
+    use tui_hn_app::internal::ui::sort::SortBy;
+
+    let backend = TestBackend::new(100, 24);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = App::new();
+    app.config.ui.list_view.sort_header = true;
+
+    terminal.draw(|f| app.ui(f)).unwrap();
+
+    let buffer = terminal.backend().buffer();
+    let columns: Vec<SortBy> = app.sort_header_hits.iter().map(|(_, c)| *c).collect();
+    assert_eq!(
+        columns,
+        vec![SortBy::Score, SortBy::Comments, SortBy::Time, SortBy::Rank]
+    );
+    for ((area, _), label) in
+        app.sort_header_hits
+            .iter()
+            .zip([" Score ", " Comments ", " Time ▼ ", " Rank "])
+    {
+        let text: String = (area.left()..area.right())
+            .map(|x| buffer[(x, area.y)].symbol())
+            .collect();
+        assert_eq!(text, label);
+    }
+}
+
+#[test]
+fn test_message_line_keeps_notifications_off_the_status_bar() {
+    use tui_hn_app::internal::notification::Notification;
+    use tui_hn_app::internal::ui::app::App;
+
+    let backend = TestBackend::new(100, 24);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = App::new();
+    app.config.ui.message_line_height = 1;
+    app.notifications
+        .push(Notification::info("Saved theme to nord"));
+
+    terminal.draw(|f| app.ui(f)).unwrap();
+
+    let buffer = terminal.backend().buffer();
+    let row = |y: u16| -> String { (0..100).map(|x| buffer[(x, y)].symbol()).collect() };
+    assert_eq!(row(22).trim(), "Saved theme to nord");
+    assert!(row(23).contains("List"));
+    // No popup in the middle of the screen
+    assert!(!(0..22).any(|y| row(y).contains("Saved theme")));
+}
+
+#[test]
+fn test_status_bar_format_lays_out_sections() {
+    use tui_hn_app::internal::ui::app::App;
+    use tui_hn_app::internal::ui::status_template::StatusTemplate;
+
+    let backend = TestBackend::new(100, 24);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = App::new();
+    app.status_template =
+        Some(StatusTemplate::parse("{mode}{?search: /{search}}{|}{category}").unwrap());
+
+    terminal.draw(|f| app.ui(f)).unwrap();
+
+    let buffer = terminal.backend().buffer();
+    let row: String = (0..100).map(|x| buffer[(x, 23)].symbol()).collect();
+    // The right section ends at the padding, the empty search leaves nothing
+    assert!(row.starts_with(" List "), "{:?}", row);
+    assert!(row.ends_with("Top "), "{:?}", row);
+    assert!(!row.contains('/'));
+}
+
+#[test]
+fn test_category_bar_shows_counts_under_the_top_bar() {
+    use tui_hn_app::api::StoryListType;
+    use tui_hn_app::internal::ui::app::App;
+
+    let backend = TestBackend::new(120, 24);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = App::new();
+    app.config.ui.category_bar = true;
+    app.list_refreshed.insert(
+        StoryListType::Top,
+        (500, jiff::Timestamp::now().as_second()),
+    );
+
+    terminal.draw(|f| app.ui(f)).unwrap();
+
+    let buffer = terminal.backend().buffer();
+    let row: String = (0..120).map(|x| buffer[(x, 1)].symbol()).collect();
+    assert!(row.contains("1 Top 500 · just now"));
+    assert!(row.contains("2 New"));
+    assert!(row.contains("6 Job"));
+}